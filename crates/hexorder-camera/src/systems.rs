@@ -70,7 +70,7 @@ pub fn configure_bounds_from_grid(
 
     // Set pan bounds.
     let hex_scale = config.layout.scale.x.max(config.layout.scale.y);
-    let grid_extent = config.map_radius as f32 * hex_scale * 2.0;
+    let grid_extent = config.extent() as f32 * hex_scale * 2.0;
     camera_state.pan_bounds = grid_extent + hex_scale * 4.0;
 
     // Compute fit scale using actual window dimensions if available,
@@ -243,22 +243,13 @@ fn fit_scale(
     margins: &ViewportMargins,
 ) -> f32 {
     let layout = &grid_config.layout;
-    let r = grid_config.map_radius as i32;
     let hex_size = layout.scale.x.max(layout.scale.y);
 
-    // Compute actual world-space extent by checking the 6 boundary hexes.
+    // Compute actual world-space extent from the board's tile positions.
     let mut max_x: f32 = 0.0;
     let mut max_y: f32 = 0.0;
-    let extremes = [
-        hexx::Hex::new(r, 0),
-        hexx::Hex::new(-r, 0),
-        hexx::Hex::new(0, r),
-        hexx::Hex::new(0, -r),
-        hexx::Hex::new(r, -r),
-        hexx::Hex::new(-r, r),
-    ];
-    for hex in &extremes {
-        let pos = layout.hex_to_world_pos(*hex);
+    for pos in grid_config.positions() {
+        let pos = layout.hex_to_world_pos(pos.to_hex());
         max_x = max_x.max(pos.x.abs());
         max_y = max_y.max(pos.y.abs());
     }
@@ -159,7 +159,7 @@ fn configure_bounds_uses_defaults_without_grid() {
 
 #[test]
 fn configure_bounds_adjusts_with_grid_config() {
-    use hexorder_contracts::hex_grid::HexGridConfig;
+    use hexorder_contracts::hex_grid::{GridShape, HexGridConfig};
 
     let mut app = test_app();
     app.insert_resource(HexGridConfig {
@@ -169,6 +169,7 @@ fn configure_bounds_adjusts_with_grid_config() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 10,
+        shape: GridShape::Hexagon,
     });
     app.add_systems(
         Startup,
@@ -978,7 +979,7 @@ fn apply_pending_reset_fires_when_margins_set() {
 
 #[test]
 fn apply_pending_reset_recomputes_scale_with_grid_config() {
-    use hexorder_contracts::hex_grid::HexGridConfig;
+    use hexorder_contracts::hex_grid::{GridShape, HexGridConfig};
 
     let mut app = test_app();
     app.insert_resource(HexGridConfig {
@@ -988,6 +989,7 @@ fn apply_pending_reset_recomputes_scale_with_grid_config() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 10,
+        shape: GridShape::Hexagon,
     });
     app.add_systems(Startup, systems::spawn_camera);
     app.add_systems(Update, systems::apply_pending_reset);
@@ -1020,7 +1022,7 @@ fn apply_pending_reset_recomputes_scale_with_grid_config() {
 
 #[test]
 fn camera_fit_command_with_grid_config() {
-    use hexorder_contracts::hex_grid::HexGridConfig;
+    use hexorder_contracts::hex_grid::{GridShape, HexGridConfig};
     use hexorder_contracts::shortcuts::{CommandExecutedEvent, CommandId};
 
     let mut app = test_app();
@@ -1031,6 +1033,7 @@ fn camera_fit_command_with_grid_config() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
     app.add_systems(Startup, systems::spawn_camera);
     app.add_observer(systems::handle_camera_command);
@@ -1069,7 +1072,7 @@ fn camera_fit_command_without_grid_config_noop() {
 
 #[test]
 fn camera_zoom_to_selection_with_selected_hex() {
-    use hexorder_contracts::hex_grid::{HexGridConfig, HexPosition, SelectedHex};
+    use hexorder_contracts::hex_grid::{GridShape, HexGridConfig, HexPosition, SelectedHex};
     use hexorder_contracts::shortcuts::{CommandExecutedEvent, CommandId};
 
     let mut app = test_app();
@@ -1080,6 +1083,7 @@ fn camera_zoom_to_selection_with_selected_hex() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 10,
+        shape: GridShape::Hexagon,
     });
     app.insert_resource(SelectedHex {
         position: Some(HexPosition::new(3, -2)),
@@ -1109,7 +1113,7 @@ fn camera_zoom_to_selection_with_selected_hex() {
 
 #[test]
 fn camera_zoom_to_selection_without_selection_noop() {
-    use hexorder_contracts::hex_grid::{HexGridConfig, SelectedHex};
+    use hexorder_contracts::hex_grid::{GridShape, HexGridConfig, SelectedHex};
     use hexorder_contracts::shortcuts::{CommandExecutedEvent, CommandId};
 
     let mut app = test_app();
@@ -1120,6 +1124,7 @@ fn camera_zoom_to_selection_without_selection_noop() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 10,
+        shape: GridShape::Hexagon,
     });
     app.insert_resource(SelectedHex { position: None });
     app.add_systems(Startup, systems::spawn_camera);
@@ -1189,7 +1194,7 @@ fn test_app_with_window() -> App {
 
 #[test]
 fn configure_bounds_with_window_computes_fit_scale() {
-    use hexorder_contracts::hex_grid::HexGridConfig;
+    use hexorder_contracts::hex_grid::{GridShape, HexGridConfig};
 
     let mut app = test_app_with_window();
     app.insert_resource(HexGridConfig {
@@ -1199,6 +1204,7 @@ fn configure_bounds_with_window_computes_fit_scale() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 10,
+        shape: GridShape::Hexagon,
     });
     app.add_systems(
         Startup,
@@ -1220,7 +1226,7 @@ fn configure_bounds_with_window_computes_fit_scale() {
 
 #[test]
 fn apply_pending_reset_with_window_recomputes_fit_scale() {
-    use hexorder_contracts::hex_grid::HexGridConfig;
+    use hexorder_contracts::hex_grid::{GridShape, HexGridConfig};
 
     let mut app = test_app_with_window();
     app.insert_resource(HexGridConfig {
@@ -1230,6 +1236,7 @@ fn apply_pending_reset_with_window_recomputes_fit_scale() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 10,
+        shape: GridShape::Hexagon,
     });
     app.add_systems(Startup, systems::spawn_camera);
     app.add_systems(Update, systems::apply_pending_reset);
@@ -1257,7 +1264,7 @@ fn apply_pending_reset_with_window_recomputes_fit_scale() {
 
 #[test]
 fn camera_fit_command_with_window() {
-    use hexorder_contracts::hex_grid::HexGridConfig;
+    use hexorder_contracts::hex_grid::{GridShape, HexGridConfig};
     use hexorder_contracts::shortcuts::{CommandExecutedEvent, CommandId};
 
     let mut app = test_app_with_window();
@@ -1268,6 +1275,7 @@ fn camera_fit_command_with_window() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
     app.add_systems(Startup, systems::spawn_camera);
     app.add_observer(systems::handle_camera_command);
@@ -1292,7 +1300,7 @@ fn camera_fit_command_with_window() {
 
 #[test]
 fn camera_reset_view_with_window_fits_and_centers() {
-    use hexorder_contracts::hex_grid::HexGridConfig;
+    use hexorder_contracts::hex_grid::{GridShape, HexGridConfig};
     use hexorder_contracts::shortcuts::{CommandExecutedEvent, CommandId};
 
     let mut app = test_app_with_window();
@@ -1303,6 +1311,7 @@ fn camera_reset_view_with_window_fits_and_centers() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
     app.add_systems(Startup, systems::spawn_camera);
     app.add_observer(systems::handle_camera_command);
@@ -11,9 +11,10 @@ use crate::game_system::{
     PropertyDefinition, PropertyType, PropertyValue, TypeId,
 };
 use crate::mechanics::{
-    CombatOutcome, CombatResultsTable, OutcomeEffect, Phase, PhaseType, PlayerOrder, TurnStructure,
+    CombatOutcome, CombatResultsTable, CombatRoles, CombatStrengthModel, CrtSelection, LossRules,
+    OutcomeEffect, Phase, PhaseType, PlayerOrder, TurnStructure,
 };
-use crate::simulation::{ColumnType, ResolutionTable, TableColumn, TableRow};
+use crate::simulation::{ColumnType, DicePool, ResolutionTable, TableColumn, TableRow};
 
 /// Creates a new `GameSystem` resource with a fresh UUID and default version.
 #[must_use]
@@ -242,6 +243,11 @@ pub fn create_default_crt() -> CombatResultsTable {
         },
         outcomes,
         combat_concept_id: None,
+        dice: DicePool::single(6),
+        strength: CombatStrengthModel::default(),
+        losses: LossRules::default(),
+        roles: CombatRoles::default(),
+        selection: CrtSelection::default(),
     }
 }
 
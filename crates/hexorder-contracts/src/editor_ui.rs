@@ -17,6 +17,9 @@ pub enum EditorTool {
     /// First click selects a hex, second click on an adjacent hex assigns
     /// the active edge feature type to the shared boundary.
     EdgePaint,
+    /// Click near a hex corner to assign the active vertex feature type to
+    /// the point where three hexes meet.
+    VertexPaint,
     /// Two-click combat selection: first click assigns attacker, second
     /// click assigns defender. Only active during Combat phases.
     CombatSelect,
@@ -78,6 +81,23 @@ pub struct ActiveEdgeType {
     pub type_name: Option<String>,
 }
 
+/// Tracks the most recently painted or erased hex vertex, if any.
+/// Used by the inspector to show vertex feature details.
+#[derive(Resource, Debug, Default, Reflect)]
+pub struct SelectedVertex {
+    /// The vertex picked by the last vertex paint click.
+    #[reflect(ignore)]
+    pub vertex: Option<super::hex_grid::HexVertex>,
+}
+
+/// Tracks which vertex feature type the user is currently painting with.
+/// Analogous to `ActiveEdgeType` for edge painting.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveVertexType {
+    /// Name of the vertex feature type to assign (e.g., "Town", "Fortress").
+    pub type_name: Option<String>,
+}
+
 /// Multi-selection set for bulk operations (Shift+click, Cmd+A).
 /// Coexists with `SelectedHex` — `SelectedHex` is the primary selection for
 /// the inspector and single-tile operations; `Selection` is for bulk actions.
@@ -101,6 +121,14 @@ pub enum ToastKind {
     Info,
 }
 
+/// Asks the export plugin to save `contents` as a CSV file, offering
+/// `file_name` (without extension) in a save dialog.
+#[derive(Event, Debug, Clone)]
+pub struct ExportCsvEvent {
+    pub file_name: String,
+    pub contents: String,
+}
+
 /// Screen rect of the Viewport dock tab, updated each frame by `editor_ui`.
 /// Used by `pointer_over_ui_panel` and viewport margin calculation.
 #[derive(Resource, Debug, Clone, Copy, Default)]
@@ -162,6 +190,13 @@ mod tests {
         assert!(aet.type_name.is_none());
     }
 
+    #[test]
+    fn vertex_paint_state_defaults_are_none() {
+        assert!(SelectedVertex::default().vertex.is_none());
+        assert!(ActiveVertexType::default().type_name.is_none());
+        assert_ne!(EditorTool::EdgePaint, EditorTool::VertexPaint);
+    }
+
     #[test]
     fn viewport_margins_default_is_zero() {
         let m = ViewportMargins::default();
@@ -4,7 +4,10 @@
 //! and the unified entity type system. 0.4.0 replaces separate `CellType`/`UnitType`
 //! with `EntityType` + `EntityRole`.
 
-use std::collections::HashMap;
+// bevy_reflect derive macros generate underscore-prefixed bindings internally
+#![allow(clippy::used_underscore_binding)]
+
+use std::collections::{HashMap, HashSet};
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -35,6 +38,38 @@ impl Default for TypeId {
     }
 }
 
+/// Persistent identity of a placed unit instance. Unlike `Entity`, it
+/// survives save/load, trips through off-map zones and undo/redo, so
+/// properties, event logs and exports can refer to a specific piece.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub struct UnitId(pub Uuid);
+
+impl UnitId {
+    /// Generate a new random `UnitId`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// First eight hex digits of the id, for compact display.
+    #[must_use]
+    pub fn short(&self) -> String {
+        self.0.simple().to_string()[..8].to_string()
+    }
+}
+
+impl Default for UnitId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for UnitId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Game System Container
 // ---------------------------------------------------------------------------
@@ -66,6 +101,8 @@ pub enum PropertyType {
     Enum(TypeId),
     /// Reference to an entity type, optionally filtered by role.
     EntityRef(Option<EntityRole>),
+    /// Reference to a specific placed unit instance.
+    UnitRef,
     /// Ordered collection of a single inner property type.
     List(Box<PropertyType>),
     /// Map with enum keys (by `TypeId`) and typed values.
@@ -98,6 +135,8 @@ pub enum PropertyValue {
     Enum(String),
     /// Reference to an entity type, or None if unset.
     EntityRef(Option<TypeId>),
+    /// Reference to a placed unit instance, or None if unset.
+    UnitRef(Option<UnitId>),
     /// Ordered collection of values.
     List(Vec<PropertyValue>),
     /// Enum key name to value pairs (preserves insertion order for display).
@@ -122,6 +161,7 @@ impl PropertyValue {
             PropertyType::Color => PropertyValue::Color(bevy::color::Color::WHITE),
             PropertyType::Enum(_) => PropertyValue::Enum(String::new()),
             PropertyType::EntityRef(_) => PropertyValue::EntityRef(None),
+            PropertyType::UnitRef => PropertyValue::UnitRef(None),
             PropertyType::List(_) => PropertyValue::List(Vec::new()),
             PropertyType::Map(_, _) => PropertyValue::Map(Vec::new()),
             PropertyType::Struct(_) => PropertyValue::Struct(HashMap::new()),
@@ -129,6 +169,33 @@ impl PropertyValue {
             PropertyType::FloatRange { min, .. } => PropertyValue::FloatRange(*min),
         }
     }
+
+    /// Clears unit references (including those nested in lists, maps and
+    /// structs) whose target is not in `known`. Returns how many were cleared.
+    pub fn clear_dangling_unit_refs(&mut self, known: &HashSet<UnitId>) -> usize {
+        match self {
+            PropertyValue::UnitRef(target) => match target {
+                Some(id) if !known.contains(id) => {
+                    *target = None;
+                    1
+                }
+                _ => 0,
+            },
+            PropertyValue::List(items) => items
+                .iter_mut()
+                .map(|v| v.clear_dangling_unit_refs(known))
+                .sum(),
+            PropertyValue::Map(entries) => entries
+                .iter_mut()
+                .map(|(_, v)| v.clear_dangling_unit_refs(known))
+                .sum(),
+            PropertyValue::Struct(fields) => fields
+                .values_mut()
+                .map(|v| v.clear_dangling_unit_refs(known))
+                .sum(),
+            _ => 0,
+        }
+    }
 }
 
 /// A property schema entry defining a named, typed property with a default value.
@@ -285,10 +352,103 @@ pub struct EntityData {
 }
 
 /// Marker component for token entities on the hex grid.
-/// Used to distinguish tokens from tiles in queries.
+/// Used to distinguish tokens from tiles in queries. Every unit instance
+/// carries a `UnitOwner` (unowned by default) and a `UnitId` (freshly
+/// generated unless spawned with a saved one).
 #[derive(Component, Debug, Reflect)]
+#[require(UnitOwner, UnitId)]
 pub struct UnitInstance;
 
+/// Lookup from persistent unit ids to the entities currently carrying them.
+/// Rebuilt whenever units are spawned or despawned.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct UnitIndex {
+    pub entities: HashMap<UnitId, Entity>,
+}
+
+impl UnitIndex {
+    /// The entity carrying `id`, if that unit is on the board.
+    #[must_use]
+    pub fn get(&self, id: UnitId) -> Option<Entity> {
+        self.entities.get(&id).copied()
+    }
+
+    /// Resolves a `UnitRef` property value to the referenced entity.
+    #[must_use]
+    pub fn resolve(&self, value: &PropertyValue) -> Option<Entity> {
+        match value {
+            PropertyValue::UnitRef(Some(id)) => self.get(*id),
+            _ => None,
+        }
+    }
+}
+
+/// The faction a unit instance belongs to. `None` means unowned (neutral).
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub struct UnitOwner {
+    pub faction_id: Option<TypeId>,
+}
+
+impl UnitOwner {
+    /// Both units are owned, by different factions.
+    #[must_use]
+    pub fn opposes(self, other: UnitOwner) -> bool {
+        matches!((self.faction_id, other.faction_id), (Some(a), Some(b)) if a != b)
+    }
+
+    /// Both units are owned by the same faction.
+    #[must_use]
+    pub fn is_friendly_to(self, other: UnitOwner) -> bool {
+        self.faction_id.is_some() && self.faction_id == other.faction_id
+    }
+}
+
+/// A side in the game (e.g. "Allies", "Axis").
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct Faction {
+    pub id: TypeId,
+    pub name: String,
+    /// Tint applied to the faction's tokens.
+    pub color: Color,
+    /// Position in the turn's player order (lowest moves first).
+    pub player_order: u32,
+}
+
+/// Registry of the factions in the game system.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct FactionRegistry {
+    pub factions: Vec<Faction>,
+}
+
+impl FactionRegistry {
+    /// Look up a faction by its ID.
+    #[must_use]
+    pub fn get(&self, id: TypeId) -> Option<&Faction> {
+        self.factions.iter().find(|f| f.id == id)
+    }
+
+    /// Look up a faction by name (case-sensitive).
+    #[must_use]
+    pub fn find_by_name(&self, name: &str) -> Option<&Faction> {
+        self.factions.iter().find(|f| f.name == name)
+    }
+
+    /// Factions sorted by player order; ties keep registry order.
+    #[must_use]
+    pub fn in_player_order(&self) -> Vec<&Faction> {
+        let mut ordered: Vec<&Faction> = self.factions.iter().collect();
+        ordered.sort_by_key(|f| f.player_order);
+        ordered
+    }
+
+    /// Display name of the faction, or "Unowned" when `id` is `None` or unknown.
+    #[must_use]
+    pub fn name_of(&self, id: Option<TypeId>) -> &str {
+        id.and_then(|id| self.get(id))
+            .map_or("Unowned", |f| f.name.as_str())
+    }
+}
+
 /// Tracks which `BoardPosition` entity type the user is currently painting with.
 #[derive(Resource, Debug, Default, Reflect)]
 pub struct ActiveBoardType {
@@ -301,6 +461,12 @@ pub struct ActiveTokenType {
     pub entity_type_id: Option<TypeId>,
 }
 
+/// Tracks which faction newly placed tokens are assigned to.
+#[derive(Resource, Debug, Default, Reflect)]
+pub struct ActiveFaction {
+    pub faction_id: Option<TypeId>,
+}
+
 /// Tracks the currently selected unit entity, if any.
 #[derive(Resource, Debug, Default, Reflect)]
 pub struct SelectedUnit {
@@ -311,10 +477,205 @@ pub struct SelectedUnit {
 #[derive(Event, Debug, Reflect)]
 pub struct UnitPlacedEvent {
     pub entity: Entity,
+    pub unit_id: UnitId,
     pub position: super::hex_grid::HexPosition,
     pub entity_type_id: TypeId,
 }
 
+// ---------------------------------------------------------------------------
+// Entity State Machines
+// ---------------------------------------------------------------------------
+
+/// One state of an entity state machine (e.g. "Full", "Reduced").
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct StateDefinition {
+    pub id: TypeId,
+    pub name: String,
+    /// Property values that replace the instance's own while it is in this
+    /// state, keyed by `PropertyDefinition` ID.
+    #[serde(default)]
+    pub property_overrides: HashMap<TypeId, PropertyValue>,
+    /// Token colour in this state, replacing the entity type's colour.
+    #[serde(default)]
+    pub color: Option<Color>,
+    /// Short label drawn over the token or hex (e.g. "R"). Empty for none.
+    #[serde(default)]
+    pub badge: String,
+}
+
+/// What causes a state transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum StateTrigger {
+    /// A combat outcome took a step from the entity. Fires once per step.
+    StepLoss,
+    /// A combat outcome forced the entity to retreat.
+    Retreat,
+    /// A combat outcome eliminated the entity.
+    Eliminated,
+    /// The phase with this ID started.
+    PhaseStart(TypeId),
+    /// At a phase boundary, the constraint evaluated to `satisfied` with the
+    /// entity in its roles.
+    Constraint {
+        constraint_id: TypeId,
+        satisfied: bool,
+    },
+    /// A trace of the reachability rule found the entity in reach
+    /// (`in_reach`) or out of reach.
+    Reachability { rule_id: TypeId, in_reach: bool },
+    /// The user chose the transition in the inspector.
+    Manual,
+}
+
+/// A transition between two states of a machine.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct StateTransition {
+    pub id: TypeId,
+    pub name: String,
+    /// State the transition leaves. `None` fires from any state.
+    pub from: Option<TypeId>,
+    pub to: TypeId,
+    pub trigger: StateTrigger,
+}
+
+/// A designer-defined state machine attached to an entity type. Every
+/// instance of the type carries an `EntityState` in one of its states.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct StateMachine {
+    pub id: TypeId,
+    pub name: String,
+    pub entity_type_id: TypeId,
+    pub states: Vec<StateDefinition>,
+    /// State new instances start in. The first state when `None`.
+    pub initial_state: Option<TypeId>,
+    pub transitions: Vec<StateTransition>,
+}
+
+impl StateMachine {
+    /// Look up a state by its ID.
+    #[must_use]
+    pub fn state(&self, id: TypeId) -> Option<&StateDefinition> {
+        self.states.iter().find(|s| s.id == id)
+    }
+
+    /// The state new instances start in.
+    #[must_use]
+    pub fn initial(&self) -> Option<&StateDefinition> {
+        self.initial_state
+            .and_then(|id| self.state(id))
+            .or_else(|| self.states.first())
+    }
+
+    /// Transitions that can fire from `current`, in definition order.
+    pub fn transitions_from(&self, current: TypeId) -> impl Iterator<Item = &StateTransition> {
+        self.transitions.iter().filter(move |t| {
+            t.from.is_none_or(|from| from == current) && self.state(t.to).is_some()
+        })
+    }
+
+    /// The state `trigger` moves an instance in `current` to: the target of
+    /// the first matching transition, or `None` if no transition matches.
+    #[must_use]
+    pub fn next_state(&self, current: TypeId, trigger: &StateTrigger) -> Option<TypeId> {
+        self.transitions_from(current)
+            .find(|t| t.trigger == *trigger)
+            .map(|t| t.to)
+    }
+}
+
+/// Registry of entity state machines, at most one per entity type.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct StateMachineRegistry {
+    pub machines: Vec<StateMachine>,
+}
+
+impl StateMachineRegistry {
+    /// The state machine attached to an entity type.
+    #[must_use]
+    pub fn for_type(&self, entity_type_id: TypeId) -> Option<&StateMachine> {
+        self.machines
+            .iter()
+            .find(|m| m.entity_type_id == entity_type_id)
+    }
+
+    /// Look up a state by its ID across all machines.
+    #[must_use]
+    pub fn state(&self, state_id: TypeId) -> Option<&StateDefinition> {
+        self.machines.iter().find_map(|m| m.state(state_id))
+    }
+
+    /// Display name of a state, or "(none)" when `id` is `None` or unknown.
+    #[must_use]
+    pub fn state_name(&self, id: Option<TypeId>) -> &str {
+        id.and_then(|id| self.state(id))
+            .map_or("(none)", |s| s.name.as_str())
+    }
+}
+
+/// The current state of a unit or tile whose entity type has a state
+/// machine. Assigned the machine's initial state when missing.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[require(StateOverrides)]
+pub struct EntityState {
+    pub state_id: TypeId,
+}
+
+/// One state property override applied to an instance.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct AppliedOverride {
+    pub property_id: TypeId,
+    /// Value before the override was applied.
+    pub before: PropertyValue,
+    /// Value the state set.
+    pub after: PropertyValue,
+}
+
+/// The current state's property overrides, applied to the instance's
+/// `EntityData`. Reverting restores `before` only if the property still
+/// holds `after`, like `PresenceEffects`.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+pub struct StateOverrides {
+    /// State whose overrides are applied.
+    pub state_id: Option<TypeId>,
+    pub applied: Vec<AppliedOverride>,
+}
+
+impl StateOverrides {
+    /// Reverts every applied override on `data`, newest first, and clears the list.
+    pub fn revert(&mut self, data: &mut EntityData) {
+        self.state_id = None;
+        for applied in self.applied.drain(..).rev() {
+            if data.properties.get(&applied.property_id) == Some(&applied.after) {
+                data.properties.insert(applied.property_id, applied.before);
+            }
+        }
+    }
+
+    /// `data` with every applied override reverted: the base values to persist.
+    #[must_use]
+    pub fn base_data(&self, data: &EntityData) -> EntityData {
+        let mut base = data.clone();
+        self.clone().revert(&mut base);
+        base
+    }
+}
+
+/// Fired to apply a trigger to an entity's state machine. Does nothing if
+/// no transition from the current state matches.
+#[derive(Event, Debug, Clone)]
+pub struct StateTriggerEvent {
+    pub entity: Entity,
+    pub trigger: StateTrigger,
+}
+
+/// Fired to put an entity directly into a state of its machine (manual
+/// transitions and the inspector's state picker).
+#[derive(Event, Debug, Clone)]
+pub struct SetEntityStateEvent {
+    pub entity: Entity,
+    pub state_id: TypeId,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -357,6 +718,123 @@ mod tests {
         assert_eq!(deserialized.types[1].role, EntityRole::Token);
     }
 
+    fn faction(name: &str, player_order: u32) -> Faction {
+        Faction {
+            id: TypeId::new(),
+            name: name.to_string(),
+            color: bevy::color::Color::srgb(0.8, 0.1, 0.1),
+            player_order,
+        }
+    }
+
+    #[test]
+    fn faction_registry_lookup_and_player_order() {
+        let registry = FactionRegistry {
+            factions: vec![faction("Axis", 2), faction("Allies", 1)],
+        };
+        let axis = registry.factions[0].id;
+        assert_eq!(registry.get(axis).map(|f| f.name.as_str()), Some("Axis"));
+        assert_eq!(
+            registry.find_by_name("Allies").map(|f| f.player_order),
+            Some(1)
+        );
+        let names: Vec<_> = registry
+            .in_player_order()
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Allies", "Axis"]);
+        assert_eq!(registry.name_of(Some(axis)), "Axis");
+        assert_eq!(registry.name_of(None), "Unowned");
+    }
+
+    #[test]
+    fn faction_registry_ron_round_trip() {
+        let registry = FactionRegistry {
+            factions: vec![faction("Axis", 0)],
+        };
+        let ron_str = ron::to_string(&registry).expect("serialize");
+        let loaded: FactionRegistry = ron::from_str(&ron_str).expect("deserialize");
+        assert_eq!(loaded.factions.len(), 1);
+        assert_eq!(loaded.factions[0].id, registry.factions[0].id);
+        assert_eq!(loaded.factions[0].name, "Axis");
+    }
+
+    #[test]
+    fn unit_owner_opposition() {
+        let a = UnitOwner {
+            faction_id: Some(TypeId::new()),
+        };
+        let b = UnitOwner {
+            faction_id: Some(TypeId::new()),
+        };
+        let neutral = UnitOwner::default();
+        assert!(a.opposes(b));
+        assert!(!a.opposes(a));
+        assert!(!a.opposes(neutral));
+        assert!(a.is_friendly_to(a));
+        assert!(!a.is_friendly_to(b));
+        assert!(!neutral.is_friendly_to(neutral));
+    }
+
+    #[test]
+    fn unit_instance_requires_owner() {
+        let mut world = World::new();
+        let entity = world.spawn(UnitInstance).id();
+        assert_eq!(world.get::<UnitOwner>(entity), Some(&UnitOwner::default()));
+    }
+
+    #[test]
+    fn unit_instance_gets_unique_id_unless_given_one() {
+        let mut world = World::new();
+        let a = world.spawn(UnitInstance).id();
+        let b = world.spawn(UnitInstance).id();
+        assert_ne!(world.get::<UnitId>(a), world.get::<UnitId>(b));
+
+        let saved = UnitId::new();
+        let c = world.spawn((UnitInstance, saved)).id();
+        assert_eq!(world.get::<UnitId>(c), Some(&saved));
+    }
+
+    #[test]
+    fn clear_dangling_unit_refs_recurses_into_compounds() {
+        let kept = UnitId::new();
+        let known: HashSet<UnitId> = [kept].into_iter().collect();
+        let mut value = PropertyValue::List(vec![
+            PropertyValue::UnitRef(Some(kept)),
+            PropertyValue::UnitRef(Some(UnitId::new())),
+            PropertyValue::Map(vec![(
+                "Leader".to_string(),
+                PropertyValue::UnitRef(Some(UnitId::new())),
+            )]),
+            PropertyValue::UnitRef(None),
+        ]);
+        assert_eq!(value.clear_dangling_unit_refs(&known), 2);
+        let PropertyValue::List(items) = &value else {
+            panic!("expected list");
+        };
+        assert_eq!(items[0], PropertyValue::UnitRef(Some(kept)));
+        assert_eq!(items[1], PropertyValue::UnitRef(None));
+    }
+
+    #[test]
+    fn unit_index_resolves_unit_refs() {
+        let id = UnitId::new();
+        let entity = Entity::PLACEHOLDER;
+        let mut index = UnitIndex::default();
+        index.entities.insert(id, entity);
+        assert_eq!(
+            index.resolve(&PropertyValue::UnitRef(Some(id))),
+            Some(entity)
+        );
+        assert_eq!(index.resolve(&PropertyValue::UnitRef(None)), None);
+        assert_eq!(
+            index.resolve(&PropertyValue::UnitRef(Some(UnitId::new()))),
+            None
+        );
+        assert_eq!(index.resolve(&PropertyValue::Int(1)), None);
+    }
+
     #[test]
     fn enum_registry_insert_and_get() {
         let mut reg = EnumRegistry::default();
@@ -449,6 +927,8 @@ mod tests {
         let values: Vec<PropertyValue> = vec![
             PropertyValue::EntityRef(Some(TypeId::new())),
             PropertyValue::EntityRef(None),
+            PropertyValue::UnitRef(Some(UnitId::new())),
+            PropertyValue::UnitRef(None),
             PropertyValue::List(vec![PropertyValue::Int(1), PropertyValue::Int(2)]),
             PropertyValue::Map(vec![
                 ("Grass".to_string(), PropertyValue::Int(1)),
@@ -496,6 +976,7 @@ mod tests {
         let variants: Vec<PropertyType> = vec![
             PropertyType::EntityRef(None),
             PropertyType::EntityRef(Some(EntityRole::Token)),
+            PropertyType::UnitRef,
             PropertyType::List(Box::new(PropertyType::Int)),
             PropertyType::Map(enum_id, Box::new(PropertyType::Int)),
             PropertyType::Struct(struct_id),
@@ -626,6 +1107,9 @@ mod tests {
             (PropertyType::EntityRef(None), |v| {
                 matches!(v, PropertyValue::EntityRef(None))
             }),
+            (PropertyType::UnitRef, |v| {
+                matches!(v, PropertyValue::UnitRef(None))
+            }),
             (
                 PropertyType::List(Box::new(PropertyType::Int)),
                 |v| matches!(v, PropertyValue::List(l) if l.is_empty()),
@@ -688,4 +1172,121 @@ mod tests {
         assert!(removed.is_some());
         assert!(reg.get(id).is_none());
     }
+
+    fn step_machine() -> StateMachine {
+        let full = TypeId::new();
+        let reduced = TypeId::new();
+        let eliminated = TypeId::new();
+        let state = |id, name: &str| StateDefinition {
+            id,
+            name: name.to_string(),
+            property_overrides: HashMap::new(),
+            color: None,
+            badge: String::new(),
+        };
+        let transition = |from, to, trigger| StateTransition {
+            id: TypeId::new(),
+            name: String::new(),
+            from,
+            to,
+            trigger,
+        };
+        StateMachine {
+            id: TypeId::new(),
+            name: "Steps".to_string(),
+            entity_type_id: TypeId::new(),
+            states: vec![
+                state(full, "Full"),
+                state(reduced, "Reduced"),
+                state(eliminated, "Eliminated"),
+            ],
+            initial_state: None,
+            transitions: vec![
+                transition(Some(full), reduced, StateTrigger::StepLoss),
+                transition(Some(reduced), eliminated, StateTrigger::StepLoss),
+                transition(None, eliminated, StateTrigger::Eliminated),
+            ],
+        }
+    }
+
+    #[test]
+    fn state_machine_follows_matching_transitions() {
+        let machine = step_machine();
+        let [full, reduced, eliminated] = [0, 1, 2].map(|i| machine.states[i].id);
+
+        assert_eq!(machine.initial().map(|s| s.id), Some(full));
+        assert_eq!(
+            machine.next_state(full, &StateTrigger::StepLoss),
+            Some(reduced)
+        );
+        assert_eq!(
+            machine.next_state(reduced, &StateTrigger::StepLoss),
+            Some(eliminated)
+        );
+        // `from: None` fires from any state.
+        assert_eq!(
+            machine.next_state(full, &StateTrigger::Eliminated),
+            Some(eliminated)
+        );
+        assert_eq!(machine.next_state(full, &StateTrigger::Retreat), None);
+    }
+
+    #[test]
+    fn state_machine_initial_state_overrides_first() {
+        let mut machine = step_machine();
+        let reduced = machine.states[1].id;
+        machine.initial_state = Some(reduced);
+        assert_eq!(machine.initial().map(|s| s.id), Some(reduced));
+        // An unknown initial state falls back to the first.
+        machine.initial_state = Some(TypeId::new());
+        assert_eq!(machine.initial().map(|s| s.id), Some(machine.states[0].id));
+    }
+
+    #[test]
+    fn state_machine_registry_lookup_and_ron_round_trip() {
+        let machine = step_machine();
+        let type_id = machine.entity_type_id;
+        let reduced = machine.states[1].id;
+        let registry = StateMachineRegistry {
+            machines: vec![machine],
+        };
+        assert!(registry.for_type(type_id).is_some());
+        assert!(registry.for_type(TypeId::new()).is_none());
+        assert_eq!(registry.state_name(Some(reduced)), "Reduced");
+        assert_eq!(registry.state_name(None), "(none)");
+
+        let ron_str = ron::to_string(&registry).expect("serialize");
+        let loaded: StateMachineRegistry = ron::from_str(&ron_str).expect("deserialize");
+        assert_eq!(loaded.machines[0].states.len(), 3);
+        assert_eq!(loaded.machines[0].transitions.len(), 3);
+        assert_eq!(loaded.state_name(Some(reduced)), "Reduced");
+    }
+
+    #[test]
+    fn state_overrides_revert_unless_changed() {
+        let strength = TypeId::new();
+        let mut data = EntityData {
+            entity_type_id: TypeId::new(),
+            properties: HashMap::from([(strength, PropertyValue::Int(2))]),
+        };
+        let mut overrides = StateOverrides {
+            state_id: Some(TypeId::new()),
+            applied: vec![AppliedOverride {
+                property_id: strength,
+                before: PropertyValue::Int(4),
+                after: PropertyValue::Int(2),
+            }],
+        };
+        assert_eq!(
+            overrides.base_data(&data).properties[&strength],
+            PropertyValue::Int(4)
+        );
+
+        // A value edited while the override was active becomes the new base.
+        data.properties.insert(strength, PropertyValue::Int(3));
+        overrides.revert(&mut data);
+        assert_eq!(data.properties[&strength], PropertyValue::Int(3));
+        assert!(overrides.applied.is_empty());
+        assert!(overrides.state_id.is_none());
+    }
 }
@@ -1,11 +1,14 @@
 //! Shared hex grid types. See `docs/contracts/hex-grid.md`.
 
-use std::collections::HashMap;
+// bevy_reflect derive macros generate underscore-prefixed bindings internally
+#![allow(clippy::used_underscore_binding)]
+
+use std::collections::{HashMap, HashSet};
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::game_system::TypeId;
+use crate::game_system::{EntityData, PropertyValue, TypeId, UnitId, UnitOwner};
 
 /// Re-export `hexx::Hex` for coordinate math.
 pub use hexx::Hex;
@@ -45,6 +48,22 @@ impl HexPosition {
     }
 }
 
+/// Overall outline of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum GridShape {
+    /// Hexagonal board of `HexGridConfig::map_radius` rings around the origin.
+    #[default]
+    Hexagon,
+    /// Rectangular board of `width` columns by `height` rows (pointy-top
+    /// offset rows), centered on the origin. With `wrap_horizontal` the
+    /// east and west edges are joined, turning the board into a cylinder.
+    Rectangle {
+        width: u32,
+        height: u32,
+        wrap_horizontal: bool,
+    },
+}
+
 /// Global grid configuration.
 #[derive(Resource, Debug, Reflect)]
 pub struct HexGridConfig {
@@ -53,12 +72,237 @@ pub struct HexGridConfig {
     pub layout: hexx::HexLayout,
     /// Radius of the map in hex tiles from center.
     pub map_radius: u32,
+    /// Board outline. `Hexagon` uses `map_radius`; `Rectangle` ignores it.
+    pub shape: GridShape,
+}
+
+impl Default for HexGridConfig {
+    fn default() -> Self {
+        Self {
+            layout: hexx::HexLayout {
+                orientation: hexx::HexOrientation::Pointy,
+                ..hexx::HexLayout::default()
+            }
+            .with_hex_size(1.0),
+            map_radius: 10,
+            shape: GridShape::Hexagon,
+        }
+    }
+}
+
+impl HexGridConfig {
+    /// Column and row ranges of a rectangular board: `(col_min, width, row_min, height)`.
+    fn rect_bounds(width: u32, height: u32) -> (i32, i32, i32, i32) {
+        let width = width.max(1) as i32;
+        let height = height.max(1) as i32;
+        (-(width / 2), width, -(height / 2), height)
+    }
+
+    /// Offset column of an axial position (pointy-top rows).
+    fn column(pos: HexPosition) -> i32 {
+        pos.q + pos.r.div_euclid(2)
+    }
+
+    /// Returns the board width in columns when the east-west seam wraps.
+    #[must_use]
+    pub fn wrap_width(&self) -> Option<i32> {
+        match self.shape {
+            GridShape::Rectangle {
+                width,
+                wrap_horizontal: true,
+                ..
+            } => Some(width.max(1) as i32),
+            _ => None,
+        }
+    }
+
+    /// Maps a position onto its canonical on-board copy. Positions across
+    /// the seam of a wrapping board are shifted back by whole board widths;
+    /// on non-wrapping boards the position is returned unchanged.
+    #[must_use]
+    pub fn normalize(&self, pos: HexPosition) -> HexPosition {
+        let GridShape::Rectangle {
+            width,
+            height,
+            wrap_horizontal: true,
+        } = self.shape
+        else {
+            return pos;
+        };
+        let (col_min, width, _, _) = Self::rect_bounds(width, height);
+        let col = Self::column(pos);
+        let wrapped = (col - col_min).rem_euclid(width) + col_min;
+        HexPosition::new(pos.q + (wrapped - col), pos.r)
+    }
+
+    /// Whether `pos` is an on-board tile (in canonical form).
+    #[must_use]
+    pub fn contains(&self, pos: HexPosition) -> bool {
+        match self.shape {
+            GridShape::Hexagon => pos.to_hex().unsigned_distance_to(Hex::ZERO) <= self.map_radius,
+            GridShape::Rectangle { width, height, .. } => {
+                let (col_min, width, row_min, height) = Self::rect_bounds(width, height);
+                let col = Self::column(pos);
+                (row_min..row_min + height).contains(&pos.r)
+                    && (col_min..col_min + width).contains(&col)
+            }
+        }
+    }
+
+    /// Normalizes `pos` and returns it if it lies on the board.
+    #[must_use]
+    pub fn resolve(&self, pos: HexPosition) -> Option<HexPosition> {
+        let pos = self.normalize(pos);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Returns the copy of `to` (possibly across the seam) closest to `from`.
+    /// Lines and paths drawn towards this image take the short way around
+    /// a wrapping board.
+    #[must_use]
+    pub fn nearest_image(&self, from: HexPosition, to: HexPosition) -> HexPosition {
+        let Some(width) = self.wrap_width() else {
+            return to;
+        };
+        let to = self.normalize(to);
+        [
+            to,
+            HexPosition::new(to.q + width, to.r),
+            HexPosition::new(to.q - width, to.r),
+        ]
+        .into_iter()
+        .min_by_key(|image| hex_distance(from, *image))
+        .unwrap_or(to)
+    }
+
+    /// Hex distance that honours the wrap seam.
+    #[must_use]
+    pub fn distance(&self, a: HexPosition, b: HexPosition) -> u32 {
+        hex_distance(a, self.nearest_image(a, b))
+    }
+
+    /// Maps a vertex onto its canonical on-board copy. All three meeting
+    /// hexes shift across the seam together, so a corner reached from either
+    /// side of a wrapping board yields the same key.
+    #[must_use]
+    pub fn normalize_vertex(&self, vertex: HexVertex) -> HexVertex {
+        HexVertex {
+            origin: self.normalize(vertex.origin),
+            direction: vertex.direction,
+        }
+    }
+
+    /// The canonical edge between adjacent hexes `a` and `b`, which may
+    /// neighbour each other across the seam. The edge is built against
+    /// `b`'s nearest image and its origin mapped back onto the board, so an
+    /// edge crossed from either side of a wrapping board yields the same
+    /// key. `None` if the hexes are not adjacent.
+    #[must_use]
+    pub fn normalize_edge(&self, a: HexPosition, b: HexPosition) -> Option<HexEdge> {
+        let edge = HexEdge::between(a, self.nearest_image(a, b))?;
+        Some(HexEdge {
+            origin: self.normalize(edge.origin),
+            direction: edge.direction,
+        })
+    }
+
+    /// The six corners of `pos` in canonical, seam-normalized form.
+    #[must_use]
+    pub fn vertices_of(&self, pos: HexPosition) -> [HexVertex; 6] {
+        HexVertex::of_hex(pos).map(|vertex| self.normalize_vertex(vertex))
+    }
+
+    /// On-board neighbors of `pos`, normalized across the seam.
+    #[must_use]
+    pub fn neighbors(&self, pos: HexPosition) -> Vec<HexPosition> {
+        pos.to_hex()
+            .all_neighbors()
+            .into_iter()
+            .filter_map(|hex| self.resolve(HexPosition::from_hex(hex)))
+            .collect()
+    }
+
+    /// On-board positions within `radius` of `center` (inclusive),
+    /// normalized and deduplicated.
+    #[must_use]
+    pub fn range(&self, center: HexPosition, radius: u32) -> Vec<HexPosition> {
+        let mut seen = std::collections::HashSet::new();
+        center
+            .to_hex()
+            .range(radius)
+            .filter_map(|hex| self.resolve(HexPosition::from_hex(hex)))
+            .filter(|pos| seen.insert(*pos))
+            .collect()
+    }
+
+    /// All on-board tile positions.
+    #[must_use]
+    pub fn positions(&self) -> Vec<HexPosition> {
+        match self.shape {
+            GridShape::Hexagon => Hex::ZERO
+                .range(self.map_radius)
+                .map(HexPosition::from_hex)
+                .collect(),
+            GridShape::Rectangle { width, height, .. } => {
+                let (col_min, width, row_min, height) = Self::rect_bounds(width, height);
+                (row_min..row_min + height)
+                    .flat_map(|r| {
+                        (col_min..col_min + width)
+                            .map(move |col| HexPosition::new(col - r.div_euclid(2), r))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Ghost columns drawn just past each side of a wrapping board, as
+    /// `(ghost_position, source_position)` pairs. Empty unless the board wraps.
+    #[must_use]
+    pub fn ghost_positions(&self) -> Vec<(HexPosition, HexPosition)> {
+        let GridShape::Rectangle {
+            width,
+            height,
+            wrap_horizontal: true,
+        } = self.shape
+        else {
+            return Vec::new();
+        };
+        let (col_min, width, row_min, height) = Self::rect_bounds(width, height);
+        let col_max = col_min + width - 1;
+        (row_min..row_min + height)
+            .flat_map(|r| {
+                let at = |col: i32| HexPosition::new(col - r.div_euclid(2), r);
+                [
+                    (at(col_min - 1), at(col_max)),
+                    (at(col_max + 1), at(col_min)),
+                ]
+            })
+            .collect()
+    }
+
+    /// Rough half-extent of the board in hexes, used for camera bounds.
+    #[must_use]
+    pub fn extent(&self) -> u32 {
+        match self.shape {
+            GridShape::Hexagon => self.map_radius,
+            GridShape::Rectangle { width, height, .. } => width.max(height).div_ceil(2) + 1,
+        }
+    }
+}
+
+/// Marks a non-interactive copy of a seam tile drawn past the edge of a
+/// wrapping board. Mirrors the material of the tile at `source`.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+pub struct GhostTile {
+    pub source: HexPosition,
 }
 
 /// Fired when an entity moves to a new hex position.
 #[derive(Event, Debug, Reflect)]
 pub struct HexMoveEvent {
     pub entity: Entity,
+    /// Persistent id of the moved unit, stable across save/load.
+    pub unit_id: UnitId,
     pub from: HexPosition,
     pub to: HexPosition,
 }
@@ -274,6 +518,152 @@ impl HexEdgeRegistry {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Hex Vertices
+// ---------------------------------------------------------------------------
+
+/// A canonical representation of a hex vertex — the corner point where
+/// three hex tiles meet. Stored in canonical form: the "lowest" of the three
+/// hexes (ordered by q, then r) is always the origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub struct HexVertex {
+    /// The canonical origin hex (lowest of the three meeting hexes).
+    pub origin: HexPosition,
+    /// Direction index (0-5). The vertex is shared by the origin and its
+    /// neighbors in directions `direction` and `direction + 1`.
+    pub direction: u8,
+}
+
+impl HexVertex {
+    /// The three hexes meeting at corner `direction` of `origin`.
+    fn hexes_at(origin: HexPosition, direction: u8) -> [HexPosition; 3] {
+        let dirs = hexx::EdgeDirection::ALL_DIRECTIONS;
+        let hex = origin.to_hex();
+        [
+            origin,
+            HexPosition::from_hex(hex.neighbor(dirs[direction as usize % 6])),
+            HexPosition::from_hex(hex.neighbor(dirs[(direction as usize + 1) % 6])),
+        ]
+    }
+
+    /// Create a new vertex from a hex and corner direction (0-5).
+    /// Direction is taken modulo 6. The result is always in canonical
+    /// form: the "lowest" of the three hexes (by q, then r) becomes the origin.
+    #[must_use]
+    pub fn new(origin: HexPosition, direction: u8) -> Self {
+        let hexes = Self::hexes_at(origin, direction % 6);
+        let lowest = hexes
+            .iter()
+            .copied()
+            .min_by_key(|pos| (pos.q, pos.r))
+            .unwrap_or(origin);
+        (0..6)
+            .map(|dir| Self {
+                origin: lowest,
+                direction: dir,
+            })
+            .find(|vertex| {
+                let candidate = vertex.hexes();
+                hexes.iter().all(|pos| candidate.contains(pos))
+            })
+            .unwrap_or(Self {
+                origin,
+                direction: direction % 6,
+            })
+    }
+
+    /// Create the canonical vertex shared by three mutually adjacent hexes.
+    /// Returns `None` if the positions do not meet at a single corner.
+    #[must_use]
+    pub fn between(a: HexPosition, b: HexPosition, c: HexPosition) -> Option<Self> {
+        (0..6)
+            .find(|&dir| {
+                let hexes = Self::hexes_at(a, dir);
+                hexes.contains(&b) && hexes.contains(&c) && b != c
+            })
+            .map(|dir| Self::new(a, dir))
+    }
+
+    /// Returns the six vertices at the corners of a hex.
+    #[must_use]
+    pub fn of_hex(pos: HexPosition) -> [Self; 6] {
+        [0, 1, 2, 3, 4, 5].map(|dir| Self::new(pos, dir))
+    }
+
+    /// Returns the three hex positions meeting at this vertex.
+    #[must_use]
+    pub fn hexes(&self) -> [HexPosition; 3] {
+        Self::hexes_at(self.origin, self.direction)
+    }
+
+    /// Returns true if `pos` is one of the three hexes meeting at this vertex.
+    #[must_use]
+    pub fn touches(&self, pos: HexPosition) -> bool {
+        self.hexes().contains(&pos)
+    }
+}
+
+/// An annotation on a hex vertex (town, fortress, supply point, ...).
+/// References a user-defined type by name, resolved against
+/// `EntityTypeRegistry` at use time.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct VertexFeature {
+    /// Name of the entity type this vertex annotation represents.
+    pub type_name: String,
+}
+
+/// Resource-based registry of vertex annotations.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[reflect(opaque)]
+pub struct HexVertexRegistry {
+    pub vertices: HashMap<HexVertex, VertexFeature>,
+}
+
+impl HexVertexRegistry {
+    /// Insert or replace a vertex feature.
+    pub fn insert(&mut self, vertex: HexVertex, feature: VertexFeature) {
+        self.vertices.insert(vertex, feature);
+    }
+
+    /// Look up the feature on a vertex.
+    #[must_use]
+    pub fn get(&self, vertex: &HexVertex) -> Option<&VertexFeature> {
+        self.vertices.get(vertex)
+    }
+
+    /// Remove a vertex feature. Returns the removed feature.
+    pub fn remove(&mut self, vertex: &HexVertex) -> Option<VertexFeature> {
+        self.vertices.remove(vertex)
+    }
+
+    /// Iterate over all vertex features.
+    pub fn iter(&self) -> impl Iterator<Item = (&HexVertex, &VertexFeature)> {
+        self.vertices.iter()
+    }
+
+    /// Iterate over all vertex features at the corners of a specific hex.
+    pub fn vertices_for_hex(
+        &self,
+        pos: HexPosition,
+    ) -> impl Iterator<Item = (&HexVertex, &VertexFeature)> {
+        self.vertices
+            .iter()
+            .filter(move |(vertex, _)| vertex.touches(pos))
+    }
+
+    /// Returns true if the registry has no vertex features.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Returns the number of vertex features in the registry.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Spatial Influence
 // ---------------------------------------------------------------------------
@@ -281,7 +671,10 @@ impl HexEdgeRegistry {
 /// A rule defining which entity type projects spatial influence.
 ///
 /// When a unit of `entity_type_id` is on the board, all hexes within `range`
-/// steps cost an extra `cost_modifier` movement points to enter.
+/// steps cost an extra `cost_modifier` movement points to enter. A vertex
+/// feature of that type radiates the same way, with the three hexes meeting
+/// at the vertex counting as range 1. `zone` adds zone-of-control behaviour
+/// on top of the entry cost.
 #[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
 pub struct InfluenceRule {
     pub id: TypeId,
@@ -291,6 +684,49 @@ pub struct InfluenceRule {
     pub range: u32,
     /// Extra movement cost to enter an influenced hex.
     pub cost_modifier: i64,
+    /// Zone-of-control effects beyond the entry cost.
+    #[serde(default)]
+    pub zone: ZoneOfControl,
+    /// When set, the influence (cost and zone effects) only applies to units
+    /// of a faction opposed to the source unit's owner.
+    #[serde(default)]
+    pub enemy_only: bool,
+}
+
+/// Zone-of-control behaviour of an influence rule. The default is a plain
+/// cost zone: no stop, no exit cost, free movement between influenced hexes.
+#[derive(Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ZoneOfControl {
+    /// A unit entering an influenced hex must end its move there.
+    pub stop_on_enter: bool,
+    /// Extra movement cost to leave an influenced hex, charged once per rule.
+    pub exit_cost: i64,
+    /// Moving directly from one hex influenced by this rule to another.
+    pub transition: ZoneTransition,
+    /// Unit types whose presence in a hex cancels the influence there
+    /// (typically the moving side's own units).
+    pub negated_by: Vec<TypeId>,
+    /// A unit of the moving unit's own faction in a hex cancels the
+    /// influence there for that unit, whatever its type.
+    pub negated_by_friendly: bool,
+    /// Edge feature types influence does not extend across (e.g. rivers).
+    pub blocking_edge_types: Vec<TypeId>,
+    /// Terrain types influence does not extend into or through.
+    pub excluded_terrain: Vec<TypeId>,
+}
+
+/// Whether a unit may move directly between two hexes influenced by the
+/// same rule ("ZOC to ZOC").
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum ZoneTransition {
+    /// No restriction beyond the entry and exit costs.
+    #[default]
+    Allowed,
+    /// The move is not allowed.
+    Forbidden,
+    /// The move costs this much extra.
+    ExtraCost(i64),
 }
 
 /// Registry of spatial influence rules. Defines which entity types project
@@ -301,6 +737,14 @@ pub struct InfluenceRuleRegistry {
     pub rules: Vec<InfluenceRule>,
 }
 
+impl InfluenceRuleRegistry {
+    /// Looks up a rule by ID.
+    #[must_use]
+    pub fn get(&self, id: TypeId) -> Option<&InfluenceRule> {
+        self.rules.iter().find(|r| r.id == id)
+    }
+}
+
 /// A single influence source affecting a hex position.
 #[derive(Debug, Clone, Reflect)]
 pub struct InfluenceEntry {
@@ -310,6 +754,11 @@ pub struct InfluenceEntry {
     pub rule_id: TypeId,
     /// Extra movement cost applied when entering this hex.
     pub cost_modifier: i64,
+    /// Set when the influence radiates from a vertex feature rather than a
+    /// unit. `source_pos` is then the vertex's origin hex.
+    pub source_vertex: Option<HexVertex>,
+    /// Owner of the projecting unit (unowned for vertex features).
+    pub source_owner: UnitOwner,
 }
 
 /// Cached map of hex positions under spatial influence.
@@ -348,22 +797,37 @@ impl InfluenceMap {
 
 /// A game system rule limiting how many units can occupy a single hex.
 ///
-/// When `max_units` is 0, stacking is unlimited. Entity types listed in
-/// `exempt_type_ids` do not count toward the limit and can always be placed.
+/// Units count by stacking points: the value of `points_property_id` on the
+/// unit, or 1 when unset. A hex's limit is the smallest of `max_units` (0
+/// means unlimited), the limit for its terrain type and the limit for the
+/// unit's faction. Entity types listed in `exempt_type_ids` count 0 points
+/// and can always be placed.
 #[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
 #[reflect(opaque)]
 pub struct StackingRule {
-    /// Maximum number of non-exempt units per hex. 0 means unlimited.
+    /// Maximum stacking points per hex. 0 means unlimited.
     pub max_units: u32,
     /// Entity type IDs that are exempt from the stacking limit.
     pub exempt_type_ids: Vec<TypeId>,
+    /// Forbid units of opposed factions from sharing a hex.
+    #[serde(default)]
+    pub no_mixed_factions: bool,
+    /// Integer unit property giving a unit's stacking points.
+    #[serde(default)]
+    pub points_property_id: Option<TypeId>,
+    /// Limits for hexes of a terrain type (e.g. 1 in swamp).
+    #[serde(default)]
+    pub terrain_limits: HashMap<TypeId, u32>,
+    /// Limits for units of a faction.
+    #[serde(default)]
+    pub faction_limits: HashMap<TypeId, u32>,
 }
 
 impl StackingRule {
-    /// Returns true if the stacking limit is active (non-zero).
+    /// Returns true if any stacking limit is set.
     #[must_use]
     pub fn is_active(&self) -> bool {
-        self.max_units > 0
+        self.max_units > 0 || !self.terrain_limits.is_empty() || !self.faction_limits.is_empty()
     }
 
     /// Returns true if the given entity type is exempt from stacking.
@@ -372,18 +836,67 @@ impl StackingRule {
         self.exempt_type_ids.contains(&type_id)
     }
 
-    /// Checks whether placing one more unit of the given type at a hex
-    /// with `current_count` non-exempt units would exceed the limit.
+    /// Stacking points a unit counts for: 0 when its type is exempt,
+    /// otherwise its points property (negative values count 0), or 1 when
+    /// the rule has no points property or the unit lacks it.
     #[must_use]
-    pub fn would_exceed(&self, type_id: TypeId, current_non_exempt: u32) -> bool {
-        if !self.is_active() {
-            return false;
+    pub fn points(&self, data: &EntityData) -> u32 {
+        if self.is_exempt(data.entity_type_id) {
+            return 0;
         }
-        if self.is_exempt(type_id) {
-            return false;
+        match self
+            .points_property_id
+            .and_then(|id| data.properties.get(&id))
+        {
+            Some(PropertyValue::Int(points)) => u32::try_from((*points).max(0)).unwrap_or(u32::MAX),
+            _ => 1,
         }
-        current_non_exempt >= self.max_units
     }
+
+    /// The stacking limit of a hex of `terrain` for a unit owned by `owner`,
+    /// or `None` when unlimited.
+    #[must_use]
+    pub fn limit(&self, terrain: Option<TypeId>, owner: UnitOwner) -> Option<u32> {
+        let global = (self.max_units > 0).then_some(self.max_units);
+        let by_terrain = terrain.and_then(|id| self.terrain_limits.get(&id).copied());
+        let by_faction = owner
+            .faction_id
+            .and_then(|id| self.faction_limits.get(&id).copied());
+        [global, by_terrain, by_faction].into_iter().flatten().min()
+    }
+
+    /// Checks whether `adding` stacking points joining a hex that holds
+    /// `present` points would exceed `limit`.
+    #[must_use]
+    pub fn would_exceed(adding: u32, present: u32, limit: Option<u32>) -> bool {
+        adding > 0 && limit.is_some_and(|limit| present.saturating_add(adding) > limit)
+    }
+
+    /// Checks whether a unit owned by `owner` joining a hex held by
+    /// `present` owners would mix opposed factions.
+    #[must_use]
+    pub fn would_mix_factions(
+        &self,
+        owner: UnitOwner,
+        present: impl IntoIterator<Item = UnitOwner>,
+    ) -> bool {
+        self.no_mixed_factions && present.into_iter().any(|other| owner.opposes(other))
+    }
+}
+
+/// A hex holding more stacking points than its limit allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackingViolation {
+    pub position: HexPosition,
+    pub points: u32,
+    pub limit: u32,
+}
+
+/// Hexes currently over-stacked, e.g. after combat or spawns. Rebuilt by the
+/// rules engine, ordered by position.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct StackingViolations {
+    pub violations: Vec<StackingViolation>,
 }
 
 // ---------------------------------------------------------------------------
@@ -431,6 +944,159 @@ impl MovementCostMatrix {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Reachability
+// ---------------------------------------------------------------------------
+
+/// Where a reachability trace may end.
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+pub enum ReachabilitySource {
+    /// The listed hexes (e.g. map-edge supply hexes).
+    Hexes(Vec<HexPosition>),
+    /// Every tile of this terrain type (e.g. all cities).
+    Terrain(TypeId),
+    /// Hexes holding a unit of this type that is not opposed to the tracing
+    /// unit (e.g. a friendly HQ or depot).
+    UnitType(TypeId),
+}
+
+/// What a reachability trace may not pass through. A blocked hex can still
+/// start or end a trace; it only stops the trace passing through it.
+#[derive(Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReachabilityBlockers {
+    /// Hexes under influence projected by a unit of an opposing faction.
+    pub enemy_influence: bool,
+    /// Terrain types the trace may not pass through.
+    pub terrain: Vec<TypeId>,
+    /// Edge feature types the trace may not cross.
+    pub edge_types: Vec<TypeId>,
+}
+
+/// A designer-defined reachability check ("can this unit trace a path of at
+/// most `max_cost` to a source without crossing a blocker?"), used for
+/// supply lines, command links and rail connectivity.
+///
+/// Path costs are movement costs: terrain relations, the movement cost
+/// matrix, edge and vertex features and area markers, as for the traced
+/// unit's own move. Influence costs and zones of control do not apply; use
+/// `blockers.enemy_influence` instead.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct ReachabilityRule {
+    pub id: TypeId,
+    pub name: String,
+    /// Unit types that trace to the sources.
+    pub traced_types: Vec<TypeId>,
+    pub sources: Vec<ReachabilitySource>,
+    /// Highest path cost a trace may have.
+    pub max_cost: i64,
+    #[serde(default)]
+    pub blockers: ReachabilityBlockers,
+    /// Phases at whose start the rule is traced during play.
+    #[serde(default)]
+    pub phases: Vec<TypeId>,
+}
+
+/// Registry of reachability rules.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[reflect(opaque)]
+pub struct ReachabilityRuleRegistry {
+    pub rules: Vec<ReachabilityRule>,
+}
+
+impl ReachabilityRuleRegistry {
+    /// Looks up a rule by ID.
+    #[must_use]
+    pub fn get(&self, id: TypeId) -> Option<&ReachabilityRule> {
+        self.rules.iter().find(|r| r.id == id)
+    }
+
+    /// Display name of a rule, or "(none)" when `id` is `None` or unknown.
+    #[must_use]
+    pub fn rule_name(&self, id: Option<TypeId>) -> &str {
+        id.and_then(|id| self.get(id))
+            .map_or("(none)", |r| r.name.as_str())
+    }
+}
+
+/// Result of the latest trace of each reachability rule for a unit, keyed
+/// by rule ID. Rules that have not traced the unit are absent.
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect)]
+pub struct ReachabilityStatus {
+    pub in_reach: HashMap<TypeId, bool>,
+}
+
+impl ReachabilityStatus {
+    /// Whether the unit was in reach at the rule's latest trace.
+    #[must_use]
+    pub fn is_in_reach(&self, rule_id: TypeId) -> Option<bool> {
+        self.in_reach.get(&rule_id).copied()
+    }
+}
+
+/// The hexes from which units sharing a movement profile (type, owner,
+/// state and cost classification) reach a rule's sources.
+#[derive(Debug, Clone, Reflect)]
+pub struct ReachabilityTrace {
+    pub rule_id: TypeId,
+    /// The traced units this trace applies to.
+    pub units: Vec<Entity>,
+    /// Each hex in reach, with the cost of its cheapest path to a source.
+    pub reached: HashMap<HexPosition, i64>,
+}
+
+/// The latest traces of every reachability rule. Replaced rule by rule as
+/// rules are traced.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(opaque)]
+pub struct ReachabilityMap {
+    pub traces: Vec<ReachabilityTrace>,
+}
+
+impl ReachabilityMap {
+    /// The trace of `rule_id` to show for `unit`: the unit's own trace, or
+    /// the rule's first trace when the unit is not traced by the rule.
+    #[must_use]
+    pub fn trace_for(&self, rule_id: TypeId, unit: Option<Entity>) -> Option<&ReachabilityTrace> {
+        let mut traces = self.traces.iter().filter(|t| t.rule_id == rule_id);
+        traces
+            .clone()
+            .find(|t| unit.is_some_and(|unit| t.units.contains(&unit)))
+            .or_else(|| traces.next())
+    }
+}
+
+/// Which reachability rule's in-reach and out-of-reach hexes are drawn on
+/// the board. `None` hides the overlay.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ReachabilityOverlay {
+    pub rule_id: Option<TypeId>,
+}
+
+/// Fired to trace a reachability rule now (`None` traces every rule).
+/// Updates each traced unit's `ReachabilityStatus`, replaces the rule's
+/// traces in `ReachabilityMap`, and fires a `StateTrigger::Reachability`
+/// for each traced unit.
+#[derive(Event, Debug, Clone)]
+pub struct TraceReachabilityEvent {
+    pub rule_id: Option<TypeId>,
+}
+
+// ---------------------------------------------------------------------------
+// Command Radius
+// ---------------------------------------------------------------------------
+
+/// Hexes within reach of the selected unit for the gating `Proximity`
+/// expressions it can satisfy as the nearest match, e.g. an HQ's command
+/// radius. Rebuilt by the rules engine when the selection or the board
+/// changes; empty when no such expression looks for the selected unit.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct CommandRadius {
+    /// The unit the radius is drawn around.
+    pub source: Option<Entity>,
+    pub hexes: HashSet<HexPosition>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -635,6 +1301,88 @@ mod tests {
         assert_eq!(edges.len(), 2);
     }
 
+    // -- Hex Vertex tests --
+
+    #[test]
+    fn hex_vertex_same_corner_from_all_three_hexes_equal() {
+        let vertex = HexVertex::new(HexPosition::new(0, 0), 0);
+        let [a, b, c] = vertex.hexes();
+        let from_each: Vec<HexVertex> = [a, b, c]
+            .iter()
+            .map(|pos| {
+                *HexVertex::of_hex(*pos)
+                    .iter()
+                    .find(|v| v.touches(a) && v.touches(b) && v.touches(c))
+                    .expect("each hex has the shared corner")
+            })
+            .collect();
+        assert!(from_each.iter().all(|v| *v == vertex));
+    }
+
+    #[test]
+    fn hex_vertex_origin_is_lowest_hex() {
+        for dir in 0..6 {
+            let vertex = HexVertex::new(HexPosition::new(2, -1), dir);
+            let lowest = vertex
+                .hexes()
+                .into_iter()
+                .min_by_key(|p| (p.q, p.r))
+                .expect("three hexes");
+            assert_eq!(vertex.origin, lowest);
+        }
+    }
+
+    #[test]
+    fn hex_vertex_of_hex_produces_six_unique_corners() {
+        let corners = HexVertex::of_hex(HexPosition::new(0, 0));
+        let unique: std::collections::HashSet<_> = corners.iter().collect();
+        assert_eq!(unique.len(), 6);
+        assert!(corners.iter().all(|v| v.touches(HexPosition::new(0, 0))));
+    }
+
+    #[test]
+    fn hex_vertex_between_requires_shared_corner() {
+        let vertex = HexVertex::new(HexPosition::new(0, 0), 2);
+        let [a, b, c] = vertex.hexes();
+        assert_eq!(HexVertex::between(c, a, b), Some(vertex));
+        assert!(
+            HexVertex::between(
+                HexPosition::new(0, 0),
+                HexPosition::new(1, 0),
+                HexPosition::new(3, 0)
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn vertex_registry_insert_lookup_and_vertices_for_hex() {
+        let mut registry = HexVertexRegistry::default();
+        let center = HexPosition::new(0, 0);
+        registry.insert(
+            HexVertex::new(center, 1),
+            VertexFeature {
+                type_name: "Town".to_string(),
+            },
+        );
+        registry.insert(
+            HexVertex::new(HexPosition::new(5, 5), 0),
+            VertexFeature {
+                type_name: "Fort".to_string(),
+            },
+        );
+        let vertex = HexVertex::new(center, 1);
+        let neighbor = vertex.hexes()[1];
+        assert_eq!(
+            registry.get(&vertex).map(|f| f.type_name.as_str()),
+            Some("Town")
+        );
+        assert_eq!(registry.vertices_for_hex(center).count(), 1);
+        assert_eq!(registry.vertices_for_hex(neighbor).count(), 1);
+        assert!(registry.remove(&vertex).is_some());
+        assert_eq!(registry.len(), 1);
+    }
+
     // -----------------------------------------------------------------------
     // Additional coverage tests
     // -----------------------------------------------------------------------
@@ -750,11 +1498,41 @@ mod tests {
             entity_type_id: TypeId::new(),
             range: 2,
             cost_modifier: 3,
+            zone: ZoneOfControl::default(),
+            enemy_only: false,
         };
         assert_eq!(rule.range, 2);
         assert_eq!(rule.cost_modifier, 3);
     }
 
+    #[test]
+    fn influence_rule_without_zone_loads_as_plain_cost_zone() {
+        let id = TypeId::new();
+        let ron_str = format!(
+            "(id: (\"{}\"), entity_type_id: (\"{}\"), range: 1, cost_modifier: 2)",
+            id.0, id.0
+        );
+        let rule: InfluenceRule = ron::from_str(&ron_str).expect("deserialize");
+        assert_eq!(rule.zone, ZoneOfControl::default());
+        assert_eq!(rule.zone.transition, ZoneTransition::Allowed);
+    }
+
+    #[test]
+    fn zone_of_control_ron_round_trip() {
+        let zone = ZoneOfControl {
+            stop_on_enter: true,
+            exit_cost: 1,
+            transition: ZoneTransition::ExtraCost(2),
+            negated_by: vec![TypeId::new()],
+            negated_by_friendly: true,
+            blocking_edge_types: vec![TypeId::new()],
+            excluded_terrain: Vec::new(),
+        };
+        let ron_str = ron::to_string(&zone).expect("serialize");
+        let loaded: ZoneOfControl = ron::from_str(&ron_str).expect("deserialize");
+        assert_eq!(loaded, zone);
+    }
+
     #[test]
     fn influence_map_default_is_empty() {
         let map = InfluenceMap::default();
@@ -770,6 +1548,8 @@ mod tests {
             source_pos: HexPosition::new(0, 0),
             rule_id: TypeId::new(),
             cost_modifier: 2,
+            source_vertex: None,
+            source_owner: UnitOwner::default(),
         });
         assert!(!map.is_empty());
         let entries = map.get(pos).expect("should have entries");
@@ -787,6 +1567,8 @@ mod tests {
                 source_pos: HexPosition::new(1, 0),
                 rule_id: TypeId::new(),
                 cost_modifier: 1,
+                source_vertex: None,
+                source_owner: UnitOwner::default(),
             });
         assert!(!map.is_empty());
         map.clear();
@@ -799,14 +1581,20 @@ mod tests {
     fn stacking_rule_default_is_inactive() {
         let rule = StackingRule::default();
         assert!(!rule.is_active());
-        assert!(!rule.would_exceed(TypeId::new(), 100));
+        assert_eq!(rule.limit(None, UnitOwner::default()), None);
+        assert!(!StackingRule::would_exceed(1, 100, None));
     }
 
     #[test]
-    fn stacking_rule_active_when_nonzero() {
+    fn stacking_rule_active_when_any_limit_set() {
         let rule = StackingRule {
             max_units: 2,
-            exempt_type_ids: Vec::new(),
+            ..Default::default()
+        };
+        assert!(rule.is_active());
+        let rule = StackingRule {
+            terrain_limits: HashMap::from([(TypeId::new(), 1)]),
+            ..Default::default()
         };
         assert!(rule.is_active());
     }
@@ -815,37 +1603,93 @@ mod tests {
     fn stacking_rule_blocks_when_at_capacity() {
         let rule = StackingRule {
             max_units: 2,
-            exempt_type_ids: Vec::new(),
+            ..Default::default()
         };
-        let unit_type = TypeId::new();
-        assert!(!rule.would_exceed(unit_type, 0));
-        assert!(!rule.would_exceed(unit_type, 1));
-        assert!(rule.would_exceed(unit_type, 2));
-        assert!(rule.would_exceed(unit_type, 5));
+        let limit = rule.limit(None, UnitOwner::default());
+        assert_eq!(limit, Some(2));
+        assert!(!StackingRule::would_exceed(1, 0, limit));
+        assert!(!StackingRule::would_exceed(1, 1, limit));
+        assert!(StackingRule::would_exceed(1, 2, limit));
+        assert!(StackingRule::would_exceed(1, 5, limit));
     }
 
     #[test]
-    fn stacking_rule_exempt_type_never_blocked() {
+    fn stacking_rule_exempt_type_counts_no_points() {
         let exempt_id = TypeId::new();
         let rule = StackingRule {
             max_units: 1,
             exempt_type_ids: vec![exempt_id],
+            ..Default::default()
+        };
+        let exempt = EntityData {
+            entity_type_id: exempt_id,
+            properties: HashMap::new(),
         };
         assert!(rule.is_exempt(exempt_id));
-        assert!(!rule.would_exceed(exempt_id, 1));
-        assert!(!rule.would_exceed(exempt_id, 100));
+        assert_eq!(rule.points(&exempt), 0);
+        assert!(!StackingRule::would_exceed(
+            rule.points(&exempt),
+            100,
+            Some(1)
+        ));
     }
 
     #[test]
-    fn stacking_rule_non_exempt_blocked_at_capacity() {
-        let exempt_id = TypeId::new();
-        let normal_id = TypeId::new();
+    fn stacking_rule_counts_points_property() {
+        let points_id = TypeId::new();
         let rule = StackingRule {
-            max_units: 1,
-            exempt_type_ids: vec![exempt_id],
+            max_units: 4,
+            points_property_id: Some(points_id),
+            ..Default::default()
+        };
+        let unit = |points: Option<i64>| EntityData {
+            entity_type_id: TypeId::new(),
+            properties: points
+                .map(|p| (points_id, PropertyValue::Int(p)))
+                .into_iter()
+                .collect(),
+        };
+        assert_eq!(rule.points(&unit(Some(3))), 3);
+        assert_eq!(rule.points(&unit(Some(-2))), 0);
+        assert_eq!(rule.points(&unit(None)), 1);
+        assert!(StackingRule::would_exceed(3, 2, Some(4)));
+        assert!(!StackingRule::would_exceed(2, 2, Some(4)));
+    }
+
+    #[test]
+    fn stacking_rule_limit_is_smallest_of_global_terrain_and_faction() {
+        let swamp = TypeId::new();
+        let faction = TypeId::new();
+        let rule = StackingRule {
+            max_units: 4,
+            terrain_limits: HashMap::from([(swamp, 1)]),
+            faction_limits: HashMap::from([(faction, 3)]),
+            ..Default::default()
+        };
+        let owned = UnitOwner {
+            faction_id: Some(faction),
+        };
+        assert_eq!(rule.limit(None, UnitOwner::default()), Some(4));
+        assert_eq!(rule.limit(None, owned), Some(3));
+        assert_eq!(rule.limit(Some(swamp), owned), Some(1));
+        assert_eq!(rule.limit(Some(TypeId::new()), owned), Some(3));
+    }
+
+    #[test]
+    fn stacking_rule_mixed_factions() {
+        let red = UnitOwner {
+            faction_id: Some(TypeId::new()),
+        };
+        let blue = UnitOwner {
+            faction_id: Some(TypeId::new()),
         };
-        assert!(!rule.is_exempt(normal_id));
-        assert!(rule.would_exceed(normal_id, 1));
+        let mut rule = StackingRule::default();
+        assert!(!rule.would_mix_factions(red, [blue]));
+        rule.no_mixed_factions = true;
+        assert!(rule.would_mix_factions(red, [red, blue]));
+        assert!(!rule.would_mix_factions(red, [red]));
+        assert!(!rule.would_mix_factions(UnitOwner::default(), [blue]));
+        assert!(!rule.would_mix_factions(red, []));
     }
 
     // -----------------------------------------------------------------------
@@ -893,4 +1737,158 @@ mod tests {
         matrix.set_cost(terrain_id, "Foot".to_string(), 5);
         assert_eq!(matrix.get_cost(terrain_id, "Foot"), Some(5));
     }
+
+    fn wrapping_config() -> HexGridConfig {
+        HexGridConfig {
+            shape: GridShape::Rectangle {
+                width: 10,
+                height: 6,
+                wrap_horizontal: true,
+            },
+            ..HexGridConfig::default()
+        }
+    }
+
+    #[test]
+    fn rectangle_positions_cover_width_times_height() {
+        let config = wrapping_config();
+        let positions = config.positions();
+        assert_eq!(positions.len(), 60);
+        assert!(positions.iter().all(|p| config.contains(*p)));
+    }
+
+    #[test]
+    fn wrap_normalizes_across_seam() {
+        let config = wrapping_config();
+        // Column 5 is one past the east edge (columns -5..=4).
+        assert_eq!(
+            config.normalize(HexPosition::new(5, 0)),
+            HexPosition::new(-5, 0)
+        );
+        assert_eq!(
+            config.normalize(HexPosition::new(-6, 0)),
+            HexPosition::new(4, 0)
+        );
+    }
+
+    #[test]
+    fn wrap_distance_takes_short_way_around() {
+        let config = wrapping_config();
+        let east = HexPosition::new(4, 0);
+        let west = HexPosition::new(-5, 0);
+        assert_eq!(hex_distance(east, west), 9);
+        assert_eq!(config.distance(east, west), 1);
+    }
+
+    #[test]
+    fn wrap_neighbors_cross_seam() {
+        let config = wrapping_config();
+        let neighbors = config.neighbors(HexPosition::new(4, 0));
+        assert!(neighbors.contains(&HexPosition::new(-5, 0)));
+        assert!(neighbors.iter().all(|p| config.contains(*p)));
+    }
+
+    #[test]
+    fn non_wrapping_rectangle_stops_at_edge() {
+        let config = HexGridConfig {
+            shape: GridShape::Rectangle {
+                width: 10,
+                height: 6,
+                wrap_horizontal: false,
+            },
+            ..HexGridConfig::default()
+        };
+        let neighbors = config.neighbors(HexPosition::new(4, 0));
+        assert!(!neighbors.contains(&HexPosition::new(-5, 0)));
+        assert!(config.ghost_positions().is_empty());
+    }
+
+    #[test]
+    fn hexagon_contains_respects_radius() {
+        let config = HexGridConfig {
+            map_radius: 2,
+            ..HexGridConfig::default()
+        };
+        assert!(config.contains(HexPosition::new(2, -2)));
+        assert!(!config.contains(HexPosition::new(3, 0)));
+        assert_eq!(config.positions().len(), 19);
+    }
+
+    #[test]
+    fn ghost_columns_mirror_opposite_edge() {
+        let config = wrapping_config();
+        let ghosts = config.ghost_positions();
+        assert_eq!(ghosts.len(), 12);
+        for (ghost, source) in ghosts {
+            assert!(!config.contains(ghost));
+            assert_eq!(config.normalize(ghost), source);
+        }
+    }
+
+    #[test]
+    fn seam_vertex_is_the_same_from_either_side() {
+        let config = wrapping_config();
+        let east = HexPosition::new(4, 0);
+        let west = HexPosition::new(-5, 0);
+        let shared_east: Vec<_> = config
+            .vertices_of(east)
+            .into_iter()
+            .filter(|v| config.vertices_of(west).contains(v))
+            .collect();
+        // East and west seam hexes are neighbors across the seam, sharing
+        // the two corners at the ends of their common edge.
+        assert_eq!(shared_east.len(), 2);
+    }
+
+    #[test]
+    fn seam_edge_is_the_same_from_either_side() {
+        let config = wrapping_config();
+        let east = HexPosition::new(4, 0);
+        let west = HexPosition::new(-5, 0);
+        let edge = config.normalize_edge(east, west).expect("seam neighbors");
+        assert_eq!(config.normalize_edge(west, east), Some(edge));
+        assert!(config.contains(edge.origin));
+        // Off the seam it is the plain canonical edge.
+        let inner = HexPosition::new(3, 0);
+        assert_eq!(
+            config.normalize_edge(inner, east),
+            HexEdge::between(east, inner)
+        );
+        assert_eq!(config.normalize_edge(east, HexPosition::new(2, 0)), None);
+    }
+
+    #[test]
+    fn reachability_rule_defaults_blockers_and_phases() {
+        let ron = "(id: (\"00000000-0000-0000-0000-000000000001\"), name: \"Supply\", \
+                   traced_types: [], sources: [Hexes([(q: 0, r: 0)])], max_cost: 4)";
+        let rule: ReachabilityRule = ron::from_str(ron).expect("deserialize");
+        assert_eq!(rule.blockers, ReachabilityBlockers::default());
+        assert!(rule.phases.is_empty());
+        assert_eq!(
+            rule.sources,
+            vec![ReachabilitySource::Hexes(vec![HexPosition::new(0, 0)])]
+        );
+    }
+
+    #[test]
+    fn reachability_trace_for_prefers_the_units_own_trace() {
+        let rule_id = TypeId::new();
+        let mut world = World::new();
+        let (a, b) = (world.spawn_empty().id(), world.spawn_empty().id());
+        let trace = |unit: Entity, q: i32| ReachabilityTrace {
+            rule_id,
+            units: vec![unit],
+            reached: HashMap::from([(HexPosition::new(q, 0), 0)]),
+        };
+        let map = ReachabilityMap {
+            traces: vec![trace(a, 1), trace(b, 2)],
+        };
+        let reached = |unit| {
+            map.trace_for(rule_id, unit)
+                .map(|t| t.reached.contains_key(&HexPosition::new(2, 0)))
+        };
+        assert_eq!(reached(Some(b)), Some(true));
+        assert_eq!(reached(None), Some(false));
+        assert!(map.trace_for(TypeId::new(), Some(a)).is_none());
+    }
 }
@@ -2,14 +2,26 @@
 //! Shared mechanics types. See `docs/contracts/mechanics.md`.
 //!
 //! Defines turn structure, combat resolution (CRT), combat modifiers,
-//! and combat execution state. Table lookup is delegated to the generic
-//! `ResolutionTable` primitives in `simulation.rs` (ADR-005).
+//! combat strength, combat execution state, movement interrupts, overruns and the play log.
+//! Table lookup is delegated to the generic `ResolutionTable` primitives in
+//! `simulation.rs` (ADR-005).
+
+use std::collections::HashMap;
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::game_system::TypeId;
-use crate::simulation::{ResolutionTable, find_table_column, find_table_row};
+use crate::game_system::{
+    EntityData, FactionRegistry, PropertyValue, StateTrigger, TypeId, UnitId, UnitOwner,
+};
+use crate::ontology::{ConceptRegistry, ConstraintExpr};
+use crate::simulation::{
+    ChainRollSource, ColumnModifier, ColumnType, DicePool, DiceRoll, ResolutionChain,
+    ResolutionTable, SimulationRng, TableColumn, TableResolution, TableResult, apply_column_shift,
+    find_table_column, find_table_row, pool_distribution, resolve_chain, resolve_table, roll_pool,
+    table_roll,
+};
+use crate::validation::ValidationResult;
 
 // ---------------------------------------------------------------------------
 // Turn Structure
@@ -155,6 +167,27 @@ pub struct CombatResultsTable {
     pub outcomes: Vec<Vec<CombatOutcome>>,
     /// Reference to the Combat concept in the ontology.
     pub combat_concept_id: Option<TypeId>,
+    /// Dice rolled to pick the row. Rows' value ranges are totals of this pool.
+    #[serde(default = "default_crt_dice")]
+    pub dice: DicePool,
+    /// How attack and defense strengths are computed from the combatants.
+    #[serde(default)]
+    pub strength: CombatStrengthModel,
+    /// How step losses are spread across the participants.
+    #[serde(default)]
+    pub losses: LossRules,
+    /// Roles of the Combat concept the participants fill in conditions.
+    #[serde(default)]
+    pub roles: CombatRoles,
+    /// When the table applies. Only read for tables in the
+    /// `CombatTableRegistry`; the default table applies otherwise.
+    #[serde(default)]
+    pub selection: CrtSelection,
+}
+
+/// Dice of tables saved before CRTs declared a pool: one d6.
+fn default_crt_dice() -> DicePool {
+    DicePool::single(6)
 }
 
 impl Default for CombatResultsTable {
@@ -171,6 +204,78 @@ impl Default for CombatResultsTable {
             },
             outcomes: Vec::new(),
             combat_concept_id: None,
+            dice: default_crt_dice(),
+            strength: CombatStrengthModel::default(),
+            losses: LossRules::default(),
+            roles: CombatRoles::default(),
+            selection: CrtSelection::default(),
+        }
+    }
+}
+
+/// Roles of a CRT's Combat concept filled by a combat's participants when
+/// a condition is evaluated: the attacking and defending units, the
+/// defender's tile and the feature on the edge between the primary
+/// attacker and defender. Unset roles are left to the concept bindings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub struct CombatRoles {
+    pub attacker: Option<TypeId>,
+    pub defender: Option<TypeId>,
+    pub terrain: Option<TypeId>,
+    pub edge: Option<TypeId>,
+}
+
+/// When a conditional CRT applies. Empty lists match every phase and
+/// faction; the condition is evaluated over the table's combat roles.
+#[derive(Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct CrtSelection {
+    /// Phases the table is used in.
+    pub phases: Vec<TypeId>,
+    /// Factions of the attacking side the table is used for.
+    pub factions: Vec<TypeId>,
+    /// Condition over the attacker, defender, terrain and edge.
+    pub condition: Option<ConstraintExpr>,
+}
+
+/// Conditional combat results tables. A combat uses the first table whose
+/// selection applies, and the `CombatResultsTable` resource otherwise.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct CombatTableRegistry {
+    pub tables: Vec<CombatResultsTable>,
+}
+
+impl CombatTableRegistry {
+    /// Looks up a table by ID.
+    #[must_use]
+    pub fn get(&self, id: TypeId) -> Option<&CombatResultsTable> {
+        self.tables.iter().find(|t| t.id == id)
+    }
+
+    /// Looks up a table by ID for editing.
+    pub fn get_mut(&mut self, id: TypeId) -> Option<&mut CombatResultsTable> {
+        self.tables.iter_mut().find(|t| t.id == id)
+    }
+
+    /// The table `id` names, or `default` when it is `None` or unknown.
+    #[must_use]
+    pub fn table_or<'a>(
+        &'a self,
+        id: Option<TypeId>,
+        default: &'a CombatResultsTable,
+    ) -> &'a CombatResultsTable {
+        id.and_then(|id| self.get(id)).unwrap_or(default)
+    }
+
+    /// The table `id` names for editing, or `default` when it is `None` or
+    /// unknown.
+    pub fn table_or_mut<'a>(
+        &'a mut self,
+        id: Option<TypeId>,
+        default: &'a mut CombatResultsTable,
+    ) -> &'a mut CombatResultsTable {
+        match id.and_then(|id| self.tables.iter_mut().find(|t| t.id == id)) {
+            Some(table) => table,
+            None => default,
         }
     }
 }
@@ -210,6 +315,10 @@ pub struct CombatModifierDefinition {
     pub cap: Option<i32>,
     /// Optional: only applies when a specific entity type is the defender terrain.
     pub terrain_type_filter: Option<TypeId>,
+    /// Optional: only applies when this condition holds over the roles of
+    /// the combat's CRT (see `CombatResultsTable::roles`).
+    #[serde(default)]
+    pub condition: Option<ConstraintExpr>,
 }
 
 /// Registry of all combat modifier definitions.
@@ -218,6 +327,351 @@ pub struct CombatModifierRegistry {
     pub modifiers: Vec<CombatModifierDefinition>,
 }
 
+impl CombatModifierRegistry {
+    /// The modifiers as generic column modifiers, for
+    /// `evaluate_column_modifiers`.
+    #[must_use]
+    pub fn column_modifiers(&self) -> Vec<ColumnModifier> {
+        self.modifiers
+            .iter()
+            .map(|m| ColumnModifier {
+                name: m.name.clone(),
+                column_shift: m.column_shift,
+                cap: m.cap,
+                priority: m.priority.max(0) as u32,
+            })
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Combat Strength
+// ---------------------------------------------------------------------------
+
+/// How fractional strengths and odds are rounded before the CRT lookup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum StrengthRounding {
+    /// Attack rounds down, defense rounds up and odds round down: every
+    /// fraction favors the defender.
+    #[default]
+    DefenderFavor,
+    /// Every fraction favors the attacker.
+    AttackerFavor,
+    /// Strengths and odds round to the nearest whole number.
+    Nearest,
+    /// No rounding.
+    Exact,
+}
+
+/// Multiplies the defense strength when the defender's hex has a terrain
+/// type (e.g. x2 for fortifications).
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct TerrainMultiplier {
+    pub terrain_type_id: TypeId,
+    pub multiplier: f64,
+}
+
+/// How combat strengths are computed. The attack and defense properties are
+/// concept-local names bound in the CRT's combat concept; combat strengths
+/// are entered by hand while either is unset.
+#[derive(Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct CombatStrengthModel {
+    pub attack_property: Option<String>,
+    pub defense_property: Option<String>,
+    pub terrain_multipliers: Vec<TerrainMultiplier>,
+    pub rounding: StrengthRounding,
+}
+
+/// One unit's contribution to its side's strength.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombatStrength {
+    /// Display name (e.g. the unit's type name).
+    pub label: String,
+    pub strength: f64,
+}
+
+/// Computed combat strengths, with each step for display before the roll.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct CombatOdds {
+    pub attacker_strength: f64,
+    pub defender_strength: f64,
+    /// Rounded ratio, differential or attack strength, per the column type.
+    pub raw_value: f64,
+    /// Each step as (description, value after the step).
+    pub steps: Vec<(String, f64)>,
+}
+
+/// The property an entity type binds to `name` in a concept.
+#[must_use]
+pub fn concept_property_id(
+    concepts: &ConceptRegistry,
+    concept_id: TypeId,
+    entity_type_id: TypeId,
+    name: &str,
+) -> Option<TypeId> {
+    concepts
+        .bindings
+        .iter()
+        .filter(|b| b.concept_id == concept_id && b.entity_type_id == entity_type_id)
+        .flat_map(|b| &b.property_bindings)
+        .find(|p| p.concept_local_name == name)
+        .map(|p| p.property_id)
+}
+
+/// Numeric value of the property `data`'s entity type binds to `name` in a
+/// concept. `None` if the type has no such binding or the value is not
+/// numeric.
+#[must_use]
+pub fn concept_property_value(
+    concepts: &ConceptRegistry,
+    concept_id: TypeId,
+    data: &EntityData,
+    name: &str,
+) -> Option<f64> {
+    let property_id = concept_property_id(concepts, concept_id, data.entity_type_id, name)?;
+    match data.properties.get(&property_id)? {
+        PropertyValue::Int(v) | PropertyValue::IntRange(v) => Some(*v as f64),
+        PropertyValue::Float(v) | PropertyValue::FloatRange(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Rounds `value` down, up or to the nearest whole number.
+fn round_value(value: f64, rounding: StrengthRounding, down: bool) -> f64 {
+    match rounding {
+        StrengthRounding::Exact => value,
+        StrengthRounding::Nearest => value.round(),
+        StrengthRounding::DefenderFavor | StrengthRounding::AttackerFavor => {
+            let favors_defender = rounding == StrengthRounding::DefenderFavor;
+            if down == favors_defender {
+                value.floor()
+            } else {
+                value.ceil()
+            }
+        }
+    }
+}
+
+/// Rounds an attack-to-defense ratio. Odds below 1:1 are rounded as 1:N, so
+/// 2 against 5 is 1:3 when fractions favor the defender.
+fn round_ratio(attack: f64, defense: f64, rounding: StrengthRounding) -> f64 {
+    if defense <= 0.0 {
+        return f64::INFINITY;
+    }
+    if attack <= 0.0 {
+        return 0.0;
+    }
+    let ratio = attack / defense;
+    if ratio >= 1.0 {
+        round_value(ratio, rounding, true)
+    } else {
+        1.0 / round_value(defense / attack, rounding, false)
+    }
+}
+
+/// Joins contributions as "Infantry 4 + Armor 6".
+fn describe_strengths(strengths: &[CombatStrength]) -> String {
+    strengths
+        .iter()
+        .map(|s| format!("{} {}", s.label, s.strength))
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// Computes combat odds: sums each side's contributions (a stack, or units
+/// from several hexes), multiplies the defense by the defender's terrain,
+/// rounds per the model and forms the ratio, differential or direct value
+/// of `column_type`.
+#[must_use]
+pub fn compute_combat_odds(
+    model: &CombatStrengthModel,
+    column_type: ColumnType,
+    attackers: &[CombatStrength],
+    defenders: &[CombatStrength],
+    defender_terrain: Option<TypeId>,
+) -> CombatOdds {
+    let mut steps = Vec::new();
+    let mut attack: f64 = attackers.iter().map(|s| s.strength).sum();
+    steps.push((
+        format!("Attack ({})", describe_strengths(attackers)),
+        attack,
+    ));
+    let mut defense: f64 = defenders.iter().map(|s| s.strength).sum();
+    steps.push((
+        format!("Defense ({})", describe_strengths(defenders)),
+        defense,
+    ));
+
+    if let Some(multiplier) = defender_terrain.and_then(|terrain| {
+        model
+            .terrain_multipliers
+            .iter()
+            .find(|m| m.terrain_type_id == terrain)
+    }) {
+        defense *= multiplier.multiplier;
+        steps.push((
+            format!("Terrain \u{00d7}{}", multiplier.multiplier),
+            defense,
+        ));
+    }
+
+    let rounded_attack = round_value(attack, model.rounding, true);
+    if (rounded_attack - attack).abs() > f64::EPSILON {
+        attack = rounded_attack;
+        steps.push(("Attack rounded".to_string(), attack));
+    }
+    let rounded_defense = round_value(defense, model.rounding, false);
+    if (rounded_defense - defense).abs() > f64::EPSILON {
+        defense = rounded_defense;
+        steps.push(("Defense rounded".to_string(), defense));
+    }
+
+    let raw_value = match column_type {
+        ColumnType::Ratio => {
+            let ratio = round_ratio(attack, defense, model.rounding);
+            steps.push((format!("Ratio {attack}:{defense}"), ratio));
+            ratio
+        }
+        ColumnType::Differential => {
+            let differential = attack - defense;
+            steps.push((
+                format!("Differential {attack} \u{2212} {defense}"),
+                differential,
+            ));
+            differential
+        }
+        ColumnType::Direct => attack,
+    };
+
+    CombatOdds {
+        attacker_strength: attack,
+        defender_strength: defense,
+        raw_value,
+        steps,
+    }
+}
+
+/// The rightmost column whose threshold the odds' raw value meets. The
+/// raw value is formed with the first column's type.
+#[must_use]
+pub fn odds_column(columns: &[TableColumn], odds: &CombatOdds) -> Option<usize> {
+    columns
+        .iter()
+        .rposition(|column| odds.raw_value >= column.threshold)
+}
+
+// ---------------------------------------------------------------------------
+// Combat Participants
+// ---------------------------------------------------------------------------
+
+/// Why a combat participant may not take part in the attack.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+pub enum AttackIneligibility {
+    /// The current phase is not a Combat phase.
+    NotCombatPhase,
+    /// An attack rule denies the unit; holds the rule's name.
+    DeniedByRule(String),
+    /// The unit has already attacked this phase.
+    AlreadyAttacked,
+    /// The unit is not adjacent to the opposing side.
+    NotAdjacent,
+}
+
+impl AttackIneligibility {
+    /// Short reason shown next to the participant.
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::NotCombatPhase => "not a Combat phase".to_string(),
+            Self::DeniedByRule(rule) => format!("denied by {rule}"),
+            Self::AlreadyAttacked => "already attacked this phase".to_string(),
+            Self::NotAdjacent => "not adjacent".to_string(),
+        }
+    }
+}
+
+/// Marks a unit that has attacked in the current phase.
+/// Runtime-only — removed when the phase changes.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+pub struct AttackedThisPhase;
+
+/// How a side's step losses are spread across its participating units.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum LossAllocation {
+    /// In the order the attacking player listed the participants.
+    #[default]
+    AttackerChoice,
+    /// Strongest unit first (attack strength for attackers, defense
+    /// strength for defenders).
+    LargestFirst,
+    /// Highest value of the named Combat concept property first.
+    ByProperty(String),
+}
+
+/// How step losses and exchanges are distributed among participants.
+#[derive(Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct LossRules {
+    pub allocation: LossAllocation,
+    /// Combat concept property holding a unit's remaining steps. Each unit
+    /// has one step while unset.
+    pub steps_property: Option<String>,
+}
+
+/// A participant that can absorb step losses.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct LossCandidate {
+    pub entity: Entity,
+    pub side: CombatSide,
+    /// Ordering key for `LargestFirst` and `ByProperty` (higher loses first).
+    pub priority: f64,
+    /// Steps the unit can lose before it is used up.
+    pub steps: u32,
+}
+
+/// Distributes an outcome's step losses across the candidates of each side.
+/// Candidates are ordered by the allocation (ties keep their listed order);
+/// each absorbs up to its steps before the next takes losses. Losses beyond
+/// a side's total steps are dropped.
+#[must_use]
+pub fn allocate_step_losses(
+    effect: &OutcomeEffect,
+    candidates: &[LossCandidate],
+    allocation: &LossAllocation,
+) -> Vec<(Entity, u32)> {
+    let (attacker_steps, defender_steps) = match effect {
+        OutcomeEffect::StepLoss { steps } => (0, *steps),
+        OutcomeEffect::AttackerStepLoss { steps } => (*steps, 0),
+        OutcomeEffect::Exchange {
+            attacker_steps,
+            defender_steps,
+        } => (*attacker_steps, *defender_steps),
+        _ => (0, 0),
+    };
+
+    let mut losses = Vec::new();
+    for (side, mut remaining) in [
+        (CombatSide::Attacker, attacker_steps),
+        (CombatSide::Defender, defender_steps),
+    ] {
+        let mut ordered: Vec<&LossCandidate> =
+            candidates.iter().filter(|c| c.side == side).collect();
+        if *allocation != LossAllocation::AttackerChoice {
+            ordered.sort_by(|a, b| b.priority.total_cmp(&a.priority));
+        }
+        for candidate in ordered {
+            if remaining == 0 {
+                break;
+            }
+            let lost = remaining.min(candidate.steps);
+            if lost > 0 {
+                losses.push((candidate.entity, lost));
+                remaining -= lost;
+            }
+        }
+    }
+    losses
+}
+
 // ---------------------------------------------------------------------------
 // Combat Execution (runtime, Play mode only)
 // ---------------------------------------------------------------------------
@@ -226,16 +680,32 @@ pub struct CombatModifierRegistry {
 /// Runtime-only — not persisted.
 #[derive(Resource, Debug, Default, Reflect)]
 pub struct ActiveCombat {
-    /// The attacking unit entity.
+    /// The primary attacking unit (the first of `attackers`).
     pub attacker: Option<Entity>,
-    /// The defending unit entity.
+    /// The primary defending unit (the first of `defenders`).
     pub defender: Option<Entity>,
+    /// Every attacking unit, in the attacking player's loss order.
+    pub attackers: Vec<Entity>,
+    /// Every defending unit, in the attacking player's loss order.
+    pub defenders: Vec<Entity>,
+    /// Participants that may not take part, with the reason.
+    pub ineligible: Vec<(Entity, AttackIneligibility)>,
+    /// Participants able to absorb step losses, kept up to date for the roll.
+    pub loss_candidates: Vec<LossCandidate>,
+    /// The conditional CRT the combat is resolved on; `None` uses the
+    /// default table (see `CombatTableRegistry::table_or`).
+    pub table: Option<TypeId>,
     /// Calculated raw odds ratio or differential before modifiers.
     pub raw_value: Option<f64>,
+    /// Strengths computed by the CRT's strength model, when it is set up.
+    pub odds: Option<CombatOdds>,
     /// Total column shift from all applicable modifiers.
     pub total_shift: i32,
     /// List of modifier names and their shifts (for display).
     pub applied_modifiers: Vec<(String, i32)>,
+    /// Whether each conditional combat modifier applies to this combat,
+    /// and why (see `modifier_applies`).
+    pub modifier_results: Vec<ValidationResult>,
     /// The final CRT column index after applying shifts.
     pub resolved_column: Option<usize>,
     /// The die roll result.
@@ -244,6 +714,127 @@ pub struct ActiveCombat {
     pub resolved_row: Option<usize>,
     /// The resolved combat outcome.
     pub outcome: Option<CombatOutcome>,
+    /// Steps each participant loses under the outcome.
+    pub step_losses: Vec<(Entity, u32)>,
+    /// Retreats waiting for the owning player to pick among equal paths.
+    pub retreats: Vec<RetreatChoice>,
+    /// Defender hexes emptied by the outcome, open to advance after combat.
+    pub vacated: Vec<crate::hex_grid::HexPosition>,
+    /// Attackers that may advance into a vacated hex, kept by `rules_engine`.
+    pub advances: Vec<AdvanceOption>,
+}
+
+/// Defending units that must retreat together, with the equally good paths
+/// to choose from. Each path starts at the units' hex.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct RetreatChoice {
+    pub units: Vec<Entity>,
+    pub paths: Vec<Vec<crate::hex_grid::HexPosition>>,
+}
+
+/// An attacking unit that may advance after combat from its hex into an
+/// adjacent vacated hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct AdvanceOption {
+    pub unit: Entity,
+    pub from: crate::hex_grid::HexPosition,
+    pub to: crate::hex_grid::HexPosition,
+}
+
+impl ActiveCombat {
+    /// Whether the modifier `id` applies to this combat: it has no recorded
+    /// result, or its result is satisfied.
+    #[must_use]
+    pub fn modifier_applies(&self, id: TypeId) -> bool {
+        self.modifier_results
+            .iter()
+            .find(|result| result.constraint_id == id)
+            .is_none_or(|result| result.satisfied)
+    }
+
+    /// The attacking units; the primary attacker alone when none are listed.
+    #[must_use]
+    pub fn attacking_units(&self) -> Vec<Entity> {
+        if self.attackers.is_empty() {
+            self.attacker.into_iter().collect()
+        } else {
+            self.attackers.clone()
+        }
+    }
+
+    /// The defending units; the primary defender alone when none are listed.
+    #[must_use]
+    pub fn defending_units(&self) -> Vec<Entity> {
+        if self.defenders.is_empty() {
+            self.defender.into_iter().collect()
+        } else {
+            self.defenders.clone()
+        }
+    }
+
+    /// Adds an attacking unit unless it already takes part.
+    pub fn add_attacker(&mut self, entity: Entity) {
+        if self.is_participant(entity) {
+            return;
+        }
+        self.attackers = self.attacking_units();
+        self.attackers.push(entity);
+        self.sync_primaries();
+    }
+
+    /// Adds a defending unit unless it already takes part.
+    pub fn add_defender(&mut self, entity: Entity) {
+        if self.is_participant(entity) {
+            return;
+        }
+        self.defenders = self.defending_units();
+        self.defenders.push(entity);
+        self.sync_primaries();
+    }
+
+    /// Removes a unit from either side.
+    pub fn remove_participant(&mut self, entity: Entity) {
+        self.attackers = self.attacking_units();
+        self.defenders = self.defending_units();
+        self.attackers.retain(|e| *e != entity);
+        self.defenders.retain(|e| *e != entity);
+        self.sync_primaries();
+    }
+
+    /// Moves a participant one place earlier in its side's loss order.
+    pub fn move_earlier(&mut self, entity: Entity) {
+        for list in [&mut self.attackers, &mut self.defenders] {
+            if let Some(index) = list.iter().position(|e| *e == entity)
+                && index > 0
+            {
+                list.swap(index, index - 1);
+            }
+        }
+        self.sync_primaries();
+    }
+
+    /// Whether the unit attacks or defends.
+    #[must_use]
+    pub fn is_participant(&self, entity: Entity) -> bool {
+        self.attacking_units().contains(&entity) || self.defending_units().contains(&entity)
+    }
+
+    /// Clears the roll and its outcome, after the participants change.
+    pub fn clear_resolution(&mut self) {
+        self.die_roll = None;
+        self.outcome = None;
+        self.step_losses.clear();
+        self.retreats.clear();
+        self.vacated.clear();
+        self.advances.clear();
+    }
+
+    /// Keeps the primary combatants the first of each side.
+    fn sync_primaries(&mut self) {
+        self.attacker = self.attackers.first().copied();
+        self.defender = self.defenders.first().copied();
+        self.clear_resolution();
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -264,12 +855,30 @@ pub struct PhaseAdvancedEvent {
 pub struct CombatResolvedEvent {
     pub attacker: Entity,
     pub defender: Entity,
+    /// Every attacking unit, including `attacker`.
+    pub attackers: Vec<Entity>,
+    /// Every defending unit, including `defender`.
+    pub defenders: Vec<Entity>,
+    /// Steps each participant loses, allocated by the CRT's loss rules.
+    pub step_losses: Vec<(Entity, u32)>,
     /// The resolved outcome with label and optional structured effect.
     pub outcome: CombatOutcome,
     pub die_roll: u32,
     pub column_label: String,
 }
 
+/// Fired to move combat participants after resolution: a retreat along a
+/// chosen path, or an advance into a vacated hex. Every unit ends at the
+/// path's last hex.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct CombatMoveEvent {
+    pub units: Vec<Entity>,
+    /// The hexes moved through, starting at the units' hex.
+    pub path: Vec<crate::hex_grid::HexPosition>,
+    /// `Retreat` or `Advance`.
+    pub action: PostResolutionAction,
+}
+
 // ---------------------------------------------------------------------------
 // Post-Resolution Movement
 // ---------------------------------------------------------------------------
@@ -402,7 +1011,7 @@ pub struct ConstrainedPathResult {
 /// The caller (rules engine) populates this with the relevant game state.
 /// The algorithm itself is pure — it reads from this context without side effects.
 #[derive(Debug)]
-pub struct PathfindingContext {
+pub struct PathfindingContext<'a> {
     /// Set of hexes the unit is allowed to move to (from BFS / `ValidMoveSet`).
     pub valid_positions: std::collections::HashSet<crate::hex_grid::HexPosition>,
     /// Hexes under influence, keyed by influence type name.
@@ -410,6 +1019,9 @@ pub struct PathfindingContext {
         std::collections::HashMap<String, std::collections::HashSet<crate::hex_grid::HexPosition>>,
     /// Terrain type at each hex, keyed by position.
     pub terrain_types: std::collections::HashMap<crate::hex_grid::HexPosition, String>,
+    /// The board, for neighbors and distances across the seam of a wrapping
+    /// board. `None` uses plain hex neighbors and distances.
+    pub grid_config: Option<&'a crate::hex_grid::HexGridConfig>,
 }
 
 /// Find the best path satisfying all constraints via BFS.
@@ -421,10 +1033,44 @@ pub struct PathfindingContext {
 #[must_use]
 pub fn find_constrained_path(
     request: &ConstrainedPathRequest,
-    ctx: &PathfindingContext,
+    ctx: &PathfindingContext<'_>,
 ) -> ConstrainedPathResult {
+    match find_constrained_paths(request, ctx).into_iter().next() {
+        Some(path) => ConstrainedPathResult {
+            path: Some(path),
+            failure_reason: None,
+        },
+        None => ConstrainedPathResult {
+            path: None,
+            failure_reason: Some("No valid path satisfying all constraints".to_string()),
+        },
+    }
+}
+
+/// Every equally good path satisfying all constraints, one per destination
+/// hex, in BFS order: those ending farthest from the `AwayFrom` sources and,
+/// among them, the shortest. `find_constrained_path` returns the first.
+#[must_use]
+pub fn find_constrained_paths(
+    request: &ConstrainedPathRequest,
+    ctx: &PathfindingContext<'_>,
+) -> Vec<Vec<crate::hex_grid::HexPosition>> {
     use std::collections::{HashSet, VecDeque};
 
+    let distance = |a, b| match ctx.grid_config {
+        Some(config) => config.distance(a, b),
+        None => crate::hex_grid::hex_distance(a, b),
+    };
+    let neighbors = |pos: crate::hex_grid::HexPosition| match ctx.grid_config {
+        Some(config) => config.neighbors(pos),
+        None => pos
+            .to_hex()
+            .all_neighbors()
+            .into_iter()
+            .map(crate::hex_grid::HexPosition::from_hex)
+            .collect(),
+    };
+
     // Parse constraints once.
     let away_from: Vec<crate::hex_grid::HexPosition> = request
         .constraints
@@ -488,7 +1134,7 @@ pub fn find_constrained_path(
     )> = VecDeque::new();
     queue.push_back((request.start, vec![request.start], 0));
 
-    let mut best_path: Option<Vec<crate::hex_grid::HexPosition>> = None;
+    let mut best_paths: Vec<Vec<crate::hex_grid::HexPosition>> = Vec::new();
     let mut best_away_distance: u32 = 0;
 
     while let Some((pos, path, dist)) = queue.pop_front() {
@@ -500,17 +1146,16 @@ pub fn find_constrained_path(
             } else {
                 away_from
                     .iter()
-                    .map(|s| crate::hex_grid::hex_distance(pos, *s))
+                    .map(|s| distance(pos, *s))
                     .min()
                     .unwrap_or(0)
             };
 
-            if min_away > best_away_distance
-                || (min_away == best_away_distance
-                    && best_path.as_ref().is_none_or(|bp| path.len() < bp.len()))
-            {
+            if min_away > best_away_distance || best_paths.is_empty() {
                 best_away_distance = min_away;
-                best_path = Some(path.clone());
+                best_paths = vec![path.clone()];
+            } else if min_away == best_away_distance && path.len() == best_paths[0].len() {
+                best_paths.push(path.clone());
             }
         }
 
@@ -519,11 +1164,7 @@ pub fn find_constrained_path(
             continue;
         }
 
-        // Expand neighbors using hexx.
-        let hex = pos.to_hex();
-        for neighbor_hex in hex.all_neighbors() {
-            let neighbor = crate::hex_grid::HexPosition::new(neighbor_hex.x, neighbor_hex.y);
-
+        for neighbor in neighbors(pos) {
             if visited.contains(&neighbor) {
                 continue;
             }
@@ -534,10 +1175,9 @@ pub fn find_constrained_path(
             }
 
             // AwayFrom: each step must not decrease distance from source.
-            let away_ok = away_from.iter().all(|s| {
-                crate::hex_grid::hex_distance(neighbor, *s)
-                    >= crate::hex_grid::hex_distance(pos, *s)
-            });
+            let away_ok = away_from
+                .iter()
+                .all(|s| distance(neighbor, *s) >= distance(pos, *s));
             if !away_ok {
                 continue;
             }
@@ -567,17 +1207,7 @@ pub fn find_constrained_path(
         }
     }
 
-    if let Some(path) = best_path {
-        ConstrainedPathResult {
-            path: Some(path),
-            failure_reason: None,
-        }
-    } else {
-        ConstrainedPathResult {
-            path: None,
-            failure_reason: Some("No valid path satisfying all constraints".to_string()),
-        }
-    }
+    best_paths
 }
 
 // ---------------------------------------------------------------------------
@@ -679,6 +1309,211 @@ pub fn find_spawn_hex<S: std::hash::BuildHasher>(
     None
 }
 
+// ---------------------------------------------------------------------------
+// Off-Map Zones
+// ---------------------------------------------------------------------------
+
+/// A unit held in an off-map zone. Mirrors the unit's `EntityData` so that
+/// per-instance property values survive the trip off the board and back.
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct ZoneUnit {
+    pub entity_type_id: TypeId,
+    pub properties: HashMap<TypeId, PropertyValue>,
+    /// Owning faction, restored when the unit is deployed.
+    #[serde(default)]
+    pub owner: Option<TypeId>,
+    /// Persistent identity, restored when the unit is deployed.
+    #[serde(default)]
+    pub id: Option<UnitId>,
+    /// State-machine state, restored when the unit is deployed.
+    #[serde(default)]
+    pub state: Option<TypeId>,
+}
+
+impl From<EntityData> for ZoneUnit {
+    fn from(data: EntityData) -> Self {
+        Self {
+            entity_type_id: data.entity_type_id,
+            properties: data.properties,
+            owner: None,
+            id: None,
+            state: None,
+        }
+    }
+}
+
+impl ZoneUnit {
+    /// Builds a zone unit from a board unit's data and owner.
+    #[must_use]
+    pub fn with_owner(data: EntityData, owner: UnitOwner) -> Self {
+        Self {
+            owner: owner.faction_id,
+            ..Self::from(data)
+        }
+    }
+
+    /// Keeps the board unit's persistent id while it is held in the zone.
+    #[must_use]
+    pub fn with_id(mut self, id: UnitId) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Keeps the board unit's state-machine state while it is held in the zone.
+    #[must_use]
+    pub fn with_state(mut self, state: Option<TypeId>) -> Self {
+        self.state = state;
+        self
+    }
+}
+
+impl From<ZoneUnit> for EntityData {
+    fn from(unit: ZoneUnit) -> Self {
+        Self {
+            entity_type_id: unit.entity_type_id,
+            properties: unit.properties,
+        }
+    }
+}
+
+/// A named off-grid holding box (e.g. "Reinforcements", "Eliminated").
+/// Units move into a zone when they leave the board and out of it when
+/// they are deployed back onto a hex.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct OffMapZone {
+    pub id: TypeId,
+    pub name: String,
+    /// Units currently held in this zone, in arrival order.
+    pub units: Vec<ZoneUnit>,
+    /// Phase during which held units may be deployed. `None` = any phase.
+    pub deploy_phase_id: Option<TypeId>,
+}
+
+impl OffMapZone {
+    /// Creates an empty zone with a fresh ID.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            id: TypeId::new(),
+            name: name.into(),
+            units: Vec::new(),
+            deploy_phase_id: None,
+        }
+    }
+}
+
+/// Registry of all off-map zones and their contents.
+/// Zone contents are board state and are saved with the scenario.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct OffMapZoneRegistry {
+    pub zones: Vec<OffMapZone>,
+    /// Zone that receives units eliminated by a combat outcome.
+    /// `None` = eliminated units are removed from play.
+    pub eliminated_zone_id: Option<TypeId>,
+}
+
+impl OffMapZoneRegistry {
+    /// Look up a zone by ID.
+    #[must_use]
+    pub fn get(&self, id: TypeId) -> Option<&OffMapZone> {
+        self.zones.iter().find(|z| z.id == id)
+    }
+
+    /// Look up a zone by ID (mutable).
+    pub fn get_mut(&mut self, id: TypeId) -> Option<&mut OffMapZone> {
+        self.zones.iter_mut().find(|z| z.id == id)
+    }
+
+    /// Look up a zone by its display name.
+    #[must_use]
+    pub fn find_by_name(&self, name: &str) -> Option<&OffMapZone> {
+        self.zones.iter().find(|z| z.name == name)
+    }
+
+    /// Moves a unit into a zone. Returns `false` if the zone does not exist.
+    pub fn store(&mut self, zone_id: TypeId, unit: ZoneUnit) -> bool {
+        let Some(zone) = self.get_mut(zone_id) else {
+            return false;
+        };
+        zone.units.push(unit);
+        true
+    }
+
+    /// Removes and returns the unit at `index` in a zone.
+    pub fn take(&mut self, zone_id: TypeId, index: usize) -> Option<ZoneUnit> {
+        let zone = self.get_mut(zone_id)?;
+        (index < zone.units.len()).then(|| zone.units.remove(index))
+    }
+
+    /// Whether units in a zone may be deployed during the given phase.
+    #[must_use]
+    pub fn can_deploy(&self, zone_id: TypeId, phase_id: Option<TypeId>) -> bool {
+        self.get(zone_id)
+            .is_some_and(|z| z.deploy_phase_id.is_none() || z.deploy_phase_id == phase_id)
+    }
+}
+
+/// Which side of a combat an effect applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum CombatSide {
+    Attacker,
+    Defender,
+}
+
+/// Returns the sides removed from the board by an outcome effect.
+#[must_use]
+pub fn eliminated_sides(effect: &OutcomeEffect) -> Vec<CombatSide> {
+    match effect {
+        OutcomeEffect::AttackerEliminated => vec![CombatSide::Attacker],
+        OutcomeEffect::DefenderEliminated => vec![CombatSide::Defender],
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the state machine triggers an outcome effect fires, per side.
+/// Each lost step is a separate `StepLoss` trigger.
+#[must_use]
+pub fn outcome_state_triggers(effect: &OutcomeEffect) -> Vec<(CombatSide, StateTrigger)> {
+    let steps =
+        |side: CombatSide, count: u32| (0..count).map(move |_| (side, StateTrigger::StepLoss));
+    match effect {
+        OutcomeEffect::NoEffect => Vec::new(),
+        OutcomeEffect::Retreat { .. } => vec![(CombatSide::Defender, StateTrigger::Retreat)],
+        OutcomeEffect::StepLoss { steps: count } => steps(CombatSide::Defender, *count).collect(),
+        OutcomeEffect::AttackerStepLoss { steps: count } => {
+            steps(CombatSide::Attacker, *count).collect()
+        }
+        OutcomeEffect::Exchange {
+            attacker_steps,
+            defender_steps,
+        } => steps(CombatSide::Attacker, *attacker_steps)
+            .chain(steps(CombatSide::Defender, *defender_steps))
+            .collect(),
+        OutcomeEffect::AttackerEliminated => {
+            vec![(CombatSide::Attacker, StateTrigger::Eliminated)]
+        }
+        OutcomeEffect::DefenderEliminated => {
+            vec![(CombatSide::Defender, StateTrigger::Eliminated)]
+        }
+    }
+}
+
+/// Fired to move a unit from the board into an off-map zone.
+#[derive(Event, Debug, Clone)]
+pub struct MoveToZoneEvent {
+    pub entity: Entity,
+    pub zone_id: TypeId,
+}
+
+/// Fired to deploy a held unit from an off-map zone onto a hex.
+#[derive(Event, Debug, Clone)]
+pub struct DeployFromZoneEvent {
+    pub zone_id: TypeId,
+    /// Index into the zone's `units` list.
+    pub index: usize,
+    pub position: crate::hex_grid::HexPosition,
+}
+
 // ---------------------------------------------------------------------------
 // CRT Resolution Helpers
 // ---------------------------------------------------------------------------
@@ -945,8 +1780,11 @@ pub enum AccumulationTrigger {
 pub struct Accumulator {
     /// Unique identifier for this accumulator.
     pub id: String,
-    /// Optional faction this accumulator belongs to.
+    /// Legacy free-text faction name, matched against faction names.
     pub faction: Option<String>,
+    /// Faction this accumulator belongs to (takes precedence over `faction`).
+    #[serde(default)]
+    pub faction_id: Option<TypeId>,
     /// Triggers that can add points to this accumulator.
     pub triggers: Vec<AccumulationTrigger>,
     /// Current accumulated value.
@@ -955,6 +1793,26 @@ pub struct Accumulator {
     pub history: Vec<(u32, i32)>,
 }
 
+impl Accumulator {
+    /// Resolves the owning faction from `faction_id`, falling back to a
+    /// faction whose name matches the legacy `faction` string.
+    #[must_use]
+    pub fn owner(&self, factions: &FactionRegistry) -> Option<TypeId> {
+        self.faction_id.or_else(|| {
+            self.faction
+                .as_deref()
+                .and_then(|name| factions.find_by_name(name))
+                .map(|f| f.id)
+        })
+    }
+
+    /// Whether the accumulator is tied to a faction at all.
+    #[must_use]
+    pub fn has_faction(&self) -> bool {
+        self.faction_id.is_some() || self.faction.is_some()
+    }
+}
+
 /// Registry of all accumulators in the scenario.
 #[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
 pub struct AccumulatorRegistry {
@@ -991,6 +1849,8 @@ pub struct VictoryConditionRegistry {
 pub struct VictoryReached {
     pub accumulator_id: String,
     pub faction: Option<String>,
+    /// The winning faction, when the accumulator belongs to one.
+    pub faction_id: Option<TypeId>,
     pub value: i32,
     pub threshold: i32,
 }
@@ -1056,17 +1916,72 @@ pub fn evaluate_occupy_triggers(
     modified
 }
 
-/// Check all victory conditions and return indices of those that are met.
+/// Evaluate occupy-hex triggers against the owners of the units on the board.
+///
+/// An accumulator scores an `OccupyHex` trigger when a unit of its faction
+/// stands on the hex; faction-less accumulators score for any owned unit.
+/// Each trigger scores at most once per call however many units occupy it.
+/// Returns the indices of accumulators that were modified.
 #[must_use]
-pub fn check_victory_conditions(
-    accumulators: &AccumulatorRegistry,
-    conditions: &VictoryConditionRegistry,
+pub fn evaluate_occupation_triggers(
+    registry: &mut AccumulatorRegistry,
+    factions: &FactionRegistry,
+    occupants: &[(crate::hex_grid::HexPosition, UnitOwner)],
+    turn: u32,
 ) -> Vec<usize> {
-    let mut met = Vec::new();
-    for (i, cond) in conditions.conditions.iter().enumerate() {
-        if let Some(acc) = accumulators
-            .accumulators
-            .iter()
+    let mut modified = Vec::new();
+    for (i, acc) in registry.accumulators.iter_mut().enumerate() {
+        let owner = acc.owner(factions);
+        if acc.has_faction() && owner.is_none() {
+            continue;
+        }
+        let mut delta = 0i32;
+        for trigger in &acc.triggers {
+            if let AccumulationTrigger::OccupyHex { hex, points } = trigger
+                && occupants.iter().any(|(pos, unit_owner)| {
+                    *pos == *hex
+                        && unit_owner.faction_id.is_some()
+                        && (owner.is_none() || unit_owner.faction_id == owner)
+                })
+            {
+                delta += points;
+            }
+        }
+        if delta != 0 {
+            acc.value += delta;
+            acc.history.push((turn, delta));
+            modified.push(i);
+        }
+    }
+    modified
+}
+
+/// The faction that wins when `condition` is met: the owner of its
+/// accumulator, if any.
+#[must_use]
+pub fn winning_faction(
+    condition: &VictoryCondition,
+    accumulators: &AccumulatorRegistry,
+    factions: &FactionRegistry,
+) -> Option<TypeId> {
+    accumulators
+        .accumulators
+        .iter()
+        .find(|a| a.id == condition.accumulator_id)
+        .and_then(|a| a.owner(factions))
+}
+
+/// Check all victory conditions and return indices of those that are met.
+#[must_use]
+pub fn check_victory_conditions(
+    accumulators: &AccumulatorRegistry,
+    conditions: &VictoryConditionRegistry,
+) -> Vec<usize> {
+    let mut met = Vec::new();
+    for (i, cond) in conditions.conditions.iter().enumerate() {
+        if let Some(acc) = accumulators
+            .accumulators
+            .iter()
             .find(|a| a.id == cond.accumulator_id)
         {
             let satisfied = match cond.comparison {
@@ -1082,6 +1997,613 @@ pub fn check_victory_conditions(
     met
 }
 
+// ---------------------------------------------------------------------------
+// Movement Interrupts
+// ---------------------------------------------------------------------------
+
+/// What makes an enemy unit react to a unit entering a hex.
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+pub enum InterruptTrigger {
+    /// The hex lies in the reacting unit's influence under this rule.
+    Influence { rule_id: TypeId },
+    /// The hex is within the reacting unit's `VisibilityRange` and in its
+    /// sight, which tiles of the listed terrain types block.
+    LineOfSight { blockers: Vec<TypeId> },
+    /// The hex is within `max` hexes of the reacting unit.
+    Proximity { max: u32 },
+}
+
+/// How an interrupt or overrun is resolved. Inputs are read from context
+/// values: the numeric properties of both units, keyed by their role (e.g.
+/// `reactor.<name>` and `mover.<name>`), and the `distance` between them.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub enum InterruptResolution {
+    /// One lookup on `table`: column inputs from the context, row from a
+    /// roll of `dice`.
+    Table {
+        table: ResolutionTable,
+        input_a_key: String,
+        input_b_key: String,
+        dice: DicePool,
+    },
+    /// A resolution chain over `tables`. The last step's result decides.
+    Chain {
+        chain: ResolutionChain,
+        tables: Vec<ResolutionTable>,
+    },
+}
+
+/// What a resolution result does to the interrupted unit.
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct InterruptOutcome {
+    /// The result this applies to: the text of a `Text` result, or the
+    /// number of a numeric one (e.g. `2`).
+    pub result: String,
+    /// Movement ends at the interrupt hex instead of continuing.
+    pub end_movement: bool,
+    /// State the interrupted unit is put into.
+    pub set_state: Option<TypeId>,
+}
+
+/// A rule that halts a moving unit at a hex enemy units react to, and
+/// resolves what happens before the move goes on.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct MovementInterruptRule {
+    pub id: TypeId,
+    pub name: String,
+    pub trigger: InterruptTrigger,
+    /// Only units of this type react. `None` lets any enemy unit react.
+    pub reactor_type: Option<TypeId>,
+    /// Only units of this type are interrupted. `None` interrupts any unit.
+    pub mover_type: Option<TypeId>,
+    pub resolution: InterruptResolution,
+    /// Effects of the resolution's results. Results without an entry let
+    /// the move continue.
+    pub outcomes: Vec<InterruptOutcome>,
+}
+
+/// Registry of movement interrupt rules.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct MovementInterruptRegistry {
+    pub rules: Vec<MovementInterruptRule>,
+}
+
+impl MovementInterruptRegistry {
+    /// The rule with `id`, if any.
+    #[must_use]
+    pub fn get(&self, id: TypeId) -> Option<&MovementInterruptRule> {
+        self.rules.iter().find(|rule| rule.id == id)
+    }
+}
+
+/// A roll made while resolving an interrupt or overrun, to be announced
+/// for the roll history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolutionRoll {
+    pub context: String,
+    pub roll: DiceRoll,
+    /// The table the roll was looked up in and what it found, if anything.
+    pub table: Option<(TypeId, TableResolution)>,
+}
+
+/// The resolved outcome of one interrupt.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InterruptResult {
+    /// The resolution's result, or `None` when the lookup found no cell.
+    pub result: Option<String>,
+    pub end_movement: bool,
+    pub set_state: Option<TypeId>,
+    /// The rolls made, in order.
+    pub rolls: Vec<ResolutionRoll>,
+}
+
+/// A move halted at an interrupt, waiting for it to be resolved.
+#[derive(Debug, Clone)]
+pub struct InterruptPause {
+    /// The halted unit.
+    pub mover: Entity,
+    /// The enemy unit reacting to it.
+    pub reactor: Entity,
+    pub rule_id: TypeId,
+    /// The hex the unit halted in.
+    pub position: crate::hex_grid::HexPosition,
+    /// Hexes of the route still to enter, in order.
+    pub remaining: Vec<crate::hex_grid::HexPosition>,
+    /// The unit's `MovementSpent` after entering each remaining hex.
+    pub costs: Vec<i64>,
+    /// Rule and reacting unit pairs resolved earlier in this move. Each
+    /// reacts at most once per move.
+    pub resolved: Vec<(TypeId, Entity)>,
+}
+
+/// The interrupt halting a move in Play, if any.
+/// Runtime-only — not persisted.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveInterrupt {
+    pub pause: Option<InterruptPause>,
+}
+
+/// Fired to move a unit in Play along its cheapest route to `to`, halting
+/// at the first hex a movement interrupt rule fires in.
+#[derive(Event, Debug, Reflect)]
+pub struct MoveRequestedEvent {
+    pub entity: Entity,
+    pub to: crate::hex_grid::HexPosition,
+}
+
+/// Fired to resolve the interrupt in `ActiveInterrupt` and apply its
+/// outcome.
+#[derive(Event, Debug, Reflect)]
+pub struct ResolveInterruptEvent;
+
+/// The label an interrupt outcome matches a table result by.
+#[must_use]
+pub fn interrupt_result_label(result: &TableResult) -> String {
+    match result {
+        TableResult::Text(text) => text.clone(),
+        TableResult::NumericValue(value) => value.to_string(),
+        TableResult::PropertyModifier { property, delta } => format!("{property} {delta:+}"),
+    }
+}
+
+/// Resolves `rule`'s table or chain against context `values`, rolling
+/// with `rng`, and looks up the outcome of the result.
+#[must_use]
+#[allow(clippy::implicit_hasher)]
+pub fn resolve_interrupt(
+    rule: &MovementInterruptRule,
+    values: &HashMap<String, f64>,
+    rng: &mut SimulationRng,
+) -> InterruptResult {
+    let (label, rolls) = resolution_label(&rule.resolution, values, rng, &rule.name);
+    let Some(label) = label else {
+        return InterruptResult {
+            rolls,
+            ..InterruptResult::default()
+        };
+    };
+    let outcome = rule.outcomes.iter().find(|o| o.result == label);
+    InterruptResult {
+        end_movement: outcome.is_some_and(|o| o.end_movement),
+        set_state: outcome.and_then(|o| o.set_state),
+        result: Some(label),
+        rolls,
+    }
+}
+
+/// Rolls and looks up `resolution`, returning the label of its result, or
+/// `None` when the lookup found no cell, with the rolls made. Rolls are
+/// logged as `context`.
+fn resolution_label(
+    resolution: &InterruptResolution,
+    values: &HashMap<String, f64>,
+    rng: &mut SimulationRng,
+    context: &str,
+) -> (Option<String>, Vec<ResolutionRoll>) {
+    let (resolution, rolls) = match resolution {
+        InterruptResolution::Table {
+            table,
+            input_a_key,
+            input_b_key,
+            dice,
+        } => {
+            let input = |key: &String| values.get(key).copied().unwrap_or(0.0);
+            let roll = roll_pool(rng, *dice, context);
+            let resolution = resolve_table(
+                table,
+                input(input_a_key),
+                input(input_b_key),
+                table_roll(roll.total),
+            );
+            let rolls = vec![ResolutionRoll {
+                context: context.to_string(),
+                roll,
+                table: resolution.clone().map(|r| (table.id, r)),
+            }];
+            (resolution, rolls)
+        }
+        InterruptResolution::Chain { chain, tables } => {
+            let tables = tables.iter().map(|t| (t.id, t.clone())).collect();
+            let mut step_log = resolve_chain(chain, values, &tables, rng).step_log;
+            let rolls = chain
+                .steps
+                .iter()
+                .zip(&step_log)
+                .filter_map(|(step, result)| {
+                    Some(ResolutionRoll {
+                        context: format!("{context} step {}", result.step_index + 1),
+                        roll: result.roll.clone()?,
+                        table: result.resolution.clone().map(|r| (step.table_id, r)),
+                    })
+                })
+                .collect();
+            (step_log.pop().and_then(|step| step.resolution), rolls)
+        }
+    };
+    (
+        resolution.map(|resolution| interrupt_result_label(&resolution.result)),
+        rolls,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Overrun
+// ---------------------------------------------------------------------------
+
+/// Movement points a unit has spent in the current phase. Moves in Play
+/// start from the unit's budget less this; it is reset to zero when the
+/// phase advances. Runtime-only — not persisted.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub struct MovementSpent(pub i64);
+
+/// What an overrun result does to the attacker and defender.
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct OverrunOutcome {
+    /// The result this applies to, matched as for interrupt outcomes.
+    pub result: String,
+    /// The defender is removed from the board (to the elimination zone, if
+    /// one is set) and the attacker may move on. Otherwise the attacker's
+    /// movement ends.
+    pub clears_defender: bool,
+    /// State the attacker is put into.
+    pub attacker_state: Option<TypeId>,
+    /// State the defender is put into, if it is not cleared.
+    pub defender_state: Option<TypeId>,
+}
+
+/// A resolution usable during a `PhaseType::Movement` phase: a moving unit
+/// spends `mp_cost` movement points to attack an adjacent enemy unit, and
+/// continues with its remaining budget if the defender is cleared.
+///
+/// The resolution is the rule's own table or chain; inputs are the numeric
+/// properties of both units, keyed `attacker.<name>` and `defender.<name>`,
+/// and their `distance`.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct OverrunRule {
+    pub id: TypeId,
+    pub name: String,
+    /// Movement points the attack costs the attacker.
+    pub mp_cost: i64,
+    /// Only units of this type may overrun. `None` allows any unit.
+    pub attacker_type: Option<TypeId>,
+    /// Only units of this type may be overrun. `None` allows any unit.
+    pub defender_type: Option<TypeId>,
+    pub resolution: InterruptResolution,
+    /// Effects of the resolution's results. Results without an entry end
+    /// the attacker's movement.
+    pub outcomes: Vec<OverrunOutcome>,
+}
+
+/// Registry of overrun rules.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct OverrunRegistry {
+    pub rules: Vec<OverrunRule>,
+}
+
+impl OverrunRegistry {
+    /// The rule with `id`, if any.
+    #[must_use]
+    pub fn get(&self, id: TypeId) -> Option<&OverrunRule> {
+        self.rules.iter().find(|rule| rule.id == id)
+    }
+}
+
+/// The resolved outcome of one overrun.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OverrunResult {
+    /// The resolution's result, or `None` when the lookup found no cell.
+    pub result: Option<String>,
+    pub clears_defender: bool,
+    pub attacker_state: Option<TypeId>,
+    pub defender_state: Option<TypeId>,
+    /// The rolls made, in order.
+    pub rolls: Vec<ResolutionRoll>,
+}
+
+/// Enemy units the selected unit may overrun now, each with the rule that
+/// allows it. Filled in Play during a `PhaseType::Movement` phase.
+/// Runtime-only — not persisted.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct OverrunTargets {
+    pub attacker: Option<Entity>,
+    /// Defender and overrun rule pairs.
+    pub targets: Vec<(Entity, TypeId)>,
+}
+
+/// Fired in Play to have `attacker` overrun the adjacent `defender` under
+/// the overrun rule `rule_id`.
+#[derive(Event, Debug, Reflect)]
+pub struct OverrunRequestedEvent {
+    pub attacker: Entity,
+    pub defender: Entity,
+    pub rule_id: TypeId,
+}
+
+/// Resolves `rule`'s table or chain against context `values`, rolling
+/// with `rng`, and looks up the outcome of the result.
+#[must_use]
+#[allow(clippy::implicit_hasher)]
+pub fn resolve_overrun(
+    rule: &OverrunRule,
+    values: &HashMap<String, f64>,
+    rng: &mut SimulationRng,
+) -> OverrunResult {
+    let (label, rolls) = resolution_label(&rule.resolution, values, rng, &rule.name);
+    let Some(label) = label else {
+        return OverrunResult {
+            rolls,
+            ..OverrunResult::default()
+        };
+    };
+    let outcome = rule.outcomes.iter().find(|o| o.result == label);
+    OverrunResult {
+        clears_defender: outcome.is_some_and(|o| o.clears_defender),
+        attacker_state: outcome.and_then(|o| o.attacker_state),
+        defender_state: outcome.and_then(|o| o.defender_state),
+        result: Some(label),
+        rolls,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Odds Analysis
+// ---------------------------------------------------------------------------
+
+/// Label of the share of rolls that find no cell in a table.
+pub const NO_RESULT_LABEL: &str = "\u{2014}";
+
+/// How likely one result is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutcomeOdds {
+    pub label: String,
+    pub probability: f64,
+}
+
+/// Steps each side is expected to lose per resolution. Eliminations are not
+/// counted as steps; their share shows in the outcome odds.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ExpectedLosses {
+    pub attacker: f64,
+    pub defender: f64,
+}
+
+/// The outcome distribution of one resolution: a CRT column, or a rule's
+/// table or chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OddsRow {
+    /// What was analyzed (e.g. the column label "3:1").
+    pub label: String,
+    /// Results in order of first appearance. Probabilities sum to one.
+    pub outcomes: Vec<OutcomeOdds>,
+    pub losses: ExpectedLosses,
+}
+
+/// Steps `(attacker, defender)` an outcome effect removes.
+#[must_use]
+pub fn outcome_step_losses(effect: &OutcomeEffect) -> (u32, u32) {
+    match effect {
+        OutcomeEffect::StepLoss { steps } => (0, *steps),
+        OutcomeEffect::AttackerStepLoss { steps } => (*steps, 0),
+        OutcomeEffect::Exchange {
+            attacker_steps,
+            defender_steps,
+        } => (*attacker_steps, *defender_steps),
+        OutcomeEffect::NoEffect
+        | OutcomeEffect::Retreat { .. }
+        | OutcomeEffect::AttackerEliminated
+        | OutcomeEffect::DefenderEliminated => (0, 0),
+    }
+}
+
+/// Expected step losses of `outcomes`, reading each result's effect from
+/// the CRT outcome with the same label. Results the CRT does not name lose
+/// no steps.
+#[must_use]
+pub fn expected_losses(outcomes: &[OutcomeOdds], crt: &CombatResultsTable) -> ExpectedLosses {
+    let mut losses = ExpectedLosses::default();
+    for odds in outcomes {
+        let effect = crt
+            .outcomes
+            .iter()
+            .flatten()
+            .find(|outcome| outcome.label == odds.label)
+            .and_then(|outcome| outcome.effect.as_ref());
+        if let Some(effect) = effect {
+            let (attacker, defender) = outcome_step_losses(effect);
+            losses.attacker += odds.probability * f64::from(attacker);
+            losses.defender += odds.probability * f64::from(defender);
+        }
+    }
+    losses
+}
+
+/// Adds `probability` to the odds of `label`, appending it if new.
+fn add_odds(outcomes: &mut Vec<OutcomeOdds>, label: &str, probability: f64) {
+    match outcomes.iter_mut().find(|odds| odds.label == label) {
+        Some(odds) => odds.probability += probability,
+        None => outcomes.push(OutcomeOdds {
+            label: label.to_string(),
+            probability,
+        }),
+    }
+}
+
+/// The exact outcome distribution of CRT column `column` for the table's
+/// dice. `None` if the column does not exist.
+#[must_use]
+pub fn crt_column_odds(crt: &CombatResultsTable, column: usize) -> Option<OddsRow> {
+    let column_label = crt.table.columns.get(column)?.label.clone();
+    let mut outcomes = Vec::new();
+    for (total, probability) in pool_distribution(crt.dice) {
+        let outcome = find_table_row(table_roll(total), &crt.table.rows)
+            .and_then(|row| crt.outcomes.get(row))
+            .and_then(|row| row.get(column));
+        let label = outcome.map_or(NO_RESULT_LABEL, |outcome| outcome.label.as_str());
+        add_odds(&mut outcomes, label, probability);
+    }
+    let losses = expected_losses(&outcomes, crt);
+    Some(OddsRow {
+        label: column_label,
+        outcomes,
+        losses,
+    })
+}
+
+/// The exact outcome distribution of every CRT column.
+#[must_use]
+pub fn crt_odds(crt: &CombatResultsTable) -> Vec<OddsRow> {
+    (0..crt.table.columns.len())
+        .filter_map(|column| crt_column_odds(crt, column))
+        .collect()
+}
+
+/// The exact outcome distribution of an attack at the given strengths,
+/// after `shift` columns of modifiers. `None` below the first column.
+#[must_use]
+pub fn crt_attack_odds(
+    crt: &CombatResultsTable,
+    attacker_strength: f64,
+    defender_strength: f64,
+    shift: i32,
+) -> Option<OddsRow> {
+    let base = find_table_column(attacker_strength, defender_strength, &crt.table.columns)?;
+    crt_column_odds(
+        crt,
+        apply_column_shift(base, shift, crt.table.columns.len()),
+    )
+}
+
+/// The context keys `resolution` reads its inputs from: the table's input
+/// keys, or the chain's input and roll keys no earlier step writes.
+#[must_use]
+pub fn resolution_input_keys(resolution: &InterruptResolution) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    let mut push = |key: &String| {
+        if !key.is_empty() && !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    };
+    match resolution {
+        InterruptResolution::Table {
+            input_a_key,
+            input_b_key,
+            ..
+        } => {
+            push(input_a_key);
+            push(input_b_key);
+        }
+        InterruptResolution::Chain { chain, .. } => {
+            let mut written: Vec<&String> = Vec::new();
+            for step in &chain.steps {
+                let roll_key = match &step.roll_source {
+                    ChainRollSource::ContextKey(key) => Some(key),
+                    ChainRollSource::Pool(_) | ChainRollSource::Fixed(_) => None,
+                };
+                for key in [Some(&step.input_a_key), Some(&step.input_b_key), roll_key]
+                    .into_iter()
+                    .flatten()
+                {
+                    if !written.contains(&key) {
+                        push(key);
+                    }
+                }
+                written.push(&step.output_key);
+            }
+        }
+    }
+    keys
+}
+
+/// Resolves `resolution` `runs` times against context `values`, rolling
+/// with `rng`, and returns how often each result came up. Rolls are logged
+/// as `context`.
+#[must_use]
+#[allow(clippy::implicit_hasher)]
+pub fn simulate_resolution(
+    resolution: &InterruptResolution,
+    values: &HashMap<String, f64>,
+    rng: &mut SimulationRng,
+    runs: u32,
+    context: &str,
+) -> Vec<OutcomeOdds> {
+    let mut outcomes = Vec::new();
+    if runs == 0 {
+        return outcomes;
+    }
+    let share = 1.0 / f64::from(runs);
+    for _ in 0..runs {
+        let (label, _) = resolution_label(resolution, values, rng, context);
+        add_odds(
+            &mut outcomes,
+            label.as_deref().unwrap_or(NO_RESULT_LABEL),
+            share,
+        );
+    }
+    outcomes
+}
+
+/// Quotes a CSV field when it holds a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Odds rows as CSV: one line per row, one column per result (in order of
+/// first appearance across rows), then the expected step losses.
+/// `name_header` heads the column of row labels.
+#[must_use]
+pub fn odds_csv(name_header: &str, rows: &[OddsRow]) -> String {
+    let mut labels: Vec<&str> = Vec::new();
+    for odds in rows.iter().flat_map(|row| &row.outcomes) {
+        if !labels.contains(&odds.label.as_str()) {
+            labels.push(&odds.label);
+        }
+    }
+    let mut header = vec![csv_field(name_header)];
+    header.extend(labels.iter().map(|label| csv_field(label)));
+    header.push("attacker_step_losses".to_string());
+    header.push("defender_step_losses".to_string());
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for row in rows {
+        let mut fields = vec![csv_field(&row.label)];
+        fields.extend(labels.iter().map(|label| {
+            let probability = row
+                .outcomes
+                .iter()
+                .find(|odds| odds.label == *label)
+                .map_or(0.0, |odds| odds.probability);
+            format!("{probability:.4}")
+        }));
+        fields.push(format!("{:.4}", row.losses.attacker));
+        fields.push(format!("{:.4}", row.losses.defender));
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+// ---------------------------------------------------------------------------
+// Play Log
+// ---------------------------------------------------------------------------
+
+/// One line of the play log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayLogEntry {
+    pub turn_number: u32,
+    pub message: String,
+}
+
+/// What happened in the current play session, oldest first. Undoing an
+/// action removes its entry. Runtime-only — not persisted.
+#[derive(Resource, Debug, Default)]
+pub struct PlayLog {
+    pub entries: Vec<PlayLogEntry>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1178,6 +2700,11 @@ mod tests {
                 ],
             ],
             combat_concept_id: None,
+            dice: DicePool::single(6),
+            strength: CombatStrengthModel::default(),
+            losses: LossRules::default(),
+            roles: CombatRoles::default(),
+            selection: CrtSelection::default(),
         }
     }
 
@@ -1193,6 +2720,36 @@ mod tests {
         assert_eq!(r.outcome.label, "DE");
     }
 
+    #[test]
+    fn combat_table_registry_falls_back_to_the_default_table() {
+        let default = test_crt();
+        let assault = CombatResultsTable {
+            name: "Assault".to_string(),
+            ..test_crt()
+        };
+        let mut registry = CombatTableRegistry {
+            tables: vec![assault.clone()],
+        };
+        assert_eq!(
+            registry.table_or(Some(assault.id), &default).name,
+            "Assault"
+        );
+        assert_eq!(registry.table_or(None, &default).id, default.id);
+        assert_eq!(
+            registry.table_or(Some(TypeId::new()), &default).id,
+            default.id
+        );
+
+        let mut edited = default.clone();
+        registry
+            .table_or_mut(Some(assault.id), &mut edited)
+            .dice
+            .count = 3;
+        assert_eq!(registry.tables[0].dice.count, 3);
+        registry.table_or_mut(None, &mut edited).dice.count = 4;
+        assert_eq!(edited.dice.count, 4);
+    }
+
     #[test]
     fn resolve_crt_no_column() {
         let crt = test_crt();
@@ -1811,6 +3368,138 @@ mod tests {
         assert!(zone.staged_entities.is_empty());
     }
 
+    #[test]
+    fn zone_unit_round_trips_entity_data() {
+        let prop_id = TypeId::new();
+        let data = EntityData {
+            entity_type_id: TypeId::new(),
+            properties: HashMap::from([(prop_id, PropertyValue::Int(3))]),
+        };
+        let unit = ZoneUnit::from(data.clone());
+        let back = EntityData::from(unit);
+        assert_eq!(back.entity_type_id, data.entity_type_id);
+        assert_eq!(back.properties.get(&prop_id), Some(&PropertyValue::Int(3)));
+    }
+
+    #[test]
+    fn off_map_zone_store_and_take() {
+        let zone = OffMapZone::new("Reinforcements");
+        let zone_id = zone.id;
+        let mut registry = OffMapZoneRegistry {
+            zones: vec![zone],
+            eliminated_zone_id: None,
+        };
+        let unit = ZoneUnit {
+            entity_type_id: TypeId::new(),
+            properties: HashMap::new(),
+            owner: None,
+            id: None,
+            state: None,
+        };
+        assert!(registry.store(zone_id, unit.clone()));
+        assert!(!registry.store(TypeId::new(), unit.clone()));
+        assert_eq!(registry.get(zone_id).map(|z| z.units.len()), Some(1));
+
+        assert!(registry.take(zone_id, 5).is_none());
+        assert_eq!(registry.take(zone_id, 0), Some(unit));
+        assert!(registry.get(zone_id).is_some_and(|z| z.units.is_empty()));
+    }
+
+    #[test]
+    fn off_map_zone_find_by_name() {
+        let registry = OffMapZoneRegistry {
+            zones: vec![OffMapZone::new("Eliminated")],
+            eliminated_zone_id: None,
+        };
+        assert!(registry.find_by_name("Eliminated").is_some());
+        assert!(registry.find_by_name("Missing").is_none());
+    }
+
+    #[test]
+    fn off_map_zone_deploy_phase_gate() {
+        let phase_id = TypeId::new();
+        let mut gated = OffMapZone::new("Reserve");
+        gated.deploy_phase_id = Some(phase_id);
+        let open = OffMapZone::new("Pool");
+        let (gated_id, open_id) = (gated.id, open.id);
+        let registry = OffMapZoneRegistry {
+            zones: vec![gated, open],
+            eliminated_zone_id: None,
+        };
+        assert!(registry.can_deploy(gated_id, Some(phase_id)));
+        assert!(!registry.can_deploy(gated_id, Some(TypeId::new())));
+        assert!(!registry.can_deploy(gated_id, None));
+        assert!(registry.can_deploy(open_id, None));
+        assert!(!registry.can_deploy(TypeId::new(), None));
+    }
+
+    #[test]
+    fn eliminated_sides_maps_outcomes() {
+        assert_eq!(
+            eliminated_sides(&OutcomeEffect::DefenderEliminated),
+            vec![CombatSide::Defender]
+        );
+        assert_eq!(
+            eliminated_sides(&OutcomeEffect::AttackerEliminated),
+            vec![CombatSide::Attacker]
+        );
+        assert!(eliminated_sides(&OutcomeEffect::StepLoss { steps: 1 }).is_empty());
+    }
+
+    #[test]
+    fn outcome_state_triggers_fire_per_step() {
+        assert!(outcome_state_triggers(&OutcomeEffect::NoEffect).is_empty());
+        assert_eq!(
+            outcome_state_triggers(&OutcomeEffect::StepLoss { steps: 2 }),
+            vec![
+                (CombatSide::Defender, StateTrigger::StepLoss),
+                (CombatSide::Defender, StateTrigger::StepLoss),
+            ]
+        );
+        assert_eq!(
+            outcome_state_triggers(&OutcomeEffect::Exchange {
+                attacker_steps: 1,
+                defender_steps: 1,
+            }),
+            vec![
+                (CombatSide::Attacker, StateTrigger::StepLoss),
+                (CombatSide::Defender, StateTrigger::StepLoss),
+            ]
+        );
+        assert_eq!(
+            outcome_state_triggers(&OutcomeEffect::Retreat { hexes: 2 }),
+            vec![(CombatSide::Defender, StateTrigger::Retreat)]
+        );
+        assert_eq!(
+            outcome_state_triggers(&OutcomeEffect::AttackerEliminated),
+            vec![(CombatSide::Attacker, StateTrigger::Eliminated)]
+        );
+    }
+
+    #[test]
+    fn off_map_zone_registry_ron_round_trip() {
+        let mut zone = OffMapZone::new("Eliminated");
+        zone.units.push(ZoneUnit {
+            entity_type_id: TypeId::new(),
+            properties: HashMap::new(),
+            owner: None,
+            id: None,
+            state: None,
+        });
+        let registry = OffMapZoneRegistry {
+            eliminated_zone_id: Some(zone.id),
+            zones: vec![zone],
+        };
+        let ron_str = ron::to_string(&registry).expect("serialize");
+        let deserialized: OffMapZoneRegistry = ron::from_str(&ron_str).expect("deserialize");
+        assert_eq!(deserialized.zones.len(), 1);
+        assert_eq!(deserialized.zones[0].units.len(), 1);
+        assert_eq!(
+            deserialized.eliminated_zone_id,
+            Some(deserialized.zones[0].id)
+        );
+    }
+
     #[test]
     fn spawn_result_variants_debug() {
         let results = [
@@ -1866,7 +3555,7 @@ mod tests {
 
     use std::collections::{HashMap, HashSet};
 
-    fn retreat_context() -> PathfindingContext {
+    fn retreat_context() -> PathfindingContext<'static> {
         // A small grid: attacker at (0,0), defender at (1,0).
         // Valid positions form a line away from attacker: (2,0), (3,0).
         let mut valid = HashSet::new();
@@ -1877,6 +3566,7 @@ mod tests {
             valid_positions: valid,
             influence_zones: HashMap::new(),
             terrain_types: HashMap::new(),
+            grid_config: None,
         }
     }
 
@@ -1983,6 +3673,7 @@ mod tests {
             valid_positions: HashSet::new(),
             influence_zones: HashMap::new(),
             terrain_types: HashMap::new(),
+            grid_config: None,
         };
 
         let request = ConstrainedPathRequest {
@@ -2011,6 +3702,45 @@ mod tests {
         assert!(result.path.is_some());
     }
 
+    #[test]
+    fn constrained_paths_list_every_equally_good_retreat() {
+        // Three neighbours of (0,0) are two hexes from the attacker at (-1,0).
+        let start = HexPosition::new(0, 0);
+        let ctx = PathfindingContext {
+            valid_positions: std::iter::once(start.to_hex())
+                .chain(start.to_hex().all_neighbors())
+                .map(|hex| HexPosition::new(hex.x, hex.y))
+                .collect(),
+            influence_zones: HashMap::new(),
+            terrain_types: HashMap::new(),
+            grid_config: None,
+        };
+        let request = ConstrainedPathRequest {
+            start,
+            constraints: vec![PathConstraint::AwayFrom {
+                source: HexPosition::new(-1, 0),
+            }],
+            max_distance: 1,
+        };
+
+        let paths = find_constrained_paths(&request, &ctx);
+        let mut ends: Vec<_> = paths.iter().filter_map(|p| p.last().copied()).collect();
+        ends.sort_by_key(|p| (p.q, p.r));
+        assert_eq!(
+            ends,
+            [
+                HexPosition::new(0, 1),
+                HexPosition::new(1, -1),
+                HexPosition::new(1, 0),
+            ]
+        );
+        assert!(paths.iter().all(|p| p.len() == 2));
+        assert_eq!(
+            find_constrained_path(&request, &ctx).path.as_ref(),
+            paths.first()
+        );
+    }
+
     #[test]
     fn constrained_path_zero_distance_fails() {
         let ctx = retreat_context();
@@ -2061,6 +3791,7 @@ mod tests {
                     ],
                     value: 0,
                     history: Vec::new(),
+                    faction_id: None,
                 },
                 Accumulator {
                     id: "vp_blue".to_string(),
@@ -2068,6 +3799,7 @@ mod tests {
                     triggers: vec![AccumulationTrigger::TurnBoundary { points: 1 }],
                     value: 0,
                     history: Vec::new(),
+                    faction_id: None,
                 },
             ],
         }
@@ -2109,6 +3841,91 @@ mod tests {
         assert_eq!(registry.accumulators[0].value, 0);
     }
 
+    fn red_blue_factions() -> FactionRegistry {
+        let faction = |name: &str, player_order| crate::game_system::Faction {
+            id: TypeId::new(),
+            name: name.to_string(),
+            color: Color::WHITE,
+            player_order,
+        };
+        FactionRegistry {
+            factions: vec![faction("Red", 0), faction("Blue", 1)],
+        }
+    }
+
+    #[test]
+    fn occupation_triggers_score_for_the_occupying_faction() {
+        let factions = red_blue_factions();
+        let red = UnitOwner {
+            faction_id: Some(factions.factions[0].id),
+        };
+        let blue = UnitOwner {
+            faction_id: Some(factions.factions[1].id),
+        };
+        let mut registry = test_accumulator_registry();
+        registry.accumulators[1]
+            .triggers
+            .push(AccumulationTrigger::OccupyHex {
+                hex: HexPosition::new(3, 0),
+                points: 4,
+            });
+
+        let occupants = [(HexPosition::new(3, 0), blue)];
+        let modified = evaluate_occupation_triggers(&mut registry, &factions, &occupants, 1);
+        assert_eq!(modified, vec![1], "legacy name resolves to the faction");
+        assert_eq!(registry.accumulators[0].value, 0);
+        assert_eq!(registry.accumulators[1].value, 4);
+
+        // Two Red units on the hex still score the trigger once.
+        let occupants = [(HexPosition::new(3, 0), red), (HexPosition::new(3, 0), red)];
+        let modified = evaluate_occupation_triggers(&mut registry, &factions, &occupants, 2);
+        assert_eq!(modified, vec![0]);
+        assert_eq!(registry.accumulators[0].value, 5);
+    }
+
+    #[test]
+    fn occupation_triggers_ignore_unowned_units() {
+        let factions = red_blue_factions();
+        let mut registry = test_accumulator_registry();
+        registry.accumulators[0].faction = None;
+        let occupants = [(HexPosition::new(3, 0), UnitOwner::default())];
+        let modified = evaluate_occupation_triggers(&mut registry, &factions, &occupants, 1);
+        assert!(modified.is_empty());
+    }
+
+    #[test]
+    fn winning_faction_prefers_faction_id() {
+        let factions = red_blue_factions();
+        let mut registry = test_accumulator_registry();
+        let condition = VictoryCondition {
+            accumulator_id: "vp_red".to_string(),
+            threshold: 10,
+            comparison: ComparisonOp::GreaterOrEqual,
+        };
+        assert_eq!(
+            winning_faction(&condition, &registry, &factions),
+            Some(factions.factions[0].id)
+        );
+        registry.accumulators[0].faction_id = Some(factions.factions[1].id);
+        assert_eq!(
+            winning_faction(&condition, &registry, &factions),
+            Some(factions.factions[1].id)
+        );
+    }
+
+    #[test]
+    fn zone_unit_keeps_owner() {
+        let owner = UnitOwner {
+            faction_id: Some(TypeId::new()),
+        };
+        let data = EntityData {
+            entity_type_id: TypeId::new(),
+            properties: HashMap::new(),
+        };
+        let unit = ZoneUnit::with_owner(data, owner);
+        assert_eq!(unit.owner, owner.faction_id);
+    }
+
     #[test]
     fn occupy_hex_trigger_wrong_hex_no_effect() {
         let mut registry = test_accumulator_registry();
@@ -2184,6 +4001,7 @@ mod tests {
                 triggers: vec![AccumulationTrigger::Manual],
                 value: 5,
                 history: Vec::new(),
+                faction_id: None,
             }],
         };
         let modified = evaluate_turn_boundary_triggers(&mut registry, 1);
@@ -2254,6 +4072,7 @@ mod tests {
                     }],
                     value: 0,
                     history: Vec::new(),
+                    faction_id: None,
                 },
                 Accumulator {
                     id: "vp_blue".to_string(),
@@ -2264,6 +4083,7 @@ mod tests {
                     }],
                     value: 0,
                     history: Vec::new(),
+                    faction_id: None,
                 },
             ],
         };
@@ -2323,6 +4143,7 @@ mod tests {
             valid_positions: valid,
             influence_zones: HashMap::new(),
             terrain_types: HashMap::new(),
+            grid_config: None,
         };
         let retreat_request = ConstrainedPathRequest {
             start: HexPosition::new(2, 0),
@@ -2406,6 +4227,7 @@ mod tests {
             valid_positions: valid,
             influence_zones: [("ZOC".to_string(), zoc)].into_iter().collect(),
             terrain_types: HashMap::new(),
+            grid_config: None,
         };
 
         let request = ConstrainedPathRequest {
@@ -2446,4 +4268,643 @@ mod tests {
         assert_eq!(entries_due_for_turn(&schedule, 3), vec![0]);
         assert!(entries_due_for_turn(&schedule, 4).is_empty());
     }
+
+    // --- Movement interrupt tests ---
+
+    /// A one-column table: rolls of 1-3 give "Halt", 4-6 give "Pass".
+    fn fire_table() -> ResolutionTable {
+        ResolutionTable {
+            id: TypeId::new(),
+            name: "Opportunity Fire".to_string(),
+            columns: vec![TableColumn {
+                label: "Any".to_string(),
+                column_type: ColumnType::Direct,
+                threshold: 0.0,
+            }],
+            rows: vec![
+                TableRow {
+                    label: "1-3".to_string(),
+                    value_min: 1,
+                    value_max: 3,
+                },
+                TableRow {
+                    label: "4-6".to_string(),
+                    value_min: 4,
+                    value_max: 6,
+                },
+            ],
+            outcomes: vec![
+                vec![TableResult::Text("Halt".to_string())],
+                vec![TableResult::Text("Pass".to_string())],
+            ],
+        }
+    }
+
+    fn fire_rule(resolution: InterruptResolution, disrupted: TypeId) -> MovementInterruptRule {
+        MovementInterruptRule {
+            id: TypeId::new(),
+            name: "Opportunity Fire".to_string(),
+            trigger: InterruptTrigger::Proximity { max: 1 },
+            reactor_type: None,
+            mover_type: None,
+            resolution,
+            outcomes: vec![InterruptOutcome {
+                result: "Halt".to_string(),
+                end_movement: true,
+                set_state: Some(disrupted),
+            }],
+        }
+    }
+
+    #[test]
+    fn interrupt_outcome_follows_table_result() {
+        let disrupted = TypeId::new();
+        // A 1d1 roll always lands on the "Halt" row; 1d1+3 always on "Pass".
+        let halt = fire_rule(
+            InterruptResolution::Table {
+                table: fire_table(),
+                input_a_key: "reactor.strength".to_string(),
+                input_b_key: "mover.strength".to_string(),
+                dice: DicePool::single(1),
+            },
+            disrupted,
+        );
+        let mut rng = SimulationRng::new(7);
+        let mut result = resolve_interrupt(&halt, &HashMap::new(), &mut rng);
+        let rolls = std::mem::take(&mut result.rolls);
+        assert_eq!(
+            result,
+            InterruptResult {
+                result: Some("Halt".to_string()),
+                end_movement: true,
+                set_state: Some(disrupted),
+                rolls: Vec::new(),
+            }
+        );
+        // The roll is reported with the table it was looked up in.
+        assert_eq!(rolls.len(), 1);
+        assert_eq!(rolls[0].context, halt.name);
+        assert_eq!(rolls[0].roll.total, 1);
+        let InterruptResolution::Table { table, .. } = &halt.resolution else {
+            unreachable!();
+        };
+        let (table_id, resolution) = rolls[0].table.as_ref().expect("table lookup");
+        assert_eq!(*table_id, table.id);
+        assert_eq!(resolution.row_label, "1-3");
+
+        let pass = fire_rule(
+            InterruptResolution::Table {
+                table: fire_table(),
+                input_a_key: String::new(),
+                input_b_key: String::new(),
+                dice: DicePool::new(1, 1, 3),
+            },
+            disrupted,
+        );
+        let result = resolve_interrupt(&pass, &HashMap::new(), &mut rng);
+        assert_eq!(result.result.as_deref(), Some("Pass"));
+        assert!(!result.end_movement, "unmapped results continue the move");
+        assert_eq!(result.set_state, None);
+    }
+
+    #[test]
+    fn interrupt_chain_decides_by_last_step() {
+        let table = fire_table();
+        let chain = ResolutionChain {
+            steps: vec![crate::simulation::ChainStep {
+                table_id: table.id,
+                input_a_key: "distance".to_string(),
+                input_b_key: String::new(),
+                roll_source: crate::simulation::ChainRollSource::ContextKey("distance".to_string()),
+                output_key: "fire".to_string(),
+            }],
+            ..ResolutionChain::default()
+        };
+        let rule = fire_rule(
+            InterruptResolution::Chain {
+                chain,
+                tables: vec![table],
+            },
+            TypeId::new(),
+        );
+        let mut rng = SimulationRng::new(7);
+        let close = HashMap::from([("distance".to_string(), 2.0)]);
+        assert!(resolve_interrupt(&rule, &close, &mut rng).end_movement);
+        let far = HashMap::from([("distance".to_string(), 5.0)]);
+        assert!(!resolve_interrupt(&rule, &far, &mut rng).end_movement);
+        let off_table = HashMap::from([("distance".to_string(), 9.0)]);
+        assert_eq!(
+            resolve_interrupt(&rule, &off_table, &mut rng),
+            InterruptResult::default()
+        );
+    }
+
+    #[test]
+    fn numeric_results_match_outcomes_by_number() {
+        assert_eq!(interrupt_result_label(&TableResult::NumericValue(2.0)), "2");
+        assert_eq!(
+            interrupt_result_label(&TableResult::NumericValue(1.5)),
+            "1.5"
+        );
+    }
+
+    // --- Overrun tests ---
+
+    #[test]
+    fn overrun_outcome_follows_table_result() {
+        let shaken = TypeId::new();
+        let overrun = |dice: DicePool| OverrunRule {
+            id: TypeId::new(),
+            name: "Overrun".to_string(),
+            mp_cost: 2,
+            attacker_type: None,
+            defender_type: None,
+            resolution: InterruptResolution::Table {
+                table: fire_table(),
+                input_a_key: "attacker.strength".to_string(),
+                input_b_key: "defender.strength".to_string(),
+                dice,
+            },
+            outcomes: vec![
+                OverrunOutcome {
+                    result: "Pass".to_string(),
+                    clears_defender: true,
+                    attacker_state: None,
+                    defender_state: None,
+                },
+                OverrunOutcome {
+                    result: "Halt".to_string(),
+                    clears_defender: false,
+                    attacker_state: Some(shaken),
+                    defender_state: None,
+                },
+            ],
+        };
+        let mut rng = SimulationRng::new(7);
+        // A 1d1 roll always lands on the "Halt" row; 1d1+3 always on "Pass".
+        let held = resolve_overrun(&overrun(DicePool::single(1)), &HashMap::new(), &mut rng);
+        assert_eq!(held.result.as_deref(), Some("Halt"));
+        assert!(!held.clears_defender);
+        assert_eq!(held.attacker_state, Some(shaken));
+
+        let cleared = resolve_overrun(&overrun(DicePool::new(1, 1, 3)), &HashMap::new(), &mut rng);
+        assert!(cleared.clears_defender);
+        assert_eq!(cleared.attacker_state, None);
+    }
+
+    // --- Odds analysis ---
+
+    #[test]
+    fn crt_column_odds_are_exact_for_the_dice() {
+        let crt = test_crt();
+        let odds = crt_column_odds(&crt, 2).expect("column exists");
+        assert_eq!(odds.label, "2:1");
+        let labels: Vec<&str> = odds.outcomes.iter().map(|o| o.label.as_str()).collect();
+        assert_eq!(labels, vec!["DR", "DE", "ASL"]);
+        assert!(
+            odds.outcomes
+                .iter()
+                .all(|o| (o.probability - 1.0 / 3.0).abs() < 1e-12)
+        );
+        // Only "ASL" loses a step; elimination is not counted in steps.
+        assert!((odds.losses.attacker - 1.0 / 3.0).abs() < 1e-12);
+        assert!(odds.losses.defender.abs() < 1e-12);
+
+        let exchange = crt_column_odds(&crt, 0).expect("column exists");
+        assert!((exchange.losses.attacker - 1.0 / 3.0).abs() < 1e-12);
+        assert!((exchange.losses.defender - 1.0 / 3.0).abs() < 1e-12);
+        assert!(crt_column_odds(&crt, 3).is_none());
+        assert_eq!(crt_odds(&crt).len(), 3);
+    }
+
+    #[test]
+    fn crt_column_odds_count_rolls_off_the_table() {
+        let mut crt = test_crt();
+        crt.dice = DicePool::single(8);
+        let odds = crt_column_odds(&crt, 1).expect("column exists");
+        let off_table = odds
+            .outcomes
+            .iter()
+            .find(|o| o.label == NO_RESULT_LABEL)
+            .expect("7 and 8 match no row");
+        assert!((off_table.probability - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn crt_column_odds_match_the_resolver_for_negative_totals() {
+        let mut crt = test_crt();
+        crt.dice = DicePool::new(1, 6, -3);
+        crt.table.rows[0].value_min = 0;
+        let odds = crt_column_odds(&crt, 2).expect("column exists");
+
+        let mut expected: Vec<OutcomeOdds> = Vec::new();
+        for (total, probability) in pool_distribution(crt.dice) {
+            let label = resolve_crt(&crt, 6.0, 2.0, table_roll(total))
+                .map_or(NO_RESULT_LABEL.to_string(), |r| r.outcome.label);
+            add_odds(&mut expected, &label, probability);
+        }
+        assert_eq!(odds.outcomes, expected);
+        assert!(odds.outcomes.iter().all(|o| o.label != NO_RESULT_LABEL));
+    }
+
+    #[test]
+    fn crt_attack_odds_apply_the_column_shift() {
+        let crt = test_crt();
+        let column_modifiers = CombatModifierRegistry {
+            modifiers: vec![CombatModifierDefinition {
+                id: TypeId::new(),
+                name: "Clear".to_string(),
+                source: ModifierSource::DefenderTerrain,
+                column_shift: 1,
+                priority: 0,
+                cap: None,
+                terrain_type_filter: None,
+                condition: None,
+            }],
+        }
+        .column_modifiers();
+        let (shift, _) = crate::simulation::evaluate_column_modifiers(&column_modifiers, 3);
+        let odds = crt_attack_odds(&crt, 2.0, 2.0, shift).expect("1:1 column");
+        assert_eq!(odds.label, "2:1");
+        assert!(crt_attack_odds(&crt, 1.0, 10.0, 0).is_none());
+    }
+
+    #[test]
+    fn simulated_resolution_shares_sum_to_one() {
+        let resolution = InterruptResolution::Table {
+            table: fire_table(),
+            input_a_key: String::new(),
+            input_b_key: String::new(),
+            dice: DicePool::single(6),
+        };
+        let mut rng = SimulationRng::new(3);
+        let odds = simulate_resolution(&resolution, &HashMap::new(), &mut rng, 600, "analysis");
+        assert_eq!(rng.roll_count(), 600);
+        let total: f64 = odds.iter().map(|o| o.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        let halt = odds
+            .iter()
+            .find(|o| o.label == "Halt")
+            .expect("Halt comes up");
+        assert!((halt.probability - 0.5).abs() < 0.1);
+        assert!(simulate_resolution(&resolution, &HashMap::new(), &mut rng, 0, "x").is_empty());
+    }
+
+    #[test]
+    fn expected_losses_read_effects_by_label() {
+        let crt = test_crt();
+        let odds = vec![
+            OutcomeOdds {
+                label: "EX".to_string(),
+                probability: 0.5,
+            },
+            OutcomeOdds {
+                label: "Halt".to_string(),
+                probability: 0.5,
+            },
+        ];
+        let losses = expected_losses(&odds, &crt);
+        assert!((losses.attacker - 0.5).abs() < 1e-12);
+        assert!((losses.defender - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn resolution_input_keys_skip_chain_outputs() {
+        let chain = ResolutionChain {
+            steps: vec![
+                crate::simulation::ChainStep {
+                    table_id: TypeId::new(),
+                    input_a_key: "attacker.strength".to_string(),
+                    input_b_key: "defender.strength".to_string(),
+                    roll_source: crate::simulation::ChainRollSource::Pool(DicePool::single(6)),
+                    output_key: "hits".to_string(),
+                },
+                crate::simulation::ChainStep {
+                    table_id: TypeId::new(),
+                    input_a_key: "hits".to_string(),
+                    input_b_key: String::new(),
+                    roll_source: crate::simulation::ChainRollSource::ContextKey(
+                        "distance".to_string(),
+                    ),
+                    output_key: "morale".to_string(),
+                },
+            ],
+            ..ResolutionChain::default()
+        };
+        let keys = resolution_input_keys(&InterruptResolution::Chain {
+            chain,
+            tables: Vec::new(),
+        });
+        assert_eq!(
+            keys,
+            vec!["attacker.strength", "defender.strength", "distance"]
+        );
+    }
+
+    #[test]
+    fn odds_csv_has_a_column_per_result() {
+        let rows = vec![
+            OddsRow {
+                label: "1:1".to_string(),
+                outcomes: vec![OutcomeOdds {
+                    label: "NE".to_string(),
+                    probability: 1.0,
+                }],
+                losses: ExpectedLosses::default(),
+            },
+            OddsRow {
+                label: "2,1".to_string(),
+                outcomes: vec![
+                    OutcomeOdds {
+                        label: "NE".to_string(),
+                        probability: 0.5,
+                    },
+                    OutcomeOdds {
+                        label: "EX".to_string(),
+                        probability: 0.5,
+                    },
+                ],
+                losses: ExpectedLosses {
+                    attacker: 0.5,
+                    defender: 0.5,
+                },
+            },
+        ];
+        let csv = odds_csv("column", &rows);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "column,NE,EX,attacker_step_losses,defender_step_losses",
+                "1:1,1.0000,0.0000,0.0000,0.0000",
+                "\"2,1\",0.5000,0.5000,0.5000,0.5000",
+            ]
+        );
+    }
+
+    fn strengths(values: &[(&str, f64)]) -> Vec<CombatStrength> {
+        values
+            .iter()
+            .map(|(label, strength)| CombatStrength {
+                label: (*label).to_string(),
+                strength: *strength,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn concept_property_value_reads_bound_property() {
+        use crate::ontology::{ConceptBinding, PropertyBinding};
+
+        let concept_id = TypeId::new();
+        let type_id = TypeId::new();
+        let prop_id = TypeId::new();
+        let concepts = ConceptRegistry {
+            concepts: Vec::new(),
+            bindings: vec![ConceptBinding {
+                id: TypeId::new(),
+                entity_type_id: type_id,
+                concept_id,
+                concept_role_id: TypeId::new(),
+                property_bindings: vec![PropertyBinding {
+                    property_id: prop_id,
+                    concept_local_name: "attack".to_string(),
+                }],
+            }],
+        };
+        let data = EntityData {
+            entity_type_id: type_id,
+            properties: HashMap::from([(prop_id, PropertyValue::Int(4))]),
+        };
+
+        assert_eq!(
+            concept_property_value(&concepts, concept_id, &data, "attack"),
+            Some(4.0)
+        );
+        assert_eq!(
+            concept_property_value(&concepts, concept_id, &data, "defense"),
+            None
+        );
+        assert_eq!(
+            concept_property_value(&concepts, TypeId::new(), &data, "attack"),
+            None
+        );
+        assert_eq!(
+            concept_property_id(&concepts, concept_id, type_id, "attack"),
+            Some(prop_id)
+        );
+    }
+
+    #[test]
+    fn combat_odds_sum_stacks_and_multiply_terrain() {
+        let fort = TypeId::new();
+        let model = CombatStrengthModel {
+            terrain_multipliers: vec![TerrainMultiplier {
+                terrain_type_id: fort,
+                multiplier: 2.0,
+            }],
+            ..CombatStrengthModel::default()
+        };
+        let odds = compute_combat_odds(
+            &model,
+            ColumnType::Ratio,
+            &strengths(&[("Infantry", 4.0), ("Armor", 8.0)]),
+            &strengths(&[("Infantry", 3.0)]),
+            Some(fort),
+        );
+
+        assert_eq!(odds.attacker_strength, 12.0);
+        assert_eq!(odds.defender_strength, 6.0);
+        assert_eq!(odds.raw_value, 2.0);
+        let labels: Vec<&str> = odds.steps.iter().map(|(l, _)| l.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Attack (Infantry 4 + Armor 8)",
+                "Defense (Infantry 3)",
+                "Terrain \u{00d7}2",
+                "Ratio 12:6",
+            ]
+        );
+    }
+
+    #[test]
+    fn combat_odds_round_in_the_defenders_favor() {
+        let model = CombatStrengthModel {
+            terrain_multipliers: vec![TerrainMultiplier {
+                terrain_type_id: TypeId::new(),
+                multiplier: 1.5,
+            }],
+            ..CombatStrengthModel::default()
+        };
+        let terrain = Some(model.terrain_multipliers[0].terrain_type_id);
+        let odds = compute_combat_odds(
+            &model,
+            ColumnType::Ratio,
+            &strengths(&[("Infantry", 7.0)]),
+            &strengths(&[("Infantry", 3.0)]),
+            terrain,
+        );
+        // 3 x 1.5 = 4.5 rounds up to 5; 7:5 rounds down to 1:1.
+        assert_eq!(odds.defender_strength, 5.0);
+        assert_eq!(odds.raw_value, 1.0);
+
+        let odds = compute_combat_odds(
+            &model,
+            ColumnType::Ratio,
+            &strengths(&[("Infantry", 2.0)]),
+            &strengths(&[("Infantry", 5.0)]),
+            None,
+        );
+        // 2:5 rounds to 1:3.
+        assert!((odds.raw_value - 1.0 / 3.0).abs() < 1e-9);
+
+        let attacker_favor = CombatStrengthModel {
+            rounding: StrengthRounding::AttackerFavor,
+            ..model
+        };
+        let odds = compute_combat_odds(
+            &attacker_favor,
+            ColumnType::Ratio,
+            &strengths(&[("Infantry", 2.0)]),
+            &strengths(&[("Infantry", 5.0)]),
+            None,
+        );
+        assert_eq!(odds.raw_value, 0.5);
+    }
+
+    #[test]
+    fn combat_odds_differential_subtracts_strengths() {
+        let odds = compute_combat_odds(
+            &CombatStrengthModel::default(),
+            ColumnType::Differential,
+            &strengths(&[("Infantry", 3.0), ("Infantry", 2.0)]),
+            &strengths(&[("Militia", 1.0)]),
+            None,
+        );
+        assert_eq!(odds.raw_value, 4.0);
+    }
+
+    #[test]
+    fn odds_column_picks_the_rightmost_met_threshold() {
+        let crt = test_crt();
+        let odds = |raw_value| CombatOdds {
+            attacker_strength: 0.0,
+            defender_strength: 0.0,
+            raw_value,
+            steps: Vec::new(),
+        };
+        assert_eq!(odds_column(&crt.table.columns, &odds(0.1)), None);
+        assert_eq!(odds_column(&crt.table.columns, &odds(0.6)), Some(0));
+        assert_eq!(odds_column(&crt.table.columns, &odds(1.0)), Some(1));
+        assert_eq!(odds_column(&crt.table.columns, &odds(99.0)), Some(2));
+    }
+
+    fn loss_candidate(
+        entity: Entity,
+        side: CombatSide,
+        priority: f64,
+        steps: u32,
+    ) -> LossCandidate {
+        LossCandidate {
+            entity,
+            side,
+            priority,
+            steps,
+        }
+    }
+
+    #[test]
+    fn step_losses_fill_listed_units_in_order() {
+        let (a, b) = (Entity::from_bits(1), Entity::from_bits(2));
+        let candidates = [
+            loss_candidate(a, CombatSide::Defender, 1.0, 1),
+            loss_candidate(b, CombatSide::Defender, 9.0, 2),
+        ];
+        let losses = allocate_step_losses(
+            &OutcomeEffect::StepLoss { steps: 5 },
+            &candidates,
+            &LossAllocation::AttackerChoice,
+        );
+        // Losses beyond the side's three steps are dropped.
+        assert_eq!(losses, vec![(a, 1), (b, 2)]);
+    }
+
+    #[test]
+    fn step_losses_take_the_highest_priority_first() {
+        let (a, b, c) = (
+            Entity::from_bits(1),
+            Entity::from_bits(2),
+            Entity::from_bits(3),
+        );
+        let candidates = [
+            loss_candidate(a, CombatSide::Defender, 2.0, 1),
+            loss_candidate(b, CombatSide::Defender, 5.0, 1),
+            loss_candidate(c, CombatSide::Defender, 2.0, 1),
+        ];
+        let losses = allocate_step_losses(
+            &OutcomeEffect::StepLoss { steps: 2 },
+            &candidates,
+            &LossAllocation::LargestFirst,
+        );
+        // Ties keep their listed order.
+        assert_eq!(losses, vec![(b, 1), (a, 1)]);
+    }
+
+    #[test]
+    fn exchange_allocates_losses_to_both_sides() {
+        let (a, d) = (Entity::from_bits(1), Entity::from_bits(2));
+        let candidates = [
+            loss_candidate(a, CombatSide::Attacker, 0.0, 3),
+            loss_candidate(d, CombatSide::Defender, 0.0, 3),
+        ];
+        let losses = allocate_step_losses(
+            &OutcomeEffect::Exchange {
+                attacker_steps: 1,
+                defender_steps: 2,
+            },
+            &candidates,
+            &LossAllocation::ByProperty("quality".to_string()),
+        );
+        assert_eq!(losses, vec![(a, 1), (d, 2)]);
+    }
+
+    #[test]
+    fn active_combat_keeps_the_first_participants_primary() {
+        let (a, b, d) = (
+            Entity::from_bits(1),
+            Entity::from_bits(2),
+            Entity::from_bits(3),
+        );
+        let mut combat = ActiveCombat::default();
+        combat.add_attacker(a);
+        combat.add_attacker(b);
+        combat.add_defender(d);
+        combat.add_defender(a);
+        assert_eq!(combat.attackers, vec![a, b]);
+        assert_eq!(combat.defenders, vec![d]);
+        assert_eq!((combat.attacker, combat.defender), (Some(a), Some(d)));
+
+        combat.move_earlier(b);
+        assert_eq!(combat.attacker, Some(b));
+        combat.remove_participant(b);
+        assert_eq!(combat.attackers, vec![a]);
+        assert_eq!(combat.attacker, Some(a));
+    }
+
+    #[test]
+    fn active_combat_modifier_applies_unless_its_result_failed() {
+        let (river, supply) = (TypeId::new(), TypeId::new());
+        let combat = ActiveCombat {
+            modifier_results: vec![ValidationResult {
+                constraint_id: river,
+                constraint_name: "River".to_string(),
+                satisfied: false,
+                explanation: "crossing is not present".to_string(),
+            }],
+            ..ActiveCombat::default()
+        };
+        assert!(!combat.modifier_applies(river));
+        assert!(combat.modifier_applies(supply));
+    }
 }
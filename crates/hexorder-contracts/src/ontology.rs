@@ -10,7 +10,7 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::game_system::{EntityRole, PropertyValue, TypeId};
+use crate::game_system::{EntityData, EntityRole, PropertyValue, TypeId};
 
 // ---------------------------------------------------------------------------
 // Concepts
@@ -144,9 +144,51 @@ pub enum CompareOp {
     Ge,
 }
 
+/// How proximity to the nearest matching unit is measured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum ProximityMeasure {
+    /// Hex distance.
+    #[default]
+    Distance,
+    /// Movement cost of the cheapest path for the unit in the scope.
+    PathCost,
+}
+
+/// Which side the units counted by a proximity check must be on, relative
+/// to the unit in the scope.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum ProximityFaction {
+    #[default]
+    Any,
+    /// Unowned or not opposed to the unit.
+    Friendly,
+    /// Opposed to the unit.
+    Enemy,
+}
+
+/// The units a proximity check looks for.
+#[derive(Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProximityFilter {
+    /// Only units of this type; `None` matches any unit type.
+    pub entity_type_id: Option<TypeId>,
+    pub faction: ProximityFaction,
+    /// Name of a property that must hold the same value on both units,
+    /// e.g. `formation`.
+    pub same_property: Option<String>,
+}
+
+/// Actions a constraint can gate: a unit may only take them while the
+/// constraint holds for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum GatedAction {
+    Move,
+    Attack,
+}
+
 /// A structured constraint expression. Deliberately limited for 0.4.0:
 /// property comparisons, cross-entity comparisons, path budgets,
-/// type checks, and boolean logic. Not a full DSL.
+/// type, state and proximity checks, and boolean logic. Not a full DSL.
 #[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
 #[reflect(opaque)]
 pub enum ConstraintExpr {
@@ -189,6 +231,24 @@ pub enum ConstraintExpr {
         budget_property: String,
         budget_role_id: TypeId,
     },
+    /// Check if an entity is in a state of its entity type's state machine.
+    /// E.g., unit is Reduced
+    InState { role_id: TypeId, state_id: TypeId },
+    /// Check if a unit was in reach at the latest trace of a reachability
+    /// rule. E.g., unit is in supply
+    InReach { role_id: TypeId, rule_id: TypeId },
+    /// Check if a unit matching `filter` is within `max` of the entity
+    /// filling the role. E.g., unit is within 3 hexes of its own HQ
+    Proximity {
+        role_id: TypeId,
+        filter: ProximityFilter,
+        measure: ProximityMeasure,
+        max: i64,
+        /// When set, the nearest match must also be in line of sight, which
+        /// tiles of the listed terrain types block.
+        #[serde(default)]
+        line_of_sight: Option<Vec<TypeId>>,
+    },
     /// All sub-expressions must be true.
     All(Vec<ConstraintExpr>),
     /// At least one sub-expression must be true.
@@ -212,6 +272,59 @@ pub struct Constraint {
     pub expression: ConstraintExpr,
     /// Whether this constraint was auto-generated (shown with "[auto]" badge in UI).
     pub auto_generated: bool,
+    /// Actions a unit may only take while this constraint holds for it.
+    #[serde(default)]
+    pub gates: Vec<GatedAction>,
+}
+
+// ---------------------------------------------------------------------------
+// Presence Effects
+// ---------------------------------------------------------------------------
+
+/// One `WhilePresent` relation effect currently applied to a property.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct AppliedEffect {
+    /// The `WhilePresent` relation that produced the effect.
+    pub relation_id: TypeId,
+    /// The tile or co-located unit providing the source property.
+    pub source: Entity,
+    /// Property on the subject that was modified.
+    pub property_id: TypeId,
+    /// Value before the effect was applied.
+    pub before: PropertyValue,
+    /// Value the effect produced.
+    pub after: PropertyValue,
+}
+
+/// Continuous `WhilePresent` effects applied to a unit's `EntityData`.
+///
+/// Effects are rebuilt whenever units move or the ontology changes: the
+/// previous effects are reverted newest-first, then every relation that still
+/// holds is applied again from the base values. Reverting restores `before`
+/// only if the property still holds `after`; if something else changed the
+/// property while the effect was active, that value becomes the new base.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+pub struct PresenceEffects {
+    pub applied: Vec<AppliedEffect>,
+}
+
+impl PresenceEffects {
+    /// Reverts every applied effect on `data`, newest first, and clears the list.
+    pub fn revert(&mut self, data: &mut EntityData) {
+        for effect in self.applied.drain(..).rev() {
+            if data.properties.get(&effect.property_id) == Some(&effect.after) {
+                data.properties.insert(effect.property_id, effect.before);
+            }
+        }
+    }
+
+    /// `data` with every applied effect reverted: the base values to persist.
+    #[must_use]
+    pub fn base_data(&self, data: &EntityData) -> EntityData {
+        let mut base = data.clone();
+        self.clone().revert(&mut base);
+        base
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -240,7 +353,7 @@ pub struct ConstraintRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::game_system::{EntityRole, PropertyValue, TypeId};
+    use crate::game_system::{EntityData, EntityRole, PropertyValue, TypeId};
 
     /// Round-trip: serialize `ConceptRegistry` with bindings.
     #[test]
@@ -360,6 +473,30 @@ mod tests {
         assert!(matches!(deserialized, ConstraintExpr::PathBudget { .. }));
     }
 
+    #[test]
+    fn constraint_expr_proximity_defaults_filter_and_sight() {
+        let role_id = TypeId::new();
+        let ron_str = format!(
+            "Proximity(role_id: {}, filter: (), measure: PathCost, max: 3)",
+            ron::to_string(&role_id).expect("serialize id")
+        );
+        let expr: ConstraintExpr = ron::from_str(&ron_str).expect("deserialize");
+        let ConstraintExpr::Proximity {
+            filter,
+            measure,
+            max,
+            line_of_sight,
+            ..
+        } = expr
+        else {
+            panic!("expected Proximity, got {expr:?}");
+        };
+        assert_eq!(filter, ProximityFilter::default());
+        assert_eq!(measure, ProximityMeasure::PathCost);
+        assert_eq!(max, 3);
+        assert_eq!(line_of_sight, None);
+    }
+
     #[test]
     fn constraint_expr_any_ron_round_trip() {
         let expr = ConstraintExpr::Any(vec![ConstraintExpr::PropertyCompare {
@@ -422,6 +559,7 @@ mod tests {
                 relation_id: None,
                 expression: ConstraintExpr::All(Vec::new()),
                 auto_generated: false,
+                gates: Vec::new(),
             }],
         };
         let ron_str = ron::to_string(&reg).expect("serialize");
@@ -430,6 +568,60 @@ mod tests {
         assert!(!deserialized.constraints[0].auto_generated);
     }
 
+    #[test]
+    fn presence_effects_revert_restores_base_values_newest_first() {
+        let strength = TypeId::new();
+        let mut data = EntityData {
+            entity_type_id: TypeId::new(),
+            properties: [(strength, PropertyValue::Int(6))].into_iter().collect(),
+        };
+        let mut effects = PresenceEffects {
+            applied: vec![
+                AppliedEffect {
+                    relation_id: TypeId::new(),
+                    source: Entity::PLACEHOLDER,
+                    property_id: strength,
+                    before: PropertyValue::Int(3),
+                    after: PropertyValue::Int(4),
+                },
+                AppliedEffect {
+                    relation_id: TypeId::new(),
+                    source: Entity::PLACEHOLDER,
+                    property_id: strength,
+                    before: PropertyValue::Int(4),
+                    after: PropertyValue::Int(6),
+                },
+            ],
+        };
+        assert_eq!(
+            effects.base_data(&data).properties[&strength],
+            PropertyValue::Int(3)
+        );
+        effects.revert(&mut data);
+        assert_eq!(data.properties[&strength], PropertyValue::Int(3));
+        assert!(effects.applied.is_empty());
+    }
+
+    #[test]
+    fn presence_effects_revert_keeps_external_edits() {
+        let strength = TypeId::new();
+        let mut data = EntityData {
+            entity_type_id: TypeId::new(),
+            properties: [(strength, PropertyValue::Int(9))].into_iter().collect(),
+        };
+        let mut effects = PresenceEffects {
+            applied: vec![AppliedEffect {
+                relation_id: TypeId::new(),
+                source: Entity::PLACEHOLDER,
+                property_id: strength,
+                before: PropertyValue::Int(3),
+                after: PropertyValue::Int(4),
+            }],
+        };
+        effects.revert(&mut data);
+        assert_eq!(data.properties[&strength], PropertyValue::Int(9));
+    }
+
     #[test]
     fn relation_trigger_variants() {
         assert_ne!(RelationTrigger::OnEnter, RelationTrigger::OnExit);
@@ -541,6 +733,7 @@ mod tests {
             relation_id: Some(TypeId::new()),
             expression: ConstraintExpr::All(vec![]),
             auto_generated: true,
+            gates: Vec::new(),
         };
         assert!(c.auto_generated);
         assert!(c.relation_id.is_some());
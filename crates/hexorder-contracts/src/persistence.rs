@@ -10,19 +10,22 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::game_system::{
-    EntityTypeRegistry, EnumRegistry, GameSystem, PropertyValue, StructRegistry, TypeId,
+    EntityTypeRegistry, EnumRegistry, FactionRegistry, GameSystem, PropertyValue,
+    StateMachineRegistry, StructRegistry, TypeId, UnitId,
 };
 use crate::hex_grid::{
-    HexEdgeRegistry, HexPosition, InfluenceRuleRegistry, MovementCostMatrix, StackingRule,
+    GridShape, HexEdgeRegistry, HexPosition, HexVertexRegistry, InfluenceRuleRegistry,
+    MovementCostMatrix, ReachabilityRuleRegistry, StackingRule,
 };
 use crate::mechanics::{
-    AccumulatorRegistry, CombatModifierRegistry, CombatResultsTable, SpawnSchedule, TurnStructure,
+    AccumulatorRegistry, CombatModifierRegistry, CombatResultsTable, CombatTableRegistry,
+    MovementInterruptRegistry, OffMapZoneRegistry, OverrunRegistry, SpawnSchedule, TurnStructure,
     VictoryConditionRegistry,
 };
 use crate::ontology::{ConceptRegistry, ConstraintRegistry, RelationRegistry};
 
 /// Current file format version. Increment when the schema changes.
-pub const FORMAT_VERSION: u32 = 8;
+pub const FORMAT_VERSION: u32 = 18;
 
 // ---------------------------------------------------------------------------
 // Application State
@@ -139,6 +142,9 @@ pub struct GameSystemFile {
     pub combat_modifiers: CombatModifierRegistry,
     /// Board configuration.
     pub map_radius: u32,
+    /// Board outline and east-west wrap (v10+). Defaults to a hexagon.
+    #[serde(default)]
+    pub grid_shape: GridShape,
     /// Board state: per-tile cell data.
     pub tiles: Vec<TileSaveData>,
     /// Board state: placed units.
@@ -152,6 +158,9 @@ pub struct GameSystemFile {
     /// Hex edge feature annotations (v6+).
     #[serde(default)]
     pub edge_features: HexEdgeRegistry,
+    /// Hex vertex feature annotations (v11+).
+    #[serde(default)]
+    pub vertex_features: HexVertexRegistry,
     /// Spatial influence rules (v6+).
     #[serde(default)]
     pub influence_rules: InfluenceRuleRegistry,
@@ -170,6 +179,27 @@ pub struct GameSystemFile {
     /// Victory conditions (v8+).
     #[serde(default)]
     pub victory_conditions: VictoryConditionRegistry,
+    /// Off-map zones and the units held in them (v9+).
+    #[serde(default)]
+    pub off_map_zones: OffMapZoneRegistry,
+    /// Factions that own units (v12+).
+    #[serde(default)]
+    pub factions: FactionRegistry,
+    /// Entity state machines (v14+).
+    #[serde(default)]
+    pub state_machines: StateMachineRegistry,
+    /// Reachability rules (v15+).
+    #[serde(default)]
+    pub reachability_rules: ReachabilityRuleRegistry,
+    /// Movement interrupt rules (v16+).
+    #[serde(default)]
+    pub movement_interrupts: MovementInterruptRegistry,
+    /// Overrun rules (v17+).
+    #[serde(default)]
+    pub overruns: OverrunRegistry,
+    /// Conditional combat results tables (v18+).
+    #[serde(default)]
+    pub combat_tables: CombatTableRegistry,
 }
 
 fn default_font_size() -> f32 {
@@ -182,6 +212,10 @@ pub struct TileSaveData {
     pub position: HexPosition,
     pub entity_type_id: TypeId,
     pub properties: HashMap<TypeId, PropertyValue>,
+    /// Current state-machine state (v14+). `None` starts the tile in its
+    /// machine's initial state.
+    #[serde(default)]
+    pub state: Option<TypeId>,
 }
 
 /// Serialized form of a placed unit.
@@ -190,6 +224,17 @@ pub struct UnitSaveData {
     pub position: HexPosition,
     pub entity_type_id: TypeId,
     pub properties: HashMap<TypeId, PropertyValue>,
+    /// Owning faction (v12+). `None` for unowned units.
+    #[serde(default)]
+    pub owner: Option<TypeId>,
+    /// Persistent instance id (v13+). Files saved before v13 get a fresh id
+    /// per unit on load.
+    #[serde(default)]
+    pub id: UnitId,
+    /// Current state-machine state (v14+). `None` starts the unit in its
+    /// machine's initial state.
+    #[serde(default)]
+    pub state: Option<TypeId>,
 }
 
 // ---------------------------------------------------------------------------
@@ -261,7 +306,7 @@ mod tests {
 
     #[test]
     fn format_version_constant() {
-        assert_eq!(FORMAT_VERSION, 8);
+        assert_eq!(FORMAT_VERSION, 18);
     }
 
     #[test]
@@ -359,6 +404,7 @@ mod tests {
             position: HexPosition { q: 1, r: -1 },
             entity_type_id: TypeId::new(),
             properties: HashMap::new(),
+            state: None,
         };
         assert_eq!(data.position.q, 1);
     }
@@ -369,6 +415,9 @@ mod tests {
             position: HexPosition { q: 0, r: 0 },
             entity_type_id: TypeId::new(),
             properties: HashMap::new(),
+            owner: None,
+            id: UnitId::new(),
+            state: None,
         };
         assert_eq!(data.position.r, 0);
     }
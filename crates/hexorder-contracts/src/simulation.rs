@@ -242,6 +242,39 @@ pub fn roll_pool(rng: &mut SimulationRng, pool: DicePool, context: &str) -> Dice
     }
 }
 
+/// The value a dice total looks up in a table's rows. Negative totals read
+/// as 0.
+#[must_use]
+pub fn table_roll(total: i16) -> u32 {
+    u32::from(total.max(0).unsigned_abs())
+}
+
+/// The exact distribution of a [`DicePool`]'s totals, as `(total, probability)`
+/// pairs in ascending order of total. Empty for a pool without dice or sides.
+#[must_use]
+pub fn pool_distribution(pool: DicePool) -> Vec<(i16, f64)> {
+    if pool.count == 0 || pool.sides == 0 {
+        return Vec::new();
+    }
+    // ways[i] counts the rolls whose sum of dice is `i + count`.
+    let mut ways: Vec<f64> = vec![1.0];
+    for _ in 0..pool.count {
+        let mut next = vec![0.0; ways.len() + usize::from(pool.sides) - 1];
+        for (i, &w) in ways.iter().enumerate() {
+            for face in 0..usize::from(pool.sides) {
+                next[i + face] += w;
+            }
+        }
+        ways = next;
+    }
+    let outcomes: f64 = ways.iter().sum();
+    let lowest = i16::from(pool.count) + i16::from(pool.modifier);
+    ways.iter()
+        .enumerate()
+        .map(|(i, &w)| (lowest + i as i16, w / outcomes))
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Table Resolution
 // ---------------------------------------------------------------------------
@@ -280,7 +313,7 @@ pub struct TableRow {
 }
 
 /// The result of a table lookup.
-#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
 pub enum TableResult {
     /// A label-only outcome (e.g., "NE", "DR").
     Text(String),
@@ -376,7 +409,7 @@ pub struct LookupEntry {
 }
 
 /// Result of a full 2D table resolution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TableResolution {
     pub column_index: usize,
     pub row_index: usize,
@@ -433,6 +466,8 @@ pub struct ChainStepResult {
     pub table_name: String,
     /// The resolution result (None if the table lookup failed).
     pub resolution: Option<TableResolution>,
+    /// The dice rolled for this step, if its roll source is a pool.
+    pub roll: Option<DiceRoll>,
 }
 
 /// A sequence of resolution table lookups where each step's output feeds the next.
@@ -538,17 +573,13 @@ pub fn evaluate_column_modifiers(
     modifiers: &[ColumnModifier],
     column_count: usize,
 ) -> (i32, Vec<(String, i32)>) {
-    let mut sorted: Vec<&ColumnModifier> = modifiers.iter().collect();
-    sorted.sort_by(|a, b| b.priority.cmp(&a.priority));
+    let sorted = column_modifier_order(modifiers);
 
     let mut total_shift: i32 = 0;
     let mut display: Vec<(String, i32)> = Vec::with_capacity(sorted.len());
 
     for modifier in &sorted {
-        total_shift += modifier.column_shift;
-        if let Some(cap) = modifier.cap {
-            total_shift = total_shift.clamp(-cap, cap);
-        }
+        total_shift = apply_column_modifier(total_shift, modifier);
         display.push((modifier.name.clone(), modifier.column_shift));
     }
 
@@ -560,6 +591,22 @@ pub fn evaluate_column_modifiers(
     (total_shift, display)
 }
 
+/// The order `evaluate_column_modifiers` applies `modifiers` in: highest
+/// priority first, ties in their given order.
+#[must_use]
+pub fn column_modifier_order(modifiers: &[ColumnModifier]) -> Vec<&ColumnModifier> {
+    let mut sorted: Vec<&ColumnModifier> = modifiers.iter().collect();
+    sorted.sort_by(|a, b| b.priority.cmp(&a.priority));
+    sorted
+}
+
+/// Adds `modifier`'s shift to the running `total` and applies its cap.
+#[must_use]
+pub fn apply_column_modifier(total: i32, modifier: &ColumnModifier) -> i32 {
+    let total = total.saturating_add(modifier.column_shift);
+    modifier.cap.map_or(total, |cap| total.clamp(-cap, cap))
+}
+
 /// Apply a column shift to a base index, clamping to bounds.
 #[must_use]
 pub fn apply_column_shift(base_column: usize, shift: i32, column_count: usize) -> usize {
@@ -601,10 +648,13 @@ pub fn resolve_chain(
         let input_a = ctx.values.get(&step.input_a_key).copied().unwrap_or(0.0);
         let input_b = ctx.values.get(&step.input_b_key).copied().unwrap_or(0.0);
 
+        let mut dice_roll = None;
         let roll: u32 = match &step.roll_source {
             ChainRollSource::Pool(pool) => {
-                let dice_roll = roll_pool(rng, *pool, &chain.name);
-                dice_roll.total.max(0) as u32
+                let rolled = roll_pool(rng, *pool, &chain.name);
+                let total = table_roll(rolled.total);
+                dice_roll = Some(rolled);
+                total
             }
             ChainRollSource::ContextKey(key) => {
                 ctx.values.get(key).copied().unwrap_or(0.0).max(0.0) as u32
@@ -626,12 +676,55 @@ pub fn resolve_chain(
             step_index: i,
             table_name: table.map_or_else(|| "(missing)".to_string(), |t| t.name.clone()),
             resolution,
+            roll: dice_roll,
         });
     }
 
     ctx
 }
 
+// ---------------------------------------------------------------------------
+// Roll History
+// ---------------------------------------------------------------------------
+
+/// Fired when dice are rolled in Play.
+#[derive(Event, Debug, Clone)]
+pub struct DieRolled {
+    /// What the roll was for (e.g., "CRT 3:1").
+    pub context: String,
+    pub roll: DiceRoll,
+}
+
+/// Fired when a table lookup completes in Play.
+#[derive(Event, Debug, Clone)]
+pub struct TableResolved {
+    pub table_id: TypeId,
+    pub resolution: TableResolution,
+    /// The dice that picked the row, if they were rolled.
+    pub roll: Option<DiceRoll>,
+}
+
+/// One roll of the play session, with the lookup it decided.
+#[derive(Debug, Clone)]
+pub struct RollHistoryEntry {
+    pub context: String,
+    pub roll: DiceRoll,
+    pub resolution: Option<TableResolution>,
+}
+
+/// Rolls made in the current play session, oldest first. Filled from
+/// `DieRolled` and `TableResolved`; cleared when a session starts.
+/// Runtime-only — not persisted.
+#[derive(Resource, Debug, Default)]
+pub struct RollHistory {
+    pub entries: Vec<RollHistoryEntry>,
+}
+
+/// Seed every play session starts from. `None` seeds each session randomly.
+/// Runtime-only — not persisted.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionSeed(pub Option<u64>);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -712,6 +805,26 @@ mod tests {
         assert_eq!(result.pool, pool);
     }
 
+    #[test]
+    fn pool_distribution_of_two_d6() {
+        let dist = pool_distribution(DicePool::new(2, 6, 0));
+        assert_eq!(dist.len(), 11);
+        assert_eq!(dist[0].0, 2);
+        assert_eq!(dist[10].0, 12);
+        assert!((dist[5].1 - 6.0 / 36.0).abs() < 1e-12, "7 comes up 6 in 36");
+        let total: f64 = dist.iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pool_distribution_applies_modifier() {
+        let dist = pool_distribution(DicePool::new(1, 4, -2));
+        let totals: Vec<i16> = dist.iter().map(|(t, _)| *t).collect();
+        assert_eq!(totals, vec![-1, 0, 1, 2]);
+        assert!(dist.iter().all(|(_, p)| (p - 0.25).abs() < 1e-12));
+        assert!(pool_distribution(DicePool::new(0, 6, 0)).is_empty());
+    }
+
     #[test]
     fn dice_pool_ron_round_trip() {
         let pool = DicePool::new(2, 6, 3);
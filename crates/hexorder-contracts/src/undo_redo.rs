@@ -9,7 +9,7 @@ use std::fmt;
 
 use bevy::prelude::*;
 
-use crate::game_system::{EntityData, PropertyValue, TypeId, UnitInstance};
+use crate::game_system::{EntityData, PropertyValue, TypeId, UnitId, UnitInstance, UnitOwner};
 use crate::hex_grid::HexPosition;
 
 // ---------------------------------------------------------------------------
@@ -296,6 +296,10 @@ pub struct PlaceUnitCommand {
     pub position: HexPosition,
     /// Entity data (type ID and properties) for the placed unit.
     pub entity_data: EntityData,
+    /// Owning faction of the placed unit.
+    pub owner: UnitOwner,
+    /// Persistent id of the placed unit, kept across undo/redo.
+    pub unit_id: UnitId,
     /// Mesh handle for rendering.
     pub mesh: Handle<Mesh>,
     /// Material handle for rendering.
@@ -324,6 +328,8 @@ impl UndoableCommand for PlaceUnitCommand {
                 UnitInstance,
                 self.position,
                 self.entity_data.clone(),
+                self.owner,
+                self.unit_id,
                 Mesh3d(self.mesh.clone()),
                 MeshMaterial3d(self.material.clone()),
                 self.transform,
@@ -361,6 +367,10 @@ pub struct DeleteUnitCommand {
     pub position: HexPosition,
     /// Entity data (type ID and properties) for the deleted unit.
     pub entity_data: EntityData,
+    /// Owning faction of the deleted unit.
+    pub owner: UnitOwner,
+    /// Persistent id of the deleted unit, kept across undo/redo.
+    pub unit_id: UnitId,
     /// Mesh handle for rendering.
     pub mesh: Handle<Mesh>,
     /// Material handle for rendering.
@@ -399,6 +409,8 @@ impl UndoableCommand for DeleteUnitCommand {
                 UnitInstance,
                 self.position,
                 self.entity_data.clone(),
+                self.owner,
+                self.unit_id,
                 Mesh3d(self.mesh.clone()),
                 MeshMaterial3d(self.material.clone()),
                 self.transform,
@@ -743,6 +755,8 @@ mod tests {
                 entity_type_id: TypeId::new(),
                 properties: HashMap::new(),
             },
+            owner: UnitOwner::default(),
+            unit_id: UnitId::new(),
             mesh: Handle::default(),
             material: Handle::default(),
             transform: Transform::IDENTITY,
@@ -760,6 +774,8 @@ mod tests {
                 entity_type_id: TypeId::new(),
                 properties: HashMap::new(),
             },
+            owner: UnitOwner::default(),
+            unit_id: UnitId::new(),
             mesh: Handle::default(),
             material: Handle::default(),
             transform: Transform::IDENTITY,
@@ -840,6 +856,8 @@ mod tests {
                 entity_type_id: TypeId::new(),
                 properties: HashMap::new(),
             },
+            owner: UnitOwner::default(),
+            unit_id: UnitId::new(),
             mesh: Handle::default(),
             material: Handle::default(),
             transform: Transform::IDENTITY,
@@ -2,14 +2,30 @@
 //!
 //! Types for schema-level validation (is the game system definition
 //! internally consistent?) and state-level validation (given a board
-//! state, are constraints satisfied?).
+//! state, are constraints satisfied?), plus static rule analysis (do the
+//! rules contradict each other or never apply?).
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use bevy::prelude::*;
 
-use crate::game_system::TypeId;
-use crate::hex_grid::HexPosition;
+use crate::game_system::{
+    EntityData, EntityRole, EntityTypeRegistry, PropertyDefinition, PropertyType, PropertyValue,
+    StateMachineRegistry, StateTrigger, TypeId,
+};
+use crate::hex_grid::{
+    HexPosition, InfluenceRuleRegistry, MovementCostMatrix, ReachabilityRuleRegistry,
+    ReachabilitySource, StackingRule,
+};
+use crate::mechanics::{
+    CombatModifierRegistry, CombatResultsTable, CombatTableRegistry, PhaseType, SpawnSchedule,
+    TurnStructure, VictoryConditionRegistry,
+};
+use crate::ontology::{
+    CompareOp, ConceptRegistry, ConstraintExpr, ConstraintRegistry, GatedAction, ModifyOperation,
+    Relation, RelationEffect, RelationRegistry,
+};
+use crate::simulation::{ColumnModifier, apply_column_modifier, column_modifier_order};
 
 // ---------------------------------------------------------------------------
 // Schema Validation
@@ -56,7 +72,7 @@ pub struct SchemaValidation {
 // ---------------------------------------------------------------------------
 
 /// The result of evaluating a single constraint against a specific board position.
-#[derive(Debug, Clone, Reflect)]
+#[derive(Debug, Clone, PartialEq, Reflect)]
 pub struct ValidationResult {
     pub constraint_id: TypeId,
     pub constraint_name: String,
@@ -67,7 +83,7 @@ pub struct ValidationResult {
 
 /// The computed set of valid moves for a selected entity.
 /// Produced by the `rules_engine` and consumed by `hex_grid` for visual overlay.
-#[derive(Resource, Debug, Default, Reflect)]
+#[derive(Resource, Debug, Clone, Default, Reflect)]
 pub struct ValidMoveSet {
     /// Hex positions the selected entity can move to.
     #[reflect(ignore)]
@@ -77,6 +93,679 @@ pub struct ValidMoveSet {
     pub blocked_explanations: HashMap<HexPosition, Vec<ValidationResult>>,
     /// The entity this move set was computed for (None when no unit is selected).
     pub for_entity: Option<Entity>,
+    /// For each reachable position, the last step of the cheapest route to it.
+    /// Covers valid destinations and hexes the unit may only pass through
+    /// (e.g. over-stacked). Following `PathStep::from` back reaches the unit's
+    /// position.
+    #[reflect(ignore)]
+    pub paths: HashMap<HexPosition, PathStep>,
+}
+
+impl ValidMoveSet {
+    /// Clears all positions, explanations and routes.
+    pub fn clear(&mut self) {
+        self.valid_positions.clear();
+        self.blocked_explanations.clear();
+        self.paths.clear();
+        self.for_entity = None;
+    }
+
+    /// The cheapest route to `to`, starting with the unit's position and
+    /// ending with `to`. `None` when `to` is not reachable.
+    #[must_use]
+    pub fn route(&self, to: HexPosition) -> Option<Vec<HexPosition>> {
+        let mut route = vec![to];
+        let mut current = self.paths.get(&to)?;
+        // A well-formed tree never revisits a hex; the bound guards bad data.
+        while route.len() <= self.paths.len() {
+            route.push(current.from);
+            let Some(step) = self.paths.get(&current.from) else {
+                route.reverse();
+                return Some(route);
+            };
+            current = step;
+        }
+        None
+    }
+
+    /// The steps of the cheapest route to `to` in travel order, each paired
+    /// with the hex it enters. `None` when `to` is not reachable.
+    #[must_use]
+    pub fn route_steps(&self, to: HexPosition) -> Option<Vec<(HexPosition, &PathStep)>> {
+        let route = self.route(to)?;
+        route
+            .into_iter()
+            .skip(1)
+            .map(|pos| self.paths.get(&pos).map(|step| (pos, step)))
+            .collect()
+    }
+}
+
+/// What a component of a step's movement cost comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum CostSource {
+    /// An `OnEnter` relation charging the entered tile's cost.
+    Terrain,
+    /// A movement cost matrix entry replacing the terrain cost.
+    ClassificationMatrix,
+    /// An `OnExit` relation charging for the tile being left.
+    Exit,
+    /// An edge feature on the crossed hex boundary.
+    Edge,
+    /// A vertex feature at a corner of the entered hex.
+    Vertex,
+    /// Spatial influence projected onto the entered hex.
+    Influence,
+    /// Zone-of-control exit or zone-to-zone cost.
+    ZoneOfControl,
+    /// An area marker covering the entered hex.
+    AreaMarker,
+}
+
+/// One contribution to the cost of a single step.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+pub struct CostComponent {
+    pub source: CostSource,
+    /// What charged the cost (relation, feature or marker name).
+    pub label: String,
+    /// Budget spent by this component (negative when it refunds budget).
+    pub amount: i64,
+}
+
+/// The last step of the cheapest route to a hex.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+pub struct PathStep {
+    /// The hex the route enters from.
+    pub from: HexPosition,
+    /// Cost components of this step, in evaluation order.
+    pub components: Vec<CostComponent>,
+    /// Total budget spent on the route up to and including this step.
+    pub total_cost: i64,
+}
+
+impl PathStep {
+    /// Total cost of this step alone.
+    #[must_use]
+    pub fn step_cost(&self) -> i64 {
+        self.components.iter().map(|c| c.amount).sum()
+    }
+}
+
+/// Gated actions a unit may not take, each with the failed constraint that
+/// denies it. Kept up to date by the `rules_engine` for every unit.
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect)]
+pub struct ActionEligibility {
+    pub denied: Vec<(GatedAction, ValidationResult)>,
+}
+
+impl ActionEligibility {
+    /// Whether no gating constraint denies `action`.
+    #[must_use]
+    pub fn allows(&self, action: GatedAction) -> bool {
+        !self.denied.iter().any(|(a, _)| *a == action)
+    }
+
+    /// The failed constraints denying `action`.
+    pub fn denials(&self, action: GatedAction) -> impl Iterator<Item = &ValidationResult> {
+        self.denied
+            .iter()
+            .filter(move |(a, _)| *a == action)
+            .map(|(_, result)| result)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rule Analysis
+// ---------------------------------------------------------------------------
+
+/// Category of a static rule analysis finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum AnalysisCategory {
+    /// A `Block` and an `Allow` relation cover the same entity type pair.
+    Conflict,
+    /// A constraint can never hold given its properties' ranges and its
+    /// roles' bindings.
+    Unsatisfiable,
+    /// An additive relation whose source property is never non-zero.
+    NoEffect,
+    /// A combat results table column no odds and column shift select.
+    UnreachableColumn,
+    /// A phase in which nothing can happen.
+    EmptyPhase,
+    /// An entity type no rule references.
+    Unreferenced,
+}
+
+/// The definition a finding is about, used to jump to it in the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum AnalysisSubject {
+    EntityType(TypeId),
+    Relation(TypeId),
+    Constraint(TypeId),
+    /// A combat results table's ID and the column's index in it.
+    CrtColumn(TypeId, usize),
+    Phase(TypeId),
+}
+
+/// A single static rule analysis finding.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct AnalysisFinding {
+    pub category: AnalysisCategory,
+    /// Human-readable description.
+    pub message: String,
+    pub subject: AnalysisSubject,
+}
+
+/// Logic-level findings about the game system definition: rules that
+/// contradict each other, can never hold or never apply. Unlike
+/// `SchemaValidation` errors these are warnings; the definition still runs.
+/// Updated by the ontology plugin when rules change.
+#[derive(Resource, Debug, Default, Reflect)]
+pub struct RuleAnalysis {
+    pub findings: Vec<AnalysisFinding>,
+}
+
+/// The definitions static rule analysis reads.
+#[derive(Clone, Copy)]
+pub struct AnalyzedRules<'a> {
+    pub entity_types: &'a EntityTypeRegistry,
+    pub concepts: &'a ConceptRegistry,
+    pub relations: &'a RelationRegistry,
+    pub constraints: &'a ConstraintRegistry,
+    pub turn_structure: &'a TurnStructure,
+    pub crt: &'a CombatResultsTable,
+    /// Conditional combat results tables, analysed like `crt`.
+    pub combat_tables: &'a CombatTableRegistry,
+    pub combat_modifiers: &'a CombatModifierRegistry,
+    pub influence_rules: &'a InfluenceRuleRegistry,
+    pub stacking_rule: &'a StackingRule,
+    pub movement_cost_matrix: &'a MovementCostMatrix,
+    pub state_machines: &'a StateMachineRegistry,
+    pub reachability_rules: &'a ReachabilityRuleRegistry,
+    pub spawn_schedule: &'a SpawnSchedule,
+    pub victory_conditions: &'a VictoryConditionRegistry,
+    /// Data of placed tiles and units, whose values may override defaults.
+    pub instances: &'a [&'a EntityData],
+}
+
+/// Runs every static rule check and returns the findings, grouped by
+/// category in declaration order.
+#[must_use]
+pub fn analyze_rules(rules: &AnalyzedRules) -> Vec<AnalysisFinding> {
+    let mut findings = Vec::new();
+    find_conflicting_relations(rules, &mut findings);
+    find_unsatisfiable_constraints(rules, &mut findings);
+    find_ineffective_relations(rules, &mut findings);
+    find_unreachable_columns(rules, &mut findings);
+    find_empty_phases(rules, &mut findings);
+    find_unreferenced_types(rules, &mut findings);
+    findings
+}
+
+fn type_name<'a>(rules: &AnalyzedRules<'a>, id: TypeId) -> &'a str {
+    rules
+        .entity_types
+        .get(id)
+        .map_or("(unknown)", |t| t.name.as_str())
+}
+
+/// Entity types bound to a concept role.
+fn role_types(rules: &AnalyzedRules, concept_id: TypeId, role_id: TypeId) -> Vec<TypeId> {
+    rules
+        .concepts
+        .bindings
+        .iter()
+        .filter(|b| b.concept_id == concept_id && b.concept_role_id == role_id)
+        .map(|b| b.entity_type_id)
+        .collect()
+}
+
+/// Properties bound to a concept role under a concept-local name, with the
+/// entity type each belongs to.
+fn role_properties<'a>(
+    rules: &AnalyzedRules<'a>,
+    concept_id: TypeId,
+    role_id: TypeId,
+    name: &str,
+) -> Vec<(TypeId, &'a PropertyDefinition)> {
+    rules
+        .concepts
+        .bindings
+        .iter()
+        .filter(|b| b.concept_id == concept_id && b.concept_role_id == role_id)
+        .filter_map(|b| {
+            let binding = b
+                .property_bindings
+                .iter()
+                .find(|p| p.concept_local_name == name)?;
+            let property = rules
+                .entity_types
+                .get(b.entity_type_id)?
+                .properties
+                .iter()
+                .find(|p| p.id == binding.property_id)?;
+            Some((b.entity_type_id, property))
+        })
+        .collect()
+}
+
+/// Flags `Block` and `Allow` relations with the same trigger whose subject
+/// and object types overlap, unless both are conditional.
+fn find_conflicting_relations(rules: &AnalyzedRules, findings: &mut Vec<AnalysisFinding>) {
+    let type_pairs = |relation: &Relation| -> Vec<(TypeId, TypeId)> {
+        let objects = role_types(rules, relation.concept_id, relation.object_role_id);
+        role_types(rules, relation.concept_id, relation.subject_role_id)
+            .into_iter()
+            .flat_map(|s| objects.iter().map(move |o| (s, *o)))
+            .collect()
+    };
+    for block in &rules.relations.relations {
+        let RelationEffect::Block {
+            condition: block_condition,
+        } = &block.effect
+        else {
+            continue;
+        };
+        let blocked = type_pairs(block);
+        for allow in &rules.relations.relations {
+            let RelationEffect::Allow {
+                condition: allow_condition,
+            } = &allow.effect
+            else {
+                continue;
+            };
+            if allow.trigger != block.trigger
+                || (block_condition.is_some() && allow_condition.is_some())
+            {
+                continue;
+            }
+            let allowed = type_pairs(allow);
+            if let Some((subject, object)) = blocked.iter().find(|pair| allowed.contains(pair)) {
+                findings.push(AnalysisFinding {
+                    category: AnalysisCategory::Conflict,
+                    message: format!(
+                        "\"{}\" blocks and \"{}\" allows {} at {}",
+                        block.name,
+                        allow.name,
+                        type_name(rules, *subject),
+                        type_name(rules, *object),
+                    ),
+                    subject: AnalysisSubject::Relation(block.id),
+                });
+            }
+        }
+    }
+}
+
+fn find_unsatisfiable_constraints(rules: &AnalyzedRules, findings: &mut Vec<AnalysisFinding>) {
+    for constraint in &rules.constraints.constraints {
+        let (can_hold, _) = outcomes(rules, constraint.concept_id, &constraint.expression);
+        if !can_hold {
+            findings.push(AnalysisFinding {
+                category: AnalysisCategory::Unsatisfiable,
+                message: format!("\"{}\" can never be satisfied", constraint.name),
+                subject: AnalysisSubject::Constraint(constraint.id),
+            });
+        }
+    }
+}
+
+/// Whether `expr` can evaluate to true and whether it can evaluate to
+/// false, judged from declared property ranges and role bindings. Anything
+/// the analysis cannot bound may go either way.
+fn outcomes(rules: &AnalyzedRules, concept_id: TypeId, expr: &ConstraintExpr) -> (bool, bool) {
+    match expr {
+        ConstraintExpr::PropertyCompare {
+            role_id,
+            property_name,
+            operator,
+            value,
+        } => {
+            let properties = role_properties(rules, concept_id, *role_id, property_name);
+            let Some(value) = numeric(value) else {
+                return (true, true);
+            };
+            if properties.is_empty() {
+                return (true, true);
+            }
+            properties
+                .iter()
+                .fold((false, false), |(can_true, can_false), (_, property)| {
+                    let (t, f) = range_of(&property.property_type).map_or((true, true), |range| {
+                        compare_outcomes(*operator, range, value)
+                    });
+                    (can_true || t, can_false || f)
+                })
+        }
+        ConstraintExpr::IsType {
+            role_id,
+            entity_type_id,
+        } => type_outcomes(rules, concept_id, *role_id, *entity_type_id),
+        ConstraintExpr::IsNotType {
+            role_id,
+            entity_type_id,
+        } => {
+            let (can_true, can_false) = type_outcomes(rules, concept_id, *role_id, *entity_type_id);
+            (can_false, can_true)
+        }
+        ConstraintExpr::All(children) => children
+            .iter()
+            .map(|child| outcomes(rules, concept_id, child))
+            .fold((true, false), |(t, f), (ct, cf)| (t && ct, f || cf)),
+        ConstraintExpr::Any(children) => children
+            .iter()
+            .map(|child| outcomes(rules, concept_id, child))
+            .fold((false, true), |(t, f), (ct, cf)| (t || ct, f && cf)),
+        ConstraintExpr::Not(inner) => {
+            let (can_true, can_false) = outcomes(rules, concept_id, inner);
+            (can_false, can_true)
+        }
+        _ => (true, true),
+    }
+}
+
+fn type_outcomes(
+    rules: &AnalyzedRules,
+    concept_id: TypeId,
+    role_id: TypeId,
+    entity_type_id: TypeId,
+) -> (bool, bool) {
+    let bound = role_types(rules, concept_id, role_id);
+    if bound.is_empty() {
+        return (true, true);
+    }
+    (
+        bound.contains(&entity_type_id),
+        bound.iter().any(|id| *id != entity_type_id),
+    )
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn numeric(value: &PropertyValue) -> Option<f64> {
+    match value {
+        PropertyValue::Int(v) | PropertyValue::IntRange(v) => Some(*v as f64),
+        PropertyValue::Float(v) | PropertyValue::FloatRange(v) => Some(*v),
+        _ => None,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn range_of(property_type: &PropertyType) -> Option<(f64, f64)> {
+    match property_type {
+        PropertyType::IntRange { min, max } => Some((*min as f64, *max as f64)),
+        PropertyType::FloatRange { min, max } => Some((*min, *max)),
+        _ => None,
+    }
+}
+
+/// Whether `x op value` can be true and can be false for some `x` in
+/// `[min, max]`.
+fn compare_outcomes(operator: CompareOp, (min, max): (f64, f64), value: f64) -> (bool, bool) {
+    let only_value = min >= max && min <= value && max >= value;
+    match operator {
+        CompareOp::Eq => (min <= value && max >= value, !only_value),
+        CompareOp::Ne => (!only_value, min <= value && max >= value),
+        CompareOp::Lt => (min < value, max >= value),
+        CompareOp::Le => (min <= value, max > value),
+        CompareOp::Gt => (max > value, min <= value),
+        CompareOp::Ge => (max >= value, min < value),
+    }
+}
+
+/// Flags `Add` and `Subtract` relations whose source property is zero by
+/// default on every bound type and on every placed instance.
+fn find_ineffective_relations(rules: &AnalyzedRules, findings: &mut Vec<AnalysisFinding>) {
+    for relation in &rules.relations.relations {
+        let RelationEffect::ModifyProperty {
+            source_property,
+            operation: ModifyOperation::Add | ModifyOperation::Subtract,
+            ..
+        } = &relation.effect
+        else {
+            continue;
+        };
+        let sources = role_properties(
+            rules,
+            relation.concept_id,
+            relation.object_role_id,
+            source_property,
+        );
+        let effective = sources.iter().any(|(type_id, property)| {
+            is_non_zero(&property.default_value)
+                || rules.instances.iter().any(|data| {
+                    data.entity_type_id == *type_id
+                        && data.properties.get(&property.id).is_some_and(is_non_zero)
+                })
+        });
+        if !sources.is_empty() && !effective {
+            findings.push(AnalysisFinding {
+                category: AnalysisCategory::NoEffect,
+                message: format!(
+                    "\"{}\" has no effect: {source_property} is never non-zero",
+                    relation.name
+                ),
+                subject: AnalysisSubject::Relation(relation.id),
+            });
+        }
+    }
+}
+
+fn is_non_zero(value: &PropertyValue) -> bool {
+    match value {
+        PropertyValue::Int(v) | PropertyValue::IntRange(v) => *v != 0,
+        PropertyValue::Float(v) | PropertyValue::FloatRange(v) => v.abs() > 0.0,
+        _ => true,
+    }
+}
+
+/// Flags columns of the combat results tables that neither odds nor any
+/// subset of the combat modifiers can select. A column is a base column
+/// unless a later column of the same type has a threshold no higher (column
+/// lookup picks the last match); modifiers then shift the base column within
+/// their caps, as `evaluate_column_modifiers` applies them.
+fn find_unreachable_columns(rules: &AnalyzedRules, findings: &mut Vec<AnalysisFinding>) {
+    let modifiers = rules.combat_modifiers.column_modifiers();
+    for crt in std::iter::once(rules.crt).chain(&rules.combat_tables.tables) {
+        let columns = &crt.table.columns;
+        let Some(last) = columns.len().checked_sub(1) else {
+            continue;
+        };
+        let last = i32::try_from(last).unwrap_or(i32::MAX);
+        let shifts = reachable_shifts(&modifiers, last);
+        let mut reached = vec![false; columns.len()];
+        for (base, column) in columns.iter().enumerate() {
+            let shadowed = columns[base + 1..].iter().any(|later| {
+                later.column_type == column.column_type && later.threshold <= column.threshold
+            });
+            if shadowed {
+                continue;
+            }
+            let base = i32::try_from(base).unwrap_or(i32::MAX);
+            for shift in &shifts {
+                let index =
+                    usize::try_from(base.saturating_add(*shift).clamp(0, last)).unwrap_or(0);
+                reached[index] = true;
+            }
+        }
+        for (index, column) in columns.iter().enumerate() {
+            if !reached[index] {
+                findings.push(AnalysisFinding {
+                    category: AnalysisCategory::UnreachableColumn,
+                    message: format!(
+                        "Column \"{}\" of \"{}\" is never selected by odds or column shifts",
+                        column.label, crt.name
+                    ),
+                    subject: AnalysisSubject::CrtColumn(crt.id, index),
+                });
+            }
+        }
+    }
+}
+
+/// Every total column shift some subset of `modifiers` can produce, applied
+/// in `evaluate_column_modifiers` order with running caps, then clamped to
+/// `max_shift`.
+fn reachable_shifts(modifiers: &[ColumnModifier], max_shift: i32) -> BTreeSet<i32> {
+    let mut totals: BTreeSet<i32> = BTreeSet::from([0]);
+    for modifier in column_modifier_order(modifiers) {
+        let applied: Vec<i32> = totals
+            .iter()
+            .map(|total| apply_column_modifier(*total, modifier))
+            .collect();
+        totals.extend(applied);
+    }
+    totals
+        .into_iter()
+        .map(|total| total.clamp(-max_shift, max_shift))
+        .collect()
+}
+
+fn find_empty_phases(rules: &AnalyzedRules, findings: &mut Vec<AnalysisFinding>) {
+    let has_units = rules
+        .entity_types
+        .types
+        .iter()
+        .any(|t| t.role == EntityRole::Token);
+    let has_cells = std::iter::once(rules.crt)
+        .chain(&rules.combat_tables.tables)
+        .any(|crt| !crt.table.columns.is_empty() && !crt.table.rows.is_empty());
+    for phase in &rules.turn_structure.phases {
+        let reason = match phase.phase_type {
+            PhaseType::Movement | PhaseType::Combat if !has_units => {
+                Some("there are no unit types")
+            }
+            PhaseType::Movement => None,
+            PhaseType::Combat => (!has_cells).then_some("every combat results table is empty"),
+            PhaseType::Admin => (!admin_has_work(rules, phase.id)).then_some(
+                "no spawns, reachability traces, phase-start transitions or victory conditions",
+            ),
+        };
+        if let Some(reason) = reason {
+            findings.push(AnalysisFinding {
+                category: AnalysisCategory::EmptyPhase,
+                message: format!("Phase \"{}\" permits no actions: {reason}", phase.name),
+                subject: AnalysisSubject::Phase(phase.id),
+            });
+        }
+    }
+}
+
+fn admin_has_work(rules: &AnalyzedRules, phase_id: TypeId) -> bool {
+    !rules.spawn_schedule.entries.is_empty()
+        || !rules.victory_conditions.conditions.is_empty()
+        || rules
+            .reachability_rules
+            .rules
+            .iter()
+            .any(|r| r.phases.contains(&phase_id))
+        || rules
+            .state_machines
+            .machines
+            .iter()
+            .flat_map(|m| &m.transitions)
+            .any(|t| t.trigger == StateTrigger::PhaseStart(phase_id))
+}
+
+fn find_unreferenced_types(rules: &AnalyzedRules, findings: &mut Vec<AnalysisFinding>) {
+    let referenced = referenced_types(rules);
+    for entity_type in &rules.entity_types.types {
+        if !referenced.contains(&entity_type.id) {
+            findings.push(AnalysisFinding {
+                category: AnalysisCategory::Unreferenced,
+                message: format!("No rule references \"{}\"", entity_type.name),
+                subject: AnalysisSubject::EntityType(entity_type.id),
+            });
+        }
+    }
+}
+
+/// Entity types referenced by concept bindings, constraints, relation
+/// conditions or any mechanic.
+fn referenced_types(rules: &AnalyzedRules) -> HashSet<TypeId> {
+    let mut referenced: HashSet<TypeId> = rules
+        .concepts
+        .bindings
+        .iter()
+        .map(|b| b.entity_type_id)
+        .collect();
+    for constraint in &rules.constraints.constraints {
+        collect_expr_types(&constraint.expression, &mut referenced);
+    }
+    for relation in &rules.relations.relations {
+        if let RelationEffect::Block {
+            condition: Some(condition),
+        }
+        | RelationEffect::Allow {
+            condition: Some(condition),
+        } = &relation.effect
+        {
+            collect_expr_types(condition, &mut referenced);
+        }
+    }
+    for rule in &rules.influence_rules.rules {
+        referenced.insert(rule.entity_type_id);
+        referenced.extend(&rule.zone.negated_by);
+        referenced.extend(&rule.zone.blocking_edge_types);
+        referenced.extend(&rule.zone.excluded_terrain);
+    }
+    referenced.extend(&rules.stacking_rule.exempt_type_ids);
+    referenced.extend(rules.stacking_rule.terrain_limits.keys());
+    referenced.extend(rules.movement_cost_matrix.entries.keys().map(|(t, _)| *t));
+    referenced.extend(
+        rules
+            .state_machines
+            .machines
+            .iter()
+            .map(|m| m.entity_type_id),
+    );
+    for rule in &rules.reachability_rules.rules {
+        referenced.extend(&rule.traced_types);
+        referenced.extend(&rule.blockers.terrain);
+        referenced.extend(&rule.blockers.edge_types);
+        referenced.extend(rule.sources.iter().filter_map(|source| match source {
+            ReachabilitySource::Terrain(id) | ReachabilitySource::UnitType(id) => Some(*id),
+            ReachabilitySource::Hexes(_) => None,
+        }));
+    }
+    referenced.extend(
+        rules
+            .combat_modifiers
+            .modifiers
+            .iter()
+            .filter_map(|m| m.terrain_type_filter),
+    );
+    referenced.extend(
+        rules
+            .spawn_schedule
+            .entries
+            .iter()
+            .map(|e| e.entity_type_id),
+    );
+    referenced
+}
+
+fn collect_expr_types(expr: &ConstraintExpr, referenced: &mut HashSet<TypeId>) {
+    match expr {
+        ConstraintExpr::IsType { entity_type_id, .. }
+        | ConstraintExpr::IsNotType { entity_type_id, .. } => {
+            referenced.insert(*entity_type_id);
+        }
+        ConstraintExpr::Proximity {
+            filter,
+            line_of_sight,
+            ..
+        } => {
+            referenced.extend(filter.entity_type_id);
+            referenced.extend(line_of_sight.iter().flatten());
+        }
+        ConstraintExpr::All(children) | ConstraintExpr::Any(children) => {
+            for child in children {
+                collect_expr_types(child, referenced);
+            }
+        }
+        ConstraintExpr::Not(inner) => collect_expr_types(inner, referenced),
+        _ => {}
+    }
 }
 
 #[cfg(test)]
@@ -123,6 +812,7 @@ mod tests {
         assert!(moves.valid_positions.is_empty());
         assert!(moves.blocked_explanations.is_empty());
         assert!(moves.for_entity.is_none());
+        assert!(moves.paths.is_empty());
     }
 
     #[test]
@@ -176,6 +866,58 @@ mod tests {
         assert_eq!(moves.valid_positions.len(), 1);
     }
 
+    fn step(from: HexPosition, amount: i64, total_cost: i64) -> PathStep {
+        PathStep {
+            from,
+            components: vec![CostComponent {
+                source: CostSource::Terrain,
+                label: "Terrain Movement Cost".to_string(),
+                amount,
+            }],
+            total_cost,
+        }
+    }
+
+    #[test]
+    fn route_follows_predecessors_back_to_start() {
+        let mut moves = ValidMoveSet::default();
+        let start = HexPosition::new(0, 0);
+        let a = HexPosition::new(1, 0);
+        let b = HexPosition::new(2, 0);
+        moves.paths.insert(a, step(start, 1, 1));
+        moves.paths.insert(b, step(a, 2, 3));
+
+        assert_eq!(moves.route(b), Some(vec![start, a, b]));
+        let steps = moves.route_steps(b).expect("reachable");
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].0, a);
+        assert_eq!(steps[1].1.step_cost(), 2);
+        assert_eq!(steps[1].1.total_cost, 3);
+        assert_eq!(moves.route(HexPosition::new(5, 5)), None);
+    }
+
+    #[test]
+    fn route_rejects_cyclic_predecessors() {
+        let mut moves = ValidMoveSet::default();
+        let a = HexPosition::new(1, 0);
+        let b = HexPosition::new(2, 0);
+        moves.paths.insert(a, step(b, 1, 1));
+        moves.paths.insert(b, step(a, 1, 2));
+        assert_eq!(moves.route(b), None);
+    }
+
+    #[test]
+    fn clear_empties_routes() {
+        let mut moves = ValidMoveSet::default();
+        let a = HexPosition::new(1, 0);
+        moves.valid_positions.insert(a);
+        moves.paths.insert(a, step(HexPosition::new(0, 0), 1, 1));
+        moves.clear();
+        assert!(moves.valid_positions.is_empty());
+        assert!(moves.paths.is_empty());
+        assert!(moves.for_entity.is_none());
+    }
+
     #[test]
     fn valid_move_set_with_blocked_explanations() {
         let mut moves = ValidMoveSet::default();
@@ -235,4 +977,475 @@ mod tests {
         let debug = format!("{result:?}");
         assert!(debug.contains("test"));
     }
+
+    #[test]
+    fn action_eligibility_lists_denials_per_action() {
+        let denial = ValidationResult {
+            constraint_id: TypeId::new(),
+            constraint_name: "Command range".to_string(),
+            satisfied: false,
+            explanation: "out of command".to_string(),
+        };
+        let eligibility = ActionEligibility {
+            denied: vec![(GatedAction::Move, denial.clone())],
+        };
+        assert!(!eligibility.allows(GatedAction::Move));
+        assert!(eligibility.allows(GatedAction::Attack));
+        assert_eq!(
+            eligibility.denials(GatedAction::Move).collect::<Vec<_>>(),
+            vec![&denial]
+        );
+        assert!(ActionEligibility::default().allows(GatedAction::Move));
+    }
+
+    // -- Rule analysis --
+
+    use crate::game_system::{EntityType, PropertyDefinition};
+    use crate::hex_grid::ReachabilityRule;
+    use crate::mechanics::{CombatModifierDefinition, Phase, SpawnEntry};
+    use crate::ontology::{
+        Concept, ConceptBinding, ConceptRole, Constraint, PropertyBinding, RelationTrigger,
+    };
+    use crate::simulation::{ColumnType, TableColumn, TableRow, evaluate_column_modifiers};
+
+    #[derive(Default)]
+    struct Rules {
+        entity_types: EntityTypeRegistry,
+        concepts: ConceptRegistry,
+        relations: RelationRegistry,
+        constraints: ConstraintRegistry,
+        turn_structure: TurnStructure,
+        crt: CombatResultsTable,
+        combat_tables: CombatTableRegistry,
+        combat_modifiers: CombatModifierRegistry,
+        influence_rules: InfluenceRuleRegistry,
+        stacking_rule: StackingRule,
+        movement_cost_matrix: MovementCostMatrix,
+        state_machines: StateMachineRegistry,
+        reachability_rules: ReachabilityRuleRegistry,
+        spawn_schedule: SpawnSchedule,
+        victory_conditions: VictoryConditionRegistry,
+        instances: Vec<EntityData>,
+    }
+
+    impl Rules {
+        fn findings(&self, category: AnalysisCategory) -> Vec<AnalysisSubject> {
+            let instances: Vec<&EntityData> = self.instances.iter().collect();
+            analyze_rules(&AnalyzedRules {
+                entity_types: &self.entity_types,
+                concepts: &self.concepts,
+                relations: &self.relations,
+                constraints: &self.constraints,
+                turn_structure: &self.turn_structure,
+                crt: &self.crt,
+                combat_tables: &self.combat_tables,
+                combat_modifiers: &self.combat_modifiers,
+                influence_rules: &self.influence_rules,
+                stacking_rule: &self.stacking_rule,
+                movement_cost_matrix: &self.movement_cost_matrix,
+                state_machines: &self.state_machines,
+                reachability_rules: &self.reachability_rules,
+                spawn_schedule: &self.spawn_schedule,
+                victory_conditions: &self.victory_conditions,
+                instances: &instances,
+            })
+            .into_iter()
+            .filter(|f| f.category == category)
+            .map(|f| f.subject)
+            .collect()
+        }
+
+        fn add_type(&mut self, role: EntityRole, properties: Vec<PropertyDefinition>) -> TypeId {
+            let id = TypeId::new();
+            self.entity_types.types.push(EntityType {
+                id,
+                name: format!("Type {}", self.entity_types.types.len()),
+                role,
+                color: bevy::color::Color::WHITE,
+                properties,
+            });
+            id
+        }
+
+        /// Binds `type_id` to `role_id`, exposing each property under its name.
+        fn bind(&mut self, concept_id: TypeId, role_id: TypeId, type_id: TypeId) {
+            let property_bindings = self
+                .entity_types
+                .get(type_id)
+                .map(|t| {
+                    t.properties
+                        .iter()
+                        .map(|p| PropertyBinding {
+                            property_id: p.id,
+                            concept_local_name: p.name.clone(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            self.concepts.bindings.push(ConceptBinding {
+                id: TypeId::new(),
+                entity_type_id: type_id,
+                concept_id,
+                concept_role_id: role_id,
+                property_bindings,
+            });
+        }
+
+        /// A concept with a unit role and a terrain role, each bound to one
+        /// type with the given properties.
+        fn motion(
+            &mut self,
+            unit_properties: Vec<PropertyDefinition>,
+            terrain_properties: Vec<PropertyDefinition>,
+        ) -> Motion {
+            let motion = Motion {
+                concept_id: TypeId::new(),
+                unit_role: TypeId::new(),
+                terrain_role: TypeId::new(),
+                unit_type: self.add_type(EntityRole::Token, unit_properties),
+                terrain_type: self.add_type(EntityRole::BoardPosition, terrain_properties),
+            };
+            self.concepts.concepts.push(Concept {
+                id: motion.concept_id,
+                name: "Motion".to_string(),
+                description: String::new(),
+                role_labels: vec![
+                    ConceptRole {
+                        id: motion.unit_role,
+                        name: "unit".to_string(),
+                        allowed_entity_roles: vec![EntityRole::Token],
+                    },
+                    ConceptRole {
+                        id: motion.terrain_role,
+                        name: "terrain".to_string(),
+                        allowed_entity_roles: vec![EntityRole::BoardPosition],
+                    },
+                ],
+            });
+            self.bind(motion.concept_id, motion.unit_role, motion.unit_type);
+            self.bind(motion.concept_id, motion.terrain_role, motion.terrain_type);
+            motion
+        }
+
+        fn relate(&mut self, motion: &Motion, effect: RelationEffect) -> TypeId {
+            let id = TypeId::new();
+            self.relations.relations.push(Relation {
+                id,
+                name: format!("Relation {}", self.relations.relations.len()),
+                concept_id: motion.concept_id,
+                subject_role_id: motion.unit_role,
+                object_role_id: motion.terrain_role,
+                trigger: RelationTrigger::OnEnter,
+                effect,
+            });
+            id
+        }
+
+        fn constrain(&mut self, motion: &Motion, expression: ConstraintExpr) -> TypeId {
+            let id = TypeId::new();
+            self.constraints.constraints.push(Constraint {
+                id,
+                name: "Constraint".to_string(),
+                description: String::new(),
+                concept_id: motion.concept_id,
+                relation_id: None,
+                expression,
+                auto_generated: false,
+                gates: Vec::new(),
+            });
+            id
+        }
+    }
+
+    struct Motion {
+        concept_id: TypeId,
+        unit_role: TypeId,
+        terrain_role: TypeId,
+        unit_type: TypeId,
+        terrain_type: TypeId,
+    }
+
+    fn property(
+        name: &str,
+        property_type: PropertyType,
+        default_value: PropertyValue,
+    ) -> PropertyDefinition {
+        PropertyDefinition {
+            id: TypeId::new(),
+            name: name.to_string(),
+            property_type,
+            default_value,
+        }
+    }
+
+    fn phase(phase_type: PhaseType) -> Phase {
+        Phase {
+            id: TypeId::new(),
+            name: format!("{phase_type:?}"),
+            phase_type,
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn block_and_allow_on_same_type_pair_conflict() {
+        let mut rules = Rules::default();
+        let motion = rules.motion(Vec::new(), Vec::new());
+        let block = rules.relate(&motion, RelationEffect::Block { condition: None });
+        rules.relate(&motion, RelationEffect::Allow { condition: None });
+        assert_eq!(
+            rules.findings(AnalysisCategory::Conflict),
+            vec![AnalysisSubject::Relation(block)]
+        );
+
+        // Two conditional relations may never hold together.
+        let condition = ConstraintExpr::All(Vec::new());
+        for relation in &mut rules.relations.relations {
+            match &mut relation.effect {
+                RelationEffect::Block { condition: c } | RelationEffect::Allow { condition: c } => {
+                    *c = Some(condition.clone());
+                }
+                RelationEffect::ModifyProperty { .. } => {}
+            }
+        }
+        assert!(rules.findings(AnalysisCategory::Conflict).is_empty());
+    }
+
+    #[test]
+    fn constraints_outside_property_ranges_are_unsatisfiable() {
+        let mut rules = Rules::default();
+        let strength = property(
+            "strength",
+            PropertyType::IntRange { min: 0, max: 5 },
+            PropertyValue::IntRange(3),
+        );
+        let motion = rules.motion(vec![strength], Vec::new());
+        let compare = |operator, value| ConstraintExpr::PropertyCompare {
+            role_id: motion.unit_role,
+            property_name: "strength".to_string(),
+            operator,
+            value: PropertyValue::Int(value),
+        };
+        let above = rules.constrain(&motion, compare(CompareOp::Gt, 5));
+        rules.constrain(&motion, compare(CompareOp::Ge, 5));
+        let negated = rules.constrain(
+            &motion,
+            ConstraintExpr::Not(Box::new(compare(CompareOp::Le, 5))),
+        );
+        let wrong_type = rules.constrain(
+            &motion,
+            ConstraintExpr::IsType {
+                role_id: motion.unit_role,
+                entity_type_id: motion.terrain_type,
+            },
+        );
+        rules.constrain(
+            &motion,
+            ConstraintExpr::Any(vec![compare(CompareOp::Gt, 5), compare(CompareOp::Eq, 0)]),
+        );
+        assert_eq!(
+            rules.findings(AnalysisCategory::Unsatisfiable),
+            vec![
+                AnalysisSubject::Constraint(above),
+                AnalysisSubject::Constraint(negated),
+                AnalysisSubject::Constraint(wrong_type),
+            ]
+        );
+    }
+
+    #[test]
+    fn relation_with_zero_source_has_no_effect() {
+        let mut rules = Rules::default();
+        let cost = property("cost", PropertyType::Int, PropertyValue::Int(0));
+        let cost_id = cost.id;
+        let budget = property("budget", PropertyType::Int, PropertyValue::Int(4));
+        let motion = rules.motion(vec![budget], vec![cost]);
+        let relation = rules.relate(
+            &motion,
+            RelationEffect::ModifyProperty {
+                target_property: "budget".to_string(),
+                source_property: "cost".to_string(),
+                operation: ModifyOperation::Subtract,
+            },
+        );
+        assert_eq!(
+            rules.findings(AnalysisCategory::NoEffect),
+            vec![AnalysisSubject::Relation(relation)]
+        );
+
+        // A placed tile overriding the cost gives the relation an effect.
+        rules.instances.push(EntityData {
+            entity_type_id: motion.terrain_type,
+            properties: HashMap::from([(cost_id, PropertyValue::Int(2))]),
+        });
+        assert!(rules.findings(AnalysisCategory::NoEffect).is_empty());
+    }
+
+    fn ratio_columns(thresholds: &[f64]) -> Vec<TableColumn> {
+        thresholds
+            .iter()
+            .map(|&threshold| TableColumn {
+                label: format!("{threshold}:1"),
+                column_type: ColumnType::Ratio,
+                threshold,
+            })
+            .collect()
+    }
+
+    fn modifier(column_shift: i32, priority: i32, cap: Option<i32>) -> CombatModifierDefinition {
+        CombatModifierDefinition {
+            id: TypeId::new(),
+            name: format!("Shift {column_shift}"),
+            source: crate::mechanics::ModifierSource::DefenderTerrain,
+            column_shift,
+            priority,
+            cap,
+            terrain_type_filter: None,
+            condition: None,
+        }
+    }
+
+    #[test]
+    fn capped_modifiers_leave_shadowed_columns_unreachable() {
+        let mut rules = Rules::default();
+        // Columns 1 and 2 are shadowed by column 3's equal threshold.
+        rules.crt.table.columns = ratio_columns(&[1.0, 5.0, 5.0, 5.0, 9.0]);
+        let crt = rules.crt.id;
+        assert_eq!(
+            rules.findings(AnalysisCategory::UnreachableColumn),
+            vec![
+                AnalysisSubject::CrtColumn(crt, 1),
+                AnalysisSubject::CrtColumn(crt, 2),
+            ]
+        );
+
+        // A -2 shift capped at 1 only reaches one column left.
+        rules
+            .combat_modifiers
+            .modifiers
+            .push(modifier(-2, 0, Some(1)));
+        assert_eq!(
+            rules.findings(AnalysisCategory::UnreachableColumn),
+            vec![AnalysisSubject::CrtColumn(crt, 1)]
+        );
+    }
+
+    #[test]
+    fn reachable_shifts_match_evaluated_modifier_subsets() {
+        // Priorities decide whether a cap cuts a shift before or after the
+        // others add to it; negative priorities evaluate as zero.
+        let registry = CombatModifierRegistry {
+            modifiers: vec![
+                modifier(3, -1, None),
+                modifier(-1, 2, Some(1)),
+                modifier(2, 0, Some(2)),
+                modifier(1, 1, None),
+            ],
+        };
+        let modifiers = registry.column_modifiers();
+        let shifts = reachable_shifts(&modifiers, 4);
+        for subset in 0..1_u32 << modifiers.len() {
+            let applied: Vec<ColumnModifier> = modifiers
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| subset & (1 << i) != 0)
+                .map(|(_, m)| m.clone())
+                .collect();
+            let (total, _) = evaluate_column_modifiers(&applied, 5);
+            assert!(shifts.contains(&total), "{total} missing for {subset:b}");
+        }
+    }
+
+    #[test]
+    fn conditional_tables_are_analysed_too() {
+        let mut rules = Rules::default();
+        let combat = phase(PhaseType::Combat);
+        let combat_id = combat.id;
+        rules.turn_structure.phases = vec![combat];
+        rules.add_type(EntityRole::Token, Vec::new());
+        let mut night = CombatResultsTable {
+            name: "Night".to_string(),
+            ..CombatResultsTable::default()
+        };
+        night.table.columns = ratio_columns(&[1.0, 3.0, 3.0]);
+        night.table.rows = vec![TableRow {
+            label: "1".to_string(),
+            value_min: 1,
+            value_max: 1,
+        }];
+        let night_id = night.id;
+        assert_eq!(
+            rules.findings(AnalysisCategory::EmptyPhase),
+            vec![AnalysisSubject::Phase(combat_id)]
+        );
+
+        // The default CRT is empty, but the conditional table has cells.
+        rules.combat_tables.tables.push(night);
+        assert!(rules.findings(AnalysisCategory::EmptyPhase).is_empty());
+        assert_eq!(
+            rules.findings(AnalysisCategory::UnreachableColumn),
+            vec![AnalysisSubject::CrtColumn(night_id, 1)]
+        );
+    }
+
+    #[test]
+    fn phases_without_possible_actions_are_empty() {
+        let mut rules = Rules::default();
+        let movement = phase(PhaseType::Movement);
+        let combat = phase(PhaseType::Combat);
+        let admin = phase(PhaseType::Admin);
+        let (movement_id, combat_id, admin_id) = (movement.id, combat.id, admin.id);
+        rules.turn_structure.phases = vec![movement, combat, admin];
+        assert_eq!(
+            rules.findings(AnalysisCategory::EmptyPhase),
+            vec![
+                AnalysisSubject::Phase(movement_id),
+                AnalysisSubject::Phase(combat_id),
+                AnalysisSubject::Phase(admin_id),
+            ]
+        );
+
+        // Units can move, but the empty CRT still leaves combat empty; a
+        // trace at the admin phase gives it work.
+        let unit = rules.add_type(EntityRole::Token, Vec::new());
+        rules.reachability_rules.rules.push(ReachabilityRule {
+            id: TypeId::new(),
+            name: "Supply".to_string(),
+            traced_types: vec![unit],
+            sources: Vec::new(),
+            max_cost: 3,
+            blockers: crate::hex_grid::ReachabilityBlockers::default(),
+            phases: vec![admin_id],
+        });
+        assert_eq!(
+            rules.findings(AnalysisCategory::EmptyPhase),
+            vec![AnalysisSubject::Phase(combat_id)]
+        );
+    }
+
+    #[test]
+    fn types_no_rule_references_are_flagged() {
+        let mut rules = Rules::default();
+        let motion = rules.motion(Vec::new(), Vec::new());
+        let spawned = rules.add_type(EntityRole::Token, Vec::new());
+        let unused = rules.add_type(EntityRole::Token, Vec::new());
+        let marker = rules.add_type(EntityRole::BoardPosition, Vec::new());
+        rules.spawn_schedule.entries.push(SpawnEntry {
+            entity_type_id: spawned,
+            turn: 2,
+            hex: HexPosition::new(0, 0),
+            source_zone: String::new(),
+        });
+        rules.constrain(
+            &motion,
+            ConstraintExpr::Not(Box::new(ConstraintExpr::IsType {
+                role_id: motion.terrain_role,
+                entity_type_id: marker,
+            })),
+        );
+        assert_eq!(
+            rules.findings(AnalysisCategory::Unreferenced),
+            vec![AnalysisSubject::EntityType(unused)]
+        );
+    }
 }
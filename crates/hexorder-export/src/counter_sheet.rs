@@ -121,6 +121,8 @@ struct CounterInfo {
     color: (f32, f32, f32),
     /// Up to three numeric property values as (name, value) pairs.
     values: Vec<(String, String)>,
+    /// Short unit id printed on counters for placed units.
+    serial: Option<String>,
 }
 
 /// Generate the counter sheet PDF bytes.
@@ -177,7 +179,7 @@ fn collect_counters(data: &ExportData, token_types: &[&EntityType]) -> Vec<Count
     // One counter per placed token instance.
     data.token_entities
         .iter()
-        .filter_map(|(_pos, entity_data)| {
+        .filter_map(|(_pos, entity_data, unit_id)| {
             let entity_type = data
                 .entity_types
                 .iter()
@@ -188,6 +190,7 @@ fn collect_counters(data: &ExportData, token_types: &[&EntityType]) -> Vec<Count
                 name: entity_type.name.clone(),
                 color: bevy_color_to_rgb(entity_type.color),
                 values,
+                serial: Some(unit_id.short()),
             })
         })
         .collect()
@@ -209,6 +212,7 @@ fn type_to_counter(entity_type: &EntityType) -> CounterInfo {
         name: entity_type.name.clone(),
         color: bevy_color_to_rgb(entity_type.color),
         values,
+        serial: None,
     }
 }
 
@@ -331,6 +335,30 @@ fn render_page(counters: &[CounterInfo], cols: usize, size_mm: f32) -> Vec<Op> {
         });
         ops.push(Op::EndTextSection);
 
+        // Unit id (small, top center) so a printed counter maps back to its unit.
+        if let Some(serial) = &counter.serial {
+            let serial_font_pt = size_mm * 0.16;
+            let serial_x = x + size_mm * 0.5 - estimate_text_width(serial, serial_font_pt) * 0.5;
+            ops.push(Op::StartTextSection);
+            ops.push(Op::SetFillColor {
+                col: text_color.clone(),
+            });
+            ops.push(Op::SetFont {
+                font: name_font.clone(),
+                size: Pt(serial_font_pt),
+            });
+            ops.push(Op::SetTextCursor {
+                pos: Point {
+                    x: Mm(serial_x).into(),
+                    y: Mm(y + size_mm * 0.88).into(),
+                },
+            });
+            ops.push(Op::ShowText {
+                items: vec![TextItem::Text(serial.clone())],
+            });
+            ops.push(Op::EndTextSection);
+        }
+
         // Property values along the bottom of the counter.
         render_property_values(
             &mut ops,
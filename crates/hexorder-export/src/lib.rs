@@ -5,10 +5,11 @@
 //! produce specific output formats (PDF, JSON, etc.).
 //!
 //! The first export target is print-and-play PDF (counter sheets + hex maps).
+//! Editor panels can also save CSV data through `ExportCsvEvent`.
 
 use bevy::prelude::*;
 
-use hexorder_contracts::game_system::{EntityData, EntityType, EntityTypeRegistry};
+use hexorder_contracts::game_system::{EntityData, EntityType, EntityTypeRegistry, UnitId};
 use hexorder_contracts::hex_grid::{HexGridConfig, HexPosition};
 use hexorder_contracts::shortcuts::{
     CommandCategory, CommandEntry, CommandId, KeyBinding, Modifiers, ShortcutRegistry,
@@ -34,8 +35,8 @@ pub struct ExportData {
     pub entity_types: Vec<EntityType>,
     /// Board position entities (hex tiles) with their position and data.
     pub board_entities: Vec<(HexPosition, EntityData)>,
-    /// Token entities (units) with their position and data.
-    pub token_entities: Vec<(HexPosition, EntityData)>,
+    /// Token entities (units) with their position, data and persistent id.
+    pub token_entities: Vec<(HexPosition, EntityData, UnitId)>,
     /// Grid configuration (layout, map radius).
     pub grid_config: GridSnapshot,
 }
@@ -119,7 +120,7 @@ pub(crate) fn collect_export_data(
     entity_types: &EntityTypeRegistry,
     grid_config: &HexGridConfig,
     tiles: &[(HexPosition, EntityData)],
-    tokens: &[(HexPosition, EntityData)],
+    tokens: &[(HexPosition, EntityData, UnitId)],
 ) -> ExportData {
     ExportData {
         entity_types: entity_types.types.clone(),
@@ -139,7 +140,8 @@ pub(crate) fn collect_export_data(
 /// Plugin that provides the export pipeline.
 ///
 /// Registers export commands in the shortcut registry and handles
-/// `CommandExecutedEvent` to trigger exports.
+/// `CommandExecutedEvent` to trigger exports, and `ExportCsvEvent` to save
+/// CSV files.
 #[derive(Debug)]
 pub struct ExportPlugin;
 
@@ -149,7 +151,14 @@ impl Plugin for ExportPlugin {
         register_shortcuts(&mut registry);
 
         app.add_observer(systems::handle_export_command);
-        app.add_systems(Update, systems::poll_pending_export);
+        app.add_observer(systems::handle_export_csv);
+        app.add_systems(
+            Update,
+            (
+                systems::poll_pending_export,
+                systems::poll_pending_csv_export,
+            ),
+        );
     }
 }
 
@@ -8,8 +8,8 @@ use std::task::{Context, Poll, Waker};
 
 use bevy::prelude::*;
 
-use hexorder_contracts::editor_ui::{ToastEvent, ToastKind};
-use hexorder_contracts::game_system::{EntityData, EntityTypeRegistry, UnitInstance};
+use hexorder_contracts::editor_ui::{ExportCsvEvent, ToastEvent, ToastKind};
+use hexorder_contracts::game_system::{EntityData, EntityTypeRegistry, UnitId, UnitInstance};
 use hexorder_contracts::hex_grid::{HexGridConfig, HexPosition, HexTile};
 use hexorder_contracts::shortcuts::{CommandExecutedEvent, CommandId};
 
@@ -20,6 +20,9 @@ use super::{ExportData, ExportError, ExportTarget, collect_export_data};
 /// Type alias for the boxed folder-picker future.
 type FolderFuture = Pin<Box<dyn Future<Output = Option<std::path::PathBuf>> + Send>>;
 
+/// Type alias for the boxed save-file future; the same shape as a folder pick.
+type FileFuture = FolderFuture;
+
 /// Spawn an async folder-picker dialog for export output.
 ///
 /// **Must be called on the main thread** so that rfd's macOS backend can
@@ -37,6 +40,25 @@ fn spawn_export_folder_dialog() -> FolderFuture {
     Box::pin(std::future::pending())
 }
 
+/// Spawn an async save-file dialog for a CSV export, suggesting `file_name`.
+///
+/// **Must be called on the main thread** — see [`spawn_export_folder_dialog`].
+#[cfg(not(test))]
+fn spawn_csv_save_dialog(file_name: &str) -> FileFuture {
+    let dialog = rfd::AsyncFileDialog::new()
+        .set_title("Export CSV")
+        .add_filter("CSV", &["csv"])
+        .set_file_name(format!("{file_name}.csv"));
+    let save_future = dialog.save_file();
+    Box::pin(async move { save_future.await.map(|h| h.path().to_path_buf()) })
+}
+
+/// Test stub: returns a pending future (rfd requires the main thread).
+#[cfg(test)]
+fn spawn_csv_save_dialog(_file_name: &str) -> FileFuture {
+    Box::pin(std::future::pending())
+}
+
 // ---------------------------------------------------------------------------
 // Async Export Dialog
 // ---------------------------------------------------------------------------
@@ -61,6 +83,24 @@ impl std::fmt::Debug for PendingExport {
     }
 }
 
+/// Holds the in-flight CSV save dialog and the contents to write once a
+/// path is picked. Only one CSV dialog at a time.
+#[derive(Resource)]
+pub(crate) struct PendingCsvExport {
+    pub contents: String,
+    /// Wrapped in `Mutex` for `Resource: Sync`, as in `PendingExport`.
+    pub future: Mutex<FileFuture>,
+}
+
+impl std::fmt::Debug for PendingCsvExport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingCsvExport")
+            .field("contents", &self.contents)
+            .field("future", &"<Future>")
+            .finish()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Systems
 // ---------------------------------------------------------------------------
@@ -89,11 +129,13 @@ pub(crate) fn handle_export_command(trigger: On<CommandExecutedEvent>, mut comma
             .iter(world)
             .map(|(pos, data)| (*pos, data.clone()))
             .collect();
-        let tokens: Vec<_> = world
-            .query_filtered::<(&HexPosition, &EntityData), With<UnitInstance>>()
+        let mut tokens: Vec<_> = world
+            .query_filtered::<(&HexPosition, &EntityData, &UnitId), With<UnitInstance>>()
             .iter(world)
-            .map(|(pos, data)| (*pos, data.clone()))
+            .map(|(pos, data, id)| (*pos, data.clone(), *id))
             .collect();
+        // Stable counter order across exports, independent of entity order.
+        tokens.sort_by_key(|(_, _, id)| id.0);
 
         let entity_types = world.resource::<EntityTypeRegistry>();
         let grid_config = world.resource::<HexGridConfig>();
@@ -216,3 +258,62 @@ pub(crate) fn run_exporters(
         });
     }
 }
+
+/// Handles a CSV export request by opening a save dialog. The file is
+/// written in `poll_pending_csv_export` once a path is picked.
+pub(crate) fn handle_export_csv(trigger: On<ExportCsvEvent>, mut commands: Commands) {
+    let request = trigger.event().clone();
+    commands.queue(move |world: &mut World| {
+        // Guard: only one CSV dialog at a time.
+        if world.get_resource::<PendingCsvExport>().is_some() {
+            return;
+        }
+        let future = spawn_csv_save_dialog(&request.file_name);
+        world.insert_resource(PendingCsvExport {
+            contents: request.contents,
+            future: Mutex::new(future),
+        });
+    });
+}
+
+/// Polls the in-flight CSV save dialog each frame and writes the file when
+/// a path is picked.
+pub(crate) fn poll_pending_csv_export(world: &mut World) {
+    let result = {
+        let Some(pending) = world.get_resource_mut::<PendingCsvExport>() else {
+            return;
+        };
+        let waker = Waker::noop();
+        let mut cx = Context::from_waker(waker);
+        let mut guard = pending
+            .future
+            .lock()
+            .expect("csv export future mutex not poisoned");
+        match guard.as_mut().poll(&mut cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return,
+        }
+    };
+
+    let pending = world
+        .remove_resource::<PendingCsvExport>()
+        .expect("checked above");
+    let Some(path) = result else {
+        return; // User cancelled.
+    };
+
+    let toast = match std::fs::write(&path, pending.contents) {
+        Ok(()) => {
+            info!("Exported CSV to {}", path.display());
+            ToastEvent {
+                message: format!("Exported {}", path.display()),
+                kind: ToastKind::Success,
+            }
+        }
+        Err(e) => ToastEvent {
+            message: format!("CSV export failed: {e}"),
+            kind: ToastKind::Error,
+        },
+    };
+    world.trigger(toast);
+}
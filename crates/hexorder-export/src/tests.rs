@@ -123,6 +123,7 @@ fn collect_export_data_captures_all_fields() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 3,
+        shape: hexorder_contracts::hex_grid::GridShape::Hexagon,
     };
 
     let tiles = vec![(
@@ -139,6 +140,7 @@ fn collect_export_data_captures_all_fields() {
             entity_type_id: registry.types[1].id,
             properties: std::collections::HashMap::new(),
         },
+        UnitId::new(),
     )];
 
     let data = collect_export_data(&registry, &grid_config, &tiles, &tokens);
@@ -146,6 +148,7 @@ fn collect_export_data_captures_all_fields() {
     assert_eq!(data.entity_types.len(), 2);
     assert_eq!(data.board_entities.len(), 1);
     assert_eq!(data.token_entities.len(), 1);
+    assert_eq!(data.token_entities[0].2, tokens[0].2);
     assert_eq!(data.grid_config.map_radius, 3);
     assert!(data.grid_config.pointy_top);
 }
@@ -160,6 +163,7 @@ fn collect_export_data_handles_empty_state() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 0,
+        shape: hexorder_contracts::hex_grid::GridShape::Hexagon,
     };
 
     let data = collect_export_data(&registry, &grid_config, &[], &[]);
@@ -187,6 +191,7 @@ fn grid_snapshot_captures_flat_top_orientation() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 10,
+        shape: hexorder_contracts::hex_grid::GridShape::Hexagon,
     };
 
     let data = collect_export_data(&EntityTypeRegistry::default(), &grid_config, &[], &[]);
@@ -569,6 +574,65 @@ fn poll_removes_resource_when_user_cancels() {
     );
 }
 
+#[test]
+fn csv_export_request_opens_one_dialog() {
+    let mut app = bevy::app::App::new();
+    app.add_plugins(bevy::MinimalPlugins);
+    app.add_observer(systems::handle_export_csv);
+    let request = |contents: &str| hexorder_contracts::editor_ui::ExportCsvEvent {
+        file_name: "crt-odds".to_string(),
+        contents: contents.to_string(),
+    };
+    app.world_mut().trigger(request("first"));
+    app.world_mut().trigger(request("second"));
+    app.update();
+
+    let pending = app
+        .world()
+        .get_resource::<systems::PendingCsvExport>()
+        .expect("the request opened a save dialog");
+    assert_eq!(
+        pending.contents, "first",
+        "a second request waits for the open dialog"
+    );
+}
+
+#[test]
+fn poll_writes_csv_to_picked_path() {
+    let mut app = bevy::app::App::new();
+    app.add_plugins(bevy::MinimalPlugins);
+    app.add_systems(bevy::app::Update, systems::poll_pending_csv_export);
+
+    let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let received_clone = received.clone();
+    app.add_observer(move |trigger: On<ToastEvent>| {
+        received_clone
+            .lock()
+            .expect("lock")
+            .push(trigger.event().clone());
+    });
+
+    let path = std::env::temp_dir().join(format!("hexorder-csv-test-{}.csv", std::process::id()));
+    let path_for_task = path.clone();
+    app.insert_resource(systems::PendingCsvExport {
+        contents: "column,NE\n".to_string(),
+        future: std::sync::Mutex::new(Box::pin(async move { Some(path_for_task) })),
+    });
+    app.update();
+
+    assert!(
+        app.world()
+            .get_resource::<systems::PendingCsvExport>()
+            .is_none()
+    );
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("csv written"),
+        "column,NE\n"
+    );
+    assert_eq!(received.lock().expect("lock")[0].kind, ToastKind::Success);
+    let _ = std::fs::remove_file(&path);
+}
+
 // ---------------------------------------------------------------------------
 // Counter Sheet — format_property_value extended variants
 // ---------------------------------------------------------------------------
@@ -684,6 +748,7 @@ fn counter_sheet_renders_token_instances_with_properties() {
                 entity_type_id: type_id,
                 properties: instance_props,
             },
+            UnitId::new(),
         )],
         grid_config: GridSnapshot {
             map_radius: 3,
@@ -795,6 +860,7 @@ fn counter_sheet_token_with_orphan_type_skipped() {
                     entity_type_id: known_type,
                     properties: std::collections::HashMap::new(),
                 },
+                UnitId::new(),
             ),
             (
                 HexPosition::new(1, 0),
@@ -802,6 +868,7 @@ fn counter_sheet_token_with_orphan_type_skipped() {
                     entity_type_id: orphan_type,
                     properties: std::collections::HashMap::new(),
                 },
+                UnitId::new(),
             ),
         ],
         grid_config: GridSnapshot {
@@ -859,6 +926,7 @@ fn counter_sheet_many_counters_spans_multiple_pages() {
                     entity_type_id: type_id,
                     properties: std::collections::HashMap::new(),
                 },
+                UnitId::new(),
             )
         })
         .collect();
@@ -1128,6 +1196,7 @@ fn handle_export_command_noop_when_pending_export_exists() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 3,
+        shape: hexorder_contracts::hex_grid::GridShape::Hexagon,
     });
 
     // Insert an existing PendingExport — guard should prevent creating another.
@@ -1199,6 +1268,7 @@ fn handle_export_command_happy_path_creates_pending_export() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 3,
+        shape: hexorder_contracts::hex_grid::GridShape::Hexagon,
     });
     app.insert_resource(EntityTypeRegistry {
         types: vec![
@@ -1324,6 +1394,7 @@ fn test_export_data() -> ExportData {
                 entity_type_id: token_type_id,
                 properties: std::collections::HashMap::new(),
             },
+            UnitId::new(),
         )],
         grid_config: GridSnapshot {
             map_radius: 5,
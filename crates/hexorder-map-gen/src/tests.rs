@@ -453,7 +453,7 @@ use bevy::prelude::*;
 use hexorder_contracts::game_system::{
     EntityData, EntityRole, EntityType, EntityTypeRegistry, PropertyValue, TypeId,
 };
-use hexorder_contracts::hex_grid::{HexGridConfig, HexTile};
+use hexorder_contracts::hex_grid::{GridShape, HexGridConfig, HexTile};
 use hexorder_contracts::map_gen::GenerateMap;
 use hexorder_contracts::persistence::AppScreen;
 use hexorder_contracts::undo_redo::UndoStack;
@@ -485,6 +485,7 @@ fn run_generation_does_nothing_without_generate_map_resource() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
     app.init_resource::<EntityTypeRegistry>();
 
@@ -522,6 +523,7 @@ fn run_generation_removes_generate_map_marker() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
 
     // Create a registry with at least one BoardPosition type.
@@ -569,6 +571,7 @@ fn run_generation_assigns_entity_type_to_tiles() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
 
     // Create a registry with BoardPosition types.
@@ -634,6 +637,7 @@ fn run_generation_records_undo_command() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
 
     let mut registry = EntityTypeRegistry::default();
@@ -685,6 +689,7 @@ fn run_generation_skips_on_invalid_biome_table() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
 
     // Insert an INVALID biome table (empty).
@@ -725,6 +730,7 @@ fn run_generation_skips_on_empty_board_types() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
 
     // Registry has only Token types, no BoardPosition.
@@ -770,6 +776,7 @@ fn run_generation_skips_on_no_tiles() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
     app.init_resource::<EntityTypeRegistry>();
 
@@ -796,6 +803,7 @@ fn run_generation_without_undo_stack_does_not_panic() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
 
     let mut registry = EntityTypeRegistry::default();
@@ -843,6 +851,7 @@ fn map_gen_plugin_builds_without_panic() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
     app.init_resource::<EntityTypeRegistry>();
 
@@ -921,6 +930,7 @@ fn run_generation_populates_default_properties() {
     app.insert_resource(HexGridConfig {
         layout,
         map_radius: 2,
+        shape: GridShape::Hexagon,
     });
 
     let prop_id = TypeId::new();
@@ -151,7 +151,8 @@ mod tests {
         TypeId,
     };
     use hexorder_contracts::hex_grid::{
-        HexEdgeRegistry, HexPosition, InfluenceRuleRegistry, MovementCostMatrix, StackingRule,
+        HexEdgeRegistry, HexPosition, InfluenceRuleRegistry, MovementCostMatrix,
+        ReachabilityRuleRegistry, StackingRule,
     };
     use hexorder_contracts::mechanics::{
         CombatModifierRegistry, CombatResultsTable, TurnStructure,
@@ -191,6 +192,7 @@ mod tests {
                 position: HexPosition::new(0, 0),
                 entity_type_id: type_id,
                 properties: HashMap::new(),
+                state: None,
             }],
             units: Vec::new(),
             workspace_preset: String::new(),
@@ -202,6 +204,16 @@ mod tests {
             spawn_schedule: hexorder_contracts::mechanics::SpawnSchedule::default(),
             accumulator_registry: hexorder_contracts::mechanics::AccumulatorRegistry::default(),
             victory_conditions: hexorder_contracts::mechanics::VictoryConditionRegistry::default(),
+            off_map_zones: hexorder_contracts::mechanics::OffMapZoneRegistry::default(),
+            grid_shape: hexorder_contracts::hex_grid::GridShape::default(),
+            vertex_features: hexorder_contracts::hex_grid::HexVertexRegistry::default(),
+            factions: hexorder_contracts::game_system::FactionRegistry::default(),
+            state_machines: hexorder_contracts::game_system::StateMachineRegistry::default(),
+            reachability_rules: ReachabilityRuleRegistry::default(),
+            movement_interrupts: hexorder_contracts::mechanics::MovementInterruptRegistry::default(
+            ),
+            overruns: hexorder_contracts::mechanics::OverrunRegistry::default(),
+            combat_tables: hexorder_contracts::mechanics::CombatTableRegistry::default(),
         }
     }
 
@@ -1,24 +1,28 @@
 //! Systems for the persistence plugin.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 
 use bevy::prelude::*;
 
 use hexorder_contracts::editor_ui::{ToastEvent, ToastKind};
 use hexorder_contracts::game_system::{
-    EntityData, EntityTypeRegistry, EnumRegistry, GameSystem, SelectedUnit, StructRegistry,
-    UnitInstance,
+    EntityData, EntityState, EntityTypeRegistry, EnumRegistry, FactionRegistry, GameSystem,
+    SelectedUnit, StateMachineRegistry, StateOverrides, StructRegistry, TypeId, UnitId, UnitIndex,
+    UnitInstance, UnitOwner,
 };
 use hexorder_contracts::hex_grid::{
-    HexEdgeRegistry, HexGridConfig, HexPosition, HexTile, InfluenceRuleRegistry, MoveOverlay,
-    MovementCostMatrix, StackingRule,
+    GhostTile, GridShape, HexEdgeRegistry, HexGridConfig, HexPosition, HexTile, HexVertexRegistry,
+    InfluenceRuleRegistry, MoveOverlay, MovementCostMatrix, ReachabilityRuleRegistry, StackingRule,
 };
 use hexorder_contracts::mechanics::{
-    AccumulatorRegistry, ActiveCombat, CombatModifierRegistry, CombatResultsTable, SpawnSchedule,
-    TurnState, TurnStructure, VictoryConditionRegistry,
+    AccumulatorRegistry, ActiveCombat, CombatModifierRegistry, CombatResultsTable,
+    CombatTableRegistry, MovementInterruptRegistry, OffMapZoneRegistry, OverrunRegistry,
+    SpawnSchedule, TurnState, TurnStructure, VictoryConditionRegistry,
+};
+use hexorder_contracts::ontology::{
+    ConceptRegistry, ConstraintRegistry, PresenceEffects, RelationRegistry,
 };
-use hexorder_contracts::ontology::{ConceptRegistry, ConstraintRegistry, RelationRegistry};
 use hexorder_contracts::persistence::{
     AppScreen, CloseProjectEvent, FORMAT_VERSION, GameSystemFile, LoadRequestEvent,
     NewProjectEvent, PendingBoardLoad, SaveRequestEvent, TileSaveData, UnitSaveData, Workspace,
@@ -59,8 +63,8 @@ pub(crate) fn sanitize_filename(name: &str) -> String {
 /// Build a `GameSystemFile` from current world state and pre-collected board data.
 fn build_game_system_file(
     world: &World,
-    tiles: &[(HexPosition, EntityData)],
-    units: &[(HexPosition, EntityData)],
+    tiles: Vec<TileSaveData>,
+    units: Vec<UnitSaveData>,
 ) -> GameSystemFile {
     let workspace = world.resource::<Workspace>();
     let game_system = world.resource::<GameSystem>();
@@ -75,30 +79,20 @@ fn build_game_system_file(
     let combat_modifiers = world.resource::<CombatModifierRegistry>();
     let config = world.resource::<HexGridConfig>();
     let edge_features = world.resource::<HexEdgeRegistry>();
+    let vertex_features = world.resource::<HexVertexRegistry>();
     let influence_rules = world.resource::<InfluenceRuleRegistry>();
     let stacking_rule = world.resource::<StackingRule>();
     let movement_cost_matrix = world.resource::<MovementCostMatrix>();
     let spawn_schedule = world.resource::<SpawnSchedule>();
     let accumulator_registry = world.resource::<AccumulatorRegistry>();
     let victory_conditions = world.resource::<VictoryConditionRegistry>();
-
-    let tile_data: Vec<TileSaveData> = tiles
-        .iter()
-        .map(|(pos, data)| TileSaveData {
-            position: *pos,
-            entity_type_id: data.entity_type_id,
-            properties: data.properties.clone(),
-        })
-        .collect();
-
-    let unit_data: Vec<UnitSaveData> = units
-        .iter()
-        .map(|(pos, data)| UnitSaveData {
-            position: *pos,
-            entity_type_id: data.entity_type_id,
-            properties: data.properties.clone(),
-        })
-        .collect();
+    let off_map_zones = world.resource::<OffMapZoneRegistry>();
+    let factions = world.resource::<FactionRegistry>();
+    let state_machines = world.resource::<StateMachineRegistry>();
+    let reachability_rules = world.resource::<ReachabilityRuleRegistry>();
+    let movement_interrupts = world.resource::<MovementInterruptRegistry>();
+    let overruns = world.resource::<OverrunRegistry>();
+    let combat_tables = world.resource::<CombatTableRegistry>();
 
     GameSystemFile {
         format_version: FORMAT_VERSION,
@@ -114,17 +108,26 @@ fn build_game_system_file(
         combat_results_table: crt.clone(),
         combat_modifiers: combat_modifiers.clone(),
         map_radius: config.map_radius,
-        tiles: tile_data,
-        units: unit_data,
+        grid_shape: config.shape,
+        tiles,
+        units,
         workspace_preset: workspace.workspace_preset.clone(),
         font_size_base: workspace.font_size_base,
         edge_features: edge_features.clone(),
+        vertex_features: vertex_features.clone(),
         influence_rules: influence_rules.clone(),
         stacking_rule: stacking_rule.clone(),
         movement_cost_matrix: movement_cost_matrix.clone(),
         spawn_schedule: spawn_schedule.clone(),
         accumulator_registry: accumulator_registry.clone(),
         victory_conditions: victory_conditions.clone(),
+        off_map_zones: off_map_zones.clone(),
+        factions: factions.clone(),
+        state_machines: state_machines.clone(),
+        reachability_rules: reachability_rules.clone(),
+        movement_interrupts: movement_interrupts.clone(),
+        overruns: overruns.clone(),
+        combat_tables: combat_tables.clone(),
     }
 }
 
@@ -133,16 +136,54 @@ fn build_game_system_file(
 /// No dialog logic — pure file I/O and state update.
 pub(crate) fn save_to_path(path: &std::path::Path, world: &mut World) -> bool {
     // Collect board data via queries (releases world borrow after each block).
-    let tiles: Vec<(HexPosition, EntityData)> = {
-        let mut q = world.query_filtered::<(&HexPosition, &EntityData), With<HexTile>>();
-        q.iter(world).map(|(p, d)| (*p, d.clone())).collect()
+    // Tiles and units are saved with their base values, without state
+    // overrides or `WhilePresent` effects.
+    let tiles: Vec<TileSaveData> = {
+        let mut q = world.query_filtered::<(
+            &HexPosition,
+            &EntityData,
+            Option<&EntityState>,
+            Option<&StateOverrides>,
+        ), With<HexTile>>();
+        q.iter(world)
+            .map(|(p, d, state, overrides)| {
+                let data = overrides.map_or_else(|| d.clone(), |o| o.base_data(d));
+                TileSaveData {
+                    position: *p,
+                    entity_type_id: data.entity_type_id,
+                    properties: data.properties,
+                    state: state.map(|s| s.state_id),
+                }
+            })
+            .collect()
     };
-    let units: Vec<(HexPosition, EntityData)> = {
-        let mut q = world.query_filtered::<(&HexPosition, &EntityData), With<UnitInstance>>();
-        q.iter(world).map(|(p, d)| (*p, d.clone())).collect()
+    let units: Vec<UnitSaveData> = {
+        let mut q = world.query_filtered::<(
+            &HexPosition,
+            &EntityData,
+            &UnitOwner,
+            &UnitId,
+            Option<&PresenceEffects>,
+            Option<&EntityState>,
+            Option<&StateOverrides>,
+        ), With<UnitInstance>>();
+        q.iter(world)
+            .map(|(p, d, owner, id, effects, state, overrides)| {
+                let data = effects.map_or_else(|| d.clone(), |e| e.base_data(d));
+                let data = overrides.map_or_else(|| data.clone(), |o| o.base_data(&data));
+                UnitSaveData {
+                    position: *p,
+                    entity_type_id: data.entity_type_id,
+                    properties: data.properties,
+                    owner: owner.faction_id,
+                    id: *id,
+                    state: state.map(|s| s.state_id),
+                }
+            })
+            .collect()
     };
 
-    let file = build_game_system_file(world, &tiles, &units);
+    let file = build_game_system_file(world, tiles, units);
 
     // Write to disk — scope the storage borrow.
     let write_result = {
@@ -215,12 +256,25 @@ pub(crate) fn load_from_path(path: &std::path::Path, world: &mut World) -> bool
     *world.resource_mut::<CombatResultsTable>() = file.combat_results_table;
     *world.resource_mut::<CombatModifierRegistry>() = file.combat_modifiers;
     *world.resource_mut::<HexEdgeRegistry>() = file.edge_features;
+    *world.resource_mut::<HexVertexRegistry>() = file.vertex_features;
     *world.resource_mut::<InfluenceRuleRegistry>() = file.influence_rules;
     *world.resource_mut::<StackingRule>() = file.stacking_rule;
     *world.resource_mut::<MovementCostMatrix>() = file.movement_cost_matrix;
     *world.resource_mut::<SpawnSchedule>() = file.spawn_schedule;
     *world.resource_mut::<AccumulatorRegistry>() = file.accumulator_registry;
     *world.resource_mut::<VictoryConditionRegistry>() = file.victory_conditions;
+    *world.resource_mut::<OffMapZoneRegistry>() = file.off_map_zones;
+    *world.resource_mut::<FactionRegistry>() = file.factions;
+    *world.resource_mut::<StateMachineRegistry>() = file.state_machines;
+    *world.resource_mut::<ReachabilityRuleRegistry>() = file.reachability_rules;
+    *world.resource_mut::<MovementInterruptRegistry>() = file.movement_interrupts;
+    *world.resource_mut::<OverrunRegistry>() = file.overruns;
+    *world.resource_mut::<CombatTableRegistry>() = file.combat_tables;
+    // The grid plugin keeps this shape when it re-creates the config on
+    // entering the editor.
+    world
+        .get_resource_or_insert_with(HexGridConfig::default)
+        .shape = file.grid_shape;
     *world.resource_mut::<SchemaValidation>() = SchemaValidation::default();
 
     // Derive workspace name: use file name field if present (v3+),
@@ -295,6 +349,7 @@ fn reset_to_new_project(name: &str, world: &mut World) {
         hexorder_contracts::defaults::create_default_crt();
     *world.resource_mut::<CombatModifierRegistry>() = CombatModifierRegistry::default();
     *world.resource_mut::<HexEdgeRegistry>() = HexEdgeRegistry::default();
+    *world.resource_mut::<HexVertexRegistry>() = HexVertexRegistry::default();
     *world.resource_mut::<InfluenceRuleRegistry>() = InfluenceRuleRegistry::default();
     *world.resource_mut::<StackingRule>() = StackingRule::default();
     *world.resource_mut::<MovementCostMatrix>() = MovementCostMatrix::default();
@@ -303,6 +358,16 @@ fn reset_to_new_project(name: &str, world: &mut World) {
     *world.resource_mut::<SpawnSchedule>() = SpawnSchedule::default();
     *world.resource_mut::<AccumulatorRegistry>() = AccumulatorRegistry::default();
     *world.resource_mut::<VictoryConditionRegistry>() = VictoryConditionRegistry::default();
+    *world.resource_mut::<OffMapZoneRegistry>() = OffMapZoneRegistry::default();
+    *world.resource_mut::<FactionRegistry>() = FactionRegistry::default();
+    *world.resource_mut::<StateMachineRegistry>() = StateMachineRegistry::default();
+    *world.resource_mut::<ReachabilityRuleRegistry>() = ReachabilityRuleRegistry::default();
+    *world.resource_mut::<MovementInterruptRegistry>() = MovementInterruptRegistry::default();
+    *world.resource_mut::<OverrunRegistry>() = OverrunRegistry::default();
+    *world.resource_mut::<CombatTableRegistry>() = CombatTableRegistry::default();
+    if let Some(mut config) = world.get_resource_mut::<HexGridConfig>() {
+        config.shape = GridShape::default();
+    }
 
     {
         let mut workspace = world.resource_mut::<Workspace>();
@@ -614,10 +679,16 @@ pub fn handle_file_command(
 pub fn cleanup_editor_entities(
     mut commands: Commands,
     tiles: Query<Entity, With<HexTile>>,
+    ghosts: Query<Entity, With<GhostTile>>,
     units: Query<Entity, With<UnitInstance>>,
     overlays: Query<Entity, With<MoveOverlay>>,
 ) {
-    for entity in tiles.iter().chain(units.iter()).chain(overlays.iter()) {
+    for entity in tiles
+        .iter()
+        .chain(ghosts.iter())
+        .chain(units.iter())
+        .chain(overlays.iter())
+    {
         commands.entity(entity).despawn();
     }
 }
@@ -635,11 +706,24 @@ pub fn cleanup_editor_entities(
 /// Unit entities are spawned with core ECS components only (no mesh/material).
 /// The unit plugin's `sync_unit_visuals` and `sync_unit_materials` systems
 /// will attach visuals on the next frame via change detection.
+///
+/// Units keep their saved `UnitId`. Unit references in tile and unit
+/// properties are resolved against the loaded units (on the board or held
+/// in an off-map zone); references to missing units are cleared, and
+/// `UnitIndex` is filled so references resolve to entities immediately.
+///
+/// Tiles and units get their saved state, or their machine's initial state
+/// when none was saved. The state's overrides are applied on top of the
+/// loaded base values by the rules engine.
+#[allow(clippy::too_many_arguments)]
 pub fn apply_pending_board_load(
     pending: Option<Res<PendingBoardLoad>>,
-    mut tiles: Query<(&HexPosition, &mut EntityData), With<HexTile>>,
+    mut tiles: Query<(Entity, &HexPosition, &mut EntityData), With<HexTile>>,
     tiles_pending_data: Query<(), (With<HexTile>, Without<EntityData>)>,
     config: Res<HexGridConfig>,
+    zones: Option<Res<OffMapZoneRegistry>>,
+    state_machines: Option<Res<StateMachineRegistry>>,
+    mut unit_index: Option<ResMut<UnitIndex>>,
     mut commands: Commands,
 ) {
     let Some(pending) = pending else {
@@ -658,32 +742,107 @@ pub fn apply_pending_board_load(
     let tile_lookup: HashMap<HexPosition, &TileSaveData> =
         pending.tiles.iter().map(|t| (t.position, t)).collect();
 
+    // Units that exist once the load completes, for resolving references.
+    let known_units: HashSet<UnitId> = pending
+        .units
+        .iter()
+        .map(|u| u.id)
+        .chain(
+            zones
+                .iter()
+                .flat_map(|z| z.zones.iter())
+                .flat_map(|zone| zone.units.iter().filter_map(|u| u.id)),
+        )
+        .collect();
+    let mut dangling = 0;
+
     // Apply tile data to existing tile entities via direct mutation
     // (not deferred commands) so the values are visible immediately.
-    for (pos, mut entity_data) in &mut tiles {
+    for (entity, pos, mut entity_data) in &mut tiles {
         if let Some(save_data) = tile_lookup.get(pos) {
             entity_data.entity_type_id = save_data.entity_type_id;
             entity_data.properties.clone_from(&save_data.properties);
+            dangling += entity_data
+                .properties
+                .values_mut()
+                .map(|v| v.clear_dangling_unit_refs(&known_units))
+                .sum::<usize>();
+            // The loaded values are base values: drop override records
+            // from before the load.
+            let mut tile = commands.entity(entity);
+            match loaded_state(
+                state_machines.as_deref(),
+                save_data.entity_type_id,
+                save_data.state,
+            ) {
+                Some(state_id) => {
+                    tile.insert((EntityState { state_id }, StateOverrides::default()));
+                }
+                None => {
+                    tile.remove::<(EntityState, StateOverrides)>();
+                }
+            }
         }
     }
 
+    if let Some(index) = unit_index.as_mut() {
+        index.entities.clear();
+    }
+
     // Spawn unit entities with core components. The unit plugin's sync
     // systems will add mesh/material via change detection.
     for unit in &pending.units {
         let hex = unit.position.to_hex();
         let world_pos = config.layout.hex_to_world_pos(hex);
 
-        commands.spawn((
-            UnitInstance,
-            HexPosition::new(unit.position.q, unit.position.r),
-            EntityData {
-                entity_type_id: unit.entity_type_id,
-                properties: unit.properties.clone(),
-            },
-            Transform::from_xyz(world_pos.x, 0.25, world_pos.y),
-        ));
+        let mut properties = unit.properties.clone();
+        dangling += properties
+            .values_mut()
+            .map(|v| v.clear_dangling_unit_refs(&known_units))
+            .sum::<usize>();
+
+        let entity = commands
+            .spawn((
+                UnitInstance,
+                HexPosition::new(unit.position.q, unit.position.r),
+                EntityData {
+                    entity_type_id: unit.entity_type_id,
+                    properties,
+                },
+                UnitOwner {
+                    faction_id: unit.owner,
+                },
+                unit.id,
+                Transform::from_xyz(world_pos.x, 0.25, world_pos.y),
+            ))
+            .id();
+        if let Some(state_id) =
+            loaded_state(state_machines.as_deref(), unit.entity_type_id, unit.state)
+        {
+            commands.entity(entity).insert(EntityState { state_id });
+        }
+        if let Some(index) = unit_index.as_mut() {
+            index.entities.insert(unit.id, entity);
+        }
+    }
+
+    if dangling > 0 {
+        warn!("Cleared {dangling} reference(s) to units missing from the loaded board");
     }
 
     // Remove the pending resource.
     commands.remove_resource::<PendingBoardLoad>();
 }
+
+/// The state a loaded tile or unit starts in: its saved state if that still
+/// belongs to its type's machine, otherwise the machine's initial state.
+fn loaded_state(
+    state_machines: Option<&StateMachineRegistry>,
+    entity_type_id: TypeId,
+    saved: Option<TypeId>,
+) -> Option<TypeId> {
+    let machine = state_machines?.for_type(entity_type_id)?;
+    saved
+        .filter(|id| machine.state(*id).is_some())
+        .or_else(|| machine.initial().map(|s| s.id))
+}
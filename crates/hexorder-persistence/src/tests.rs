@@ -5,12 +5,13 @@ use std::collections::HashMap;
 use bevy::prelude::*;
 
 use hexorder_contracts::game_system::{
-    EntityData, EntityRole, EntityType, EntityTypeRegistry, EnumRegistry, GameSystem,
-    StructRegistry, TypeId, UnitInstance,
+    EntityData, EntityRole, EntityState, EntityType, EntityTypeRegistry, EnumRegistry, GameSystem,
+    PropertyValue, StateDefinition, StateMachine, StateMachineRegistry, StructRegistry, TypeId,
+    UnitId, UnitIndex, UnitInstance, UnitOwner,
 };
 use hexorder_contracts::hex_grid::{
-    HexEdgeRegistry, HexGridConfig, HexPosition, HexTile, InfluenceRuleRegistry,
-    MovementCostMatrix, StackingRule,
+    GridShape, HexEdgeRegistry, HexGridConfig, HexPosition, HexTile, HexVertexRegistry,
+    InfluenceRuleRegistry, MovementCostMatrix, StackingRule,
 };
 use hexorder_contracts::mechanics::{CombatModifierRegistry, CombatResultsTable, TurnStructure};
 use hexorder_contracts::ontology::{ConceptRegistry, ConstraintRegistry, RelationRegistry};
@@ -59,12 +60,21 @@ fn test_app() -> App {
     // ShortcutRegistry must exist before PersistencePlugin (registers shortcuts in build).
     app.init_resource::<hexorder_contracts::shortcuts::ShortcutRegistry>();
     app.init_resource::<HexEdgeRegistry>();
+    app.init_resource::<HexVertexRegistry>();
     app.init_resource::<InfluenceRuleRegistry>();
     app.init_resource::<StackingRule>();
     app.init_resource::<MovementCostMatrix>();
     app.init_resource::<hexorder_contracts::mechanics::SpawnSchedule>();
     app.init_resource::<hexorder_contracts::mechanics::AccumulatorRegistry>();
     app.init_resource::<hexorder_contracts::mechanics::VictoryConditionRegistry>();
+    app.init_resource::<hexorder_contracts::mechanics::OffMapZoneRegistry>();
+    app.init_resource::<hexorder_contracts::game_system::FactionRegistry>();
+    app.init_resource::<hexorder_contracts::game_system::StateMachineRegistry>();
+    app.init_resource::<hexorder_contracts::hex_grid::ReachabilityRuleRegistry>();
+    app.init_resource::<hexorder_contracts::mechanics::MovementInterruptRegistry>();
+    app.init_resource::<hexorder_contracts::mechanics::OverrunRegistry>();
+    app.init_resource::<hexorder_contracts::mechanics::CombatTableRegistry>();
+    app.init_resource::<UnitIndex>();
     app.add_plugins(crate::PersistencePlugin);
     app
 }
@@ -101,11 +111,15 @@ fn test_game_system_file() -> GameSystemFile {
             position: HexPosition::new(0, 0),
             entity_type_id: type_id,
             properties: HashMap::new(),
+            state: None,
         }],
         units: vec![UnitSaveData {
             position: HexPosition::new(1, 0),
             entity_type_id: type_id,
             properties: HashMap::new(),
+            owner: None,
+            id: UnitId::new(),
+            state: None,
         }],
         workspace_preset: String::new(),
         font_size_base: 15.0,
@@ -116,6 +130,15 @@ fn test_game_system_file() -> GameSystemFile {
         spawn_schedule: hexorder_contracts::mechanics::SpawnSchedule::default(),
         accumulator_registry: hexorder_contracts::mechanics::AccumulatorRegistry::default(),
         victory_conditions: hexorder_contracts::mechanics::VictoryConditionRegistry::default(),
+        off_map_zones: hexorder_contracts::mechanics::OffMapZoneRegistry::default(),
+        grid_shape: GridShape::default(),
+        vertex_features: HexVertexRegistry::default(),
+        factions: hexorder_contracts::game_system::FactionRegistry::default(),
+        state_machines: hexorder_contracts::game_system::StateMachineRegistry::default(),
+        reachability_rules: hexorder_contracts::hex_grid::ReachabilityRuleRegistry::default(),
+        movement_interrupts: hexorder_contracts::mechanics::MovementInterruptRegistry::default(),
+        overruns: hexorder_contracts::mechanics::OverrunRegistry::default(),
+        combat_tables: hexorder_contracts::mechanics::CombatTableRegistry::default(),
     }
 }
 
@@ -132,6 +155,7 @@ fn apply_pending_board_load_maps_tiles_and_spawns_units() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
 
     app.update(); // Startup
@@ -181,6 +205,196 @@ fn apply_pending_board_load_maps_tiles_and_spawns_units() {
     );
 }
 
+/// Units spawned from a board load keep their owning faction.
+#[test]
+fn apply_pending_board_load_restores_unit_owner() {
+    let mut app = test_app();
+
+    app.insert_resource(HexGridConfig {
+        layout: hexx::HexLayout {
+            orientation: hexx::HexOrientation::Pointy,
+            scale: bevy::math::Vec2::splat(1.0),
+            origin: bevy::math::Vec2::ZERO,
+        },
+        map_radius: 5,
+        shape: GridShape::Hexagon,
+    });
+
+    app.update(); // Startup
+
+    let mut file = test_game_system_file();
+    let faction_id = TypeId::new();
+    file.units[0].owner = Some(faction_id);
+
+    app.insert_resource(PendingBoardLoad {
+        tiles: Vec::new(),
+        units: file.units.clone(),
+    });
+
+    app.update(); // apply_pending_board_load runs
+
+    let mut unit_query = app
+        .world_mut()
+        .query_filtered::<&UnitOwner, With<UnitInstance>>();
+    let owners: Vec<_> = unit_query.iter(app.world()).copied().collect();
+    assert_eq!(owners.len(), 1);
+    assert_eq!(owners[0].faction_id, Some(faction_id));
+}
+
+/// A v11 unit without an `owner` field loads as unowned.
+#[test]
+fn unit_save_data_without_owner_loads_as_unowned() {
+    let file = test_game_system_file();
+    let ron_str =
+        ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default()).expect("serialize");
+    let without_owner = ron_str.replace("owner: None,", "");
+
+    let loaded: GameSystemFile = ron::from_str(&without_owner).expect("deserialize v11 units");
+    assert_eq!(loaded.units[0].owner, None);
+    assert!(loaded.factions.factions.is_empty());
+}
+
+/// `apply_pending_board_load` spawns units with their saved ids and indexes them.
+#[test]
+fn apply_pending_board_load_restores_unit_id() {
+    let mut app = test_app_with_grid();
+
+    let file = test_game_system_file();
+    let saved_id = file.units[0].id;
+
+    app.insert_resource(PendingBoardLoad {
+        tiles: Vec::new(),
+        units: file.units.clone(),
+    });
+
+    app.update(); // apply_pending_board_load runs
+
+    let mut unit_query = app
+        .world_mut()
+        .query_filtered::<(Entity, &UnitId), With<UnitInstance>>();
+    let units: Vec<_> = unit_query
+        .iter(app.world())
+        .map(|(e, id)| (e, *id))
+        .collect();
+    assert_eq!(units.len(), 1);
+    assert_eq!(units[0].1, saved_id);
+    assert_eq!(
+        app.world().resource::<UnitIndex>().get(saved_id),
+        Some(units[0].0)
+    );
+}
+
+/// References to units that were not loaded are cleared; others are kept.
+#[test]
+fn apply_pending_board_load_clears_dangling_unit_refs() {
+    let mut app = test_app_with_grid();
+
+    let mut file = test_game_system_file();
+    let leader = file.units[0].clone();
+    let follower_prop = TypeId::new();
+    let missing_prop = TypeId::new();
+    let mut follower = leader.clone();
+    follower.id = UnitId::new();
+    follower.position = HexPosition::new(2, 0);
+    follower.properties = HashMap::from([
+        (follower_prop, PropertyValue::UnitRef(Some(leader.id))),
+        (missing_prop, PropertyValue::UnitRef(Some(UnitId::new()))),
+    ]);
+    file.units.push(follower.clone());
+
+    app.insert_resource(PendingBoardLoad {
+        tiles: Vec::new(),
+        units: file.units,
+    });
+
+    app.update(); // apply_pending_board_load runs
+
+    let mut unit_query = app
+        .world_mut()
+        .query_filtered::<(&UnitId, &EntityData), With<UnitInstance>>();
+    let data = unit_query
+        .iter(app.world())
+        .find(|(id, _)| **id == follower.id)
+        .map(|(_, d)| d.clone())
+        .expect("follower spawned");
+    assert_eq!(
+        data.properties.get(&follower_prop),
+        Some(&PropertyValue::UnitRef(Some(leader.id)))
+    );
+    assert_eq!(
+        data.properties.get(&missing_prop),
+        Some(&PropertyValue::UnitRef(None))
+    );
+}
+
+/// Units load in their saved state; a state their machine no longer has
+/// falls back to the machine's initial state.
+#[test]
+fn apply_pending_board_load_restores_unit_state() {
+    let mut app = test_app_with_grid();
+
+    let mut file = test_game_system_file();
+    let type_id = file.units[0].entity_type_id;
+    let define = |name: &str| StateDefinition {
+        id: TypeId::new(),
+        name: name.to_string(),
+        property_overrides: HashMap::new(),
+        color: None,
+        badge: String::new(),
+    };
+    let (full, disrupted) = (define("Full"), define("Disrupted"));
+    app.insert_resource(StateMachineRegistry {
+        machines: vec![StateMachine {
+            id: TypeId::new(),
+            name: "Readiness".to_string(),
+            entity_type_id: type_id,
+            states: vec![full.clone(), disrupted.clone()],
+            initial_state: None,
+            transitions: Vec::new(),
+        }],
+    });
+    let saved_id = file.units[0].id;
+    file.units[0].state = Some(disrupted.id);
+    let mut stale = file.units[0].clone();
+    stale.id = UnitId::new();
+    stale.state = Some(TypeId::new());
+    file.units.push(stale.clone());
+
+    app.insert_resource(PendingBoardLoad {
+        tiles: Vec::new(),
+        units: file.units,
+    });
+    app.update();
+
+    let mut unit_query = app
+        .world_mut()
+        .query_filtered::<(&UnitId, &EntityState), With<UnitInstance>>();
+    let states: HashMap<UnitId, TypeId> = unit_query
+        .iter(app.world())
+        .map(|(id, s)| (*id, s.state_id))
+        .collect();
+    assert_eq!(states.get(&saved_id), Some(&disrupted.id));
+    assert_eq!(states.get(&stale.id), Some(&full.id));
+}
+
+/// A unit saved before v13 has no `id` and gets a fresh one on load.
+#[test]
+fn unit_save_data_without_id_gets_fresh_id() {
+    let file = test_game_system_file();
+    let ron_str = ron::ser::to_string_pretty(&file.units[0], ron::ser::PrettyConfig::default())
+        .expect("serialize");
+    let without_id: String = ron_str
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("id:"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert_ne!(without_id, ron_str);
+
+    let a: UnitSaveData = ron::from_str(&without_id).expect("deserialize v12 unit");
+    let b: UnitSaveData = ron::from_str(&without_id).expect("deserialize v12 unit");
+    assert_ne!(a.id, b.id);
+}
+
 /// `apply_pending_board_load` defers when tiles lack `EntityData`.
 #[test]
 fn apply_pending_board_load_defers_until_tiles_have_entity_data() {
@@ -193,6 +407,7 @@ fn apply_pending_board_load_defers_until_tiles_have_entity_data() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
 
     app.update(); // Startup
@@ -281,6 +496,7 @@ fn sync_dirty_flag_sets_dirty_on_new_records() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
     app.update();
 
@@ -389,6 +605,7 @@ fn save_to_path_writes_file_and_updates_workspace() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
     app.update();
 
@@ -422,6 +639,7 @@ fn load_from_path_overwrites_registries() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
     app.update();
 
@@ -471,6 +689,7 @@ fn dispatch_confirm_no_executes_pending_action() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
     app.update();
 
@@ -505,6 +724,7 @@ fn dispatch_confirm_cancel_does_nothing() {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
     app.update();
 
@@ -528,10 +748,10 @@ fn dispatch_confirm_cancel_does_nothing() {
     assert_eq!(workspace.name, "Original");
 }
 
-/// Format version was bumped to 8 for accumulator/victory types.
+/// Format version was bumped to 17 for overrun rules.
 #[test]
-fn format_version_is_8() {
-    assert_eq!(FORMAT_VERSION, 8);
+fn format_version_is_18() {
+    assert_eq!(FORMAT_VERSION, 18);
 }
 
 // ---------------------------------------------------------------------------
@@ -547,6 +767,7 @@ fn test_app_with_grid() -> App {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     });
     app.update();
     app
@@ -687,6 +908,74 @@ fn load_from_path_restores_workspace_preset_and_font_size() {
     let _ = std::fs::remove_file(&tmp);
 }
 
+/// `load_from_path` applies the saved board shape to `HexGridConfig`.
+#[test]
+fn load_from_path_restores_grid_shape() {
+    use hexorder_contracts::storage::Storage;
+
+    let mut app = test_app_with_grid();
+
+    let mut file = test_game_system_file();
+    file.grid_shape = GridShape::Rectangle {
+        width: 12,
+        height: 8,
+        wrap_horizontal: true,
+    };
+
+    let tmp = std::env::temp_dir().join("hexorder_test_grid_shape.hexorder");
+    {
+        let storage = app.world().resource::<Storage>();
+        storage
+            .provider()
+            .save_at(&tmp, &file)
+            .expect("write test file");
+    }
+
+    super::systems::load_from_path(&tmp, app.world_mut());
+
+    let config = app.world().resource::<HexGridConfig>();
+    assert_eq!(config.shape, file.grid_shape);
+
+    let _ = std::fs::remove_file(&tmp);
+}
+
+/// `load_from_path` restores vertex feature annotations.
+#[test]
+fn load_from_path_restores_vertex_features() {
+    use hexorder_contracts::hex_grid::{HexVertex, VertexFeature};
+    use hexorder_contracts::storage::Storage;
+
+    let mut app = test_app_with_grid();
+
+    let vertex = HexVertex::new(HexPosition::new(1, -1), 2);
+    let mut file = test_game_system_file();
+    file.vertex_features.insert(
+        vertex,
+        VertexFeature {
+            type_name: "Town".to_string(),
+        },
+    );
+
+    let tmp = std::env::temp_dir().join("hexorder_test_vertex_features.hexorder");
+    {
+        let storage = app.world().resource::<Storage>();
+        storage
+            .provider()
+            .save_at(&tmp, &file)
+            .expect("write test file");
+    }
+
+    super::systems::load_from_path(&tmp, app.world_mut());
+
+    let registry = app.world().resource::<HexVertexRegistry>();
+    assert_eq!(
+        registry.get(&vertex).map(|f| f.type_name.as_str()),
+        Some("Town")
+    );
+
+    let _ = std::fs::remove_file(&tmp);
+}
+
 // ---------------------------------------------------------------------------
 // save_to_path marks undo stack clean
 // ---------------------------------------------------------------------------
@@ -1326,6 +1615,43 @@ fn save_to_path_captures_tiles_and_units() {
     let _ = std::fs::remove_file(&tmp);
 }
 
+/// Units are saved with `WhilePresent` effects reverted.
+#[test]
+fn save_to_path_writes_unit_base_values() {
+    use hexorder_contracts::game_system::PropertyValue;
+    use hexorder_contracts::ontology::{AppliedEffect, PresenceEffects};
+
+    let mut app = test_app_with_grid();
+    let strength = TypeId::new();
+    app.world_mut().spawn((
+        UnitInstance,
+        HexPosition::new(0, 0),
+        EntityData {
+            entity_type_id: TypeId::new(),
+            properties: HashMap::from([(strength, PropertyValue::Int(5))]),
+        },
+        PresenceEffects {
+            applied: vec![AppliedEffect {
+                relation_id: TypeId::new(),
+                source: Entity::PLACEHOLDER,
+                property_id: strength,
+                before: PropertyValue::Int(3),
+                after: PropertyValue::Int(5),
+            }],
+        },
+    ));
+
+    let tmp = std::env::temp_dir().join("hexorder_test_save_base_values.hexorder");
+    let _ = std::fs::remove_file(&tmp);
+    assert!(super::systems::save_to_path(&tmp, app.world_mut()));
+
+    let contents = std::fs::read_to_string(&tmp).expect("read file");
+    let loaded: GameSystemFile = ron::from_str(&contents).expect("deserialize");
+    assert_eq!(loaded.units[0].properties[&strength], PropertyValue::Int(3));
+
+    let _ = std::fs::remove_file(&tmp);
+}
+
 // ---------------------------------------------------------------------------
 // Confirm Yes save failure aborts chain
 // ---------------------------------------------------------------------------
@@ -0,0 +1,253 @@
+//! Benchmarks for headless rules evaluation on boards of realistic size.
+//!
+//! Each board is a hexagon of terrain with costs 1–3, one unit per ten
+//! hexes split between two sides, a zone of control around every unit and
+//! a stacking limit.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use hexorder_contracts::game_system::{
+    EntityRole, EntityType, EntityTypeRegistry, EnumRegistry, FactionRegistry, GameSystem,
+    PropertyDefinition, PropertyType, PropertyValue, StateMachineRegistry, StructRegistry, TypeId,
+    UnitId,
+};
+use hexorder_contracts::hex_grid::{
+    GridShape, HexEdgeRegistry, HexGridConfig, HexVertexRegistry, InfluenceRule,
+    InfluenceRuleRegistry, MovementCostMatrix, ReachabilityRuleRegistry, StackingRule,
+    ZoneOfControl,
+};
+use hexorder_contracts::mechanics::{
+    AccumulatorRegistry, CombatModifierRegistry, CombatResultsTable, CombatTableRegistry,
+    MovementInterruptRegistry, OffMapZoneRegistry, OverrunRegistry, SpawnSchedule, TurnStructure,
+    VictoryConditionRegistry,
+};
+use hexorder_contracts::ontology::{
+    Concept, ConceptBinding, ConceptRegistry, ConceptRole, ConstraintRegistry, ModifyOperation,
+    PropertyBinding, Relation, RelationEffect, RelationRegistry, RelationTrigger,
+};
+use hexorder_contracts::persistence::{FORMAT_VERSION, GameSystemFile, TileSaveData, UnitSaveData};
+use hexorder_rules_engine::{BoardSnapshot, RulesContext};
+
+/// Map radii benchmarked: a small scenario, a typical one and a large one.
+const RADII: [u32; 3] = [10, 20, 30];
+
+/// A game system with a Motion concept (terrain cost subtracts from the
+/// unit's budget of 6), saved with a board of the given radius.
+fn board_file(radius: u32) -> GameSystemFile {
+    let concept_id = TypeId::new();
+    let traveler = TypeId::new();
+    let terrain = TypeId::new();
+    let unit_type = TypeId::new();
+    let tile_type = TypeId::new();
+    let budget = TypeId::new();
+    let cost = TypeId::new();
+    let sides = [TypeId::new(), TypeId::new()];
+
+    let property = |id, name: &str, value| PropertyDefinition {
+        id,
+        name: name.to_string(),
+        property_type: PropertyType::Int,
+        default_value: PropertyValue::Int(value),
+    };
+    let entity_types = EntityTypeRegistry {
+        types: vec![
+            EntityType {
+                id: unit_type,
+                name: "Infantry".to_string(),
+                role: EntityRole::Token,
+                color: bevy::color::Color::WHITE,
+                properties: vec![property(budget, "movement_points", 6)],
+            },
+            EntityType {
+                id: tile_type,
+                name: "Terrain".to_string(),
+                role: EntityRole::BoardPosition,
+                color: bevy::color::Color::WHITE,
+                properties: vec![property(cost, "terrain_cost", 1)],
+            },
+        ],
+    };
+    let binding = |entity_type_id, concept_role_id, property_id, name: &str| ConceptBinding {
+        id: TypeId::new(),
+        entity_type_id,
+        concept_id,
+        concept_role_id,
+        property_bindings: vec![PropertyBinding {
+            property_id,
+            concept_local_name: name.to_string(),
+        }],
+    };
+    let role = |id, name: &str, entity_role| ConceptRole {
+        id,
+        name: name.to_string(),
+        allowed_entity_roles: vec![entity_role],
+    };
+    let concepts = ConceptRegistry {
+        concepts: vec![Concept {
+            id: concept_id,
+            name: "Motion".to_string(),
+            description: String::new(),
+            role_labels: vec![
+                role(traveler, "traveler", EntityRole::Token),
+                role(terrain, "terrain", EntityRole::BoardPosition),
+            ],
+        }],
+        bindings: vec![
+            binding(unit_type, traveler, budget, "budget"),
+            binding(tile_type, terrain, cost, "cost"),
+        ],
+    };
+    let relations = RelationRegistry {
+        relations: vec![Relation {
+            id: TypeId::new(),
+            name: "Terrain Movement Cost".to_string(),
+            concept_id,
+            subject_role_id: traveler,
+            object_role_id: terrain,
+            trigger: RelationTrigger::OnEnter,
+            effect: RelationEffect::ModifyProperty {
+                target_property: "budget".to_string(),
+                source_property: "cost".to_string(),
+                operation: ModifyOperation::Subtract,
+            },
+        }],
+    };
+
+    let config = HexGridConfig {
+        map_radius: radius,
+        ..HexGridConfig::default()
+    };
+    let positions = config.positions();
+    let tiles = positions
+        .iter()
+        .map(|&position| TileSaveData {
+            position,
+            entity_type_id: tile_type,
+            properties: HashMap::from([(
+                cost,
+                PropertyValue::Int(i64::from(
+                    (position.q * 7 + position.r * 13).rem_euclid(3) + 1,
+                )),
+            )]),
+            state: None,
+        })
+        .collect();
+    let units = positions
+        .iter()
+        .step_by(10)
+        .enumerate()
+        .map(|(index, &position)| UnitSaveData {
+            position,
+            entity_type_id: unit_type,
+            properties: HashMap::from([(budget, PropertyValue::Int(6))]),
+            owner: Some(sides[index % 2]),
+            id: UnitId::new(),
+            state: None,
+        })
+        .collect();
+
+    GameSystemFile {
+        format_version: FORMAT_VERSION,
+        name: "Benchmark".to_string(),
+        game_system: GameSystem {
+            id: "benchmark".to_string(),
+            version: "0.1.0".to_string(),
+        },
+        entity_types,
+        enums: EnumRegistry::default(),
+        structs: StructRegistry::default(),
+        concepts,
+        relations,
+        constraints: ConstraintRegistry::default(),
+        turn_structure: TurnStructure::default(),
+        combat_results_table: CombatResultsTable::default(),
+        combat_modifiers: CombatModifierRegistry::default(),
+        map_radius: radius,
+        grid_shape: GridShape::default(),
+        tiles,
+        units,
+        workspace_preset: String::new(),
+        font_size_base: 15.0,
+        edge_features: HexEdgeRegistry::default(),
+        vertex_features: HexVertexRegistry::default(),
+        influence_rules: InfluenceRuleRegistry {
+            rules: vec![InfluenceRule {
+                id: TypeId::new(),
+                entity_type_id: unit_type,
+                range: 1,
+                cost_modifier: 1,
+                zone: ZoneOfControl {
+                    stop_on_enter: true,
+                    ..ZoneOfControl::default()
+                },
+                enemy_only: false,
+            }],
+        },
+        stacking_rule: StackingRule {
+            max_units: 3,
+            ..StackingRule::default()
+        },
+        movement_cost_matrix: MovementCostMatrix::default(),
+        spawn_schedule: SpawnSchedule::default(),
+        accumulator_registry: AccumulatorRegistry::default(),
+        victory_conditions: VictoryConditionRegistry::default(),
+        off_map_zones: OffMapZoneRegistry::default(),
+        factions: FactionRegistry::default(),
+        state_machines: StateMachineRegistry::default(),
+        reachability_rules: ReachabilityRuleRegistry::default(),
+        movement_interrupts: MovementInterruptRegistry::default(),
+        overruns: OverrunRegistry::default(),
+        combat_tables: CombatTableRegistry::default(),
+    }
+}
+
+fn rules_context(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rules_context");
+    for radius in RADII {
+        let file = board_file(radius);
+        let snapshot = BoardSnapshot::from_file(&file);
+        let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+        let board = snapshot.board();
+        let influence = rules.influence_map(&board);
+        let unit = board.units.len() / 2;
+
+        group.bench_with_input(BenchmarkId::new("snapshot", radius), &file, |b, file| {
+            b.iter(|| BoardSnapshot::from_file(black_box(file)));
+        });
+        group.bench_with_input(
+            BenchmarkId::new("influence_map", radius),
+            &board,
+            |b, board| {
+                b.iter(|| rules.influence_map(black_box(board)));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("valid_moves", radius),
+            &board,
+            |b, board| {
+                b.iter(|| rules.valid_moves(black_box(board), unit, &influence));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("path_costs", radius),
+            &board,
+            |b, board| {
+                b.iter(|| rules.path_costs(black_box(board), unit, 12));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("stacking_violations", radius),
+            &board,
+            |b, board| {
+                b.iter(|| rules.stacking_violations(black_box(board)));
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, rules_context);
+criterion_main!(benches);
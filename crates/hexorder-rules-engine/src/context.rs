@@ -0,0 +1,3421 @@
+//! ECS-free rules evaluation.
+//!
+//! A `RulesContext` holds the rule definitions (ontology, board, influence,
+//! stacking and movement rules) and a `RulesBoard` the tiles and units they
+//! are evaluated against. Together they answer what a unit can do — valid
+//! moves, path costs, influence, stacking, constraints, reachability,
+//! movement interrupts, overruns — without a running app, so scripts,
+//! tests, AI and tools can ask directly.
+//! The plugin's systems build both from resources and queries and store the
+//! answers in resources and components.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use hexorder_contracts::game_system::{
+    EntityData, EntityTypeRegistry, PropertyValue, StateMachineRegistry, TypeId, UnitOwner,
+};
+use hexorder_contracts::hex_grid::{
+    HexEdgeRegistry, HexGridConfig, HexPosition, HexVertexRegistry, InfluenceEntry, InfluenceMap,
+    InfluenceRule, InfluenceRuleRegistry, MovementCostMatrix, ReachabilityRule,
+    ReachabilityRuleRegistry, ReachabilitySource, ReachabilityStatus, StackingRule,
+    StackingViolation, ZoneTransition,
+};
+use hexorder_contracts::mechanics::{
+    AreaEffect, AreaMarkerRegistry, CombatModifierDefinition, CombatResultsTable,
+    CombatTableRegistry, ConstrainedPathRequest, InterruptResult, InterruptTrigger,
+    MovementInterruptRegistry, MovementInterruptRule, OverrunRegistry, OverrunResult, OverrunRule,
+    PathConstraint, PathfindingContext, concept_property_id, find_constrained_paths,
+    resolve_interrupt, resolve_overrun,
+};
+use hexorder_contracts::ontology::{
+    CompareOp, ConceptBinding, ConceptRegistry, Constraint, ConstraintExpr, ConstraintRegistry,
+    GatedAction, ModifyOperation, ProximityFaction, ProximityFilter, ProximityMeasure, Relation,
+    RelationEffect, RelationRegistry, RelationTrigger,
+};
+use hexorder_contracts::persistence::GameSystemFile;
+use hexorder_contracts::simulation::SimulationRng;
+use hexorder_contracts::validation::{
+    ActionEligibility, CostComponent, CostSource, PathStep, ValidMoveSet, ValidationResult,
+};
+
+/// Area markers of a rules context built from a saved file, which has none.
+static NO_AREA_MARKERS: AreaMarkerRegistry = AreaMarkerRegistry {
+    markers: Vec::new(),
+};
+
+/// The rule definitions a board is evaluated against.
+#[derive(Debug, Clone, Copy)]
+pub struct RulesContext<'a> {
+    pub concepts: &'a ConceptRegistry,
+    pub relations: &'a RelationRegistry,
+    pub constraints: &'a ConstraintRegistry,
+    pub entity_types: &'a EntityTypeRegistry,
+    pub grid_config: &'a HexGridConfig,
+    pub edges: &'a HexEdgeRegistry,
+    pub vertices: &'a HexVertexRegistry,
+    pub influence_rules: &'a InfluenceRuleRegistry,
+    pub stacking_rule: &'a StackingRule,
+    pub movement_cost_matrix: &'a MovementCostMatrix,
+    pub area_markers: &'a AreaMarkerRegistry,
+    pub state_machines: &'a StateMachineRegistry,
+    pub reachability: &'a ReachabilityRuleRegistry,
+    pub interrupts: &'a MovementInterruptRegistry,
+    pub overruns: &'a OverrunRegistry,
+}
+
+/// A unit on a `RulesBoard`.
+#[derive(Debug, Clone, Copy)]
+pub struct BoardUnit<'a> {
+    pub pos: HexPosition,
+    pub data: &'a EntityData,
+    pub owner: UnitOwner,
+    /// Current state-machine state, if its type has a machine.
+    pub state: Option<TypeId>,
+    /// Latest reachability results, for `InReach` conditions.
+    pub reach: Option<&'a ReachabilityStatus>,
+    /// Gated actions the unit may not take. `None` allows everything.
+    pub eligibility: Option<&'a ActionEligibility>,
+    /// Visibility range in hexes, for line-of-sight interrupts. `None`
+    /// sees nothing.
+    pub sight: Option<u32>,
+    /// Movement points already spent this phase; moves start from the
+    /// budget less this.
+    pub spent: i64,
+}
+
+impl<'a> BoardUnit<'a> {
+    /// A unit with no state, reachability results or denied actions.
+    #[must_use]
+    pub fn new(pos: HexPosition, data: &'a EntityData, owner: UnitOwner) -> Self {
+        Self {
+            pos,
+            data,
+            owner,
+            state: None,
+            reach: None,
+            eligibility: None,
+            sight: None,
+            spent: 0,
+        }
+    }
+}
+
+/// The tiles and units rules are evaluated against. Units are addressed by
+/// their index in `units`.
+#[derive(Debug, Clone, Default)]
+pub struct RulesBoard<'a> {
+    tiles: HashMap<HexPosition, &'a EntityData>,
+    tile_states: HashMap<HexPosition, TypeId>,
+    pub units: Vec<BoardUnit<'a>>,
+}
+
+impl<'a> RulesBoard<'a> {
+    /// A board of `tiles` (position, data and state) and `units`.
+    pub fn new(
+        tiles: impl IntoIterator<Item = (HexPosition, &'a EntityData, Option<TypeId>)>,
+        units: Vec<BoardUnit<'a>>,
+    ) -> Self {
+        let mut board = Self {
+            units,
+            ..Self::default()
+        };
+        for (pos, data, state) in tiles {
+            board.tiles.insert(pos, data);
+            if let Some(state) = state {
+                board.tile_states.insert(pos, state);
+            }
+        }
+        board
+    }
+
+    /// The tile at `pos`, if any.
+    #[must_use]
+    pub fn tile(&self, pos: HexPosition) -> Option<&'a EntityData> {
+        self.tiles.get(&pos).copied()
+    }
+}
+
+/// A board read from a saved game system, owning its tiles and units.
+#[derive(Debug)]
+pub struct BoardSnapshot {
+    pub grid_config: HexGridConfig,
+    /// Position, data and state of each tile.
+    pub tiles: Vec<(HexPosition, EntityData, Option<TypeId>)>,
+    /// Position, data, owner and state of each unit.
+    pub units: Vec<(HexPosition, EntityData, UnitOwner, Option<TypeId>)>,
+}
+
+impl BoardSnapshot {
+    /// The board saved in `file`. Instances without a valid saved state
+    /// start in their machine's initial state, and each state's property
+    /// overrides are applied; `WhilePresent` effects are not.
+    #[must_use]
+    pub fn from_file(file: &GameSystemFile) -> Self {
+        let machines = &file.state_machines;
+        let instance = |type_id: TypeId,
+                        properties: &HashMap<TypeId, PropertyValue>,
+                        saved: Option<TypeId>| {
+            let mut data = EntityData {
+                entity_type_id: type_id,
+                properties: properties.clone(),
+            };
+            let state = machines.for_type(type_id).and_then(|machine| {
+                saved
+                    .filter(|id| machine.state(*id).is_some())
+                    .or_else(|| machine.initial().map(|s| s.id))
+            });
+            if let Some(definition) = state.and_then(|id| machines.state(id)) {
+                for (property_id, value) in &definition.property_overrides {
+                    if data.properties.contains_key(property_id) {
+                        data.properties.insert(*property_id, value.clone());
+                    }
+                }
+            }
+            (data, state)
+        };
+        Self {
+            grid_config: HexGridConfig {
+                map_radius: file.map_radius,
+                shape: file.grid_shape,
+                ..HexGridConfig::default()
+            },
+            tiles: file
+                .tiles
+                .iter()
+                .map(|tile| {
+                    let (data, state) = instance(tile.entity_type_id, &tile.properties, tile.state);
+                    (tile.position, data, state)
+                })
+                .collect(),
+            units: file
+                .units
+                .iter()
+                .map(|unit| {
+                    let (data, state) = instance(unit.entity_type_id, &unit.properties, unit.state);
+                    let owner = UnitOwner {
+                        faction_id: unit.owner,
+                    };
+                    (unit.position, data, owner, state)
+                })
+                .collect(),
+        }
+    }
+
+    /// The snapshot as a board to evaluate rules against, with units in
+    /// saved order.
+    #[must_use]
+    pub fn board(&self) -> RulesBoard<'_> {
+        RulesBoard::new(
+            self.tiles
+                .iter()
+                .map(|(pos, data, state)| (*pos, data, *state)),
+            self.units
+                .iter()
+                .map(|(pos, data, owner, state)| BoardUnit {
+                    state: *state,
+                    ..BoardUnit::new(*pos, data, *owner)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// A hex on a route where a movement interrupt rule halts the moving unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MovementInterrupt {
+    /// Index of the hex in the route.
+    pub step: usize,
+    pub pos: HexPosition,
+    pub rule_id: TypeId,
+    /// Index of the reacting unit on the board.
+    pub reactor: usize,
+}
+
+/// How a move along a route went.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MoveReport {
+    /// Hexes of the route the unit entered. It stands in the last of them,
+    /// or where it started when none.
+    pub entered: usize,
+    /// Interrupts met on the way, in order, with how each resolved.
+    pub interrupts: Vec<(MovementInterrupt, InterruptResult)>,
+    /// The state the last state-changing outcome put the unit into.
+    pub state: Option<TypeId>,
+}
+
+/// How an overrun went.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OverrunReport {
+    pub result: OverrunResult,
+    /// Movement points the attacker has spent after it: what it spent
+    /// before plus the rule's cost, or its whole budget when the defender
+    /// held and its movement ends.
+    pub spent: i64,
+}
+
+/// One trace of a reachability rule, shared by traced units with the same
+/// movement profile.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnitTrace {
+    /// Indices of the traced units on the board.
+    pub units: Vec<usize>,
+    /// Cheapest path cost from each hex in reach to the nearest source.
+    pub reached: HashMap<HexPosition, i64>,
+}
+
+impl<'a> RulesContext<'a> {
+    /// The rules saved in `file`, on a board laid out by `grid_config` (see
+    /// `BoardSnapshot::grid_config`). Saved files hold no area markers.
+    #[must_use]
+    pub fn from_file(file: &'a GameSystemFile, grid_config: &'a HexGridConfig) -> Self {
+        Self {
+            concepts: &file.concepts,
+            relations: &file.relations,
+            constraints: &file.constraints,
+            entity_types: &file.entity_types,
+            grid_config,
+            edges: &file.edge_features,
+            vertices: &file.vertex_features,
+            influence_rules: &file.influence_rules,
+            stacking_rule: &file.stacking_rule,
+            movement_cost_matrix: &file.movement_cost_matrix,
+            area_markers: &NO_AREA_MARKERS,
+            state_machines: &file.state_machines,
+            reachability: &file.reachability_rules,
+            interrupts: &file.movement_interrupts,
+            overruns: &file.overruns,
+        }
+    }
+
+    /// Influence projected by every unit and vertex feature on the board.
+    /// See `compute_influence_map`.
+    #[must_use]
+    pub fn influence_map(&self, board: &RulesBoard<'_>) -> InfluenceMap {
+        let ctx = InfluenceContext {
+            rules: self.influence_rules,
+            entity_types: self.entity_types,
+            config: self.grid_config,
+            edges: self.edges,
+            tiles: &board.tiles,
+        };
+        let mut influence_map = InfluenceMap::default();
+        compute_influence_map(&ctx, &board.units, self.vertices, &mut influence_map);
+        influence_map
+    }
+
+    /// The movement budget `unit` starts a move with.
+    #[must_use]
+    pub fn budget(&self, unit: &BoardUnit<'_>) -> i64 {
+        let bindings: Vec<&ConceptBinding> = self
+            .concepts
+            .bindings
+            .iter()
+            .filter(|b| b.entity_type_id == unit.data.entity_type_id)
+            .collect();
+        let on_enter_relations: Vec<&Relation> = self
+            .relations
+            .relations
+            .iter()
+            .filter(|r| r.trigger == RelationTrigger::OnEnter)
+            .collect();
+        determine_budget(&bindings, &on_enter_relations, unit.data, self.concepts)
+    }
+
+    /// Where the unit at index `unit` may move with what remains of its
+    /// budget after `BoardUnit::spent`, found by a BFS from its hex
+    /// that evaluates ontology relations (`OnExit` for the hex being left,
+    /// `OnEnter` for the hex entered), edge crossings, vertex features at
+    /// the corners of each entered hex, `influence` and area markers at each
+    /// step. The set holds reachable positions, the cheapest route to each
+    /// with its cost breakdown, and explanations for blocked ones.
+    ///
+    /// The unit may pass through hexes it would over-stack, but not end
+    /// there; those hexes have a route and a stacking explanation but are
+    /// not valid. A unit denied `Move` gets no moves; its neighbors carry
+    /// the denials. With no relations, no constraints and no stacking limits
+    /// every in-bounds position is reachable (free movement).
+    #[must_use]
+    pub fn valid_moves(
+        &self,
+        board: &RulesBoard<'_>,
+        unit: usize,
+        influence: &InfluenceMap,
+    ) -> ValidMoveSet {
+        let mover = &board.units[unit];
+        let mut valid_moves = ValidMoveSet::default();
+
+        // A unit a gating constraint denies moving cannot leave its hex.
+        if let Some(eligibility) = mover.eligibility
+            && !eligibility.allows(GatedAction::Move)
+        {
+            let reasons: Vec<ValidationResult> =
+                eligibility.denials(GatedAction::Move).cloned().collect();
+            for neighbor_pos in self.grid_config.neighbors(mover.pos) {
+                valid_moves
+                    .blocked_explanations
+                    .insert(neighbor_pos, reasons.clone());
+            }
+            return valid_moves;
+        }
+
+        let steps = BareSteps::new(*self, board);
+
+        // If no relations, constraints or stacking limits exist, free
+        // movement within bounds.
+        if steps.on_enter_relations.is_empty()
+            && steps.on_exit_relations.is_empty()
+            && self.constraints.constraints.is_empty()
+            && !self.stacking_rule.is_active()
+            && !self.stacking_rule.no_mixed_factions
+        {
+            bfs_free_movement(mover.pos, self.grid_config, &mut valid_moves);
+            return valid_moves;
+        }
+
+        // Stacking points and owners already in each hex.
+        let stack_points = if self.stacking_rule.is_active() {
+            self.stack_points(board)
+        } else {
+            HashMap::new()
+        };
+        // Owners of the other units in each hex, for mixed stacks and zones
+        // friendly units cancel.
+        let mut unit_owners: HashMap<HexPosition, Vec<UnitOwner>> = HashMap::new();
+        if self.stacking_rule.no_mixed_factions
+            || self
+                .influence_rules
+                .rules
+                .iter()
+                .any(|rule| rule.zone.negated_by_friendly)
+        {
+            for (index, other) in board.units.iter().enumerate() {
+                if index != unit {
+                    unit_owners.entry(other.pos).or_default().push(other.owner);
+                }
+            }
+        }
+
+        let bare = steps.unit(mover);
+        let initial_budget = determine_budget(
+            &bare.bindings,
+            &steps.on_enter_relations,
+            mover.data,
+            self.concepts,
+        ) - mover.spent;
+        let proximity = ProximityBoard::of(self.grid_config, board);
+        let ctx = StepContext {
+            influence_map: influence,
+            stacking_rule: self.stacking_rule,
+            stack_points: &stack_points,
+            unit_owners: &unit_owners,
+            proximity: Some(&proximity),
+            ..steps.context(&bare, initial_budget)
+        };
+
+        // BFS with budget tracking.
+        let mut queue: VecDeque<(HexPosition, i64)> = VecDeque::new();
+        let mut best_budget: HashMap<HexPosition, i64> = HashMap::new();
+
+        queue.push_back((mover.pos, initial_budget));
+        best_budget.insert(mover.pos, initial_budget);
+
+        while let Some((current_pos, remaining_budget)) = queue.pop_front() {
+            for neighbor_pos in self.grid_config.neighbors(current_pos) {
+                if neighbor_pos == mover.pos {
+                    continue;
+                }
+
+                let step_result = evaluate_step(
+                    &ctx,
+                    board.tile(current_pos),
+                    board.tile(neighbor_pos),
+                    remaining_budget,
+                    current_pos,
+                    neighbor_pos,
+                );
+
+                match step_result {
+                    StepResult::Valid {
+                        new_budget,
+                        components,
+                        overstack,
+                    } => {
+                        let dominated = best_budget
+                            .get(&neighbor_pos)
+                            .is_some_and(|&prev| prev >= new_budget);
+                        if dominated {
+                            continue;
+                        }
+                        best_budget.insert(neighbor_pos, new_budget);
+                        // A cheaper route replaces the hex's predecessor, so the
+                        // stored tree always describes the cheapest route found.
+                        valid_moves.paths.insert(
+                            neighbor_pos,
+                            PathStep {
+                                from: current_pos,
+                                components,
+                                total_cost: initial_budget - new_budget,
+                            },
+                        );
+                        if let Some(reason) = overstack {
+                            let reasons = valid_moves
+                                .blocked_explanations
+                                .entry(neighbor_pos)
+                                .or_default();
+                            if !reasons.contains(&reason) {
+                                reasons.push(reason);
+                            }
+                        } else {
+                            valid_moves.valid_positions.insert(neighbor_pos);
+                            valid_moves.blocked_explanations.remove(&neighbor_pos);
+                        }
+
+                        if new_budget > 0 {
+                            queue.push_back((neighbor_pos, new_budget));
+                        }
+                    }
+                    StepResult::Blocked { reasons } => {
+                        if !valid_moves.valid_positions.contains(&neighbor_pos) {
+                            valid_moves
+                                .blocked_explanations
+                                .entry(neighbor_pos)
+                                .or_default()
+                                .extend(reasons);
+                        }
+                    }
+                }
+            }
+        }
+        valid_moves
+    }
+
+    /// Cheapest movement cost from the unit's hex to each hex within `max`,
+    /// paying what the unit pays per step. Influence, stacking and
+    /// proximity are left out.
+    #[must_use]
+    pub fn path_costs(
+        &self,
+        board: &RulesBoard<'_>,
+        unit: usize,
+        max: i64,
+    ) -> HashMap<HexPosition, i64> {
+        let mover = &board.units[unit];
+        let steps = BareSteps::new(*self, board);
+        let bare = steps.unit(mover);
+        path_costs_from(&steps.context(&bare, max), &board.tiles, mover.pos, max)
+    }
+
+    /// Stacking points already in each hex.
+    fn stack_points(&self, board: &RulesBoard<'_>) -> HashMap<HexPosition, u32> {
+        let mut points: HashMap<HexPosition, u32> = HashMap::new();
+        for unit in &board.units {
+            *points.entry(unit.pos).or_insert(0) += self.stacking_rule.points(unit.data);
+        }
+        points
+    }
+
+    /// Whether a unit with `data` owned by `owner` may end a move in, or be
+    /// placed at, `pos`: it stays within the hex's stacking limit and does
+    /// not mix opposing factions where that is forbidden.
+    #[must_use]
+    pub fn stacking_allows(
+        &self,
+        board: &RulesBoard<'_>,
+        pos: HexPosition,
+        data: &EntityData,
+        owner: UnitOwner,
+    ) -> bool {
+        self.stack_fits(board, pos, &[(data, owner)], &[])
+    }
+
+    /// Whether the `entering` units, together, fit in `pos` alongside the
+    /// units already there other than those at indices `leaving`.
+    fn stack_fits(
+        &self,
+        board: &RulesBoard<'_>,
+        pos: HexPosition,
+        entering: &[(&EntityData, UnitOwner)],
+        leaving: &[usize],
+    ) -> bool {
+        let present: Vec<&BoardUnit<'_>> = board
+            .units
+            .iter()
+            .enumerate()
+            .filter(|(index, u)| u.pos == pos && !leaving.contains(index))
+            .map(|(_, u)| u)
+            .collect();
+        let points: u32 = present
+            .iter()
+            .map(|u| self.stacking_rule.points(u.data))
+            .sum();
+        let adding: u32 = entering
+            .iter()
+            .map(|(data, _)| self.stacking_rule.points(data))
+            .sum();
+        let terrain = board.tile(pos).map(|t| t.entity_type_id);
+        entering.iter().all(|&(_, owner)| {
+            !StackingRule::would_exceed(adding, points, self.stacking_rule.limit(terrain, owner))
+                && !self
+                    .stacking_rule
+                    .would_mix_factions(owner, present.iter().map(|u| u.owner))
+        })
+    }
+
+    /// Every hex whose units hold more stacking points than its limit, using
+    /// the strictest limit among the factions present, ordered by position.
+    #[must_use]
+    pub fn stacking_violations(&self, board: &RulesBoard<'_>) -> Vec<StackingViolation> {
+        let mut found = Vec::new();
+        if !self.stacking_rule.is_active() {
+            return found;
+        }
+        let mut owners: HashMap<HexPosition, Vec<UnitOwner>> = HashMap::new();
+        for unit in &board.units {
+            owners.entry(unit.pos).or_default().push(unit.owner);
+        }
+        for (position, points) in self.stack_points(board) {
+            let terrain = board.tile(position).map(|t| t.entity_type_id);
+            let limit = owners[&position]
+                .iter()
+                .filter_map(|owner| self.stacking_rule.limit(terrain, *owner))
+                .min();
+            if let Some(limit) = limit
+                && points > limit
+            {
+                found.push(StackingViolation {
+                    position,
+                    points,
+                    limit,
+                });
+            }
+        }
+        found.sort_by_key(|v| (v.position.q, v.position.r));
+        found
+    }
+
+    /// Checks `constraint` for the unit at index `unit`, with the unit and
+    /// the tile it stands on filling the constraint's roles.
+    #[must_use]
+    pub fn check_constraint(
+        &self,
+        board: &RulesBoard<'_>,
+        unit: usize,
+        constraint: &Constraint,
+    ) -> ValidationResult {
+        let steps = BareSteps::new(*self, board);
+        let proximity = ProximityBoard::of(self.grid_config, board);
+        let bare = steps.unit(&board.units[unit]);
+        let ctx = steps.context(&bare, 0);
+        let scope = steps.unit_scope(constraint.concept_id, &bare, &proximity, &ctx);
+        let outcome = evaluate_block_condition(&constraint.expression, &scope);
+        ValidationResult {
+            constraint_id: constraint.id,
+            constraint_name: constraint.name.clone(),
+            satisfied: outcome.holds,
+            explanation: outcome.detail,
+        }
+    }
+
+    /// The actions each unit may not take, in board order. Each constraint
+    /// that gates actions is checked for every unit whose type is bound to
+    /// its concept (see `check_constraint`); a constraint that does not hold
+    /// denies its gated actions.
+    #[must_use]
+    pub fn action_eligibility(&self, board: &RulesBoard<'_>) -> Vec<ActionEligibility> {
+        let gating: Vec<&Constraint> = self
+            .constraints
+            .constraints
+            .iter()
+            .filter(|c| !c.gates.is_empty())
+            .collect();
+        if gating.is_empty() {
+            return vec![ActionEligibility::default(); board.units.len()];
+        }
+
+        let steps = BareSteps::new(*self, board);
+        let proximity = ProximityBoard::of(self.grid_config, board);
+        board
+            .units
+            .iter()
+            .map(|unit| {
+                let bare = steps.unit(unit);
+                let ctx = steps.context(&bare, 0);
+                let unit_type_name = self
+                    .entity_types
+                    .get(unit.data.entity_type_id)
+                    .map_or("Unit", |et| et.name.as_str());
+                let mut eligibility = ActionEligibility::default();
+                for constraint in &gating {
+                    let bound = bare
+                        .bindings
+                        .iter()
+                        .any(|b| b.concept_id == constraint.concept_id);
+                    if !bound {
+                        continue;
+                    }
+                    let scope = steps.unit_scope(constraint.concept_id, &bare, &proximity, &ctx);
+                    let outcome = evaluate_block_condition(&constraint.expression, &scope);
+                    if outcome.holds {
+                        continue;
+                    }
+                    for action in &constraint.gates {
+                        let verb = match action {
+                            GatedAction::Move => "move",
+                            GatedAction::Attack => "attack",
+                        };
+                        eligibility.denied.push((
+                            *action,
+                            ValidationResult {
+                                constraint_id: constraint.id,
+                                constraint_name: constraint.name.clone(),
+                                satisfied: false,
+                                explanation: format!(
+                                    "{unit_type_name} may not {verb}: {} does not hold ({})",
+                                    constraint.name, outcome.detail
+                                ),
+                            },
+                        ));
+                    }
+                }
+                eligibility
+            })
+            .collect()
+    }
+
+    /// Hexes around the unit at index `unit` within `max` of it for each
+    /// gating `Proximity` expression that looks for units of its type (or
+    /// for any unit). Path-cost radii are measured with the unit's own
+    /// movement costs. Empty when no expression looks for it.
+    #[must_use]
+    pub fn command_radius(&self, board: &RulesBoard<'_>, unit: usize) -> HashSet<HexPosition> {
+        let mover = &board.units[unit];
+        let mut expressions = Vec::new();
+        for constraint in self
+            .constraints
+            .constraints
+            .iter()
+            .filter(|c| !c.gates.is_empty())
+        {
+            collect_proximity(&constraint.expression, &mut expressions);
+        }
+        expressions.retain(|(filter, ..)| {
+            filter
+                .entity_type_id
+                .is_none_or(|id| id == mover.data.entity_type_id)
+        });
+        let mut hexes = HashSet::new();
+        if expressions.is_empty() {
+            return hexes;
+        }
+
+        let steps = BareSteps::new(*self, board);
+        let proximity = ProximityBoard {
+            grid_config: self.grid_config,
+            tiles: &board.tiles,
+            units: Vec::new(),
+        };
+        let bare = steps.unit(mover);
+        let ctx = steps.context(&bare, 0);
+        for (_, measure, max, line_of_sight) in expressions {
+            let within: Vec<HexPosition> = match measure {
+                ProximityMeasure::Distance => self
+                    .grid_config
+                    .range(mover.pos, u32::try_from(max).unwrap_or(0)),
+                ProximityMeasure::PathCost => path_costs_from(&ctx, &board.tiles, mover.pos, max)
+                    .into_keys()
+                    .collect(),
+            };
+            hexes.extend(within.into_iter().filter(|to| {
+                line_of_sight.is_none_or(|blockers| proximity.in_sight(mover.pos, *to, blockers))
+            }));
+        }
+        hexes
+    }
+
+    /// Traces `rule` for the units it traces, back to its sources at
+    /// movement cost within `max_cost`, around blocking terrain, edges and
+    /// (with `influence`) enemy zones. Traced units sharing a type, owner,
+    /// state and cost classification share one trace.
+    #[must_use]
+    pub fn trace_reachability(
+        &self,
+        board: &RulesBoard<'_>,
+        rule: &ReachabilityRule,
+        influence: &InfluenceMap,
+    ) -> Vec<UnitTrace> {
+        let steps = BareSteps::new(*self, board);
+        let proximity = ProximityBoard::of(self.grid_config, board);
+        let mut groups: Vec<TraceGroup> = Vec::new();
+        for (index, unit) in board.units.iter().enumerate() {
+            if !rule.traced_types.contains(&unit.data.entity_type_id) {
+                continue;
+            }
+            let bare = steps.unit(unit);
+            match groups.iter_mut().find(|g| {
+                g.unit.data.entity_type_id == unit.data.entity_type_id
+                    && g.unit.owner == unit.owner
+                    && g.unit.state == unit.state
+                    && g.unit.classification == bare.classification
+            }) {
+                Some(group) => group.units.push(index),
+                None => groups.push(TraceGroup {
+                    unit: bare,
+                    units: vec![index],
+                }),
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|group| {
+                let ctx = StepContext {
+                    proximity: Some(&proximity),
+                    ..steps.context(&group.unit, rule.max_cost)
+                };
+                let trace = TraceContext {
+                    rule,
+                    owner: group.unit.owner,
+                    tiles: &board.tiles,
+                    influence_map: influence,
+                };
+                let sources = trace_sources(&trace, self.grid_config, &board.tiles, &board.units);
+                let reached = trace_to_sources(&ctx, &trace, &sources);
+                UnitTrace {
+                    units: group.units,
+                    reached,
+                }
+            })
+            .collect()
+    }
+
+    /// The first hex of `route` where a movement interrupt rule halts the
+    /// unit at index `unit`: an enemy unit the rule lets react is in
+    /// influence, sight or range of it. `route` lists the hexes the unit
+    /// enters, in order, without its own. Rule and reacting unit pairs in
+    /// `resolved` already reacted this move and are skipped.
+    #[must_use]
+    pub fn movement_interrupt(
+        &self,
+        board: &RulesBoard<'_>,
+        unit: usize,
+        route: &[HexPosition],
+        influence: &InfluenceMap,
+        resolved: &[(TypeId, usize)],
+    ) -> Option<MovementInterrupt> {
+        let mover = &board.units[unit];
+        let rules: Vec<&MovementInterruptRule> = self
+            .interrupts
+            .rules
+            .iter()
+            .filter(|r| r.mover_type.is_none_or(|t| t == mover.data.entity_type_id))
+            .collect();
+        if rules.is_empty() {
+            return None;
+        }
+        let sight = ProximityBoard {
+            grid_config: self.grid_config,
+            tiles: &board.tiles,
+            units: Vec::new(),
+        };
+        route.iter().enumerate().find_map(|(step, &pos)| {
+            rules.iter().find_map(|rule| {
+                board
+                    .units
+                    .iter()
+                    .enumerate()
+                    .find(|(index, reactor)| {
+                        mover.owner.opposes(reactor.owner)
+                            && rule
+                                .reactor_type
+                                .is_none_or(|t| t == reactor.data.entity_type_id)
+                            && !resolved.contains(&(rule.id, *index))
+                            && self.reacts(rule, reactor, pos, influence, &sight)
+                    })
+                    .map(|(reactor, _)| MovementInterrupt {
+                        step,
+                        pos,
+                        rule_id: rule.id,
+                        reactor,
+                    })
+            })
+        })
+    }
+
+    /// Whether `reactor` reacts under `rule` to a unit entering `pos`.
+    fn reacts(
+        &self,
+        rule: &MovementInterruptRule,
+        reactor: &BoardUnit<'_>,
+        pos: HexPosition,
+        influence: &InfluenceMap,
+        sight: &ProximityBoard<'_>,
+    ) -> bool {
+        match &rule.trigger {
+            InterruptTrigger::Influence { rule_id } => influence.get(pos).is_some_and(|entries| {
+                entries.iter().any(|entry| {
+                    entry.rule_id == *rule_id
+                        && entry.source_vertex.is_none()
+                        && entry.source_pos == reactor.pos
+                })
+            }),
+            InterruptTrigger::LineOfSight { blockers } => reactor.sight.is_some_and(|range| {
+                self.grid_config.distance(reactor.pos, pos) <= range
+                    && sight.in_sight(reactor.pos, pos, blockers)
+            }),
+            InterruptTrigger::Proximity { max } => {
+                self.grid_config.distance(reactor.pos, pos) <= *max
+            }
+        }
+    }
+
+    /// Context values an interrupt by the unit at index `reactor` on the
+    /// unit at index `mover`, halted at `pos`, is resolved with: numeric
+    /// properties as `reactor.<name>` and `mover.<name>`, and `distance`
+    /// between the reactor and `pos`.
+    #[must_use]
+    pub fn interrupt_values(
+        &self,
+        board: &RulesBoard<'_>,
+        reactor: usize,
+        mover: usize,
+        pos: HexPosition,
+    ) -> HashMap<String, f64> {
+        let mut values = self.role_values(board, [("reactor", reactor), ("mover", mover)]);
+        let distance = self.grid_config.distance(board.units[reactor].pos, pos);
+        values.insert("distance".to_string(), f64::from(distance));
+        values
+    }
+
+    /// Numeric properties of the units at the given board indices, keyed
+    /// `<role>.<name>`.
+    fn role_values<const N: usize>(
+        &self,
+        board: &RulesBoard<'_>,
+        roles: [(&str, usize); N],
+    ) -> HashMap<String, f64> {
+        let mut values = HashMap::new();
+        for (role, unit) in roles {
+            let unit = &board.units[unit];
+            let Some(entity_type) = self.entity_types.get(unit.data.entity_type_id) else {
+                continue;
+            };
+            for property in &entity_type.properties {
+                if let Some(value) = unit
+                    .data
+                    .properties
+                    .get(&property.id)
+                    .and_then(property_value_as_f64)
+                {
+                    values.insert(format!("{role}.{}", property.name), value);
+                }
+            }
+        }
+        values
+    }
+
+    /// Whether the unit at index `attacker` may overrun the unit at index
+    /// `defender` under `rule`: the types match the rule, the units are
+    /// enemies in adjacent hexes, the attacker may attack, and it has the
+    /// rule's movement point cost left.
+    #[must_use]
+    pub fn can_overrun(
+        &self,
+        board: &RulesBoard<'_>,
+        attacker: usize,
+        defender: usize,
+        rule: &OverrunRule,
+    ) -> bool {
+        let (unit, target) = (&board.units[attacker], &board.units[defender]);
+        rule.attacker_type
+            .is_none_or(|t| t == unit.data.entity_type_id)
+            && rule
+                .defender_type
+                .is_none_or(|t| t == target.data.entity_type_id)
+            && unit.owner.opposes(target.owner)
+            && self.grid_config.distance(unit.pos, target.pos) == 1
+            && unit
+                .eligibility
+                .is_none_or(|e| e.allows(GatedAction::Attack))
+            && self.budget(unit) - unit.spent >= rule.mp_cost
+    }
+
+    /// Context values an overrun by the unit at index `attacker` on the
+    /// unit at index `defender` is resolved with: numeric properties as
+    /// `attacker.<name>` and `defender.<name>`, and their `distance`.
+    #[must_use]
+    pub fn overrun_values(
+        &self,
+        board: &RulesBoard<'_>,
+        attacker: usize,
+        defender: usize,
+    ) -> HashMap<String, f64> {
+        let mut values = self.role_values(board, [("attacker", attacker), ("defender", defender)]);
+        let distance = self
+            .grid_config
+            .distance(board.units[attacker].pos, board.units[defender].pos);
+        values.insert("distance".to_string(), f64::from(distance));
+        values
+    }
+
+    /// Resolves an overrun by the unit at index `attacker` on the unit at
+    /// index `defender` under the overrun rule `rule_id`, rolling with
+    /// `rng`. `None` when the rule is unknown or does not allow it (see
+    /// `can_overrun`). To move on after a cleared defender, remove it from
+    /// the board and set the attacker's `spent` to the report's: its valid
+    /// moves then start from its hex with the budget left.
+    pub fn overrun(
+        &self,
+        board: &RulesBoard<'_>,
+        attacker: usize,
+        defender: usize,
+        rule_id: TypeId,
+        rng: &mut SimulationRng,
+    ) -> Option<OverrunReport> {
+        let rule = self.overruns.get(rule_id)?;
+        if !self.can_overrun(board, attacker, defender, rule) {
+            return None;
+        }
+        let values = self.overrun_values(board, attacker, defender);
+        let result = resolve_overrun(rule, &values, rng);
+        let unit = &board.units[attacker];
+        let spent = if result.clears_defender {
+            unit.spent + rule.mp_cost
+        } else {
+            unit.spent.max(self.budget(unit))
+        };
+        Some(OverrunReport { result, spent })
+    }
+
+    /// The equally good paths the units at indices `units`, sharing a hex,
+    /// may retreat `hexes` hexes along, away from every hex in `threats`
+    /// (see `find_constrained_paths`). Each hex entered must be on the board,
+    /// one every retreating unit's movement rules let it enter (impassable
+    /// terrain and blocked hexes are left out), free of units opposed to them
+    /// and of the zones of influence projected by opposing or threatening
+    /// units, and within stacking limits for the whole retreating stack.
+    /// Only full-length paths ending farther from the nearest threat are
+    /// offered: a stack that cannot get away the full distance has no path.
+    #[must_use]
+    pub fn retreat_paths(
+        &self,
+        board: &RulesBoard<'_>,
+        units: &[usize],
+        hexes: u32,
+        threats: &[HexPosition],
+    ) -> Vec<Vec<HexPosition>> {
+        let Some(unit) = units.first().map(|&index| &board.units[index]) else {
+            return Vec::new();
+        };
+        let retreating: Vec<(&EntityData, UnitOwner)> = units
+            .iter()
+            .map(|&index| (board.units[index].data, board.units[index].owner))
+            .collect();
+        let opposed = |owner: UnitOwner| retreating.iter().any(|(_, own)| own.opposes(owner));
+        // Hexes every retreating unit can enter at some cost.
+        let enterable: Vec<HashMap<HexPosition, i64>> = units
+            .iter()
+            .map(|&index| self.path_costs(board, index, i64::from(u32::MAX)))
+            .collect();
+        let valid_positions = self
+            .grid_config
+            .range(unit.pos, hexes)
+            .into_iter()
+            .filter(|pos| {
+                board.tile(*pos).is_some()
+                    && enterable.iter().all(|costs| costs.contains_key(pos))
+                    && !board
+                        .units
+                        .iter()
+                        .any(|u| u.pos == *pos && opposed(u.owner))
+                    && self.stack_fits(board, *pos, &retreating, units)
+            })
+            .collect();
+
+        // Zones of control the retreat may not enter, one per influence rule.
+        let mut influence_zones: HashMap<String, HashSet<HexPosition>> = HashMap::new();
+        for (pos, entries) in &self.influence_map(board).influenced {
+            for entry in entries {
+                if opposed(entry.source_owner) || threats.contains(&entry.source_pos) {
+                    influence_zones
+                        .entry(entry.rule_id.0.to_string())
+                        .or_default()
+                        .insert(*pos);
+                }
+            }
+        }
+        let request = ConstrainedPathRequest {
+            start: unit.pos,
+            constraints: threats
+                .iter()
+                .map(|source| PathConstraint::AwayFrom { source: *source })
+                .chain(influence_zones.keys().map(|influence_type| {
+                    PathConstraint::AvoidInfluence {
+                        influence_type: influence_type.clone(),
+                    }
+                }))
+                .collect(),
+            max_distance: hexes,
+        };
+        let ctx = PathfindingContext {
+            valid_positions,
+            influence_zones,
+            terrain_types: HashMap::new(),
+            grid_config: Some(self.grid_config),
+        };
+        let nearest_threat = |pos: HexPosition| {
+            threats
+                .iter()
+                .map(|threat| self.grid_config.distance(pos, *threat))
+                .min()
+                .unwrap_or(u32::MAX)
+        };
+        let full_length = hexes as usize + 1;
+        find_constrained_paths(&request, &ctx)
+            .into_iter()
+            .filter(|path| {
+                path.len() == full_length
+                    && path
+                        .last()
+                        .is_some_and(|end| nearest_threat(*end) > nearest_threat(unit.pos))
+            })
+            .collect()
+    }
+
+    /// The vacated hexes each of the units at indices `attackers` may
+    /// advance into after combat: hexes of `vacated` adjacent to its own
+    /// (across the seam of a wrapping board) where it fits the stacking
+    /// limits.
+    #[must_use]
+    pub fn advance_options(
+        &self,
+        board: &RulesBoard<'_>,
+        attackers: &[usize],
+        vacated: &[HexPosition],
+    ) -> Vec<(usize, HexPosition)> {
+        let mut options = Vec::new();
+        for &to in vacated {
+            for &index in attackers {
+                let unit = &board.units[index];
+                if self.grid_config.distance(unit.pos, to) == 1
+                    && self.stacking_allows(board, to, unit.data, unit.owner)
+                {
+                    options.push((index, to));
+                }
+            }
+        }
+        options
+    }
+
+    /// Whether the conditional CRT `table` applies to a combat by the units
+    /// at indices `attackers` on the units at indices `defenders` in the
+    /// phase `phase_id`: the phase and the first attacker's faction are
+    /// listed (or the lists are empty) and the selection condition holds
+    /// over the table's combat roles (see `combat_condition`).
+    #[must_use]
+    pub fn combat_table_applies(
+        &self,
+        board: &RulesBoard<'_>,
+        table: &CombatResultsTable,
+        phase_id: Option<TypeId>,
+        attackers: &[usize],
+        defenders: &[usize],
+    ) -> ValidationResult {
+        let selection = &table.selection;
+        let faction = attackers
+            .first()
+            .and_then(|&index| board.units[index].owner.faction_id);
+        let outcome = if !selection.phases.is_empty()
+            && !phase_id.is_some_and(|id| selection.phases.contains(&id))
+        {
+            ConditionOutcome {
+                holds: false,
+                detail: "not used in this phase".to_string(),
+            }
+        } else if !selection.factions.is_empty()
+            && !faction.is_some_and(|id| selection.factions.contains(&id))
+        {
+            ConditionOutcome {
+                holds: false,
+                detail: "not used by the attacking faction".to_string(),
+            }
+        } else if let Some(condition) = &selection.condition {
+            self.combat_condition(board, table, attackers, defenders, condition)
+        } else {
+            ConditionOutcome {
+                holds: true,
+                detail: "no condition".to_string(),
+            }
+        };
+        ValidationResult {
+            constraint_id: table.id,
+            constraint_name: table.name.clone(),
+            satisfied: outcome.holds,
+            explanation: outcome.detail,
+        }
+    }
+
+    /// The conditional table a combat by the units at indices `attackers`
+    /// on the units at indices `defenders` in the phase `phase_id` is
+    /// resolved on: the first of `tables` that applies (see
+    /// `combat_table_applies`). `None` resolves it on the default table.
+    #[must_use]
+    pub fn select_combat_table<'t>(
+        &self,
+        board: &RulesBoard<'_>,
+        tables: &'t CombatTableRegistry,
+        phase_id: Option<TypeId>,
+        attackers: &[usize],
+        defenders: &[usize],
+    ) -> Option<&'t CombatResultsTable> {
+        tables.tables.iter().find(|table| {
+            self.combat_table_applies(board, table, phase_id, attackers, defenders)
+                .satisfied
+        })
+    }
+
+    /// Whether the combat modifier `modifier` applies to a combat by the
+    /// units at indices `attackers` on the units at indices `defenders`
+    /// resolved on `crt`: the primary defender's terrain matches the
+    /// modifier's terrain filter and its condition holds over the CRT's
+    /// combat roles (see `combat_condition`).
+    #[must_use]
+    pub fn modifier_applies(
+        &self,
+        board: &RulesBoard<'_>,
+        crt: &CombatResultsTable,
+        modifier: &CombatModifierDefinition,
+        attackers: &[usize],
+        defenders: &[usize],
+    ) -> ValidationResult {
+        let type_name = |id: TypeId| {
+            self.entity_types
+                .get(id)
+                .map_or("Unknown", |et| et.name.as_str())
+        };
+        let mut details = Vec::new();
+        let mut holds = true;
+        if let Some(terrain_id) = modifier.terrain_type_filter {
+            let expected = type_name(terrain_id);
+            let terrain = defenders
+                .first()
+                .and_then(|&index| board.tile(board.units[index].pos));
+            match terrain {
+                Some(tile) if tile.entity_type_id == terrain_id => {
+                    details.push(format!("defender terrain is {expected}"));
+                }
+                Some(tile) => {
+                    holds = false;
+                    details.push(format!(
+                        "defender terrain is {}, not {expected}",
+                        type_name(tile.entity_type_id)
+                    ));
+                }
+                None => {
+                    holds = false;
+                    details.push("defender terrain is not present".to_string());
+                }
+            }
+        }
+        if holds && let Some(condition) = &modifier.condition {
+            let outcome = self.combat_condition(board, crt, attackers, defenders, condition);
+            holds = outcome.holds;
+            details.push(outcome.detail);
+        }
+        ValidationResult {
+            constraint_id: modifier.id,
+            constraint_name: modifier.name.clone(),
+            satisfied: holds,
+            explanation: if details.is_empty() {
+                "no condition".to_string()
+            } else {
+                details.join(" and ")
+            },
+        }
+    }
+
+    /// Evaluates `condition` over the roles of `table`'s Combat concept:
+    /// every attacker and defender fill the attacker and defender roles,
+    /// the primary defender's tile the terrain role and the features on the
+    /// edges between the attackers and the primary defender the edge role.
+    /// A leaf on a role several entities fill holds when it holds for any of
+    /// them. Roles the table leaves unset are matched against the concept
+    /// bindings; proximity is measured from the primary attacker.
+    fn combat_condition(
+        &self,
+        board: &RulesBoard<'_>,
+        table: &CombatResultsTable,
+        attackers: &[usize],
+        defenders: &[usize],
+        condition: &ConstraintExpr,
+    ) -> ConditionOutcome {
+        let Some(concept_id) = table.combat_concept_id else {
+            return ConditionOutcome {
+                holds: false,
+                detail: "the table has no Combat concept".to_string(),
+            };
+        };
+        let attacker = attackers.first().map(|&index| &board.units[index]);
+        let defender = defenders.first().map(|&index| &board.units[index]);
+        let terrain_pos = defender.map(|unit| unit.pos);
+        let tile = terrain_pos.and_then(|pos| board.tile(pos));
+        let tile_state = terrain_pos.and_then(|pos| board.tile_states.get(&pos).copied());
+        let edges: Vec<EntityData> = defender
+            .into_iter()
+            .flat_map(|d| {
+                attackers.iter().filter_map(move |&index| {
+                    let a = &board.units[index];
+                    self.grid_config
+                        .normalize_edge(a.pos, d.pos)
+                        .and_then(|edge| self.edges.get(&edge))
+                })
+            })
+            .filter_map(|feature| edge_entity_data(feature, self.entity_types))
+            .collect();
+
+        let roles = &table.roles;
+        let unit_fills = |role_id: Option<TypeId>, units: &[usize]| -> Vec<RoleFill<'_>> {
+            role_id.map_or_else(Vec::new, |role_id| {
+                units
+                    .iter()
+                    .map(|&index| RoleFill::unit((role_id, &board.units[index])))
+                    .collect()
+            })
+        };
+        let mut fills = unit_fills(roles.attacker, attackers);
+        fills.extend(unit_fills(roles.defender, defenders));
+        fills.extend(roles.terrain.zip(tile).map(|(role_id, data)| RoleFill {
+            role_id,
+            data,
+            state: tile_state,
+            pos: terrain_pos,
+            reach: None,
+        }));
+        if let Some(role_id) = roles.edge {
+            fills.extend(edges.iter().map(|data| RoleFill {
+                role_id,
+                data,
+                state: None,
+                pos: None,
+                reach: None,
+            }));
+        }
+
+        let proximity = ProximityBoard::of(self.grid_config, board);
+        let scope = ConditionScope {
+            concept_id,
+            relation: None,
+            concepts: self.concepts,
+            entity_types: self.entity_types,
+            state_machines: self.state_machines,
+            unit: attacker.map(|unit| unit.data),
+            tile,
+            edge: edges.first(),
+            unit_state: attacker.and_then(|unit| unit.state),
+            tile_state,
+            reachability: self.reachability,
+            unit_reach: attacker.and_then(|unit| unit.reach),
+            spent: 0,
+            unit_pos: attacker.map(|unit| unit.pos),
+            tile_pos: terrain_pos,
+            unit_owner: attacker.map(|unit| unit.owner),
+            proximity: Some(&proximity),
+            step: None,
+            fills: &fills,
+        };
+        evaluate_block_condition(condition, &scope)
+    }
+
+    /// Moves the unit at index `unit` along `route` (see
+    /// `movement_interrupt`), resolving each interrupt it meets with `rng`.
+    /// The unit stops where an outcome ends its movement and otherwise goes
+    /// on; several enemy units may react at the same hex.
+    pub fn resolve_move(
+        &self,
+        board: &RulesBoard<'_>,
+        unit: usize,
+        route: &[HexPosition],
+        rng: &mut SimulationRng,
+    ) -> MoveReport {
+        let influence = self.influence_map(board);
+        let mut report = MoveReport {
+            entered: route.len(),
+            ..MoveReport::default()
+        };
+        let mut resolved = Vec::new();
+        let mut from = 0;
+        while let Some(found) =
+            self.movement_interrupt(board, unit, &route[from..], &influence, &resolved)
+        {
+            let interrupt = MovementInterrupt {
+                step: from + found.step,
+                ..found
+            };
+            let Some(rule) = self.interrupts.get(interrupt.rule_id) else {
+                break;
+            };
+            let values = self.interrupt_values(board, interrupt.reactor, unit, interrupt.pos);
+            let result = resolve_interrupt(rule, &values, rng);
+            resolved.push((interrupt.rule_id, interrupt.reactor));
+            report.state = result.set_state.or(report.state);
+            let end_movement = result.end_movement;
+            report.interrupts.push((interrupt, result));
+            if end_movement {
+                report.entered = interrupt.step + 1;
+                break;
+            }
+            from = interrupt.step;
+        }
+        report
+    }
+}
+
+/// Shared inputs for step contexts outside a unit's own move. These pay
+/// movement costs only: influence, stacking and mixed stacks are left out.
+struct BareSteps<'a> {
+    rules: RulesContext<'a>,
+    board: &'a RulesBoard<'a>,
+    on_enter_relations: Vec<&'a Relation>,
+    on_exit_relations: Vec<&'a Relation>,
+    no_influence: InfluenceMap,
+    no_stacking: StackingRule,
+    no_units: HashMap<HexPosition, u32>,
+    no_owners: HashMap<HexPosition, Vec<UnitOwner>>,
+}
+
+/// The unit side of a bare step context.
+struct BareUnit<'a> {
+    data: &'a EntityData,
+    bindings: Vec<&'a ConceptBinding>,
+    classification: Option<String>,
+    pos: HexPosition,
+    owner: UnitOwner,
+    state: Option<TypeId>,
+    reach: Option<&'a ReachabilityStatus>,
+}
+
+/// Units tracing a rule that share a movement profile, and so a trace.
+struct TraceGroup<'a> {
+    unit: BareUnit<'a>,
+    units: Vec<usize>,
+}
+
+impl<'a> BareSteps<'a> {
+    fn new(rules: RulesContext<'a>, board: &'a RulesBoard<'a>) -> Self {
+        let relations_with = |trigger: RelationTrigger| {
+            rules
+                .relations
+                .relations
+                .iter()
+                .filter(|r| r.trigger == trigger)
+                .collect()
+        };
+        Self {
+            rules,
+            board,
+            on_enter_relations: relations_with(RelationTrigger::OnEnter),
+            on_exit_relations: relations_with(RelationTrigger::OnExit),
+            no_influence: InfluenceMap::default(),
+            no_stacking: StackingRule::default(),
+            no_units: HashMap::new(),
+            no_owners: HashMap::new(),
+        }
+    }
+
+    /// The unit side of a context for `unit`.
+    fn unit(&self, unit: &BoardUnit<'a>) -> BareUnit<'a> {
+        BareUnit {
+            data: unit.data,
+            bindings: self
+                .rules
+                .concepts
+                .bindings
+                .iter()
+                .filter(|b| b.entity_type_id == unit.data.entity_type_id)
+                .collect(),
+            classification: unit_classification(self.rules.movement_cost_matrix, unit.data),
+            pos: unit.pos,
+            owner: unit.owner,
+            state: unit.state,
+            reach: unit.reach,
+        }
+    }
+
+    /// A step context for `unit` with `initial_budget` to spend.
+    fn context<'b>(&'b self, unit: &'b BareUnit<'b>, initial_budget: i64) -> StepContext<'b> {
+        let rules = &self.rules;
+        StepContext {
+            unit_data: unit.data,
+            unit_bindings: &unit.bindings,
+            on_enter_relations: &self.on_enter_relations,
+            on_exit_relations: &self.on_exit_relations,
+            concepts: rules.concepts,
+            entity_types: rules.entity_types,
+            edge_registry: rules.edges,
+            vertex_registry: rules.vertices,
+            grid_config: rules.grid_config,
+            influence_map: &self.no_influence,
+            influence_rules: rules.influence_rules,
+            unit_pos: unit.pos,
+            unit_owner: unit.owner,
+            stacking_rule: &self.no_stacking,
+            stack_points: &self.no_units,
+            unit_owners: &self.no_owners,
+            movement_cost_matrix: rules.movement_cost_matrix,
+            unit_classification: unit.classification.as_deref(),
+            area_markers: rules.area_markers,
+            initial_budget,
+            state_machines: rules.state_machines,
+            unit_state: unit.state,
+            tile_states: &self.board.tile_states,
+            reachability: rules.reachability,
+            unit_reach: unit.reach,
+            proximity: None,
+        }
+    }
+
+    /// A condition scope for a constraint of `concept_id`, with `unit` and
+    /// the tile it stands on filling the roles.
+    fn unit_scope<'b>(
+        &'b self,
+        concept_id: TypeId,
+        unit: &'b BareUnit<'b>,
+        proximity: &'b ProximityBoard<'b>,
+        step: &'b StepContext<'b>,
+    ) -> ConditionScope<'b> {
+        ConditionScope {
+            concept_id,
+            relation: None,
+            concepts: self.rules.concepts,
+            entity_types: self.rules.entity_types,
+            state_machines: self.rules.state_machines,
+            unit: Some(unit.data),
+            tile: self.board.tile(unit.pos),
+            edge: None,
+            unit_state: unit.state,
+            tile_state: self.board.tile_states.get(&unit.pos).copied(),
+            reachability: self.rules.reachability,
+            unit_reach: unit.reach,
+            spent: 0,
+            unit_pos: Some(unit.pos),
+            tile_pos: Some(unit.pos),
+            unit_owner: Some(unit.owner),
+            proximity: Some(proximity),
+            step: Some(step),
+            fills: &[],
+        }
+    }
+}
+
+/// Shared context for step evaluation, avoiding excessive parameter counts.
+#[derive(Clone, Copy)]
+pub(crate) struct StepContext<'a> {
+    unit_data: &'a EntityData,
+    unit_bindings: &'a [&'a ConceptBinding],
+    on_enter_relations: &'a [&'a Relation],
+    on_exit_relations: &'a [&'a Relation],
+    concepts: &'a ConceptRegistry,
+    entity_types: &'a EntityTypeRegistry,
+    edge_registry: &'a HexEdgeRegistry,
+    vertex_registry: &'a HexVertexRegistry,
+    grid_config: &'a HexGridConfig,
+    influence_map: &'a InfluenceMap,
+    influence_rules: &'a InfluenceRuleRegistry,
+    /// Position of the moving unit (to exclude self-influence).
+    unit_pos: HexPosition,
+    /// Owner of the moving unit, for enemy-only zones and mixed stacks.
+    unit_owner: UnitOwner,
+    stacking_rule: &'a StackingRule,
+    /// Stacking points already in each hex.
+    stack_points: &'a HashMap<HexPosition, u32>,
+    unit_owners: &'a HashMap<HexPosition, Vec<UnitOwner>>,
+    movement_cost_matrix: &'a MovementCostMatrix,
+    /// The unit's classification value for matrix cost lookup.
+    unit_classification: Option<&'a str>,
+    area_markers: &'a AreaMarkerRegistry,
+    /// Budget at the start of the move, to derive what a path has spent.
+    initial_budget: i64,
+    state_machines: &'a StateMachineRegistry,
+    /// State-machine state of the moving unit and of each tile that has one.
+    unit_state: Option<TypeId>,
+    tile_states: &'a HashMap<HexPosition, TypeId>,
+    reachability: &'a ReachabilityRuleRegistry,
+    /// The moving unit's latest reachability results.
+    unit_reach: Option<&'a ReachabilityStatus>,
+    /// Units that proximity conditions measure to.
+    proximity: Option<&'a ProximityBoard<'a>>,
+}
+
+impl StepContext<'_> {
+    /// Whether an influence entry at `pos` affects the moving unit.
+    /// Influence it projects itself is ignored, vertex features always
+    /// apply, enemy-only rules apply only when the source's owner opposes
+    /// the mover's, and rules negated by friendly units do not apply where
+    /// a unit of the mover's faction stands.
+    fn influence_applies(&self, entry: &InfluenceEntry, pos: HexPosition) -> bool {
+        if entry.source_vertex.is_none() && entry.source_pos == self.unit_pos {
+            return false;
+        }
+        self.influence_rules.get(entry.rule_id).is_none_or(|rule| {
+            (!rule.enemy_only || entry.source_owner.opposes(self.unit_owner))
+                && !(rule.zone.negated_by_friendly && self.friendly_at(pos))
+        })
+    }
+
+    /// Whether a unit of the mover's faction, other than the mover, stands
+    /// at `pos`.
+    fn friendly_at(&self, pos: HexPosition) -> bool {
+        self.unit_owners.get(&pos).is_some_and(|owners| {
+            owners
+                .iter()
+                .any(|owner| owner.is_friendly_to(self.unit_owner))
+        })
+    }
+}
+
+/// Result of evaluating a single BFS step into a neighbor hex.
+enum StepResult {
+    /// The unit may enter the hex. With `overstack` set it may pass through
+    /// but not end its move there.
+    Valid {
+        new_budget: i64,
+        components: Vec<CostComponent>,
+        overstack: Option<ValidationResult>,
+    },
+    Blocked {
+        reasons: Vec<ValidationResult>,
+    },
+}
+
+/// Free-movement BFS: all positions within grid bounds reachable from the
+/// unit's position (no budget limit beyond the board edge). Routes are
+/// recorded as the shortest hop sequence, with no cost components.
+fn bfs_free_movement(start: HexPosition, config: &HexGridConfig, valid_moves: &mut ValidMoveSet) {
+    let mut queue: VecDeque<HexPosition> = VecDeque::new();
+    queue.push_back(start);
+
+    let mut visited = HashSet::new();
+    visited.insert(start);
+
+    while let Some(current) = queue.pop_front() {
+        for neighbor in config.neighbors(current) {
+            if visited.contains(&neighbor) {
+                continue;
+            }
+            visited.insert(neighbor);
+            valid_moves.valid_positions.insert(neighbor);
+            valid_moves.paths.insert(
+                neighbor,
+                PathStep {
+                    from: current,
+                    components: Vec::new(),
+                    total_cost: 0,
+                },
+            );
+            queue.push_back(neighbor);
+        }
+    }
+}
+
+/// Board state read while projecting influence.
+struct InfluenceContext<'a> {
+    rules: &'a InfluenceRuleRegistry,
+    entity_types: &'a EntityTypeRegistry,
+    config: &'a HexGridConfig,
+    edges: &'a HexEdgeRegistry,
+    tiles: &'a HashMap<HexPosition, &'a EntityData>,
+}
+
+/// Computes the influence map from all placed units, vertex features and the
+/// influence rule registry.
+///
+/// For each unit on the board, checks if its entity type has an influence rule.
+/// If so, projects influence into hexes within the rule's range, excluding
+/// the unit's own hex. Vertex features whose type has a rule radiate the same
+/// way, with the three hexes meeting at the vertex counting as range 1.
+/// Influence does not cross the rule's blocking edges or enter its excluded
+/// terrain, and is cancelled in hexes holding one of its negating unit types.
+fn compute_influence_map(
+    ctx: &InfluenceContext<'_>,
+    units: &[BoardUnit<'_>],
+    vertices: &HexVertexRegistry,
+    influence_map: &mut InfluenceMap,
+) {
+    influence_map.clear();
+
+    if ctx.rules.rules.is_empty() {
+        return;
+    }
+
+    let mut unit_types_at: HashMap<HexPosition, Vec<TypeId>> = HashMap::new();
+    for unit in units {
+        unit_types_at
+            .entry(unit.pos)
+            .or_default()
+            .push(unit.data.entity_type_id);
+    }
+    let negated = |rule: &InfluenceRule, pos: HexPosition| {
+        unit_types_at
+            .get(&pos)
+            .is_some_and(|types| types.iter().any(|t| rule.zone.negated_by.contains(t)))
+    };
+
+    for unit in units {
+        for rule in ctx
+            .rules
+            .rules
+            .iter()
+            .filter(|r| r.entity_type_id == unit.data.entity_type_id)
+        {
+            for pos in project_influence(ctx, rule, &[unit.pos], rule.range) {
+                if pos == unit.pos || negated(rule, pos) {
+                    continue;
+                }
+                influence_map
+                    .influenced
+                    .entry(pos)
+                    .or_default()
+                    .push(InfluenceEntry {
+                        source_pos: unit.pos,
+                        rule_id: rule.id,
+                        cost_modifier: rule.cost_modifier,
+                        source_vertex: None,
+                        source_owner: unit.owner,
+                    });
+            }
+        }
+    }
+
+    for (vertex, feature) in vertices.iter() {
+        let Some(type_id) = ctx
+            .entity_types
+            .types
+            .iter()
+            .find(|t| t.name == feature.type_name)
+            .map(|t| t.id)
+        else {
+            continue;
+        };
+        for rule in ctx
+            .rules
+            .rules
+            .iter()
+            .filter(|r| r.entity_type_id == type_id && r.range > 0)
+        {
+            let seeds: Vec<HexPosition> = vertex
+                .hexes()
+                .into_iter()
+                .filter_map(|hex| ctx.config.resolve(hex))
+                .filter(|hex| !excluded_terrain(ctx, rule, *hex))
+                .collect();
+            for pos in project_influence(ctx, rule, &seeds, rule.range - 1) {
+                if negated(rule, pos) {
+                    continue;
+                }
+                influence_map
+                    .influenced
+                    .entry(pos)
+                    .or_default()
+                    .push(InfluenceEntry {
+                        source_pos: vertex.origin,
+                        rule_id: rule.id,
+                        cost_modifier: rule.cost_modifier,
+                        source_vertex: Some(*vertex),
+                        source_owner: UnitOwner::default(),
+                    });
+            }
+        }
+    }
+}
+
+/// Hexes reached by spreading `steps` steps out from `seeds` (seeds
+/// included) without crossing the rule's blocking edges or entering its
+/// excluded terrain. On open ground this is the plain hex range.
+fn project_influence(
+    ctx: &InfluenceContext<'_>,
+    rule: &InfluenceRule,
+    seeds: &[HexPosition],
+    steps: u32,
+) -> Vec<HexPosition> {
+    let mut reached: Vec<HexPosition> = seeds.to_vec();
+    let mut seen: HashSet<HexPosition> = seeds.iter().copied().collect();
+    let mut frontier = seeds.to_vec();
+    for _ in 0..steps {
+        let mut next = Vec::new();
+        for pos in frontier {
+            for neighbor in ctx.config.neighbors(pos) {
+                if seen.contains(&neighbor)
+                    || excluded_terrain(ctx, rule, neighbor)
+                    || crosses_blocking_edge(ctx, rule, pos, neighbor)
+                {
+                    continue;
+                }
+                seen.insert(neighbor);
+                reached.push(neighbor);
+                next.push(neighbor);
+            }
+        }
+        frontier = next;
+    }
+    reached
+}
+
+/// Whether the tile at `pos` is terrain the rule's influence does not enter.
+fn excluded_terrain(ctx: &InfluenceContext<'_>, rule: &InfluenceRule, pos: HexPosition) -> bool {
+    ctx.tiles
+        .get(&pos)
+        .is_some_and(|tile| rule.zone.excluded_terrain.contains(&tile.entity_type_id))
+}
+
+/// Whether the edge between two adjacent hexes carries a feature that stops
+/// the rule's influence.
+fn crosses_blocking_edge(
+    ctx: &InfluenceContext<'_>,
+    rule: &InfluenceRule,
+    from: HexPosition,
+    to: HexPosition,
+) -> bool {
+    !rule.zone.blocking_edge_types.is_empty()
+        && edge_feature_type(ctx.config, ctx.edges, ctx.entity_types, from, to)
+            .is_some_and(|type_id| rule.zone.blocking_edge_types.contains(&type_id))
+}
+
+/// Entity type of the feature on the edge between two adjacent hexes.
+fn edge_feature_type(
+    config: &HexGridConfig,
+    edges: &HexEdgeRegistry,
+    entity_types: &EntityTypeRegistry,
+    from: HexPosition,
+    to: HexPosition,
+) -> Option<TypeId> {
+    let feature = config
+        .normalize_edge(from, to)
+        .and_then(|edge| edges.get(&edge))?;
+    entity_types
+        .types
+        .iter()
+        .find(|t| t.name == feature.type_name)
+        .map(|t| t.id)
+}
+
+/// The unit's value of the movement cost matrix's classification property.
+fn unit_classification(matrix: &MovementCostMatrix, unit_data: &EntityData) -> Option<String> {
+    matrix
+        .classification_property_id
+        .and_then(|prop_id| unit_data.properties.get(&prop_id))
+        .and_then(|v| match v {
+            PropertyValue::Enum(s) => Some(s.clone()),
+            _ => None,
+        })
+}
+
+/// Determines the initial movement budget for a unit based on its concept
+/// bindings and the ontology relations.
+///
+/// Strategy:
+/// 1. Look for `ModifyProperty` with `Subtract` operation to identify the
+///    budget property via `target_property`.
+/// 2. Find the matching property value on the unit's `EntityData`.
+/// 3. Fall back to a named "budget" property.
+/// 4. Fall back to a generous default if no budget property is found.
+fn determine_budget(
+    unit_bindings: &[&ConceptBinding],
+    on_enter_relations: &[&hexorder_contracts::ontology::Relation],
+    unit_data: &EntityData,
+    concepts: &ConceptRegistry,
+) -> i64 {
+    // Strategy 1: Find ModifyProperty Subtract relations and extract the
+    // target_property (which is the budget concept-local name on the subject).
+    for relation in on_enter_relations {
+        if let RelationEffect::ModifyProperty {
+            target_property,
+            operation: ModifyOperation::Subtract,
+            ..
+        } = &relation.effect
+        {
+            for binding in unit_bindings {
+                if binding.concept_id != relation.concept_id
+                    || binding.concept_role_id != relation.subject_role_id
+                {
+                    continue;
+                }
+                for prop_binding in &binding.property_bindings {
+                    if prop_binding.concept_local_name != *target_property {
+                        continue;
+                    }
+                    if let Some(value) = unit_data.properties.get(&prop_binding.property_id)
+                        && let Some(budget) = property_value_as_i64(value)
+                    {
+                        return budget;
+                    }
+                }
+            }
+        }
+    }
+
+    // Strategy 2: Look through all bindings for a property with concept-local
+    // name "budget" as a fallback heuristic.
+    for binding in unit_bindings {
+        for prop_binding in &binding.property_bindings {
+            if prop_binding.concept_local_name == "budget"
+                && let Some(value) = unit_data.properties.get(&prop_binding.property_id)
+                && let Some(budget) = property_value_as_i64(value)
+            {
+                return budget;
+            }
+        }
+    }
+
+    // Fallback: no budget found, allow generous default.
+    i64::from(concepts.concepts.len().max(1) as u32) * 10
+}
+
+/// Evaluates a single step of the BFS: checks whether the unit can enter
+/// `target_pos` given the tile at that position and the applicable relations.
+/// Also checks edge annotations on the boundary between `from_pos` and `target_pos`.
+fn evaluate_step(
+    ctx: &StepContext<'_>,
+    from_tile: Option<&EntityData>,
+    tile_data: Option<&EntityData>,
+    remaining_budget: i64,
+    from_pos: HexPosition,
+    target_pos: HexPosition,
+) -> StepResult {
+    let mut blocked_reasons: Vec<ValidationResult> = Vec::new();
+    let mut components: Vec<CostComponent> = Vec::new();
+    let mut cost: i64 = 0;
+    let mut has_block = false;
+
+    // Check edge annotations on the boundary being crossed, keyed the same
+    // from either side of a wrapping board's seam.
+    let edge_feature = ctx
+        .grid_config
+        .normalize_edge(from_pos, target_pos)
+        .and_then(|edge| ctx.edge_registry.get(&edge));
+    // The crossed edge's feature, as entity data with its type's defaults,
+    // so block conditions can refer to it through a concept role.
+    let edge_data = edge_feature.and_then(|feature| edge_entity_data(feature, ctx.entity_types));
+    if let Some(feature) = edge_feature {
+        let edge_cost = resolve_edge_cost(feature, ctx.entity_types);
+        cost += edge_cost;
+        push_component(
+            &mut components,
+            CostSource::Edge,
+            &feature.type_name,
+            edge_cost,
+        );
+        if remaining_budget - cost < 0 {
+            let unit_type_name = ctx
+                .entity_types
+                .get(ctx.unit_data.entity_type_id)
+                .map_or("Unit", |et| et.name.as_str());
+            blocked_reasons.push(ValidationResult {
+                constraint_id: TypeId(uuid::Uuid::nil()),
+                constraint_name: format!("{} crossing", feature.type_name),
+                satisfied: false,
+                explanation: format!(
+                    "{unit_type_name} cannot reach ({}, {}): edge crossing cost {cost} exceeds budget of {remaining_budget}",
+                    target_pos.q,
+                    target_pos.r,
+                ),
+            });
+        }
+    }
+
+    // Check vertex features at the corners of the target hex. A feature whose
+    // type defines a "cost" property charges it for entering any of the
+    // three hexes meeting at the vertex.
+    for vertex in ctx.grid_config.vertices_of(target_pos) {
+        let Some(feature) = ctx.vertex_registry.get(&vertex) else {
+            continue;
+        };
+        let vertex_cost = resolve_vertex_cost(feature, ctx.entity_types);
+        if vertex_cost == 0 {
+            continue;
+        }
+        cost += vertex_cost;
+        push_component(
+            &mut components,
+            CostSource::Vertex,
+            &feature.type_name,
+            vertex_cost,
+        );
+        if remaining_budget - cost < 0 {
+            let unit_type_name = ctx
+                .entity_types
+                .get(ctx.unit_data.entity_type_id)
+                .map_or("Unit", |et| et.name.as_str());
+            blocked_reasons.push(ValidationResult {
+                constraint_id: TypeId(uuid::Uuid::nil()),
+                constraint_name: format!("{} vertex", feature.type_name),
+                satisfied: false,
+                explanation: format!(
+                    "{unit_type_name} cannot reach ({}, {}): {} at a corner adds {vertex_cost}, path cost {cost} exceeds budget of {remaining_budget}",
+                    target_pos.q, target_pos.r, feature.type_name,
+                ),
+            });
+        }
+    }
+
+    // Check spatial influence on the target hex, skipping entries that do
+    // not affect the moving unit.
+    let applies = |e: &&InfluenceEntry| ctx.influence_applies(e, target_pos);
+    if let Some(entries) = ctx.influence_map.get(target_pos) {
+        for entry in entries.iter().filter(applies) {
+            cost += entry.cost_modifier;
+            let source_name = ctx
+                .influence_rules
+                .get(entry.rule_id)
+                .and_then(|rule| ctx.entity_types.get(rule.entity_type_id))
+                .map_or("Unknown", |et| et.name.as_str());
+            push_component(
+                &mut components,
+                CostSource::Influence,
+                &format!("{source_name} influence"),
+                entry.cost_modifier,
+            );
+        }
+        if remaining_budget - cost < 0 && !entries.is_empty() {
+            let total_influence: i64 = entries
+                .iter()
+                .filter(applies)
+                .map(|e| e.cost_modifier)
+                .sum();
+            if total_influence > 0 {
+                blocked_reasons.push(ValidationResult {
+                    constraint_id: TypeId(uuid::Uuid::nil()),
+                    constraint_name: "Spatial influence".to_string(),
+                    satisfied: false,
+                    explanation: format!(
+                        "Cannot reach ({}, {}): influence cost +{total_influence} exceeds remaining budget of {remaining_budget}",
+                        target_pos.q, target_pos.r,
+                    ),
+                });
+            }
+        }
+    }
+
+    // Area markers covering the target hex add their extra cost.
+    for marker in &ctx.area_markers.markers {
+        if ctx.grid_config.distance(marker.center, target_pos) > marker.radius {
+            continue;
+        }
+        let marker_cost: i64 = marker
+            .effects
+            .iter()
+            .map(|effect| match effect {
+                AreaEffect::CostModifier { extra_cost } => *extra_cost,
+                _ => 0,
+            })
+            .sum();
+        if marker_cost == 0 {
+            continue;
+        }
+        cost += marker_cost;
+        push_component(
+            &mut components,
+            CostSource::AreaMarker,
+            &marker.marker_type,
+            marker_cost,
+        );
+        if remaining_budget - cost < 0 {
+            blocked_reasons.push(ValidationResult {
+                constraint_id: TypeId(uuid::Uuid::nil()),
+                constraint_name: format!("{} area marker", marker.marker_type),
+                satisfied: false,
+                explanation: format!(
+                    "Cannot reach ({}, {}): {} adds {marker_cost}, path cost {cost} exceeds budget of {remaining_budget}",
+                    target_pos.q, target_pos.r, marker.marker_type,
+                ),
+            });
+        }
+    }
+
+    // Over-stacking the target hex only forbids ending the move there.
+    let mut overstack = None;
+    if ctx.stacking_rule.is_active() {
+        let adding = ctx.stacking_rule.points(ctx.unit_data);
+        let present = ctx.stack_points.get(&target_pos).copied().unwrap_or(0);
+        let limit = ctx
+            .stacking_rule
+            .limit(tile_data.map(|t| t.entity_type_id), ctx.unit_owner);
+        if let Some(limit) = limit
+            && StackingRule::would_exceed(adding, present, Some(limit))
+        {
+            overstack = Some(ValidationResult {
+                constraint_id: TypeId(uuid::Uuid::nil()),
+                constraint_name: "Stacking limit".to_string(),
+                satisfied: false,
+                explanation: format!(
+                    "Hex ({}, {}) holds {present}/{limit} stacking points; the unit adds {adding}",
+                    target_pos.q, target_pos.r,
+                ),
+            });
+        }
+    }
+
+    // Opposed factions may not share a hex.
+    if let Some(present) = ctx.unit_owners.get(&target_pos)
+        && ctx
+            .stacking_rule
+            .would_mix_factions(ctx.unit_owner, present.iter().copied())
+    {
+        has_block = true;
+        blocked_reasons.push(ValidationResult {
+            constraint_id: TypeId(uuid::Uuid::nil()),
+            constraint_name: "Mixed stack".to_string(),
+            satisfied: false,
+            explanation: format!(
+                "Hex ({}, {}) holds units of an opposing faction",
+                target_pos.q, target_pos.r,
+            ),
+        });
+    }
+
+    let mut state = StepState {
+        target_pos,
+        remaining_budget,
+        cost,
+        has_block,
+        blocked_reasons,
+        components,
+    };
+
+    // Leaving the current hex, then entering the target.
+    for (trigger, object, object_pos) in [
+        (RelationTrigger::OnExit, from_tile, from_pos),
+        (RelationTrigger::OnEnter, tile_data, target_pos),
+    ] {
+        let object_state = ctx.tile_states.get(&object_pos).copied();
+        apply_relations(
+            ctx,
+            trigger,
+            object,
+            object_state,
+            edge_data.as_ref(),
+            &mut state,
+        );
+    }
+
+    // Zone of control last, so its costs are reported against the full step.
+    apply_zone_of_control(ctx, from_pos, &mut state);
+    let StepState {
+        cost,
+        has_block,
+        blocked_reasons,
+        components,
+        ..
+    } = state;
+
+    if has_block {
+        return StepResult::Blocked {
+            reasons: blocked_reasons,
+        };
+    }
+
+    let new_budget = remaining_budget - cost;
+    if new_budget < 0 {
+        StepResult::Blocked {
+            reasons: blocked_reasons,
+        }
+    } else {
+        StepResult::Valid {
+            new_budget,
+            components,
+            overstack,
+        }
+    }
+}
+
+/// Records a non-zero cost component of a step.
+fn push_component(
+    components: &mut Vec<CostComponent>,
+    source: CostSource,
+    label: &str,
+    amount: i64,
+) {
+    if amount != 0 {
+        components.push(CostComponent {
+            source,
+            label: label.to_string(),
+            amount,
+        });
+    }
+}
+
+/// Applies the zone-of-control effects of influence on the hex being left
+/// and the hex entered: must-stop, exit cost and zone-to-zone moves. Each
+/// rule counts once however many of its sources overlap.
+fn apply_zone_of_control(ctx: &StepContext<'_>, from_pos: HexPosition, state: &mut StepState) {
+    let rules_at = |pos: HexPosition| {
+        let mut rules: Vec<&InfluenceRule> = Vec::new();
+        let entries = ctx.influence_map.get(pos).into_iter().flatten();
+        for entry in entries.filter(|e| ctx.influence_applies(e, pos)) {
+            if let Some(rule) = ctx.influence_rules.get(entry.rule_id)
+                && !rules.iter().any(|r| r.id == rule.id)
+            {
+                rules.push(rule);
+            }
+        }
+        rules
+    };
+    let from_rules = rules_at(from_pos);
+    if from_rules.is_empty() {
+        return;
+    }
+    let target_rules = rules_at(state.target_pos);
+    let unit_type_name = ctx
+        .entity_types
+        .get(ctx.unit_data.entity_type_id)
+        .map_or("Unit", |et| et.name.as_str());
+    let (q, r) = (state.target_pos.q, state.target_pos.r);
+
+    for rule in from_rules {
+        let source_name = ctx
+            .entity_types
+            .get(rule.entity_type_id)
+            .map_or("Unknown", |et| et.name.as_str());
+        let reason = |explanation: String| ValidationResult {
+            constraint_id: rule.id,
+            constraint_name: format!("{source_name} zone of control"),
+            satisfied: false,
+            explanation,
+        };
+
+        if rule.zone.stop_on_enter && from_pos != ctx.unit_pos {
+            state.has_block = true;
+            state.blocked_reasons.push(reason(format!(
+                "{unit_type_name} cannot reach ({q}, {r}): must stop at ({}, {}) on entering {source_name} zone of control",
+                from_pos.q, from_pos.r,
+            )));
+            continue;
+        }
+
+        if rule.zone.exit_cost != 0 {
+            state.cost += rule.zone.exit_cost;
+            push_component(
+                &mut state.components,
+                CostSource::ZoneOfControl,
+                &format!("Leaving {source_name} zone of control"),
+                rule.zone.exit_cost,
+            );
+            if state.remaining_budget - state.cost < 0 {
+                state.blocked_reasons.push(reason(format!(
+                    "{unit_type_name} cannot reach ({q}, {r}): leaving {source_name} zone of control costs {}, path cost {} exceeds budget of {}",
+                    rule.zone.exit_cost, state.cost, state.remaining_budget,
+                )));
+            }
+        }
+
+        if !target_rules.iter().any(|t| t.id == rule.id) {
+            continue;
+        }
+        match rule.zone.transition {
+            ZoneTransition::Allowed => {}
+            ZoneTransition::Forbidden => {
+                state.has_block = true;
+                state.blocked_reasons.push(reason(format!(
+                    "{unit_type_name} cannot move from ({}, {}) to ({q}, {r}): both hexes are in {source_name} zone of control",
+                    from_pos.q, from_pos.r,
+                )));
+            }
+            ZoneTransition::ExtraCost(extra) => {
+                state.cost += extra;
+                push_component(
+                    &mut state.components,
+                    CostSource::ZoneOfControl,
+                    &format!("Within {source_name} zone of control"),
+                    extra,
+                );
+                if state.remaining_budget - state.cost < 0 {
+                    state.blocked_reasons.push(reason(format!(
+                        "{unit_type_name} cannot reach ({q}, {r}): moving within {source_name} zone of control adds {extra}, path cost {} exceeds budget of {}",
+                        state.cost, state.remaining_budget,
+                    )));
+                }
+            }
+        }
+    }
+}
+
+/// One BFS step being evaluated and its accumulated outcome.
+struct StepState {
+    target_pos: HexPosition,
+    remaining_budget: i64,
+    cost: i64,
+    has_block: bool,
+    blocked_reasons: Vec<ValidationResult>,
+    components: Vec<CostComponent>,
+}
+
+/// Applies the relations with `trigger` to a step. `OnEnter` relations take
+/// the entered tile as their object and `OnExit` relations the tile being
+/// left; the movement cost matrix only replaces `OnEnter` terrain costs.
+fn apply_relations(
+    ctx: &StepContext<'_>,
+    trigger: RelationTrigger,
+    tile_data: Option<&EntityData>,
+    tile_state: Option<TypeId>,
+    edge_data: Option<&EntityData>,
+    state: &mut StepState,
+) {
+    let StepState {
+        target_pos,
+        remaining_budget,
+        ..
+    } = *state;
+    let relations = match trigger {
+        RelationTrigger::OnExit => ctx.on_exit_relations,
+        _ => ctx.on_enter_relations,
+    };
+    for relation in relations {
+        // Find unit bindings matching the subject role of this relation.
+        let unit_matches_subject = ctx.unit_bindings.iter().any(|b| {
+            b.concept_id == relation.concept_id && b.concept_role_id == relation.subject_role_id
+        });
+        if !unit_matches_subject {
+            continue;
+        }
+
+        // Find tile bindings matching the object role of this relation.
+        let tile_matches_object = tile_data.is_some_and(|td| {
+            ctx.concepts.bindings.iter().any(|b| {
+                b.entity_type_id == td.entity_type_id
+                    && b.concept_id == relation.concept_id
+                    && b.concept_role_id == relation.object_role_id
+            })
+        });
+        if !tile_matches_object {
+            continue;
+        }
+
+        match &relation.effect {
+            RelationEffect::ModifyProperty {
+                target_property,
+                source_property,
+                operation,
+            } => {
+                let source_value = tile_data.and_then(|td| {
+                    resolve_concept_property(
+                        td,
+                        source_property,
+                        relation.concept_id,
+                        relation.object_role_id,
+                        &ctx.concepts.bindings,
+                    )
+                });
+
+                // Check the movement cost matrix first: if active and the tile
+                // has an entity type, use the matrix cost for this (terrain, classification)
+                // pair. Fall back to the standard source property cost.
+                let (source_val, source) = if trigger == RelationTrigger::OnEnter
+                    && *operation == ModifyOperation::Subtract
+                    && ctx.movement_cost_matrix.is_active()
+                    && let Some(classification) = ctx.unit_classification
+                    && let Some(td) = tile_data
+                    && let Some(matrix_cost) = ctx
+                        .movement_cost_matrix
+                        .get_cost(td.entity_type_id, classification)
+                {
+                    (matrix_cost, CostSource::ClassificationMatrix)
+                } else {
+                    let value = source_value
+                        .and_then(|v| property_value_as_i64(&v))
+                        .unwrap_or(0);
+                    let source = if trigger == RelationTrigger::OnExit {
+                        CostSource::Exit
+                    } else {
+                        CostSource::Terrain
+                    };
+                    (value, source)
+                };
+
+                let step_cost = match operation {
+                    ModifyOperation::Subtract => source_val,
+                    ModifyOperation::Add => -source_val,
+                    _ => 0,
+                };
+                state.cost += step_cost;
+                push_component(&mut state.components, source, &relation.name, step_cost);
+
+                let cost = state.cost;
+                if remaining_budget - cost < 0 {
+                    let unit_type_name = ctx
+                        .entity_types
+                        .get(ctx.unit_data.entity_type_id)
+                        .map_or("Unit", |et| et.name.as_str());
+                    state.blocked_reasons.push(ValidationResult {
+                        constraint_id: relation.id,
+                        constraint_name: relation.name.clone(),
+                        satisfied: false,
+                        explanation: format!(
+                            "{unit_type_name} cannot reach ({}, {}): path cost {cost} exceeds {target_property} of {remaining_budget}",
+                            target_pos.q,
+                            target_pos.r,
+                        ),
+                    });
+                }
+            }
+            RelationEffect::Block { condition } => {
+                let outcome = condition.as_ref().map(|expr| {
+                    let scope = ConditionScope {
+                        concept_id: relation.concept_id,
+                        relation: Some(relation),
+                        concepts: ctx.concepts,
+                        entity_types: ctx.entity_types,
+                        state_machines: ctx.state_machines,
+                        unit: Some(ctx.unit_data),
+                        tile: tile_data,
+                        edge: edge_data,
+                        unit_state: ctx.unit_state,
+                        tile_state,
+                        reachability: ctx.reachability,
+                        unit_reach: ctx.unit_reach,
+                        spent: ctx.initial_budget - remaining_budget,
+                        unit_pos: Some(ctx.unit_pos),
+                        tile_pos: Some(target_pos),
+                        unit_owner: Some(ctx.unit_owner),
+                        proximity: ctx.proximity,
+                        step: Some(ctx),
+                        fills: &[],
+                    };
+                    evaluate_block_condition(expr, &scope)
+                });
+                if outcome.as_ref().is_none_or(|o| o.holds) {
+                    state.has_block = true;
+                    let unit_type_name = ctx
+                        .entity_types
+                        .get(ctx.unit_data.entity_type_id)
+                        .map_or("Unit", |et| et.name.as_str());
+                    let tile_type_name = tile_data
+                        .and_then(|td| ctx.entity_types.get(td.entity_type_id))
+                        .map_or("Unknown", |et| et.name.as_str());
+                    let (verb, noun) = if trigger == RelationTrigger::OnExit {
+                        ("leave", "exit")
+                    } else {
+                        ("enter", "entry")
+                    };
+                    let detail = outcome
+                        .map(|o| format!(" ({})", o.detail))
+                        .unwrap_or_default();
+                    state.blocked_reasons.push(ValidationResult {
+                        constraint_id: relation.id,
+                        constraint_name: relation.name.clone(),
+                        satisfied: false,
+                        explanation: format!(
+                            "{unit_type_name} cannot {verb} {tile_type_name}: {} blocks {noun}{detail}",
+                            relation.name
+                        ),
+                    });
+                }
+            }
+            RelationEffect::Allow { .. } => {
+                // Allow effects are not blocking; they permit entry.
+                // In a default-deny model these would allowlist, but for
+                // 0.4.0 we treat absence of Block as implicit allow.
+            }
+        }
+    }
+}
+
+/// Resolves a concept-local property name to the actual `PropertyValue` on
+/// an entity, using the concept bindings to map from concept-local names
+/// to property IDs.
+pub(crate) fn resolve_concept_property(
+    entity_data: &EntityData,
+    concept_local_name: &str,
+    concept_id: TypeId,
+    concept_role_id: TypeId,
+    bindings: &[ConceptBinding],
+) -> Option<PropertyValue> {
+    for binding in bindings {
+        if binding.entity_type_id != entity_data.entity_type_id
+            || binding.concept_id != concept_id
+            || binding.concept_role_id != concept_role_id
+        {
+            continue;
+        }
+        for prop_binding in &binding.property_bindings {
+            if prop_binding.concept_local_name == concept_local_name {
+                return entity_data
+                    .properties
+                    .get(&prop_binding.property_id)
+                    .cloned();
+            }
+        }
+    }
+    None
+}
+
+/// Entities a condition is evaluated against: for a block condition, the
+/// moving unit (the relation's subject), the entered tile (its object) and
+/// the feature on the crossed edge, if any; for a state transition, the
+/// instance and the tile it stands on.
+pub(crate) struct ConditionScope<'a> {
+    /// Concept the expression's roles belong to.
+    pub(crate) concept_id: TypeId,
+    /// The relation whose block condition is evaluated, if any.
+    pub(crate) relation: Option<&'a Relation>,
+    pub(crate) concepts: &'a ConceptRegistry,
+    pub(crate) entity_types: &'a EntityTypeRegistry,
+    pub(crate) state_machines: &'a StateMachineRegistry,
+    pub(crate) unit: Option<&'a EntityData>,
+    pub(crate) tile: Option<&'a EntityData>,
+    pub(crate) edge: Option<&'a EntityData>,
+    /// State-machine states of the unit and tile.
+    pub(crate) unit_state: Option<TypeId>,
+    pub(crate) tile_state: Option<TypeId>,
+    pub(crate) reachability: &'a ReachabilityRuleRegistry,
+    /// The unit's latest reachability results.
+    pub(crate) unit_reach: Option<&'a ReachabilityStatus>,
+    /// Movement already spent on the path before this step.
+    pub(crate) spent: i64,
+    /// Positions of the unit and tile, owner of the unit, and the units
+    /// proximity conditions measure to.
+    pub(crate) unit_pos: Option<HexPosition>,
+    pub(crate) tile_pos: Option<HexPosition>,
+    pub(crate) unit_owner: Option<UnitOwner>,
+    pub(crate) proximity: Option<&'a ProximityBoard<'a>>,
+    /// The unit's step context, for path-cost proximity.
+    pub(crate) step: Option<&'a StepContext<'a>>,
+    /// Entities filling roles of `concept_id` directly, ahead of the
+    /// relation's roles and the concept bindings.
+    pub(crate) fills: &'a [RoleFill<'a>],
+}
+
+/// An entity filling a role of a condition scope's concept, such as a
+/// combat's attacker.
+#[derive(Clone, Copy)]
+pub(crate) struct RoleFill<'a> {
+    pub(crate) role_id: TypeId,
+    pub(crate) data: &'a EntityData,
+    pub(crate) state: Option<TypeId>,
+    pub(crate) pos: Option<HexPosition>,
+    pub(crate) reach: Option<&'a ReachabilityStatus>,
+}
+
+impl<'a> RoleFill<'a> {
+    /// `unit` filling `role_id`.
+    fn unit((role_id, unit): (TypeId, &BoardUnit<'a>)) -> Self {
+        Self {
+            role_id,
+            data: unit.data,
+            state: unit.state,
+            pos: Some(unit.pos),
+            reach: unit.reach,
+        }
+    }
+}
+
+impl ConditionScope<'_> {
+    /// The entity directly filling `role_id` of `concept_id`, if any.
+    fn fill(&self, concept_id: TypeId, role_id: TypeId) -> Option<&RoleFill<'_>> {
+        (concept_id == self.concept_id)
+            .then(|| self.fills.iter().find(|fill| fill.role_id == role_id))
+            .flatten()
+    }
+
+    /// The entity filling `role_id` of `concept_id`, with its state. Direct
+    /// fills come first; the relation's subject and object roles map to the
+    /// unit and tile; any other role is matched against the concept bindings
+    /// of the unit, tile and edge.
+    fn entity_for_role(
+        &self,
+        concept_id: TypeId,
+        role_id: TypeId,
+    ) -> Option<(&EntityData, Option<TypeId>)> {
+        if let Some(fill) = self.fill(concept_id, role_id) {
+            return Some((fill.data, fill.state));
+        }
+        let unit = self.unit.map(|data| (data, self.unit_state));
+        let tile = self.tile.map(|data| (data, self.tile_state));
+        if let Some(relation) = self.relation
+            && concept_id == relation.concept_id
+        {
+            if role_id == relation.subject_role_id {
+                return unit;
+            }
+            if role_id == relation.object_role_id {
+                return tile;
+            }
+        }
+        [unit, tile, self.edge.map(|data| (data, None))]
+            .into_iter()
+            .flatten()
+            .find(|(data, _)| {
+                self.concepts.bindings.iter().any(|b| {
+                    b.entity_type_id == data.entity_type_id
+                        && b.concept_id == concept_id
+                        && b.concept_role_id == role_id
+                })
+            })
+    }
+
+    /// Latest reachability results of the unit filling the role.
+    fn reach_for_role(&self, concept_id: TypeId, role_id: TypeId) -> Option<&ReachabilityStatus> {
+        if let Some(fill) = self.fill(concept_id, role_id) {
+            return fill.reach;
+        }
+        let is_unit = matches!(
+            (self.unit, self.entity_for_role(concept_id, role_id)),
+            (Some(unit), Some((data, _))) if std::ptr::eq(unit, data)
+        );
+        self.unit_reach.filter(|_| is_unit)
+    }
+
+    /// The entity filling the role with its board position, when it is the
+    /// unit or the tile.
+    fn placed_entity_for_role(
+        &self,
+        concept_id: TypeId,
+        role_id: TypeId,
+    ) -> Option<(&EntityData, HexPosition)> {
+        if let Some(fill) = self.fill(concept_id, role_id) {
+            return Some((fill.data, fill.pos?));
+        }
+        let (data, _) = self.entity_for_role(concept_id, role_id)?;
+        if self.unit.is_some_and(|unit| std::ptr::eq(unit, data)) {
+            return Some((data, self.unit_pos?));
+        }
+        if self.tile.is_some_and(|tile| std::ptr::eq(tile, data)) {
+            return Some((data, self.tile_pos?));
+        }
+        None
+    }
+
+    /// Whether a unit counts for a proximity check of `subject`. Faction
+    /// filters are relative to the scope's unit; without one only `Any`
+    /// matches.
+    fn proximity_matches(
+        &self,
+        filter: &ProximityFilter,
+        subject: &EntityData,
+        candidate: &EntityData,
+        owner: UnitOwner,
+    ) -> bool {
+        if std::ptr::eq(subject, candidate)
+            || filter
+                .entity_type_id
+                .is_some_and(|id| id != candidate.entity_type_id)
+        {
+            return false;
+        }
+        let faction_matches = match filter.faction {
+            ProximityFaction::Any => true,
+            ProximityFaction::Friendly => self.unit_owner.is_some_and(|o| !o.opposes(owner)),
+            ProximityFaction::Enemy => self.unit_owner.is_some_and(|o| o.opposes(owner)),
+        };
+        faction_matches
+            && filter.same_property.as_deref().is_none_or(|name| {
+                let subject_value = property_named(self.entity_types, subject, name);
+                subject_value.is_some()
+                    && subject_value == property_named(self.entity_types, candidate, name)
+            })
+    }
+
+    /// Resolves a concept-local property on whichever entity fills the role.
+    /// A directly filled role reads it through any binding of the entity's
+    /// type to the concept, as combat strengths are.
+    fn property(&self, concept_id: TypeId, role_id: TypeId, name: &str) -> Option<PropertyValue> {
+        if let Some(fill) = self.fill(concept_id, role_id) {
+            let property_id =
+                concept_property_id(self.concepts, concept_id, fill.data.entity_type_id, name)?;
+            return fill.data.properties.get(&property_id).cloned();
+        }
+        let (data, _) = self.entity_for_role(concept_id, role_id)?;
+        resolve_concept_property(data, name, concept_id, role_id, &self.concepts.bindings)
+    }
+
+    /// Display name of a concept role, for explanations.
+    fn role_name(&self, concept_id: TypeId, role_id: TypeId) -> &str {
+        self.concepts
+            .concepts
+            .iter()
+            .find(|c| c.id == concept_id)
+            .and_then(|c| c.role_labels.iter().find(|r| r.id == role_id))
+            .map_or("?", |r| r.name.as_str())
+    }
+
+    fn type_name(&self, entity_type_id: TypeId) -> &str {
+        self.entity_types
+            .get(entity_type_id)
+            .map_or("Unknown", |et| et.name.as_str())
+    }
+}
+
+/// Result of evaluating a constraint expression. `detail` describes the
+/// sub-expression that decided the outcome, with the values it compared.
+pub(crate) struct ConditionOutcome {
+    pub(crate) holds: bool,
+    pub(crate) detail: String,
+}
+
+/// Evaluates a condition expression against the scope's unit, tile and
+/// edge. Every `ConstraintExpr` variant is supported; comparisons against a
+/// property that cannot be resolved do not hold.
+pub(crate) fn evaluate_block_condition(
+    expr: &ConstraintExpr,
+    scope: &ConditionScope<'_>,
+) -> ConditionOutcome {
+    if let Some(outcome) = evaluate_for_each_fill(expr, scope) {
+        return outcome;
+    }
+    let concept_id = scope.concept_id;
+    match expr {
+        ConstraintExpr::IsType {
+            role_id,
+            entity_type_id,
+        }
+        | ConstraintExpr::IsNotType {
+            role_id,
+            entity_type_id,
+        } => {
+            let negate = matches!(expr, ConstraintExpr::IsNotType { .. });
+            let role = scope.role_name(concept_id, *role_id);
+            let expected = scope.type_name(*entity_type_id);
+            let Some((data, _)) = scope.entity_for_role(concept_id, *role_id) else {
+                return ConditionOutcome {
+                    holds: false,
+                    detail: format!("{role} is not present"),
+                };
+            };
+            let is_type = data.entity_type_id == *entity_type_id;
+            let actual = scope.type_name(data.entity_type_id);
+            ConditionOutcome {
+                holds: is_type != negate,
+                detail: if is_type {
+                    format!("{role} is {expected}")
+                } else {
+                    format!("{role} is {actual}, not {expected}")
+                },
+            }
+        }
+        ConstraintExpr::InState { role_id, state_id } => {
+            let role = scope.role_name(concept_id, *role_id);
+            let expected = scope.state_machines.state_name(Some(*state_id));
+            let Some((_, state)) = scope.entity_for_role(concept_id, *role_id) else {
+                return ConditionOutcome {
+                    holds: false,
+                    detail: format!("{role} is not present"),
+                };
+            };
+            let holds = state == Some(*state_id);
+            ConditionOutcome {
+                holds,
+                detail: if holds {
+                    format!("{role} is {expected}")
+                } else {
+                    let actual = scope.state_machines.state_name(state);
+                    format!("{role} is {actual}, not {expected}")
+                },
+            }
+        }
+        ConstraintExpr::InReach { role_id, rule_id } => {
+            let role = scope.role_name(concept_id, *role_id);
+            let rule_name = scope.reachability.rule_name(Some(*rule_id));
+            let in_reach = scope
+                .reach_for_role(concept_id, *role_id)
+                .and_then(|status| status.is_in_reach(*rule_id));
+            match in_reach {
+                Some(holds) => ConditionOutcome {
+                    holds,
+                    detail: if holds {
+                        format!("{role} is in reach of {rule_name}")
+                    } else {
+                        format!("{role} is out of reach of {rule_name}")
+                    },
+                },
+                None => ConditionOutcome {
+                    holds: false,
+                    detail: format!("{role} has not been traced by {rule_name}"),
+                },
+            }
+        }
+        ConstraintExpr::Proximity {
+            role_id,
+            filter,
+            measure,
+            max,
+            line_of_sight,
+        } => evaluate_proximity(
+            scope,
+            *role_id,
+            filter,
+            *measure,
+            *max,
+            line_of_sight.as_deref(),
+        ),
+        ConstraintExpr::PropertyCompare {
+            role_id,
+            property_name,
+            operator,
+            value,
+        } => {
+            let label = format!("{}.{property_name}", scope.role_name(concept_id, *role_id));
+            let actual = scope.property(concept_id, *role_id, property_name);
+            compare_outcome(
+                &label,
+                actual.as_ref(),
+                *operator,
+                &describe_value(value),
+                Some(value),
+            )
+        }
+        ConstraintExpr::CrossCompare {
+            left_role_id,
+            left_property,
+            operator,
+            right_role_id,
+            right_property,
+        } => {
+            let left_label = format!(
+                "{}.{left_property}",
+                scope.role_name(concept_id, *left_role_id)
+            );
+            let right_label = format!(
+                "{}.{right_property}",
+                scope.role_name(concept_id, *right_role_id)
+            );
+            let left = scope.property(concept_id, *left_role_id, left_property);
+            let right = scope.property(concept_id, *right_role_id, right_property);
+            let right_desc = right.as_ref().map_or_else(
+                || format!("{right_label} (unset)"),
+                |v| format!("{right_label} ({})", describe_value(v)),
+            );
+            compare_outcome(
+                &left_label,
+                left.as_ref(),
+                *operator,
+                &right_desc,
+                right.as_ref(),
+            )
+        }
+        ConstraintExpr::PathBudget {
+            concept_id: budget_concept_id,
+            cost_property,
+            cost_role_id,
+            budget_property,
+            budget_role_id,
+        } => {
+            let cost_label = format!(
+                "{}.{cost_property}",
+                scope.role_name(*budget_concept_id, *cost_role_id)
+            );
+            let budget_label = format!(
+                "{}.{budget_property}",
+                scope.role_name(*budget_concept_id, *budget_role_id)
+            );
+            let cost = scope
+                .property(*budget_concept_id, *cost_role_id, cost_property)
+                .and_then(|v| property_value_as_i64(&v));
+            let budget = scope
+                .property(*budget_concept_id, *budget_role_id, budget_property)
+                .and_then(|v| property_value_as_i64(&v));
+            match (cost, budget) {
+                (Some(cost), Some(budget)) => {
+                    let total = scope.spent + cost;
+                    ConditionOutcome {
+                        holds: total <= budget,
+                        detail: format!(
+                            "path cost {total} (spent {} + {cost_label} {cost}) <= {budget_label} ({budget})",
+                            scope.spent
+                        ),
+                    }
+                }
+                (None, _) => ConditionOutcome {
+                    holds: false,
+                    detail: format!("{cost_label} is unset"),
+                },
+                (_, None) => ConditionOutcome {
+                    holds: false,
+                    detail: format!("{budget_label} is unset"),
+                },
+            }
+        }
+        ConstraintExpr::All(exprs) => {
+            let mut details = Vec::with_capacity(exprs.len());
+            for e in exprs {
+                let outcome = evaluate_block_condition(e, scope);
+                if !outcome.holds {
+                    return outcome;
+                }
+                details.push(outcome.detail);
+            }
+            ConditionOutcome {
+                holds: true,
+                detail: details.join(" and "),
+            }
+        }
+        ConstraintExpr::Any(exprs) => {
+            let mut details = Vec::with_capacity(exprs.len());
+            for e in exprs {
+                let outcome = evaluate_block_condition(e, scope);
+                if outcome.holds {
+                    return outcome;
+                }
+                details.push(outcome.detail);
+            }
+            ConditionOutcome {
+                holds: false,
+                detail: details.join(" or "),
+            }
+        }
+        ConstraintExpr::Not(inner) => {
+            let outcome = evaluate_block_condition(inner, scope);
+            ConditionOutcome {
+                holds: !outcome.holds,
+                detail: format!("not ({})", outcome.detail),
+            }
+        }
+    }
+}
+
+/// Evaluates a leaf expression on a role several entities fill, such as a
+/// stack's attackers, once per entity: it holds when it holds for any of
+/// them. `None` when every role of the leaf has at most one fill.
+fn evaluate_for_each_fill(
+    expr: &ConstraintExpr,
+    scope: &ConditionScope<'_>,
+) -> Option<ConditionOutcome> {
+    let roles = match expr {
+        ConstraintExpr::IsType { role_id, .. }
+        | ConstraintExpr::IsNotType { role_id, .. }
+        | ConstraintExpr::InState { role_id, .. }
+        | ConstraintExpr::InReach { role_id, .. }
+        | ConstraintExpr::Proximity { role_id, .. }
+        | ConstraintExpr::PropertyCompare { role_id, .. } => vec![*role_id],
+        ConstraintExpr::CrossCompare {
+            left_role_id,
+            right_role_id,
+            ..
+        } => vec![*left_role_id, *right_role_id],
+        ConstraintExpr::PathBudget {
+            concept_id,
+            cost_role_id,
+            budget_role_id,
+            ..
+        } if *concept_id == scope.concept_id => vec![*cost_role_id, *budget_role_id],
+        _ => Vec::new(),
+    };
+    let role_id = roles.into_iter().find(|&role_id| {
+        scope
+            .fills
+            .iter()
+            .filter(|fill| fill.role_id == role_id)
+            .nth(1)
+            .is_some()
+    })?;
+    let mut details: Vec<String> = Vec::new();
+    for fill in scope.fills.iter().filter(|fill| fill.role_id == role_id) {
+        let fills: Vec<RoleFill<'_>> = scope
+            .fills
+            .iter()
+            .filter(|other| other.role_id != role_id || std::ptr::eq(*other, fill))
+            .copied()
+            .collect();
+        let outcome = evaluate_block_condition(
+            expr,
+            &ConditionScope {
+                fills: &fills,
+                ..*scope
+            },
+        );
+        if outcome.holds {
+            return Some(outcome);
+        }
+        if !details.contains(&outcome.detail) {
+            details.push(outcome.detail);
+        }
+    }
+    Some(ConditionOutcome {
+        holds: false,
+        detail: details.join(" or "),
+    })
+}
+
+/// Evaluates a `Proximity` expression: finds the nearest unit matching
+/// `filter` (and in sight, when `line_of_sight` lists blocking terrain) from
+/// the hex of the entity filling the role, and checks it is within `max`.
+/// Path costs are only measured with a step context, i.e. for a unit's move
+/// or eligibility, not for phase-start transitions.
+fn evaluate_proximity(
+    scope: &ConditionScope<'_>,
+    role_id: TypeId,
+    filter: &ProximityFilter,
+    measure: ProximityMeasure,
+    max: i64,
+    line_of_sight: Option<&[TypeId]>,
+) -> ConditionOutcome {
+    let role = scope.role_name(scope.concept_id, role_id);
+    let mut target = filter
+        .entity_type_id
+        .map_or("unit", |id| scope.type_name(id))
+        .to_string();
+    if line_of_sight.is_some() {
+        target.push_str(" in sight");
+    }
+    let not_holding = |detail: String| ConditionOutcome {
+        holds: false,
+        detail,
+    };
+    let Some(board) = scope.proximity else {
+        return not_holding(format!("no units to measure {role} to"));
+    };
+    let Some((subject, from)) = scope.placed_entity_for_role(scope.concept_id, role_id) else {
+        return not_holding(format!("{role} is not on the board"));
+    };
+    let path_costs = match (measure, scope.step) {
+        (ProximityMeasure::Distance, _) => None,
+        (ProximityMeasure::PathCost, Some(step)) => {
+            Some(path_costs_from(step, board.tiles, from, max))
+        }
+        (ProximityMeasure::PathCost, None) => {
+            return not_holding(format!("path cost from {role} is not measured here"));
+        }
+    };
+    let nearest = board
+        .units
+        .iter()
+        .filter(|(_, data, owner)| scope.proximity_matches(filter, subject, data, *owner))
+        .filter(|(to, ..)| line_of_sight.is_none_or(|blockers| board.in_sight(from, *to, blockers)))
+        .filter_map(|(to, ..)| match &path_costs {
+            Some(costs) => costs.get(to).copied(),
+            None => Some(i64::from(board.grid_config.distance(from, *to))),
+        })
+        .min();
+    let Some(nearest) = nearest else {
+        return not_holding(format!("no {target} within {max} of {role}"));
+    };
+    let amount = match measure {
+        ProximityMeasure::Distance => format!("{nearest} hexes"),
+        ProximityMeasure::PathCost => format!("path cost {nearest}"),
+    };
+    let holds = nearest <= max;
+    ConditionOutcome {
+        holds,
+        detail: if holds {
+            format!("{role} is {amount} from the nearest {target}")
+        } else {
+            format!("{role} is {amount} from the nearest {target}, over {max}")
+        },
+    }
+}
+
+/// Units and tiles a `Proximity` expression measures against.
+pub(crate) struct ProximityBoard<'a> {
+    pub(crate) grid_config: &'a HexGridConfig,
+    pub(crate) tiles: &'a HashMap<HexPosition, &'a EntityData>,
+    pub(crate) units: Vec<(HexPosition, &'a EntityData, UnitOwner)>,
+}
+
+impl<'a> ProximityBoard<'a> {
+    /// Measures against every unit on `board`.
+    fn of(grid_config: &'a HexGridConfig, board: &'a RulesBoard<'a>) -> Self {
+        Self {
+            grid_config,
+            tiles: &board.tiles,
+            units: board
+                .units
+                .iter()
+                .map(|unit| (unit.pos, unit.data, unit.owner))
+                .collect(),
+        }
+    }
+
+    /// Whether `to` is in sight from `from`: no hex strictly between them
+    /// on the hex line is a tile of a blocking terrain type.
+    fn in_sight(&self, from: HexPosition, to: HexPosition, blockers: &[TypeId]) -> bool {
+        let to = self.grid_config.nearest_image(from, to);
+        let line: Vec<_> = from.to_hex().line_to(to.to_hex()).collect();
+        line.iter()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+            .all(|hex| {
+                let pos = self.grid_config.normalize(HexPosition::from_hex(*hex));
+                self.tiles
+                    .get(&pos)
+                    .is_none_or(|tile| !blockers.contains(&tile.entity_type_id))
+            })
+    }
+}
+
+/// Cheapest movement cost from `from` to each hex within `max`, paying what
+/// the context's unit pays per step. Only movement costs count: influence,
+/// stacking and proximity are left out.
+fn path_costs_from(
+    step: &StepContext<'_>,
+    tiles: &HashMap<HexPosition, &EntityData>,
+    from: HexPosition,
+    max: i64,
+) -> HashMap<HexPosition, i64> {
+    let no_influence = InfluenceMap::default();
+    let no_stacking = StackingRule::default();
+    let no_units = HashMap::new();
+    let no_owners = HashMap::new();
+    let ctx = StepContext {
+        influence_map: &no_influence,
+        stacking_rule: &no_stacking,
+        stack_points: &no_units,
+        unit_owners: &no_owners,
+        initial_budget: max,
+        proximity: None,
+        ..*step
+    };
+    let mut queue: VecDeque<(HexPosition, i64)> = VecDeque::from([(from, max)]);
+    let mut best_budget: HashMap<HexPosition, i64> = HashMap::from([(from, max)]);
+    while let Some((current_pos, remaining_budget)) = queue.pop_front() {
+        for neighbor_pos in ctx.grid_config.neighbors(current_pos) {
+            let step_result = evaluate_step(
+                &ctx,
+                tiles.get(&current_pos).copied(),
+                tiles.get(&neighbor_pos).copied(),
+                remaining_budget,
+                current_pos,
+                neighbor_pos,
+            );
+            let StepResult::Valid { new_budget, .. } = step_result else {
+                continue;
+            };
+            let dominated = best_budget
+                .get(&neighbor_pos)
+                .is_some_and(|&prev| prev >= new_budget);
+            if dominated {
+                continue;
+            }
+            best_budget.insert(neighbor_pos, new_budget);
+            if new_budget > 0 {
+                queue.push_back((neighbor_pos, new_budget));
+            }
+        }
+    }
+    best_budget
+        .into_iter()
+        .map(|(pos, budget)| (pos, max - budget))
+        .collect()
+}
+
+/// The value of the property named `name` on an entity, looked up through
+/// its type's property definitions.
+fn property_named<'a>(
+    entity_types: &EntityTypeRegistry,
+    data: &'a EntityData,
+    name: &str,
+) -> Option<&'a PropertyValue> {
+    let property = entity_types
+        .get(data.entity_type_id)?
+        .properties
+        .iter()
+        .find(|p| p.name == name)?;
+    data.properties.get(&property.id)
+}
+
+/// Builds the outcome of comparing `actual` (described by `label`) against
+/// `expected` (described by `expected_desc`).
+fn compare_outcome(
+    label: &str,
+    actual: Option<&PropertyValue>,
+    operator: CompareOp,
+    expected_desc: &str,
+    expected: Option<&PropertyValue>,
+) -> ConditionOutcome {
+    let op = compare_op_symbol(operator);
+    let Some(actual) = actual else {
+        return ConditionOutcome {
+            holds: false,
+            detail: format!("{label} is unset"),
+        };
+    };
+    let actual_desc = describe_value(actual);
+    match expected.and_then(|expected| compare_values(actual, operator, expected)) {
+        Some(holds) => ConditionOutcome {
+            holds,
+            detail: format!("{label} ({actual_desc}) {op} {expected_desc}"),
+        },
+        None => ConditionOutcome {
+            holds: false,
+            detail: format!("{label} ({actual_desc}) cannot be compared {op} {expected_desc}"),
+        },
+    }
+}
+
+/// Compares two property values. Numbers (int, float and ranges) compare
+/// numerically, bools order `false < true`, and enums and strings support
+/// equality only. Returns `None` for values that cannot be compared.
+fn compare_values(
+    left: &PropertyValue,
+    operator: CompareOp,
+    right: &PropertyValue,
+) -> Option<bool> {
+    let ordering = match (left, right) {
+        (PropertyValue::Bool(a), PropertyValue::Bool(b)) => a.cmp(b),
+        (
+            PropertyValue::Enum(a) | PropertyValue::String(a),
+            PropertyValue::Enum(b) | PropertyValue::String(b),
+        ) => {
+            return match operator {
+                CompareOp::Eq => Some(a == b),
+                CompareOp::Ne => Some(a != b),
+                _ => None,
+            };
+        }
+        _ => property_value_as_f64(left)?.partial_cmp(&property_value_as_f64(right)?)?,
+    };
+    Some(match operator {
+        CompareOp::Eq => ordering.is_eq(),
+        CompareOp::Ne => ordering.is_ne(),
+        CompareOp::Lt => ordering.is_lt(),
+        CompareOp::Le => ordering.is_le(),
+        CompareOp::Gt => ordering.is_gt(),
+        CompareOp::Ge => ordering.is_ge(),
+    })
+}
+
+fn compare_op_symbol(operator: CompareOp) -> &'static str {
+    match operator {
+        CompareOp::Eq => "==",
+        CompareOp::Ne => "!=",
+        CompareOp::Lt => "<",
+        CompareOp::Le => "<=",
+        CompareOp::Gt => ">",
+        CompareOp::Ge => ">=",
+    }
+}
+
+/// Short human-readable form of a property value for explanations.
+fn describe_value(value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::Bool(v) => v.to_string(),
+        PropertyValue::Int(v) | PropertyValue::IntRange(v) => v.to_string(),
+        PropertyValue::Float(v) | PropertyValue::FloatRange(v) => v.to_string(),
+        PropertyValue::String(v) | PropertyValue::Enum(v) => v.clone(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Extracts an `f64` value from a numeric `PropertyValue`.
+pub(crate) fn property_value_as_f64(value: &PropertyValue) -> Option<f64> {
+    match value {
+        PropertyValue::Int(v) | PropertyValue::IntRange(v) => Some(*v as f64),
+        PropertyValue::Float(v) | PropertyValue::FloatRange(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Extracts an `i64` value from a `PropertyValue`, coercing numeric types.
+pub(crate) fn property_value_as_i64(value: &PropertyValue) -> Option<i64> {
+    match value {
+        PropertyValue::Int(v) => Some(*v),
+        PropertyValue::Float(v) => Some(*v as i64),
+        _ => None,
+    }
+}
+
+/// Entity data for an edge feature: its type with every property at its
+/// default value. `None` if the type name does not resolve.
+fn edge_entity_data(
+    feature: &hexorder_contracts::hex_grid::EdgeFeature,
+    entity_types: &EntityTypeRegistry,
+) -> Option<EntityData> {
+    let entity_type = entity_types
+        .types
+        .iter()
+        .find(|t| t.name == feature.type_name)?;
+    Some(EntityData {
+        entity_type_id: entity_type.id,
+        properties: entity_type
+            .properties
+            .iter()
+            .map(|p| (p.id, p.default_value.clone()))
+            .collect(),
+    })
+}
+
+/// Resolves the movement cost of crossing a hex edge with the given feature.
+///
+/// Looks up the feature's `type_name` in the entity type registry. If the
+/// entity type has a property named "cost", its default value is used as the
+/// crossing cost. Otherwise the edge adds +1 cost (non-zero so it always
+/// affects movement).
+fn resolve_edge_cost(
+    feature: &hexorder_contracts::hex_grid::EdgeFeature,
+    entity_types: &EntityTypeRegistry,
+) -> i64 {
+    feature_type_cost(&feature.type_name, entity_types).unwrap_or(1)
+}
+
+/// Resolves the movement cost of entering a hex that meets at a vertex with
+/// the given feature. Uses the entity type's "cost" property like edges, but
+/// a vertex without one costs nothing (towns and supply points are usually
+/// markers, not obstacles).
+fn resolve_vertex_cost(
+    feature: &hexorder_contracts::hex_grid::VertexFeature,
+    entity_types: &EntityTypeRegistry,
+) -> i64 {
+    feature_type_cost(&feature.type_name, entity_types).unwrap_or(0)
+}
+
+/// Default value of the "cost" property on the entity type named `type_name`.
+fn feature_type_cost(type_name: &str, entity_types: &EntityTypeRegistry) -> Option<i64> {
+    entity_types
+        .types
+        .iter()
+        .find(|t| t.name == type_name)?
+        .properties
+        .iter()
+        .find(|p| p.name == "cost")
+        .and_then(|p| property_value_as_i64(&p.default_value))
+}
+
+/// The `Proximity` expressions in `expr`, with their filter, measure, limit
+/// and line-of-sight blockers.
+fn collect_proximity<'a>(
+    expr: &'a ConstraintExpr,
+    found: &mut Vec<(
+        &'a ProximityFilter,
+        ProximityMeasure,
+        i64,
+        Option<&'a [TypeId]>,
+    )>,
+) {
+    match expr {
+        ConstraintExpr::Proximity {
+            filter,
+            measure,
+            max,
+            line_of_sight,
+            ..
+        } => found.push((filter, *measure, *max, line_of_sight.as_deref())),
+        ConstraintExpr::All(exprs) | ConstraintExpr::Any(exprs) => {
+            for e in exprs {
+                collect_proximity(e, found);
+            }
+        }
+        ConstraintExpr::Not(inner) => collect_proximity(inner, found),
+        _ => {}
+    }
+}
+
+/// The rule being traced and what blocks it for the tracing side.
+struct TraceContext<'a> {
+    rule: &'a ReachabilityRule,
+    owner: UnitOwner,
+    tiles: &'a HashMap<HexPosition, &'a EntityData>,
+    influence_map: &'a InfluenceMap,
+}
+
+impl TraceContext<'_> {
+    /// Whether a trace may not pass through `pos`: blocking terrain, or
+    /// influence projected by a unit opposed to the tracing side.
+    fn blocks(&self, pos: HexPosition) -> bool {
+        let blockers = &self.rule.blockers;
+        let terrain = self
+            .tiles
+            .get(&pos)
+            .is_some_and(|tile| blockers.terrain.contains(&tile.entity_type_id));
+        let enemy = blockers.enemy_influence
+            && self.influence_map.get(pos).is_some_and(|entries| {
+                entries
+                    .iter()
+                    .any(|e| e.source_vertex.is_none() && e.source_owner.opposes(self.owner))
+            });
+        terrain || enemy
+    }
+}
+
+/// Hexes a rule's traces may end in for the tracing side.
+fn trace_sources(
+    trace: &TraceContext<'_>,
+    config: &HexGridConfig,
+    tiles: &HashMap<HexPosition, &EntityData>,
+    units: &[BoardUnit<'_>],
+) -> HashSet<HexPosition> {
+    let mut sources = HashSet::new();
+    for source in &trace.rule.sources {
+        match source {
+            ReachabilitySource::Hexes(hexes) => {
+                sources.extend(hexes.iter().filter_map(|hex| config.resolve(*hex)));
+            }
+            ReachabilitySource::Terrain(type_id) => {
+                sources.extend(
+                    tiles
+                        .iter()
+                        .filter(|(_, tile)| tile.entity_type_id == *type_id)
+                        .map(|(pos, _)| *pos),
+                );
+            }
+            ReachabilitySource::UnitType(type_id) => {
+                sources.extend(
+                    units
+                        .iter()
+                        .filter(|unit| {
+                            unit.data.entity_type_id == *type_id && !unit.owner.opposes(trace.owner)
+                        })
+                        .map(|unit| unit.pos),
+                );
+            }
+        }
+    }
+    sources
+}
+
+/// Cheapest path cost from each hex in reach to the nearest source. Searches
+/// outward from the sources, charging each step what the traced unit pays
+/// to move from the outer hex toward the source, up to the rule's
+/// `max_cost`. Blocked hexes are reached but not passed through, so a unit
+/// standing in one can still trace out of it.
+fn trace_to_sources(
+    ctx: &StepContext<'_>,
+    trace: &TraceContext<'_>,
+    sources: &HashSet<HexPosition>,
+) -> HashMap<HexPosition, i64> {
+    let max_cost = trace.rule.max_cost;
+    let mut queue: VecDeque<(HexPosition, i64)> = VecDeque::new();
+    let mut best_budget: HashMap<HexPosition, i64> = HashMap::new();
+    for &source in sources {
+        best_budget.insert(source, max_cost);
+        if !trace.blocks(source) {
+            queue.push_back((source, max_cost));
+        }
+    }
+
+    while let Some((current_pos, remaining_budget)) = queue.pop_front() {
+        for neighbor_pos in ctx.grid_config.neighbors(current_pos) {
+            let crosses_blocker = edge_feature_type(
+                ctx.grid_config,
+                ctx.edge_registry,
+                ctx.entity_types,
+                neighbor_pos,
+                current_pos,
+            )
+            .is_some_and(|type_id| trace.rule.blockers.edge_types.contains(&type_id));
+            if crosses_blocker {
+                continue;
+            }
+            let step_result = evaluate_step(
+                ctx,
+                trace.tiles.get(&neighbor_pos).copied(),
+                trace.tiles.get(&current_pos).copied(),
+                remaining_budget,
+                neighbor_pos,
+                current_pos,
+            );
+            let StepResult::Valid { new_budget, .. } = step_result else {
+                continue;
+            };
+            let dominated = best_budget
+                .get(&neighbor_pos)
+                .is_some_and(|&prev| prev >= new_budget);
+            if dominated {
+                continue;
+            }
+            best_budget.insert(neighbor_pos, new_budget);
+            if new_budget > 0 && !trace.blocks(neighbor_pos) {
+                queue.push_back((neighbor_pos, new_budget));
+            }
+        }
+    }
+
+    best_budget
+        .into_iter()
+        .map(|(pos, budget)| (pos, max_cost - budget))
+        .collect()
+}
@@ -1,19 +1,45 @@
 //! Rules Engine plugin.
 //!
 //! Evaluates ontology constraints against board state. Computes valid
-//! moves for selected units via BFS with constraint evaluation.
+//! moves for selected units via BFS with constraint evaluation, caching
+//! them until the board or rules change and precomputing the active
+//! faction's in Play. Keeps `WhilePresent` relation effects applied to unit
+//! data, drives entity state machines, traces reachability rules (supply, command), keeps
+//! units' action eligibility and the selected unit's command radius,
+//! reports over-stacked hexes, moves units in Play with halts at movement
+//! interrupts, resolves overruns during movement phases, and in the editor
+//! runs static rule analysis over the ontology and mechanics.
+//!
+//! The evaluation itself lives in [`RulesContext`], which is free of ECS
+//! types: tools and tests can build one from a `GameSystemFile` and a
+//! [`BoardSnapshot`] and ask it for valid moves, path costs, influence,
+//! stacking, constraint checks, interrupted moves and overruns headlessly.
+//! The Bevy systems are thin adapters over it.
 
 use bevy::prelude::*;
 use hexorder_sdk::{HexorderPlugin, PluginId};
 
+use hexorder_contracts::game_system::StateMachineRegistry;
 use hexorder_contracts::hex_grid::{
-    InfluenceMap, InfluenceRuleRegistry, MovementCostMatrix, StackingRule,
+    CommandRadius, InfluenceMap, InfluenceRuleRegistry, MovementCostMatrix, ReachabilityMap,
+    ReachabilityOverlay, ReachabilityRuleRegistry, StackingRule, StackingViolations,
+};
+use hexorder_contracts::mechanics::{
+    ActiveCombat, ActiveInterrupt, AreaMarkerRegistry, CombatModifierRegistry, CombatResultsTable,
+    CombatTableRegistry, MovementInterruptRegistry, OffMapZoneRegistry, OverrunRegistry,
+    OverrunTargets, PlayLog, SpawnSchedule, TurnState, TurnStructure, VictoryConditionRegistry,
 };
 use hexorder_contracts::persistence::AppScreen;
-use hexorder_contracts::validation::ValidMoveSet;
+use hexorder_contracts::validation::{RuleAnalysis, ValidMoveSet};
 
+mod context;
 mod systems;
 
+pub use context::{
+    BoardSnapshot, BoardUnit, MoveReport, MovementInterrupt, OverrunReport, RulesBoard,
+    RulesContext, UnitTrace,
+};
+
 #[cfg(test)]
 mod tests;
 
@@ -33,14 +59,77 @@ impl HexorderPlugin for RulesEnginePlugin {
 
     fn build(&self, app: &mut App) {
         app.init_resource::<ValidMoveSet>();
+        app.init_resource::<systems::MoveCache>();
         app.init_resource::<InfluenceRuleRegistry>();
         app.init_resource::<InfluenceMap>();
         app.init_resource::<StackingRule>();
+        app.init_resource::<StackingViolations>();
         app.init_resource::<MovementCostMatrix>();
+        app.init_resource::<AreaMarkerRegistry>();
+        app.init_resource::<StateMachineRegistry>();
+        app.init_resource::<ReachabilityRuleRegistry>();
+        app.init_resource::<ReachabilityMap>();
+        app.init_resource::<ReachabilityOverlay>();
+        app.init_resource::<CommandRadius>();
+        app.init_resource::<RuleAnalysis>();
+        app.init_resource::<TurnStructure>();
+        app.init_resource::<CombatResultsTable>();
+        app.init_resource::<CombatTableRegistry>();
+        app.init_resource::<CombatModifierRegistry>();
+        app.init_resource::<SpawnSchedule>();
+        app.init_resource::<VictoryConditionRegistry>();
+        app.init_resource::<MovementInterruptRegistry>();
+        app.init_resource::<ActiveInterrupt>();
+        app.init_resource::<OverrunRegistry>();
+        app.init_resource::<OverrunTargets>();
+        app.init_resource::<OffMapZoneRegistry>();
+        app.init_resource::<PlayLog>();
+        app.init_resource::<ActiveCombat>();
+        app.init_resource::<TurnState>();
         app.add_systems(
             Update,
-            systems::compute_valid_moves.run_if(in_state(AppScreen::Editor)),
+            (
+                (
+                    (
+                        systems::reset_movement_spent,
+                        systems::trace_reachability_at_phase_start,
+                        systems::fire_phase_state_triggers,
+                    )
+                        .chain()
+                        .run_if(in_state(AppScreen::Play)),
+                    systems::sync_entity_states,
+                    systems::apply_state_overrides,
+                    systems::apply_presence_effects,
+                    systems::compute_action_eligibility,
+                    systems::compute_command_radius,
+                    systems::detect_stacking_violations,
+                )
+                    .chain()
+                    .run_if(in_state(AppScreen::Editor).or(in_state(AppScreen::Play))),
+                (
+                    systems::invalidate_move_cache,
+                    systems::precompute_active_moves.run_if(in_state(AppScreen::Play)),
+                    systems::compute_valid_moves,
+                    systems::compute_overrun_targets.run_if(in_state(AppScreen::Play)),
+                    systems::select_combat_table.run_if(in_state(AppScreen::Play)),
+                    systems::evaluate_combat_modifiers.run_if(in_state(AppScreen::Play)),
+                    systems::update_advance_options.run_if(in_state(AppScreen::Play)),
+                )
+                    .chain()
+                    .run_if(in_state(AppScreen::Editor).or(in_state(AppScreen::Play))),
+                systems::run_rule_analysis.run_if(in_state(AppScreen::Editor)),
+            )
+                .chain(),
         );
+        app.add_observer(systems::handle_state_trigger);
+        app.add_observer(systems::handle_set_entity_state);
+        app.add_observer(systems::handle_combat_resolved);
+        app.add_observer(systems::handle_combat_move);
+        app.add_observer(systems::handle_trace_reachability);
+        app.add_observer(systems::handle_move_request);
+        app.add_observer(systems::handle_resolve_interrupt);
+        app.add_observer(systems::handle_overrun_request);
+        app.add_systems(OnEnter(AppScreen::Play), systems::reset_play_session);
     }
 }
 
@@ -1,711 +1,2232 @@
-//! Rules engine systems: valid move computation via BFS with constraint evaluation.
+//! Rules engine systems: thin adapters that read resources and queries into
+//! a `RulesContext` and `RulesBoard`, and store what they answer.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
+use std::time::Duration;
 
+use bevy::ecs::system::SystemParam;
+use bevy::platform::time::Instant;
 use bevy::prelude::*;
 
+use hexorder_contracts::editor_ui::{ToastEvent, ToastKind};
 use hexorder_contracts::game_system::{
-    EntityData, EntityTypeRegistry, PropertyValue, SelectedUnit, TypeId, UnitInstance,
+    ActiveFaction, AppliedOverride, EntityData, EntityState, EntityTypeRegistry, PropertyValue,
+    SelectedUnit, SetEntityStateEvent, StateMachineRegistry, StateOverrides, StateTrigger,
+    StateTriggerEvent, TypeId, UnitId, UnitInstance, UnitOwner,
 };
 use hexorder_contracts::hex_grid::{
-    HexEdge, HexEdgeRegistry, HexGridConfig, HexPosition, HexTile, InfluenceEntry, InfluenceMap,
-    InfluenceRuleRegistry, MovementCostMatrix, StackingRule,
+    CommandRadius, HexEdgeRegistry, HexGridConfig, HexMoveEvent, HexPosition, HexTile,
+    HexVertexRegistry, InfluenceMap, InfluenceRuleRegistry, MovementCostMatrix, ReachabilityMap,
+    ReachabilityRule, ReachabilityRuleRegistry, ReachabilityStatus, ReachabilityTrace,
+    StackingRule, StackingViolations, TraceReachabilityEvent, VisibilityRange,
+};
+use hexorder_contracts::mechanics::{
+    ActiveCombat, ActiveInterrupt, AdvanceOption, AreaMarkerRegistry, AttackedThisPhase,
+    CombatModifierRegistry, CombatMoveEvent, CombatResolvedEvent, CombatResultsTable, CombatSide,
+    CombatTableRegistry, InterruptPause, MoveRequestedEvent, MovementInterruptRegistry,
+    MovementSpent, OffMapZoneRegistry, OutcomeEffect, OverrunRegistry, OverrunRequestedEvent,
+    OverrunTargets, PhaseType, PlayLog, PlayLogEntry, PostResolutionAction, ResolutionRoll,
+    ResolveInterruptEvent, RetreatChoice, SpawnSchedule, VictoryConditionRegistry, ZoneUnit,
+    concept_property_id, current_phase, eliminated_sides, outcome_state_triggers,
+    resolve_interrupt,
 };
 use hexorder_contracts::ontology::{
-    ConceptBinding, ConceptRegistry, ConstraintExpr, ConstraintRegistry, ModifyOperation,
-    RelationEffect, RelationRegistry, RelationTrigger,
+    AppliedEffect, ConceptBinding, ConceptRegistry, ConstraintRegistry, ModifyOperation,
+    PresenceEffects, Relation, RelationEffect, RelationRegistry, RelationTrigger,
+};
+use hexorder_contracts::simulation::{DieRolled, SimulationRng, TableResolved};
+use hexorder_contracts::undo_redo::{UndoStack, UndoableCommand};
+use hexorder_contracts::validation::{
+    ActionEligibility, AnalyzedRules, RuleAnalysis, ValidMoveSet, ValidationResult, analyze_rules,
 };
-use hexorder_contracts::validation::{ValidMoveSet, ValidationResult};
 
-/// Computes the set of valid moves for the currently selected unit.
-///
-/// Runs a BFS from the unit's position, evaluating ontology relations
-/// (with `OnEnter` trigger) at each step. Produces a `ValidMoveSet`
-/// containing reachable positions and explanations for blocked ones.
-///
-/// When no unit is selected the move set is cleared. When no ontology
-/// constraints exist all in-bounds positions are reachable (free movement).
-#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+use crate::context::{
+    BoardUnit, ConditionScope, ProximityBoard, RulesBoard, RulesContext, evaluate_block_condition,
+    property_value_as_f64, property_value_as_i64, resolve_concept_property,
+};
+
+/// Time a frame may spend precomputing move sets. At least one unit is
+/// computed per frame; further units wait for the next frame once this is
+/// spent.
+const PRECOMPUTE_BUDGET: Duration = Duration::from_millis(2);
+
+/// Influence map and per-unit move sets computed for the current board,
+/// kept until a change they depend on invalidates them (see
+/// `invalidate_move_cache`).
+#[derive(Resource, Debug, Default)]
+pub struct MoveCache {
+    /// Whether `InfluenceMap` reflects the current board.
+    pub(crate) influence_fresh: bool,
+    /// Move sets of units whose inputs have not changed since.
+    pub(crate) moves: HashMap<Entity, ValidMoveSet>,
+    /// The unit whose cached move set `ValidMoveSet` holds.
+    shown: Option<Entity>,
+}
+
+/// Drops cached results whose inputs changed. Rule registry changes, tile
+/// edits and units moving, being placed or removed, or changing data,
+/// owner, state, reachability or eligibility invalidate the influence map
+/// and every move set, since influence, stacking, proximity and conditions
+/// read the whole board. A unit's spent movement points changing only
+/// invalidates its own move set.
+#[allow(clippy::type_complexity)]
+pub fn invalidate_move_cache(
+    params: RulesParams,
+    changed_tiles: Query<
+        (),
+        (
+            With<HexTile>,
+            Without<UnitInstance>,
+            Or<(Changed<EntityData>, Changed<EntityState>)>,
+        ),
+    >,
+    changed_units: Query<
+        (),
+        (
+            With<UnitInstance>,
+            Or<(
+                Changed<HexPosition>,
+                Changed<EntityData>,
+                Changed<UnitOwner>,
+                Changed<EntityState>,
+                Changed<ReachabilityStatus>,
+                Changed<ActionEligibility>,
+            )>,
+        ),
+    >,
+    changed_spent: Query<Entity, (With<UnitInstance>, Changed<MovementSpent>)>,
+    mut removed: RemovedComponents<UnitInstance>,
+    mut cache: ResMut<MoveCache>,
+) {
+    let any_removed = removed.read().count() > 0;
+    if params.is_changed() || !changed_tiles.is_empty() || !changed_units.is_empty() || any_removed
+    {
+        if cache.influence_fresh || !cache.moves.is_empty() {
+            *cache = MoveCache::default();
+        }
+        return;
+    }
+    for entity in &changed_spent {
+        cache.moves.remove(&entity);
+        if cache.shown == Some(entity) {
+            cache.shown = None;
+        }
+    }
+}
+
+/// Computes move sets for units of the active faction ahead of selection
+/// in Play, so selecting one of them is instant. Runs on the main schedule
+/// and stops for the frame once `PRECOMPUTE_BUDGET` is spent; the remaining
+/// units follow on later frames.
+pub fn precompute_active_moves(
+    active: Option<Res<ActiveFaction>>,
+    params: RulesParams,
+    mut cache: ResMut<MoveCache>,
+    mut influence_map: ResMut<InfluenceMap>,
+) {
+    let faction = active.and_then(|a| a.faction_id);
+    let pending: Vec<Entity> = params
+        .board
+        .units
+        .iter()
+        .filter(|(entity, _, _, owner, ..)| {
+            owner.faction_id == faction && !cache.moves.contains_key(entity)
+        })
+        .map(|(entity, ..)| entity)
+        .collect();
+    if pending.is_empty() {
+        return;
+    }
+
+    let started = Instant::now();
+    let rules = params.rules();
+    let (board, entities) = params.board();
+    if !cache.influence_fresh {
+        *influence_map = rules.influence_map(&board);
+        cache.influence_fresh = true;
+    }
+    for entity in pending {
+        if let Some(unit) = entities.iter().position(|e| *e == entity) {
+            let mut moves = rules.valid_moves(&board, unit, &influence_map);
+            moves.for_entity = Some(entity);
+            cache.moves.insert(entity, moves);
+        }
+        if started.elapsed() >= PRECOMPUTE_BUDGET {
+            break;
+        }
+    }
+}
+
+/// Sets `ValidMoveSet` to the selected unit's move set (see
+/// `RulesContext::valid_moves`), computing it and the influence map it
+/// moves through only when they are not cached. When no unit is selected the
+/// move set is cleared.
 pub fn compute_valid_moves(
     selected: Res<SelectedUnit>,
-    concepts: Res<ConceptRegistry>,
-    relations: Res<RelationRegistry>,
-    constraints: Res<ConstraintRegistry>,
-    entity_types: Res<EntityTypeRegistry>,
-    grid_config: Res<HexGridConfig>,
-    edge_registry: Res<HexEdgeRegistry>,
-    influence_rules: Res<InfluenceRuleRegistry>,
-    stacking_rule: Res<StackingRule>,
-    movement_cost_matrix: Res<MovementCostMatrix>,
+    params: RulesParams,
+    mut cache: ResMut<MoveCache>,
     mut influence_map: ResMut<InfluenceMap>,
     mut valid_moves: ResMut<ValidMoveSet>,
-    units: Query<(&HexPosition, &EntityData), With<UnitInstance>>,
-    tiles: Query<(&HexPosition, &EntityData), (With<HexTile>, Without<UnitInstance>)>,
 ) {
-    // Only recompute when something relevant changed.
-    if !selected.is_changed()
-        && !concepts.is_changed()
+    // If no unit is selected, clear the move set.
+    let Some(entity) = selected
+        .entity
+        .filter(|entity| params.board.units.contains(*entity))
+    else {
+        if cache.shown.is_some() || valid_moves.for_entity.is_some() {
+            cache.shown = None;
+            valid_moves.clear();
+        }
+        return;
+    };
+
+    // The move set shown is still current.
+    if cache.shown == Some(entity) {
+        return;
+    }
+    if let Some(moves) = cache.moves.get(&entity) {
+        *valid_moves = moves.clone();
+        cache.shown = Some(entity);
+        return;
+    }
+
+    let rules = params.rules();
+    let (board, entities) = params.board();
+    if !cache.influence_fresh {
+        *influence_map = rules.influence_map(&board);
+        cache.influence_fresh = true;
+    }
+    let Some(unit) = entities.iter().position(|e| *e == entity) else {
+        return;
+    };
+    let mut moves = rules.valid_moves(&board, unit, &influence_map);
+    moves.for_entity = Some(entity);
+    *valid_moves = moves.clone();
+    cache.moves.insert(entity, moves);
+    cache.shown = Some(entity);
+}
+
+/// Rule resources and board state, read into a `RulesContext` and a
+/// `RulesBoard`.
+#[derive(SystemParam)]
+pub struct RulesParams<'w, 's> {
+    concepts: Res<'w, ConceptRegistry>,
+    relations: Res<'w, RelationRegistry>,
+    constraints: Res<'w, ConstraintRegistry>,
+    entity_types: Res<'w, EntityTypeRegistry>,
+    grid_config: Res<'w, HexGridConfig>,
+    edge_registry: Res<'w, HexEdgeRegistry>,
+    vertex_registry: Res<'w, HexVertexRegistry>,
+    influence_rules: Res<'w, InfluenceRuleRegistry>,
+    stacking_rule: Res<'w, StackingRule>,
+    movement_cost_matrix: Res<'w, MovementCostMatrix>,
+    area_markers: Res<'w, AreaMarkerRegistry>,
+    interrupts: Res<'w, MovementInterruptRegistry>,
+    overruns: Res<'w, OverrunRegistry>,
+    board: BoardStates<'w, 's>,
+}
+
+impl RulesParams<'_, '_> {
+    /// Whether any rule resource changed since the system last ran.
+    fn is_changed(&self) -> bool {
+        self.concepts.is_changed()
+            || self.relations.is_changed()
+            || self.constraints.is_changed()
+            || self.entity_types.is_changed()
+            || self.grid_config.is_changed()
+            || self.edge_registry.is_changed()
+            || self.vertex_registry.is_changed()
+            || self.influence_rules.is_changed()
+            || self.stacking_rule.is_changed()
+            || self.movement_cost_matrix.is_changed()
+            || self.area_markers.is_changed()
+            || self.interrupts.is_changed()
+            || self.overruns.is_changed()
+            || self.board.machines.is_changed()
+            || self.board.reachability.is_changed()
+    }
+
+    /// The rule resources as a `RulesContext`.
+    fn rules(&self) -> RulesContext<'_> {
+        RulesContext {
+            concepts: &self.concepts,
+            relations: &self.relations,
+            constraints: &self.constraints,
+            entity_types: &self.entity_types,
+            grid_config: &self.grid_config,
+            edges: &self.edge_registry,
+            vertices: &self.vertex_registry,
+            influence_rules: &self.influence_rules,
+            stacking_rule: &self.stacking_rule,
+            movement_cost_matrix: &self.movement_cost_matrix,
+            area_markers: &self.area_markers,
+            state_machines: &self.board.machines,
+            reachability: &self.board.reachability,
+            interrupts: &self.interrupts,
+            overruns: &self.overruns,
+        }
+    }
+
+    /// The tiles and units as a `RulesBoard`, with each unit's entity in
+    /// board order.
+    fn board(&self) -> (RulesBoard<'_>, Vec<Entity>) {
+        let (entities, units) = self
+            .board
+            .units
+            .iter()
+            .map(
+                |(entity, pos, data, owner, state, reach, sight, eligibility, spent)| {
+                    let unit = BoardUnit {
+                        state: state.map(|s| s.state_id),
+                        reach,
+                        eligibility,
+                        sight: sight.map(|s| s.range),
+                        spent: spent.map_or(0, |s| s.0),
+                        ..BoardUnit::new(*pos, data, *owner)
+                    };
+                    (entity, unit)
+                },
+            )
+            .unzip();
+        let tiles = self
+            .board
+            .tiles
+            .iter()
+            .map(|(pos, data, state)| (*pos, data, state.map(|s| s.state_id)));
+        (RulesBoard::new(tiles, units), entities)
+    }
+}
+
+/// Board tiles and units with their state-machine states, reachability
+/// statuses, visibility ranges, action eligibility and spent movement
+/// points.
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+pub struct BoardStates<'w, 's> {
+    machines: Res<'w, StateMachineRegistry>,
+    reachability: Res<'w, ReachabilityRuleRegistry>,
+    units: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static HexPosition,
+            &'static EntityData,
+            &'static UnitOwner,
+            Option<&'static EntityState>,
+            Option<&'static ReachabilityStatus>,
+            Option<&'static VisibilityRange>,
+            Option<&'static ActionEligibility>,
+            Option<&'static MovementSpent>,
+        ),
+        With<UnitInstance>,
+    >,
+    tiles: Query<
+        'w,
+        's,
+        (
+            &'static HexPosition,
+            &'static EntityData,
+            Option<&'static EntityState>,
+        ),
+        (With<HexTile>, Without<UnitInstance>),
+    >,
+}
+
+// ---------------------------------------------------------------------------
+// WhilePresent Effects
+// ---------------------------------------------------------------------------
+
+/// Applies `WhilePresent` relations as continuous effects on unit
+/// `EntityData`, so combat and other resolution read the effective values.
+///
+/// A unit bound to a relation's subject role is modified by every object at
+/// its hex: the tile it stands on and each other unit there. Only
+/// `ModifyProperty` effects apply continuously. Runs when units move, are
+/// added or removed, have their state overrides reapplied (which reverts
+/// their effects), tiles change, or the ontology changes; previous effects
+/// are reverted first and sources read base values, so effects never feed
+/// into each other (see `PresenceEffects`).
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn apply_presence_effects(
+    mut commands: Commands,
+    concepts: Res<ConceptRegistry>,
+    relations: Res<RelationRegistry>,
+    moved: Query<
+        (),
+        (
+            With<UnitInstance>,
+            Or<(
+                Changed<HexPosition>,
+                Added<UnitInstance>,
+                Changed<StateOverrides>,
+            )>,
+        ),
+    >,
+    changed_tiles: Query<(), (With<HexTile>, Without<UnitInstance>, Changed<EntityData>)>,
+    mut removed: RemovedComponents<UnitInstance>,
+    mut units: Query<
+        (
+            Entity,
+            &HexPosition,
+            &mut EntityData,
+            Option<&mut PresenceEffects>,
+        ),
+        With<UnitInstance>,
+    >,
+    tiles: Query<(Entity, &HexPosition, &EntityData), (With<HexTile>, Without<UnitInstance>)>,
+) {
+    let any_removed = removed.read().count() > 0;
+    if !concepts.is_changed()
         && !relations.is_changed()
-        && !constraints.is_changed()
-        && !edge_registry.is_changed()
-        && !influence_rules.is_changed()
-        && !stacking_rule.is_changed()
-        && !movement_cost_matrix.is_changed()
+        && moved.is_empty()
+        && changed_tiles.is_empty()
+        && !any_removed
     {
         return;
     }
 
-    // If no unit is selected, clear the move set.
-    let Some(unit_entity) = selected.entity else {
-        valid_moves.valid_positions.clear();
-        valid_moves.blocked_explanations.clear();
-        valid_moves.for_entity = None;
+    // Revert everything applied last time to get back to base values.
+    for (_, _, mut data, effects) in &mut units {
+        if let Some(mut effects) = effects
+            && !effects.applied.is_empty()
+        {
+            effects.revert(&mut data);
+        }
+    }
+
+    let while_present: Vec<&Relation> = relations
+        .relations
+        .iter()
+        .filter(|r| {
+            r.trigger == RelationTrigger::WhilePresent
+                && matches!(r.effect, RelationEffect::ModifyProperty { .. })
+        })
+        .collect();
+    if while_present.is_empty() {
         return;
+    }
+
+    // Everything that can be an object, read from base values.
+    let mut occupants: HashMap<HexPosition, Vec<(Entity, EntityData)>> = HashMap::new();
+    for (entity, pos, data) in &tiles {
+        occupants
+            .entry(*pos)
+            .or_default()
+            .push((entity, data.clone()));
+    }
+    for (entity, pos, data, _) in &units {
+        occupants
+            .entry(*pos)
+            .or_default()
+            .push((entity, data.clone()));
+    }
+
+    for (entity, pos, mut data, effects) in &mut units {
+        let mut applied = Vec::new();
+        for relation in &while_present {
+            let RelationEffect::ModifyProperty {
+                target_property,
+                source_property,
+                operation,
+            } = &relation.effect
+            else {
+                continue;
+            };
+            let Some(property_id) = bound_property_id(
+                &concepts.bindings,
+                data.entity_type_id,
+                relation.concept_id,
+                relation.subject_role_id,
+                target_property,
+            ) else {
+                continue;
+            };
+            let objects = occupants.get(pos).into_iter().flatten();
+            for (source, object_data) in objects.filter(|(e, _)| *e != entity) {
+                let Some(source_value) = resolve_concept_property(
+                    object_data,
+                    source_property,
+                    relation.concept_id,
+                    relation.object_role_id,
+                    &concepts.bindings,
+                ) else {
+                    continue;
+                };
+                let Some(before) = data.properties.get(&property_id).cloned() else {
+                    continue;
+                };
+                let Some(after) = apply_operation(*operation, &before, &source_value) else {
+                    continue;
+                };
+                data.properties.insert(property_id, after.clone());
+                applied.push(AppliedEffect {
+                    relation_id: relation.id,
+                    source: *source,
+                    property_id,
+                    before,
+                    after,
+                });
+            }
+        }
+        match effects {
+            Some(mut effects) => effects.applied = applied,
+            None if !applied.is_empty() => {
+                commands.entity(entity).insert(PresenceEffects { applied });
+            }
+            None => {}
+        }
+    }
+}
+
+/// The property ID bound to `local_name` for an entity type in a concept role.
+fn bound_property_id(
+    bindings: &[ConceptBinding],
+    entity_type_id: TypeId,
+    concept_id: TypeId,
+    role_id: TypeId,
+    local_name: &str,
+) -> Option<TypeId> {
+    bindings
+        .iter()
+        .filter(|b| {
+            b.entity_type_id == entity_type_id
+                && b.concept_id == concept_id
+                && b.concept_role_id == role_id
+        })
+        .flat_map(|b| &b.property_bindings)
+        .find(|pb| pb.concept_local_name == local_name)
+        .map(|pb| pb.property_id)
+}
+
+/// Applies a modify operation to a numeric property, keeping its variant.
+/// Returns `None` for non-numeric targets or sources.
+fn apply_operation(
+    operation: ModifyOperation,
+    target: &PropertyValue,
+    source: &PropertyValue,
+) -> Option<PropertyValue> {
+    let float_op = |a: f64, b: f64| match operation {
+        ModifyOperation::Add => a + b,
+        ModifyOperation::Subtract => a - b,
+        ModifyOperation::Multiply => a * b,
+        ModifyOperation::Min => a.min(b),
+        ModifyOperation::Max => a.max(b),
+    };
+    let int_op = |a: i64, b: i64| match operation {
+        ModifyOperation::Add => a.saturating_add(b),
+        ModifyOperation::Subtract => a.saturating_sub(b),
+        ModifyOperation::Multiply => a.saturating_mul(b),
+        ModifyOperation::Min => a.min(b),
+        ModifyOperation::Max => a.max(b),
     };
+    Some(match target {
+        PropertyValue::Int(a) => PropertyValue::Int(int_op(*a, property_value_as_i64(source)?)),
+        PropertyValue::IntRange(a) => {
+            PropertyValue::IntRange(int_op(*a, property_value_as_i64(source)?))
+        }
+        PropertyValue::Float(a) => {
+            PropertyValue::Float(float_op(*a, property_value_as_f64(source)?))
+        }
+        PropertyValue::FloatRange(a) => {
+            PropertyValue::FloatRange(float_op(*a, property_value_as_f64(source)?))
+        }
+        _ => return None,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Entity State Machines
+// ---------------------------------------------------------------------------
+
+/// Keeps every unit and tile whose entity type has a state machine in one of
+/// its states. Instances without a state, or in a state their machine does
+/// not have (after a type change or a machine edit), enter the machine's
+/// initial state; instances whose type has no machine lose their state and
+/// its overrides.
+#[allow(clippy::type_complexity)]
+pub fn sync_entity_states(
+    mut commands: Commands,
+    state_machines: Res<StateMachineRegistry>,
+    mut instances: Query<
+        (
+            Entity,
+            &mut EntityData,
+            Option<&EntityState>,
+            Option<&mut StateOverrides>,
+        ),
+        Or<(With<UnitInstance>, With<HexTile>)>,
+    >,
+) {
+    let refresh_all = state_machines.is_changed();
+    for (entity, mut data, state, overrides) in &mut instances {
+        if !refresh_all && !data.is_changed() {
+            continue;
+        }
+        let machine = state_machines.for_type(data.entity_type_id);
+        if let (Some(machine), Some(state)) = (machine, state)
+            && machine.state(state.state_id).is_some()
+        {
+            continue;
+        }
+        match machine.and_then(|m| m.initial()) {
+            Some(initial) => {
+                commands.entity(entity).insert(EntityState {
+                    state_id: initial.id,
+                });
+            }
+            None if state.is_some() => {
+                if let Some(mut overrides) = overrides {
+                    overrides.revert(&mut data);
+                }
+                commands
+                    .entity(entity)
+                    .remove::<(EntityState, StateOverrides)>();
+            }
+            None => {}
+        }
+    }
+}
 
-    // Look up the unit's position and data.
-    let Ok((unit_pos, unit_data)) = units.get(unit_entity) else {
-        valid_moves.valid_positions.clear();
-        valid_moves.blocked_explanations.clear();
-        valid_moves.for_entity = None;
+/// Applies the property overrides of each instance's current state. Runs for
+/// instances that changed state, or for all when the machines change. A
+/// unit's `WhilePresent` effects are reverted first (`apply_presence_effects`
+/// rebuilds them on top), then the previous state's overrides, and the new
+/// state's overrides are applied over the base values.
+#[allow(clippy::type_complexity)]
+pub fn apply_state_overrides(
+    state_machines: Res<StateMachineRegistry>,
+    mut instances: Query<(
+        Ref<EntityState>,
+        &mut EntityData,
+        &mut StateOverrides,
+        Option<&mut PresenceEffects>,
+    )>,
+) {
+    let refresh_all = state_machines.is_changed();
+    for (state, mut data, mut overrides, effects) in &mut instances {
+        if !refresh_all && !state.is_changed() {
+            continue;
+        }
+        if let Some(mut effects) = effects
+            && !effects.applied.is_empty()
+        {
+            effects.revert(&mut data);
+        }
+        overrides.revert(&mut data);
+        overrides.state_id = Some(state.state_id);
+        let Some(definition) = state_machines.state(state.state_id) else {
+            continue;
+        };
+        for (property_id, after) in &definition.property_overrides {
+            let Some(before) = data.properties.get(property_id).cloned() else {
+                continue;
+            };
+            data.properties.insert(*property_id, after.clone());
+            overrides.applied.push(AppliedOverride {
+                property_id: *property_id,
+                before,
+                after: after.clone(),
+            });
+        }
+    }
+}
+
+/// Fires phase-boundary transitions when play moves to a new phase. For each
+/// instance with a state, the first transition from that state triggered by
+/// the new phase starting, or by a constraint whose result matches, moves it
+/// to the transition's target. Constraints are evaluated with the instance
+/// and the tile it stands on filling their roles. At most one transition
+/// fires per instance per boundary.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn fire_phase_state_triggers(
+    mut last_phase: Local<Option<(u32, usize)>>,
+    turn_state: Res<TurnState>,
+    turn_structure: Res<TurnStructure>,
+    state_machines: Res<StateMachineRegistry>,
+    concepts: Res<ConceptRegistry>,
+    constraints: Res<ConstraintRegistry>,
+    entity_types: Res<EntityTypeRegistry>,
+    reachability: Res<ReachabilityRuleRegistry>,
+    grid_config: Res<HexGridConfig>,
+    mut instances: Query<(
+        Entity,
+        &mut EntityState,
+        &EntityData,
+        &HexPosition,
+        Has<UnitInstance>,
+        Option<&UnitOwner>,
+        Option<&ReachabilityStatus>,
+    )>,
+    tiles: Query<(&HexPosition, &EntityData), With<HexTile>>,
+    units: Query<(&HexPosition, &EntityData, &UnitOwner), With<UnitInstance>>,
+) {
+    if turn_state.turn_number == 0 {
+        return;
+    }
+    let current = (turn_state.turn_number, turn_state.current_phase_index);
+    if *last_phase == Some(current) {
+        return;
+    }
+    *last_phase = Some(current);
+    let Some(phase) = current_phase(&turn_state, &turn_structure) else {
         return;
     };
 
-    // Build a spatial lookup for tiles.
     let tile_lookup: HashMap<HexPosition, &EntityData> =
         tiles.iter().map(|(pos, data)| (*pos, data)).collect();
-
-    // Build unit count lookup (non-exempt units per hex) for stacking checks.
-    let unit_counts: HashMap<HexPosition, u32> = if stacking_rule.is_active() {
-        let mut counts: HashMap<HexPosition, u32> = HashMap::new();
-        for (pos, data) in units.iter() {
-            if !stacking_rule.is_exempt(data.entity_type_id) {
-                *counts.entry(*pos).or_insert(0) += 1;
-            }
+    let tile_states: HashMap<HexPosition, TypeId> = instances
+        .iter()
+        .filter(|(_, _, _, _, is_unit, ..)| !is_unit)
+        .map(|(_, state, _, pos, ..)| (*pos, state.state_id))
+        .collect();
+    let proximity = ProximityBoard {
+        grid_config: &grid_config,
+        tiles: &tile_lookup,
+        units: units
+            .iter()
+            .map(|(pos, data, owner)| (*pos, data, *owner))
+            .collect(),
+    };
+    let mut moves: Vec<(Entity, TypeId)> = Vec::new();
+    for (entity, state, data, pos, is_unit, owner, reach) in &instances {
+        let Some(machine) = state_machines.for_type(data.entity_type_id) else {
+            continue;
+        };
+        let (tile, tile_state) = if is_unit {
+            (tile_lookup.get(pos).copied(), tile_states.get(pos).copied())
+        } else {
+            (Some(data), Some(state.state_id))
+        };
+        let constraint_holds = |constraint_id: TypeId| {
+            let constraint = constraints
+                .constraints
+                .iter()
+                .find(|c| c.id == constraint_id)?;
+            let scope = ConditionScope {
+                concept_id: constraint.concept_id,
+                relation: None,
+                concepts: &concepts,
+                entity_types: &entity_types,
+                state_machines: &state_machines,
+                unit: is_unit.then_some(data),
+                tile,
+                edge: None,
+                unit_state: is_unit.then_some(state.state_id),
+                tile_state,
+                reachability: &reachability,
+                unit_reach: reach,
+                spent: 0,
+                unit_pos: is_unit.then_some(*pos),
+                tile_pos: Some(*pos),
+                unit_owner: owner.copied(),
+                proximity: Some(&proximity),
+                step: None,
+                fills: &[],
+            };
+            Some(evaluate_block_condition(&constraint.expression, &scope).holds)
+        };
+        let fired = machine
+            .transitions_from(state.state_id)
+            .find(|t| match t.trigger {
+                StateTrigger::PhaseStart(phase_id) => phase_id == phase.id,
+                StateTrigger::Constraint {
+                    constraint_id,
+                    satisfied,
+                } => constraint_holds(constraint_id) == Some(satisfied),
+                _ => false,
+            });
+        if let Some(transition) = fired
+            && transition.to != state.state_id
+        {
+            moves.push((entity, transition.to));
         }
-        counts
-    } else {
-        HashMap::new()
+    }
+    for (entity, to) in moves {
+        if let Ok((_, mut state, ..)) = instances.get_mut(entity) {
+            state.state_id = to;
+        }
+    }
+}
+
+/// Observer: applies a trigger to an entity's state machine, following the
+/// first matching transition from its current state.
+pub fn handle_state_trigger(
+    trigger: On<StateTriggerEvent>,
+    state_machines: Res<StateMachineRegistry>,
+    mut instances: Query<(&EntityData, &mut EntityState)>,
+) {
+    let event = trigger.event();
+    let Ok((data, mut state)) = instances.get_mut(event.entity) else {
+        return;
     };
+    let next = state_machines
+        .for_type(data.entity_type_id)
+        .and_then(|machine| machine.next_state(state.state_id, &event.trigger));
+    if let Some(next) = next
+        && next != state.state_id
+    {
+        state.state_id = next;
+    }
+}
 
-    let map_radius = grid_config.map_radius;
+/// Observer: puts an entity directly into a state of its type's machine.
+/// States of other machines are ignored.
+pub fn handle_set_entity_state(
+    trigger: On<SetEntityStateEvent>,
+    state_machines: Res<StateMachineRegistry>,
+    mut instances: Query<(&EntityData, &mut EntityState)>,
+) {
+    let event = trigger.event();
+    let Ok((data, mut state)) = instances.get_mut(event.entity) else {
+        return;
+    };
+    let known = state_machines
+        .for_type(data.entity_type_id)
+        .is_some_and(|machine| machine.state(event.state_id).is_some());
+    if known && state.state_id != event.state_id {
+        state.state_id = event.state_id;
+    }
+}
 
-    // Compute influence map from all units and influence rules.
-    compute_influence_map(
-        &influence_rules,
-        &units,
-        *unit_pos,
-        map_radius,
-        &mut influence_map,
-    );
+// ---------------------------------------------------------------------------
+// Reachability
+// ---------------------------------------------------------------------------
 
-    // Collect OnEnter relations.
-    let on_enter_relations: Vec<_> = relations
-        .relations
+/// Observer: traces the requested reachability rules (see
+/// `RulesContext::trace_reachability`). Each traced unit's
+/// `ReachabilityStatus` records whether its hex is in reach, the rule's
+/// traces in `ReachabilityMap` are replaced, and a
+/// `StateTrigger::Reachability` fires for every traced unit.
+pub fn handle_trace_reachability(
+    trigger: On<TraceReachabilityEvent>,
+    params: RulesParams,
+    mut reachability_map: ResMut<ReachabilityMap>,
+    mut commands: Commands,
+) {
+    let requested = trigger.event().rule_id;
+    let rules: Vec<&ReachabilityRule> = params
+        .board
+        .reachability
+        .rules
         .iter()
-        .filter(|r| r.trigger == RelationTrigger::OnEnter)
+        .filter(|r| requested.is_none_or(|id| id == r.id))
         .collect();
+    if rules.is_empty() {
+        return;
+    }
+
+    let context = params.rules();
+    let (board, entities) = params.board();
+    let influence_map = context.influence_map(&board);
+    let mut statuses: HashMap<Entity, ReachabilityStatus> = HashMap::new();
+    for rule in rules {
+        reachability_map.traces.retain(|t| t.rule_id != rule.id);
+        for trace in context.trace_reachability(&board, rule, &influence_map) {
+            for &index in &trace.units {
+                let unit = &board.units[index];
+                let in_reach = trace.reached.contains_key(&unit.pos);
+                statuses
+                    .entry(entities[index])
+                    .or_insert_with(|| unit.reach.cloned().unwrap_or_default())
+                    .in_reach
+                    .insert(rule.id, in_reach);
+                commands.trigger(StateTriggerEvent {
+                    entity: entities[index],
+                    trigger: StateTrigger::Reachability {
+                        rule_id: rule.id,
+                        in_reach,
+                    },
+                });
+            }
+            reachability_map.traces.push(ReachabilityTrace {
+                rule_id: rule.id,
+                units: trace.units.iter().map(|&index| entities[index]).collect(),
+                reached: trace.reached,
+            });
+        }
+    }
+    for (entity, status) in statuses {
+        commands.entity(entity).insert(status);
+    }
+}
+
+/// Keeps every unit's `ActionEligibility` up to date (see
+/// `RulesContext::action_eligibility`). Components are only written when a
+/// unit's denials change, and are removed when no constraint gates actions.
+pub fn compute_action_eligibility(params: RulesParams, mut commands: Commands) {
+    let gating = params
+        .constraints
+        .constraints
+        .iter()
+        .any(|c| !c.gates.is_empty());
+    if !gating {
+        for (entity, .., eligibility) in &params.board.units {
+            if eligibility.is_some() {
+                commands.entity(entity).remove::<ActionEligibility>();
+            }
+        }
+        return;
+    }
+
+    let (board, entities) = params.board();
+    let eligibilities = params.rules().action_eligibility(&board);
+    for ((unit, entity), eligibility) in board.units.iter().zip(&entities).zip(eligibilities) {
+        if unit.eligibility.cloned().unwrap_or_default() != eligibility {
+            commands.entity(*entity).insert(eligibility);
+        }
+    }
+}
+
+/// Rebuilds the `CommandRadius` around the selected unit (see
+/// `RulesContext::command_radius`).
+pub fn compute_command_radius(
+    selected: Res<SelectedUnit>,
+    params: RulesParams,
+    mut radius: ResMut<CommandRadius>,
+) {
+    let gating = params
+        .constraints
+        .constraints
+        .iter()
+        .any(|c| !c.gates.is_empty());
+    if !gating {
+        radius.set_if_neq(CommandRadius::default());
+        return;
+    }
+    let (board, entities) = params.board();
+    let Some(unit) = selected
+        .entity
+        .and_then(|entity| entities.iter().position(|e| *e == entity))
+    else {
+        radius.set_if_neq(CommandRadius::default());
+        return;
+    };
+
+    let hexes = params.rules().command_radius(&board, unit);
+    if hexes.is_empty() {
+        radius.set_if_neq(CommandRadius::default());
+        return;
+    }
+    radius.set_if_neq(CommandRadius {
+        source: Some(entities[unit]),
+        hexes,
+    });
+}
+
+/// Traces the reachability rules that run in a phase when play moves to it.
+pub fn trace_reachability_at_phase_start(
+    mut last_phase: Local<Option<(u32, usize)>>,
+    turn_state: Res<TurnState>,
+    turn_structure: Res<TurnStructure>,
+    reachability: Res<ReachabilityRuleRegistry>,
+    mut commands: Commands,
+) {
+    if turn_state.turn_number == 0 {
+        return;
+    }
+    let current = (turn_state.turn_number, turn_state.current_phase_index);
+    if *last_phase == Some(current) {
+        return;
+    }
+    *last_phase = Some(current);
+    let Some(phase) = current_phase(&turn_state, &turn_structure) else {
+        return;
+    };
+    for rule in &reachability.rules {
+        if rule.phases.contains(&phase.id) {
+            commands.trigger(TraceReachabilityEvent {
+                rule_id: Some(rule.id),
+            });
+        }
+    }
+}
 
-    // Clear previous results.
-    valid_moves.valid_positions.clear();
-    valid_moves.blocked_explanations.clear();
-    valid_moves.for_entity = Some(unit_entity);
+// ---------------------------------------------------------------------------
+// Stacking
+// ---------------------------------------------------------------------------
 
-    // If no relations and no constraints exist, free movement within bounds.
-    if on_enter_relations.is_empty() && constraints.constraints.is_empty() {
-        bfs_free_movement(*unit_pos, map_radius, &mut valid_moves);
+/// Rebuilds `StackingViolations` (see `RulesContext::stacking_violations`).
+/// Movement and placement never over-stack, so violations come from combat,
+/// spawns or rule changes; each newly over-stacked hex is reported with an
+/// error toast.
+pub fn detect_stacking_violations(
+    params: RulesParams,
+    mut violations: ResMut<StackingViolations>,
+    mut commands: Commands,
+) {
+    if !params.stacking_rule.is_active() {
+        violations.set_if_neq(StackingViolations::default());
         return;
     }
+    let (board, _) = params.board();
+    let found = params.rules().stacking_violations(&board);
+    for violation in &found {
+        let known = violations
+            .violations
+            .iter()
+            .any(|v| v.position == violation.position);
+        if !known {
+            commands.trigger(ToastEvent {
+                message: format!(
+                    "Hex ({}, {}) is over-stacked: {}/{} stacking points",
+                    violation.position.q, violation.position.r, violation.points, violation.limit
+                ),
+                kind: ToastKind::Error,
+            });
+        }
+    }
+    violations.set_if_neq(StackingViolations { violations: found });
+}
 
-    // Find the unit's concept bindings.
-    let unit_bindings: Vec<&ConceptBinding> = concepts
-        .bindings
+// ---------------------------------------------------------------------------
+// Movement Interrupts
+// ---------------------------------------------------------------------------
+
+/// Observer: moves a unit in Play along its cheapest route to the requested
+/// hex, halting at the first movement interrupt (see
+/// `RulesContext::movement_interrupt`) until it is resolved. The unit's
+/// `MovementSpent` grows by the cost of the hexes it enters. Requests while
+/// a move is halted are ignored.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_move_request(
+    trigger: On<MoveRequestedEvent>,
+    params: RulesParams,
+    valid_moves: Res<ValidMoveSet>,
+    unit_ids: Query<&UnitId>,
+    mut cache: ResMut<MoveCache>,
+    mut influence_map: ResMut<InfluenceMap>,
+    mut active: ResMut<ActiveInterrupt>,
+    mut commands: Commands,
+) {
+    if active.pause.is_some() {
+        return;
+    }
+    let event = trigger.event();
+    let spent = params
+        .board
+        .units
+        .get(event.entity)
+        .ok()
+        .and_then(|(.., spent)| spent)
+        .map_or(0, |s| s.0);
+    let moves = cache
+        .moves
+        .get(&event.entity)
+        .or((valid_moves.for_entity == Some(event.entity)).then_some(&*valid_moves));
+    // Without a computed route (free movement) the unit enters `to` directly.
+    let route = moves
+        .and_then(|moves| moves.route(event.to))
+        .map_or_else(|| vec![event.to], |route| route[1..].to_vec());
+    let costs: Vec<i64> = route
         .iter()
-        .filter(|b| b.entity_type_id == unit_data.entity_type_id)
+        .map(|pos| {
+            spent
+                + moves
+                    .and_then(|moves| moves.paths.get(pos))
+                    .map_or(0, |step| step.total_cost)
+        })
         .collect();
+    active.pause = advance_move(
+        &params,
+        event.entity,
+        &route,
+        &costs,
+        Vec::new(),
+        &mut cache,
+        &mut influence_map,
+        &unit_ids,
+        &mut commands,
+    );
+}
 
-    // Determine the initial budget from ModifyProperty Subtract relations.
-    let initial_budget =
-        determine_budget(&unit_bindings, &on_enter_relations, unit_data, &concepts);
-
-    // Resolve the unit's classification for matrix cost lookup.
-    let unit_classification_value: Option<String> = movement_cost_matrix
-        .classification_property_id
-        .and_then(|prop_id| unit_data.properties.get(&prop_id))
-        .and_then(|v| match v {
-            PropertyValue::Enum(s) => Some(s.clone()),
-            _ => None,
+/// Observer: resolves the interrupt halting a move with the simulation RNG
+/// and applies its outcome: the unit is put into the outcome's state, and
+/// unless the outcome ends its movement the move goes on along the rest of
+/// its route.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_resolve_interrupt(
+    _trigger: On<ResolveInterruptEvent>,
+    params: RulesParams,
+    unit_ids: Query<&UnitId>,
+    mut rng: ResMut<SimulationRng>,
+    mut cache: ResMut<MoveCache>,
+    mut influence_map: ResMut<InfluenceMap>,
+    mut active: ResMut<ActiveInterrupt>,
+    mut commands: Commands,
+) {
+    let Some(pause) = active.pause.take() else {
+        return;
+    };
+    let rules = params.rules();
+    let (board, entities) = params.board();
+    let index = |entity: Entity| entities.iter().position(|e| *e == entity);
+    let (Some(mover), Some(reactor), Some(rule)) = (
+        index(pause.mover),
+        index(pause.reactor),
+        rules.interrupts.get(pause.rule_id),
+    ) else {
+        return;
+    };
+    let values = rules.interrupt_values(&board, reactor, mover, pause.position);
+    let result = resolve_interrupt(rule, &values, &mut rng);
+    announce_rolls(&result.rolls, &mut commands);
+    if let Some(state_id) = result.set_state {
+        commands.trigger(SetEntityStateEvent {
+            entity: pause.mover,
+            state_id,
         });
-
-    // Gather context needed for step evaluation.
-    let ctx = StepContext {
-        unit_data,
-        unit_bindings: &unit_bindings,
-        on_enter_relations: &on_enter_relations,
-        concepts: &concepts,
-        entity_types: &entity_types,
-        edge_registry: &edge_registry,
-        influence_map: &influence_map,
-        unit_pos: *unit_pos,
-        stacking_rule: &stacking_rule,
-        unit_counts: &unit_counts,
-        movement_cost_matrix: &movement_cost_matrix,
-        unit_classification: unit_classification_value.as_deref(),
+    }
+    let effect = if result.end_movement {
+        "movement ends"
+    } else {
+        "movement continues"
     };
+    commands.trigger(ToastEvent {
+        message: format!(
+            "{}: {}, {effect}",
+            rule.name,
+            result.result.as_deref().unwrap_or("no result")
+        ),
+        kind: ToastKind::Info,
+    });
+    if result.end_movement {
+        return;
+    }
 
-    // BFS with budget tracking.
-    let mut queue: VecDeque<(HexPosition, i64)> = VecDeque::new();
-    let mut best_budget: HashMap<HexPosition, i64> = HashMap::new();
+    let mut resolved = pause.resolved;
+    resolved.push((pause.rule_id, pause.reactor));
+    // Other units may still react in the hex the unit halted in.
+    let route: Vec<HexPosition> = std::iter::once(pause.position)
+        .chain(pause.remaining)
+        .collect();
+    let costs: Vec<i64> = std::iter::once(board.units[mover].spent)
+        .chain(pause.costs)
+        .collect();
+    active.pause = advance_move(
+        &params,
+        pause.mover,
+        &route,
+        &costs,
+        resolved,
+        &mut cache,
+        &mut influence_map,
+        &unit_ids,
+        &mut commands,
+    );
+}
 
-    queue.push_back((*unit_pos, initial_budget));
-    best_budget.insert(*unit_pos, initial_budget);
+/// Announces the rolls made resolving an interrupt or overrun for the roll
+/// history, each with the table lookup it fed.
+fn announce_rolls(rolls: &[ResolutionRoll], commands: &mut Commands) {
+    for ResolutionRoll {
+        context,
+        roll,
+        table,
+    } in rolls
+    {
+        commands.trigger(DieRolled {
+            context: context.clone(),
+            roll: roll.clone(),
+        });
+        if let Some((table_id, resolution)) = table {
+            commands.trigger(TableResolved {
+                table_id: *table_id,
+                resolution: resolution.clone(),
+                roll: Some(roll.clone()),
+            });
+        }
+    }
+}
 
-    while let Some((current_pos, remaining_budget)) = queue.pop_front() {
-        let hex = current_pos.to_hex();
+/// Clears what is left over from an earlier play session: a halted move,
+/// the play log, spent movement points and attacked-this-phase markers.
+pub fn reset_play_session(
+    mut active: ResMut<ActiveInterrupt>,
+    mut log: ResMut<PlayLog>,
+    spent: Query<Entity, With<MovementSpent>>,
+    attacked: Query<Entity, With<AttackedThisPhase>>,
+    mut commands: Commands,
+) {
+    active.pause = None;
+    log.entries.clear();
+    for entity in &spent {
+        commands.entity(entity).remove::<MovementSpent>();
+    }
+    for entity in &attacked {
+        commands.entity(entity).remove::<AttackedThisPhase>();
+    }
+}
 
-        for neighbor_hex in hex.all_neighbors() {
-            let neighbor_pos = HexPosition::from_hex(neighbor_hex);
+/// Moves `entity` along `route` up to the first interrupt by a rule and
+/// reacting unit not in `resolved`, and returns the pause it halts in.
+/// `costs` holds the unit's `MovementSpent` after entering each hex.
+#[allow(clippy::too_many_arguments)]
+fn advance_move(
+    params: &RulesParams,
+    entity: Entity,
+    route: &[HexPosition],
+    costs: &[i64],
+    resolved: Vec<(TypeId, Entity)>,
+    cache: &mut MoveCache,
+    influence_map: &mut InfluenceMap,
+    unit_ids: &Query<&UnitId>,
+    commands: &mut Commands,
+) -> Option<InterruptPause> {
+    let rules = params.rules();
+    let (board, entities) = params.board();
+    let unit = entities.iter().position(|e| *e == entity)?;
+    if !cache.influence_fresh {
+        *influence_map = rules.influence_map(&board);
+        cache.influence_fresh = true;
+    }
+    let skip: Vec<(TypeId, usize)> = resolved
+        .iter()
+        .filter_map(|(rule_id, reactor)| {
+            let index = entities.iter().position(|e| e == reactor)?;
+            Some((*rule_id, index))
+        })
+        .collect();
+    let interrupt = rules.movement_interrupt(&board, unit, route, influence_map, &skip);
+    let entered = interrupt.map_or(route.len(), |i| i.step + 1);
+    let from = board.units[unit].pos;
+    if let Some(&to) = route[..entered].last()
+        && to != from
+    {
+        commands.entity(entity).insert(to);
+        if let Ok(unit_id) = unit_ids.get(entity) {
+            commands.trigger(HexMoveEvent {
+                entity,
+                unit_id: *unit_id,
+                from,
+                to,
+            });
+        }
+    }
+    if let Some(&spent) = costs[..entered].last()
+        && spent != board.units[unit].spent
+    {
+        commands.entity(entity).insert(MovementSpent(spent));
+    }
+    interrupt.map(|i| InterruptPause {
+        mover: entity,
+        reactor: entities[i.reactor],
+        rule_id: i.rule_id,
+        position: i.pos,
+        remaining: route[entered..].to_vec(),
+        costs: costs[entered..].to_vec(),
+        resolved,
+    })
+}
 
-            if !is_within_bounds(neighbor_pos, map_radius) {
-                continue;
+/// Resets every unit's spent movement points when play moves to another
+/// phase.
+pub fn reset_movement_spent(
+    mut last_phase: Local<Option<(u32, usize)>>,
+    turn_state: Res<TurnState>,
+    mut spent: Query<&mut MovementSpent>,
+) {
+    let current = (turn_state.turn_number, turn_state.current_phase_index);
+    if *last_phase == Some(current) {
+        return;
+    }
+    *last_phase = Some(current);
+    for mut spent in &mut spent {
+        spent.set_if_neq(MovementSpent(0));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Overrun
+// ---------------------------------------------------------------------------
+
+/// Whether the current phase is a movement phase, in which overruns are
+/// allowed.
+fn in_movement_phase(turn_state: &TurnState, turn_structure: &TurnStructure) -> bool {
+    current_phase(turn_state, turn_structure).is_some_and(|p| p.phase_type == PhaseType::Movement)
+}
+
+/// Sets `OverrunTargets` to the enemy units the selected unit may overrun
+/// (see `RulesContext::can_overrun`), in Play during a movement phase and
+/// while no move is halted.
+pub fn compute_overrun_targets(
+    selected: Res<SelectedUnit>,
+    params: RulesParams,
+    turn_state: Res<TurnState>,
+    turn_structure: Res<TurnStructure>,
+    active: Res<ActiveInterrupt>,
+    mut targets: ResMut<OverrunTargets>,
+) {
+    let attacker = selected
+        .entity
+        .filter(|_| !params.overruns.rules.is_empty() && active.pause.is_none())
+        .filter(|_| in_movement_phase(&turn_state, &turn_structure));
+    let Some(attacker) = attacker else {
+        targets.set_if_neq(OverrunTargets::default());
+        return;
+    };
+    let rules = params.rules();
+    let (board, entities) = params.board();
+    let Some(unit) = entities.iter().position(|e| *e == attacker) else {
+        targets.set_if_neq(OverrunTargets::default());
+        return;
+    };
+    let mut found = Vec::new();
+    for (defender, entity) in entities.iter().enumerate() {
+        for rule in &rules.overruns.rules {
+            if rules.can_overrun(&board, unit, defender, rule) {
+                found.push((*entity, rule.id));
             }
+        }
+    }
+    targets.set_if_neq(OverrunTargets {
+        attacker: Some(attacker),
+        targets: found,
+    });
+}
 
-            if neighbor_pos == *unit_pos {
-                continue;
-            }
+/// Observer: resolves an overrun in Play during a movement phase (see
+/// `RulesContext::overrun`). The attacker pays the rule's movement points;
+/// a cleared defender leaves the board and the attacker moves on with the
+/// budget left, otherwise the attacker's movement ends. The overrun is
+/// logged in `PlayLog` and recorded on the undo stack.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_overrun_request(
+    trigger: On<OverrunRequestedEvent>,
+    params: RulesParams,
+    turn_state: Res<TurnState>,
+    turn_structure: Res<TurnStructure>,
+    active: Res<ActiveInterrupt>,
+    zones: Res<OffMapZoneRegistry>,
+    mut rng: ResMut<SimulationRng>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    if active.pause.is_some() || !in_movement_phase(&turn_state, &turn_structure) {
+        return;
+    }
+    let rules = params.rules();
+    let (board, entities) = params.board();
+    let index = |entity: Entity| entities.iter().position(|e| *e == entity);
+    let (Some(attacker), Some(defender), Some(rule)) = (
+        index(event.attacker),
+        index(event.defender),
+        rules.overruns.get(event.rule_id),
+    ) else {
+        return;
+    };
+    let Some(report) = rules.overrun(&board, attacker, defender, rule.id, &mut rng) else {
+        return;
+    };
+    announce_rolls(&report.result.rolls, &mut commands);
+
+    let name = |unit: usize| {
+        let unit = &board.units[unit];
+        let type_name = rules
+            .entity_types
+            .get(unit.data.entity_type_id)
+            .map_or("Unit", |t| t.name.as_str());
+        format!("{type_name} at ({}, {})", unit.pos.q, unit.pos.r)
+    };
+    let effect = if report.result.clears_defender {
+        "defender cleared"
+    } else {
+        "attacker halted"
+    };
+    let message = format!(
+        "{}: {} overruns {}: {}, {effect}",
+        rule.name,
+        name(attacker),
+        name(defender),
+        report.result.result.as_deref().unwrap_or("no result"),
+    );
+    // Outcome states outside a unit's machine are ignored.
+    let state_change = |unit: usize, target: Option<TypeId>| {
+        let unit = &board.units[unit];
+        let known = params
+            .board
+            .machines
+            .for_type(unit.data.entity_type_id)
+            .is_some_and(|machine| target.is_some_and(|id| machine.state(id).is_some()));
+        unit.state.zip(target).filter(|_| known)
+    };
+    let command = OverrunCommand {
+        attacker: event.attacker,
+        spent: (board.units[attacker].spent, report.spent),
+        attacker_state: state_change(attacker, report.result.attacker_state),
+        defender: Some(event.defender),
+        defender_state: if report.result.clears_defender {
+            None
+        } else {
+            state_change(defender, report.result.defender_state)
+        },
+        cleared: report
+            .result
+            .clears_defender
+            .then(|| RemovedUnit::new(board.units[defender].pos, zones.eliminated_zone_id)),
+        entry: PlayLogEntry {
+            turn_number: turn_state.turn_number,
+            message: message.clone(),
+        },
+        label: format!("Overrun: {}", rule.name),
+    };
+    commands.queue(move |world: &mut World| {
+        let mut command = command;
+        command.execute(world);
+        if let Some(mut stack) = world.get_resource_mut::<UndoStack>() {
+            stack.record(Box::new(command));
+        }
+    });
+    commands.trigger(ToastEvent {
+        message,
+        kind: ToastKind::Info,
+    });
+}
 
-            let tile_data = tile_lookup.get(&neighbor_pos).copied();
+/// A unit an overrun or combat removed from the board.
+#[derive(Debug)]
+struct RemovedUnit {
+    position: HexPosition,
+    /// The elimination zone it was sent to, if one is set.
+    zone_id: Option<TypeId>,
+    /// Its persistent id, to find it in the zone.
+    unit_id: Option<UnitId>,
+    /// The unit while it is off the board and not in a zone.
+    held: Option<ZoneUnit>,
+}
 
-            let step_result =
-                evaluate_step(&ctx, tile_data, remaining_budget, current_pos, neighbor_pos);
+impl RemovedUnit {
+    fn new(position: HexPosition, zone_id: Option<TypeId>) -> Self {
+        Self {
+            position,
+            zone_id,
+            unit_id: None,
+            held: None,
+        }
+    }
 
-            match step_result {
-                StepResult::Valid { new_budget } => {
-                    let dominated = best_budget
-                        .get(&neighbor_pos)
-                        .is_some_and(|&prev| prev >= new_budget);
-                    if dominated {
-                        continue;
-                    }
-                    best_budget.insert(neighbor_pos, new_budget);
-                    valid_moves.valid_positions.insert(neighbor_pos);
-                    valid_moves.blocked_explanations.remove(&neighbor_pos);
+    /// Takes `entity` off the board into the zone, or holds it when there
+    /// is no zone. Whether the unit was on the board.
+    fn remove(&mut self, world: &mut World, entity: Entity) -> bool {
+        let Some(unit) = take_off_board(world, entity) else {
+            return false;
+        };
+        self.unit_id = unit.id;
+        let mut zones = world.resource_mut::<OffMapZoneRegistry>();
+        if !self.zone_id.is_some_and(|id| zones.store(id, unit.clone())) {
+            self.held = Some(unit);
+        }
+        true
+    }
 
-                    if new_budget > 0 {
-                        queue.push_back((neighbor_pos, new_budget));
-                    }
-                }
-                StepResult::Blocked { reasons } => {
-                    if !valid_moves.valid_positions.contains(&neighbor_pos) {
-                        valid_moves
-                            .blocked_explanations
-                            .entry(neighbor_pos)
-                            .or_default()
-                            .extend(reasons);
-                    }
-                }
-            }
+    /// Puts the unit back on its hex, returning its new entity.
+    fn restore(&mut self, world: &mut World) -> Option<Entity> {
+        let unit = self.held.take().or_else(|| {
+            let zone_id = self.zone_id?;
+            let mut zones = world.resource_mut::<OffMapZoneRegistry>();
+            let index = zones
+                .get(zone_id)?
+                .units
+                .iter()
+                .rposition(|u| u.id.is_some() && u.id == self.unit_id)?;
+            zones.take(zone_id, index)
+        })?;
+        let owner = UnitOwner {
+            faction_id: unit.owner,
+        };
+        let unit_id = unit.id.unwrap_or_default();
+        let state = unit.state;
+        let mut spawned = world.spawn((
+            UnitInstance,
+            self.position,
+            EntityData::from(unit),
+            owner,
+            unit_id,
+            Transform::default(),
+        ));
+        if let Some(state_id) = state {
+            spawned.insert(EntityState { state_id });
         }
+        Some(spawned.id())
     }
 }
 
-/// Shared context for step evaluation, avoiding excessive parameter counts.
-struct StepContext<'a> {
-    unit_data: &'a EntityData,
-    unit_bindings: &'a [&'a ConceptBinding],
-    on_enter_relations: &'a [&'a hexorder_contracts::ontology::Relation],
-    concepts: &'a ConceptRegistry,
-    entity_types: &'a EntityTypeRegistry,
-    edge_registry: &'a HexEdgeRegistry,
-    influence_map: &'a InfluenceMap,
-    /// Position of the moving unit (to exclude self-influence).
-    unit_pos: HexPosition,
-    stacking_rule: &'a StackingRule,
-    unit_counts: &'a HashMap<HexPosition, u32>,
-    movement_cost_matrix: &'a MovementCostMatrix,
-    /// The unit's classification value for matrix cost lookup.
-    unit_classification: Option<&'a str>,
+/// An overrun's effects on the board, recorded for undo. Undo restores the
+/// attacker's spent movement points and both units' states, puts a cleared
+/// defender back on its hex and removes the log entry; redo applies them
+/// again without rolling.
+#[derive(Debug)]
+struct OverrunCommand {
+    attacker: Entity,
+    /// The attacker's spent movement points before and after.
+    spent: (i64, i64),
+    /// States before and after, for units the outcome put into a state.
+    attacker_state: Option<(TypeId, TypeId)>,
+    /// The defender, while it is on the board.
+    defender: Option<Entity>,
+    defender_state: Option<(TypeId, TypeId)>,
+    cleared: Option<RemovedUnit>,
+    entry: PlayLogEntry,
+    label: String,
 }
 
-/// Result of evaluating a single BFS step into a neighbor hex.
-enum StepResult {
-    Valid { new_budget: i64 },
-    Blocked { reasons: Vec<ValidationResult> },
+/// Puts `entity` into `state_id` if it has a state.
+fn set_state(world: &mut World, entity: Entity, state_id: TypeId) {
+    if let Some(mut state) = world.get_mut::<EntityState>(entity) {
+        state.state_id = state_id;
+    }
 }
 
-/// Free-movement BFS: all positions within grid bounds reachable from the
-/// unit's position (no budget limit beyond map radius).
-fn bfs_free_movement(start: HexPosition, map_radius: u32, valid_moves: &mut ValidMoveSet) {
-    let mut queue: VecDeque<HexPosition> = VecDeque::new();
-    queue.push_back(start);
+/// Takes the unit `entity` off the board, keeping its base data, owner, id
+/// and state. See `handle_move_to_zone` in the unit plugin.
+fn take_off_board(world: &mut World, entity: Entity) -> Option<ZoneUnit> {
+    let unit = world.get_entity(entity).ok()?;
+    let data = unit.get::<EntityData>()?;
+    let mut data = unit
+        .get::<PresenceEffects>()
+        .map_or_else(|| data.clone(), |effects| effects.base_data(data));
+    if let Some(overrides) = unit.get::<StateOverrides>() {
+        data = overrides.base_data(&data);
+    }
+    let zone_unit =
+        ZoneUnit::with_owner(data, unit.get::<UnitOwner>().copied().unwrap_or_default())
+            .with_id(unit.get::<UnitId>().copied().unwrap_or_default())
+            .with_state(unit.get::<EntityState>().map(|s| s.state_id));
+    let mut selected = world.resource_mut::<SelectedUnit>();
+    if selected.entity == Some(entity) {
+        selected.entity = None;
+    }
+    world.despawn(entity);
+    Some(zone_unit)
+}
 
-    let mut visited = HashSet::new();
-    visited.insert(start);
+impl UndoableCommand for OverrunCommand {
+    fn execute(&mut self, world: &mut World) {
+        if let Ok(mut attacker) = world.get_entity_mut(self.attacker) {
+            attacker.insert(MovementSpent(self.spent.1));
+        }
+        if let Some((_, state_id)) = self.attacker_state {
+            set_state(world, self.attacker, state_id);
+        }
+        if let (Some(defender), Some((_, state_id))) = (self.defender, self.defender_state) {
+            set_state(world, defender, state_id);
+        }
+        if let Some(cleared) = &mut self.cleared
+            && let Some(defender) = self.defender
+            && cleared.remove(world, defender)
+        {
+            self.defender = None;
+        }
+        world
+            .resource_mut::<PlayLog>()
+            .entries
+            .push(self.entry.clone());
+    }
 
-    while let Some(current) = queue.pop_front() {
-        let hex = current.to_hex();
-        for neighbor_hex in hex.all_neighbors() {
-            let neighbor = HexPosition::from_hex(neighbor_hex);
-            if !is_within_bounds(neighbor, map_radius) {
-                continue;
-            }
-            if visited.contains(&neighbor) {
-                continue;
-            }
-            visited.insert(neighbor);
-            valid_moves.valid_positions.insert(neighbor);
-            queue.push_back(neighbor);
+    fn undo(&mut self, world: &mut World) {
+        if let Ok(mut attacker) = world.get_entity_mut(self.attacker) {
+            attacker.insert(MovementSpent(self.spent.0));
+        }
+        if let Some((state_id, _)) = self.attacker_state {
+            set_state(world, self.attacker, state_id);
+        }
+        if let (Some(defender), Some((state_id, _))) = (self.defender, self.defender_state) {
+            set_state(world, defender, state_id);
+        }
+        if let Some(cleared) = &mut self.cleared
+            && let Some(defender) = cleared.restore(world)
+        {
+            self.defender = Some(defender);
+        }
+        let mut log = world.resource_mut::<PlayLog>();
+        if let Some(index) = log.entries.iter().rposition(|e| *e == self.entry) {
+            log.entries.remove(index);
         }
     }
+
+    fn description(&self) -> String {
+        self.label.clone()
+    }
 }
 
-/// Checks whether a hex position is within grid bounds.
-/// Grid bounds: `max(|q|, |r|, |q+r|) <= map_radius`.
-fn is_within_bounds(pos: HexPosition, map_radius: u32) -> bool {
-    let q = pos.q.unsigned_abs();
-    let r = pos.r.unsigned_abs();
-    let s = (pos.q + pos.r).unsigned_abs();
-    q.max(r).max(s) <= map_radius
+// ---------------------------------------------------------------------------
+// Combat Tables
+// ---------------------------------------------------------------------------
+
+/// Board indices of the active combat's attacking and defending units.
+fn combat_indices(active_combat: &ActiveCombat, entities: &[Entity]) -> (Vec<usize>, Vec<usize>) {
+    let indices = |units: Vec<Entity>| -> Vec<usize> {
+        units
+            .into_iter()
+            .filter_map(|unit| entities.iter().position(|e| *e == unit))
+            .collect()
+    };
+    (
+        indices(active_combat.attacking_units()),
+        indices(active_combat.defending_units()),
+    )
 }
 
-/// Computes the influence map from all placed units and the influence rule registry.
-///
-/// For each unit on the board, checks if its entity type has an influence rule.
-/// If so, projects influence into all hexes within the rule's range. The
-/// selected unit's position is excluded so it doesn't influence itself.
-fn compute_influence_map(
-    rules: &InfluenceRuleRegistry,
-    units: &Query<(&HexPosition, &EntityData), With<UnitInstance>>,
-    _selected_unit_pos: HexPosition,
-    map_radius: u32,
-    influence_map: &mut InfluenceMap,
+/// Sets `ActiveCombat::table` to the conditional CRT the active combat is
+/// resolved on (see `RulesContext::select_combat_table`), in Play. The table
+/// is kept once the combat is rolled, so the outcome is read against it.
+pub fn select_combat_table(
+    params: RulesParams,
+    turn_state: Res<TurnState>,
+    turn_structure: Res<TurnStructure>,
+    tables: Res<CombatTableRegistry>,
+    mut active_combat: ResMut<ActiveCombat>,
 ) {
-    influence_map.clear();
-
-    if rules.rules.is_empty() {
+    if active_combat.outcome.is_some() {
         return;
     }
+    let mut table = None;
+    if !tables.tables.is_empty() {
+        let (board, entities) = params.board();
+        let (attackers, defenders) = combat_indices(&active_combat, &entities);
+        if !attackers.is_empty() && !defenders.is_empty() {
+            let phase_id = current_phase(&turn_state, &turn_structure).map(|p| p.id);
+            table = params
+                .rules()
+                .select_combat_table(&board, &tables, phase_id, &attackers, &defenders)
+                .map(|selected| selected.id);
+        }
+    }
+    if active_combat.table != table {
+        active_combat.table = table;
+    }
+}
 
-    for (unit_pos, unit_data) in units.iter() {
-        for rule in &rules.rules {
-            if unit_data.entity_type_id != rule.entity_type_id {
-                continue;
-            }
-            // Project influence to all hexes within range.
-            let center = unit_pos.to_hex();
-            for ring in 1..=rule.range {
-                for hex in center.ring(ring) {
-                    let pos = HexPosition::from_hex(hex);
-                    if !is_within_bounds(pos, map_radius) {
-                        continue;
+// ---------------------------------------------------------------------------
+// Combat Modifiers
+// ---------------------------------------------------------------------------
+
+/// Records in `ActiveCombat::modifier_results` whether each combat modifier
+/// with a terrain filter or a condition applies to the active combat, and
+/// why (see `RulesContext::modifier_applies`), in Play. Modifiers with
+/// neither always apply and are not listed. The results are kept once the
+/// combat is rolled.
+pub fn evaluate_combat_modifiers(
+    params: RulesParams,
+    crt: Res<CombatResultsTable>,
+    tables: Res<CombatTableRegistry>,
+    modifiers: Res<CombatModifierRegistry>,
+    mut active_combat: ResMut<ActiveCombat>,
+) {
+    if active_combat.outcome.is_some() {
+        return;
+    }
+    let conditional: Vec<_> = modifiers
+        .modifiers
+        .iter()
+        .filter(|m| m.condition.is_some() || m.terrain_type_filter.is_some())
+        .collect();
+    let mut results = Vec::new();
+    if !conditional.is_empty() {
+        let (board, entities) = params.board();
+        let (attackers, defenders) = combat_indices(&active_combat, &entities);
+        let crt = tables.table_or(active_combat.table, &crt);
+        let rules = params.rules();
+        results = conditional
+            .into_iter()
+            .map(|modifier| {
+                if attackers.is_empty() || defenders.is_empty() {
+                    ValidationResult {
+                        constraint_id: modifier.id,
+                        constraint_name: modifier.name.clone(),
+                        satisfied: false,
+                        explanation: "waiting for an attacker and a defender".to_string(),
                     }
-                    influence_map
-                        .influenced
-                        .entry(pos)
-                        .or_default()
-                        .push(InfluenceEntry {
-                            source_pos: *unit_pos,
-                            rule_id: rule.id,
-                            cost_modifier: rule.cost_modifier,
-                        });
+                } else {
+                    rules.modifier_applies(&board, crt, modifier, &attackers, &defenders)
                 }
-            }
-        }
+            })
+            .collect();
+    }
+    if active_combat.modifier_results != results {
+        active_combat.modifier_results = results;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Combat Outcomes
+// ---------------------------------------------------------------------------
+
+/// Keeps `ActiveCombat::advances` listing the attackers that may advance
+/// into each vacated hex (see `RulesContext::advance_options`), in Play.
+pub fn update_advance_options(params: RulesParams, mut active_combat: ResMut<ActiveCombat>) {
+    let mut advances = Vec::new();
+    if !active_combat.vacated.is_empty() {
+        let (board, entities) = params.board();
+        let (attackers, _) = combat_indices(&active_combat, &entities);
+        advances = params
+            .rules()
+            .advance_options(&board, &attackers, &active_combat.vacated)
+            .into_iter()
+            .map(|(unit, to)| AdvanceOption {
+                unit: entities[unit],
+                from: board.units[unit].pos,
+                to,
+            })
+            .collect();
     }
+    if active_combat.advances != advances {
+        active_combat.advances = advances;
+    }
+}
+
+/// A property value after losing `steps`, never below zero, and whether
+/// none are left. `None` if the value is not numeric.
+fn lose_steps(value: &PropertyValue, steps: u32) -> Option<(PropertyValue, bool)> {
+    let steps_int = i64::from(steps);
+    let steps_float = f64::from(steps);
+    Some(match value {
+        PropertyValue::Int(v) => {
+            let left = (v - steps_int).max(0);
+            (PropertyValue::Int(left), left == 0)
+        }
+        PropertyValue::IntRange(v) => {
+            let left = (v - steps_int).max(0);
+            (PropertyValue::IntRange(left), left == 0)
+        }
+        PropertyValue::Float(v) => {
+            let left = (v - steps_float).max(0.0);
+            (PropertyValue::Float(left), left <= 0.0)
+        }
+        PropertyValue::FloatRange(v) => {
+            let left = (v - steps_float).max(0.0);
+            (PropertyValue::FloatRange(left), left <= 0.0)
+        }
+        _ => return None,
+    })
 }
 
-/// Determines the initial movement budget for a unit based on its concept
-/// bindings and the ontology relations.
+/// Observer: executes a resolved combat's outcome effect, read against the
+/// CRT the combat was resolved on (`ActiveCombat::table`). The changes are
+/// logged in `PlayLog` and recorded on the undo stack as one action.
 ///
-/// Strategy:
-/// 1. Look for `ModifyProperty` with `Subtract` operation to identify the
-///    budget property via `target_property`.
-/// 2. Find the matching property value on the unit's `EntityData`.
-/// 3. Fall back to a named "budget" property.
-/// 4. Fall back to a generous default if no budget property is found.
-fn determine_budget(
-    unit_bindings: &[&ConceptBinding],
-    on_enter_relations: &[&hexorder_contracts::ontology::Relation],
-    unit_data: &EntityData,
-    concepts: &ConceptRegistry,
-) -> i64 {
-    // Strategy 1: Find ModifyProperty Subtract relations and extract the
-    // target_property (which is the budget concept-local name on the subject).
-    for relation in on_enter_relations {
-        if let RelationEffect::ModifyProperty {
-            target_property,
-            operation: ModifyOperation::Subtract,
-            ..
-        } = &relation.effect
-        {
-            for binding in unit_bindings {
-                if binding.concept_id != relation.concept_id
-                    || binding.concept_role_id != relation.subject_role_id
-                {
-                    continue;
-                }
-                for prop_binding in &binding.property_bindings {
-                    if prop_binding.concept_local_name != *target_property {
-                        continue;
-                    }
-                    if let Some(value) = unit_data.properties.get(&prop_binding.property_id)
-                        && let Some(budget) = property_value_as_i64(value)
-                    {
-                        return budget;
-                    }
-                }
+/// - Step losses follow the event's allocation, or fall on the primary
+///   attacker or defender when none was allocated. A unit in a state
+///   machine follows one `StepLoss` transition per step; otherwise the
+///   CRT's steps property (`LossRules::steps_property`) drops by one per
+///   step. A unit with no step left to lose is eliminated.
+/// - Retreat and elimination outcomes put every unit of the side through
+///   its `Retreat` or `Eliminated` transition, and eliminated sides leave
+///   the board.
+/// - Eliminated units go to the elimination zone of `OffMapZoneRegistry`.
+/// - Retreating defenders move the outcome's distance away from the
+///   attackers (see `RulesContext::retreat_paths`), one stack at a time. A
+///   single best path is followed at once, equally good paths wait in
+///   `ActiveCombat::retreats` for the owning player, and units with
+///   nowhere to go are eliminated.
+/// - Defender hexes left empty are listed in `ActiveCombat::vacated`, open
+///   to advance after combat (see `handle_combat_move`).
+#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+pub fn handle_combat_resolved(
+    trigger: On<CombatResolvedEvent>,
+    params: RulesParams,
+    crt: Res<CombatResultsTable>,
+    tables: Res<CombatTableRegistry>,
+    zones: Res<OffMapZoneRegistry>,
+    turn_state: Res<TurnState>,
+    mut active_combat: ResMut<ActiveCombat>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let crt = tables.table_or(active_combat.table, &crt);
+    let rules = params.rules();
+    let (board, entities) = params.board();
+    let index = |entity: Entity| entities.iter().position(|e| *e == entity);
+    let side_units = |side: CombatSide| -> Vec<usize> {
+        let (primary, units) = match side {
+            CombatSide::Attacker => (event.attacker, &event.attackers),
+            CombatSide::Defender => (event.defender, &event.defenders),
+        };
+        let units = if units.is_empty() {
+            vec![primary]
+        } else {
+            units.clone()
+        };
+        units.into_iter().filter_map(index).collect()
+    };
+    let name = |unit: usize| {
+        let unit = &board.units[unit];
+        let type_name = rules
+            .entity_types
+            .get(unit.data.entity_type_id)
+            .map_or("Unit", |t| t.name.as_str());
+        format!("{type_name} at ({}, {})", unit.pos.q, unit.pos.r)
+    };
+    let machine = |unit: usize| {
+        params
+            .board
+            .machines
+            .for_type(board.units[unit].data.entity_type_id)
+    };
+    let effect = event
+        .outcome
+        .effect
+        .clone()
+        .unwrap_or(OutcomeEffect::NoEffect);
+    let triggers = outcome_state_triggers(&effect);
+
+    let mut losses: Vec<(usize, u32)> = event
+        .step_losses
+        .iter()
+        .filter_map(|&(entity, steps)| Some((index(entity)?, steps)))
+        .collect();
+    if event.step_losses.is_empty() {
+        for (side, primary) in [
+            (CombatSide::Attacker, event.attacker),
+            (CombatSide::Defender, event.defender),
+        ] {
+            let steps = triggers
+                .iter()
+                .filter(|(s, t)| *s == side && *t == StateTrigger::StepLoss)
+                .count() as u32;
+            if let Some(unit) = index(primary).filter(|_| steps > 0) {
+                losses.push((unit, steps));
             }
         }
     }
 
-    // Strategy 2: Look through all bindings for a property with concept-local
-    // name "budget" as a fallback heuristic.
-    for binding in unit_bindings {
-        for prop_binding in &binding.property_bindings {
-            if prop_binding.concept_local_name == "budget"
-                && let Some(value) = unit_data.properties.get(&prop_binding.property_id)
-                && let Some(budget) = property_value_as_i64(value)
+    let mut states: Vec<Option<TypeId>> = board.units.iter().map(|u| u.state).collect();
+    let mut properties = Vec::new();
+    let mut eliminated: Vec<usize> = Vec::new();
+    let mut parts = Vec::new();
+    for (unit, steps) in losses {
+        if eliminated.contains(&unit) {
+            continue;
+        }
+        let steps_property = crt
+            .combat_concept_id
+            .zip(crt.losses.steps_property.as_deref())
+            .and_then(|(concept_id, name)| {
+                let data = board.units[unit].data;
+                concept_property_id(rules.concepts, concept_id, data.entity_type_id, name)
+            })
+            .and_then(|property_id| {
+                let before = board.units[unit].data.properties.get(&property_id)?;
+                Some((property_id, before.clone(), lose_steps(before, steps)?))
+            });
+        let survives = if let (Some(machine), Some(mut state)) = (machine(unit), states[unit]) {
+            let walked = (0..steps).all(|_| {
+                machine
+                    .next_state(state, &StateTrigger::StepLoss)
+                    .map(|next| state = next)
+                    .is_some()
+            });
+            states[unit] = Some(state);
+            walked
+        } else if let Some((property_id, before, (after, exhausted))) = steps_property {
+            properties.push((entities[unit], property_id, (before, after)));
+            !exhausted
+        } else {
+            false
+        };
+        if survives {
+            let noun = if steps == 1 { "step" } else { "steps" };
+            parts.push(format!("{} loses {steps} {noun}", name(unit)));
+        } else {
+            eliminated.push(unit);
+        }
+    }
+    for (side, state_trigger) in &triggers {
+        if *state_trigger == StateTrigger::StepLoss {
+            continue;
+        }
+        for unit in side_units(*side) {
+            if let (Some(machine), Some(state)) = (machine(unit), states[unit])
+                && let Some(next) = machine.next_state(state, state_trigger)
             {
-                return budget;
+                states[unit] = Some(next);
             }
         }
     }
-
-    // Fallback: no budget found, allow generous default.
-    i64::from(concepts.concepts.len().max(1) as u32) * 10
-}
-
-/// Evaluates a single step of the BFS: checks whether the unit can enter
-/// `target_pos` given the tile at that position and the applicable relations.
-/// Also checks edge annotations on the boundary between `from_pos` and `target_pos`.
-fn evaluate_step(
-    ctx: &StepContext<'_>,
-    tile_data: Option<&EntityData>,
-    remaining_budget: i64,
-    from_pos: HexPosition,
-    target_pos: HexPosition,
-) -> StepResult {
-    let mut blocked_reasons: Vec<ValidationResult> = Vec::new();
-    let mut cost: i64 = 0;
-    let mut has_block = false;
-
-    // Check edge annotations on the boundary being crossed.
-    if let Some(edge) = HexEdge::between(from_pos, target_pos)
-        && let Some(feature) = ctx.edge_registry.get(&edge)
-    {
-        let edge_cost = resolve_edge_cost(feature, ctx.entity_types);
-        cost += edge_cost;
-        if remaining_budget - cost < 0 {
-            let unit_type_name = ctx
-                .entity_types
-                .get(ctx.unit_data.entity_type_id)
-                .map_or("Unit", |et| et.name.as_str());
-            blocked_reasons.push(ValidationResult {
-                constraint_id: TypeId(uuid::Uuid::nil()),
-                constraint_name: format!("{} crossing", feature.type_name),
-                satisfied: false,
-                explanation: format!(
-                    "{unit_type_name} cannot reach ({}, {}): edge crossing cost {cost} exceeds budget of {remaining_budget}",
-                    target_pos.q,
-                    target_pos.r,
-                ),
-            });
+    for side in eliminated_sides(&effect) {
+        for unit in side_units(side) {
+            if !eliminated.contains(&unit) {
+                eliminated.push(unit);
+            }
         }
     }
 
-    // Check spatial influence on the target hex.
-    if let Some(entries) = ctx.influence_map.get(target_pos) {
-        for entry in entries {
-            // Don't apply influence from the moving unit's own position.
-            if entry.source_pos == ctx.unit_pos {
+    let mut moves = Vec::new();
+    let mut retreats = Vec::new();
+    if let OutcomeEffect::Retreat { hexes } = effect {
+        let threats: Vec<HexPosition> = side_units(CombatSide::Attacker)
+            .into_iter()
+            .map(|unit| board.units[unit].pos)
+            .collect();
+        let mut stacks: Vec<Vec<usize>> = Vec::new();
+        for unit in side_units(CombatSide::Defender) {
+            if eliminated.contains(&unit) {
                 continue;
             }
-            cost += entry.cost_modifier;
+            let pos = board.units[unit].pos;
+            match stacks.iter_mut().find(|s| board.units[s[0]].pos == pos) {
+                Some(stack) => stack.push(unit),
+                None => stacks.push(vec![unit]),
+            }
         }
-        if remaining_budget - cost < 0 && !entries.is_empty() {
-            let total_influence: i64 = entries
-                .iter()
-                .filter(|e| e.source_pos != ctx.unit_pos)
-                .map(|e| e.cost_modifier)
-                .sum();
-            if total_influence > 0 {
-                blocked_reasons.push(ValidationResult {
-                    constraint_id: TypeId(uuid::Uuid::nil()),
-                    constraint_name: "Spatial influence".to_string(),
-                    satisfied: false,
-                    explanation: format!(
-                        "Cannot reach ({}, {}): influence cost +{total_influence} exceeds remaining budget of {remaining_budget}",
-                        target_pos.q, target_pos.r,
-                    ),
+        for stack in stacks {
+            let mut paths = rules.retreat_paths(&board, &stack, hexes, &threats);
+            if paths.len() > 1 {
+                parts.push(format!("{} must choose a retreat", name(stack[0])));
+                retreats.push(RetreatChoice {
+                    units: stack.iter().map(|&unit| entities[unit]).collect(),
+                    paths,
                 });
+            } else if let Some(&to) = paths.pop().as_ref().and_then(|path| path.last()) {
+                for unit in stack {
+                    parts.push(format!("{} retreats to ({}, {})", name(unit), to.q, to.r));
+                    moves.push((entities[unit], (board.units[unit].pos, to)));
+                }
+            } else {
+                for unit in stack {
+                    parts.push(format!("{} cannot retreat", name(unit)));
+                    eliminated.push(unit);
+                }
             }
         }
     }
+    parts.extend(
+        eliminated
+            .iter()
+            .map(|&unit| format!("{} eliminated", name(unit))),
+    );
 
-    // Check stacking constraint on the target hex.
-    if ctx.stacking_rule.is_active() {
-        let current_count = ctx.unit_counts.get(&target_pos).copied().unwrap_or(0);
-        if ctx
-            .stacking_rule
-            .would_exceed(ctx.unit_data.entity_type_id, current_count)
-        {
-            has_block = true;
-            blocked_reasons.push(ValidationResult {
-                constraint_id: TypeId(uuid::Uuid::nil()),
-                constraint_name: "Stacking limit".to_string(),
-                satisfied: false,
-                explanation: format!(
-                    "Hex ({}, {}) has {current_count}/{} units",
-                    target_pos.q, target_pos.r, ctx.stacking_rule.max_units,
-                ),
-            });
+    let gone = |unit: usize| {
+        eliminated.contains(&unit) || moves.iter().any(|(entity, _)| *entity == entities[unit])
+    };
+    let mut vacated = Vec::new();
+    for unit in side_units(CombatSide::Defender) {
+        let pos = board.units[unit].pos;
+        let empty = board
+            .units
+            .iter()
+            .enumerate()
+            .all(|(other, u)| u.pos != pos || gone(other));
+        if empty && !vacated.contains(&pos) {
+            vacated.push(pos);
         }
     }
+    active_combat.retreats = retreats;
+    active_combat.vacated = vacated;
 
-    for relation in ctx.on_enter_relations {
-        // Find unit bindings matching the subject role of this relation.
-        let unit_matches_subject = ctx.unit_bindings.iter().any(|b| {
-            b.concept_id == relation.concept_id && b.concept_role_id == relation.subject_role_id
-        });
-        if !unit_matches_subject {
-            continue;
-        }
-
-        // Find tile bindings matching the object role of this relation.
-        let tile_matches_object = tile_data.is_some_and(|td| {
-            ctx.concepts.bindings.iter().any(|b| {
-                b.entity_type_id == td.entity_type_id
-                    && b.concept_id == relation.concept_id
-                    && b.concept_role_id == relation.object_role_id
+    let state_change = |unit: usize| board.units[unit].state.zip(states[unit]);
+    let mut message = format!(
+        "Combat {}, roll {}: {}",
+        event.column_label, event.die_roll, event.outcome.label
+    );
+    if !parts.is_empty() {
+        message = format!("{message}: {}", parts.join(", "));
+    }
+    let command = CombatOutcomeCommand {
+        states: (0..board.units.len())
+            .filter(|unit| !eliminated.contains(unit))
+            .filter_map(|unit| {
+                let change = state_change(unit).filter(|(before, after)| before != after)?;
+                Some((entities[unit], change))
             })
-        });
-        if !tile_matches_object {
-            continue;
-        }
-
-        match &relation.effect {
-            RelationEffect::ModifyProperty {
-                target_property,
-                source_property,
-                operation,
-            } => {
-                let source_value = tile_data.and_then(|td| {
-                    resolve_concept_property(
-                        td,
-                        source_property,
-                        relation.concept_id,
-                        relation.object_role_id,
-                        &ctx.concepts.bindings,
-                    )
-                });
-
-                // Check the movement cost matrix first: if active and the tile
-                // has an entity type, use the matrix cost for this (terrain, classification)
-                // pair. Fall back to the standard source property cost.
-                let source_val = if *operation == ModifyOperation::Subtract
-                    && ctx.movement_cost_matrix.is_active()
-                    && let Some(classification) = ctx.unit_classification
-                    && let Some(td) = tile_data
-                    && let Some(matrix_cost) = ctx
-                        .movement_cost_matrix
-                        .get_cost(td.entity_type_id, classification)
-                {
-                    matrix_cost
-                } else {
-                    source_value
-                        .and_then(|v| property_value_as_i64(&v))
-                        .unwrap_or(0)
-                };
+            .collect(),
+        properties: properties
+            .into_iter()
+            .filter(|(entity, ..)| !eliminated.iter().any(|&unit| entities[unit] == *entity))
+            .collect(),
+        moves,
+        eliminated: eliminated
+            .iter()
+            .map(|&unit| EliminatedUnit {
+                entity: Some(entities[unit]),
+                state: state_change(unit),
+                removed: RemovedUnit::new(board.units[unit].pos, zones.eliminated_zone_id),
+            })
+            .collect(),
+        entry: PlayLogEntry {
+            turn_number: turn_state.turn_number,
+            message,
+        },
+        label: format!("Combat: {} {}", event.column_label, event.outcome.label),
+    };
+    record_combat_command(&mut commands, command);
+}
 
-                match operation {
-                    ModifyOperation::Subtract => {
-                        cost += source_val;
-                    }
-                    ModifyOperation::Add => {
-                        cost -= source_val;
-                    }
-                    _ => {}
-                }
+/// Observer: moves combat participants after resolution, logged in
+/// `PlayLog` and recorded on the undo stack. A retreat follows a path the
+/// owning player chose from `ActiveCombat::retreats` and opens the hex it
+/// empties to advance; an advance must end in a hex of
+/// `ActiveCombat::vacated` next to the advancing units (see
+/// `RulesContext::advance_options`), which it then fills.
+pub fn handle_combat_move(
+    trigger: On<CombatMoveEvent>,
+    params: RulesParams,
+    turn_state: Res<TurnState>,
+    mut active_combat: ResMut<ActiveCombat>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let (Some(&from), Some(&to)) = (event.path.first(), event.path.last()) else {
+        return;
+    };
+    let verb = match event.action {
+        PostResolutionAction::Retreat => "retreats",
+        PostResolutionAction::Advance if active_combat.vacated.contains(&to) => "advances",
+        _ => return,
+    };
+    let rules = params.rules();
+    let (board, entities) = params.board();
+    let units: Vec<usize> = event
+        .units
+        .iter()
+        .filter_map(|entity| entities.iter().position(|e| e == entity))
+        .filter(|&unit| board.units[unit].pos == from)
+        .collect();
+    if units.is_empty() || from == to {
+        return;
+    }
+    // Every advancing unit must be next to the vacated hex and fit there.
+    if event.action == PostResolutionAction::Advance
+        && rules.advance_options(&board, &units, &[to]).len() != units.len()
+    {
+        return;
+    }
 
-                if remaining_budget - cost < 0 {
-                    let unit_type_name = ctx
-                        .entity_types
-                        .get(ctx.unit_data.entity_type_id)
-                        .map_or("Unit", |et| et.name.as_str());
-                    blocked_reasons.push(ValidationResult {
-                        constraint_id: relation.id,
-                        constraint_name: relation.name.clone(),
-                        satisfied: false,
-                        explanation: format!(
-                            "{unit_type_name} cannot reach ({}, {}): path cost {cost} exceeds {target_property} of {remaining_budget}",
-                            target_pos.q,
-                            target_pos.r,
-                        ),
-                    });
-                }
-            }
-            RelationEffect::Block { condition } => {
-                let is_blocked = match condition {
-                    None => true,
-                    Some(expr) => {
-                        evaluate_block_condition(expr, ctx.unit_data, tile_data, relation)
-                    }
-                };
-                if is_blocked {
-                    has_block = true;
-                    let unit_type_name = ctx
-                        .entity_types
-                        .get(ctx.unit_data.entity_type_id)
-                        .map_or("Unit", |et| et.name.as_str());
-                    let target_type_name = tile_data
-                        .and_then(|td| ctx.entity_types.get(td.entity_type_id))
-                        .map_or("Unknown", |et| et.name.as_str());
-                    blocked_reasons.push(ValidationResult {
-                        constraint_id: relation.id,
-                        constraint_name: relation.name.clone(),
-                        satisfied: false,
-                        explanation: format!(
-                            "{unit_type_name} cannot enter {target_type_name}: {} blocks entry",
-                            relation.name
-                        ),
-                    });
-                }
-            }
-            RelationEffect::Allow { .. } => {
-                // Allow effects are not blocking; they permit entry.
-                // In a default-deny model these would allowlist, but for
-                // 0.4.0 we treat absence of Block as implicit allow.
+    let parts: Vec<String> = units
+        .iter()
+        .map(|&unit| {
+            let type_name = rules
+                .entity_types
+                .get(board.units[unit].data.entity_type_id)
+                .map_or("Unit", |t| t.name.as_str());
+            format!(
+                "{type_name} at ({}, {}) {verb} to ({}, {})",
+                from.q, from.r, to.q, to.r
+            )
+        })
+        .collect();
+    match event.action {
+        PostResolutionAction::Retreat => {
+            active_combat.retreats.retain(|r| r.units != event.units);
+            let emptied = board
+                .units
+                .iter()
+                .enumerate()
+                .all(|(unit, u)| u.pos != from || units.contains(&unit));
+            if emptied && !active_combat.vacated.contains(&from) {
+                active_combat.vacated.push(from);
             }
         }
+        _ => active_combat.vacated.retain(|pos| *pos != to),
     }
 
-    if has_block {
-        return StepResult::Blocked {
-            reasons: blocked_reasons,
-        };
-    }
+    let label = match event.action {
+        PostResolutionAction::Retreat => "Retreat",
+        _ => "Advance after combat",
+    };
+    let command = CombatOutcomeCommand {
+        states: Vec::new(),
+        properties: Vec::new(),
+        moves: units
+            .iter()
+            .map(|&unit| (entities[unit], (from, to)))
+            .collect(),
+        eliminated: Vec::new(),
+        entry: PlayLogEntry {
+            turn_number: turn_state.turn_number,
+            message: parts.join(", "),
+        },
+        label: label.to_string(),
+    };
+    record_combat_command(&mut commands, command);
+}
 
-    let new_budget = remaining_budget - cost;
-    if new_budget < 0 {
-        StepResult::Blocked {
-            reasons: blocked_reasons,
+/// Executes a combat command and records it on the undo stack.
+fn record_combat_command(commands: &mut Commands, command: CombatOutcomeCommand) {
+    commands.queue(move |world: &mut World| {
+        let mut command = command;
+        command.execute(world);
+        if let Some(mut stack) = world.get_resource_mut::<UndoStack>() {
+            stack.record(Box::new(command));
         }
-    } else {
-        StepResult::Valid { new_budget }
+    });
+}
+
+/// A unit a combat eliminated.
+#[derive(Debug)]
+struct EliminatedUnit {
+    /// The unit, while it is on the board.
+    entity: Option<Entity>,
+    /// Its state before and after the outcome, if it has one.
+    state: Option<(TypeId, TypeId)>,
+    removed: RemovedUnit,
+}
+
+/// A combat outcome's effects on the board, or a move after combat,
+/// recorded for undo. Undo puts eliminated units back, restores states,
+/// step properties and positions and removes the log entry; redo applies
+/// them again without rolling.
+#[derive(Debug)]
+struct CombatOutcomeCommand {
+    /// States before and after, for surviving units.
+    states: Vec<(Entity, (TypeId, TypeId))>,
+    /// Steps properties before and after, for surviving units.
+    properties: Vec<(Entity, TypeId, (PropertyValue, PropertyValue))>,
+    /// Hexes moved from and to.
+    moves: Vec<(Entity, (HexPosition, HexPosition))>,
+    eliminated: Vec<EliminatedUnit>,
+    entry: PlayLogEntry,
+    label: String,
+}
+
+/// Sets the property `property_id` of `entity`, if it has entity data.
+fn set_property(world: &mut World, entity: Entity, property_id: TypeId, value: &PropertyValue) {
+    if let Some(mut data) = world.get_mut::<EntityData>(entity) {
+        data.properties.insert(property_id, value.clone());
     }
 }
 
-/// Resolves a concept-local property name to the actual `PropertyValue` on
-/// an entity, using the concept bindings to map from concept-local names
-/// to property IDs.
-fn resolve_concept_property(
-    entity_data: &EntityData,
-    concept_local_name: &str,
-    concept_id: TypeId,
-    concept_role_id: TypeId,
-    bindings: &[ConceptBinding],
-) -> Option<PropertyValue> {
-    for binding in bindings {
-        if binding.entity_type_id != entity_data.entity_type_id
-            || binding.concept_id != concept_id
-            || binding.concept_role_id != concept_role_id
-        {
-            continue;
+impl UndoableCommand for CombatOutcomeCommand {
+    fn execute(&mut self, world: &mut World) {
+        for &(entity, (_, to)) in &self.moves {
+            if let Ok(mut unit) = world.get_entity_mut(entity) {
+                unit.insert(to);
+            }
+        }
+        for &(entity, (_, state_id)) in &self.states {
+            set_state(world, entity, state_id);
+        }
+        for (entity, property_id, (_, after)) in &self.properties {
+            set_property(world, *entity, *property_id, after);
         }
-        for prop_binding in &binding.property_bindings {
-            if prop_binding.concept_local_name == concept_local_name {
-                return entity_data
-                    .properties
-                    .get(&prop_binding.property_id)
-                    .cloned();
+        for unit in &mut self.eliminated {
+            if let Some(entity) = unit.entity {
+                if let Some((_, state_id)) = unit.state {
+                    set_state(world, entity, state_id);
+                }
+                if unit.removed.remove(world, entity) {
+                    unit.entity = None;
+                }
             }
         }
+        world
+            .resource_mut::<PlayLog>()
+            .entries
+            .push(self.entry.clone());
     }
-    None
-}
-
-/// Evaluates a block condition expression. For 0.4.0, handles `IsType`,
-/// `IsNotType`, `All`, `Any`, and `Not` checks. Other expression types
-/// default to true (blocked, conservative).
-fn evaluate_block_condition(
-    expr: &ConstraintExpr,
-    unit_data: &EntityData,
-    tile_data: Option<&EntityData>,
-    relation: &hexorder_contracts::ontology::Relation,
-) -> bool {
-    match expr {
-        ConstraintExpr::IsType {
-            role_id,
-            entity_type_id,
-        } => {
-            let data = if *role_id == relation.subject_role_id {
-                Some(unit_data)
-            } else if *role_id == relation.object_role_id {
-                tile_data
-            } else {
-                None
-            };
-            data.is_some_and(|d| d.entity_type_id == *entity_type_id)
-        }
-        ConstraintExpr::IsNotType {
-            role_id,
-            entity_type_id,
-        } => {
-            let data = if *role_id == relation.subject_role_id {
-                Some(unit_data)
-            } else if *role_id == relation.object_role_id {
-                tile_data
-            } else {
-                None
-            };
-            data.is_some_and(|d| d.entity_type_id != *entity_type_id)
+
+    fn undo(&mut self, world: &mut World) {
+        for unit in &mut self.eliminated {
+            if unit.entity.is_none() {
+                unit.entity = unit.removed.restore(world);
+            }
+            if let (Some(entity), Some((state_id, _))) = (unit.entity, unit.state) {
+                set_state(world, entity, state_id);
+            }
         }
-        ConstraintExpr::All(exprs) => exprs
-            .iter()
-            .all(|e| evaluate_block_condition(e, unit_data, tile_data, relation)),
-        ConstraintExpr::Any(exprs) => exprs
-            .iter()
-            .any(|e| evaluate_block_condition(e, unit_data, tile_data, relation)),
-        ConstraintExpr::Not(inner) => {
-            !evaluate_block_condition(inner, unit_data, tile_data, relation)
+        for (entity, property_id, (before, _)) in &self.properties {
+            set_property(world, *entity, *property_id, before);
+        }
+        for &(entity, (state_id, _)) in &self.states {
+            set_state(world, entity, state_id);
         }
-        // For other expression types, default to blocked (conservative).
-        _ => true,
+        for &(entity, (from, _)) in &self.moves {
+            if let Ok(mut unit) = world.get_entity_mut(entity) {
+                unit.insert(from);
+            }
+        }
+        let mut log = world.resource_mut::<PlayLog>();
+        if let Some(index) = log.entries.iter().rposition(|e| *e == self.entry) {
+            log.entries.remove(index);
+        }
+    }
+
+    fn description(&self) -> String {
+        self.label.clone()
     }
 }
 
-/// Extracts an `i64` value from a `PropertyValue`, coercing numeric types.
-fn property_value_as_i64(value: &PropertyValue) -> Option<i64> {
-    match value {
-        PropertyValue::Int(v) => Some(*v),
-        PropertyValue::Float(v) => Some(*v as i64),
-        _ => None,
+// ---------------------------------------------------------------------------
+// Rule Analysis
+// ---------------------------------------------------------------------------
+
+/// Mechanic resources read by static rule analysis.
+#[derive(SystemParam)]
+pub struct MechanicRules<'w> {
+    turn_structure: Res<'w, TurnStructure>,
+    crt: Res<'w, CombatResultsTable>,
+    combat_tables: Res<'w, CombatTableRegistry>,
+    combat_modifiers: Res<'w, CombatModifierRegistry>,
+    influence_rules: Res<'w, InfluenceRuleRegistry>,
+    stacking_rule: Res<'w, StackingRule>,
+    movement_cost_matrix: Res<'w, MovementCostMatrix>,
+    state_machines: Res<'w, StateMachineRegistry>,
+    reachability_rules: Res<'w, ReachabilityRuleRegistry>,
+    spawn_schedule: Res<'w, SpawnSchedule>,
+    victory_conditions: Res<'w, VictoryConditionRegistry>,
+}
+
+impl MechanicRules<'_> {
+    /// Whether any mechanic resource changed since the system last ran.
+    fn is_changed(&self) -> bool {
+        self.turn_structure.is_changed()
+            || self.crt.is_changed()
+            || self.combat_tables.is_changed()
+            || self.combat_modifiers.is_changed()
+            || self.influence_rules.is_changed()
+            || self.stacking_rule.is_changed()
+            || self.movement_cost_matrix.is_changed()
+            || self.state_machines.is_changed()
+            || self.reachability_rules.is_changed()
+            || self.spawn_schedule.is_changed()
+            || self.victory_conditions.is_changed()
     }
 }
 
-/// Resolves the movement cost of crossing a hex edge with the given feature.
-///
-/// Looks up the feature's `type_name` in the entity type registry. If the
-/// entity type has a property named "cost", its default value is used as the
-/// crossing cost. Otherwise the edge adds +1 cost (non-zero so it always
-/// affects movement).
-fn resolve_edge_cost(
-    feature: &hexorder_contracts::hex_grid::EdgeFeature,
-    entity_types: &EntityTypeRegistry,
-) -> i64 {
-    let Some(entity_type) = entity_types
-        .types
-        .iter()
-        .find(|t| t.name == feature.type_name)
-    else {
-        return 1;
-    };
-    entity_type
-        .properties
-        .iter()
-        .find(|p| p.name == "cost")
-        .and_then(|p| property_value_as_i64(&p.default_value))
-        .unwrap_or(1)
+/// Rebuilds `RuleAnalysis` (see `analyze_rules`) when the ontology, a
+/// mechanic or a placed entity's data changes.
+#[allow(clippy::too_many_arguments)]
+pub fn run_rule_analysis(
+    concepts: Res<ConceptRegistry>,
+    relations: Res<RelationRegistry>,
+    constraints: Res<ConstraintRegistry>,
+    entity_types: Res<EntityTypeRegistry>,
+    mechanics: MechanicRules,
+    instances: Query<&EntityData>,
+    changed_instances: Query<(), Changed<EntityData>>,
+    mut analysis: ResMut<RuleAnalysis>,
+) {
+    if !concepts.is_changed()
+        && !relations.is_changed()
+        && !constraints.is_changed()
+        && !entity_types.is_changed()
+        && !mechanics.is_changed()
+        && changed_instances.is_empty()
+    {
+        return;
+    }
+    let instances: Vec<&EntityData> = instances.iter().collect();
+    let findings = analyze_rules(&AnalyzedRules {
+        entity_types: &entity_types,
+        concepts: &concepts,
+        relations: &relations,
+        constraints: &constraints,
+        turn_structure: &mechanics.turn_structure,
+        crt: &mechanics.crt,
+        combat_tables: &mechanics.combat_tables,
+        combat_modifiers: &mechanics.combat_modifiers,
+        influence_rules: &mechanics.influence_rules,
+        stacking_rule: &mechanics.stacking_rule,
+        movement_cost_matrix: &mechanics.movement_cost_matrix,
+        state_machines: &mechanics.state_machines,
+        reachability_rules: &mechanics.reachability_rules,
+        spawn_schedule: &mechanics.spawn_schedule,
+        victory_conditions: &mechanics.victory_conditions,
+        instances: &instances,
+    });
+    if analysis.findings != findings {
+        analysis.findings = findings;
+    }
 }
 
 // Combat resolution: `resolve_crt` lives in `hexorder_contracts::mechanics` and delegates
@@ -6,18 +6,20 @@ use bevy::prelude::*;
 
 use hexorder_contracts::game_system::{
     EntityData, EntityRole, EntityType, EntityTypeRegistry, PropertyDefinition, PropertyType,
-    PropertyValue, SelectedUnit, TypeId, UnitInstance,
+    PropertyValue, SelectedUnit, TypeId, UnitInstance, UnitOwner,
 };
 use hexorder_contracts::hex_grid::{
-    HexEdgeRegistry, HexGridConfig, HexPosition, HexTile, InfluenceMap, InfluenceRule,
-    InfluenceRuleRegistry, MovementCostMatrix, StackingRule,
+    GridShape, HexEdgeRegistry, HexGridConfig, HexPosition, HexTile, HexVertex, HexVertexRegistry,
+    InfluenceMap, InfluenceRule, InfluenceRuleRegistry, MovementCostMatrix, StackingRule,
+    StackingViolation, StackingViolations, VertexFeature, ZoneOfControl, ZoneTransition,
 };
 use hexorder_contracts::ontology::{
-    Concept, ConceptBinding, ConceptRegistry, ConceptRole, ConstraintExpr, ConstraintRegistry,
-    ModifyOperation, PropertyBinding, Relation, RelationEffect, RelationRegistry, RelationTrigger,
+    CompareOp, Concept, ConceptBinding, ConceptRegistry, ConceptRole, ConstraintExpr,
+    ConstraintRegistry, ModifyOperation, PresenceEffects, PropertyBinding, Relation,
+    RelationEffect, RelationRegistry, RelationTrigger,
 };
 use hexorder_contracts::persistence::AppScreen;
-use hexorder_contracts::validation::ValidMoveSet;
+use hexorder_contracts::validation::{AnalysisCategory, RuleAnalysis, ValidMoveSet};
 
 /// Creates a minimal headless test app with the `RulesEnginePlugin`.
 fn test_app() -> App {
@@ -32,6 +34,7 @@ fn test_app() -> App {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 3,
+        shape: GridShape::Hexagon,
     });
     app.init_resource::<EntityTypeRegistry>();
     app.init_resource::<SelectedUnit>();
@@ -39,6 +42,7 @@ fn test_app() -> App {
     app.init_resource::<RelationRegistry>();
     app.init_resource::<ConstraintRegistry>();
     app.init_resource::<HexEdgeRegistry>();
+    app.init_resource::<HexVertexRegistry>();
     app.add_plugins(super::RulesEnginePlugin);
     app
 }
@@ -666,13 +670,116 @@ fn free_movement_when_no_constraints() {
     );
 }
 
+/// Without relations or constraints, a stacking limit still keeps units
+/// from ending a move in a full hex, though they may pass through it.
+#[test]
+fn free_movement_respects_stacking_limits() {
+    let mut app = test_app();
+    spawn_hex_grid(&mut app, 3, TypeId::new());
+    app.insert_resource(StackingRule {
+        max_units: 1,
+        ..Default::default()
+    });
+    let unit_type_id = TypeId::new();
+    let data = EntityData {
+        entity_type_id: unit_type_id,
+        properties: HashMap::new(),
+    };
+    spawn_unit(&mut app, 1, 0, data.clone());
+    let unit = spawn_unit(&mut app, 0, 0, data);
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    let full = HexPosition::new(1, 0);
+    assert!(!valid_moves.valid_positions.contains(&full));
+    assert!(
+        valid_moves.blocked_explanations[&full]
+            .iter()
+            .any(|r| r.constraint_name == "Stacking limit")
+    );
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(3, 0))
+    );
+    // Every other in-bounds hex stays reachable: 36 minus the full one.
+    assert_eq!(valid_moves.valid_positions.len(), 35);
+}
+
+/// On a wrapping board the budgeted BFS continues across the east-west seam.
+#[test]
+fn path_budget_crosses_wrap_seam() {
+    let mut app = test_app();
+    let shape = GridShape::Rectangle {
+        width: 10,
+        height: 6,
+        wrap_horizontal: true,
+    };
+    app.world_mut().resource_mut::<HexGridConfig>().shape = shape;
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+
+    let positions = app.world().resource::<HexGridConfig>().positions();
+    for pos in positions {
+        let mut properties = HashMap::new();
+        properties.insert(setup.cost_prop_id, PropertyValue::Int(1));
+        app.world_mut().spawn((
+            HexTile,
+            pos,
+            EntityData {
+                entity_type_id: setup.tile_type_id,
+                properties,
+            },
+        ));
+    }
+
+    let mut unit_props = HashMap::new();
+    unit_props.insert(setup.budget_prop_id, PropertyValue::Int(2));
+    let unit_entity = spawn_unit(
+        &mut app,
+        4,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: unit_props,
+        },
+    );
+
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit_entity);
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(-5, 0))
+    );
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(-4, 0))
+    );
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(-3, 0))
+    );
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(5, 0)),
+        "positions past the seam are normalized, never raw"
+    );
+}
+
 // =========================================================================
 // CRT Resolution Tests (0.9.0)
 // =========================================================================
 
 use hexorder_contracts::mechanics::resolve_crt;
 use hexorder_contracts::mechanics::{
-    CombatModifierDefinition, CombatOutcome, CombatResultsTable, ModifierSource, OutcomeEffect,
+    CombatModifierDefinition, CombatOutcome, CombatResultsTable, CombatRoles, CombatStrengthModel,
+    CrtSelection, LossRules, ModifierSource, OutcomeEffect,
 };
 use hexorder_contracts::simulation::{
     ColumnModifier, ColumnType, ResolutionTable, TableColumn, TableRow, apply_column_shift,
@@ -895,6 +1002,11 @@ fn test_odds_crt() -> CombatResultsTable {
             ],
         ],
         combat_concept_id: None,
+        dice: DicePool::single(6),
+        strength: CombatStrengthModel::default(),
+        losses: LossRules::default(),
+        roles: CombatRoles::default(),
+        selection: CrtSelection::default(),
     }
 }
 
@@ -1169,6 +1281,7 @@ fn modifiers_priority_order() {
             priority: 10,
             cap: None,
             terrain_type_filter: None,
+            condition: None,
         },
         CombatModifierDefinition {
             id: TypeId::new(),
@@ -1178,6 +1291,7 @@ fn modifiers_priority_order() {
             priority: 5,
             cap: None,
             terrain_type_filter: None,
+            condition: None,
         },
     ];
 
@@ -1202,6 +1316,7 @@ fn modifiers_cap_limits_total() {
             priority: 20,
             cap: Some(2), // cap total to [-2, +2]
             terrain_type_filter: None,
+            condition: None,
         },
         CombatModifierDefinition {
             id: TypeId::new(),
@@ -1211,6 +1326,7 @@ fn modifiers_cap_limits_total() {
             priority: 10,
             cap: None,
             terrain_type_filter: None,
+            condition: None,
         },
     ];
 
@@ -1236,6 +1352,7 @@ fn modifiers_clamp_to_column_bounds() {
         priority: 1,
         cap: None,
         terrain_type_filter: None,
+        condition: None,
     }];
 
     let column_modifiers = to_column_modifiers(&modifiers);
@@ -2305,11 +2422,11 @@ fn block_condition_any_expression() {
 }
 
 // ---------------------------------------------------------------------------
-// Block condition: default (e.g., PropertyCompare) falls through to blocked
+// Block condition: PropertyCompare
 // ---------------------------------------------------------------------------
 
 #[test]
-fn block_condition_default_expression_type_blocks() {
+fn block_condition_property_compare_blocks_when_it_holds() {
     let mut app = test_app();
     let setup = setup_motion_ontology(&mut app, 4, 1);
 
@@ -2344,13 +2461,12 @@ fn block_condition_default_expression_type_blocks() {
         });
     }
 
-    // Block with PropertyCompare condition (not IsType/IsNotType/All/Any/Not).
-    // The default case in evaluate_block_condition returns true (blocked).
+    // Block when traveler.budget >= 0, which holds for every unit here.
     {
         let mut relations = app.world_mut().resource_mut::<RelationRegistry>();
         relations.relations.push(Relation {
             id: TypeId::new(),
-            name: "Default blocks".to_string(),
+            name: "Budget check".to_string(),
             concept_id: setup.concept_id,
             subject_role_id: setup.traveler_role_id,
             object_role_id: setup.terrain_role_id,
@@ -2404,7 +2520,341 @@ fn block_condition_default_expression_type_blocks() {
     let valid_moves = app.world().resource::<ValidMoveSet>();
     assert!(
         valid_moves.valid_positions.is_empty(),
-        "PropertyCompare as block condition should default to blocked (conservative)"
+        "PropertyCompare that holds should block"
+    );
+    let reasons = valid_moves
+        .blocked_explanations
+        .get(&HexPosition::new(1, 0))
+        .expect("blocked hex should be explained");
+    assert!(
+        reasons[0].explanation.contains("traveler.budget (4) >= 0"),
+        "explanation should name the deciding sub-expression: {}",
+        reasons[0].explanation
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Block condition: full expression evaluation
+// ---------------------------------------------------------------------------
+
+/// Adds an `OnEnter` block relation with `condition` to the motion concept.
+fn add_block_condition(app: &mut App, setup: &MotionSetup, condition: ConstraintExpr) {
+    app.world_mut()
+        .resource_mut::<RelationRegistry>()
+        .relations
+        .push(Relation {
+            id: TypeId::new(),
+            name: "Conditional block".to_string(),
+            concept_id: setup.concept_id,
+            subject_role_id: setup.traveler_role_id,
+            object_role_id: setup.terrain_role_id,
+            trigger: RelationTrigger::OnEnter,
+            effect: RelationEffect::Block {
+                condition: Some(condition),
+            },
+        });
+}
+
+/// Binds an extra unit property into the traveler role under `local_name`.
+fn bind_unit_property(app: &mut App, setup: &MotionSetup, prop_id: TypeId, local_name: &str) {
+    let mut concepts = app.world_mut().resource_mut::<ConceptRegistry>();
+    let binding = concepts
+        .bindings
+        .iter_mut()
+        .find(|b| b.entity_type_id == setup.unit_type_id)
+        .expect("unit binding");
+    binding.property_bindings.push(PropertyBinding {
+        property_id: prop_id,
+        concept_local_name: local_name.to_string(),
+    });
+}
+
+/// Spawns and selects a unit with the given extra properties and budget.
+fn spawn_selected_unit(
+    app: &mut App,
+    setup: &MotionSetup,
+    budget: i64,
+    extra: Vec<(TypeId, PropertyValue)>,
+) {
+    let mut properties: HashMap<TypeId, PropertyValue> = extra.into_iter().collect();
+    properties.insert(setup.budget_prop_id, PropertyValue::Int(budget));
+    let unit = spawn_unit(
+        app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties,
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+}
+
+#[test]
+fn block_condition_property_compare_allows_when_false() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 2, setup.tile_type_id, setup.cost_prop_id, 1);
+    add_block_condition(
+        &mut app,
+        &setup,
+        ConstraintExpr::PropertyCompare {
+            role_id: setup.traveler_role_id,
+            property_name: "budget".to_string(),
+            operator: CompareOp::Lt,
+            value: PropertyValue::Float(2.5),
+        },
+    );
+    spawn_selected_unit(&mut app, &setup, 4, Vec::new());
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(2, 0)),
+        "budget 4 < 2.5 is false, so nothing should be blocked"
+    );
+}
+
+#[test]
+fn block_condition_cross_compare_uses_traveler_and_terrain() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 2, setup.tile_type_id, setup.cost_prop_id, 3);
+    // Block when entering terrain costs more than the unit's full budget.
+    add_block_condition(
+        &mut app,
+        &setup,
+        ConstraintExpr::CrossCompare {
+            left_role_id: setup.terrain_role_id,
+            left_property: "cost".to_string(),
+            operator: CompareOp::Gt,
+            right_role_id: setup.traveler_role_id,
+            right_property: "budget".to_string(),
+        },
+    );
+    spawn_selected_unit(&mut app, &setup, 2, Vec::new());
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(valid_moves.valid_positions.is_empty());
+    let explanation = &valid_moves
+        .blocked_explanations
+        .get(&HexPosition::new(1, 0))
+        .expect("blocked hex should be explained")
+        .iter()
+        .find(|r| r.constraint_name == "Conditional block")
+        .expect("block reason")
+        .explanation;
+    assert!(
+        explanation.contains("terrain.cost (3) > traveler.budget (2)"),
+        "unexpected explanation: {explanation}"
+    );
+}
+
+#[test]
+fn block_condition_compares_enums_and_bools() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 2, setup.tile_type_id, setup.cost_prop_id, 1);
+    let stance_id = TypeId::new();
+    let routed_id = TypeId::new();
+    bind_unit_property(&mut app, &setup, stance_id, "stance");
+    bind_unit_property(&mut app, &setup, routed_id, "routed");
+    add_block_condition(
+        &mut app,
+        &setup,
+        ConstraintExpr::Any(vec![
+            ConstraintExpr::PropertyCompare {
+                role_id: setup.traveler_role_id,
+                property_name: "stance".to_string(),
+                operator: CompareOp::Eq,
+                value: PropertyValue::Enum("Entrenched".to_string()),
+            },
+            ConstraintExpr::PropertyCompare {
+                role_id: setup.traveler_role_id,
+                property_name: "routed".to_string(),
+                operator: CompareOp::Eq,
+                value: PropertyValue::Bool(true),
+            },
+        ]),
+    );
+    spawn_selected_unit(
+        &mut app,
+        &setup,
+        4,
+        vec![
+            (stance_id, PropertyValue::Enum("Mobile".to_string())),
+            (routed_id, PropertyValue::Bool(false)),
+        ],
+    );
+    app.update();
+    assert!(
+        app.world()
+            .resource::<ValidMoveSet>()
+            .valid_positions
+            .contains(&HexPosition::new(1, 0)),
+        "Mobile, not routed: neither branch holds"
+    );
+
+    // Entrench the unit: the enum branch now holds.
+    let mut units = app
+        .world_mut()
+        .query_filtered::<&mut EntityData, With<UnitInstance>>();
+    for mut data in units.iter_mut(app.world_mut()) {
+        data.properties
+            .insert(stance_id, PropertyValue::Enum("Entrenched".to_string()));
+    }
+    app.world_mut().resource_mut::<SelectedUnit>().set_changed();
+    app.update();
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(valid_moves.valid_positions.is_empty());
+    let explanation = &valid_moves.blocked_explanations[&HexPosition::new(1, 0)]
+        .iter()
+        .find(|r| r.constraint_name == "Conditional block")
+        .expect("block reason")
+        .explanation;
+    assert!(
+        explanation.contains("traveler.stance (Entrenched) == Entrenched"),
+        "unexpected explanation: {explanation}"
+    );
+}
+
+#[test]
+fn block_condition_path_budget_limits_by_bound_property() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let reserve_id = TypeId::new();
+    bind_unit_property(&mut app, &setup, reserve_id, "reserve");
+    // Block once the path would cost more than the unit's reserve.
+    add_block_condition(
+        &mut app,
+        &setup,
+        ConstraintExpr::Not(Box::new(ConstraintExpr::PathBudget {
+            concept_id: setup.concept_id,
+            cost_property: "cost".to_string(),
+            cost_role_id: setup.terrain_role_id,
+            budget_property: "reserve".to_string(),
+            budget_role_id: setup.traveler_role_id,
+        })),
+    );
+    spawn_selected_unit(
+        &mut app,
+        &setup,
+        4,
+        vec![(reserve_id, PropertyValue::Int(2))],
+    );
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(2, 0))
+    );
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(3, 0)),
+        "path cost 3 exceeds reserve 2"
+    );
+    let explanation = &valid_moves.blocked_explanations[&HexPosition::new(3, 0)]
+        .iter()
+        .find(|r| r.constraint_name == "Conditional block")
+        .expect("block reason")
+        .explanation;
+    assert!(
+        explanation.contains("not (path cost 3"),
+        "unexpected explanation: {explanation}"
+    );
+}
+
+#[test]
+fn block_condition_can_reference_crossed_edge_feature() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 1, 1);
+    spawn_hex_grid_with_properties(&mut app, 2, setup.tile_type_id, setup.cost_prop_id, 1);
+
+    // A "barrier" role on the motion concept, filled by edge features.
+    let barrier_role_id = TypeId::new();
+    let wall_type_id = TypeId::new();
+    let height_id = TypeId::new();
+    app.world_mut()
+        .resource_mut::<EntityTypeRegistry>()
+        .types
+        .push(EntityType {
+            id: wall_type_id,
+            name: "Wall".to_string(),
+            role: EntityRole::BoardPosition,
+            color: bevy::color::Color::srgb(0.5, 0.5, 0.5),
+            properties: vec![
+                PropertyDefinition {
+                    id: TypeId::new(),
+                    name: "cost".to_string(),
+                    property_type: PropertyType::Int,
+                    default_value: PropertyValue::Int(0),
+                },
+                PropertyDefinition {
+                    id: height_id,
+                    name: "height".to_string(),
+                    property_type: PropertyType::Int,
+                    default_value: PropertyValue::Int(3),
+                },
+            ],
+        });
+    {
+        let mut concepts = app.world_mut().resource_mut::<ConceptRegistry>();
+        concepts.concepts[0].role_labels.push(ConceptRole {
+            id: barrier_role_id,
+            name: "barrier".to_string(),
+            allowed_entity_roles: vec![EntityRole::BoardPosition],
+        });
+        concepts.bindings.push(ConceptBinding {
+            id: TypeId::new(),
+            entity_type_id: wall_type_id,
+            concept_id: setup.concept_id,
+            concept_role_id: barrier_role_id,
+            property_bindings: vec![PropertyBinding {
+                property_id: height_id,
+                concept_local_name: "height".to_string(),
+            }],
+        });
+    }
+    let edge =
+        HexEdge::between(HexPosition::new(0, 0), HexPosition::new(1, 0)).expect("adjacent hexes");
+    app.world_mut().resource_mut::<HexEdgeRegistry>().insert(
+        edge,
+        EdgeFeature {
+            type_name: "Wall".to_string(),
+        },
+    );
+    add_block_condition(
+        &mut app,
+        &setup,
+        ConstraintExpr::PropertyCompare {
+            role_id: barrier_role_id,
+            property_name: "height".to_string(),
+            operator: CompareOp::Ge,
+            value: PropertyValue::Int(2),
+        },
+    );
+    spawn_selected_unit(&mut app, &setup, 1, Vec::new());
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0)),
+        "crossing the wall should be blocked"
+    );
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(-1, 0)),
+        "no edge feature, so the barrier condition is unset and does not hold"
     );
 }
 
@@ -3564,6 +4014,8 @@ fn influence_increases_movement_cost() {
             entity_type_id: enemy_type_id,
             range: 1,
             cost_modifier: 2,
+            zone: ZoneOfControl::default(),
+            enemy_only: false,
         }],
     });
 
@@ -3670,6 +4122,8 @@ fn self_influence_is_excluded() {
             entity_type_id: setup.unit_type_id,
             range: 1,
             cost_modifier: 10,
+            zone: ZoneOfControl::default(),
+            enemy_only: false,
         }],
     });
 
@@ -3732,6 +4186,8 @@ fn influence_map_is_computed() {
             entity_type_id: enemy_type_id,
             range: 1,
             cost_modifier: 3,
+            zone: ZoneOfControl::default(),
+            enemy_only: false,
         }],
     });
 
@@ -3786,191 +4242,413 @@ fn influence_map_is_computed() {
 }
 
 // ---------------------------------------------------------------------------
-// Stacking constraint tests
+// OnExit and WhilePresent triggers
 // ---------------------------------------------------------------------------
 
-/// When a stacking limit is active and a hex is at capacity,
-/// that hex should be blocked as a move destination.
+/// Adds a relation on the motion concept with the given trigger and effect.
+fn add_motion_relation(
+    app: &mut App,
+    setup: &MotionSetup,
+    trigger: RelationTrigger,
+    effect: RelationEffect,
+) {
+    app.world_mut()
+        .resource_mut::<RelationRegistry>()
+        .relations
+        .push(Relation {
+            id: TypeId::new(),
+            name: "Leaving".to_string(),
+            concept_id: setup.concept_id,
+            subject_role_id: setup.traveler_role_id,
+            object_role_id: setup.terrain_role_id,
+            trigger,
+            effect,
+        });
+}
+
+/// `OnExit` relations charge the tile being left, including the start hex.
 #[test]
-fn stacking_blocks_movement_to_full_hex() {
+fn on_exit_relation_charges_tile_being_left() {
     let mut app = test_app();
-    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let setup = setup_motion_ontology(&mut app, 3, 1);
     spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
-
-    // Set stacking limit to 1.
-    app.insert_resource(StackingRule {
-        max_units: 1,
-        exempt_type_ids: Vec::new(),
-    });
-
-    // Place a blocking unit at (1, 0).
-    spawn_unit(
-        &mut app,
-        1,
-        0,
-        EntityData {
-            entity_type_id: setup.unit_type_id,
-            properties: {
-                let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
-                p
-            },
-        },
-    );
-
-    // Place the selected unit at origin.
-    let unit = spawn_unit(
+    add_motion_relation(
         &mut app,
-        0,
-        0,
-        EntityData {
-            entity_type_id: setup.unit_type_id,
-            properties: {
-                let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
-                p
-            },
+        &setup,
+        RelationTrigger::OnExit,
+        RelationEffect::ModifyProperty {
+            target_property: "budget".to_string(),
+            source_property: "cost".to_string(),
+            operation: ModifyOperation::Subtract,
         },
     );
-    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
-
+    spawn_selected_unit(&mut app, &setup, 3, Vec::new());
     app.update();
 
+    // Each step costs 1 to leave plus 1 to enter: budget 3 reaches one hex.
     let valid_moves = app.world().resource::<ValidMoveSet>();
-    // (1, 0) is at capacity — should be blocked.
     assert!(
-        !valid_moves
+        valid_moves
             .valid_positions
-            .contains(&HexPosition::new(1, 0)),
-        "(1,0) should be blocked by stacking limit"
+            .contains(&HexPosition::new(1, 0))
     );
     assert!(
-        valid_moves
-            .blocked_explanations
-            .contains_key(&HexPosition::new(1, 0)),
-        "should have blocked explanation for (1,0)"
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(2, 0)),
+        "second step would cost 4 in total"
     );
 }
 
-/// When no stacking rule is active, movement is not restricted by unit count.
+/// An `OnExit` block keeps the unit from leaving matching terrain.
 #[test]
-fn no_stacking_rule_allows_all_movement() {
+fn on_exit_block_prevents_leaving() {
     let mut app = test_app();
     let setup = setup_motion_ontology(&mut app, 4, 1);
     spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    add_motion_relation(
+        &mut app,
+        &setup,
+        RelationTrigger::OnExit,
+        RelationEffect::Block { condition: None },
+    );
+    spawn_selected_unit(&mut app, &setup, 4, Vec::new());
+    app.update();
 
-    // Default stacking rule (inactive, max_units=0).
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(valid_moves.valid_positions.is_empty());
+    let explanation = &valid_moves.blocked_explanations[&HexPosition::new(1, 0)][0].explanation;
+    assert!(
+        explanation.contains("cannot leave Plains") && explanation.contains("blocks exit"),
+        "unexpected explanation: {explanation}"
+    );
+}
 
-    // Place a unit at (1, 0).
-    spawn_unit(
+/// Sets up a "Command" concept: a follower gains its leader's bonus while
+/// both share a hex. Returns (follower type, leader type, strength property).
+fn setup_command_ontology(app: &mut App) -> (TypeId, TypeId, TypeId) {
+    let concept_id = TypeId::new();
+    let follower_role = TypeId::new();
+    let leader_role = TypeId::new();
+    let follower_type = TypeId::new();
+    let leader_type = TypeId::new();
+    let strength_id = TypeId::new();
+    let bonus_id = TypeId::new();
+
+    let mut registry = app.world_mut().resource_mut::<EntityTypeRegistry>();
+    for (id, name) in [(follower_type, "Infantry"), (leader_type, "General")] {
+        registry.types.push(EntityType {
+            id,
+            name: name.to_string(),
+            role: EntityRole::Token,
+            color: bevy::color::Color::WHITE,
+            properties: Vec::new(),
+        });
+    }
+
+    let mut concepts = app.world_mut().resource_mut::<ConceptRegistry>();
+    concepts.concepts.push(Concept {
+        id: concept_id,
+        name: "Command".to_string(),
+        description: String::new(),
+        role_labels: vec![
+            ConceptRole {
+                id: follower_role,
+                name: "follower".to_string(),
+                allowed_entity_roles: vec![EntityRole::Token],
+            },
+            ConceptRole {
+                id: leader_role,
+                name: "leader".to_string(),
+                allowed_entity_roles: vec![EntityRole::Token],
+            },
+        ],
+    });
+    for (entity_type_id, role, property_id, name) in [
+        (follower_type, follower_role, strength_id, "strength"),
+        (leader_type, leader_role, bonus_id, "bonus"),
+    ] {
+        concepts.bindings.push(ConceptBinding {
+            id: TypeId::new(),
+            entity_type_id,
+            concept_id,
+            concept_role_id: role,
+            property_bindings: vec![PropertyBinding {
+                property_id,
+                concept_local_name: name.to_string(),
+            }],
+        });
+    }
+
+    app.world_mut()
+        .resource_mut::<RelationRegistry>()
+        .relations
+        .push(Relation {
+            id: TypeId::new(),
+            name: "Leadership".to_string(),
+            concept_id,
+            subject_role_id: follower_role,
+            object_role_id: leader_role,
+            trigger: RelationTrigger::WhilePresent,
+            effect: RelationEffect::ModifyProperty {
+                target_property: "strength".to_string(),
+                source_property: "bonus".to_string(),
+                operation: ModifyOperation::Add,
+            },
+        });
+
+    app.world_mut().spawn((
+        UnitInstance,
+        HexPosition::new(0, 0),
+        EntityData {
+            entity_type_id: leader_type,
+            properties: HashMap::from([(bonus_id, PropertyValue::Int(2))]),
+        },
+    ));
+    (follower_type, leader_type, strength_id)
+}
+
+/// A co-located leader boosts the follower; the boost is reverted when the
+/// follower moves away.
+#[test]
+fn while_present_applies_and_reverts_with_co_location() {
+    let mut app = test_app();
+    let (follower_type, _, strength_id) = setup_command_ontology(&mut app);
+    let follower = spawn_unit(
         &mut app,
-        1,
+        0,
         0,
         EntityData {
-            entity_type_id: setup.unit_type_id,
-            properties: {
-                let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
-                p
-            },
+            entity_type_id: follower_type,
+            properties: HashMap::from([(strength_id, PropertyValue::Int(3))]),
         },
     );
+    app.update();
+
+    let strength = |app: &App| {
+        app.world()
+            .get::<EntityData>(follower)
+            .expect("follower data")
+            .properties[&strength_id]
+            .clone()
+    };
+    assert_eq!(strength(&app), PropertyValue::Int(5));
+    assert_eq!(
+        app.world()
+            .get::<PresenceEffects>(follower)
+            .expect("effects recorded")
+            .applied
+            .len(),
+        1
+    );
 
-    // Place the selected unit at origin.
-    let unit = spawn_unit(
+    *app.world_mut()
+        .get_mut::<HexPosition>(follower)
+        .expect("follower position") = HexPosition::new(1, 0);
+    app.update();
+    assert_eq!(strength(&app), PropertyValue::Int(3));
+    assert!(
+        app.world()
+            .get::<PresenceEffects>(follower)
+            .expect("effects component")
+            .applied
+            .is_empty()
+    );
+}
+
+/// Effects are reverted when the source unit is removed, and never stack
+/// across recomputations.
+#[test]
+fn while_present_reverts_when_source_removed() {
+    let mut app = test_app();
+    let (follower_type, leader_type, strength_id) = setup_command_ontology(&mut app);
+    let follower = spawn_unit(
         &mut app,
         0,
         0,
         EntityData {
-            entity_type_id: setup.unit_type_id,
-            properties: {
-                let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
-                p
-            },
+            entity_type_id: follower_type,
+            properties: HashMap::from([(strength_id, PropertyValue::Int(3))]),
         },
     );
-    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
-
     app.update();
-
-    let valid_moves = app.world().resource::<ValidMoveSet>();
-    // No stacking rule — (1, 0) should be valid.
-    assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(1, 0)),
-        "(1,0) should be reachable with no stacking limit"
+    // Recompute without any change in presence: still a single bonus.
+    app.world_mut()
+        .resource_mut::<RelationRegistry>()
+        .set_changed();
+    app.update();
+    assert_eq!(
+        app.world()
+            .get::<EntityData>(follower)
+            .expect("data")
+            .properties[&strength_id],
+        PropertyValue::Int(5)
+    );
+
+    let leader = app
+        .world_mut()
+        .query::<(Entity, &EntityData)>()
+        .iter(app.world())
+        .find(|(_, d)| d.entity_type_id == leader_type)
+        .map(|(e, _)| e)
+        .expect("leader");
+    app.world_mut().despawn(leader);
+    app.update();
+    assert_eq!(
+        app.world()
+            .get::<EntityData>(follower)
+            .expect("data")
+            .properties[&strength_id],
+        PropertyValue::Int(3)
     );
 }
 
-/// Exempt types can move to a full hex.
+/// `WhilePresent` relations whose object is terrain apply to units on it.
 #[test]
-fn stacking_exempt_type_can_enter_full_hex() {
+fn while_present_applies_terrain_effects() {
     let mut app = test_app();
-    let setup = setup_motion_ontology(&mut app, 4, 1);
-    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let setup = setup_motion_ontology(&mut app, 4, 2);
+    spawn_hex_grid_with_properties(&mut app, 1, setup.tile_type_id, setup.cost_prop_id, 2);
+    // While on terrain, budget is capped by the terrain cost.
+    add_motion_relation(
+        &mut app,
+        &setup,
+        RelationTrigger::WhilePresent,
+        RelationEffect::ModifyProperty {
+            target_property: "budget".to_string(),
+            source_property: "cost".to_string(),
+            operation: ModifyOperation::Min,
+        },
+    );
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: HashMap::from([(setup.budget_prop_id, PropertyValue::Int(4))]),
+        },
+    );
+    app.update();
+    assert_eq!(
+        app.world()
+            .get::<EntityData>(unit)
+            .expect("data")
+            .properties[&setup.budget_prop_id],
+        PropertyValue::Int(2)
+    );
+}
 
-    let exempt_type_id = TypeId::new();
-    app.insert_resource(StackingRule {
-        max_units: 1,
-        exempt_type_ids: vec![exempt_type_id],
-    });
+// ---------------------------------------------------------------------------
+// Vertex feature tests
+// ---------------------------------------------------------------------------
 
-    // Register the exempt type.
+/// Registers a vertex feature type and places it at the corner shared by
+/// (0,0), (1,0) and (1,-1).
+fn place_corner_feature(app: &mut App, name: &str, cost: Option<i64>) -> TypeId {
+    let type_id = TypeId::new();
     app.world_mut()
         .resource_mut::<EntityTypeRegistry>()
         .types
-        .push(hexorder_contracts::game_system::EntityType {
-            id: exempt_type_id,
-            name: "Marker".to_string(),
-            role: hexorder_contracts::game_system::EntityRole::Token,
-            color: bevy::color::Color::WHITE,
-            properties: vec![hexorder_contracts::game_system::PropertyDefinition {
-                id: setup.budget_prop_id,
-                name: "MP".to_string(),
-                property_type: hexorder_contracts::game_system::PropertyType::Int,
-                default_value: PropertyValue::Int(0),
-            }],
+        .push(EntityType {
+            id: type_id,
+            name: name.to_string(),
+            role: EntityRole::BoardPosition,
+            color: bevy::color::Color::srgb(0.9, 0.7, 0.2),
+            properties: cost
+                .map(|c| PropertyDefinition {
+                    id: TypeId::new(),
+                    name: "cost".to_string(),
+                    property_type: PropertyType::Int,
+                    default_value: PropertyValue::Int(c),
+                })
+                .into_iter()
+                .collect(),
         });
+    let vertex = HexVertex::between(
+        HexPosition::new(0, 0),
+        HexPosition::new(1, 0),
+        HexPosition::new(1, -1),
+    )
+    .expect("mutually adjacent hexes");
+    app.world_mut().resource_mut::<HexVertexRegistry>().insert(
+        vertex,
+        VertexFeature {
+            type_name: name.to_string(),
+        },
+    );
+    type_id
+}
 
-    // Bind exempt type to the motion concept.
-    app.world_mut()
-        .resource_mut::<ConceptRegistry>()
-        .bindings
-        .push(hexorder_contracts::ontology::ConceptBinding {
-            id: TypeId::new(),
-            entity_type_id: exempt_type_id,
-            concept_id: setup.concept_id,
-            concept_role_id: setup.traveler_role_id,
-            property_bindings: Vec::new(),
-        });
+/// A vertex feature with a "cost" property charges it for entering any of
+/// the three hexes meeting at the vertex.
+#[test]
+fn vertex_cost_applies_to_hexes_meeting_at_corner() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    place_corner_feature(&mut app, "Fortress", Some(5));
 
-    // Place a normal unit at (1, 0) to fill it.
-    spawn_unit(
+    let unit = spawn_unit(
         &mut app,
-        1,
+        0,
         0,
         EntityData {
             entity_type_id: setup.unit_type_id,
             properties: {
                 let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p.insert(setup.budget_prop_id, PropertyValue::Int(2));
                 p
             },
         },
     );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+
+    // (1,0) and (1,-1) touch the fortress corner: 1 + 5 > 2.
+    for pos in [HexPosition::new(1, 0), HexPosition::new(1, -1)] {
+        assert!(
+            !valid_moves.valid_positions.contains(&pos),
+            "{pos:?} touches the fortress vertex and should be too expensive"
+        );
+    }
+    // (-1,0) does not touch it and costs terrain only.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(-1, 0))
+    );
+    let reasons = valid_moves
+        .blocked_explanations
+        .get(&HexPosition::new(1, 0))
+        .expect("blocked hex should be explained");
+    assert!(
+        reasons
+            .iter()
+            .any(|r| r.constraint_name == "Fortress vertex"),
+        "explanation should name the vertex feature"
+    );
+}
+
+/// A vertex feature without a "cost" property is a marker and costs nothing.
+#[test]
+fn vertex_without_cost_property_is_free() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 1, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    place_corner_feature(&mut app, "Town", None);
 
-    // Place an exempt unit at origin.
     let unit = spawn_unit(
         &mut app,
         0,
         0,
         EntityData {
-            entity_type_id: exempt_type_id,
+            entity_type_id: setup.unit_type_id,
             properties: {
                 let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p.insert(setup.budget_prop_id, PropertyValue::Int(1));
                 p
             },
         },
@@ -3978,52 +4656,35 @@ fn stacking_exempt_type_can_enter_full_hex() {
     app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
 
     app.update();
-
     let valid_moves = app.world().resource::<ValidMoveSet>();
-    // Exempt type should be able to enter the full hex.
     assert!(
         valid_moves
             .valid_positions
-            .contains(&HexPosition::new(1, 0)),
-        "(1,0) should be reachable for exempt type despite stacking limit"
+            .contains(&HexPosition::new(1, 0))
     );
 }
 
-/// Movement cost matrix overrides terrain cost per classification.
+/// A vertex feature whose type has an influence rule radiates from the
+/// vertex: its three hexes are range 1, and it also affects a unit standing
+/// in one of them.
 #[test]
-fn movement_cost_matrix_overrides_terrain_cost() {
+fn vertex_feature_radiates_influence() {
     let mut app = test_app();
-    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let setup = setup_motion_ontology(&mut app, 3, 1);
     spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let fort_type_id = place_corner_feature(&mut app, "Fort", None);
+    app.insert_resource(InfluenceRuleRegistry {
+        rules: vec![InfluenceRule {
+            id: TypeId::new(),
+            entity_type_id: fort_type_id,
+            range: 1,
+            cost_modifier: 3,
+            zone: ZoneOfControl::default(),
+            enemy_only: false,
+        }],
+    });
 
-    // Add a classification enum property to the unit type.
-    let class_prop_id = TypeId::new();
-    let enum_id = TypeId::new();
-    app.world_mut()
-        .resource_mut::<EntityTypeRegistry>()
-        .types
-        .iter_mut()
-        .find(|t| t.id == setup.unit_type_id)
-        .expect("unit type exists")
-        .properties
-        .push(PropertyDefinition {
-            id: class_prop_id,
-            name: "Movement Mode".to_string(),
-            property_type: PropertyType::Enum(enum_id),
-            default_value: PropertyValue::Enum("Wheeled".to_string()),
-        });
-
-    // Set up the matrix: Wheeled costs 3 for this terrain type.
-    app.insert_resource(MovementCostMatrix {
-        classification_property_id: Some(class_prop_id),
-        entries: {
-            let mut m = HashMap::new();
-            m.insert((setup.tile_type_id, "Wheeled".to_string()), 3);
-            m
-        },
-    });
-
-    // Spawn a unit with classification "Wheeled" and budget 4.
+    // Unit stands in (0,0), one of the fort's hexes.
     let unit = spawn_unit(
         &mut app,
         0,
@@ -4032,8 +4693,7 @@ fn movement_cost_matrix_overrides_terrain_cost() {
             entity_type_id: setup.unit_type_id,
             properties: {
                 let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
-                p.insert(class_prop_id, PropertyValue::Enum("Wheeled".to_string()));
+                p.insert(setup.budget_prop_id, PropertyValue::Int(3));
                 p
             },
         },
@@ -4041,660 +4701,4324 @@ fn movement_cost_matrix_overrides_terrain_cost() {
     app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
 
     app.update();
+    let influence_map = app.world().resource::<InfluenceMap>();
+    for pos in [
+        HexPosition::new(0, 0),
+        HexPosition::new(1, 0),
+        HexPosition::new(1, -1),
+    ] {
+        let entries = influence_map.get(pos).expect("vertex hex influenced");
+        assert!(entries.iter().all(|e| e.source_vertex.is_some()));
+    }
+    assert!(
+        influence_map.get(HexPosition::new(-1, 0)).is_none(),
+        "range 1 reaches only the hexes meeting at the vertex"
+    );
 
     let valid_moves = app.world().resource::<ValidMoveSet>();
-    // With matrix cost 3 and budget 4, unit can reach adjacent hexes (cost 3)
-    // but not two hexes away (cost 6 > 4).
+    // Entering (1,0): 1 + 3 > 3, even though the unit stands in the zone.
     assert!(
-        valid_moves
+        !valid_moves
             .valid_positions
-            .contains(&HexPosition::new(1, 0)),
-        "(1,0) should be reachable at cost 3"
+            .contains(&HexPosition::new(1, 0))
     );
     assert!(
-        !valid_moves
+        valid_moves
             .valid_positions
-            .contains(&HexPosition::new(2, 0)),
-        "(2,0) should NOT be reachable at cost 6"
+            .contains(&HexPosition::new(-1, 0))
     );
 }
 
-/// When the matrix is inactive, standard terrain cost is used.
+// ---------------------------------------------------------------------------
+// Zone of control tests
+// ---------------------------------------------------------------------------
+
+/// Registers an "Enemy" token type with a range-1 influence rule using
+/// `zone`, and places one at `(q, r)`. Returns the enemy type ID.
+fn place_enemy_zone(app: &mut App, q: i32, r: i32, cost: i64, zone: ZoneOfControl) -> TypeId {
+    let enemy_type_id = TypeId::new();
+    app.world_mut()
+        .resource_mut::<EntityTypeRegistry>()
+        .types
+        .push(EntityType {
+            id: enemy_type_id,
+            name: "Enemy".to_string(),
+            role: EntityRole::Token,
+            color: bevy::color::Color::srgb(1.0, 0.0, 0.0),
+            properties: Vec::new(),
+        });
+    app.insert_resource(InfluenceRuleRegistry {
+        rules: vec![InfluenceRule {
+            id: TypeId::new(),
+            entity_type_id: enemy_type_id,
+            range: 1,
+            cost_modifier: cost,
+            zone,
+            enemy_only: false,
+        }],
+    });
+    spawn_unit(
+        app,
+        q,
+        r,
+        EntityData {
+            entity_type_id: enemy_type_id,
+            properties: HashMap::new(),
+        },
+    );
+    enemy_type_id
+}
+
+fn blocked_text(app: &App, q: i32, r: i32) -> String {
+    app.world()
+        .resource::<ValidMoveSet>()
+        .blocked_explanations
+        .get(&HexPosition::new(q, r))
+        .map(|reasons| {
+            reasons
+                .iter()
+                .map(|r| r.explanation.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        })
+        .unwrap_or_default()
+}
+
 #[test]
-fn no_matrix_uses_standard_terrain_cost() {
+fn zone_stop_on_enter_ends_movement() {
     let mut app = test_app();
     let setup = setup_motion_ontology(&mut app, 4, 1);
     spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
-
-    // Matrix is default (inactive).
-    // Standard terrain cost is 1, budget is 4 → can reach up to 4 hexes away.
-    let unit = spawn_unit(
+    place_enemy_zone(
         &mut app,
+        3,
         0,
         0,
-        EntityData {
-            entity_type_id: setup.unit_type_id,
-            properties: {
-                let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
-                p
-            },
+        ZoneOfControl {
+            stop_on_enter: true,
+            ..ZoneOfControl::default()
         },
     );
-    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
-
+    spawn_selected_unit(&mut app, &setup, 4, Vec::new());
     app.update();
 
     let valid_moves = app.world().resource::<ValidMoveSet>();
-    // With cost 1 and budget 4, should reach (2,0) (cost 2).
     assert!(
         valid_moves
             .valid_positions
             .contains(&HexPosition::new(2, 0)),
-        "(2,0) should be reachable with standard cost 1 and budget 4"
+        "entering the zone is allowed"
+    );
+    // Every neighbor of the enemy hex is in its zone, so it cannot be reached.
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(3, 0))
+    );
+    let text = blocked_text(&app, 3, 0);
+    assert!(
+        text.contains("must stop at") && text.contains("Enemy zone of control"),
+        "unexpected explanation: {text}"
     );
 }
 
-/// Matrix falls back to default cost when no entry exists for a classification.
 #[test]
-fn matrix_fallback_when_no_entry_for_classification() {
+fn zone_exit_cost_applies_when_leaving() {
     let mut app = test_app();
-    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let setup = setup_motion_ontology(&mut app, 2, 1);
     spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
-
-    let class_prop_id = TypeId::new();
-    let enum_id = TypeId::new();
-    app.world_mut()
-        .resource_mut::<EntityTypeRegistry>()
-        .types
-        .iter_mut()
-        .find(|t| t.id == setup.unit_type_id)
-        .expect("unit type exists")
-        .properties
-        .push(PropertyDefinition {
-            id: class_prop_id,
-            name: "Movement Mode".to_string(),
-            property_type: PropertyType::Enum(enum_id),
-            default_value: PropertyValue::Enum("Foot".to_string()),
-        });
-
-    // Matrix has entries for "Wheeled" but NOT "Foot".
-    app.insert_resource(MovementCostMatrix {
-        classification_property_id: Some(class_prop_id),
-        entries: {
-            let mut m = HashMap::new();
-            m.insert((setup.tile_type_id, "Wheeled".to_string()), 3);
-            m
+    place_enemy_zone(
+        &mut app,
+        1,
+        0,
+        0,
+        ZoneOfControl {
+            exit_cost: 2,
+            ..ZoneOfControl::default()
         },
-    });
+    );
+    // The unit starts next to the enemy, inside its zone.
+    spawn_selected_unit(&mut app, &setup, 2, Vec::new());
+    app.update();
 
-    // Spawn a unit with classification "Foot" (no matrix entry → falls back to cost=1).
-    let unit = spawn_unit(
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(-1, 0)),
+        "terrain 1 + exit 2 exceeds budget 2"
+    );
+    let text = blocked_text(&app, -1, 0);
+    assert!(
+        text.contains("leaving Enemy zone of control costs 2"),
+        "unexpected explanation: {text}"
+    );
+}
+
+#[test]
+fn zone_to_zone_move_can_be_forbidden() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 1, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    place_enemy_zone(
         &mut app,
+        1,
         0,
         0,
-        EntityData {
-            entity_type_id: setup.unit_type_id,
-            properties: {
-                let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
-                p.insert(class_prop_id, PropertyValue::Enum("Foot".to_string()));
-                p
-            },
+        ZoneOfControl {
+            transition: ZoneTransition::Forbidden,
+            ..ZoneOfControl::default()
         },
     );
-    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
-
+    spawn_selected_unit(&mut app, &setup, 1, Vec::new());
     app.update();
 
     let valid_moves = app.world().resource::<ValidMoveSet>();
-    // Falls back to standard cost 1, budget 4 → can reach (2,0).
+    // (0,1) is also adjacent to the enemy: zone to zone.
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(0, 1))
+    );
     assert!(
         valid_moves
             .valid_positions
-            .contains(&HexPosition::new(2, 0)),
-        "(2,0) should be reachable with fallback cost 1 and budget 4"
+            .contains(&HexPosition::new(-1, 0)),
+        "leaving the zone for open ground is fine"
+    );
+    let text = blocked_text(&app, 0, 1);
+    assert!(
+        text.contains("both hexes are in Enemy zone of control"),
+        "unexpected explanation: {text}"
     );
 }
 
-// =========================================================================
-// Canary Validation: Traces of Victory (SC-5)
-// =========================================================================
-//
-// End-to-end integration test exercising all four spatial primitives
-// together with Traces-of-Victory-like data:
-//   1. Hex-side edge features (river crossing cost)
-//   2. Spatial influence (ZOC: +2 MP to enter adjacent enemy hexes)
-//   3. Stacking constraint (max 2 combat units per hex)
-//   4. Movement cost matrix (terrain × classification costs)
-//
-// Grid layout (radius 3, pointy-top):
-//
-//       (-1,0)  (0,-1)
-//          \   /
-//   (-1,1)-(0,0)-(1,-1)
-//          /   \
-//        (0,1)  (1,0)-(2,-1)-(3,-1)
-//                 |
-//               (1,1)-(2,0)-(3,0)
-//                       |
-//                     (2,1)
-//
-// Scenario:
-//   - Terrain: Plains (cost 1) everywhere, Forest at (1,0) (cost 2 for Foot)
-//   - River edge between (0,0) and (0,-1) — crossing cost +1
-//   - Enemy unit at (2,0) projects ZOC range 1, cost +2
-//   - Stacking: max 2 per hex; two allies already at (0,1)
-//   - Infantry (Foot, 4 MP) at origin
-//
-// Expected reachability from (0,0) with budget 4:
-//   - (1,0) Forest: matrix cost 2 → reachable (budget 4-2=2)
-//   - (0,-1): terrain 1 + river 1 = 2 → reachable (budget 4-2=2)
-//   - (1,-1): influenced by enemy at (2,0), terrain 1 + ZOC 2 = 3 → reachable (budget 4-3=1)
-//   - (0,1): stacking full (2/2) → blocked
-//   - (3,0): adjacent to enemy, from (2,0) which is also adjacent → unreachable (budget exhausted)
-
-/// Spawn a single hex tile with the given entity type and optional property.
-fn spawn_tile_at(
-    app: &mut App,
-    q: i32,
-    r: i32,
-    tile_type_id: TypeId,
-    prop_id: Option<TypeId>,
-    prop_value: Option<i64>,
-) {
-    let mut properties = HashMap::new();
-    if let (Some(pid), Some(val)) = (prop_id, prop_value) {
-        properties.insert(pid, PropertyValue::Int(val));
-    }
-    app.world_mut().spawn((
-        HexTile,
-        HexPosition::new(q, r),
+#[test]
+fn zone_negated_by_friendly_unit_in_hex() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    place_enemy_zone(
+        &mut app,
+        2,
+        0,
+        5,
+        ZoneOfControl {
+            negated_by: vec![setup.unit_type_id],
+            ..ZoneOfControl::default()
+        },
+    );
+    // A friendly unit holds (1,0), cancelling the enemy zone there.
+    spawn_unit(
+        &mut app,
+        1,
+        0,
         EntityData {
-            entity_type_id: tile_type_id,
-            properties,
+            entity_type_id: setup.unit_type_id,
+            properties: HashMap::new(),
         },
-    ));
+    );
+    spawn_selected_unit(&mut app, &setup, 2, Vec::new());
+    app.update();
+
+    let influence_map = app.world().resource::<InfluenceMap>();
+    assert!(influence_map.get(HexPosition::new(1, 0)).is_none());
+    assert!(influence_map.get(HexPosition::new(1, 1)).is_some());
+    assert!(
+        app.world()
+            .resource::<ValidMoveSet>()
+            .valid_positions
+            .contains(&HexPosition::new(1, 0))
+    );
 }
 
-/// Canary: all four spatial primitives interact correctly in a single BFS.
+/// A zone negated by friendly units is cancelled for the mover where a unit
+/// of its faction stands, but not where an enemy unit of the same type does.
 #[test]
-fn canary_tov_all_primitives_combined() {
-    use hexorder_contracts::hex_grid::{EdgeFeature, HexEdge};
-
+fn zone_negated_by_friendly_faction_not_by_type() {
     let mut app = test_app();
-
-    // -- Entity type IDs --
-    let plains_type_id = TypeId::new();
-    let forest_type_id = TypeId::new();
-    let infantry_type_id = TypeId::new();
-    let enemy_type_id = TypeId::new();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    place_enemy_zone(
+        &mut app,
+        2,
+        0,
+        5,
+        ZoneOfControl {
+            negated_by_friendly: true,
+            ..ZoneOfControl::default()
+        },
+    );
+    let (blue, red) = (TypeId::new(), TypeId::new());
+    spawn_owned_unit(&mut app, &setup, (1, 0), setup.unit_type_id, blue);
+    spawn_owned_unit(&mut app, &setup, (1, 1), setup.unit_type_id, red);
+    let mover = spawn_owned_unit(&mut app, &setup, (0, 0), setup.unit_type_id, blue);
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(mover);
+    app.update();
+
+    // The shared map keeps the zone; only the mover's step ignores it.
+    let influence_map = app.world().resource::<InfluenceMap>();
+    assert!(influence_map.get(HexPosition::new(1, 0)).is_some());
+    let valid = &app.world().resource::<ValidMoveSet>().valid_positions;
+    assert!(valid.contains(&HexPosition::new(1, 0)));
+    assert!(!valid.contains(&HexPosition::new(1, 1)));
+}
+
+#[test]
+fn zone_does_not_cross_blocking_edges_or_enter_excluded_terrain() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+
+    let river_type_id = TypeId::new();
+    let marsh_type_id = TypeId::new();
+    {
+        let mut registry = app.world_mut().resource_mut::<EntityTypeRegistry>();
+        for (id, name) in [(river_type_id, "River"), (marsh_type_id, "Marsh")] {
+            registry.types.push(EntityType {
+                id,
+                name: name.to_string(),
+                role: EntityRole::BoardPosition,
+                color: bevy::color::Color::srgb(0.2, 0.4, 0.8),
+                properties: Vec::new(),
+            });
+        }
+    }
+    let edge =
+        HexEdge::between(HexPosition::new(1, 0), HexPosition::new(2, 0)).expect("adjacent hexes");
+    app.world_mut().resource_mut::<HexEdgeRegistry>().insert(
+        edge,
+        EdgeFeature {
+            type_name: "River".to_string(),
+        },
+    );
+    let mut marsh = app.world_mut().query::<(&HexPosition, &mut EntityData)>();
+    for (pos, mut data) in marsh.iter_mut(app.world_mut()) {
+        if *pos == HexPosition::new(2, -1) {
+            data.entity_type_id = marsh_type_id;
+        }
+    }
+
+    place_enemy_zone(
+        &mut app,
+        2,
+        0,
+        3,
+        ZoneOfControl {
+            blocking_edge_types: vec![river_type_id],
+            excluded_terrain: vec![marsh_type_id],
+            ..ZoneOfControl::default()
+        },
+    );
+    spawn_selected_unit(&mut app, &setup, 2, Vec::new());
+    app.update();
+
+    let influence_map = app.world().resource::<InfluenceMap>();
+    assert!(
+        influence_map.get(HexPosition::new(1, 0)).is_none(),
+        "the river stops the zone"
+    );
+    assert!(
+        influence_map.get(HexPosition::new(2, -1)).is_none(),
+        "the zone does not extend into marsh"
+    );
+    assert!(influence_map.get(HexPosition::new(1, 1)).is_some());
+}
+
+/// A 10-wide wrapping board of cost-1 tiles with a "River" edge, costing 9
+/// to cross, on the seam between (4, 0) and (-5, 0). Returns the river type.
+fn seam_river_app() -> (App, MotionSetup, TypeId) {
+    let mut app = test_app();
+    app.world_mut().resource_mut::<HexGridConfig>().shape = GridShape::Rectangle {
+        width: 10,
+        height: 6,
+        wrap_horizontal: true,
+    };
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+    let positions = app.world().resource::<HexGridConfig>().positions();
+    for pos in positions {
+        app.world_mut().spawn((
+            HexTile,
+            pos,
+            EntityData {
+                entity_type_id: setup.tile_type_id,
+                properties: HashMap::from([(setup.cost_prop_id, PropertyValue::Int(1))]),
+            },
+        ));
+    }
     let river_type_id = TypeId::new();
+    app.world_mut()
+        .resource_mut::<EntityTypeRegistry>()
+        .types
+        .push(EntityType {
+            id: river_type_id,
+            name: "River".to_string(),
+            role: EntityRole::BoardPosition,
+            color: bevy::color::Color::srgb(0.2, 0.4, 0.8),
+            properties: vec![PropertyDefinition {
+                id: TypeId::new(),
+                name: "cost".to_string(),
+                property_type: PropertyType::Int,
+                default_value: PropertyValue::Int(9),
+            }],
+        });
+    let edge = app
+        .world()
+        .resource::<HexGridConfig>()
+        .normalize_edge(HexPosition::new(4, 0), HexPosition::new(-5, 0))
+        .expect("seam neighbours");
+    app.world_mut().resource_mut::<HexEdgeRegistry>().insert(
+        edge,
+        EdgeFeature {
+            type_name: "River".to_string(),
+        },
+    );
+    (app, setup, river_type_id)
+}
+
+#[test]
+fn seam_river_blocks_movement_and_zones_from_both_sides() {
+    let east = HexPosition::new(4, 0);
+    let west = HexPosition::new(-5, 0);
+    for (from, to) in [(east, west), (west, east)] {
+        let (mut app, setup, _) = seam_river_app();
+        let unit = spawn_unit(
+            &mut app,
+            from.q,
+            from.r,
+            EntityData {
+                entity_type_id: setup.unit_type_id,
+                properties: HashMap::from([(setup.budget_prop_id, PropertyValue::Int(1))]),
+            },
+        );
+        app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+        app.update();
+        let valid_moves = app.world().resource::<ValidMoveSet>();
+        assert!(
+            !valid_moves.valid_positions.contains(&to),
+            "the river stops {from:?} -> {to:?}"
+        );
+        assert!(!valid_moves.valid_positions.is_empty());
+
+        let (mut app, setup, river_type_id) = seam_river_app();
+        place_enemy_zone(
+            &mut app,
+            from.q,
+            from.r,
+            3,
+            ZoneOfControl {
+                blocking_edge_types: vec![river_type_id],
+                ..ZoneOfControl::default()
+            },
+        );
+        spawn_selected_unit(&mut app, &setup, 2, Vec::new());
+        app.update();
+        let config = app.world().resource::<HexGridConfig>();
+        let influence_map = app.world().resource::<InfluenceMap>();
+        assert!(
+            influence_map.get(to).is_none(),
+            "the river stops the zone of {from:?}"
+        );
+        for neighbor in config.neighbors(from) {
+            assert_eq!(
+                influence_map.get(neighbor).is_some(),
+                neighbor != to,
+                "zone of {from:?} at {neighbor:?}"
+            );
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Stacking constraint tests
+// ---------------------------------------------------------------------------
+
+/// When a stacking limit is active and a hex is at capacity,
+/// that hex should be blocked as a move destination.
+#[test]
+fn stacking_blocks_movement_to_full_hex() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+
+    // Set stacking limit to 1.
+    app.insert_resource(StackingRule {
+        max_units: 1,
+        exempt_type_ids: Vec::new(),
+        ..Default::default()
+    });
+
+    // Place a blocking unit at (1, 0).
+    spawn_unit(
+        &mut app,
+        1,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p
+            },
+        },
+    );
+
+    // Place the selected unit at origin.
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p
+            },
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    // (1, 0) is at capacity — should be blocked.
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0)),
+        "(1,0) should be blocked by stacking limit"
+    );
+    assert!(
+        valid_moves
+            .blocked_explanations
+            .contains_key(&HexPosition::new(1, 0)),
+        "should have blocked explanation for (1,0)"
+    );
+}
+
+/// When no stacking rule is active, movement is not restricted by unit count.
+#[test]
+fn no_stacking_rule_allows_all_movement() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+
+    // Default stacking rule (inactive, max_units=0).
+
+    // Place a unit at (1, 0).
+    spawn_unit(
+        &mut app,
+        1,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p
+            },
+        },
+    );
+
+    // Place the selected unit at origin.
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p
+            },
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    // No stacking rule — (1, 0) should be valid.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0)),
+        "(1,0) should be reachable with no stacking limit"
+    );
+}
+
+/// Exempt types can move to a full hex.
+#[test]
+fn stacking_exempt_type_can_enter_full_hex() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+
+    let exempt_type_id = TypeId::new();
+    app.insert_resource(StackingRule {
+        max_units: 1,
+        exempt_type_ids: vec![exempt_type_id],
+        ..Default::default()
+    });
+
+    // Register the exempt type.
+    app.world_mut()
+        .resource_mut::<EntityTypeRegistry>()
+        .types
+        .push(hexorder_contracts::game_system::EntityType {
+            id: exempt_type_id,
+            name: "Marker".to_string(),
+            role: hexorder_contracts::game_system::EntityRole::Token,
+            color: bevy::color::Color::WHITE,
+            properties: vec![hexorder_contracts::game_system::PropertyDefinition {
+                id: setup.budget_prop_id,
+                name: "MP".to_string(),
+                property_type: hexorder_contracts::game_system::PropertyType::Int,
+                default_value: PropertyValue::Int(0),
+            }],
+        });
+
+    // Bind exempt type to the motion concept.
+    app.world_mut()
+        .resource_mut::<ConceptRegistry>()
+        .bindings
+        .push(hexorder_contracts::ontology::ConceptBinding {
+            id: TypeId::new(),
+            entity_type_id: exempt_type_id,
+            concept_id: setup.concept_id,
+            concept_role_id: setup.traveler_role_id,
+            property_bindings: Vec::new(),
+        });
+
+    // Place a normal unit at (1, 0) to fill it.
+    spawn_unit(
+        &mut app,
+        1,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p
+            },
+        },
+    );
+
+    // Place an exempt unit at origin.
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: exempt_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p
+            },
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    // Exempt type should be able to enter the full hex.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0)),
+        "(1,0) should be reachable for exempt type despite stacking limit"
+    );
+}
+
+/// Builds traveler data with a movement budget and optional extra properties.
+fn traveler_data(setup: &MotionSetup, budget: i64, extra: &[(TypeId, i64)]) -> EntityData {
+    let mut properties = HashMap::new();
+    properties.insert(setup.budget_prop_id, PropertyValue::Int(budget));
+    for (id, value) in extra {
+        properties.insert(*id, PropertyValue::Int(*value));
+    }
+    EntityData {
+        entity_type_id: setup.unit_type_id,
+        properties,
+    }
+}
+
+/// A full hex can be moved through even though it cannot be the destination.
+#[test]
+fn stacking_allows_pass_through_full_hex() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    app.insert_resource(StackingRule {
+        max_units: 1,
+        ..Default::default()
+    });
+
+    spawn_unit(&mut app, 1, 0, traveler_data(&setup, 2, &[]));
+    let unit = spawn_unit(&mut app, 0, 0, traveler_data(&setup, 2, &[]));
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0))
+    );
+    // (2, 0) is two steps away and only reachable through (1, 0).
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(2, 0)),
+        "(2,0) should be reachable by passing through the full hex"
+    );
+    assert_eq!(
+        valid_moves.route(HexPosition::new(2, 0)),
+        Some(vec![
+            HexPosition::new(0, 0),
+            HexPosition::new(1, 0),
+            HexPosition::new(2, 0),
+        ])
+    );
+}
+
+/// A per-terrain limit caps stacking even when no global limit is set.
+#[test]
+fn stacking_terrain_limit_blocks_entry() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    app.insert_resource(StackingRule {
+        terrain_limits: HashMap::from([(setup.tile_type_id, 1)]),
+        ..Default::default()
+    });
+
+    spawn_unit(&mut app, 1, 0, traveler_data(&setup, 4, &[]));
+    let unit = spawn_unit(&mut app, 0, 0, traveler_data(&setup, 4, &[]));
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0))
+    );
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(0, 1))
+    );
+}
+
+/// Units count by their stacking points property rather than one each.
+#[test]
+fn stacking_counts_points_property() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let size_prop_id = TypeId::new();
+    app.insert_resource(StackingRule {
+        max_units: 3,
+        points_property_id: Some(size_prop_id),
+        ..Default::default()
+    });
+
+    spawn_unit(
+        &mut app,
+        1,
+        0,
+        traveler_data(&setup, 4, &[(size_prop_id, 2)]),
+    );
+    spawn_unit(
+        &mut app,
+        0,
+        1,
+        traveler_data(&setup, 4, &[(size_prop_id, 1)]),
+    );
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        traveler_data(&setup, 4, &[(size_prop_id, 2)]),
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    // 2 + 2 > 3: blocked.
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0))
+    );
+    // 1 + 2 <= 3: allowed.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(0, 1))
+    );
+}
+
+/// Hexes already over their limit are reported as violations.
+#[test]
+fn stacking_violations_report_over_stacked_hexes() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    app.insert_resource(StackingRule {
+        max_units: 1,
+        ..Default::default()
+    });
+
+    spawn_unit(&mut app, 1, 0, traveler_data(&setup, 4, &[]));
+    spawn_unit(&mut app, 1, 0, traveler_data(&setup, 4, &[]));
+    spawn_unit(&mut app, 0, 1, traveler_data(&setup, 4, &[]));
+
+    app.update();
+
+    let violations = app.world().resource::<StackingViolations>();
+    assert_eq!(
+        violations.violations,
+        vec![StackingViolation {
+            position: HexPosition::new(1, 0),
+            points: 2,
+            limit: 1,
+        }]
+    );
+}
+
+/// Movement cost matrix overrides terrain cost per classification.
+#[test]
+fn movement_cost_matrix_overrides_terrain_cost() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+
+    // Add a classification enum property to the unit type.
+    let class_prop_id = TypeId::new();
+    let enum_id = TypeId::new();
+    app.world_mut()
+        .resource_mut::<EntityTypeRegistry>()
+        .types
+        .iter_mut()
+        .find(|t| t.id == setup.unit_type_id)
+        .expect("unit type exists")
+        .properties
+        .push(PropertyDefinition {
+            id: class_prop_id,
+            name: "Movement Mode".to_string(),
+            property_type: PropertyType::Enum(enum_id),
+            default_value: PropertyValue::Enum("Wheeled".to_string()),
+        });
+
+    // Set up the matrix: Wheeled costs 3 for this terrain type.
+    app.insert_resource(MovementCostMatrix {
+        classification_property_id: Some(class_prop_id),
+        entries: {
+            let mut m = HashMap::new();
+            m.insert((setup.tile_type_id, "Wheeled".to_string()), 3);
+            m
+        },
+    });
+
+    // Spawn a unit with classification "Wheeled" and budget 4.
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p.insert(class_prop_id, PropertyValue::Enum("Wheeled".to_string()));
+                p
+            },
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    // With matrix cost 3 and budget 4, unit can reach adjacent hexes (cost 3)
+    // but not two hexes away (cost 6 > 4).
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0)),
+        "(1,0) should be reachable at cost 3"
+    );
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(2, 0)),
+        "(2,0) should NOT be reachable at cost 6"
+    );
+}
+
+/// When the matrix is inactive, standard terrain cost is used.
+#[test]
+fn no_matrix_uses_standard_terrain_cost() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+
+    // Matrix is default (inactive).
+    // Standard terrain cost is 1, budget is 4 → can reach up to 4 hexes away.
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p
+            },
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    // With cost 1 and budget 4, should reach (2,0) (cost 2).
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(2, 0)),
+        "(2,0) should be reachable with standard cost 1 and budget 4"
+    );
+}
+
+/// Matrix falls back to default cost when no entry exists for a classification.
+#[test]
+fn matrix_fallback_when_no_entry_for_classification() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+
+    let class_prop_id = TypeId::new();
+    let enum_id = TypeId::new();
+    app.world_mut()
+        .resource_mut::<EntityTypeRegistry>()
+        .types
+        .iter_mut()
+        .find(|t| t.id == setup.unit_type_id)
+        .expect("unit type exists")
+        .properties
+        .push(PropertyDefinition {
+            id: class_prop_id,
+            name: "Movement Mode".to_string(),
+            property_type: PropertyType::Enum(enum_id),
+            default_value: PropertyValue::Enum("Foot".to_string()),
+        });
+
+    // Matrix has entries for "Wheeled" but NOT "Foot".
+    app.insert_resource(MovementCostMatrix {
+        classification_property_id: Some(class_prop_id),
+        entries: {
+            let mut m = HashMap::new();
+            m.insert((setup.tile_type_id, "Wheeled".to_string()), 3);
+            m
+        },
+    });
+
+    // Spawn a unit with classification "Foot" (no matrix entry → falls back to cost=1).
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p.insert(class_prop_id, PropertyValue::Enum("Foot".to_string()));
+                p
+            },
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    // Falls back to standard cost 1, budget 4 → can reach (2,0).
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(2, 0)),
+        "(2,0) should be reachable with fallback cost 1 and budget 4"
+    );
+}
+
+// =========================================================================
+// Canary Validation: Traces of Victory (SC-5)
+// =========================================================================
+//
+// End-to-end integration test exercising all four spatial primitives
+// together with Traces-of-Victory-like data:
+//   1. Hex-side edge features (river crossing cost)
+//   2. Spatial influence (ZOC: +2 MP to enter adjacent enemy hexes)
+//   3. Stacking constraint (max 2 combat units per hex)
+//   4. Movement cost matrix (terrain × classification costs)
+//
+// Grid layout (radius 3, pointy-top):
+//
+//       (-1,0)  (0,-1)
+//          \   /
+//   (-1,1)-(0,0)-(1,-1)
+//          /   \
+//        (0,1)  (1,0)-(2,-1)-(3,-1)
+//                 |
+//               (1,1)-(2,0)-(3,0)
+//                       |
+//                     (2,1)
+//
+// Scenario:
+//   - Terrain: Plains (cost 1) everywhere, Forest at (1,0) (cost 2 for Foot)
+//   - River edge between (0,0) and (0,-1) — crossing cost +1
+//   - Enemy unit at (2,0) projects ZOC range 1, cost +2
+//   - Stacking: max 2 per hex; two allies already at (0,1)
+//   - Infantry (Foot, 4 MP) at origin
+//
+// Expected reachability from (0,0) with budget 4:
+//   - (1,0) Forest: matrix cost 2 → reachable (budget 4-2=2)
+//   - (0,-1): terrain 1 + river 1 = 2 → reachable (budget 4-2=2)
+//   - (1,-1): influenced by enemy at (2,0), terrain 1 + ZOC 2 = 3 → reachable (budget 4-3=1)
+//   - (0,1): stacking full (2/2) → blocked
+//   - (3,0): adjacent to enemy, from (2,0) which is also adjacent → unreachable (budget exhausted)
+
+/// Spawn a single hex tile with the given entity type and optional property.
+fn spawn_tile_at(
+    app: &mut App,
+    q: i32,
+    r: i32,
+    tile_type_id: TypeId,
+    prop_id: Option<TypeId>,
+    prop_value: Option<i64>,
+) {
+    let mut properties = HashMap::new();
+    if let (Some(pid), Some(val)) = (prop_id, prop_value) {
+        properties.insert(pid, PropertyValue::Int(val));
+    }
+    app.world_mut().spawn((
+        HexTile,
+        HexPosition::new(q, r),
+        EntityData {
+            entity_type_id: tile_type_id,
+            properties,
+        },
+    ));
+}
+
+/// Canary: all four spatial primitives interact correctly in a single BFS.
+#[test]
+fn canary_tov_all_primitives_combined() {
+    use hexorder_contracts::hex_grid::{EdgeFeature, HexEdge};
+
+    let mut app = test_app();
+
+    // -- Entity type IDs --
+    let plains_type_id = TypeId::new();
+    let forest_type_id = TypeId::new();
+    let infantry_type_id = TypeId::new();
+    let enemy_type_id = TypeId::new();
+    let river_type_id = TypeId::new();
+
+    let budget_prop_id = TypeId::new();
+    let cost_prop_id = TypeId::new();
+    let class_prop_id = TypeId::new();
+    let enum_id = TypeId::new();
+
+    // -- Concept IDs --
+    let concept_id = TypeId::new();
+    let traveler_role_id = TypeId::new();
+    let terrain_role_id = TypeId::new();
+
+    // -- Register entity types --
+    let mut registry = EntityTypeRegistry::default();
+
+    // Infantry: Token with movement_points and classification enum
+    registry.types.push(EntityType {
+        id: infantry_type_id,
+        name: "Infantry".to_string(),
+        role: EntityRole::Token,
+        color: bevy::color::Color::WHITE,
+        properties: vec![
+            PropertyDefinition {
+                id: budget_prop_id,
+                name: "movement_points".to_string(),
+                property_type: PropertyType::Int,
+                default_value: PropertyValue::Int(4),
+            },
+            PropertyDefinition {
+                id: class_prop_id,
+                name: "Movement Mode".to_string(),
+                property_type: PropertyType::Enum(enum_id),
+                default_value: PropertyValue::Enum("Foot".to_string()),
+            },
+        ],
+    });
+
+    // Enemy: Token (projects influence, no movement properties needed)
+    registry.types.push(EntityType {
+        id: enemy_type_id,
+        name: "Enemy".to_string(),
+        role: EntityRole::Token,
+        color: bevy::color::Color::srgb(1.0, 0.0, 0.0),
+        properties: Vec::new(),
+    });
+
+    // Plains: BoardPosition with terrain_cost = 1
+    registry.types.push(EntityType {
+        id: plains_type_id,
+        name: "Plains".to_string(),
+        role: EntityRole::BoardPosition,
+        color: bevy::color::Color::srgb(0.3, 0.6, 0.2),
+        properties: vec![PropertyDefinition {
+            id: cost_prop_id,
+            name: "terrain_cost".to_string(),
+            property_type: PropertyType::Int,
+            default_value: PropertyValue::Int(1),
+        }],
+    });
+
+    // Forest: BoardPosition with terrain_cost = 2
+    registry.types.push(EntityType {
+        id: forest_type_id,
+        name: "Forest".to_string(),
+        role: EntityRole::BoardPosition,
+        color: bevy::color::Color::srgb(0.1, 0.4, 0.1),
+        properties: vec![PropertyDefinition {
+            id: cost_prop_id,
+            name: "terrain_cost".to_string(),
+            property_type: PropertyType::Int,
+            default_value: PropertyValue::Int(2),
+        }],
+    });
+
+    // River: edge type with cost = 1
+    registry.types.push(EntityType {
+        id: river_type_id,
+        name: "River".to_string(),
+        role: EntityRole::BoardPosition,
+        color: bevy::color::Color::srgb(0.2, 0.4, 0.8),
+        properties: vec![PropertyDefinition {
+            id: TypeId::new(),
+            name: "cost".to_string(),
+            property_type: PropertyType::Int,
+            default_value: PropertyValue::Int(1),
+        }],
+    });
+
+    app.insert_resource(registry);
+
+    // -- Ontology: Motion concept --
+    let concept = Concept {
+        id: concept_id,
+        name: "Motion".to_string(),
+        description: "Movement across terrain".to_string(),
+        role_labels: vec![
+            ConceptRole {
+                id: traveler_role_id,
+                name: "traveler".to_string(),
+                allowed_entity_roles: vec![EntityRole::Token],
+            },
+            ConceptRole {
+                id: terrain_role_id,
+                name: "terrain".to_string(),
+                allowed_entity_roles: vec![EntityRole::BoardPosition],
+            },
+        ],
+    };
+
+    let infantry_binding = ConceptBinding {
+        id: TypeId::new(),
+        entity_type_id: infantry_type_id,
+        concept_id,
+        concept_role_id: traveler_role_id,
+        property_bindings: vec![PropertyBinding {
+            property_id: budget_prop_id,
+            concept_local_name: "budget".to_string(),
+        }],
+    };
+    let plains_binding = ConceptBinding {
+        id: TypeId::new(),
+        entity_type_id: plains_type_id,
+        concept_id,
+        concept_role_id: terrain_role_id,
+        property_bindings: vec![PropertyBinding {
+            property_id: cost_prop_id,
+            concept_local_name: "cost".to_string(),
+        }],
+    };
+    let forest_binding = ConceptBinding {
+        id: TypeId::new(),
+        entity_type_id: forest_type_id,
+        concept_id,
+        concept_role_id: terrain_role_id,
+        property_bindings: vec![PropertyBinding {
+            property_id: cost_prop_id,
+            concept_local_name: "cost".to_string(),
+        }],
+    };
+
+    app.insert_resource(ConceptRegistry {
+        concepts: vec![concept],
+        bindings: vec![infantry_binding, plains_binding, forest_binding],
+    });
+
+    // Relation: terrain cost subtracts from movement budget.
+    app.insert_resource(RelationRegistry {
+        relations: vec![Relation {
+            id: TypeId::new(),
+            name: "Terrain Movement Cost".to_string(),
+            concept_id,
+            subject_role_id: traveler_role_id,
+            object_role_id: terrain_role_id,
+            trigger: RelationTrigger::OnEnter,
+            effect: RelationEffect::ModifyProperty {
+                target_property: "budget".to_string(),
+                source_property: "cost".to_string(),
+                operation: ModifyOperation::Subtract,
+            },
+        }],
+    });
+
+    // -- Primitive 1: Edge features (River) --
+    // River between (0,0) and (0,-1).
+    let edge =
+        HexEdge::between(HexPosition::new(0, 0), HexPosition::new(0, -1)).expect("adjacent hexes");
+    app.world_mut().resource_mut::<HexEdgeRegistry>().insert(
+        edge,
+        EdgeFeature {
+            type_name: "River".to_string(),
+        },
+    );
+
+    // -- Primitive 2: Spatial influence (ZOC) --
+    // Enemy projects +2 cost into adjacent hexes (range 1).
+    app.insert_resource(InfluenceRuleRegistry {
+        rules: vec![InfluenceRule {
+            id: TypeId::new(),
+            entity_type_id: enemy_type_id,
+            range: 1,
+            cost_modifier: 2,
+            zone: ZoneOfControl::default(),
+            enemy_only: false,
+        }],
+    });
+
+    // -- Primitive 3: Stacking --
+    // Max 2 combat units per hex.
+    app.insert_resource(StackingRule {
+        max_units: 2,
+        exempt_type_ids: Vec::new(),
+        ..Default::default()
+    });
+
+    // -- Primitive 4: Movement cost matrix --
+    // Foot infantry: Plains costs 1, Forest costs 2.
+    app.insert_resource(MovementCostMatrix {
+        classification_property_id: Some(class_prop_id),
+        entries: {
+            let mut m = HashMap::new();
+            m.insert((plains_type_id, "Foot".to_string()), 1);
+            m.insert((forest_type_id, "Foot".to_string()), 2);
+            m
+        },
+    });
+
+    // -- Spawn hex grid: Plains everywhere except Forest at (1,0) --
+    let radius = 3i32;
+    for q in -radius..=radius {
+        for r in -radius..=radius {
+            if (q + r).unsigned_abs() <= 3 {
+                if q == 1 && r == 0 {
+                    spawn_tile_at(&mut app, q, r, forest_type_id, Some(cost_prop_id), Some(2));
+                } else {
+                    spawn_tile_at(&mut app, q, r, plains_type_id, Some(cost_prop_id), Some(1));
+                }
+            }
+        }
+    }
+
+    // -- Spawn units --
+    // Enemy at (2, 0) — projects ZOC.
+    spawn_unit(
+        &mut app,
+        2,
+        0,
+        EntityData {
+            entity_type_id: enemy_type_id,
+            properties: HashMap::new(),
+        },
+    );
+
+    // Two allied infantry at (0, 1) — fills stacking limit.
+    for _ in 0..2 {
+        spawn_unit(
+            &mut app,
+            0,
+            1,
+            EntityData {
+                entity_type_id: infantry_type_id,
+                properties: {
+                    let mut p = HashMap::new();
+                    p.insert(budget_prop_id, PropertyValue::Int(4));
+                    p.insert(class_prop_id, PropertyValue::Enum("Foot".to_string()));
+                    p
+                },
+            },
+        );
+    }
+
+    // Selected infantry at origin (0, 0) with budget 4, classification Foot.
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: infantry_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(budget_prop_id, PropertyValue::Int(4));
+                p.insert(class_prop_id, PropertyValue::Enum("Foot".to_string()));
+                p
+            },
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+
+    // -- Run BFS --
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+
+    // === Primitive 4 (Matrix): Forest at (1,0) costs 2 for Foot ===
+    // Budget 4 - 2 = 2 remaining. Reachable.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0)),
+        "Forest (1,0) should be reachable: matrix cost 2, budget 4"
+    );
+
+    // === Primitive 1 (Edge): River crossing to (0,-1) ===
+    // Plains cost 1 + river crossing 1 = 2 total. Budget 4 - 2 = 2. Reachable.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(0, -1)),
+        "Plains (0,-1) with river crossing should be reachable: cost 1 + river 1 = 2"
+    );
+
+    // === Primitive 2 (Influence/ZOC): Hex (1,-1) adjacent to enemy at (2,0) ===
+    // Plains cost 1 + ZOC +2 = 3 total. Budget 4 - 3 = 1. Reachable.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, -1)),
+        "(1,-1) adjacent to enemy: plains 1 + ZOC 2 = 3, budget 4 → reachable"
+    );
+
+    // === Primitive 3 (Stacking): Hex (0,1) full with 2 units ===
+    // Blocked by stacking — cannot enter regardless of budget.
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(0, 1)),
+        "(0,1) should be blocked by stacking limit (2/2 units)"
+    );
+    assert!(
+        valid_moves
+            .blocked_explanations
+            .contains_key(&HexPosition::new(0, 1)),
+        "(0,1) should have blocked explanation for stacking"
+    );
+
+    // === Combined: (3,0) unreachable due to accumulated costs ===
+    // Path (0,0)→(1,0)[forest 2]→(2,0)[plains 1 + ZOC 2 = 3] = total 5 > budget 4.
+    // Even the cheapest path through ZOC hexes exhausts the budget.
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(3, 0)),
+        "(3,0) should be unreachable: path through forest + ZOC exceeds budget 4"
+    );
+
+    // === Verify continued movement through the full stack ===
+    // From (1,0) with 2 remaining, hex (1,1) costs plains 1 + ZOC 2 = 3 > 2.
+    // Passing through the full stack at (0,1) instead costs 1, leaving 3,
+    // which covers (1,1): stacking only applies where movement ends.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 1)),
+        "(1,1) via pass-through of (0,1): plains 1 + plains 1 + ZOC 2 = 4"
+    );
+    assert_eq!(
+        valid_moves.route(HexPosition::new(1, 1)),
+        Some(vec![
+            HexPosition::new(0, 0),
+            HexPosition::new(0, 1),
+            HexPosition::new(1, 1),
+        ])
+    );
+
+    // === Alternative path without ZOC ===
+    // (-1,0) is plains cost 1, not influenced. Budget 4 - 1 = 3.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(-1, 0)),
+        "(-1,0) away from ZOC should be easily reachable"
+    );
+    // (-2,0) is plains cost 1 from (-1,0), remaining 2.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(-2, 0)),
+        "(-2,0) should be reachable (2 plains steps, no ZOC)"
+    );
+    // (-3,0) is plains cost 1 from (-2,0), remaining 1.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(-3, 0)),
+        "(-3,0) should be reachable (3 plains steps away, budget 4)"
+    );
+
+    // === River crossing limits onward movement ===
+    // (0,-1) costs 2 to reach (river), leaving budget 2.
+    // (0,-2) from (0,-1) costs 1 (plains), leaving 1.
+    // (0,-3) from (0,-2) costs 1 (plains), leaving 0. Reachable.
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(0, -2)),
+        "(0,-2) should be reachable through river crossing with remaining budget"
+    );
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(0, -3)),
+        "(0,-3) should be reachable: 3 steps from origin with river costing 2 total on first step"
+    );
+}
+
+/// Canary: movement cost matrix differentiates unit classifications.
+/// Two units with different classifications on the same terrain see different costs.
+#[test]
+fn canary_tov_matrix_differentiates_classifications() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+
+    let class_prop_id = TypeId::new();
+    let enum_id = TypeId::new();
+
+    // Add classification enum to unit type.
+    app.world_mut()
+        .resource_mut::<EntityTypeRegistry>()
+        .types
+        .iter_mut()
+        .find(|t| t.id == setup.unit_type_id)
+        .expect("unit type exists")
+        .properties
+        .push(PropertyDefinition {
+            id: class_prop_id,
+            name: "Movement Mode".to_string(),
+            property_type: PropertyType::Enum(enum_id),
+            default_value: PropertyValue::Enum("Foot".to_string()),
+        });
+
+    // Foot costs 1 per hex, Mechanized costs 3 per hex (on Plains).
+    app.insert_resource(MovementCostMatrix {
+        classification_property_id: Some(class_prop_id),
+        entries: {
+            let mut m = HashMap::new();
+            m.insert((setup.tile_type_id, "Foot".to_string()), 1);
+            m.insert((setup.tile_type_id, "Mechanized".to_string()), 3);
+            m
+        },
+    });
+
+    // --- Test Foot infantry: budget 4, cost 1 → reaches 3 hexes (map limit) ---
+    let foot_unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p.insert(class_prop_id, PropertyValue::Enum("Foot".to_string()));
+                p
+            },
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(foot_unit);
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(3, 0)),
+        "Foot (cost 1): should reach 3 hexes with budget 4"
+    );
+
+    // --- Test Mechanized: budget 4, cost 3 → reaches only adjacent ---
+    // Despawn foot unit, spawn mechanized at origin.
+    app.world_mut().despawn(foot_unit);
+    let mech_unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: {
+                let mut p = HashMap::new();
+                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
+                p.insert(class_prop_id, PropertyValue::Enum("Mechanized".to_string()));
+                p
+            },
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(mech_unit);
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0)),
+        "Mechanized (cost 3): should reach adjacent hex"
+    );
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(2, 0)),
+        "Mechanized (cost 3): should NOT reach 2 hexes (cost 6 > budget 4)"
+    );
+}
+
+// -------------------------------------------------------------------------
+// Cheapest path explanation
+// -------------------------------------------------------------------------
+
+use hexorder_contracts::mechanics::{AreaEffect, AreaMarker, AreaMarkerRegistry, MarkerDuration};
+use hexorder_contracts::validation::CostSource;
+
+/// Overrides the terrain cost of the tile at `pos`.
+fn set_tile_cost(app: &mut App, setup: &MotionSetup, pos: HexPosition, cost: i64) {
+    let mut tiles = app
+        .world_mut()
+        .query_filtered::<(&HexPosition, &mut EntityData), With<HexTile>>();
+    for (tile_pos, mut data) in tiles.iter_mut(app.world_mut()) {
+        if *tile_pos == pos {
+            data.properties
+                .insert(setup.cost_prop_id, PropertyValue::Int(cost));
+        }
+    }
+}
+
+#[test]
+fn cheapest_route_detours_around_expensive_hex() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    set_tile_cost(&mut app, &setup, HexPosition::new(1, 0), 3);
+    spawn_selected_unit(&mut app, &setup, 4, Vec::new());
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    let target = HexPosition::new(2, 0);
+    let route = valid_moves.route(target).expect("target is reachable");
+    assert_eq!(route.first(), Some(&HexPosition::new(0, 0)));
+    assert_eq!(route.last(), Some(&target));
+    assert_eq!(route.len(), 4, "detour of three 1-cost steps beats 3 + 1");
+    assert!(!route.contains(&HexPosition::new(1, 0)));
+    assert_eq!(valid_moves.paths[&target].total_cost, 3);
+
+    let expensive = &valid_moves.paths[&HexPosition::new(1, 0)];
+    assert_eq!(expensive.from, HexPosition::new(0, 0));
+    assert_eq!(expensive.components.len(), 1);
+    assert_eq!(expensive.components[0].source, CostSource::Terrain);
+    assert_eq!(expensive.components[0].label, "Terrain Movement Cost");
+    assert_eq!(expensive.step_cost(), 3);
+}
+
+#[test]
+fn cost_breakdown_lists_each_component() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 6, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    app.insert_resource(AreaMarkerRegistry {
+        markers: vec![AreaMarker {
+            marker_type: "Bombardment Zone".to_string(),
+            center: HexPosition::new(1, 0),
+            radius: 0,
+            effects: vec![AreaEffect::CostModifier { extra_cost: 2 }],
+            duration: MarkerDuration::Permanent,
+        }],
+    });
+    place_enemy_zone(&mut app, 3, -3, 2, ZoneOfControl::default());
+    spawn_selected_unit(&mut app, &setup, 6, Vec::new());
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    let marked = &valid_moves.paths[&HexPosition::new(1, 0)];
+    let sources: Vec<_> = marked.components.iter().map(|c| c.source).collect();
+    assert_eq!(sources, vec![CostSource::AreaMarker, CostSource::Terrain]);
+    assert_eq!(marked.components[0].label, "Bombardment Zone");
+    assert_eq!(marked.step_cost(), 3);
+
+    let influenced = &valid_moves.paths[&HexPosition::new(2, -2)];
+    assert!(
+        influenced
+            .components
+            .iter()
+            .any(|c| c.source == CostSource::Influence && c.amount == 2),
+        "influence shows up in the breakdown: {:?}",
+        influenced.components
+    );
+    let steps = valid_moves
+        .route_steps(HexPosition::new(2, -2))
+        .expect("reachable");
+    let summed: i64 = steps.iter().map(|(_, step)| step.step_cost()).sum();
+    assert_eq!(
+        summed,
+        valid_moves.paths[&HexPosition::new(2, -2)].total_cost
+    );
+}
+
+#[test]
+fn area_marker_cost_blocks_when_over_budget() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+    spawn_hex_grid_with_properties(&mut app, 2, setup.tile_type_id, setup.cost_prop_id, 1);
+    app.insert_resource(AreaMarkerRegistry {
+        markers: vec![AreaMarker {
+            marker_type: "Minefield".to_string(),
+            center: HexPosition::new(1, 0),
+            radius: 0,
+            effects: vec![AreaEffect::CostModifier { extra_cost: 5 }],
+            duration: MarkerDuration::Permanent,
+        }],
+    });
+    spawn_selected_unit(&mut app, &setup, 2, Vec::new());
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    let pos = HexPosition::new(1, 0);
+    assert!(!valid_moves.valid_positions.contains(&pos));
+    assert!(!valid_moves.paths.contains_key(&pos));
+    assert!(
+        valid_moves.blocked_explanations[&pos]
+            .iter()
+            .any(|r| r.constraint_name == "Minefield area marker")
+    );
+}
+
+#[test]
+fn free_movement_records_routes() {
+    let mut app = test_app();
+    spawn_hex_grid(&mut app, 2, TypeId::new());
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: TypeId::new(),
+            properties: HashMap::new(),
+        },
+    );
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    let route = valid_moves
+        .route(HexPosition::new(2, 0))
+        .expect("reachable");
+    assert_eq!(route.len(), 3);
+    assert!(
+        valid_moves.paths[&HexPosition::new(2, 0)]
+            .components
+            .is_empty()
+    );
+}
+
+// -------------------------------------------------------------------------
+// Faction-aware movement
+// -------------------------------------------------------------------------
+
+/// Spawns a unit owned by `faction_id` with the motion budget set to 4.
+fn spawn_owned_unit(
+    app: &mut App,
+    setup: &MotionSetup,
+    pos: (i32, i32),
+    entity_type_id: TypeId,
+    faction_id: TypeId,
+) -> Entity {
+    let unit = spawn_unit(
+        app,
+        pos.0,
+        pos.1,
+        EntityData {
+            entity_type_id,
+            properties: HashMap::from([(setup.budget_prop_id, PropertyValue::Int(4))]),
+        },
+    );
+    app.world_mut().entity_mut(unit).insert(UnitOwner {
+        faction_id: Some(faction_id),
+    });
+    unit
+}
+
+#[test]
+fn enemy_only_influence_ignores_friendly_units() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    app.insert_resource(InfluenceRuleRegistry {
+        rules: vec![InfluenceRule {
+            id: TypeId::new(),
+            entity_type_id: setup.unit_type_id,
+            range: 1,
+            cost_modifier: 2,
+            zone: ZoneOfControl::default(),
+            enemy_only: true,
+        }],
+    });
+
+    let blue = TypeId::new();
+    let red = TypeId::new();
+    let guard = spawn_owned_unit(&mut app, &setup, (2, 0), setup.unit_type_id, blue);
+    let unit = spawn_owned_unit(&mut app, &setup, (0, 0), setup.unit_type_id, blue);
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+    app.update();
+
+    // A friendly unit's influence costs the mover nothing.
+    let target = HexPosition::new(1, 0);
+    assert_eq!(
+        app.world().resource::<ValidMoveSet>().paths[&target].total_cost,
+        1
+    );
+
+    // Once the guard changes sides, its influence applies.
+    app.world_mut().entity_mut(guard).insert(UnitOwner {
+        faction_id: Some(red),
+    });
+    app.world_mut().resource_mut::<SelectedUnit>().set_changed();
+    app.update();
+    assert_eq!(
+        app.world().resource::<ValidMoveSet>().paths[&target].total_cost,
+        3
+    );
+}
+
+#[test]
+fn mixed_stack_is_blocked() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    app.insert_resource(StackingRule {
+        no_mixed_factions: true,
+        ..Default::default()
+    });
+
+    let blue = TypeId::new();
+    let red = TypeId::new();
+    spawn_owned_unit(&mut app, &setup, (1, 0), setup.unit_type_id, red);
+    spawn_owned_unit(&mut app, &setup, (0, 1), setup.unit_type_id, blue);
+    let unit = spawn_owned_unit(&mut app, &setup, (0, 0), setup.unit_type_id, blue);
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(
+        !valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(1, 0))
+    );
+    assert!(
+        valid_moves.blocked_explanations[&HexPosition::new(1, 0)]
+            .iter()
+            .any(|r| r.constraint_name == "Mixed stack")
+    );
+    assert!(
+        valid_moves
+            .valid_positions
+            .contains(&HexPosition::new(0, 1))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Entity state machines
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::game_system::{
+    EntityState, SetEntityStateEvent, StateDefinition, StateMachine, StateMachineRegistry,
+    StateTransition, StateTrigger, StateTriggerEvent,
+};
+use hexorder_contracts::mechanics::CombatResolvedEvent;
+use hexorder_contracts::ontology::Constraint;
+
+/// States of the step-loss machine added by `add_step_machine`.
+struct StepStates {
+    full: TypeId,
+    reduced: TypeId,
+    eliminated: TypeId,
+}
+
+/// Attaches a Full → Reduced → Eliminated machine to the unit type. Each
+/// step loss moves one state down; Reduced caps the budget at 1.
+fn add_step_machine(app: &mut App, setup: &MotionSetup) -> StepStates {
+    let states = StepStates {
+        full: TypeId::new(),
+        reduced: TypeId::new(),
+        eliminated: TypeId::new(),
+    };
+    let state = |id, name: &str, overrides: Vec<(TypeId, PropertyValue)>| StateDefinition {
+        id,
+        name: name.to_string(),
+        property_overrides: overrides.into_iter().collect(),
+        color: None,
+        badge: String::new(),
+    };
+    let step = |from, to| StateTransition {
+        id: TypeId::new(),
+        name: "Step loss".to_string(),
+        from: Some(from),
+        to,
+        trigger: StateTrigger::StepLoss,
+    };
+    app.insert_resource(StateMachineRegistry {
+        machines: vec![StateMachine {
+            id: TypeId::new(),
+            name: "Steps".to_string(),
+            entity_type_id: setup.unit_type_id,
+            states: vec![
+                state(states.full, "Full", Vec::new()),
+                state(
+                    states.reduced,
+                    "Reduced",
+                    vec![(setup.budget_prop_id, PropertyValue::Int(1))],
+                ),
+                state(states.eliminated, "Eliminated", Vec::new()),
+            ],
+            initial_state: None,
+            transitions: vec![
+                step(states.full, states.reduced),
+                step(states.reduced, states.eliminated),
+            ],
+        }],
+    });
+    states
+}
+
+fn state_of(app: &App, entity: Entity) -> Option<TypeId> {
+    app.world().get::<EntityState>(entity).map(|s| s.state_id)
+}
+
+fn budget_of(app: &App, entity: Entity, setup: &MotionSetup) -> PropertyValue {
+    app.world()
+        .get::<EntityData>(entity)
+        .expect("unit data")
+        .properties[&setup.budget_prop_id]
+        .clone()
+}
+
+#[test]
+fn state_overrides_follow_transitions() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let states = add_step_machine(&mut app, &setup);
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: HashMap::from([(setup.budget_prop_id, PropertyValue::Int(4))]),
+        },
+    );
+    app.update();
+    assert_eq!(state_of(&app, unit), Some(states.full));
+
+    let step_loss = StateTriggerEvent {
+        entity: unit,
+        trigger: StateTrigger::StepLoss,
+    };
+    app.world_mut().commands().trigger(step_loss.clone());
+    app.update();
+    assert_eq!(state_of(&app, unit), Some(states.reduced));
+    assert_eq!(budget_of(&app, unit, &setup), PropertyValue::Int(1));
+
+    // Leaving Reduced restores the unit's own value.
+    app.world_mut().commands().trigger(step_loss);
+    app.update();
+    assert_eq!(state_of(&app, unit), Some(states.eliminated));
+    assert_eq!(budget_of(&app, unit, &setup), PropertyValue::Int(4));
+}
+
+#[test]
+fn combat_outcome_applies_step_losses() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let states = add_step_machine(&mut app, &setup);
+    let data = EntityData {
+        entity_type_id: setup.unit_type_id,
+        properties: HashMap::from([(setup.budget_prop_id, PropertyValue::Int(4))]),
+    };
+    let attacker = spawn_unit(&mut app, 0, 0, data.clone());
+    let defender = spawn_unit(&mut app, 1, 0, data);
+    app.update();
+
+    app.world_mut().commands().trigger(CombatResolvedEvent {
+        attacker,
+        defender,
+        attackers: Vec::new(),
+        defenders: Vec::new(),
+        step_losses: Vec::new(),
+        outcome: CombatOutcome {
+            label: "D2".to_string(),
+            effect: Some(OutcomeEffect::StepLoss { steps: 2 }),
+        },
+        die_roll: 6,
+        column_label: "3:1".to_string(),
+    });
+    app.update();
+
+    assert_eq!(state_of(&app, defender), Some(states.eliminated));
+    assert_eq!(state_of(&app, attacker), Some(states.full));
+}
+
+#[test]
+fn combat_outcome_follows_allocated_step_losses() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let states = add_step_machine(&mut app, &setup);
+    let data = EntityData {
+        entity_type_id: setup.unit_type_id,
+        properties: HashMap::from([(setup.budget_prop_id, PropertyValue::Int(4))]),
+    };
+    let attacker = spawn_unit(&mut app, 0, 0, data.clone());
+    let first = spawn_unit(&mut app, 1, 0, data.clone());
+    let second = spawn_unit(&mut app, 1, 0, data);
+    app.update();
+
+    // The second defender takes the only loss, though the first is primary.
+    app.world_mut().commands().trigger(CombatResolvedEvent {
+        attacker,
+        defender: first,
+        attackers: vec![attacker],
+        defenders: vec![first, second],
+        step_losses: vec![(second, 1)],
+        outcome: CombatOutcome {
+            label: "D1".to_string(),
+            effect: Some(OutcomeEffect::StepLoss { steps: 1 }),
+        },
+        die_roll: 5,
+        column_label: "2:1".to_string(),
+    });
+    app.update();
+
+    assert_eq!(state_of(&app, first), Some(states.full));
+    assert_eq!(state_of(&app, second), Some(states.reduced));
+}
+
+#[test]
+fn set_entity_state_ignores_states_of_other_machines() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let states = add_step_machine(&mut app, &setup);
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: HashMap::new(),
+        },
+    );
+    app.update();
+
+    app.world_mut().commands().trigger(SetEntityStateEvent {
+        entity: unit,
+        state_id: TypeId::new(),
+    });
+    app.update();
+    assert_eq!(state_of(&app, unit), Some(states.full));
+
+    app.world_mut().commands().trigger(SetEntityStateEvent {
+        entity: unit,
+        state_id: states.eliminated,
+    });
+    app.update();
+    assert_eq!(state_of(&app, unit), Some(states.eliminated));
+}
+
+#[test]
+fn block_condition_in_state_blocks_reduced_units() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 2, setup.tile_type_id, setup.cost_prop_id, 1);
+    let states = add_step_machine(&mut app, &setup);
+    add_block_condition(
+        &mut app,
+        &setup,
+        ConstraintExpr::InState {
+            role_id: setup.traveler_role_id,
+            state_id: states.reduced,
+        },
+    );
+    spawn_selected_unit(&mut app, &setup, 4, Vec::new());
+    app.update();
+    let target = HexPosition::new(1, 0);
+    assert!(
+        app.world()
+            .resource::<ValidMoveSet>()
+            .valid_positions
+            .contains(&target)
+    );
+
+    let unit = app
+        .world()
+        .resource::<SelectedUnit>()
+        .entity
+        .expect("selected");
+    app.world_mut().commands().trigger(StateTriggerEvent {
+        entity: unit,
+        trigger: StateTrigger::StepLoss,
+    });
+    app.update();
+
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(valid_moves.valid_positions.is_empty());
+    let explanation = &valid_moves.blocked_explanations[&target]
+        .iter()
+        .find(|r| r.constraint_name == "Conditional block")
+        .expect("block reason")
+        .explanation;
+    assert!(
+        explanation.contains("traveler is Reduced"),
+        "unexpected explanation: {explanation}"
+    );
+}
+
+/// A play-mode app on turn 1 of a two-phase turn.
+fn play_app_with_phases() -> (App, TypeId) {
+    let mut app = test_app();
+    app.insert_state(AppScreen::Play);
+    let phase = |name: &str, phase_type| Phase {
+        id: TypeId::new(),
+        name: name.to_string(),
+        phase_type,
+        description: String::new(),
+    };
+    let phases = vec![
+        phase("Movement", PhaseType::Movement),
+        phase("Recovery", PhaseType::Admin),
+    ];
+    let recovery = phases[1].id;
+    app.insert_resource(TurnStructure {
+        phases,
+        player_order: PlayerOrder::Simultaneous,
+    });
+    app.insert_resource(TurnState {
+        turn_number: 1,
+        current_phase_index: 0,
+        is_active: true,
+        phase_actions_remaining: None,
+    });
+    (app, recovery)
+}
+
+fn advance_to_phase(app: &mut App, index: usize) {
+    app.world_mut()
+        .resource_mut::<TurnState>()
+        .current_phase_index = index;
+    app.update();
+}
+
+#[test]
+fn phase_start_transition_fires_at_boundary() {
+    let (mut app, recovery) = play_app_with_phases();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let states = add_step_machine(&mut app, &setup);
+    app.world_mut()
+        .resource_mut::<StateMachineRegistry>()
+        .machines[0]
+        .transitions
+        .push(StateTransition {
+            id: TypeId::new(),
+            name: "Recover".to_string(),
+            from: Some(states.reduced),
+            to: states.full,
+            trigger: StateTrigger::PhaseStart(recovery),
+        });
+    let unit = spawn_unit(
+        &mut app,
+        0,
+        0,
+        EntityData {
+            entity_type_id: setup.unit_type_id,
+            properties: HashMap::new(),
+        },
+    );
+    app.update();
+    app.world_mut().commands().trigger(SetEntityStateEvent {
+        entity: unit,
+        state_id: states.reduced,
+    });
+    app.update();
+    assert_eq!(state_of(&app, unit), Some(states.reduced));
+
+    advance_to_phase(&mut app, 1);
+    assert_eq!(state_of(&app, unit), Some(states.full));
+}
+
+#[test]
+fn constraint_transition_uses_the_tile_under_the_unit() {
+    let (mut app, _) = play_app_with_phases();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let states = add_step_machine(&mut app, &setup);
+    // Units on terrain costing 3 or more are disrupted at the next phase.
+    let constraint_id = TypeId::new();
+    app.insert_resource(ConstraintRegistry {
+        constraints: vec![Constraint {
+            id: constraint_id,
+            name: "Rough ground".to_string(),
+            description: String::new(),
+            concept_id: setup.concept_id,
+            relation_id: None,
+            expression: ConstraintExpr::PropertyCompare {
+                role_id: setup.terrain_role_id,
+                property_name: "cost".to_string(),
+                operator: CompareOp::Ge,
+                value: PropertyValue::Int(3),
+            },
+            auto_generated: false,
+            gates: Vec::new(),
+        }],
+    });
+    app.world_mut()
+        .resource_mut::<StateMachineRegistry>()
+        .machines[0]
+        .transitions
+        .push(StateTransition {
+            id: TypeId::new(),
+            name: "Disrupted".to_string(),
+            from: Some(states.full),
+            to: states.reduced,
+            trigger: StateTrigger::Constraint {
+                constraint_id,
+                satisfied: true,
+            },
+        });
+    let tile = |app: &mut App, q, cost| {
+        app.world_mut().spawn((
+            HexTile,
+            HexPosition::new(q, 0),
+            EntityData {
+                entity_type_id: setup.tile_type_id,
+                properties: HashMap::from([(setup.cost_prop_id, PropertyValue::Int(cost))]),
+            },
+        ));
+    };
+    tile(&mut app, 0, 1);
+    tile(&mut app, 1, 3);
+    let data = EntityData {
+        entity_type_id: setup.unit_type_id,
+        properties: HashMap::from([(setup.budget_prop_id, PropertyValue::Int(4))]),
+    };
+    let on_plains = spawn_unit(&mut app, 0, 0, data.clone());
+    let on_rough = spawn_unit(&mut app, 1, 0, data);
+    app.update();
+
+    advance_to_phase(&mut app, 1);
+    assert_eq!(state_of(&app, on_plains), Some(states.full));
+    assert_eq!(state_of(&app, on_rough), Some(states.reduced));
+}
+
+// ---------------------------------------------------------------------------
+// Reachability
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::hex_grid::{
+    ReachabilityBlockers, ReachabilityMap, ReachabilityRule, ReachabilityRuleRegistry,
+    ReachabilitySource, ReachabilityStatus, TraceReachabilityEvent,
+};
+
+/// Registers a supply rule tracing the unit type back to hex (0, 0).
+fn add_supply_rule(app: &mut App, setup: &MotionSetup, max_cost: i64) -> TypeId {
+    let rule_id = TypeId::new();
+    app.insert_resource(ReachabilityRuleRegistry {
+        rules: vec![ReachabilityRule {
+            id: rule_id,
+            name: "Supply".to_string(),
+            traced_types: vec![setup.unit_type_id],
+            sources: vec![ReachabilitySource::Hexes(vec![HexPosition::new(0, 0)])],
+            max_cost,
+            blockers: ReachabilityBlockers::default(),
+            phases: Vec::new(),
+        }],
+    });
+    rule_id
+}
+
+fn trace_all(app: &mut App) {
+    app.world_mut()
+        .commands()
+        .trigger(TraceReachabilityEvent { rule_id: None });
+    app.update();
+}
+
+fn in_reach(app: &App, unit: Entity, rule_id: TypeId) -> Option<bool> {
+    app.world()
+        .get::<ReachabilityStatus>(unit)
+        .and_then(|s| s.is_in_reach(rule_id))
+}
+
+#[test]
+fn trace_marks_units_within_max_cost_in_reach() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let rule_id = add_supply_rule(&mut app, &setup, 2);
+    let blue = TypeId::new();
+    let near = spawn_owned_unit(&mut app, &setup, (2, 0), setup.unit_type_id, blue);
+    let far = spawn_owned_unit(&mut app, &setup, (3, 0), setup.unit_type_id, blue);
+    app.update();
+    assert_eq!(in_reach(&app, near, rule_id), None);
+
+    trace_all(&mut app);
+    assert_eq!(in_reach(&app, near, rule_id), Some(true));
+    assert_eq!(in_reach(&app, far, rule_id), Some(false));
+
+    let map = app.world().resource::<ReachabilityMap>();
+    let trace = map.trace_for(rule_id, Some(near)).expect("trace");
+    assert_eq!(trace.reached.get(&HexPosition::new(2, 0)), Some(&2));
+    assert!(!trace.reached.contains_key(&HexPosition::new(3, 0)));
+}
+
+#[test]
+fn trace_respects_enemy_influence_and_terrain_blockers() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    // With a budget of 2, the only route from (2, 0) to (0, 0) is via (1, 0).
+    let rule_id = add_supply_rule(&mut app, &setup, 2);
+    app.insert_resource(InfluenceRuleRegistry {
+        rules: vec![InfluenceRule {
+            id: TypeId::new(),
+            entity_type_id: setup.unit_type_id,
+            range: 1,
+            cost_modifier: 1,
+            zone: ZoneOfControl::default(),
+            enemy_only: false,
+        }],
+    });
+    let blue = TypeId::new();
+    let red = TypeId::new();
+    let unit = spawn_owned_unit(&mut app, &setup, (2, 0), setup.unit_type_id, blue);
+    spawn_owned_unit(&mut app, &setup, (1, 1), setup.unit_type_id, red);
+    app.update();
+
+    // Influence is only a blocker when the rule says so.
+    trace_all(&mut app);
+    assert_eq!(in_reach(&app, unit, rule_id), Some(true));
+
+    app.world_mut()
+        .resource_mut::<ReachabilityRuleRegistry>()
+        .rules[0]
+        .blockers
+        .enemy_influence = true;
+    trace_all(&mut app);
+    assert_eq!(in_reach(&app, unit, rule_id), Some(false));
+
+    // A blocked terrain type cuts the route just the same.
+    let swamp = TypeId::new();
+    {
+        let mut registry = app.world_mut().resource_mut::<ReachabilityRuleRegistry>();
+        registry.rules[0].blockers = ReachabilityBlockers {
+            terrain: vec![swamp],
+            ..Default::default()
+        };
+    }
+    trace_all(&mut app);
+    assert_eq!(in_reach(&app, unit, rule_id), Some(true));
+    let mut tiles = app
+        .world_mut()
+        .query_filtered::<(&HexPosition, &mut EntityData), With<HexTile>>();
+    for (pos, mut data) in tiles.iter_mut(app.world_mut()) {
+        if *pos == HexPosition::new(1, 0) {
+            data.entity_type_id = swamp;
+        }
+    }
+    trace_all(&mut app);
+    assert_eq!(in_reach(&app, unit, rule_id), Some(false));
+}
+
+#[test]
+fn phase_start_trace_drives_reachability_transitions() {
+    let (mut app, recovery) = play_app_with_phases();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let states = add_step_machine(&mut app, &setup);
+    let rule_id = add_supply_rule(&mut app, &setup, 2);
+    app.world_mut()
+        .resource_mut::<ReachabilityRuleRegistry>()
+        .rules[0]
+        .phases = vec![recovery];
+    app.world_mut()
+        .resource_mut::<StateMachineRegistry>()
+        .machines[0]
+        .transitions
+        .push(StateTransition {
+            id: TypeId::new(),
+            name: "Out of supply".to_string(),
+            from: Some(states.full),
+            to: states.reduced,
+            trigger: StateTrigger::Reachability {
+                rule_id,
+                in_reach: false,
+            },
+        });
+    let blue = TypeId::new();
+    let supplied = spawn_owned_unit(&mut app, &setup, (1, 0), setup.unit_type_id, blue);
+    let cut_off = spawn_owned_unit(&mut app, &setup, (3, 0), setup.unit_type_id, blue);
+    app.update();
+    assert_eq!(in_reach(&app, cut_off, rule_id), None);
+
+    advance_to_phase(&mut app, 1);
+    app.update();
+    assert_eq!(in_reach(&app, cut_off, rule_id), Some(false));
+    assert_eq!(state_of(&app, supplied), Some(states.full));
+    assert_eq!(state_of(&app, cut_off), Some(states.reduced));
+}
+
+#[test]
+fn block_condition_in_reach_blocks_untraced_and_cut_off_units() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 2, setup.tile_type_id, setup.cost_prop_id, 1);
+    let rule_id = add_supply_rule(&mut app, &setup, 2);
+    add_block_condition(
+        &mut app,
+        &setup,
+        ConstraintExpr::Not(Box::new(ConstraintExpr::InReach {
+            role_id: setup.traveler_role_id,
+            rule_id,
+        })),
+    );
+    spawn_selected_unit(&mut app, &setup, 4, Vec::new());
+    app.update();
+    let target = HexPosition::new(1, 0);
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(valid_moves.valid_positions.is_empty());
+    let explanation = &valid_moves.blocked_explanations[&target]
+        .iter()
+        .find(|r| r.constraint_name == "Conditional block")
+        .expect("block reason")
+        .explanation;
+    assert!(
+        explanation.contains("has not been traced by Supply"),
+        "unexpected explanation: {explanation}"
+    );
+
+    let unit = app
+        .world()
+        .resource::<SelectedUnit>()
+        .entity
+        .expect("selected");
+    app.world_mut().entity_mut(unit).insert(ReachabilityStatus {
+        in_reach: HashMap::from([(rule_id, true)]),
+    });
+    app.update();
+    assert!(
+        app.world()
+            .resource::<ValidMoveSet>()
+            .valid_positions
+            .contains(&target)
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Proximity and action eligibility
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::hex_grid::CommandRadius;
+use hexorder_contracts::ontology::{
+    GatedAction, ProximityFaction, ProximityFilter, ProximityMeasure,
+};
+use hexorder_contracts::validation::ActionEligibility;
+
+/// Registers a constraint gating `gates` on the traveler being within `max`
+/// of a friendly unit of `hq_type_id`.
+fn add_command_range(
+    app: &mut App,
+    setup: &MotionSetup,
+    hq_type_id: TypeId,
+    measure: ProximityMeasure,
+    max: i64,
+    gates: Vec<GatedAction>,
+) {
+    app.world_mut()
+        .resource_mut::<ConstraintRegistry>()
+        .constraints
+        .push(Constraint {
+            id: TypeId::new(),
+            name: "Command range".to_string(),
+            description: String::new(),
+            concept_id: setup.concept_id,
+            relation_id: None,
+            expression: ConstraintExpr::Proximity {
+                role_id: setup.traveler_role_id,
+                filter: ProximityFilter {
+                    entity_type_id: Some(hq_type_id),
+                    faction: ProximityFaction::Friendly,
+                    same_property: None,
+                },
+                measure,
+                max,
+                line_of_sight: None,
+            },
+            auto_generated: false,
+            gates,
+        });
+}
+
+fn eligibility(app: &App, unit: Entity) -> ActionEligibility {
+    app.world()
+        .get::<ActionEligibility>(unit)
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[test]
+fn proximity_gate_denies_move_outside_command_range() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let hq_type = TypeId::new();
+    add_command_range(
+        &mut app,
+        &setup,
+        hq_type,
+        ProximityMeasure::Distance,
+        2,
+        vec![GatedAction::Move],
+    );
+    let blue = TypeId::new();
+    let red = TypeId::new();
+    let hq = spawn_owned_unit(&mut app, &setup, (0, 0), hq_type, blue);
+    // An enemy HQ next to the unit does not command it.
+    spawn_owned_unit(&mut app, &setup, (3, -1), hq_type, red);
+    let unit = spawn_owned_unit(&mut app, &setup, (3, 0), setup.unit_type_id, blue);
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+    app.update();
+
+    assert!(!eligibility(&app, unit).allows(GatedAction::Move));
+    assert!(eligibility(&app, unit).allows(GatedAction::Attack));
+    let valid_moves = app.world().resource::<ValidMoveSet>();
+    assert!(valid_moves.valid_positions.is_empty());
+    let explanation = &valid_moves.blocked_explanations[&HexPosition::new(2, 0)][0].explanation;
+    assert!(
+        explanation.contains("may not move: Command range does not hold"),
+        "unexpected explanation: {explanation}"
+    );
+
+    app.world_mut()
+        .entity_mut(hq)
+        .insert(HexPosition::new(1, 0));
+    app.update();
+    assert!(eligibility(&app, unit).allows(GatedAction::Move));
+    assert!(
+        app.world()
+            .resource::<ValidMoveSet>()
+            .valid_positions
+            .contains(&HexPosition::new(2, 0))
+    );
+}
+
+#[test]
+fn proximity_path_cost_pays_terrain_costs() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let hq_type = TypeId::new();
+    add_command_range(
+        &mut app,
+        &setup,
+        hq_type,
+        ProximityMeasure::PathCost,
+        2,
+        vec![GatedAction::Attack],
+    );
+    let blue = TypeId::new();
+    spawn_owned_unit(&mut app, &setup, (0, 0), hq_type, blue);
+    let unit = spawn_owned_unit(&mut app, &setup, (2, 0), setup.unit_type_id, blue);
+    app.update();
+    assert!(eligibility(&app, unit).allows(GatedAction::Attack));
+
+    // The only two-hex route to the HQ now costs more than the range.
+    set_tile_cost(&mut app, &setup, HexPosition::new(1, 0), 3);
+    app.update();
+    let denied = eligibility(&app, unit);
+    assert!(denied.allows(GatedAction::Move));
+    assert!(!denied.allows(GatedAction::Attack));
+    let explanation = &denied
+        .denials(GatedAction::Attack)
+        .next()
+        .expect("denial")
+        .explanation;
+    assert!(
+        explanation.contains("may not attack"),
+        "unexpected explanation: {explanation}"
+    );
+}
+
+#[test]
+fn proximity_line_of_sight_skips_hidden_units() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let hq_type = TypeId::new();
+    let forest = TypeId::new();
+    add_command_range(
+        &mut app,
+        &setup,
+        hq_type,
+        ProximityMeasure::Distance,
+        3,
+        vec![GatedAction::Move],
+    );
+    if let ConstraintExpr::Proximity { line_of_sight, .. } = &mut app
+        .world_mut()
+        .resource_mut::<ConstraintRegistry>()
+        .constraints[0]
+        .expression
+    {
+        *line_of_sight = Some(vec![forest]);
+    }
+    let blue = TypeId::new();
+    spawn_owned_unit(&mut app, &setup, (2, 0), hq_type, blue);
+    let unit = spawn_owned_unit(&mut app, &setup, (0, 0), setup.unit_type_id, blue);
+    app.update();
+    assert!(eligibility(&app, unit).allows(GatedAction::Move));
+
+    let mut tiles = app
+        .world_mut()
+        .query_filtered::<(&HexPosition, &mut EntityData), With<HexTile>>();
+    for (pos, mut data) in tiles.iter_mut(app.world_mut()) {
+        if *pos == HexPosition::new(1, 0) {
+            data.entity_type_id = forest;
+        }
+    }
+    app.update();
+    assert!(!eligibility(&app, unit).allows(GatedAction::Move));
+}
+
+#[test]
+fn command_radius_surrounds_selected_hq() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let hq_type = TypeId::new();
+    add_command_range(
+        &mut app,
+        &setup,
+        hq_type,
+        ProximityMeasure::Distance,
+        1,
+        vec![GatedAction::Move],
+    );
+    let blue = TypeId::new();
+    let hq = spawn_owned_unit(&mut app, &setup, (0, 0), hq_type, blue);
+    let unit = spawn_owned_unit(&mut app, &setup, (2, 0), setup.unit_type_id, blue);
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(hq);
+    app.update();
+
+    let radius = app.world().resource::<CommandRadius>();
+    assert_eq!(radius.source, Some(hq));
+    assert_eq!(radius.hexes.len(), 7);
+    assert!(radius.hexes.contains(&HexPosition::new(1, 0)));
+    assert!(!radius.hexes.contains(&HexPosition::new(2, 0)));
+
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+    app.update();
+    assert_eq!(
+        *app.world().resource::<CommandRadius>(),
+        CommandRadius::default()
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Headless evaluation (RulesContext)
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::game_system::{EnumRegistry, GameSystem, StructRegistry, UnitId};
+use hexorder_contracts::mechanics::{
+    AccumulatorRegistry, CombatModifierRegistry, CombatTableRegistry, MovementInterruptRegistry,
+    OffMapZoneRegistry, OverrunRegistry, SpawnSchedule, VictoryConditionRegistry,
+};
+use hexorder_contracts::persistence::{FORMAT_VERSION, GameSystemFile, TileSaveData, UnitSaveData};
+
+use crate::{BoardSnapshot, RulesContext};
+
+/// Saves the app's rule resources with a radius-3 board of the setup's
+/// tiles (terrain cost `cost`) and `units` of the setup's unit type.
+fn headless_file(
+    app: &App,
+    setup: &MotionSetup,
+    cost: i64,
+    units: &[(HexPosition, Option<TypeId>)],
+) -> GameSystemFile {
+    let world = app.world();
+    let config = world.resource::<HexGridConfig>();
+    let tiles = config
+        .positions()
+        .into_iter()
+        .map(|position| TileSaveData {
+            position,
+            entity_type_id: setup.tile_type_id,
+            properties: HashMap::from([(setup.cost_prop_id, PropertyValue::Int(cost))]),
+            state: None,
+        })
+        .collect();
+    let units = units
+        .iter()
+        .map(|&(position, state)| UnitSaveData {
+            position,
+            entity_type_id: setup.unit_type_id,
+            properties: world
+                .resource::<EntityTypeRegistry>()
+                .get(setup.unit_type_id)
+                .map(|t| {
+                    t.properties
+                        .iter()
+                        .map(|p| (p.id, p.default_value.clone()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            owner: None,
+            id: UnitId::new(),
+            state,
+        })
+        .collect();
+    GameSystemFile {
+        format_version: FORMAT_VERSION,
+        name: "Headless".to_string(),
+        game_system: GameSystem {
+            id: "headless".to_string(),
+            version: "0.1.0".to_string(),
+        },
+        entity_types: world.resource::<EntityTypeRegistry>().clone(),
+        enums: EnumRegistry::default(),
+        structs: StructRegistry::default(),
+        concepts: world.resource::<ConceptRegistry>().clone(),
+        relations: world.resource::<RelationRegistry>().clone(),
+        constraints: world.resource::<ConstraintRegistry>().clone(),
+        turn_structure: TurnStructure::default(),
+        combat_results_table: CombatResultsTable::default(),
+        combat_modifiers: CombatModifierRegistry::default(),
+        map_radius: config.map_radius,
+        grid_shape: config.shape,
+        tiles,
+        units,
+        workspace_preset: String::new(),
+        font_size_base: 15.0,
+        edge_features: HexEdgeRegistry::default(),
+        vertex_features: HexVertexRegistry::default(),
+        influence_rules: InfluenceRuleRegistry::default(),
+        stacking_rule: world.resource::<StackingRule>().clone(),
+        movement_cost_matrix: MovementCostMatrix::default(),
+        spawn_schedule: SpawnSchedule::default(),
+        accumulator_registry: AccumulatorRegistry::default(),
+        victory_conditions: VictoryConditionRegistry::default(),
+        off_map_zones: OffMapZoneRegistry::default(),
+        factions: hexorder_contracts::game_system::FactionRegistry::default(),
+        state_machines: world.resource::<StateMachineRegistry>().clone(),
+        reachability_rules: ReachabilityRuleRegistry::default(),
+        movement_interrupts: MovementInterruptRegistry::default(),
+        overruns: OverrunRegistry::default(),
+        combat_tables: CombatTableRegistry::default(),
+    }
+}
+
+/// REQ-25 / SC-22: a saved game system answers the same valid moves without
+/// an app as the ECS system does for the same board.
+#[test]
+fn headless_valid_moves_match_ecs() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    spawn_selected_unit(&mut app, &setup, 2, Vec::new());
+    app.update();
+    let expected = app
+        .world()
+        .resource::<ValidMoveSet>()
+        .valid_positions
+        .clone();
+
+    let file = headless_file(&app, &setup, 1, &[(HexPosition::new(0, 0), None)]);
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
+    let influence = rules.influence_map(&board);
+    let moves = rules.valid_moves(&board, 0, &influence);
+
+    assert_eq!(moves.valid_positions, expected);
+    assert_eq!(moves.valid_positions.len(), 18);
+    assert_eq!(moves.for_entity, None);
+}
+
+#[test]
+fn headless_path_costs_follow_terrain() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 6, 2);
+    let file = headless_file(&app, &setup, 2, &[(HexPosition::new(0, 0), None)]);
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
+
+    assert_eq!(rules.budget(&board.units[0]), 6);
+    let costs = rules.path_costs(&board, 0, 4);
+    assert_eq!(costs.get(&HexPosition::new(0, 0)), Some(&0));
+    assert_eq!(costs.get(&HexPosition::new(1, 0)), Some(&2));
+    assert_eq!(costs.get(&HexPosition::new(2, 0)), Some(&4));
+    assert!(!costs.contains_key(&HexPosition::new(3, 0)));
+}
+
+#[test]
+fn headless_stacking_checks_board() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+    app.insert_resource(StackingRule {
+        max_units: 2,
+        ..StackingRule::default()
+    });
+    let origin = HexPosition::new(0, 0);
+    let file = headless_file(&app, &setup, 1, &[(origin, None), (origin, None)]);
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
+    let data = &snapshot.units[0].1;
+    let owner = snapshot.units[0].2;
+
+    assert!(!rules.stacking_allows(&board, origin, data, owner));
+    assert!(rules.stacking_allows(&board, HexPosition::new(1, 0), data, owner));
+    assert!(rules.stacking_violations(&board).is_empty());
+}
+
+#[test]
+fn headless_constraint_reads_unit_and_tile() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 2, 1);
+    let file = headless_file(&app, &setup, 1, &[(HexPosition::new(0, 0), None)]);
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
+    let constraint = |value| Constraint {
+        id: TypeId::new(),
+        name: "Fresh".to_string(),
+        description: String::new(),
+        concept_id: setup.concept_id,
+        relation_id: None,
+        expression: ConstraintExpr::PropertyCompare {
+            role_id: setup.traveler_role_id,
+            property_name: "budget".to_string(),
+            operator: CompareOp::Ge,
+            value: PropertyValue::Int(value),
+        },
+        auto_generated: false,
+        gates: Vec::new(),
+    };
+
+    let result = rules.check_constraint(&board, 0, &constraint(2));
+    assert!(result.satisfied, "{}", result.explanation);
+    assert_eq!(result.constraint_name, "Fresh");
+    assert!(!rules.check_constraint(&board, 0, &constraint(3)).satisfied);
+}
+
+#[test]
+fn snapshot_starts_units_in_initial_state() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let states = add_step_machine(&mut app, &setup);
+    let file = headless_file(
+        &app,
+        &setup,
+        1,
+        &[
+            (HexPosition::new(0, 0), None),
+            (HexPosition::new(1, 0), Some(states.reduced)),
+            (HexPosition::new(2, 0), Some(TypeId::new())),
+        ],
+    );
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
+
+    assert_eq!(board.units[0].state, Some(states.full));
+    assert_eq!(board.units[1].state, Some(states.reduced));
+    assert_eq!(board.units[2].state, Some(states.full));
+    assert_eq!(rules.budget(&board.units[0]), 4);
+    assert_eq!(rules.budget(&board.units[1]), 1);
+}
+
+// ---------------------------------------------------------------------------
+// Move cache
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::game_system::ActiveFaction;
+
+use crate::systems::MoveCache;
+
+/// Two blue units four hexes apart on a radius-3 board of cost-2 terrain,
+/// each able to move two hexes.
+fn cache_app() -> (App, MotionSetup, Entity, Entity) {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 2);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 2);
+    let blue = TypeId::new();
+    let a = spawn_owned_unit(&mut app, &setup, (-2, 0), setup.unit_type_id, blue);
+    let b = spawn_owned_unit(&mut app, &setup, (2, 0), setup.unit_type_id, blue);
+    (app, setup, a, b)
+}
+
+fn select(app: &mut App, unit: Entity) {
+    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
+    app.update();
+}
+
+fn cached(app: &App) -> Vec<Entity> {
+    let mut units: Vec<Entity> = app
+        .world()
+        .resource::<MoveCache>()
+        .moves
+        .keys()
+        .copied()
+        .collect();
+    units.sort();
+    units
+}
+
+/// REQ-26 / SC-23: reselecting a unit reuses its cached move set.
+#[test]
+fn move_cache_keeps_sets_across_selection() {
+    let (mut app, _, a, b) = cache_app();
+    select(&mut app, a);
+    let first = app
+        .world()
+        .resource::<ValidMoveSet>()
+        .valid_positions
+        .clone();
+    select(&mut app, b);
+    assert_eq!(app.world().resource::<ValidMoveSet>().for_entity, Some(b));
+
+    select(&mut app, a);
+    let mut expected = vec![a, b];
+    expected.sort();
+    assert_eq!(cached(&app), expected);
+    let moves = app.world().resource::<ValidMoveSet>();
+    assert_eq!(moves.for_entity, Some(a));
+    assert_eq!(moves.valid_positions, first);
+}
+
+#[test]
+fn unit_move_invalidates_cached_sets() {
+    let (mut app, _, a, b) = cache_app();
+    select(&mut app, b);
+    select(&mut app, a);
+
+    *app.world_mut().get_mut::<HexPosition>(b).expect("position") = HexPosition::new(0, 2);
+    app.update();
+    assert_eq!(cached(&app), vec![a]);
+}
+
+#[test]
+fn tile_edit_invalidates_cached_sets() {
+    let (mut app, setup, a, _) = cache_app();
+    select(&mut app, a);
+    assert_eq!(
+        app.world().resource::<ValidMoveSet>().valid_positions.len(),
+        13
+    );
+
+    let mut tiles = app
+        .world_mut()
+        .query_filtered::<&mut EntityData, With<HexTile>>();
+    for mut data in tiles.iter_mut(app.world_mut()) {
+        data.properties
+            .insert(setup.cost_prop_id, PropertyValue::Int(4));
+    }
+    app.update();
+    assert_eq!(
+        app.world().resource::<ValidMoveSet>().valid_positions.len(),
+        6
+    );
+}
+
+#[test]
+fn spent_movement_invalidates_only_its_set() {
+    let (mut app, _, a, b) = cache_app();
+    select(&mut app, b);
+    select(&mut app, a);
+
+    app.world_mut().entity_mut(b).insert(MovementSpent(1));
+    app.update();
+    assert_eq!(cached(&app), vec![a]);
+}
+
+#[test]
+fn unit_state_change_invalidates_every_set() {
+    let (mut app, _, a, b) = cache_app();
+    select(&mut app, b);
+    select(&mut app, a);
+
+    app.world_mut()
+        .entity_mut(a)
+        .insert(ReachabilityStatus::default());
+    app.update();
+    assert_eq!(cached(&app), vec![a]);
+    select(&mut app, b);
+    let mut expected = vec![a, b];
+    expected.sort();
+    assert_eq!(cached(&app), expected);
+}
+
+/// One unit entering a state that raises its stacking points closes its
+/// hex to another unit's cached move set.
+#[test]
+fn unit_state_change_updates_other_units_moves() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    let size_prop_id = TypeId::new();
+    app.insert_resource(StackingRule {
+        max_units: 2,
+        points_property_id: Some(size_prop_id),
+        ..Default::default()
+    });
+    let (ready, dug_in) = (TypeId::new(), TypeId::new());
+    let state = |id, name: &str, overrides: Vec<(TypeId, PropertyValue)>| StateDefinition {
+        id,
+        name: name.to_string(),
+        property_overrides: overrides.into_iter().collect(),
+        color: None,
+        badge: String::new(),
+    };
+    app.insert_resource(StateMachineRegistry {
+        machines: vec![StateMachine {
+            id: TypeId::new(),
+            name: "Posture".to_string(),
+            entity_type_id: setup.unit_type_id,
+            states: vec![
+                state(ready, "Ready", Vec::new()),
+                state(
+                    dug_in,
+                    "Dug In",
+                    vec![(size_prop_id, PropertyValue::Int(2))],
+                ),
+            ],
+            initial_state: Some(ready),
+            transitions: Vec::new(),
+        }],
+    });
+    let blocker = spawn_unit(
+        &mut app,
+        1,
+        0,
+        traveler_data(&setup, 4, &[(size_prop_id, 1)]),
+    );
+    let mover = spawn_unit(
+        &mut app,
+        0,
+        0,
+        traveler_data(&setup, 4, &[(size_prop_id, 1)]),
+    );
+    select(&mut app, mover);
+    let target = HexPosition::new(1, 0);
+    assert!(
+        app.world()
+            .resource::<ValidMoveSet>()
+            .valid_positions
+            .contains(&target)
+    );
 
-    let budget_prop_id = TypeId::new();
-    let cost_prop_id = TypeId::new();
-    let class_prop_id = TypeId::new();
-    let enum_id = TypeId::new();
+    app.world_mut().commands().trigger(SetEntityStateEvent {
+        entity: blocker,
+        state_id: dug_in,
+    });
+    app.update();
+    assert!(
+        !app.world()
+            .resource::<ValidMoveSet>()
+            .valid_positions
+            .contains(&target)
+    );
+}
 
-    // -- Concept IDs --
-    let concept_id = TypeId::new();
-    let traveler_role_id = TypeId::new();
-    let terrain_role_id = TypeId::new();
+#[test]
+fn play_precomputes_active_faction_moves() {
+    let (mut app, _) = play_app_with_phases();
+    let setup = setup_motion_ontology(&mut app, 4, 2);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 2);
+    let blue = TypeId::new();
+    let red = TypeId::new();
+    let mut expected: Vec<Entity> = [(-2, 0), (2, 0), (0, 2)]
+        .into_iter()
+        .map(|pos| spawn_owned_unit(&mut app, &setup, pos, setup.unit_type_id, blue))
+        .collect();
+    expected.sort();
+    spawn_owned_unit(&mut app, &setup, (0, -2), setup.unit_type_id, red);
+    app.insert_resource(ActiveFaction {
+        faction_id: Some(blue),
+    });
+    // At least one unit per frame, whatever the time budget allows.
+    for _ in &expected {
+        app.update();
+    }
 
-    // -- Register entity types --
-    let mut registry = EntityTypeRegistry::default();
+    assert_eq!(cached(&app), expected);
+    assert_eq!(app.world().resource::<ValidMoveSet>().for_entity, None);
+}
 
-    // Infantry: Token with movement_points and classification enum
-    registry.types.push(EntityType {
-        id: infantry_type_id,
-        name: "Infantry".to_string(),
-        role: EntityRole::Token,
-        color: bevy::color::Color::WHITE,
-        properties: vec![
-            PropertyDefinition {
-                id: budget_prop_id,
-                name: "movement_points".to_string(),
-                property_type: PropertyType::Int,
-                default_value: PropertyValue::Int(4),
-            },
-            PropertyDefinition {
-                id: class_prop_id,
-                name: "Movement Mode".to_string(),
-                property_type: PropertyType::Enum(enum_id),
-                default_value: PropertyValue::Enum("Foot".to_string()),
+// ---------------------------------------------------------------------------
+// Rule analysis
+// ---------------------------------------------------------------------------
+
+fn analysis_categories(app: &App) -> Vec<AnalysisCategory> {
+    app.world()
+        .resource::<RuleAnalysis>()
+        .findings
+        .iter()
+        .map(|f| f.category)
+        .collect()
+}
+
+#[test]
+fn rule_analysis_follows_rules_and_placed_data() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 0);
+    app.update();
+    // Every tile costs nothing, so the cost relation never changes the budget.
+    assert_eq!(analysis_categories(&app), vec![AnalysisCategory::NoEffect]);
+
+    spawn_hex_grid_with_properties(&mut app, 1, setup.tile_type_id, setup.cost_prop_id, 2);
+    app.update();
+    assert!(analysis_categories(&app).is_empty());
+
+    app.world_mut()
+        .resource_mut::<TurnStructure>()
+        .phases
+        .push(Phase {
+            id: TypeId::new(),
+            name: "Reinforcements".to_string(),
+            phase_type: PhaseType::Admin,
+            description: String::new(),
+        });
+    app.update();
+    assert_eq!(
+        analysis_categories(&app),
+        vec![AnalysisCategory::EmptyPhase]
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Movement interrupts
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::mechanics::{
+    ActiveInterrupt, InterruptOutcome, InterruptResolution, InterruptTrigger, MoveRequestedEvent,
+    MovementInterruptRule, ResolveInterruptEvent,
+};
+use hexorder_contracts::simulation::{DicePool, RollHistory, SimulationRng, TableResult};
+
+/// A rule whose table always gives "Fire", with `outcome` for that result.
+fn interrupt_rule(trigger: InterruptTrigger, outcome: InterruptOutcome) -> MovementInterruptRule {
+    MovementInterruptRule {
+        id: TypeId::new(),
+        name: "Opportunity Fire".to_string(),
+        trigger,
+        reactor_type: None,
+        mover_type: None,
+        resolution: InterruptResolution::Table {
+            table: ResolutionTable {
+                columns: vec![TableColumn {
+                    label: "Any".to_string(),
+                    column_type: ColumnType::Direct,
+                    threshold: 0.0,
+                }],
+                rows: vec![TableRow {
+                    label: "1".to_string(),
+                    value_min: 1,
+                    value_max: 1,
+                }],
+                outcomes: vec![vec![TableResult::Text("Fire".to_string())]],
+                ..ResolutionTable::default()
             },
+            input_a_key: "reactor.movement_points".to_string(),
+            input_b_key: "mover.movement_points".to_string(),
+            dice: DicePool::single(1),
+        },
+        outcomes: vec![outcome],
+    }
+}
+
+fn fire_outcome(end_movement: bool, set_state: Option<TypeId>) -> InterruptOutcome {
+    InterruptOutcome {
+        result: "Fire".to_string(),
+        end_movement,
+        set_state,
+    }
+}
+
+/// A saved board with a blue mover at (0, 0) and a red unit at (3, 0),
+/// under `rule`.
+fn interrupt_file(app: &App, setup: &MotionSetup, rule: MovementInterruptRule) -> GameSystemFile {
+    let mut file = headless_file(
+        app,
+        setup,
+        1,
+        &[
+            (HexPosition::new(0, 0), None),
+            (HexPosition::new(3, 0), None),
         ],
+    );
+    file.units[0].owner = Some(TypeId::new());
+    file.units[1].owner = Some(TypeId::new());
+    file.movement_interrupts.rules.push(rule);
+    file
+}
+
+const EAST: [HexPosition; 2] = [HexPosition { q: 1, r: 0 }, HexPosition { q: 2, r: 0 }];
+
+#[test]
+fn headless_move_halts_at_first_interrupt() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let halt = interrupt_rule(
+        InterruptTrigger::Proximity { max: 1 },
+        fire_outcome(true, None),
+    );
+    let file = interrupt_file(&app, &setup, halt);
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
+    let mut rng = SimulationRng::new(1);
+
+    let report = rules.resolve_move(&board, 0, &EAST, &mut rng);
+    assert_eq!(report.entered, 2, "halts in (2, 0), next to the red unit");
+    assert_eq!(report.interrupts.len(), 1);
+    let (interrupt, result) = &report.interrupts[0];
+    assert_eq!(interrupt.pos, HexPosition::new(2, 0));
+    assert_eq!(interrupt.reactor, 1);
+    assert_eq!(result.result.as_deref(), Some("Fire"));
+    assert_eq!(
+        rules.interrupt_values(&board, 1, 0, interrupt.pos)["reactor.movement_points"],
+        4.0
+    );
+
+    // A unit never interrupts its own side.
+    let mut allied = file.clone();
+    allied.units[1].owner = allied.units[0].owner;
+    let snapshot = BoardSnapshot::from_file(&allied);
+    let report = rules.resolve_move(&snapshot.board(), 0, &EAST, &mut rng);
+    assert_eq!(report.entered, 2);
+    assert_eq!(report.interrupts, []);
+}
+
+#[test]
+fn headless_move_continues_and_reacts_once() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let state = TypeId::new();
+    let pass = interrupt_rule(
+        InterruptTrigger::Proximity { max: 2 },
+        fire_outcome(false, Some(state)),
+    );
+    let file = interrupt_file(&app, &setup, pass);
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
+
+    let report = rules.resolve_move(&board, 0, &EAST, &mut SimulationRng::new(1));
+    assert_eq!(report.entered, 2);
+    assert_eq!(report.interrupts.len(), 1, "the red unit reacts once");
+    assert_eq!(report.interrupts[0].0.pos, HexPosition::new(1, 0));
+    assert_eq!(report.state, Some(state));
+}
+
+#[test]
+fn line_of_sight_interrupt_needs_range_and_clear_sight() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let in_sight = |blockers| {
+        let rule = interrupt_rule(
+            InterruptTrigger::LineOfSight { blockers },
+            fire_outcome(true, None),
+        );
+        let file = interrupt_file(&app, &setup, rule);
+        let snapshot = BoardSnapshot::from_file(&file);
+        let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+        [None, Some(2)].map(|sight| {
+            let mut board = snapshot.board();
+            board.units[1].sight = sight;
+            rules
+                .movement_interrupt(&board, 0, &EAST[..1], &InfluenceMap::default(), &[])
+                .is_some()
+        })
+    };
+    // (1, 0) is two hexes from the red unit, seen across (2, 0).
+    assert_eq!(in_sight(Vec::new()), [false, true]);
+    assert_eq!(in_sight(vec![setup.tile_type_id]), [false, false]);
+}
+
+#[test]
+fn influence_interrupt_follows_reactor_zone() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let zone = InfluenceRule {
+        id: TypeId::new(),
+        entity_type_id: setup.unit_type_id,
+        range: 1,
+        cost_modifier: 0,
+        zone: ZoneOfControl::default(),
+        enemy_only: true,
+    };
+    let rule = interrupt_rule(
+        InterruptTrigger::Influence { rule_id: zone.id },
+        fire_outcome(true, None),
+    );
+    let mut file = interrupt_file(&app, &setup, rule);
+    file.influence_rules.rules.push(zone);
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
+    let influence = rules.influence_map(&board);
+
+    let interrupt = rules.movement_interrupt(&board, 0, &EAST, &influence, &[]);
+    assert_eq!(interrupt.map(|i| (i.step, i.reactor)), Some((1, 1)));
+    let resolved = [(file.movement_interrupts.rules[0].id, 1)];
+    assert_eq!(
+        rules.movement_interrupt(&board, 0, &EAST, &influence, &resolved),
+        None
+    );
+}
+
+/// A Play board with a blue mover at (-2, 0) whose route to (0, 0) passes
+/// (-1, 0), next to a red unit at (0, -1), after the mover was asked to move
+/// there. `outcome` gives the rule's outcome for the board's ontology.
+fn interrupt_play_app(
+    outcome: impl FnOnce(&mut App, &MotionSetup) -> InterruptOutcome,
+) -> (App, Entity) {
+    let (mut app, _) = play_app_with_phases();
+    let setup = setup_motion_ontology(&mut app, 4, 2);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 2);
+    app.insert_resource(SimulationRng::new(1));
+    let outcome = outcome(&mut app, &setup);
+    app.insert_resource(MovementInterruptRegistry {
+        rules: vec![interrupt_rule(
+            InterruptTrigger::Proximity { max: 1 },
+            outcome,
+        )],
     });
+    let mover = spawn_owned_unit(&mut app, &setup, (-2, 0), setup.unit_type_id, TypeId::new());
+    spawn_owned_unit(&mut app, &setup, (0, -1), setup.unit_type_id, TypeId::new());
+    select(&mut app, mover);
+    app.world_mut().commands().trigger(MoveRequestedEvent {
+        entity: mover,
+        to: HexPosition::new(0, 0),
+    });
+    app.update();
+    (app, mover)
+}
 
-    // Enemy: Token (projects influence, no movement properties needed)
-    registry.types.push(EntityType {
-        id: enemy_type_id,
-        name: "Enemy".to_string(),
-        role: EntityRole::Token,
-        color: bevy::color::Color::srgb(1.0, 0.0, 0.0),
-        properties: Vec::new(),
+fn position(app: &App, entity: Entity) -> HexPosition {
+    *app.world().get::<HexPosition>(entity).unwrap()
+}
+
+#[test]
+fn play_move_halts_until_interrupt_resolves() {
+    let (mut app, mover) = interrupt_play_app(|_, _| fire_outcome(false, None));
+    assert_eq!(position(&app, mover), HexPosition::new(-1, 0));
+    let pause = app.world().resource::<ActiveInterrupt>().pause.clone();
+    let pause = pause.expect("the move halts next to the red unit");
+    assert_eq!(pause.position, HexPosition::new(-1, 0));
+    assert_eq!(pause.remaining, vec![HexPosition::new(0, 0)]);
+
+    app.world_mut().commands().trigger(ResolveInterruptEvent);
+    app.update();
+    assert_eq!(position(&app, mover), HexPosition::new(0, 0));
+    assert!(app.world().resource::<ActiveInterrupt>().pause.is_none());
+}
+
+#[test]
+fn play_interrupt_outcome_ends_move_and_changes_state() {
+    let mut reduced = None;
+    let (mut app, mover) = interrupt_play_app(|app, setup| {
+        let states = add_step_machine(app, setup);
+        reduced = Some(states.reduced);
+        fire_outcome(true, reduced)
     });
 
-    // Plains: BoardPosition with terrain_cost = 1
-    registry.types.push(EntityType {
-        id: plains_type_id,
-        name: "Plains".to_string(),
-        role: EntityRole::BoardPosition,
-        color: bevy::color::Color::srgb(0.3, 0.6, 0.2),
-        properties: vec![PropertyDefinition {
-            id: cost_prop_id,
-            name: "terrain_cost".to_string(),
-            property_type: PropertyType::Int,
-            default_value: PropertyValue::Int(1),
+    app.world_mut().commands().trigger(ResolveInterruptEvent);
+    app.update();
+    assert_eq!(position(&app, mover), HexPosition::new(-1, 0));
+    assert!(app.world().resource::<ActiveInterrupt>().pause.is_none());
+    assert_eq!(
+        app.world().get::<EntityState>(mover).map(|s| s.state_id),
+        reduced
+    );
+}
+
+/// Adds the simulation plugin's roll history to a Play `app`, keeping the
+/// RNG seeded with 1 as the Play apps here seed it.
+fn record_rolls(app: &mut App) {
+    app.add_plugins(hexorder_simulation::SimulationPlugin);
+    app.insert_resource(SimulationRng::new(1));
+}
+
+#[test]
+fn play_interrupt_roll_is_recorded_in_the_roll_history() {
+    let (mut app, _) = interrupt_play_app(|_, _| fire_outcome(false, None));
+    record_rolls(&mut app);
+
+    app.world_mut().commands().trigger(ResolveInterruptEvent);
+    app.update();
+    let history = app.world().resource::<RollHistory>();
+    assert_eq!(history.entries.len(), 1);
+    let entry = &history.entries[0];
+    assert_eq!(entry.context, "Opportunity Fire");
+    assert!(entry.resolution.is_some());
+}
+
+// ---------------------------------------------------------------------------
+// Overrun
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::mechanics::{
+    MovementSpent, OverrunOutcome, OverrunRequestedEvent, OverrunRule, OverrunTargets, PlayLog,
+};
+use hexorder_contracts::undo_redo::UndoStack;
+
+/// A 2 MP overrun whose table always gives "Fire", which clears the
+/// defender when `clears_defender`.
+fn overrun_rule(clears_defender: bool) -> OverrunRule {
+    let InterruptResolution::Table { table, dice, .. } = interrupt_rule(
+        InterruptTrigger::Proximity { max: 1 },
+        fire_outcome(true, None),
+    )
+    .resolution
+    else {
+        unreachable!("interrupt_rule resolves with a table");
+    };
+    OverrunRule {
+        id: TypeId::new(),
+        name: "Overrun".to_string(),
+        mp_cost: 2,
+        attacker_type: None,
+        defender_type: None,
+        resolution: InterruptResolution::Table {
+            table,
+            input_a_key: "attacker.movement_points".to_string(),
+            input_b_key: "defender.movement_points".to_string(),
+            dice,
+        },
+        outcomes: vec![OverrunOutcome {
+            result: "Fire".to_string(),
+            clears_defender,
+            attacker_state: None,
+            defender_state: None,
         }],
+    }
+}
+
+/// A saved board with a blue attacker at (0, 0) and a red unit at (1, 0),
+/// under `rule`.
+fn overrun_file(app: &App, setup: &MotionSetup, rule: OverrunRule) -> GameSystemFile {
+    let mut file = headless_file(
+        app,
+        setup,
+        1,
+        &[
+            (HexPosition::new(0, 0), None),
+            (HexPosition::new(1, 0), None),
+        ],
+    );
+    file.units[0].owner = Some(TypeId::new());
+    file.units[1].owner = Some(TypeId::new());
+    file.overruns.rules.push(rule);
+    file
+}
+
+#[test]
+fn headless_overrun_clears_defender_and_moves_on_with_remaining_budget() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let file = overrun_file(&app, &setup, overrun_rule(true));
+    let rule = &file.overruns.rules[0];
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let mut board = snapshot.board();
+
+    let report = rules
+        .overrun(&board, 0, 1, rule.id, &mut SimulationRng::new(1))
+        .expect("adjacent enemies with MP to spare");
+    assert_eq!(report.result.result.as_deref(), Some("Fire"));
+    assert!(report.result.clears_defender);
+    assert_eq!(report.spent, 2);
+    assert_eq!(rules.overrun_values(&board, 0, 1)["distance"], 1.0);
+
+    board.units.remove(1);
+    board.units[0].spent = report.spent;
+    let moves = rules.valid_moves(&board, 0, &InfluenceMap::default());
+    assert!(moves.valid_positions.contains(&HexPosition::new(2, 0)));
+    assert!(!moves.valid_positions.contains(&HexPosition::new(3, 0)));
+}
+
+#[test]
+fn headless_overrun_needs_adjacency_and_movement_points() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let file = overrun_file(&app, &setup, overrun_rule(false));
+    let rule = &file.overruns.rules[0];
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let mut board = snapshot.board();
+
+    // A defender that holds ends the attacker's movement.
+    let report = rules.overrun(&board, 0, 1, rule.id, &mut SimulationRng::new(1));
+    assert_eq!(report.map(|r| r.spent), Some(4));
+
+    board.units[0].spent = 3;
+    assert!(!rules.can_overrun(&board, 0, 1, rule));
+    board.units[0].spent = 0;
+    board.units[1].pos = HexPosition::new(2, 0);
+    assert!(!rules.can_overrun(&board, 0, 1, rule));
+    assert_eq!(
+        rules.overrun(&board, 0, 1, rule.id, &mut SimulationRng::new(1)),
+        None
+    );
+}
+
+/// A Play board in its Movement phase with a selected blue attacker at
+/// (0, 0) next to a red defender at (1, 0), under a clearing overrun.
+fn overrun_play_app() -> (App, Entity, Entity, TypeId) {
+    let (mut app, _) = play_app_with_phases();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
+    app.insert_resource(SimulationRng::new(1));
+    app.init_resource::<UndoStack>();
+    let rule = overrun_rule(true);
+    let rule_id = rule.id;
+    app.insert_resource(OverrunRegistry { rules: vec![rule] });
+    let attacker = spawn_owned_unit(&mut app, &setup, (0, 0), setup.unit_type_id, TypeId::new());
+    let defender = spawn_owned_unit(&mut app, &setup, (1, 0), setup.unit_type_id, TypeId::new());
+    select(&mut app, attacker);
+    (app, attacker, defender, rule_id)
+}
+
+#[test]
+fn play_overrun_targets_list_adjacent_enemies() {
+    let (mut app, attacker, defender, rule_id) = overrun_play_app();
+    assert_eq!(
+        *app.world().resource::<OverrunTargets>(),
+        OverrunTargets {
+            attacker: Some(attacker),
+            targets: vec![(defender, rule_id)],
+        }
+    );
+
+    // Only the Movement phase allows overruns.
+    advance_to_phase(&mut app, 1);
+    assert_eq!(app.world().resource::<OverrunTargets>().targets, []);
+}
+
+#[test]
+fn play_overrun_clears_defender_logs_and_undoes() {
+    let (mut app, attacker, defender, rule_id) = overrun_play_app();
+    app.world_mut().commands().trigger(OverrunRequestedEvent {
+        attacker,
+        defender,
+        rule_id,
+    });
+    app.update();
+    app.update();
+
+    assert!(app.world().get_entity(defender).is_err());
+    assert_eq!(
+        app.world().get::<MovementSpent>(attacker),
+        Some(&MovementSpent(2))
+    );
+    assert_eq!(app.world().resource::<PlayLog>().entries.len(), 1);
+    let moves = &app.world().resource::<ValidMoveSet>().valid_positions;
+    assert!(moves.contains(&HexPosition::new(2, 0)));
+    assert!(!moves.contains(&HexPosition::new(3, 0)));
+
+    let mut command = app
+        .world_mut()
+        .resource_mut::<UndoStack>()
+        .pop_undo()
+        .expect("the overrun is recorded");
+    command.undo(app.world_mut());
+    app.update();
+    let mut units = app
+        .world_mut()
+        .query_filtered::<&HexPosition, With<UnitInstance>>();
+    let mut positions: Vec<_> = units.iter(app.world()).copied().collect();
+    positions.sort_by_key(|p| (p.q, p.r));
+    assert_eq!(positions, [HexPosition::new(0, 0), HexPosition::new(1, 0)]);
+    assert_eq!(
+        app.world().get::<MovementSpent>(attacker),
+        Some(&MovementSpent(0))
+    );
+    assert!(app.world().resource::<PlayLog>().entries.is_empty());
+}
+
+#[test]
+fn play_overrun_roll_is_recorded_in_the_roll_history() {
+    let (mut app, attacker, defender, rule_id) = overrun_play_app();
+    record_rolls(&mut app);
+    app.world_mut().commands().trigger(OverrunRequestedEvent {
+        attacker,
+        defender,
+        rule_id,
+    });
+    app.update();
+
+    let history = app.world().resource::<RollHistory>();
+    assert_eq!(history.entries.len(), 1);
+    let entry = &history.entries[0];
+    assert_eq!(entry.context, "Overrun");
+    let resolution = entry.resolution.as_ref().expect("the table lookup");
+    assert_eq!(resolution.result, TableResult::Text("Fire".to_string()));
+}
+
+#[test]
+fn play_move_spends_movement_points_until_the_phase_ends() {
+    let (mut app, attacker, _, _) = overrun_play_app();
+    app.world_mut().commands().trigger(MoveRequestedEvent {
+        entity: attacker,
+        to: HexPosition::new(-2, 0),
+    });
+    app.update();
+    assert_eq!(
+        app.world().get::<MovementSpent>(attacker),
+        Some(&MovementSpent(2))
+    );
+
+    advance_to_phase(&mut app, 1);
+    assert_eq!(
+        app.world().get::<MovementSpent>(attacker),
+        Some(&MovementSpent(0))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Combat outcomes
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::mechanics::{
+    ActiveCombat, AdvanceOption, CombatMoveEvent, OffMapZone, PostResolutionAction,
+};
+
+/// A radius-3 board with a blue attacker at (0, 0) next to a red defender
+/// at (1, 0). The CRT reads step losses from the units' budget, bound as
+/// "steps", and eliminated units go to the "Eliminated" zone. Returns the
+/// blue faction too.
+fn combat_outcome_app() -> (App, MotionSetup, Entity, Entity, TypeId) {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    spawn_hex_grid(&mut app, 3, setup.tile_type_id);
+    bind_unit_property(&mut app, &setup, setup.budget_prop_id, "steps");
+    app.insert_resource(CombatResultsTable {
+        combat_concept_id: Some(setup.concept_id),
+        losses: LossRules {
+            steps_property: Some("steps".to_string()),
+            ..LossRules::default()
+        },
+        ..CombatResultsTable::default()
+    });
+    let zone = OffMapZone::new("Eliminated");
+    app.insert_resource(OffMapZoneRegistry {
+        eliminated_zone_id: Some(zone.id),
+        zones: vec![zone],
     });
+    app.init_resource::<UndoStack>();
+    let blue = TypeId::new();
+    let attacker = spawn_owned_unit(&mut app, &setup, (0, 0), setup.unit_type_id, blue);
+    let defender = spawn_owned_unit(&mut app, &setup, (1, 0), setup.unit_type_id, TypeId::new());
+    app.update();
+    (app, setup, attacker, defender, blue)
+}
+
+fn resolve_combat(app: &mut App, attacker: Entity, defender: Entity, effect: OutcomeEffect) {
+    app.world_mut().commands().trigger(CombatResolvedEvent {
+        attacker,
+        defender,
+        attackers: Vec::new(),
+        defenders: Vec::new(),
+        step_losses: Vec::new(),
+        outcome: CombatOutcome {
+            label: "R".to_string(),
+            effect: Some(effect),
+        },
+        die_roll: 4,
+        column_label: "2:1".to_string(),
+    });
+    app.update();
+}
+
+fn position_of(app: &App, entity: Entity) -> Option<HexPosition> {
+    app.world().get::<HexPosition>(entity).copied()
+}
+
+fn undo_last(app: &mut App) {
+    let mut command = app
+        .world_mut()
+        .resource_mut::<UndoStack>()
+        .pop_undo()
+        .expect("an action is recorded");
+    command.undo(app.world_mut());
+    app.update();
+}
+
+#[test]
+fn combat_outcome_steps_properties_down_then_eliminates_to_the_zone() {
+    let (mut app, setup, attacker, defender, _) = combat_outcome_app();
+    resolve_combat(
+        &mut app,
+        attacker,
+        defender,
+        OutcomeEffect::StepLoss { steps: 3 },
+    );
+    assert_eq!(budget_of(&app, defender, &setup), PropertyValue::Int(1));
+
+    resolve_combat(
+        &mut app,
+        attacker,
+        defender,
+        OutcomeEffect::StepLoss { steps: 1 },
+    );
+    assert!(app.world().get_entity(defender).is_err());
+    let zones = app.world().resource::<OffMapZoneRegistry>();
+    assert_eq!(zones.zones[0].units.len(), 1);
+    assert_eq!(
+        app.world().resource::<ActiveCombat>().vacated,
+        [HexPosition::new(1, 0)]
+    );
+    let log = &app.world().resource::<PlayLog>().entries;
+    assert_eq!(log.len(), 2);
+    assert!(log[1].message.ends_with("eliminated"), "{}", log[1].message);
+
+    // Undo puts the unit back with the step it had left.
+    undo_last(&mut app);
+    assert!(
+        app.world().resource::<OffMapZoneRegistry>().zones[0]
+            .units
+            .is_empty()
+    );
+    let mut units = app
+        .world_mut()
+        .query_filtered::<(&HexPosition, &EntityData), With<UnitInstance>>();
+    let restored: Vec<_> = units
+        .iter(app.world())
+        .filter(|(pos, _)| **pos == HexPosition::new(1, 0))
+        .map(|(_, data)| data.properties[&setup.budget_prop_id].clone())
+        .collect();
+    assert_eq!(restored, [PropertyValue::Int(1)]);
+    assert_eq!(app.world().resource::<PlayLog>().entries.len(), 1);
+}
 
-    // Forest: BoardPosition with terrain_cost = 2
-    registry.types.push(EntityType {
-        id: forest_type_id,
-        name: "Forest".to_string(),
-        role: EntityRole::BoardPosition,
-        color: bevy::color::Color::srgb(0.1, 0.4, 0.1),
-        properties: vec![PropertyDefinition {
-            id: cost_prop_id,
-            name: "terrain_cost".to_string(),
-            property_type: PropertyType::Int,
-            default_value: PropertyValue::Int(2),
-        }],
+#[test]
+fn combat_outcome_eliminates_a_whole_side() {
+    let (mut app, setup, attacker, defender, _) = combat_outcome_app();
+    let red = app
+        .world()
+        .get::<UnitOwner>(defender)
+        .and_then(|o| o.faction_id);
+    let second = spawn_owned_unit(&mut app, &setup, (1, 0), setup.unit_type_id, TypeId::new());
+    app.world_mut()
+        .entity_mut(second)
+        .insert(UnitOwner { faction_id: red });
+    app.update();
+    app.world_mut().commands().trigger(CombatResolvedEvent {
+        attacker,
+        defender,
+        attackers: vec![attacker],
+        defenders: vec![defender, second],
+        step_losses: Vec::new(),
+        outcome: CombatOutcome {
+            label: "DE".to_string(),
+            effect: Some(OutcomeEffect::DefenderEliminated),
+        },
+        die_roll: 6,
+        column_label: "3:1".to_string(),
     });
+    app.update();
 
-    // River: edge type with cost = 1
-    registry.types.push(EntityType {
-        id: river_type_id,
-        name: "River".to_string(),
-        role: EntityRole::BoardPosition,
-        color: bevy::color::Color::srgb(0.2, 0.4, 0.8),
-        properties: vec![PropertyDefinition {
-            id: TypeId::new(),
-            name: "cost".to_string(),
-            property_type: PropertyType::Int,
-            default_value: PropertyValue::Int(1),
-        }],
-    });
+    assert!(app.world().get_entity(defender).is_err());
+    assert!(app.world().get_entity(second).is_err());
+    assert_eq!(
+        app.world().resource::<OffMapZoneRegistry>().zones[0]
+            .units
+            .len(),
+        2
+    );
+    assert_eq!(position_of(&app, attacker), Some(HexPosition::new(0, 0)));
+}
 
-    app.insert_resource(registry);
+#[test]
+fn combat_retreat_follows_the_only_path_away() {
+    let (mut app, setup, attacker, defender, blue) = combat_outcome_app();
+    // Blue units close off two of the three hexes away from the attacker.
+    for pos in [(2, -1), (1, 1)] {
+        spawn_owned_unit(&mut app, &setup, pos, setup.unit_type_id, blue);
+    }
+    app.update();
+    resolve_combat(
+        &mut app,
+        attacker,
+        defender,
+        OutcomeEffect::Retreat { hexes: 1 },
+    );
 
-    // -- Ontology: Motion concept --
-    let concept = Concept {
-        id: concept_id,
-        name: "Motion".to_string(),
-        description: "Movement across terrain".to_string(),
-        role_labels: vec![
-            ConceptRole {
-                id: traveler_role_id,
-                name: "traveler".to_string(),
-                allowed_entity_roles: vec![EntityRole::Token],
-            },
-            ConceptRole {
-                id: terrain_role_id,
-                name: "terrain".to_string(),
-                allowed_entity_roles: vec![EntityRole::BoardPosition],
-            },
-        ],
-    };
+    assert_eq!(position_of(&app, defender), Some(HexPosition::new(2, 0)));
+    let combat = app.world().resource::<ActiveCombat>();
+    assert!(combat.retreats.is_empty());
+    assert_eq!(combat.vacated, [HexPosition::new(1, 0)]);
 
-    let infantry_binding = ConceptBinding {
-        id: TypeId::new(),
-        entity_type_id: infantry_type_id,
-        concept_id,
-        concept_role_id: traveler_role_id,
-        property_bindings: vec![PropertyBinding {
-            property_id: budget_prop_id,
-            concept_local_name: "budget".to_string(),
-        }],
-    };
-    let plains_binding = ConceptBinding {
-        id: TypeId::new(),
-        entity_type_id: plains_type_id,
-        concept_id,
-        concept_role_id: terrain_role_id,
-        property_bindings: vec![PropertyBinding {
-            property_id: cost_prop_id,
-            concept_local_name: "cost".to_string(),
-        }],
-    };
-    let forest_binding = ConceptBinding {
-        id: TypeId::new(),
-        entity_type_id: forest_type_id,
-        concept_id,
-        concept_role_id: terrain_role_id,
-        property_bindings: vec![PropertyBinding {
-            property_id: cost_prop_id,
-            concept_local_name: "cost".to_string(),
-        }],
-    };
+    undo_last(&mut app);
+    assert_eq!(position_of(&app, defender), Some(HexPosition::new(1, 0)));
+}
 
-    app.insert_resource(ConceptRegistry {
-        concepts: vec![concept],
-        bindings: vec![infantry_binding, plains_binding, forest_binding],
-    });
+#[test]
+fn combat_retreat_without_a_path_eliminates() {
+    let (mut app, setup, attacker, defender, blue) = combat_outcome_app();
+    for pos in [(2, 0), (2, -1), (1, 1)] {
+        spawn_owned_unit(&mut app, &setup, pos, setup.unit_type_id, blue);
+    }
+    app.update();
+    resolve_combat(
+        &mut app,
+        attacker,
+        defender,
+        OutcomeEffect::Retreat { hexes: 1 },
+    );
 
-    // Relation: terrain cost subtracts from movement budget.
-    app.insert_resource(RelationRegistry {
-        relations: vec![Relation {
-            id: TypeId::new(),
-            name: "Terrain Movement Cost".to_string(),
-            concept_id,
-            subject_role_id: traveler_role_id,
-            object_role_id: terrain_role_id,
-            trigger: RelationTrigger::OnEnter,
-            effect: RelationEffect::ModifyProperty {
-                target_property: "budget".to_string(),
-                source_property: "cost".to_string(),
-                operation: ModifyOperation::Subtract,
-            },
-        }],
+    assert!(app.world().get_entity(defender).is_err());
+    assert_eq!(
+        app.world().resource::<OffMapZoneRegistry>().zones[0]
+            .units
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn combat_retreat_choice_then_advance_after_combat() {
+    let (mut app, _, attacker, defender, _) = combat_outcome_app();
+    resolve_combat(
+        &mut app,
+        attacker,
+        defender,
+        OutcomeEffect::Retreat { hexes: 1 },
+    );
+
+    // Three hexes are equally far from the attacker: the player chooses.
+    assert_eq!(position_of(&app, defender), Some(HexPosition::new(1, 0)));
+    let choice = app.world().resource::<ActiveCombat>().retreats[0].clone();
+    assert_eq!(choice.units, [defender]);
+    assert_eq!(choice.paths.len(), 3);
+    assert!(app.world().resource::<ActiveCombat>().vacated.is_empty());
+
+    let path = choice.paths[1].clone();
+    app.world_mut().commands().trigger(CombatMoveEvent {
+        units: vec![defender],
+        path: path.clone(),
+        action: PostResolutionAction::Retreat,
     });
+    app.update();
+    assert_eq!(position_of(&app, defender), path.last().copied());
+    let combat = app.world().resource::<ActiveCombat>();
+    assert!(combat.retreats.is_empty());
+    assert_eq!(combat.vacated, [HexPosition::new(1, 0)]);
+
+    // Advancing anywhere but a vacated hex is refused.
+    let advance = |to: (i32, i32)| CombatMoveEvent {
+        units: vec![attacker],
+        path: vec![HexPosition::new(0, 0), HexPosition::new(to.0, to.1)],
+        action: PostResolutionAction::Advance,
+    };
+    app.world_mut().commands().trigger(advance((-1, 0)));
+    app.update();
+    assert_eq!(position_of(&app, attacker), Some(HexPosition::new(0, 0)));
 
-    // -- Primitive 1: Edge features (River) --
-    // River between (0,0) and (0,-1).
-    let edge =
-        HexEdge::between(HexPosition::new(0, 0), HexPosition::new(0, -1)).expect("adjacent hexes");
-    app.world_mut().resource_mut::<HexEdgeRegistry>().insert(
-        edge,
-        EdgeFeature {
-            type_name: "River".to_string(),
-        },
+    app.world_mut().commands().trigger(advance((1, 0)));
+    app.update();
+    assert_eq!(position_of(&app, attacker), Some(HexPosition::new(1, 0)));
+    assert!(app.world().resource::<ActiveCombat>().vacated.is_empty());
+    let log = &app.world().resource::<PlayLog>().entries;
+    assert_eq!(log.len(), 3);
+    assert!(
+        log[2].message.contains("advances to (1, 0)"),
+        "{}",
+        log[2].message
     );
 
-    // -- Primitive 2: Spatial influence (ZOC) --
-    // Enemy projects +2 cost into adjacent hexes (range 1).
-    app.insert_resource(InfluenceRuleRegistry {
-        rules: vec![InfluenceRule {
-            id: TypeId::new(),
-            entity_type_id: enemy_type_id,
-            range: 1,
-            cost_modifier: 2,
-        }],
+    undo_last(&mut app);
+    assert_eq!(position_of(&app, attacker), Some(HexPosition::new(0, 0)));
+    assert_eq!(app.world().resource::<PlayLog>().entries.len(), 2);
+}
+
+/// Resolves a retreat of the units `defenders` from `attacker`.
+fn resolve_stack_retreat(app: &mut App, attacker: Entity, defenders: Vec<Entity>, hexes: u32) {
+    app.world_mut().commands().trigger(CombatResolvedEvent {
+        attacker,
+        defender: defenders[0],
+        attackers: vec![attacker],
+        defenders,
+        step_losses: Vec::new(),
+        outcome: CombatOutcome {
+            label: "R".to_string(),
+            effect: Some(OutcomeEffect::Retreat { hexes }),
+        },
+        die_roll: 4,
+        column_label: "2:1".to_string(),
     });
+    app.update();
+}
 
-    // -- Primitive 3: Stacking --
-    // Max 2 combat units per hex.
+#[test]
+fn combat_retreat_keeps_the_whole_stack_within_stacking_limits() {
+    let (mut app, setup, attacker, defender, _) = combat_outcome_app();
     app.insert_resource(StackingRule {
         max_units: 2,
-        exempt_type_ids: Vec::new(),
+        ..StackingRule::default()
     });
+    let red = app
+        .world()
+        .get::<UnitOwner>(defender)
+        .and_then(|owner| owner.faction_id)
+        .expect("the defender is owned");
+    let second = spawn_owned_unit(&mut app, &setup, (1, 0), setup.unit_type_id, red);
+    // One red unit fits beside each of these, but not the two retreating.
+    for pos in [(2, 0), (2, -1)] {
+        spawn_owned_unit(&mut app, &setup, pos, setup.unit_type_id, red);
+    }
+    app.update();
+    resolve_stack_retreat(&mut app, attacker, vec![defender, second], 1);
 
-    // -- Primitive 4: Movement cost matrix --
-    // Foot infantry: Plains costs 1, Forest costs 2.
-    app.insert_resource(MovementCostMatrix {
-        classification_property_id: Some(class_prop_id),
-        entries: {
-            let mut m = HashMap::new();
-            m.insert((plains_type_id, "Foot".to_string()), 1);
-            m.insert((forest_type_id, "Foot".to_string()), 2);
-            m
-        },
-    });
+    assert!(app.world().resource::<ActiveCombat>().retreats.is_empty());
+    assert_eq!(position_of(&app, defender), Some(HexPosition::new(1, 1)));
+    assert_eq!(position_of(&app, second), Some(HexPosition::new(1, 1)));
+}
 
-    // -- Spawn hex grid: Plains everywhere except Forest at (1,0) --
-    let radius = 3i32;
-    for q in -radius..=radius {
-        for r in -radius..=radius {
-            if (q + r).unsigned_abs() <= 3 {
-                if q == 1 && r == 0 {
-                    spawn_tile_at(&mut app, q, r, forest_type_id, Some(cost_prop_id), Some(2));
-                } else {
-                    spawn_tile_at(&mut app, q, r, plains_type_id, Some(cost_prop_id), Some(1));
-                }
-            }
+#[test]
+fn combat_retreat_avoids_impassable_terrain_and_enemy_zones() {
+    let (mut app, setup, attacker, defender, blue) = combat_outcome_app();
+    let mountain = TypeId::new();
+    app.world_mut()
+        .resource_mut::<RelationRegistry>()
+        .relations
+        .push(Relation {
+            id: TypeId::new(),
+            name: "Mountain Impassable".to_string(),
+            concept_id: setup.concept_id,
+            subject_role_id: setup.traveler_role_id,
+            object_role_id: setup.terrain_role_id,
+            trigger: RelationTrigger::OnEnter,
+            effect: RelationEffect::Block {
+                condition: Some(ConstraintExpr::IsType {
+                    role_id: setup.terrain_role_id,
+                    entity_type_id: mountain,
+                }),
+            },
+        });
+    let mut tiles = app
+        .world_mut()
+        .query_filtered::<(&HexPosition, &mut EntityData), With<HexTile>>();
+    for (pos, mut data) in tiles.iter_mut(app.world_mut()) {
+        if *pos == HexPosition::new(2, 0) {
+            data.entity_type_id = mountain;
         }
     }
-
-    // -- Spawn units --
-    // Enemy at (2, 0) — projects ZOC.
-    spawn_unit(
+    app.world_mut()
+        .resource_mut::<ConceptRegistry>()
+        .bindings
+        .push(ConceptBinding {
+            id: TypeId::new(),
+            entity_type_id: mountain,
+            concept_id: setup.concept_id,
+            concept_role_id: setup.terrain_role_id,
+            property_bindings: Vec::new(),
+        });
+    // A blue unit's zone of control covers (2, -1).
+    app.insert_resource(InfluenceRuleRegistry {
+        rules: vec![InfluenceRule {
+            id: TypeId::new(),
+            entity_type_id: setup.unit_type_id,
+            range: 1,
+            cost_modifier: 0,
+            zone: ZoneOfControl {
+                stop_on_enter: true,
+                ..ZoneOfControl::default()
+            },
+            enemy_only: true,
+        }],
+    });
+    spawn_owned_unit(&mut app, &setup, (3, -2), setup.unit_type_id, blue);
+    app.update();
+    resolve_combat(
         &mut app,
-        2,
-        0,
-        EntityData {
-            entity_type_id: enemy_type_id,
-            properties: HashMap::new(),
-        },
+        attacker,
+        defender,
+        OutcomeEffect::Retreat { hexes: 1 },
     );
 
-    // Two allied infantry at (0, 1) — fills stacking limit.
-    for _ in 0..2 {
-        spawn_unit(
-            &mut app,
-            0,
-            1,
+    assert!(app.world().resource::<ActiveCombat>().retreats.is_empty());
+    assert_eq!(position_of(&app, defender), Some(HexPosition::new(1, 1)));
+}
+
+/// On a wrapping board a defender next to the seam retreats away from an
+/// attacker across it, and the attacker may advance across it after.
+#[test]
+fn combat_retreat_and_advance_follow_the_wrap_seam() {
+    let mut app = test_app();
+    app.world_mut().resource_mut::<HexGridConfig>().shape = GridShape::Rectangle {
+        width: 10,
+        height: 6,
+        wrap_horizontal: true,
+    };
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let positions = app.world().resource::<HexGridConfig>().positions();
+    for pos in &positions {
+        app.world_mut().spawn((
+            HexTile,
+            *pos,
             EntityData {
-                entity_type_id: infantry_type_id,
-                properties: {
-                    let mut p = HashMap::new();
-                    p.insert(budget_prop_id, PropertyValue::Int(4));
-                    p.insert(class_prop_id, PropertyValue::Enum("Foot".to_string()));
-                    p
-                },
+                entity_type_id: setup.tile_type_id,
+                properties: HashMap::new(),
             },
-        );
+        ));
     }
-
-    // Selected infantry at origin (0, 0) with budget 4, classification Foot.
-    let unit = spawn_unit(
-        &mut app,
-        0,
-        0,
-        EntityData {
-            entity_type_id: infantry_type_id,
-            properties: {
-                let mut p = HashMap::new();
-                p.insert(budget_prop_id, PropertyValue::Int(4));
-                p.insert(class_prop_id, PropertyValue::Enum("Foot".to_string()));
-                p
-            },
-        },
+    app.init_resource::<UndoStack>();
+    app.world_mut()
+        .resource_mut::<NextState<AppScreen>>()
+        .set(AppScreen::Play);
+    let row: Vec<i32> = positions.iter().filter(|p| p.r == 0).map(|p| p.q).collect();
+    let west = HexPosition::new(*row.iter().min().expect("a row"), 0);
+    let east = HexPosition::new(*row.iter().max().expect("a row"), 0);
+    let attacker = spawn_owned_unit(
+        &mut app,
+        &setup,
+        (east.q, east.r),
+        setup.unit_type_id,
+        TypeId::new(),
     );
-    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(unit);
-
-    // -- Run BFS --
+    let defender = spawn_owned_unit(
+        &mut app,
+        &setup,
+        (west.q, west.r),
+        setup.unit_type_id,
+        TypeId::new(),
+    );
+    let mut combat = app.world_mut().resource_mut::<ActiveCombat>();
+    combat.add_attacker(attacker);
+    combat.add_defender(defender);
+    app.update();
+    resolve_combat(
+        &mut app,
+        attacker,
+        defender,
+        OutcomeEffect::Retreat { hexes: 1 },
+    );
+    let choice = app
+        .world()
+        .resource::<ActiveCombat>()
+        .retreats
+        .first()
+        .cloned();
+    if let Some(choice) = choice {
+        app.world_mut().commands().trigger(CombatMoveEvent {
+            units: choice.units,
+            path: choice.paths[0].clone(),
+            action: PostResolutionAction::Retreat,
+        });
+        app.update();
+    }
     app.update();
 
-    let valid_moves = app.world().resource::<ValidMoveSet>();
-
-    // === Primitive 4 (Matrix): Forest at (1,0) costs 2 for Foot ===
-    // Budget 4 - 2 = 2 remaining. Reachable.
-    assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(1, 0)),
-        "Forest (1,0) should be reachable: matrix cost 2, budget 4"
-    );
+    let config = app.world().resource::<HexGridConfig>();
+    let to = position_of(&app, defender).expect("the defender retreated");
+    assert_eq!(config.distance(to, east), 2);
+    assert_eq!(
+        app.world().resource::<ActiveCombat>().advances,
+        [AdvanceOption {
+            unit: attacker,
+            from: east,
+            to: west,
+        }]
+    );
+
+    app.world_mut().commands().trigger(CombatMoveEvent {
+        units: vec![attacker],
+        path: vec![east, west],
+        action: PostResolutionAction::Advance,
+    });
+    app.update();
+    assert_eq!(position_of(&app, attacker), Some(west));
+}
 
-    // === Primitive 1 (Edge): River crossing to (0,-1) ===
-    // Plains cost 1 + river crossing 1 = 2 total. Budget 4 - 2 = 2. Reachable.
-    assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(0, -1)),
-        "Plains (0,-1) with river crossing should be reachable: cost 1 + river 1 = 2"
-    );
+// ---------------------------------------------------------------------------
+// Combat tables
+// ---------------------------------------------------------------------------
 
-    // === Primitive 2 (Influence/ZOC): Hex (1,-1) adjacent to enemy at (2,0) ===
-    // Plains cost 1 + ZOC +2 = 3 total. Budget 4 - 3 = 1. Reachable.
-    assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(1, -1)),
-        "(1,-1) adjacent to enemy: plains 1 + ZOC 2 = 3, budget 4 → reachable"
-    );
+/// A conditional table of the setup's concept with the traveler and
+/// terrain roles as attacker and terrain.
+fn conditional_table(
+    setup: &MotionSetup,
+    name: &str,
+    selection: CrtSelection,
+) -> CombatResultsTable {
+    CombatResultsTable {
+        name: name.to_string(),
+        combat_concept_id: Some(setup.concept_id),
+        roles: CombatRoles {
+            attacker: Some(setup.traveler_role_id),
+            terrain: Some(setup.terrain_role_id),
+            ..CombatRoles::default()
+        },
+        selection,
+        ..CombatResultsTable::default()
+    }
+}
 
-    // === Primitive 3 (Stacking): Hex (0,1) full with 2 units ===
-    // Blocked by stacking — cannot enter regardless of budget.
-    assert!(
-        !valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(0, 1)),
-        "(0,1) should be blocked by stacking limit (2/2 units)"
+/// REQ-33 / SC-28: in Play the active combat uses the first conditional
+/// table whose faction list and condition match, and the default table
+/// once none does.
+#[test]
+fn combat_table_selected_by_faction_and_condition() {
+    let (mut app, setup, attacker, defender, blue) = combat_outcome_app();
+    app.world_mut()
+        .resource_mut::<NextState<AppScreen>>()
+        .set(AppScreen::Play);
+    let swamp = conditional_table(
+        &setup,
+        "Swamp",
+        CrtSelection {
+            condition: Some(ConstraintExpr::IsType {
+                role_id: setup.terrain_role_id,
+                entity_type_id: TypeId::new(),
+            }),
+            ..CrtSelection::default()
+        },
     );
-    assert!(
-        valid_moves
-            .blocked_explanations
-            .contains_key(&HexPosition::new(0, 1)),
-        "(0,1) should have blocked explanation for stacking"
+    let assault = conditional_table(
+        &setup,
+        "Assault",
+        CrtSelection {
+            factions: vec![blue],
+            condition: Some(ConstraintExpr::IsType {
+                role_id: setup.traveler_role_id,
+                entity_type_id: setup.unit_type_id,
+            }),
+            ..CrtSelection::default()
+        },
     );
-
-    // === Combined: (3,0) unreachable due to accumulated costs ===
-    // Path (0,0)→(1,0)[forest 2]→(2,0)[plains 1 + ZOC 2 = 3] = total 5 > budget 4.
-    // Even the cheapest path through ZOC hexes exhausts the budget.
-    assert!(
-        !valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(3, 0)),
-        "(3,0) should be unreachable: path through forest + ZOC exceeds budget 4"
+    let assault_id = assault.id;
+    app.insert_resource(CombatTableRegistry {
+        tables: vec![swamp, assault],
+    });
+    {
+        let mut combat = app.world_mut().resource_mut::<ActiveCombat>();
+        combat.add_attacker(attacker);
+        combat.add_defender(defender);
+    }
+    app.update();
+    app.update();
+    assert_eq!(
+        app.world().resource::<ActiveCombat>().table,
+        Some(assault_id)
     );
 
-    // === Verify continued movement after forest ===
-    // From (1,0) with 2 remaining, hex (1,1) is plains cost 1 and adjacent to
-    // enemy at (2,0), so cost = 1 + 2 = 3 > 2 remaining. Blocked.
-    assert!(
-        !valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(1, 1)),
-        "(1,1) from forest with 2 remaining + ZOC 2 + plains 1 = 3 > 2"
-    );
+    // Only red attacks use the assault table now.
+    app.world_mut().resource_mut::<CombatTableRegistry>().tables[1]
+        .selection
+        .factions = vec![TypeId::new()];
+    app.update();
+    assert_eq!(app.world().resource::<ActiveCombat>().table, None);
+}
 
-    // === Alternative path without ZOC ===
-    // (-1,0) is plains cost 1, not influenced. Budget 4 - 1 = 3.
-    assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(-1, 0)),
-        "(-1,0) away from ZOC should be easily reachable"
+/// REQ-33: a table's condition reads the defender, the defender's terrain
+/// and the feature on the crossed edge through the table's combat roles,
+/// and explains what decided it.
+#[test]
+fn headless_combat_table_condition_reads_defender_terrain_and_edge() {
+    let mut app = test_app();
+    let setup = setup_motion_ontology(&mut app, 4, 1);
+    let mut file = headless_file(
+        &app,
+        &setup,
+        1,
+        &[
+            (HexPosition::new(0, 0), None),
+            (HexPosition::new(1, 0), None),
+            (HexPosition::new(0, 1), None),
+        ],
     );
-    // (-2,0) is plains cost 1 from (-1,0), remaining 2.
-    assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(-2, 0)),
-        "(-2,0) should be reachable (2 plains steps, no ZOC)"
+    let river_id = TypeId::new();
+    file.entity_types.types.push(EntityType {
+        id: river_id,
+        name: "River".to_string(),
+        role: EntityRole::BoardPosition,
+        color: bevy::color::Color::srgb(0.2, 0.4, 0.8),
+        properties: Vec::new(),
+    });
+    file.edge_features.insert(
+        HexEdge::between(HexPosition::new(0, 0), HexPosition::new(1, 0)).expect("adjacent hexes"),
+        EdgeFeature {
+            type_name: "River".to_string(),
+        },
     );
-    // (-3,0) is plains cost 1 from (-2,0), remaining 1.
-    assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(-3, 0)),
-        "(-3,0) should be reachable (3 plains steps away, budget 4)"
+    let defender_role_id = TypeId::new();
+    let edge_role_id = TypeId::new();
+    file.concepts.concepts[0].role_labels.extend([
+        ConceptRole {
+            id: defender_role_id,
+            name: "defender".to_string(),
+            allowed_entity_roles: vec![EntityRole::Token],
+        },
+        ConceptRole {
+            id: edge_role_id,
+            name: "crossing".to_string(),
+            allowed_entity_roles: vec![EntityRole::BoardPosition],
+        },
+    ]);
+
+    let mut river = conditional_table(
+        &setup,
+        "River Assault",
+        CrtSelection {
+            condition: Some(ConstraintExpr::All(vec![
+                ConstraintExpr::IsType {
+                    role_id: edge_role_id,
+                    entity_type_id: river_id,
+                },
+                ConstraintExpr::IsType {
+                    role_id: setup.terrain_role_id,
+                    entity_type_id: setup.tile_type_id,
+                },
+                ConstraintExpr::PropertyCompare {
+                    role_id: defender_role_id,
+                    property_name: "budget".to_string(),
+                    operator: CompareOp::Ge,
+                    value: PropertyValue::Int(4),
+                },
+            ])),
+            ..CrtSelection::default()
+        },
     );
+    river.roles.defender = Some(defender_role_id);
+    river.roles.edge = Some(edge_role_id);
+    let tables = CombatTableRegistry {
+        tables: vec![river],
+    };
 
-    // === River crossing limits onward movement ===
-    // (0,-1) costs 2 to reach (river), leaving budget 2.
-    // (0,-2) from (0,-1) costs 1 (plains), leaving 1.
-    // (0,-3) from (0,-2) costs 1 (plains), leaving 0. Reachable.
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
+
+    let across = rules.combat_table_applies(&board, &tables.tables[0], None, &[0], &[1]);
+    assert!(across.satisfied, "{}", across.explanation);
     assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(0, -2)),
-        "(0,-2) should be reachable through river crossing with remaining budget"
-    );
+        across.explanation.contains("crossing is River")
+            && across.explanation.contains("terrain is Plains"),
+        "{}",
+        across.explanation
+    );
+    let table = rules.select_combat_table(&board, &tables, None, &[0], &[1]);
+    assert_eq!(table.map(|t| t.name.as_str()), Some("River Assault"));
+
+    // No river between (0, 0) and (0, 1).
+    let dry = rules.combat_table_applies(&board, &tables.tables[0], None, &[0], &[2]);
+    assert!(!dry.satisfied);
+    assert_eq!(dry.explanation, "crossing is not present");
     assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(0, -3)),
-        "(0,-3) should be reachable: 3 steps from origin with river costing 2 total on first step"
+        rules
+            .select_combat_table(&board, &tables, None, &[0], &[2])
+            .is_none()
     );
 }
 
-/// Canary: movement cost matrix differentiates unit classifications.
-/// Two units with different classifications on the same terrain see different costs.
+// ---------------------------------------------------------------------------
+// Combat modifiers
+// ---------------------------------------------------------------------------
+
+/// REQ-34: a modifier's condition reads the whole attacking stack, so a
+/// combined-arms bonus needs both unit types among the attackers.
 #[test]
-fn canary_tov_matrix_differentiates_classifications() {
+fn headless_combat_modifier_condition_checks_the_whole_attacking_stack() {
     let mut app = test_app();
     let setup = setup_motion_ontology(&mut app, 4, 1);
-    spawn_hex_grid_with_properties(&mut app, 3, setup.tile_type_id, setup.cost_prop_id, 1);
-
-    let class_prop_id = TypeId::new();
-    let enum_id = TypeId::new();
+    let mut file = headless_file(
+        &app,
+        &setup,
+        1,
+        &[
+            (HexPosition::new(0, 0), None),
+            (HexPosition::new(0, 0), None),
+            (HexPosition::new(1, 0), None),
+        ],
+    );
+    let armor_id = TypeId::new();
+    file.entity_types.types.push(EntityType {
+        id: armor_id,
+        name: "Armor".to_string(),
+        role: EntityRole::Token,
+        color: bevy::color::Color::srgb(0.3, 0.3, 0.3),
+        properties: Vec::new(),
+    });
+    file.units[1].entity_type_id = armor_id;
+    let crt = CombatResultsTable {
+        combat_concept_id: Some(setup.concept_id),
+        roles: CombatRoles {
+            attacker: Some(setup.traveler_role_id),
+            ..CombatRoles::default()
+        },
+        ..CombatResultsTable::default()
+    };
+    let combined_arms = CombatModifierDefinition {
+        id: TypeId::new(),
+        name: "Combined Arms".to_string(),
+        source: ModifierSource::Custom("combined arms".to_string()),
+        column_shift: 1,
+        priority: 0,
+        cap: None,
+        terrain_type_filter: Some(setup.tile_type_id),
+        condition: Some(ConstraintExpr::All(vec![
+            ConstraintExpr::IsType {
+                role_id: setup.traveler_role_id,
+                entity_type_id: setup.unit_type_id,
+            },
+            ConstraintExpr::IsType {
+                role_id: setup.traveler_role_id,
+                entity_type_id: armor_id,
+            },
+        ])),
+    };
 
-    // Add classification enum to unit type.
-    app.world_mut()
-        .resource_mut::<EntityTypeRegistry>()
-        .types
-        .iter_mut()
-        .find(|t| t.id == setup.unit_type_id)
-        .expect("unit type exists")
-        .properties
-        .push(PropertyDefinition {
-            id: class_prop_id,
-            name: "Movement Mode".to_string(),
-            property_type: PropertyType::Enum(enum_id),
-            default_value: PropertyValue::Enum("Foot".to_string()),
-        });
+    let snapshot = BoardSnapshot::from_file(&file);
+    let rules = RulesContext::from_file(&file, &snapshot.grid_config);
+    let board = snapshot.board();
 
-    // Foot costs 1 per hex, Mechanized costs 3 per hex (on Plains).
-    app.insert_resource(MovementCostMatrix {
-        classification_property_id: Some(class_prop_id),
-        entries: {
-            let mut m = HashMap::new();
-            m.insert((setup.tile_type_id, "Foot".to_string()), 1);
-            m.insert((setup.tile_type_id, "Mechanized".to_string()), 3);
-            m
-        },
-    });
+    let stack = rules.modifier_applies(&board, &crt, &combined_arms, &[0, 1], &[2]);
+    assert!(stack.satisfied, "{}", stack.explanation);
+    assert_eq!(
+        stack.explanation,
+        "defender terrain is Plains and traveler is Infantry and traveler is Armor"
+    );
 
-    // --- Test Foot infantry: budget 4, cost 1 → reaches 3 hexes (map limit) ---
-    let foot_unit = spawn_unit(
-        &mut app,
-        0,
-        0,
-        EntityData {
-            entity_type_id: setup.unit_type_id,
-            properties: {
-                let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
-                p.insert(class_prop_id, PropertyValue::Enum("Foot".to_string()));
-                p
-            },
-        },
+    let infantry_only = rules.modifier_applies(&board, &crt, &combined_arms, &[0], &[2]);
+    assert!(!infantry_only.satisfied);
+    assert_eq!(
+        infantry_only.explanation,
+        "defender terrain is Plains and traveler is Infantry, not Armor"
     );
-    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(foot_unit);
-    app.update();
 
-    let valid_moves = app.world().resource::<ValidMoveSet>();
-    assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(3, 0)),
-        "Foot (cost 1): should reach 3 hexes with budget 4"
+    let mut swamp_only = combined_arms.clone();
+    swamp_only.terrain_type_filter = Some(armor_id);
+    let off_terrain = rules.modifier_applies(&board, &crt, &swamp_only, &[0, 1], &[2]);
+    assert!(!off_terrain.satisfied);
+    assert_eq!(
+        off_terrain.explanation,
+        "defender terrain is Plains, not Armor"
     );
+}
 
-    // --- Test Mechanized: budget 4, cost 3 → reaches only adjacent ---
-    // Despawn foot unit, spawn mechanized at origin.
-    app.world_mut().despawn(foot_unit);
-    let mech_unit = spawn_unit(
-        &mut app,
-        0,
-        0,
-        EntityData {
+/// REQ-34 / SC-29: in Play the active combat lists whether each
+/// conditional modifier applies, and why; unconditional modifiers are not
+/// listed.
+#[test]
+fn combat_modifier_results_follow_the_active_combat() {
+    let (mut app, setup, attacker, defender, _) = combat_outcome_app();
+    app.world_mut()
+        .resource_mut::<NextState<AppScreen>>()
+        .set(AppScreen::Play);
+    let infantry_attack = CombatModifierDefinition {
+        id: TypeId::new(),
+        name: "Infantry Assault".to_string(),
+        source: ModifierSource::Custom("assault".to_string()),
+        column_shift: 1,
+        priority: 0,
+        cap: None,
+        terrain_type_filter: None,
+        condition: Some(ConstraintExpr::IsType {
+            role_id: setup.traveler_role_id,
             entity_type_id: setup.unit_type_id,
-            properties: {
-                let mut p = HashMap::new();
-                p.insert(setup.budget_prop_id, PropertyValue::Int(4));
-                p.insert(class_prop_id, PropertyValue::Enum("Mechanized".to_string()));
-                p
+        }),
+    };
+    let infantry_id = infantry_attack.id;
+    app.insert_resource(CombatModifierRegistry {
+        modifiers: vec![
+            infantry_attack,
+            CombatModifierDefinition {
+                id: TypeId::new(),
+                name: "Leader".to_string(),
+                source: ModifierSource::Custom("leader".to_string()),
+                column_shift: 1,
+                priority: 0,
+                cap: None,
+                terrain_type_filter: None,
+                condition: None,
             },
-        },
-    );
-    app.world_mut().resource_mut::<SelectedUnit>().entity = Some(mech_unit);
+        ],
+    });
     app.update();
-
-    let valid_moves = app.world().resource::<ValidMoveSet>();
-    assert!(
-        valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(1, 0)),
-        "Mechanized (cost 3): should reach adjacent hex"
+    app.update();
+    let results = app
+        .world()
+        .resource::<ActiveCombat>()
+        .modifier_results
+        .clone();
+    assert_eq!(results.len(), 1);
+    assert!(!results[0].satisfied);
+    assert_eq!(
+        results[0].explanation,
+        "waiting for an attacker and a defender"
     );
-    assert!(
-        !valid_moves
-            .valid_positions
-            .contains(&HexPosition::new(2, 0)),
-        "Mechanized (cost 3): should NOT reach 2 hexes (cost 6 > budget 4)"
+
+    {
+        let mut combat = app.world_mut().resource_mut::<ActiveCombat>();
+        combat.add_attacker(attacker);
+        combat.add_defender(defender);
+    }
+    app.update();
+    let combat = app.world().resource::<ActiveCombat>();
+    assert!(combat.modifier_applies(infantry_id));
+    assert_eq!(
+        combat.modifier_results[0].explanation,
+        "traveler is Infantry"
     );
 }
@@ -83,6 +83,10 @@ fn property_value_to_lua(lua: &Lua, val: &PropertyValue) -> LuaResult<Value> {
             Some(id) => Ok(Value::String(lua.create_string(id.0.to_string())?)),
             None => Ok(Value::Nil),
         },
+        PropertyValue::UnitRef(opt) => match opt {
+            Some(id) => Ok(Value::String(lua.create_string(id.to_string())?)),
+            None => Ok(Value::Nil),
+        },
         PropertyValue::List(items) => {
             let t = lua.create_table()?;
             for (i, item) in items.iter().enumerate() {
@@ -127,6 +131,7 @@ fn property_type_to_string(pt: &PropertyType) -> &'static str {
         PropertyType::Color => "color",
         PropertyType::Enum(_) => "enum",
         PropertyType::EntityRef(_) => "entity_ref",
+        PropertyType::UnitRef => "unit_ref",
         PropertyType::List(_) => "list",
         PropertyType::Map(_, _) => "map",
         PropertyType::Struct(_) => "struct",
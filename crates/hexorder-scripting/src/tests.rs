@@ -107,6 +107,7 @@ fn test_constraint_registry() -> ConstraintRegistry {
                 value: PropertyValue::Int(0),
             },
             auto_generated: false,
+            gates: Vec::new(),
         }],
     }
 }
@@ -745,6 +746,7 @@ fn lua_constraint_auto_generated_true() {
                 value: PropertyValue::Int(0),
             },
             auto_generated: true,
+            gates: Vec::new(),
         }],
     };
     let result = lua_api::constraints_to_lua(&lua, &registry).expect("convert");
@@ -1,17 +1,4 @@
-use bevy::prelude::*;
-use hexorder_contracts::game_system::TypeId;
-use hexorder_contracts::simulation::{RollRecord, TableResolution};
+//! Simulation events. Defined in `hexorder_contracts::simulation` so other
+//! plugins can fire them; re-exported here for existing callers.
 
-/// Fired when a die is rolled (for UI display, logging).
-#[derive(Event, Debug, Clone)]
-pub struct DieRolled {
-    pub record: RollRecord,
-}
-
-/// Fired when a table resolution completes.
-#[derive(Event, Debug, Clone)]
-pub struct TableResolved {
-    pub table_id: TypeId,
-    pub resolution: TableResolution,
-    pub roll: Option<RollRecord>,
-}
+pub use hexorder_contracts::simulation::{DieRolled, TableResolved};
@@ -1,11 +1,13 @@
 //! Simulation plugin.
 //!
-//! Hosts the `SimulationRng` resource and provides observer events
-//! for die rolls and table resolutions. All types and pure functions
-//! live in `hexorder_contracts::simulation`.
+//! Hosts the `SimulationRng` resource, reseeds it at the start of each
+//! play session, and records die rolls and table resolutions in the
+//! session's `RollHistory`. All types and pure functions live in
+//! `hexorder_contracts::simulation`.
 
 use bevy::prelude::*;
-use hexorder_contracts::simulation::SimulationRng;
+use hexorder_contracts::persistence::AppScreen;
+use hexorder_contracts::simulation::{RollHistory, SessionSeed, SimulationRng};
 use hexorder_sdk::{HexorderPlugin, PluginId};
 
 pub mod events;
@@ -30,6 +32,9 @@ impl HexorderPlugin for SimulationPlugin {
 
     fn build(&self, app: &mut App) {
         app.insert_resource(SimulationRng::new_random());
+        app.init_resource::<RollHistory>();
+        app.init_resource::<SessionSeed>();
+        app.add_systems(OnEnter(AppScreen::Play), systems::start_play_session);
         app.add_observer(systems::on_die_rolled);
         app.add_observer(systems::on_table_resolved);
     }
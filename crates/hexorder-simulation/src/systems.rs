@@ -1,13 +1,47 @@
 use bevy::prelude::*;
+use hexorder_contracts::simulation::{
+    RollHistory, RollHistoryEntry, SessionSeed, SimulationRng, reset_rng,
+};
 
 use crate::events::{DieRolled, TableResolved};
 
-/// Observer: log die roll events (future: update UI).
-pub fn on_die_rolled(_trigger: On<DieRolled>) {
-    // Future: notify editor UI, update roll display panel.
+/// Observer: records each roll in the session's roll history.
+pub fn on_die_rolled(trigger: On<DieRolled>, mut history: ResMut<RollHistory>) {
+    let event = trigger.event();
+    history.entries.push(RollHistoryEntry {
+        context: event.context.clone(),
+        roll: event.roll.clone(),
+        resolution: None,
+    });
 }
 
-/// Observer: log table resolution events (future: update UI).
-pub fn on_table_resolved(_trigger: On<TableResolved>) {
-    // Future: notify editor UI, apply outcome effects.
+/// Observer: attaches a table resolution to the latest unresolved entry
+/// rolled with the same dice.
+pub fn on_table_resolved(trigger: On<TableResolved>, mut history: ResMut<RollHistory>) {
+    let event = trigger.event();
+    let Some(roll) = &event.roll else {
+        return;
+    };
+    if let Some(entry) = history
+        .entries
+        .iter_mut()
+        .rev()
+        .find(|e| e.resolution.is_none() && e.roll == *roll)
+    {
+        entry.resolution = Some(event.resolution.clone());
+    }
+}
+
+/// Starts a play session: reseeds the RNG from the session seed (or a
+/// random one) and clears the roll history.
+pub fn start_play_session(
+    session_seed: Res<SessionSeed>,
+    mut rng: ResMut<SimulationRng>,
+    mut history: ResMut<RollHistory>,
+) {
+    let seed = session_seed
+        .0
+        .unwrap_or_else(|| SimulationRng::new_random().seed());
+    reset_rng(&mut rng, seed);
+    history.entries.clear();
 }
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
+use hexorder_contracts::game_system::TypeId;
+use hexorder_contracts::persistence::AppScreen;
 use hexorder_contracts::simulation::{
-    ColumnType, DieType, ResolutionTable, SimulationRng, TableColumn, TableResult, TableRow,
-    reset_rng, resolve_table, roll_die,
+    ColumnType, DicePool, DieRolled, DieType, ResolutionTable, RollHistory, RollHistoryEntry,
+    SessionSeed, SimulationRng, TableColumn, TableResolution, TableResolved, TableResult, TableRow,
+    reset_rng, resolve_table, roll_die, roll_pool,
 };
 
 /// `SimulationPlugin` inserts `SimulationRng` resource.
@@ -124,3 +127,86 @@ fn reset_replays_same_sequence() {
 
     assert_eq!(first_run, second_run);
 }
+
+/// `DieRolled` adds a roll history entry and `TableResolved` attaches its
+/// lookup to it.
+#[test]
+fn roll_events_fill_roll_history() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins(super::SimulationPlugin);
+
+    let roll = roll_pool(
+        &mut app.world_mut().resource_mut::<SimulationRng>(),
+        DicePool::new(2, 6, 0),
+        "CRT 3:1",
+    );
+    app.world_mut().commands().trigger(DieRolled {
+        context: "CRT 3:1".to_string(),
+        roll: roll.clone(),
+    });
+    app.world_mut().commands().trigger(TableResolved {
+        table_id: TypeId::new(),
+        resolution: TableResolution {
+            column_index: 2,
+            row_index: 0,
+            column_label: "3:1".to_string(),
+            row_label: "2-7".to_string(),
+            result: TableResult::Text("DR".to_string()),
+        },
+        roll: Some(roll.clone()),
+    });
+    app.update();
+
+    let history = app.world().resource::<RollHistory>();
+    assert_eq!(history.entries.len(), 1);
+    assert_eq!(history.entries[0].context, "CRT 3:1");
+    assert_eq!(history.entries[0].roll, roll);
+    assert_eq!(
+        history.entries[0]
+            .resolution
+            .as_ref()
+            .map(|r| r.column_label.as_str()),
+        Some("3:1")
+    );
+}
+
+/// With a session seed, every play session rolls the same sequence and
+/// starts with an empty roll history.
+#[test]
+fn session_seed_replays_each_play_session() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins(bevy::state::app::StatesPlugin);
+    app.init_state::<AppScreen>();
+    app.add_plugins(super::SimulationPlugin);
+    app.insert_resource(SessionSeed(Some(7)));
+
+    let mut session = || {
+        app.world_mut()
+            .resource_mut::<NextState<AppScreen>>()
+            .set(AppScreen::Play);
+        app.update();
+        assert!(app.world().resource::<RollHistory>().entries.is_empty());
+        let mut rng = app.world_mut().resource_mut::<SimulationRng>();
+        assert_eq!(rng.seed(), 7);
+        let rolls: Vec<u32> = (0..5)
+            .map(|_| roll_die(&mut rng, DieType::D6, ""))
+            .collect();
+        app.world_mut()
+            .resource_mut::<RollHistory>()
+            .entries
+            .push(RollHistoryEntry {
+                context: String::new(),
+                roll: roll_pool(&mut SimulationRng::new(0), DicePool::single(6), ""),
+                resolution: None,
+            });
+        app.world_mut()
+            .resource_mut::<NextState<AppScreen>>()
+            .set(AppScreen::Editor);
+        app.update();
+        rolls
+    };
+    let first = session();
+    assert_eq!(session(), first);
+}
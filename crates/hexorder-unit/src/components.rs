@@ -10,6 +10,9 @@ use hexorder_contracts::game_system::TypeId;
 #[derive(Resource, Debug)]
 pub struct UnitMaterials {
     pub materials: HashMap<TypeId, Handle<StandardMaterial>>,
+    /// Faction- and state-tinted materials, keyed by (unit type, faction,
+    /// state).
+    pub tinted: HashMap<(TypeId, Option<TypeId>, Option<TypeId>), Handle<StandardMaterial>>,
 }
 
 impl UnitMaterials {
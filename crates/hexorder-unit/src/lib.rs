@@ -6,6 +6,7 @@
 
 use bevy::prelude::*;
 
+use hexorder_contracts::game_system::UnitIndex;
 use hexorder_contracts::persistence::AppScreen;
 use hexorder_sdk::{HexorderPlugin, PluginId};
 
@@ -29,21 +30,39 @@ impl HexorderPlugin for UnitPlugin {
     }
 
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppScreen::Editor), systems::setup_unit_visuals)
+        app.init_resource::<UnitIndex>()
+            .add_systems(OnEnter(AppScreen::Editor), systems::setup_unit_visuals)
             .add_systems(
                 Update,
                 (
                     systems::delete_selected_unit,
+                    systems::sync_unit_index,
+                    systems::sync_unit_positions,
                     systems::assign_unit_visuals,
                     systems::sync_unit_materials,
                     systems::sync_unit_visuals,
+                    systems::apply_faction_tints,
                 )
                     .chain()
                     .run_if(in_state(AppScreen::Editor).or(in_state(AppScreen::Play))),
             )
+            .add_systems(
+                Update,
+                (
+                    systems::reset_attacked_this_phase,
+                    systems::update_attack_eligibility,
+                    systems::update_loss_candidates,
+                    systems::update_combat_odds,
+                )
+                    .chain()
+                    .run_if(in_state(AppScreen::Play)),
+            )
             .add_observer(systems::handle_unit_placement)
             .add_observer(systems::handle_unit_interaction)
-            .add_observer(systems::handle_combat_select);
+            .add_observer(systems::handle_combat_select)
+            .add_observer(systems::mark_attackers)
+            .add_observer(systems::handle_move_to_zone)
+            .add_observer(systems::handle_deploy_from_zone);
     }
 }
 
@@ -6,14 +6,25 @@ use bevy::prelude::*;
 
 use hexorder_contracts::editor_ui::EditorTool;
 use hexorder_contracts::game_system::{
-    ActiveTokenType, EntityData, EntityRole, EntityTypeRegistry, PropertyValue, SelectedUnit,
-    UnitInstance, UnitPlacedEvent,
+    ActiveFaction, ActiveTokenType, EntityData, EntityRole, EntityState, EntityTypeRegistry,
+    FactionRegistry, PropertyValue, SelectedUnit, StateMachineRegistry, StateOverrides, UnitId,
+    UnitIndex, UnitInstance, UnitOwner, UnitPlacedEvent,
 };
-use hexorder_contracts::hex_grid::{HexGridConfig, HexMoveEvent, HexPosition, HexSelectedEvent};
-use hexorder_contracts::mechanics::ActiveCombat;
+use hexorder_contracts::hex_grid::{
+    HexGridConfig, HexMoveEvent, HexPosition, HexSelectedEvent, HexTile, StackingRule,
+};
+use hexorder_contracts::mechanics::{
+    ActiveCombat, AttackIneligibility, AttackedThisPhase, CombatOdds, CombatResolvedEvent,
+    CombatResultsTable, CombatSide, CombatStrength, CombatTableRegistry, DeployFromZoneEvent,
+    LossAllocation, LossCandidate, MoveRequestedEvent, MoveToZoneEvent, OffMapZoneRegistry,
+    PhaseType, TurnState, TurnStructure, ZoneUnit, compute_combat_odds, concept_property_value,
+    current_phase,
+};
+use hexorder_contracts::ontology::{ConceptRegistry, GatedAction, PresenceEffects};
 use hexorder_contracts::persistence::AppScreen;
+use hexorder_contracts::simulation::ColumnType;
 use hexorder_contracts::undo_redo::{PlaceUnitCommand, UndoStack};
-use hexorder_contracts::validation::ValidMoveSet;
+use hexorder_contracts::validation::{ActionEligibility, ValidMoveSet};
 
 use super::components::{UnitMaterials, UnitMesh};
 
@@ -41,6 +52,7 @@ pub fn setup_unit_visuals(
     }
     commands.insert_resource(UnitMaterials {
         materials: unit_materials,
+        tinted: HashMap::new(),
     });
 
     let mesh_handle = meshes.add(Cylinder::new(0.3, 0.4));
@@ -53,20 +65,23 @@ pub fn setup_unit_visuals(
 // Observers
 // ---------------------------------------------------------------------------
 
-/// Places a unit on the clicked hex tile when in Place mode.
-/// Records a `PlaceUnitCommand` on the undo stack for reversibility.
-#[allow(clippy::too_many_arguments)]
+/// Places a unit on the clicked hex tile when in Place mode, owned by the
+/// active faction. Records a `PlaceUnitCommand` on the undo stack for
+/// reversibility.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn handle_unit_placement(
     trigger: On<HexSelectedEvent>,
     screen: Res<State<AppScreen>>,
     tool: Res<EditorTool>,
     active_unit: Res<ActiveTokenType>,
+    active_faction: Option<Res<ActiveFaction>>,
     registry: Res<EntityTypeRegistry>,
     config: Res<HexGridConfig>,
-    stacking_rule: Res<hexorder_contracts::hex_grid::StackingRule>,
+    stacking_rule: Res<StackingRule>,
     unit_materials: Res<UnitMaterials>,
     unit_mesh: Res<UnitMesh>,
-    existing_units: Query<(&HexPosition, &EntityData), With<UnitInstance>>,
+    existing_units: Query<(&HexPosition, &EntityData, &UnitOwner), With<UnitInstance>>,
+    tiles: Query<(&HexPosition, &EntityData), (With<HexTile>, Without<UnitInstance>)>,
     mut undo_stack: ResMut<UndoStack>,
     mut commands: Commands,
 ) {
@@ -89,24 +104,16 @@ pub fn handle_unit_placement(
     let pos = event.position;
 
     // Verify position is within grid bounds.
-    let hex = pos.to_hex();
-    if hex.unsigned_distance_to(hexx::Hex::ZERO) > config.map_radius {
+    if !config.contains(pos) {
         return;
     }
 
-    // Check stacking limit.
-    if stacking_rule.is_active() {
-        let current_non_exempt = existing_units
-            .iter()
-            .filter(|(p, d)| **p == pos && !stacking_rule.is_exempt(d.entity_type_id))
-            .count() as u32;
-        if stacking_rule.would_exceed(active_id, current_non_exempt) {
-            return;
-        }
-    }
+    let owner = UnitOwner {
+        faction_id: active_faction.and_then(|a| a.faction_id),
+    };
 
     // Compute world position from hex coordinates.
-    let world_pos = config.layout.hex_to_world_pos(hex);
+    let world_pos = config.layout.hex_to_world_pos(pos.to_hex());
 
     // Build default properties for this entity type.
     let default_properties: HashMap<_, _> = entity_type
@@ -125,12 +132,27 @@ pub fn handle_unit_placement(
         properties: default_properties,
     };
 
+    // Check stacking limit and mixed stacks.
+    if stacking_blocks(
+        &stacking_rule,
+        &existing_units,
+        &tiles,
+        pos,
+        &entity_data,
+        owner,
+    ) {
+        return;
+    }
+
     // Spawn unit entity.
+    let unit_id = UnitId::new();
     let entity = commands
         .spawn((
             UnitInstance,
             HexPosition::new(pos.q, pos.r),
             entity_data.clone(),
+            owner,
+            unit_id,
             Mesh3d(unit_mesh.handle.clone()),
             MeshMaterial3d(material.clone()),
             transform,
@@ -143,6 +165,8 @@ pub fn handle_unit_placement(
         entity: Some(entity),
         position: pos,
         entity_data,
+        owner,
+        unit_id,
         mesh: unit_mesh.handle.clone(),
         material: material.clone(),
         transform,
@@ -151,6 +175,7 @@ pub fn handle_unit_placement(
 
     commands.trigger(UnitPlacedEvent {
         entity,
+        unit_id,
         position: pos,
         entity_type_id: active_id,
     });
@@ -162,6 +187,9 @@ pub fn handle_unit_placement(
 /// - Click same hex as selected unit → deselect
 /// - Click different hex while unit selected → move unit there
 ///
+/// In Play the move is requested with a `MoveRequestedEvent` instead, so
+/// the rules engine can walk the route and halt it at movement interrupts.
+///
 /// If `ValidMoveSet` has valid positions, only allows movement to those
 /// positions. If `ValidMoveSet` is empty (no constraints), all in-bounds
 /// positions are allowed (backward compatible with 0.3.0).
@@ -173,12 +201,14 @@ pub fn handle_unit_interaction(
     mut selected_unit: ResMut<SelectedUnit>,
     valid_moves: Option<Res<ValidMoveSet>>,
     config: Res<HexGridConfig>,
-    mut units: Query<(Entity, &mut HexPosition, &mut Transform), With<UnitInstance>>,
+    mut units: Query<(Entity, &UnitId, &mut HexPosition, &mut Transform), With<UnitInstance>>,
     mut commands: Commands,
 ) {
-    if *screen.get() != AppScreen::Editor {
-        return;
-    }
+    let playing = match screen.get() {
+        AppScreen::Editor => false,
+        AppScreen::Play => true,
+        AppScreen::Launcher => return,
+    };
     if *tool != EditorTool::Select {
         return;
     }
@@ -189,8 +219,8 @@ pub fn handle_unit_interaction(
     // Check if there's a unit at the clicked position.
     let unit_at_pos = units
         .iter()
-        .find(|(_, pos, _)| **pos == clicked_pos)
-        .map(|(e, _, _)| e);
+        .find(|(_, _, pos, _)| **pos == clicked_pos)
+        .map(|(e, _, _, _)| e);
 
     if let Some(entity) = unit_at_pos {
         if selected_unit.entity == Some(entity) {
@@ -202,8 +232,7 @@ pub fn handle_unit_interaction(
         }
     } else if let Some(selected_entity) = selected_unit.entity {
         // Clicked empty tile while a unit is selected → move the unit.
-        let hex = clicked_pos.to_hex();
-        if hex.unsigned_distance_to(hexx::Hex::ZERO) > config.map_radius {
+        if !config.contains(clicked_pos) {
             return;
         }
 
@@ -217,20 +246,30 @@ pub fn handle_unit_interaction(
             return;
         }
 
-        let Ok((_, mut pos, mut transform)) = units.get_mut(selected_entity) else {
+        let Ok((_, unit_id, mut pos, mut transform)) = units.get_mut(selected_entity) else {
             // Entity no longer exists — clear selection.
             selected_unit.entity = None;
             return;
         };
 
+        if playing {
+            commands.trigger(MoveRequestedEvent {
+                entity: selected_entity,
+                to: clicked_pos,
+            });
+            selected_unit.entity = None;
+            return;
+        }
+
         let from = *pos;
         *pos = clicked_pos;
 
-        let world_pos = config.layout.hex_to_world_pos(hex);
+        let world_pos = config.layout.hex_to_world_pos(clicked_pos.to_hex());
         transform.translation = Vec3::new(world_pos.x, UNIT_Y_OFFSET, world_pos.y);
 
         commands.trigger(HexMoveEvent {
             entity: selected_entity,
+            unit_id: *unit_id,
             from,
             to: clicked_pos,
         });
@@ -241,15 +280,29 @@ pub fn handle_unit_interaction(
 
 /// Handles combat selection in `CombatSelect` tool mode.
 ///
-/// First click on a unit assigns it as **attacker**; second click assigns
-/// **defender**. Clicking the same unit twice deselects it. Resetting
-/// either combatant clears the resolution state.
+/// The first unit clicked becomes the primary **attacker**. Clicking a hex of
+/// the attacker's faction adds its next unit to the attack; once all of the
+/// hex's units attack, clicking it again withdraws them. Clicking a hex with
+/// units opposed to the attackers adds them all as **defenders**, or
+/// withdraws them if they already defend. A unit whose `ActionEligibility`
+/// denies attacking, or that has attacked this phase, cannot join the
+/// attack. Changing the participants clears the resolution state.
+#[allow(clippy::type_complexity)]
 pub fn handle_combat_select(
     trigger: On<HexSelectedEvent>,
     screen: Res<State<AppScreen>>,
     tool: Res<EditorTool>,
     mut active_combat: ResMut<ActiveCombat>,
-    units: Query<(Entity, &HexPosition), With<UnitInstance>>,
+    units: Query<
+        (
+            Entity,
+            &HexPosition,
+            &UnitOwner,
+            Option<&ActionEligibility>,
+            Has<AttackedThisPhase>,
+        ),
+        With<UnitInstance>,
+    >,
 ) {
     if *screen.get() != AppScreen::Play {
         return;
@@ -259,38 +312,452 @@ pub fn handle_combat_select(
     }
 
     let clicked_pos = trigger.event().position;
+    let at_hex: Vec<_> = units
+        .iter()
+        .filter(|(_, pos, ..)| **pos == clicked_pos)
+        .collect();
+    let Some(&(_, _, first_owner, ..)) = at_hex.first() else {
+        return; // Clicked empty hex — ignore.
+    };
 
-    let unit_at_pos = units
+    let attacking_owner = active_combat
+        .attacker
+        .and_then(|attacker| units.get(attacker).ok())
+        .map(|(_, _, owner, ..)| *owner);
+
+    // Units opposed to the attackers defend as a whole hex.
+    if let Some(attacking_owner) = attacking_owner {
+        let opposed: Vec<Entity> = at_hex
+            .iter()
+            .filter(|(_, _, owner, ..)| attacking_owner.opposes(**owner))
+            .map(|(entity, ..)| *entity)
+            .collect();
+        if !opposed.is_empty() {
+            let defending = active_combat.defending_units();
+            if opposed.iter().all(|entity| defending.contains(entity)) {
+                for entity in opposed {
+                    active_combat.remove_participant(entity);
+                }
+            } else {
+                for entity in opposed {
+                    active_combat.add_defender(entity);
+                }
+            }
+            return;
+        }
+    }
+
+    // Otherwise the hex's units of the attacking faction join one at a time.
+    let side = attacking_owner.unwrap_or(*first_owner);
+    let attacking = active_combat.attacking_units();
+    let friendly: Vec<_> = at_hex
         .iter()
-        .find(|(_, pos)| **pos == clicked_pos)
-        .map(|(e, _)| e);
+        .filter(|(_, _, owner, ..)| **owner == side)
+        .collect();
+    let next = friendly
+        .iter()
+        .find(|(entity, _, _, eligibility, attacked)| {
+            !attacking.contains(entity)
+                && !active_combat.is_participant(*entity)
+                && !attacked
+                && eligibility.is_none_or(|e| e.allows(GatedAction::Attack))
+        });
+    if let Some((entity, ..)) = next {
+        active_combat.add_attacker(*entity);
+    } else {
+        for (entity, ..) in friendly {
+            if attacking.contains(entity) {
+                active_combat.remove_participant(*entity);
+            }
+        }
+    }
+}
 
-    let Some(entity) = unit_at_pos else {
-        return; // Clicked empty hex — ignore.
+/// Checks each combat participant's eligibility. Attackers must be in a
+/// Combat phase, allowed to attack by the rules, not have attacked this
+/// phase, and be adjacent to a defender; defenders must be adjacent to an
+/// attacker. Updates `ActiveCombat::ineligible` when it changes.
+#[allow(clippy::type_complexity)]
+pub fn update_attack_eligibility(
+    mut active_combat: ResMut<ActiveCombat>,
+    turn_state: Res<TurnState>,
+    turn_structure: Res<TurnStructure>,
+    config: Res<HexGridConfig>,
+    units: Query<
+        (
+            &HexPosition,
+            Option<&ActionEligibility>,
+            Has<AttackedThisPhase>,
+        ),
+        With<UnitInstance>,
+    >,
+) {
+    let in_combat_phase = current_phase(&turn_state, &turn_structure)
+        .is_some_and(|phase| phase.phase_type == PhaseType::Combat);
+    let positions = |entities: &[Entity]| -> Vec<HexPosition> {
+        entities
+            .iter()
+            .filter_map(|entity| units.get(*entity).ok())
+            .map(|(pos, ..)| *pos)
+            .collect()
+    };
+    let attackers = active_combat.attacking_units();
+    let defenders = active_combat.defending_units();
+    let attacker_hexes = positions(&attackers);
+    let defender_hexes = positions(&defenders);
+    let adjacent = |pos: HexPosition, others: &[HexPosition]| {
+        others.is_empty() || others.iter().any(|other| config.distance(pos, *other) == 1)
+    };
+
+    let mut ineligible = Vec::new();
+    for entity in attackers {
+        let Ok((pos, eligibility, attacked)) = units.get(entity) else {
+            continue;
+        };
+        let denied = eligibility.and_then(|e| {
+            e.denied
+                .iter()
+                .find(|(action, _)| *action == GatedAction::Attack)
+        });
+        let reason = if !in_combat_phase {
+            Some(AttackIneligibility::NotCombatPhase)
+        } else if let Some((_, result)) = denied {
+            Some(AttackIneligibility::DeniedByRule(
+                result.constraint_name.clone(),
+            ))
+        } else if attacked {
+            Some(AttackIneligibility::AlreadyAttacked)
+        } else if !adjacent(*pos, &defender_hexes) {
+            Some(AttackIneligibility::NotAdjacent)
+        } else {
+            None
+        };
+        ineligible.extend(reason.map(|reason| (entity, reason)));
+    }
+    for entity in defenders {
+        let Ok((pos, ..)) = units.get(entity) else {
+            continue;
+        };
+        if !adjacent(*pos, &attacker_hexes) {
+            ineligible.push((entity, AttackIneligibility::NotAdjacent));
+        }
+    }
+
+    if active_combat.ineligible != ineligible {
+        active_combat.ineligible = ineligible;
+    }
+}
+
+/// Lists the combat participants that can absorb step losses, with the
+/// priority and steps the loss rules of the combat's CRT read from their
+/// Combat concept properties. Updates `ActiveCombat::loss_candidates` when
+/// it changes.
+pub fn update_loss_candidates(
+    mut active_combat: ResMut<ActiveCombat>,
+    crt: Res<CombatResultsTable>,
+    tables: Res<CombatTableRegistry>,
+    concepts: Res<ConceptRegistry>,
+    units: Query<&EntityData, With<UnitInstance>>,
+) {
+    let crt = tables.table_or(active_combat.table, &crt);
+    let rules = &crt.losses;
+    let mut candidates = Vec::new();
+    for (side, entities, strength) in [
+        (
+            CombatSide::Attacker,
+            active_combat.attacking_units(),
+            crt.strength.attack_property.as_deref(),
+        ),
+        (
+            CombatSide::Defender,
+            active_combat.defending_units(),
+            crt.strength.defense_property.as_deref(),
+        ),
+    ] {
+        for entity in entities {
+            let Ok(data) = units.get(entity) else {
+                continue;
+            };
+            let value = |name: Option<&str>| {
+                crt.combat_concept_id
+                    .zip(name)
+                    .and_then(|(concept_id, name)| {
+                        concept_property_value(&concepts, concept_id, data, name)
+                    })
+            };
+            let priority = match &rules.allocation {
+                LossAllocation::AttackerChoice => None,
+                LossAllocation::LargestFirst => value(strength),
+                LossAllocation::ByProperty(name) => value(Some(name)),
+            };
+            let steps = rules
+                .steps_property
+                .as_deref()
+                .and_then(|name| value(Some(name)))
+                .map_or(1, |steps| steps.max(0.0) as u32);
+            candidates.push(LossCandidate {
+                entity,
+                side,
+                priority: priority.unwrap_or(0.0),
+                steps,
+            });
+        }
+    }
+
+    if active_combat.loss_candidates != candidates {
+        active_combat.loss_candidates = candidates;
+    }
+}
+
+/// Observer: marks every attacker of a resolved combat so it cannot attack
+/// again this phase.
+pub fn mark_attackers(trigger: On<CombatResolvedEvent>, mut commands: Commands) {
+    let event = trigger.event();
+    let attackers = if event.attackers.is_empty() {
+        vec![event.attacker]
+    } else {
+        event.attackers.clone()
+    };
+    for attacker in attackers {
+        if let Ok(mut entity) = commands.get_entity(attacker) {
+            entity.insert(AttackedThisPhase);
+        }
+    }
+}
+
+/// Clears every unit's `AttackedThisPhase` marker when play moves to
+/// another phase.
+pub fn reset_attacked_this_phase(
+    mut last_phase: Local<Option<(u32, usize)>>,
+    turn_state: Res<TurnState>,
+    attacked: Query<Entity, With<AttackedThisPhase>>,
+    mut commands: Commands,
+) {
+    let current = (turn_state.turn_number, turn_state.current_phase_index);
+    if *last_phase == Some(current) {
+        return;
+    }
+    *last_phase = Some(current);
+    for entity in &attacked {
+        commands.entity(entity).remove::<AttackedThisPhase>();
+    }
+}
+
+/// Computes the active combat's odds with the strength model of the
+/// combat's CRT (see `ActiveCombat::table`): the attack property summed
+/// over the attacking units against the defense property summed over the
+/// defending units, multiplied by the primary defender's terrain. Clears
+/// the odds while the model or a combatant is missing.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_combat_odds(
+    mut active_combat: ResMut<ActiveCombat>,
+    crt: Res<CombatResultsTable>,
+    tables: Res<CombatTableRegistry>,
+    concepts: Res<ConceptRegistry>,
+    entity_types: Res<EntityTypeRegistry>,
+    units: Query<(&HexPosition, &EntityData), With<UnitInstance>>,
+    tiles: Query<(&HexPosition, &EntityData), (With<HexTile>, Without<UnitInstance>)>,
+) {
+    let odds = combat_odds(
+        &active_combat,
+        tables.table_or(active_combat.table, &crt),
+        &concepts,
+        &entity_types,
+        &units,
+        &tiles,
+    );
+    if active_combat.odds != odds {
+        active_combat.odds = odds;
+    }
+}
+
+/// The odds of the active combat, if the strength model is set up and both
+/// combatants are on the board.
+#[allow(clippy::type_complexity)]
+fn combat_odds(
+    active_combat: &ActiveCombat,
+    crt: &CombatResultsTable,
+    concepts: &ConceptRegistry,
+    entity_types: &EntityTypeRegistry,
+    units: &Query<(&HexPosition, &EntityData), With<UnitInstance>>,
+    tiles: &Query<(&HexPosition, &EntityData), (With<HexTile>, Without<UnitInstance>)>,
+) -> Option<CombatOdds> {
+    let concept_id = crt.combat_concept_id?;
+    let attack = crt.strength.attack_property.as_deref()?;
+    let defense = crt.strength.defense_property.as_deref()?;
+    units.get(active_combat.attacker?).ok()?;
+    let (defender_pos, _) = units.get(active_combat.defender?).ok()?;
+
+    let strengths = |entities: Vec<Entity>, name: &str| -> Vec<CombatStrength> {
+        entities
+            .into_iter()
+            .filter_map(|entity| units.get(entity).ok())
+            .filter_map(|(_, data)| {
+                Some(CombatStrength {
+                    label: entity_types
+                        .get(data.entity_type_id)
+                        .map_or("Unit", |t| t.name.as_str())
+                        .to_string(),
+                    strength: concept_property_value(concepts, concept_id, data, name)?,
+                })
+            })
+            .collect()
+    };
+    let terrain = tiles
+        .iter()
+        .find(|(pos, _)| *pos == defender_pos)
+        .map(|(_, data)| data.entity_type_id);
+    let column_type = crt
+        .table
+        .columns
+        .first()
+        .map_or(ColumnType::Ratio, |column| column.column_type);
+
+    Some(compute_combat_odds(
+        &crt.strength,
+        column_type,
+        &strengths(active_combat.attacking_units(), attack),
+        &strengths(active_combat.defending_units(), defense),
+        terrain,
+    ))
+}
+
+/// Moves a unit off the board into an off-map zone. The unit's base
+/// `EntityData` (without presence effects or state overrides), owner, id and
+/// state are stored in the zone so it can be deployed again unchanged.
+#[allow(clippy::type_complexity)]
+pub fn handle_move_to_zone(
+    trigger: On<MoveToZoneEvent>,
+    mut zones: ResMut<OffMapZoneRegistry>,
+    mut selected_unit: ResMut<SelectedUnit>,
+    units: Query<
+        (
+            &EntityData,
+            &UnitOwner,
+            &UnitId,
+            Option<&PresenceEffects>,
+            Option<(&EntityState, &StateOverrides)>,
+        ),
+        With<UnitInstance>,
+    >,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let Ok((entity_data, owner, unit_id, effects, state)) = units.get(event.entity) else {
+        return;
+    };
+    let mut data = effects.map_or_else(|| entity_data.clone(), |e| e.base_data(entity_data));
+    if let Some((_, overrides)) = state {
+        data = overrides.base_data(&data);
+    }
+    let unit = ZoneUnit::with_owner(data, *owner)
+        .with_id(*unit_id)
+        .with_state(state.map(|(s, _)| s.state_id));
+    if !zones.store(event.zone_id, unit) {
+        return;
+    }
+    if selected_unit.entity == Some(event.entity) {
+        selected_unit.entity = None;
+    }
+    commands.entity(event.entity).despawn();
+}
+
+/// Deploys a held unit from an off-map zone onto a hex.
+///
+/// In Play mode the zone's deploy phase (if any) must be the current phase.
+/// Bounds, stacking and mixed stacks are checked as for placement. Visuals
+/// are attached by `assign_unit_visuals` on the next frame.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn handle_deploy_from_zone(
+    trigger: On<DeployFromZoneEvent>,
+    screen: Res<State<AppScreen>>,
+    config: Res<HexGridConfig>,
+    stacking_rule: Res<StackingRule>,
+    turn_state: Res<TurnState>,
+    turn_structure: Res<TurnStructure>,
+    mut zones: ResMut<OffMapZoneRegistry>,
+    existing_units: Query<(&HexPosition, &EntityData, &UnitOwner), With<UnitInstance>>,
+    tiles: Query<(&HexPosition, &EntityData), (With<HexTile>, Without<UnitInstance>)>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let pos = event.position;
+
+    if *screen.get() == AppScreen::Play {
+        let phase_id = current_phase(&turn_state, &turn_structure).map(|p| p.id);
+        if !zones.can_deploy(event.zone_id, phase_id) {
+            return;
+        }
+    }
+
+    if !config.contains(pos) {
+        return;
+    }
+
+    let Some((data, owner)) = zones
+        .get(event.zone_id)
+        .and_then(|z| z.units.get(event.index))
+        .map(|u| {
+            (
+                EntityData::from(u.clone()),
+                UnitOwner {
+                    faction_id: u.owner,
+                },
+            )
+        })
+    else {
+        return;
+    };
+
+    if stacking_blocks(&stacking_rule, &existing_units, &tiles, pos, &data, owner) {
+        return;
+    }
+
+    let Some(unit) = zones.take(event.zone_id, event.index) else {
+        return;
     };
 
-    if active_combat.attacker == Some(entity) {
-        // Clicked current attacker → deselect.
-        active_combat.attacker = None;
-        active_combat.die_roll = None;
-        active_combat.outcome = None;
-    } else if active_combat.defender == Some(entity) {
-        // Clicked current defender → deselect.
-        active_combat.defender = None;
-        active_combat.die_roll = None;
-        active_combat.outcome = None;
-    } else if active_combat.attacker.is_none() {
-        // No attacker yet → assign.
-        active_combat.attacker = Some(entity);
-        active_combat.die_roll = None;
-        active_combat.outcome = None;
-    } else if active_combat.defender.is_none() {
-        // Attacker set, no defender → assign.
-        active_combat.defender = Some(entity);
-        active_combat.die_roll = None;
-        active_combat.outcome = None;
-    }
-    // Both already set — ignore (deselect first to reassign).
+    let world_pos = config.layout.hex_to_world_pos(pos.to_hex());
+    let unit_id = unit.id.unwrap_or_default();
+    let state = unit.state;
+    let mut spawned = commands.spawn((
+        UnitInstance,
+        pos,
+        EntityData::from(unit),
+        owner,
+        unit_id,
+        Transform::from_xyz(world_pos.x, UNIT_Y_OFFSET, world_pos.y),
+    ));
+    if let Some(state_id) = state {
+        spawned.insert(EntityState { state_id });
+    }
+}
+
+/// Whether a unit with `data` owned by `owner` may not join the units
+/// already at `pos`: its stacking points would exceed the hex's limit, or the
+/// hex holds an opposed faction.
+#[allow(clippy::type_complexity)]
+fn stacking_blocks(
+    stacking_rule: &StackingRule,
+    existing_units: &Query<(&HexPosition, &EntityData, &UnitOwner), With<UnitInstance>>,
+    tiles: &Query<(&HexPosition, &EntityData), (With<HexTile>, Without<UnitInstance>)>,
+    pos: HexPosition,
+    data: &EntityData,
+    owner: UnitOwner,
+) -> bool {
+    let here = || existing_units.iter().filter(move |(p, _, _)| **p == pos);
+    if stacking_rule.is_active() {
+        let terrain = tiles
+            .iter()
+            .find(|(p, _)| **p == pos)
+            .map(|(_, tile)| tile.entity_type_id);
+        let present = here().map(|(_, d, _)| stacking_rule.points(d)).sum();
+        let limit = stacking_rule.limit(terrain, owner);
+        if StackingRule::would_exceed(stacking_rule.points(data), present, limit) {
+            return true;
+        }
+    }
+    stacking_rule.would_mix_factions(owner, here().map(|(_, _, o)| *o))
 }
 
 // ---------------------------------------------------------------------------
@@ -309,6 +776,20 @@ pub fn delete_selected_unit(
     }
 }
 
+/// Keeps `UnitIndex` in step with the board, rebuilding it whenever a unit
+/// is spawned, despawned or given a different id.
+pub fn sync_unit_index(
+    mut index: ResMut<UnitIndex>,
+    units: Query<(Entity, Ref<UnitId>), With<UnitInstance>>,
+    mut removed: RemovedComponents<UnitId>,
+) {
+    let any_removed = removed.read().count() > 0;
+    if !any_removed && !units.iter().any(|(_, id)| id.is_changed()) {
+        return;
+    }
+    index.entities = units.iter().map(|(entity, id)| (*id, entity)).collect();
+}
+
 /// Attaches mesh and material to `UnitInstance` entities that lack them.
 /// This covers units spawned by `apply_pending_board_load` which only
 /// adds core ECS components (no visuals).
@@ -362,6 +843,22 @@ pub fn sync_unit_materials(
         .retain(|id, _| valid_ids.contains(id));
 }
 
+/// Moves unit tokens to their hex when `HexPosition` changes (change
+/// detection), e.g. after a move in Play.
+#[allow(clippy::type_complexity)]
+pub fn sync_unit_positions(
+    config: Res<HexGridConfig>,
+    mut units: Query<(&HexPosition, &mut Transform), (With<UnitInstance>, Changed<HexPosition>)>,
+) {
+    for (pos, mut transform) in &mut units {
+        let world_pos = config.layout.hex_to_world_pos(pos.to_hex());
+        let translation = Vec3::new(world_pos.x, UNIT_Y_OFFSET, world_pos.y);
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+}
+
 /// Syncs unit material when `EntityData` changes (change detection).
 #[allow(clippy::type_complexity)]
 pub fn sync_unit_visuals(
@@ -377,3 +874,84 @@ pub fn sync_unit_visuals(
         }
     }
 }
+
+/// Tints each unit's token. A unit in a state with a colour uses that
+/// colour in place of its type's; an owned unit's colour is mixed evenly
+/// with its faction's. Units with neither use the plain type material, as do
+/// units whose faction or state no longer exists. Runs after
+/// `sync_unit_visuals` so the tint wins over the type material.
+#[allow(clippy::type_complexity)]
+pub fn apply_faction_tints(
+    registry: Res<EntityTypeRegistry>,
+    factions: Option<Res<FactionRegistry>>,
+    state_machines: Option<Res<StateMachineRegistry>>,
+    mut unit_materials: ResMut<UnitMaterials>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut units: Query<
+        (
+            Ref<EntityData>,
+            Ref<UnitOwner>,
+            Option<Ref<EntityState>>,
+            &mut MeshMaterial3d<StandardMaterial>,
+        ),
+        With<UnitInstance>,
+    >,
+) {
+    let refresh_all = registry.is_changed()
+        || factions.as_ref().is_some_and(DetectChanges::is_changed)
+        || state_machines
+            .as_ref()
+            .is_some_and(DetectChanges::is_changed);
+    if refresh_all {
+        // Colours may have changed; tinted materials are rebuilt on demand.
+        unit_materials.tinted.clear();
+    }
+
+    for (data, owner, state, mut material) in &mut units {
+        let state_changed = state.as_ref().is_some_and(DetectChanges::is_changed);
+        if !refresh_all
+            && !data.is_changed()
+            && !owner.is_changed()
+            && !state_changed
+            && !material.is_added()
+        {
+            continue;
+        }
+        let faction = owner
+            .faction_id
+            .and_then(|id| factions.as_ref().and_then(|f| f.get(id)));
+        let state = state.and_then(|s| {
+            state_machines
+                .as_ref()
+                .and_then(|m| m.state(s.state_id))
+                .filter(|def| def.color.is_some())
+        });
+        let unit_type = registry.get(data.entity_type_id);
+        let handle = match unit_type {
+            Some(unit_type) if faction.is_some() || state.is_some() => {
+                let key = (unit_type.id, faction.map(|f| f.id), state.map(|s| s.id));
+                unit_materials
+                    .tinted
+                    .entry(key)
+                    .or_insert_with(|| {
+                        let base = state.and_then(|s| s.color).unwrap_or(unit_type.color);
+                        let color = faction.map_or(base, |f| base.mix(&f.color, 0.5));
+                        materials.add(StandardMaterial {
+                            base_color: color,
+                            ..default()
+                        })
+                    })
+                    .clone()
+            }
+            _ => {
+                let Some(handle) = unit_materials.get(data.entity_type_id) else {
+                    continue;
+                };
+                handle.clone()
+            }
+        };
+        if material.0 != handle {
+            material.0 = handle;
+        }
+    }
+}
@@ -6,10 +6,14 @@ use bevy::prelude::*;
 
 use hexorder_contracts::editor_ui::EditorTool;
 use hexorder_contracts::game_system::{
-    ActiveTokenType, EntityData, EntityRole, EntityType, EntityTypeRegistry, SelectedUnit, TypeId,
-    UnitInstance,
+    ActiveFaction, ActiveTokenType, EntityData, EntityRole, EntityState, EntityType,
+    EntityTypeRegistry, Faction, FactionRegistry, SelectedUnit, StateDefinition, StateMachine,
+    StateMachineRegistry, StateOverrides, TypeId, UnitId, UnitIndex, UnitInstance, UnitOwner,
 };
-use hexorder_contracts::hex_grid::{HexGridConfig, HexPosition, HexSelectedEvent};
+use hexorder_contracts::hex_grid::{
+    GridShape, HexGridConfig, HexPosition, HexSelectedEvent, HexTile,
+};
+use hexorder_contracts::mechanics::MoveRequestedEvent;
 use hexorder_contracts::persistence::AppScreen;
 use hexorder_contracts::shortcuts::ShortcutRegistry;
 use hexorder_contracts::undo_redo::UndoStack;
@@ -62,6 +66,7 @@ fn test_grid_config() -> HexGridConfig {
             origin: bevy::math::Vec2::ZERO,
         },
         map_radius: 5,
+        shape: GridShape::Hexagon,
     }
 }
 
@@ -145,6 +150,262 @@ fn place_unit_creates_entity() {
     assert_eq!(data.entity_type_id, first_id);
 }
 
+#[test]
+fn place_unit_owned_by_active_faction() {
+    let mut app = test_app();
+    setup_unit_resources(&mut app);
+    app.update();
+
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    let faction_id = TypeId::new();
+    app.world_mut().insert_resource(EditorTool::Place);
+    app.world_mut().insert_resource(ActiveTokenType {
+        entity_type_id: Some(first_id),
+    });
+    app.world_mut().insert_resource(ActiveFaction {
+        faction_id: Some(faction_id),
+    });
+    app.add_observer(systems::handle_unit_placement);
+
+    app.world_mut().commands().trigger(HexSelectedEvent {
+        position: HexPosition::new(0, 0),
+    });
+    app.update();
+
+    let mut query = app
+        .world_mut()
+        .query_filtered::<&UnitOwner, With<UnitInstance>>();
+    let owners: Vec<_> = query.iter(app.world()).copied().collect();
+    assert_eq!(
+        owners,
+        vec![UnitOwner {
+            faction_id: Some(faction_id)
+        }]
+    );
+}
+
+#[test]
+fn place_unit_rejects_mixed_stack() {
+    let mut app = test_app();
+    setup_unit_resources(&mut app);
+    app.insert_resource(hexorder_contracts::hex_grid::StackingRule {
+        no_mixed_factions: true,
+        ..Default::default()
+    });
+    app.update();
+
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    app.world_mut().spawn((
+        UnitInstance,
+        HexPosition::new(0, 0),
+        EntityData {
+            entity_type_id: first_id,
+            properties: HashMap::new(),
+        },
+        UnitOwner {
+            faction_id: Some(TypeId::new()),
+        },
+    ));
+    app.world_mut().insert_resource(EditorTool::Place);
+    app.world_mut().insert_resource(ActiveTokenType {
+        entity_type_id: Some(first_id),
+    });
+    app.world_mut().insert_resource(ActiveFaction {
+        faction_id: Some(TypeId::new()),
+    });
+    app.add_observer(systems::handle_unit_placement);
+
+    app.world_mut().commands().trigger(HexSelectedEvent {
+        position: HexPosition::new(0, 0),
+    });
+    app.update();
+
+    let mut query = app.world_mut().query_filtered::<(), With<UnitInstance>>();
+    assert_eq!(query.iter(app.world()).count(), 1);
+}
+
+#[test]
+fn place_unit_respects_terrain_stacking_limit() {
+    let mut app = test_app();
+    setup_unit_resources(&mut app);
+    let swamp_id = TypeId::new();
+    app.insert_resource(hexorder_contracts::hex_grid::StackingRule {
+        terrain_limits: HashMap::from([(swamp_id, 1)]),
+        ..Default::default()
+    });
+    app.update();
+
+    app.world_mut().spawn((
+        HexTile,
+        HexPosition::new(0, 0),
+        EntityData {
+            entity_type_id: swamp_id,
+            properties: HashMap::new(),
+        },
+    ));
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    app.world_mut().insert_resource(EditorTool::Place);
+    app.world_mut().insert_resource(ActiveTokenType {
+        entity_type_id: Some(first_id),
+    });
+    app.add_observer(systems::handle_unit_placement);
+
+    for _ in 0..2 {
+        app.world_mut().commands().trigger(HexSelectedEvent {
+            position: HexPosition::new(0, 0),
+        });
+        app.update();
+    }
+
+    let mut query = app.world_mut().query_filtered::<(), With<UnitInstance>>();
+    assert_eq!(query.iter(app.world()).count(), 1);
+}
+
+#[test]
+fn faction_tint_applied_to_owned_unit() {
+    let mut app = test_app();
+    setup_unit_resources(&mut app);
+    let faction = Faction {
+        id: TypeId::new(),
+        name: "Red".to_string(),
+        color: Color::srgb(1.0, 0.0, 0.0),
+        player_order: 0,
+    };
+    let faction_id = faction.id;
+    app.insert_resource(FactionRegistry {
+        factions: vec![faction],
+    });
+    app.add_systems(
+        Update,
+        (systems::assign_unit_visuals, systems::apply_faction_tints).chain(),
+    );
+    app.update();
+
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    let owned = app
+        .world_mut()
+        .spawn((
+            UnitInstance,
+            HexPosition::new(0, 0),
+            EntityData {
+                entity_type_id: first_id,
+                properties: HashMap::new(),
+            },
+            UnitOwner {
+                faction_id: Some(faction_id),
+            },
+        ))
+        .id();
+    let unowned = app
+        .world_mut()
+        .spawn((
+            UnitInstance,
+            HexPosition::new(1, 0),
+            EntityData {
+                entity_type_id: first_id,
+                properties: HashMap::new(),
+            },
+        ))
+        .id();
+    app.update();
+
+    let materials = app.world().resource::<UnitMaterials>();
+    let base = materials.get(first_id).expect("base material").clone();
+    let tinted = materials
+        .tinted
+        .get(&(first_id, Some(faction_id), None))
+        .expect("tinted material")
+        .clone();
+    let material_of = |entity| {
+        app.world()
+            .get::<MeshMaterial3d<StandardMaterial>>(entity)
+            .expect("material")
+            .0
+            .clone()
+    };
+    assert_eq!(material_of(owned), tinted);
+    assert_eq!(material_of(unowned), base);
+}
+
+/// A state with a colour replaces the unit type's colour, and the token
+/// follows the unit as its state changes.
+#[test]
+fn state_color_tints_unit() {
+    let mut app = test_app();
+    setup_unit_resources(&mut app);
+    app.update();
+
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    let state = |name: &str, color| StateDefinition {
+        id: TypeId::new(),
+        name: name.to_string(),
+        property_overrides: HashMap::new(),
+        color,
+        badge: String::new(),
+    };
+    let full = state("Full", None);
+    let reduced = state("Reduced", Some(Color::srgb(0.5, 0.5, 0.5)));
+    let (full_id, reduced_id) = (full.id, reduced.id);
+    app.insert_resource(StateMachineRegistry {
+        machines: vec![StateMachine {
+            id: TypeId::new(),
+            name: "Steps".to_string(),
+            entity_type_id: first_id,
+            states: vec![full, reduced],
+            initial_state: None,
+            transitions: Vec::new(),
+        }],
+    });
+    app.add_systems(
+        Update,
+        (systems::assign_unit_visuals, systems::apply_faction_tints).chain(),
+    );
+    app.update();
+
+    let unit = app
+        .world_mut()
+        .spawn((
+            UnitInstance,
+            HexPosition::new(0, 0),
+            EntityData {
+                entity_type_id: first_id,
+                properties: HashMap::new(),
+            },
+            EntityState { state_id: full_id },
+        ))
+        .id();
+    app.update();
+
+    let material_of = |app: &App| {
+        app.world()
+            .get::<MeshMaterial3d<StandardMaterial>>(unit)
+            .expect("material")
+            .0
+            .clone()
+    };
+    let base = app
+        .world()
+        .resource::<UnitMaterials>()
+        .get(first_id)
+        .expect("base material")
+        .clone();
+    assert_eq!(material_of(&app), base, "a state without a colour");
+
+    app.world_mut().entity_mut(unit).insert(EntityState {
+        state_id: reduced_id,
+    });
+    app.update();
+
+    let tinted = app
+        .world()
+        .resource::<UnitMaterials>()
+        .tinted
+        .get(&(first_id, None, Some(reduced_id)))
+        .expect("state material")
+        .clone();
+    assert_eq!(material_of(&app), tinted);
+}
+
 #[test]
 fn place_unit_skipped_in_select_mode() {
     let mut app = test_app();
@@ -321,6 +582,79 @@ fn move_unit_respects_grid_bounds() {
     );
 }
 
+/// Destinations requested by `MoveRequestedEvent`s.
+#[derive(Resource, Default)]
+struct RequestedMoves(Vec<(Entity, HexPosition)>);
+
+#[test]
+fn move_in_play_is_requested_from_rules_engine() {
+    let mut app = test_app();
+    setup_unit_resources(&mut app);
+    app.world_mut()
+        .resource_mut::<NextState<AppScreen>>()
+        .set(AppScreen::Play);
+    app.update();
+
+    app.world_mut().insert_resource(EditorTool::Select);
+    app.init_resource::<ValidMoveSet>();
+    app.init_resource::<RequestedMoves>();
+    app.add_observer(
+        |trigger: On<MoveRequestedEvent>, mut requested: ResMut<RequestedMoves>| {
+            requested
+                .0
+                .push((trigger.event().entity, trigger.event().to));
+        },
+    );
+    app.add_systems(Update, systems::sync_unit_positions);
+
+    let registry = app.world().resource::<EntityTypeRegistry>();
+    let first_id = registry.types_by_role(EntityRole::Token)[0].id;
+    let unit_entity = app
+        .world_mut()
+        .spawn((
+            UnitInstance,
+            HexPosition::new(0, 0),
+            EntityData {
+                entity_type_id: first_id,
+                properties: HashMap::new(),
+            },
+            Transform::default(),
+        ))
+        .id();
+    app.world_mut().insert_resource(SelectedUnit {
+        entity: Some(unit_entity),
+    });
+    app.add_observer(systems::handle_unit_interaction);
+
+    app.world_mut().commands().trigger(HexSelectedEvent {
+        position: HexPosition::new(2, 1),
+    });
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<RequestedMoves>().0,
+        vec![(unit_entity, HexPosition::new(2, 1))]
+    );
+    assert_eq!(
+        *app.world().get::<HexPosition>(unit_entity).unwrap(),
+        HexPosition::new(0, 0),
+        "the rules engine moves the unit, not the click"
+    );
+    assert!(app.world().resource::<SelectedUnit>().entity.is_none());
+
+    // Once moved, the token follows its new hex.
+    app.world_mut()
+        .entity_mut(unit_entity)
+        .insert(HexPosition::new(2, 1));
+    app.update();
+    let expected = test_grid_config()
+        .layout
+        .hex_to_world_pos(HexPosition::new(2, 1).to_hex());
+    let transform = app.world().get::<Transform>(unit_entity).unwrap();
+    assert_eq!(transform.translation.x, expected.x);
+    assert_eq!(transform.translation.z, expected.y);
+}
+
 #[test]
 fn sync_unit_visuals_updates_material() {
     let mut app = test_app();
@@ -787,6 +1121,7 @@ fn move_unit_blocked_by_valid_move_set() {
         valid_positions: HashSet::from([HexPosition::new(1, 0)]),
         blocked_explanations: HashMap::new(),
         for_entity: Some(unit_entity),
+        ..Default::default()
     });
 
     app.add_observer(systems::handle_unit_interaction);
@@ -845,6 +1180,7 @@ fn move_unit_allowed_by_valid_move_set() {
         valid_positions: HashSet::from([HexPosition::new(1, 0)]),
         blocked_explanations: HashMap::new(),
         for_entity: Some(unit_entity),
+        ..Default::default()
     });
 
     app.add_observer(systems::handle_unit_interaction);
@@ -987,7 +1323,8 @@ fn assign_unit_visuals_adds_mesh_to_bare_units() {
 
 use hexorder_contracts::mechanics::ActiveCombat;
 
-/// Helper: set up an app with CombatSelect tool, two placed units, and the observer.
+/// Helper: set up an app with `CombatSelect` tool, two placed units of opposed
+/// factions, and the observer.
 fn combat_select_app() -> (App, Entity, Entity) {
     let mut app = test_app();
     setup_unit_resources(&mut app);
@@ -1016,6 +1353,9 @@ fn combat_select_app() -> (App, Entity, Entity) {
                 entity_type_id: type_id,
                 properties: HashMap::new(),
             },
+            UnitOwner {
+                faction_id: Some(TypeId::new()),
+            },
             Transform::default(),
         ))
         .id();
@@ -1028,6 +1368,9 @@ fn combat_select_app() -> (App, Entity, Entity) {
                 entity_type_id: type_id,
                 properties: HashMap::new(),
             },
+            UnitOwner {
+                faction_id: Some(TypeId::new()),
+            },
             Transform::default(),
         ))
         .id();
@@ -1068,6 +1411,58 @@ fn combat_select_second_click_sets_defender() {
     assert_eq!(combat.defender, Some(defender));
 }
 
+#[test]
+fn combat_select_rejects_friendly_defender() {
+    let (mut app, attacker, defender) = combat_select_app();
+    let attacker_owner = *app
+        .world()
+        .get::<UnitOwner>(attacker)
+        .expect("attacker owner");
+    app.world_mut().entity_mut(defender).insert(attacker_owner);
+
+    app.world_mut().trigger(HexSelectedEvent {
+        position: HexPosition::new(0, 0),
+    });
+    app.update();
+    app.world_mut().trigger(HexSelectedEvent {
+        position: HexPosition::new(1, 0),
+    });
+    app.update();
+
+    let combat = app.world().resource::<ActiveCombat>();
+    assert_eq!(combat.attacker, Some(attacker));
+    assert_eq!(combat.defender, None);
+}
+
+#[test]
+fn combat_select_rejects_attacker_denied_attack() {
+    use hexorder_contracts::ontology::GatedAction;
+    use hexorder_contracts::validation::{ActionEligibility, ValidationResult};
+
+    let (mut app, attacker, _) = combat_select_app();
+    app.world_mut()
+        .entity_mut(attacker)
+        .insert(ActionEligibility {
+            denied: vec![(
+                GatedAction::Attack,
+                ValidationResult {
+                    constraint_id: TypeId::new(),
+                    constraint_name: "Command Range".to_string(),
+                    satisfied: false,
+                    explanation: "out of command".to_string(),
+                },
+            )],
+        });
+
+    app.world_mut().trigger(HexSelectedEvent {
+        position: HexPosition::new(0, 0),
+    });
+    app.update();
+
+    let combat = app.world().resource::<ActiveCombat>();
+    assert_eq!(combat.attacker, None);
+}
+
 #[test]
 fn combat_select_click_attacker_again_deselects() {
     let (mut app, _, _) = combat_select_app();
@@ -1115,3 +1510,610 @@ fn combat_select_wrong_tool_ignored() {
     let combat = app.world().resource::<ActiveCombat>();
     assert_eq!(combat.attacker, None);
 }
+
+#[test]
+fn combat_select_adds_stacked_attackers_one_at_a_time() {
+    let (mut app, attacker, _) = combat_select_app();
+    let owner = *app.world().get::<UnitOwner>(attacker).expect("owner");
+    let data = app
+        .world()
+        .get::<EntityData>(attacker)
+        .expect("data")
+        .clone();
+    let second = app
+        .world_mut()
+        .spawn((UnitInstance, HexPosition::new(0, 0), data, owner))
+        .id();
+
+    let click = |app: &mut App| {
+        app.world_mut().trigger(HexSelectedEvent {
+            position: HexPosition::new(0, 0),
+        });
+        app.update();
+    };
+    click(&mut app);
+    click(&mut app);
+    let combat = app.world().resource::<ActiveCombat>();
+    assert_eq!(combat.attackers, vec![attacker, second]);
+    assert_eq!(combat.attacker, Some(attacker));
+
+    // Every unit of the hex attacks, so the next click withdraws them.
+    click(&mut app);
+    let combat = app.world().resource::<ActiveCombat>();
+    assert!(combat.attackers.is_empty());
+    assert_eq!(combat.attacker, None);
+}
+
+#[test]
+fn combat_select_adds_the_whole_defending_hex() {
+    let (mut app, attacker, defender) = combat_select_app();
+    let owner = *app.world().get::<UnitOwner>(defender).expect("owner");
+    let data = app
+        .world()
+        .get::<EntityData>(defender)
+        .expect("data")
+        .clone();
+    let second = app
+        .world_mut()
+        .spawn((UnitInstance, HexPosition::new(1, 0), data, owner))
+        .id();
+
+    for position in [HexPosition::new(0, 0), HexPosition::new(1, 0)] {
+        app.world_mut().trigger(HexSelectedEvent { position });
+        app.update();
+    }
+    let combat = app.world().resource::<ActiveCombat>();
+    assert_eq!(combat.attackers, vec![attacker]);
+    assert_eq!(combat.defenders, vec![defender, second]);
+
+    app.world_mut().trigger(HexSelectedEvent {
+        position: HexPosition::new(1, 0),
+    });
+    app.update();
+    let combat = app.world().resource::<ActiveCombat>();
+    assert!(combat.defenders.is_empty());
+    assert_eq!(combat.defender, None);
+}
+
+#[test]
+fn combat_select_skips_units_that_already_attacked() {
+    let (mut app, attacker, _) = combat_select_app();
+    app.world_mut()
+        .entity_mut(attacker)
+        .insert(AttackedThisPhase);
+
+    app.world_mut().trigger(HexSelectedEvent {
+        position: HexPosition::new(0, 0),
+    });
+    app.update();
+
+    assert_eq!(app.world().resource::<ActiveCombat>().attacker, None);
+}
+
+// ---------------------------------------------------------------------------
+// Attack eligibility
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::mechanics::{
+    AttackIneligibility, AttackedThisPhase, CombatOutcome, CombatResolvedEvent, Phase, PhaseType,
+    TurnState, TurnStructure,
+};
+
+/// Helper: the combat select app in the Combat phase of a Movement/Combat
+/// turn, with the attacker and defender selected and the eligibility,
+/// marking and reset systems added.
+fn eligibility_app() -> (App, Entity, Entity) {
+    let (mut app, attacker, defender) = combat_select_app();
+    let phase = |name: &str, phase_type| Phase {
+        id: TypeId::new(),
+        name: name.to_string(),
+        phase_type,
+        description: String::new(),
+    };
+    app.insert_resource(TurnStructure {
+        phases: vec![
+            phase("Move", PhaseType::Movement),
+            phase("Fight", PhaseType::Combat),
+        ],
+        ..TurnStructure::default()
+    });
+    app.insert_resource(TurnState {
+        turn_number: 1,
+        current_phase_index: 1,
+        is_active: true,
+        phase_actions_remaining: None,
+    });
+    app.add_systems(
+        Update,
+        (
+            systems::reset_attacked_this_phase,
+            systems::update_attack_eligibility,
+        )
+            .chain(),
+    );
+    app.add_observer(systems::mark_attackers);
+
+    let mut combat = app.world_mut().resource_mut::<ActiveCombat>();
+    combat.add_attacker(attacker);
+    combat.add_defender(defender);
+    app.update();
+    (app, attacker, defender)
+}
+
+#[test]
+fn eligibility_accepts_adjacent_attackers_in_combat_phase() {
+    let (app, ..) = eligibility_app();
+    assert!(app.world().resource::<ActiveCombat>().ineligible.is_empty());
+}
+
+#[test]
+fn eligibility_flags_participants_that_are_not_adjacent() {
+    let (mut app, attacker, defender) = eligibility_app();
+    app.world_mut()
+        .entity_mut(defender)
+        .insert(HexPosition::new(3, 0));
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<ActiveCombat>().ineligible,
+        vec![
+            (attacker, AttackIneligibility::NotAdjacent),
+            (defender, AttackIneligibility::NotAdjacent),
+        ]
+    );
+}
+
+#[test]
+fn eligibility_flags_attacks_outside_a_combat_phase() {
+    let (mut app, attacker, _) = eligibility_app();
+    app.world_mut()
+        .resource_mut::<TurnState>()
+        .current_phase_index = 0;
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<ActiveCombat>().ineligible,
+        vec![(attacker, AttackIneligibility::NotCombatPhase)]
+    );
+}
+
+#[test]
+fn attackers_are_marked_until_the_phase_changes() {
+    let (mut app, attacker, defender) = eligibility_app();
+    app.world_mut().trigger(CombatResolvedEvent {
+        attacker,
+        defender,
+        attackers: vec![attacker],
+        defenders: vec![defender],
+        step_losses: Vec::new(),
+        outcome: CombatOutcome {
+            label: "NE".to_string(),
+            effect: None,
+        },
+        die_roll: 3,
+        column_label: "1:1".to_string(),
+    });
+    app.update();
+    assert!(app.world().get::<AttackedThisPhase>(attacker).is_some());
+    assert_eq!(
+        app.world().resource::<ActiveCombat>().ineligible,
+        vec![(attacker, AttackIneligibility::AlreadyAttacked)]
+    );
+
+    app.world_mut().resource_mut::<TurnState>().turn_number = 2;
+    app.update();
+    assert!(app.world().get::<AttackedThisPhase>(attacker).is_none());
+}
+
+// ---------------------------------------------------------------------------
+// Combat odds
+// ---------------------------------------------------------------------------
+
+use hexorder_contracts::game_system::PropertyValue;
+use hexorder_contracts::mechanics::{CombatResultsTable, CombatTableRegistry, TerrainMultiplier};
+use hexorder_contracts::ontology::{ConceptBinding, ConceptRegistry, PropertyBinding};
+
+/// Helper: an app where two units with attack 3 and 4 at (0,0) attack one
+/// unit with defense 2 at (1,0) on terrain that doubles defense. Returns the
+/// app with the participants set in `ActiveCombat`.
+fn combat_odds_app() -> App {
+    let mut app = test_app();
+    let registry = test_registry();
+    let type_id = registry.types[0].id;
+    app.insert_resource(registry);
+
+    let concept_id = TypeId::new();
+    let attack_id = TypeId::new();
+    let defense_id = TypeId::new();
+    app.insert_resource(ConceptRegistry {
+        concepts: Vec::new(),
+        bindings: vec![ConceptBinding {
+            id: TypeId::new(),
+            entity_type_id: type_id,
+            concept_id,
+            concept_role_id: TypeId::new(),
+            property_bindings: vec![
+                PropertyBinding {
+                    property_id: attack_id,
+                    concept_local_name: "attack".to_string(),
+                },
+                PropertyBinding {
+                    property_id: defense_id,
+                    concept_local_name: "defense".to_string(),
+                },
+            ],
+        }],
+    });
+
+    let fort_id = TypeId::new();
+    let mut crt = CombatResultsTable {
+        combat_concept_id: Some(concept_id),
+        ..CombatResultsTable::default()
+    };
+    crt.strength.attack_property = Some("attack".to_string());
+    crt.strength.defense_property = Some("defense".to_string());
+    crt.strength.terrain_multipliers.push(TerrainMultiplier {
+        terrain_type_id: fort_id,
+        multiplier: 2.0,
+    });
+    app.insert_resource(crt);
+    app.init_resource::<CombatTableRegistry>();
+
+    let unit = |attack: i64, defense: i64| EntityData {
+        entity_type_id: type_id,
+        properties: HashMap::from([
+            (attack_id, PropertyValue::Int(attack)),
+            (defense_id, PropertyValue::Int(defense)),
+        ]),
+    };
+    let attacker = app
+        .world_mut()
+        .spawn((UnitInstance, HexPosition::new(0, 0), unit(3, 1)))
+        .id();
+    let second = app
+        .world_mut()
+        .spawn((UnitInstance, HexPosition::new(0, 0), unit(4, 1)))
+        .id();
+    let defender = app
+        .world_mut()
+        .spawn((UnitInstance, HexPosition::new(1, 0), unit(1, 2)))
+        .id();
+    app.world_mut().spawn((
+        HexTile,
+        HexPosition::new(1, 0),
+        EntityData {
+            entity_type_id: fort_id,
+            properties: HashMap::new(),
+        },
+    ));
+
+    app.insert_resource(ActiveCombat {
+        attacker: Some(attacker),
+        defender: Some(defender),
+        attackers: vec![attacker, second],
+        defenders: vec![defender],
+        ..ActiveCombat::default()
+    });
+    app.add_systems(Update, systems::update_combat_odds);
+    app
+}
+
+#[test]
+fn combat_odds_sum_the_stack_and_double_fortified_defense() {
+    let mut app = combat_odds_app();
+    app.update();
+
+    let combat = app.world().resource::<ActiveCombat>();
+    let odds = combat.odds.as_ref().expect("odds computed");
+    assert_eq!(odds.attacker_strength, 7.0);
+    assert_eq!(odds.defender_strength, 4.0);
+    // 7:4 rounds down to 1:1 in the defender's favor.
+    assert_eq!(odds.raw_value, 1.0);
+}
+
+#[test]
+fn combat_odds_cleared_without_a_strength_model() {
+    let mut app = combat_odds_app();
+    app.update();
+    app.world_mut()
+        .resource_mut::<CombatResultsTable>()
+        .strength
+        .attack_property = None;
+    app.update();
+
+    assert!(app.world().resource::<ActiveCombat>().odds.is_none());
+}
+
+#[test]
+fn combat_odds_use_the_selected_table() {
+    let mut app = combat_odds_app();
+    let mut assault = app.world().resource::<CombatResultsTable>().clone();
+    assault.id = TypeId::new();
+    assault.strength.terrain_multipliers.clear();
+    let assault_id = assault.id;
+    app.world_mut()
+        .resource_mut::<CombatTableRegistry>()
+        .tables
+        .push(assault);
+    app.world_mut().resource_mut::<ActiveCombat>().table = Some(assault_id);
+    app.update();
+
+    let combat = app.world().resource::<ActiveCombat>();
+    let odds = combat.odds.as_ref().expect("odds computed");
+    // The assault table ignores the fort, so the defense stays at 2.
+    assert_eq!(odds.defender_strength, 2.0);
+    assert_eq!(odds.raw_value, 3.0);
+}
+
+#[test]
+fn loss_candidates_read_priority_and_steps_from_the_concept() {
+    use hexorder_contracts::mechanics::{CombatSide, LossAllocation};
+
+    let mut app = combat_odds_app();
+    {
+        let mut crt = app.world_mut().resource_mut::<CombatResultsTable>();
+        crt.losses.allocation = LossAllocation::ByProperty("attack".to_string());
+        crt.losses.steps_property = Some("defense".to_string());
+    }
+    app.add_systems(Update, systems::update_loss_candidates);
+    app.update();
+
+    let candidates = &app.world().resource::<ActiveCombat>().loss_candidates;
+    let summary: Vec<_> = candidates
+        .iter()
+        .map(|c| (c.side, c.priority, c.steps))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (CombatSide::Attacker, 3.0, 1),
+            (CombatSide::Attacker, 4.0, 1),
+            (CombatSide::Defender, 1.0, 2),
+        ]
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Off-map zones
+// ---------------------------------------------------------------------------
+
+/// Helper: app with one empty off-map zone and the zone observers registered.
+fn zone_app() -> (App, TypeId) {
+    use hexorder_contracts::mechanics::{OffMapZone, OffMapZoneRegistry, TurnState, TurnStructure};
+
+    let mut app = test_app();
+    setup_unit_resources(&mut app);
+    app.init_resource::<SelectedUnit>();
+    app.init_resource::<TurnState>();
+    app.init_resource::<TurnStructure>();
+    let zone = OffMapZone::new("Eliminated");
+    let zone_id = zone.id;
+    app.insert_resource(OffMapZoneRegistry {
+        zones: vec![zone],
+        eliminated_zone_id: Some(zone_id),
+    });
+    app.add_observer(systems::handle_move_to_zone);
+    app.add_observer(systems::handle_deploy_from_zone);
+    app.update();
+    (app, zone_id)
+}
+
+/// Moving a unit to a zone despawns it and keeps its properties in the zone.
+#[test]
+fn move_to_zone_stores_entity_data() {
+    use hexorder_contracts::game_system::PropertyValue;
+    use hexorder_contracts::mechanics::{MoveToZoneEvent, OffMapZoneRegistry};
+
+    let (mut app, zone_id) = zone_app();
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    let prop_id = TypeId::new();
+    let faction_id = TypeId::new();
+    let unit_id = UnitId::new();
+    let unit = app
+        .world_mut()
+        .spawn((
+            UnitInstance,
+            HexPosition::new(0, 0),
+            EntityData {
+                entity_type_id: first_id,
+                properties: HashMap::from([(prop_id, PropertyValue::Int(2))]),
+            },
+            UnitOwner {
+                faction_id: Some(faction_id),
+            },
+            unit_id,
+        ))
+        .id();
+
+    app.world_mut().commands().trigger(MoveToZoneEvent {
+        entity: unit,
+        zone_id,
+    });
+    app.update();
+
+    assert!(app.world().get_entity(unit).is_err());
+    let zones = app.world().resource::<OffMapZoneRegistry>();
+    let held = &zones.get(zone_id).expect("zone").units;
+    assert_eq!(held.len(), 1);
+    assert_eq!(held[0].owner, Some(faction_id));
+    assert_eq!(held[0].id, Some(unit_id));
+    assert_eq!(
+        held[0].properties.get(&prop_id),
+        Some(&PropertyValue::Int(2))
+    );
+}
+
+/// Deploying from a zone spawns the held unit at the target hex.
+#[test]
+fn deploy_from_zone_spawns_unit() {
+    use hexorder_contracts::mechanics::{DeployFromZoneEvent, OffMapZoneRegistry, ZoneUnit};
+
+    let (mut app, zone_id) = zone_app();
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    app.world_mut().resource_mut::<OffMapZoneRegistry>().store(
+        zone_id,
+        ZoneUnit {
+            entity_type_id: first_id,
+            properties: HashMap::new(),
+            owner: None,
+            id: None,
+            state: None,
+        },
+    );
+
+    app.world_mut().commands().trigger(DeployFromZoneEvent {
+        zone_id,
+        index: 0,
+        position: HexPosition::new(1, 0),
+    });
+    app.update();
+
+    let mut query = app
+        .world_mut()
+        .query_filtered::<(&HexPosition, &EntityData), With<UnitInstance>>();
+    let units: Vec<_> = query.iter(app.world()).collect();
+    assert_eq!(units.len(), 1);
+    assert_eq!(*units[0].0, HexPosition::new(1, 0));
+    assert_eq!(units[0].1.entity_type_id, first_id);
+    let zones = app.world().resource::<OffMapZoneRegistry>();
+    assert!(zones.get(zone_id).is_some_and(|z| z.units.is_empty()));
+}
+
+/// A unit deployed from a zone keeps the id it had on the board.
+#[test]
+fn deploy_from_zone_restores_unit_id() {
+    use hexorder_contracts::mechanics::{DeployFromZoneEvent, OffMapZoneRegistry, ZoneUnit};
+
+    let (mut app, zone_id) = zone_app();
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    let unit_id = UnitId::new();
+    app.world_mut().resource_mut::<OffMapZoneRegistry>().store(
+        zone_id,
+        ZoneUnit {
+            entity_type_id: first_id,
+            properties: HashMap::new(),
+            owner: None,
+            id: Some(unit_id),
+            state: None,
+        },
+    );
+
+    app.world_mut().commands().trigger(DeployFromZoneEvent {
+        zone_id,
+        index: 0,
+        position: HexPosition::new(1, 0),
+    });
+    app.update();
+
+    let mut query = app
+        .world_mut()
+        .query_filtered::<&UnitId, With<UnitInstance>>();
+    let ids: Vec<_> = query.iter(app.world()).copied().collect();
+    assert_eq!(ids, vec![unit_id]);
+}
+
+/// A unit moved to a zone keeps its state and its base property values, and
+/// is deployed back in that state.
+#[test]
+fn zone_round_trip_keeps_state_and_base_data() {
+    use hexorder_contracts::game_system::{AppliedOverride, PropertyValue};
+    use hexorder_contracts::mechanics::{DeployFromZoneEvent, MoveToZoneEvent, OffMapZoneRegistry};
+
+    let (mut app, zone_id) = zone_app();
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    let strength = TypeId::new();
+    let reduced = TypeId::new();
+    let unit = app
+        .world_mut()
+        .spawn((
+            UnitInstance,
+            HexPosition::new(0, 0),
+            EntityData {
+                entity_type_id: first_id,
+                properties: HashMap::from([(strength, PropertyValue::Int(2))]),
+            },
+            EntityState { state_id: reduced },
+            StateOverrides {
+                state_id: Some(reduced),
+                applied: vec![AppliedOverride {
+                    property_id: strength,
+                    before: PropertyValue::Int(4),
+                    after: PropertyValue::Int(2),
+                }],
+            },
+        ))
+        .id();
+
+    app.world_mut().commands().trigger(MoveToZoneEvent {
+        entity: unit,
+        zone_id,
+    });
+    app.update();
+
+    let zones = app.world().resource::<OffMapZoneRegistry>();
+    let held = &zones.get(zone_id).expect("zone").units[0];
+    assert_eq!(held.state, Some(reduced));
+    assert_eq!(held.properties[&strength], PropertyValue::Int(4));
+
+    app.world_mut().commands().trigger(DeployFromZoneEvent {
+        zone_id,
+        index: 0,
+        position: HexPosition::new(1, 0),
+    });
+    app.update();
+
+    let mut query = app
+        .world_mut()
+        .query_filtered::<&EntityState, With<UnitInstance>>();
+    let states: Vec<_> = query.iter(app.world()).copied().collect();
+    assert_eq!(states, vec![EntityState { state_id: reduced }]);
+}
+
+/// `UnitIndex` follows units as they are spawned and despawned.
+#[test]
+fn unit_index_tracks_spawn_and_despawn() {
+    let mut app = test_app();
+    app.init_resource::<UnitIndex>();
+    app.add_systems(Update, systems::sync_unit_index);
+
+    let unit_id = UnitId::new();
+    let entity = app.world_mut().spawn((UnitInstance, unit_id)).id();
+    app.update();
+    assert_eq!(
+        app.world().resource::<UnitIndex>().get(unit_id),
+        Some(entity)
+    );
+
+    app.world_mut().despawn(entity);
+    app.update();
+    assert!(app.world().resource::<UnitIndex>().entities.is_empty());
+}
+
+/// Deploying outside the grid leaves the unit in its zone.
+#[test]
+fn deploy_from_zone_out_of_bounds_is_noop() {
+    use hexorder_contracts::mechanics::{DeployFromZoneEvent, OffMapZoneRegistry, ZoneUnit};
+
+    let (mut app, zone_id) = zone_app();
+    let first_id = app.world().resource::<EntityTypeRegistry>().types[0].id;
+    app.world_mut().resource_mut::<OffMapZoneRegistry>().store(
+        zone_id,
+        ZoneUnit {
+            entity_type_id: first_id,
+            properties: HashMap::new(),
+            owner: None,
+            id: None,
+            state: None,
+        },
+    );
+
+    app.world_mut().commands().trigger(DeployFromZoneEvent {
+        zone_id,
+        index: 0,
+        position: HexPosition::new(10, 10),
+    });
+    app.update();
+
+    let zones = app.world().resource::<OffMapZoneRegistry>();
+    assert_eq!(zones.get(zone_id).map(|z| z.units.len()), Some(1));
+}
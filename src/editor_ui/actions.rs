@@ -3,21 +3,24 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
 
+use hexorder_contracts::editor_ui::ExportCsvEvent;
 use hexorder_contracts::game_system::{
     ActiveBoardType, ActiveTokenType, EntityData, EntityRole, EntityType, EntityTypeRegistry,
     EnumDefinition, EnumRegistry, PropertyDefinition, PropertyType, PropertyValue, SelectedUnit,
-    StructDefinition, StructRegistry, TypeId, UnitInstance,
+    SetEntityStateEvent, StructDefinition, StructRegistry, TypeId, UnitInstance,
 };
+use hexorder_contracts::hex_grid::TraceReachabilityEvent;
 use hexorder_contracts::map_gen::GenerateMap;
 use hexorder_contracts::mechanic_reference::{MechanicCatalog, ScaffoldAction};
 use hexorder_contracts::mechanics::{
     Accumulator, AccumulatorRegistry, CombatModifierDefinition, CombatModifierRegistry,
-    CombatOutcome, CombatResultsTable, ModifierSource, Phase, PhaseType, SpawnEntry, SpawnSchedule,
-    TurnStructure, VictoryCondition, VictoryConditionRegistry,
+    CombatOutcome, CombatResultsTable, CombatTableRegistry, ModifierSource, Phase, PhaseType,
+    SpawnEntry, SpawnSchedule, TurnStructure, VictoryCondition, VictoryConditionRegistry,
 };
 use hexorder_contracts::ontology::{
     CompareOp, ConceptBinding, ConceptRegistry, ConceptRole, Constraint, ConstraintExpr,
-    ConstraintRegistry, ModifyOperation, Relation, RelationEffect, RelationRegistry,
+    ConstraintRegistry, ModifyOperation, ProximityFaction, ProximityFilter, ProximityMeasure,
+    Relation, RelationEffect, RelationRegistry,
 };
 use hexorder_contracts::simulation::{ColumnType, TableColumn, TableRow};
 
@@ -44,6 +47,7 @@ pub(super) fn apply_actions(
     constraint_registry: &mut ConstraintRegistry,
     turn_structure: &mut TurnStructure,
     combat_results_table: &mut CombatResultsTable,
+    combat_tables: &mut CombatTableRegistry,
     combat_modifiers: &mut CombatModifierRegistry,
     mechanic_catalog: &MechanicCatalog,
     spawn_schedule: &mut SpawnSchedule,
@@ -51,6 +55,9 @@ pub(super) fn apply_actions(
     victory_conditions: &mut VictoryConditionRegistry,
 ) {
     for action in actions {
+        // CRT edits go to the table the Mechanics tab is editing.
+        let combat_results_table =
+            combat_tables.table_or_mut(editor_state.edited_combat_table, combat_results_table);
         match action {
             EditorAction::CreateEntityType { name, role, color } => {
                 registry.types.push(EntityType {
@@ -202,6 +209,12 @@ pub(super) fn apply_actions(
                     selected_unit.entity = None;
                 }
             }
+            EditorAction::SetEntityState { entity, state_id } => {
+                commands.trigger(SetEntityStateEvent { entity, state_id });
+            }
+            EditorAction::TraceReachability { rule_id } => {
+                commands.trigger(TraceReachabilityEvent { rule_id });
+            }
             EditorAction::CreateConcept { name, description } => {
                 concept_registry
                     .concepts
@@ -309,11 +322,21 @@ pub(super) fn apply_actions(
                     relation_id: None,
                     expression,
                     auto_generated: false,
+                    gates: Vec::new(),
                 });
             }
             EditorAction::DeleteConstraint { id } => {
                 constraint_registry.constraints.retain(|c| c.id != id);
             }
+            EditorAction::SetConstraintGates { id, gates } => {
+                if let Some(constraint) = constraint_registry
+                    .constraints
+                    .iter_mut()
+                    .find(|c| c.id == id)
+                {
+                    constraint.gates = gates;
+                }
+            }
             EditorAction::CreateEnum { name, options } => {
                 enum_registry.insert(EnumDefinition {
                     id: TypeId::new(),
@@ -460,6 +483,18 @@ pub(super) fn apply_actions(
                     outcome.label = label;
                 }
             }
+            EditorAction::SetCrtDice { dice } => {
+                combat_results_table.dice = dice;
+            }
+            EditorAction::ExportCsv {
+                file_name,
+                contents,
+            } => {
+                commands.trigger(ExportCsvEvent {
+                    file_name,
+                    contents,
+                });
+            }
             EditorAction::AddCombatModifier {
                 name,
                 source,
@@ -474,11 +509,17 @@ pub(super) fn apply_actions(
                     priority,
                     cap: None,
                     terrain_type_filter: None,
+                    condition: None,
                 });
             }
             EditorAction::RemoveCombatModifier { id } => {
                 combat_modifiers.modifiers.retain(|m| m.id != id);
             }
+            EditorAction::SetCombatModifierCondition { id, condition } => {
+                if let Some(modifier) = combat_modifiers.modifiers.iter_mut().find(|m| m.id == id) {
+                    modifier.condition = condition;
+                }
+            }
             // -- Spawn Schedule --
             EditorAction::AddSpawnEntry {
                 entity_type_id,
@@ -499,13 +540,18 @@ pub(super) fn apply_actions(
                 }
             }
             // -- Accumulators --
-            EditorAction::AddAccumulator { id, faction } => {
+            EditorAction::AddAccumulator {
+                id,
+                faction,
+                faction_id,
+            } => {
                 accumulator_registry.accumulators.push(Accumulator {
                     id,
                     faction,
                     triggers: Vec::new(),
                     value: 0,
                     history: Vec::new(),
+                    faction_id,
                 });
             }
             EditorAction::RemoveAccumulator { index } => {
@@ -567,9 +613,6 @@ pub(super) fn apply_actions(
             }
         }
     }
-
-    // Suppress unused warning.
-    let _ = editor_state;
 }
 
 /// Converts a `ScaffoldRecipe` into concrete registry mutations.
@@ -702,6 +745,7 @@ pub(super) fn apply_scaffold_recipe(
                     priority: *priority,
                     cap: None,
                     terrain_type_filter: None,
+                    condition: None,
                 });
             }
         }
@@ -780,6 +824,7 @@ pub(super) fn format_property_type(pt: &PropertyType) -> &'static str {
         PropertyType::Color => "Color",
         PropertyType::Enum(_) => "Enum",
         PropertyType::EntityRef(_) => "EntityRef",
+        PropertyType::UnitRef => "UnitRef",
         PropertyType::List(_) => "List",
         PropertyType::Map(_, _) => "Map",
         PropertyType::Struct(_) => "Struct",
@@ -801,6 +846,7 @@ pub(super) fn index_to_property_type(index: usize) -> PropertyType {
         9 => PropertyType::Struct(TypeId::new()),
         10 => PropertyType::IntRange { min: 0, max: 100 },
         11 => PropertyType::FloatRange { min: 0.0, max: 1.0 },
+        12 => PropertyType::UnitRef,
         _ => PropertyType::Bool,
     }
 }
@@ -887,6 +933,31 @@ pub(super) fn format_constraint_expr(expr: &ConstraintExpr) -> String {
         }
         ConstraintExpr::IsType { .. } => "is type".to_string(),
         ConstraintExpr::IsNotType { .. } => "is not type".to_string(),
+        ConstraintExpr::InState { .. } => "in state".to_string(),
+        ConstraintExpr::InReach { .. } => "in reach".to_string(),
+        ConstraintExpr::Proximity {
+            filter,
+            measure,
+            max,
+            line_of_sight,
+            ..
+        } => {
+            let measure = match measure {
+                ProximityMeasure::Distance => "distance",
+                ProximityMeasure::PathCost => "path cost",
+            };
+            let side = match filter.faction {
+                ProximityFaction::Any => "",
+                ProximityFaction::Friendly => "friendly ",
+                ProximityFaction::Enemy => "enemy ",
+            };
+            let sight = if line_of_sight.is_some() {
+                " in sight"
+            } else {
+                ""
+            };
+            format!("{measure} to nearest {side}unit{sight} <= {max}")
+        }
         ConstraintExpr::PathBudget {
             cost_property,
             budget_property,
@@ -943,6 +1014,7 @@ pub(super) fn index_to_compare_op(index: usize) -> CompareOp {
 pub(super) fn build_constraint_expression(
     editor_state: &EditorState,
     roles: &[ConceptRole],
+    entity_types: &EntityTypeRegistry,
 ) -> ConstraintExpr {
     match editor_state.new_constraint_expr_type_index {
         0 => {
@@ -981,6 +1053,59 @@ pub(super) fn build_constraint_expression(
                 budget_role_id,
             }
         }
+        4 => {
+            // Proximity
+            let role_id = roles
+                .get(editor_state.new_constraint_role_index)
+                .map_or_else(TypeId::new, |r| r.id);
+            let entity_type_id = editor_state
+                .new_constraint_proximity_type_index
+                .checked_sub(1)
+                .and_then(|i| {
+                    entity_types
+                        .types_by_role(EntityRole::Token)
+                        .get(i)
+                        .copied()
+                })
+                .map(|t| t.id);
+            let faction = match editor_state.new_constraint_proximity_faction_index {
+                1 => ProximityFaction::Friendly,
+                2 => ProximityFaction::Enemy,
+                _ => ProximityFaction::Any,
+            };
+            let measure = if editor_state.new_constraint_proximity_measure_index == 1 {
+                ProximityMeasure::PathCost
+            } else {
+                ProximityMeasure::Distance
+            };
+            let same_property = editor_state.new_constraint_property.trim();
+            let line_of_sight = editor_state
+                .new_constraint_proximity_sight_index
+                .checked_sub(1)
+                .map(|i| {
+                    entity_types
+                        .types_by_role(EntityRole::BoardPosition)
+                        .get(i)
+                        .map(|t| t.id)
+                        .into_iter()
+                        .collect()
+                });
+            ConstraintExpr::Proximity {
+                role_id,
+                filter: ProximityFilter {
+                    entity_type_id,
+                    faction,
+                    same_property: (!same_property.is_empty()).then(|| same_property.to_string()),
+                },
+                measure,
+                max: editor_state
+                    .new_constraint_value_str
+                    .trim()
+                    .parse()
+                    .unwrap_or(0),
+                line_of_sight,
+            }
+        }
         _ => {
             // TODO(#17): CrossCompare and IsType constraint expressions
             ConstraintExpr::All(Vec::new())
@@ -3,6 +3,8 @@
 //! Contract types (`EditorTool`) live in `hexorder_contracts::editor_ui`.
 //! This module holds types that are internal to the `editor_ui` plugin.
 
+use std::collections::HashMap;
+
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_egui::egui;
@@ -73,8 +75,8 @@ use hexorder_contracts::mechanics::{
     TurnStructure,
 };
 use hexorder_contracts::ontology::{
-    ConceptRegistry, ConstraintExpr, ConstraintRegistry, RelationEffect, RelationRegistry,
-    RelationTrigger,
+    ConceptRegistry, ConstraintExpr, ConstraintRegistry, GatedAction, RelationEffect,
+    RelationRegistry, RelationTrigger,
 };
 use hexorder_contracts::persistence::Workspace;
 use hexorder_contracts::simulation::ColumnType;
@@ -102,6 +104,15 @@ pub(crate) enum EditorAction {
         prop_id: TypeId,
     },
     DeleteSelectedUnit,
+    /// Move a tile or unit into a state of its type's state machine.
+    SetEntityState {
+        entity: Entity,
+        state_id: TypeId,
+    },
+    /// Trace a reachability rule now (`None` traces every rule).
+    TraceReachability {
+        rule_id: Option<TypeId>,
+    },
     CreateConcept {
         name: String,
         description: String,
@@ -148,6 +159,10 @@ pub(crate) enum EditorAction {
     DeleteConstraint {
         id: TypeId,
     },
+    SetConstraintGates {
+        id: TypeId,
+        gates: Vec<GatedAction>,
+    },
     CreateEnum {
         name: String,
         options: Vec<String>,
@@ -216,6 +231,14 @@ pub(crate) enum EditorAction {
         col: usize,
         label: String,
     },
+    SetCrtDice {
+        dice: hexorder_contracts::simulation::DicePool,
+    },
+    /// Save CSV text through the export plugin's save dialog.
+    ExportCsv {
+        file_name: String,
+        contents: String,
+    },
     AddCombatModifier {
         name: String,
         source: ModifierSource,
@@ -225,6 +248,10 @@ pub(crate) enum EditorAction {
     RemoveCombatModifier {
         id: TypeId,
     },
+    SetCombatModifierCondition {
+        id: TypeId,
+        condition: Option<ConstraintExpr>,
+    },
     // -- Spawn Schedule --
     AddSpawnEntry {
         entity_type_id: TypeId,
@@ -239,6 +266,7 @@ pub(crate) enum EditorAction {
     AddAccumulator {
         id: String,
         faction: Option<String>,
+        faction_id: Option<TypeId>,
     },
     RemoveAccumulator {
         index: usize,
@@ -280,6 +308,15 @@ pub enum OntologyTab {
     Constraints,
     Validation,
     Mechanics,
+    Analysis,
+}
+
+/// A roll made in a play panel, with the table lookup it decided.
+#[derive(Debug, Clone)]
+pub struct PlayRoll {
+    pub context: String,
+    pub roll: hexorder_contracts::simulation::DiceRoll,
+    pub table: Option<(TypeId, hexorder_contracts::simulation::TableResolution)>,
 }
 
 /// Persistent UI state for the editor panels.
@@ -305,7 +342,7 @@ pub struct EditorState {
     /// Name for a new property being added to an entity type.
     pub new_prop_name: String,
     /// Selected property type index (0=Bool, 1=Int, 2=Float, 3=String, 4=Color, 5=Enum,
-    /// 6=EntityRef, 7=List, 8=Map, 9=Struct, 10=IntRange, 11=FloatRange).
+    /// 6=EntityRef, 7=List, 8=Map, 9=Struct, 10=IntRange, 11=FloatRange, 12=UnitRef).
     pub new_prop_type_index: usize,
     /// Comma-separated enum options when adding an Enum property.
     pub new_enum_options: String,
@@ -395,6 +432,10 @@ pub struct EditorState {
     /// Mutable edit buffer for CRT outcome labels, indexed \[row\]\[col\].
     /// Re-synced from `CombatResultsTable` when dimensions change.
     pub crt_outcome_labels: Vec<Vec<String>>,
+    /// The conditional combat table the Mechanics tab edits; `None` edits
+    /// the default table.
+    pub edited_combat_table: Option<TypeId>,
+    pub new_combat_table_name: String,
 
     // -- Table editor live preview --
     /// Test input A for table resolution preview.
@@ -408,13 +449,22 @@ pub struct EditorState {
     pub new_constraint_name: String,
     pub new_constraint_description: String,
     pub new_constraint_concept_index: usize,
-    /// 0=PropertyCompare, 1=CrossCompare, 2=IsType, 3=PathBudget.
+    /// 0=PropertyCompare, 1=CrossCompare, 2=IsType, 3=PathBudget, 4=Proximity.
     pub new_constraint_expr_type_index: usize,
     pub new_constraint_role_index: usize,
     pub new_constraint_property: String,
     /// 0=Eq, 1=Ne, 2=Lt, 3=Le, 4=Gt, 5=Ge.
     pub new_constraint_op_index: usize,
     pub new_constraint_value_str: String,
+    /// Proximity target: 0=any unit, otherwise the token type at index - 1.
+    pub new_constraint_proximity_type_index: usize,
+    /// 0=Any, 1=Friendly, 2=Enemy.
+    pub new_constraint_proximity_faction_index: usize,
+    /// 0=Distance, 1=PathCost.
+    pub new_constraint_proximity_measure_index: usize,
+    /// Proximity line of sight: 0=not required, otherwise the board type at
+    /// index - 1 blocks sight.
+    pub new_constraint_proximity_sight_index: usize,
 
     // -- Combat panel state (Play mode) --
     pub combat_attacker_strength: f64,
@@ -439,6 +489,10 @@ pub struct EditorState {
     pub new_influence_cost: i32,
     /// Selected entity type index for stacking exempt picker.
     pub new_stacking_exempt_idx: Option<usize>,
+    /// Selected terrain type index for a new per-terrain stacking limit.
+    pub new_stacking_terrain_idx: Option<usize>,
+    /// Selected faction index for a new per-faction stacking limit.
+    pub new_stacking_faction_idx: Option<usize>,
 
     // -- Dice panel state --
     /// Number of dice in the pool (1-255).
@@ -458,6 +512,18 @@ pub struct EditorState {
     /// Last chain resolution context (None if not yet resolved).
     pub last_chain_result: Option<hexorder_contracts::simulation::ChainContext>,
 
+    /// Rolls made in the play panels since the last frame; the play panel
+    /// system fires `DieRolled` and `TableResolved` for them and clears it.
+    pub play_rolls: Vec<PlayRoll>,
+
+    // -- Combat panel state --
+    /// Set when a die roll resolves a combat outcome; the play panel system
+    /// fires `CombatResolvedEvent` and clears it.
+    pub combat_resolved: bool,
+    /// Retreats and advances chosen in the combat panel; the play panel
+    /// system fires a `CombatMoveEvent` for each and clears it.
+    pub combat_moves: Vec<hexorder_contracts::mechanics::CombatMoveEvent>,
+
     // -- About panel --
     /// Whether the About panel is visible.
     pub about_panel_visible: bool,
@@ -478,12 +544,55 @@ pub struct EditorState {
     pub new_accumulator_id: String,
     /// Faction for new accumulator.
     pub new_accumulator_faction: String,
+    /// Faction registry entry for new accumulator.
+    pub new_accumulator_faction_id: Option<TypeId>,
     /// ID for new victory condition accumulator.
     pub new_victory_accumulator_id: String,
     /// Threshold for new victory condition.
     pub new_victory_threshold: i32,
     /// Comparison for new victory condition.
     pub new_victory_comparison: hexorder_contracts::mechanics::ComparisonOp,
+    // -- Off-map zone editor --
+    /// Name for a new off-map zone.
+    pub new_zone_name: String,
+    /// Selected entity type index for staging a unit into a zone.
+    pub new_zone_unit_type_idx: Option<usize>,
+    // -- Faction editor --
+    /// Name for a new faction.
+    pub new_faction_name: String,
+    // -- State machine editor --
+    /// Index into the entity types for a new state machine.
+    pub new_state_machine_type_idx: Option<usize>,
+    /// Name for a new state, shared by every machine's add form.
+    pub new_state_name: String,
+    // -- Reachability editor --
+    /// Name for a new reachability rule.
+    pub new_reachability_name: String,
+    // -- Overrun editor --
+    /// Name for a new overrun rule.
+    pub new_overrun_name: String,
+    // -- Validation --
+    /// Dock tab a rule analysis "Go to" link asked to bring forward;
+    /// `editor_dock_system` focuses it after drawing the dock.
+    pub focus_dock_tab: Option<DockTab>,
+    // -- Odds analysis --
+    /// Attacker strength of the analyzed attack.
+    pub analysis_attacker_strength: f64,
+    /// Defender strength of the analyzed attack.
+    pub analysis_defender_strength: f64,
+    /// Combat modifiers applied to the attack, by id.
+    pub analysis_modifiers: Vec<TypeId>,
+    /// Area markers whose column shifts apply to the attack, by marker type
+    /// and center.
+    pub analysis_area_markers: Vec<(String, hexorder_contracts::hex_grid::HexPosition)>,
+    /// Runs per simulated resolution.
+    pub analysis_runs: u32,
+    /// Seed of the simulation's `SimulationRng`.
+    pub analysis_seed: u64,
+    /// Context values the simulated resolutions read, by key.
+    pub analysis_inputs: HashMap<String, f64>,
+    /// Outcome odds of the last simulation, one row per rule.
+    pub analysis_results: Vec<hexorder_contracts::mechanics::OddsRow>,
 }
 
 impl Default for EditorState {
@@ -546,6 +655,8 @@ impl Default for EditorState {
             new_modifier_shift: 0,
             new_modifier_priority: 0,
             crt_outcome_labels: Vec::new(),
+            edited_combat_table: None,
+            new_combat_table_name: String::new(),
             table_test_input_a: 0.0,
             table_test_input_b: 0.0,
             table_test_die_roll: 1,
@@ -557,6 +668,10 @@ impl Default for EditorState {
             new_constraint_property: String::new(),
             new_constraint_op_index: 0,
             new_constraint_value_str: String::new(),
+            new_constraint_proximity_type_index: 0,
+            new_constraint_proximity_faction_index: 0,
+            new_constraint_proximity_measure_index: 0,
+            new_constraint_proximity_sight_index: 0,
             combat_attacker_strength: 0.0,
             combat_defender_strength: 0.0,
             font_size_base: 15.0,
@@ -567,13 +682,18 @@ impl Default for EditorState {
             new_influence_range: 1,
             new_influence_cost: 1,
             new_stacking_exempt_idx: None,
+            new_stacking_terrain_idx: None,
+            new_stacking_faction_idx: None,
             dice_count: 1,
             dice_sides: 6,
             dice_modifier: 0,
             last_dice_roll: None,
             dice_seed_input: String::new(),
             chain_panel_expanded: false,
+            combat_resolved: false,
+            combat_moves: Vec::new(),
             last_chain_result: None,
+            play_rolls: Vec::new(),
             about_panel_visible: false,
             new_spawn_type_idx: None,
             new_spawn_turn: 1,
@@ -582,9 +702,26 @@ impl Default for EditorState {
             new_spawn_zone: String::new(),
             new_accumulator_id: String::new(),
             new_accumulator_faction: String::new(),
+            new_accumulator_faction_id: None,
             new_victory_accumulator_id: String::new(),
             new_victory_threshold: 10,
             new_victory_comparison: hexorder_contracts::mechanics::ComparisonOp::GreaterOrEqual,
+            new_zone_name: String::new(),
+            new_zone_unit_type_idx: None,
+            new_faction_name: String::new(),
+            new_state_machine_type_idx: None,
+            new_state_name: String::new(),
+            new_reachability_name: String::new(),
+            new_overrun_name: String::new(),
+            focus_dock_tab: None,
+            analysis_attacker_strength: 2.0,
+            analysis_defender_strength: 1.0,
+            analysis_modifiers: Vec::new(),
+            analysis_area_markers: Vec::new(),
+            analysis_runs: 1000,
+            analysis_seed: 1,
+            analysis_inputs: HashMap::new(),
+            analysis_results: Vec::new(),
         }
     }
 }
@@ -606,6 +743,14 @@ pub(super) struct ProjectParams<'w> {
     pub(super) undo_stack: Res<'w, hexorder_contracts::undo_redo::UndoStack>,
 }
 
+/// Bundled system parameter for schema validation and rule analysis results.
+/// Reduces the system parameter count in `editor_dock_system`.
+#[derive(SystemParam)]
+pub(super) struct ValidationParams<'w> {
+    pub(super) schema: Res<'w, hexorder_contracts::validation::SchemaValidation>,
+    pub(super) analysis: Res<'w, hexorder_contracts::validation::RuleAnalysis>,
+}
+
 /// Bundled system parameter for active selection and tool state.
 /// Reduces the system parameter count in `editor_dock_system`.
 #[derive(SystemParam)]
@@ -614,6 +759,8 @@ pub(super) struct SelectionParams<'w> {
     pub(super) active_board: ResMut<'w, ActiveBoardType>,
     pub(super) active_token: ResMut<'w, ActiveTokenType>,
     pub(super) active_edge: ResMut<'w, hexorder_contracts::editor_ui::ActiveEdgeType>,
+    pub(super) active_vertex: ResMut<'w, hexorder_contracts::editor_ui::ActiveVertexType>,
+    pub(super) active_faction: ResMut<'w, hexorder_contracts::game_system::ActiveFaction>,
     pub(super) selected_unit: ResMut<'w, SelectedUnit>,
     pub(super) multi: Res<'w, hexorder_contracts::editor_ui::Selection>,
     pub(super) selected_hex: Res<'w, SelectedHex>,
@@ -633,16 +780,76 @@ pub(super) struct TypeRegistryParams<'w> {
 #[derive(SystemParam)]
 pub(super) struct MechanicsParams<'w> {
     pub(super) turn_structure: ResMut<'w, TurnStructure>,
-    pub(super) combat_results_table: ResMut<'w, CombatResultsTable>,
-    pub(super) combat_modifiers: ResMut<'w, CombatModifierRegistry>,
+    pub(super) combat: CombatParams<'w>,
     pub(super) mechanic_catalog: Res<'w, hexorder_contracts::mechanic_reference::MechanicCatalog>,
     pub(super) influence_rules: ResMut<'w, hexorder_contracts::hex_grid::InfluenceRuleRegistry>,
     pub(super) stacking_rule: ResMut<'w, hexorder_contracts::hex_grid::StackingRule>,
     pub(super) movement_cost_matrix: ResMut<'w, hexorder_contracts::hex_grid::MovementCostMatrix>,
     pub(super) spawn_schedule: ResMut<'w, SpawnSchedule>,
+    pub(super) off_map_zones: ResMut<'w, hexorder_contracts::mechanics::OffMapZoneRegistry>,
+    pub(super) grid_config: Option<ResMut<'w, hexorder_contracts::hex_grid::HexGridConfig>>,
     pub(super) accumulator_registry: ResMut<'w, hexorder_contracts::mechanics::AccumulatorRegistry>,
     pub(super) victory_conditions:
         ResMut<'w, hexorder_contracts::mechanics::VictoryConditionRegistry>,
+    pub(super) factions: ResMut<'w, hexorder_contracts::game_system::FactionRegistry>,
+    pub(super) state_machines: ResMut<'w, hexorder_contracts::game_system::StateMachineRegistry>,
+    pub(super) movement_rules: MovementRuleParams<'w>,
+    pub(super) analysis: AnalysisParams<'w>,
+}
+
+/// Bundled system parameter for the combat results tables and modifiers.
+/// Keeps `MechanicsParams` within the system parameter limit.
+#[derive(SystemParam)]
+pub(super) struct CombatParams<'w> {
+    pub(super) combat_results_table: ResMut<'w, CombatResultsTable>,
+    pub(super) combat_tables: ResMut<'w, hexorder_contracts::mechanics::CombatTableRegistry>,
+    pub(super) combat_modifiers: ResMut<'w, CombatModifierRegistry>,
+}
+
+/// Bundled system parameter for reachability and overrun rules.
+/// Keeps `MechanicsParams` within the system parameter limit.
+#[derive(SystemParam)]
+pub(super) struct MovementRuleParams<'w> {
+    pub(super) reachability_rules:
+        ResMut<'w, hexorder_contracts::hex_grid::ReachabilityRuleRegistry>,
+    pub(super) reachability_overlay: ResMut<'w, hexorder_contracts::hex_grid::ReachabilityOverlay>,
+    pub(super) overruns: ResMut<'w, hexorder_contracts::mechanics::OverrunRegistry>,
+}
+
+/// Bundled system parameter for the resources only the odds analysis reads.
+/// Keeps `MechanicsParams` within the system parameter limit.
+#[derive(SystemParam)]
+pub(super) struct AnalysisParams<'w> {
+    pub(super) area_markers: Res<'w, hexorder_contracts::mechanics::AreaMarkerRegistry>,
+    pub(super) movement_interrupts:
+        Res<'w, hexorder_contracts::mechanics::MovementInterruptRegistry>,
+}
+
+/// Bundled system parameter for play-mode board state (zones, area markers,
+/// movement interrupts, overruns and the play log).
+/// Reduces the system parameter count in `play_panel_system`.
+#[derive(SystemParam)]
+pub(crate) struct PlayBoardParams<'w> {
+    pub(crate) area_markers: ResMut<'w, hexorder_contracts::mechanics::AreaMarkerRegistry>,
+    pub(crate) off_map_zones: Res<'w, hexorder_contracts::mechanics::OffMapZoneRegistry>,
+    pub(crate) selected_hex: Res<'w, SelectedHex>,
+    pub(crate) active_interrupt: Res<'w, hexorder_contracts::mechanics::ActiveInterrupt>,
+    pub(crate) movement_interrupts:
+        Res<'w, hexorder_contracts::mechanics::MovementInterruptRegistry>,
+    pub(crate) overrun_targets: Res<'w, hexorder_contracts::mechanics::OverrunTargets>,
+    pub(crate) overruns: Res<'w, hexorder_contracts::mechanics::OverrunRegistry>,
+    pub(crate) play_log: Res<'w, hexorder_contracts::mechanics::PlayLog>,
+    pub(crate) combat_tables: Res<'w, hexorder_contracts::mechanics::CombatTableRegistry>,
+}
+
+/// Bundled system parameter for the play session's dice: the RNG, the
+/// session seed and the roll history.
+/// Reduces the system parameter count in `play_panel_system`.
+#[derive(SystemParam)]
+pub(crate) struct PlayDiceParams<'w> {
+    pub(crate) sim_rng: ResMut<'w, hexorder_contracts::simulation::SimulationRng>,
+    pub(crate) session_seed: ResMut<'w, hexorder_contracts::simulation::SessionSeed>,
+    pub(crate) roll_history: Res<'w, hexorder_contracts::simulation::RollHistory>,
 }
 
 /// Bundled system parameter for ontology-related resources.
@@ -10,7 +10,7 @@ use bevy_egui::{EguiPlugin, EguiPrimaryContextPass};
 use hexorder_contracts::editor_ui::{
     EditorTool, Selection, ToastEvent, ViewportMargins, ViewportRect,
 };
-use hexorder_contracts::game_system::{EntityData, SelectedUnit, UnitInstance};
+use hexorder_contracts::game_system::{EntityData, SelectedUnit, UnitId, UnitInstance, UnitOwner};
 use hexorder_contracts::hex_grid::{HexPosition, HexTile};
 use hexorder_contracts::mechanics::{ActiveCombat, TurnState};
 use hexorder_contracts::ontology::{ConceptRegistry, ConstraintRegistry, RelationRegistry};
@@ -25,6 +25,7 @@ use hexorder_contracts::validation::SchemaValidation;
 
 mod actions;
 mod components;
+mod render_analysis;
 mod render_design;
 mod render_ontology;
 mod render_panels;
@@ -62,6 +63,8 @@ impl Plugin for EditorUiPlugin {
         app.insert_resource(EditorTool::default());
         app.init_resource::<hexorder_contracts::editor_ui::SelectedEdge>();
         app.init_resource::<hexorder_contracts::editor_ui::ActiveEdgeType>();
+        app.init_resource::<hexorder_contracts::editor_ui::SelectedVertex>();
+        app.init_resource::<hexorder_contracts::editor_ui::ActiveVertexType>();
         app.init_resource::<ViewportMargins>();
         app.insert_resource(components::EditorState::default());
         app.init_resource::<Selection>();
@@ -160,6 +163,17 @@ impl Plugin for EditorUiPlugin {
             EguiPrimaryContextPass,
             systems::render_grid_overlay.run_if(in_state(AppScreen::Editor)),
         );
+        // Route cost breakdown follows the pointer over reachable hexes.
+        app.add_systems(
+            EguiPrimaryContextPass,
+            systems::render_move_tooltip.run_if(in_state(AppScreen::Editor)),
+        );
+        // State badges label tiles and units in the editor and during play.
+        app.add_systems(
+            EguiPrimaryContextPass,
+            systems::render_state_badges
+                .run_if(in_state(AppScreen::Editor).or(in_state(AppScreen::Play))),
+        );
         // Toast renders on all screens (Editor and Play).
         app.add_systems(EguiPrimaryContextPass, systems::render_toast);
     }
@@ -183,6 +197,8 @@ fn handle_editor_ui_command(
         (
             &HexPosition,
             &EntityData,
+            &UnitOwner,
+            &UnitId,
             &Mesh3d,
             &MeshMaterial3d<StandardMaterial>,
             &Transform,
@@ -196,6 +212,7 @@ fn handle_editor_ui_command(
         "tool.paint" => *tool = EditorTool::Paint,
         "tool.place" => *tool = EditorTool::Place,
         "tool.edge_paint" => *tool = EditorTool::EdgePaint,
+        "tool.vertex_paint" => *tool = EditorTool::VertexPaint,
         "tool.combat_select" => *tool = EditorTool::CombatSelect,
         "mode.editor" => next_state.set(AppScreen::Editor),
         "mode.close" => commands.trigger(CloseProjectEvent),
@@ -276,6 +293,8 @@ fn delete_unit(
         (
             &HexPosition,
             &EntityData,
+            &UnitOwner,
+            &UnitId,
             &Mesh3d,
             &MeshMaterial3d<StandardMaterial>,
             &Transform,
@@ -285,11 +304,13 @@ fn delete_unit(
     undo_stack: &mut Option<ResMut<UndoStack>>,
     commands: &mut Commands,
 ) {
-    if let Ok((pos, data, mesh, mat, transform)) = unit_query.get(entity) {
+    if let Ok((pos, data, owner, unit_id, mesh, mat, transform)) = unit_query.get(entity) {
         let cmd = DeleteUnitCommand {
             entity: Some(entity),
             position: *pos,
             entity_data: data.clone(),
+            owner: *owner,
+            unit_id: *unit_id,
             mesh: mesh.0.clone(),
             material: mat.0.clone(),
             transform: *transform,
@@ -351,6 +372,14 @@ fn register_shortcuts(registry: &mut ShortcutRegistry) {
         category: CommandCategory::Tool,
         continuous: false,
     });
+    registry.register(CommandEntry {
+        id: CommandId("tool.vertex_paint"),
+        name: "Vertex Paint Tool".to_string(),
+        description: "Switch to vertex paint mode".to_string(),
+        bindings: vec![KeyBinding::new(KeyCode::Digit6, Modifiers::NONE)],
+        category: CommandCategory::Tool,
+        continuous: false,
+    });
     registry.register(CommandEntry {
         id: CommandId("tool.combat_select"),
         name: "Combat Select Tool".to_string(),
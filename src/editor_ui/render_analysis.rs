@@ -0,0 +1,350 @@
+//! Rules tab rendering — odds analysis of the CRT and of rule resolutions.
+
+use std::collections::HashMap;
+
+use bevy_egui::egui;
+
+use hexorder_contracts::mechanics::{
+    AreaEffect, AreaMarkerRegistry, CombatModifierRegistry, CombatResultsTable,
+    InterruptResolution, MovementInterruptRegistry, NO_RESULT_LABEL, OddsRow, OverrunRegistry,
+    crt_attack_odds, crt_odds, expected_losses, odds_csv, resolution_input_keys,
+    simulate_resolution,
+};
+use hexorder_contracts::simulation::{SimulationRng, evaluate_column_modifiers};
+
+use super::components::{BrandTheme, EditorAction, EditorState};
+
+/// Fill colors of stacked-bar segments, by result order. Results past the
+/// palette reuse it from the start.
+const SEGMENT_COLORS: [egui::Color32; 6] = [
+    BrandTheme::ACCENT_TEAL,
+    BrandTheme::ACCENT_AMBER,
+    BrandTheme::SUCCESS,
+    BrandTheme::DANGER,
+    BrandTheme::TEXT_TERTIARY,
+    BrandTheme::TEXT_PRIMARY,
+];
+
+/// Height of one stacked bar.
+const BAR_HEIGHT: f32 = 14.0;
+
+/// Renders the Analysis tab: exact CRT odds per column and for a chosen
+/// attack, and Monte Carlo odds of movement interrupt and overrun
+/// resolutions, each exportable as CSV.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_odds_analysis(
+    ui: &mut egui::Ui,
+    crt: &CombatResultsTable,
+    modifiers: &CombatModifierRegistry,
+    area_markers: &AreaMarkerRegistry,
+    interrupts: &MovementInterruptRegistry,
+    overruns: &OverrunRegistry,
+    editor_state: &mut EditorState,
+    actions: &mut Vec<EditorAction>,
+) {
+    render_crt_odds(ui, crt, modifiers, area_markers, editor_state, actions);
+    ui.add_space(12.0);
+    render_resolution_odds(ui, crt, interrupts, overruns, editor_state, actions);
+}
+
+/// Renders the exact outcome distribution of every CRT column, then of the
+/// attack set up with strengths and the checked modifiers and area markers.
+fn render_crt_odds(
+    ui: &mut egui::Ui,
+    crt: &CombatResultsTable,
+    modifiers: &CombatModifierRegistry,
+    area_markers: &AreaMarkerRegistry,
+    editor_state: &mut EditorState,
+    actions: &mut Vec<EditorAction>,
+) {
+    ui.label(
+        egui::RichText::new(format!("CRT Odds \u{2014} {} ({})", crt.name, crt.dice))
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    if crt.table.columns.is_empty() || crt.table.rows.is_empty() {
+        ui.label(
+            egui::RichText::new("No CRT defined. Set up columns and rows in the Mechanics tab.")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+        return;
+    }
+
+    let columns = crt_odds(crt);
+    render_odds_bars(ui, "crt_odds", &columns);
+    if ui.button("Export CSV").clicked() {
+        actions.push(EditorAction::ExportCsv {
+            file_name: "crt-odds".to_string(),
+            contents: odds_csv("column", &columns),
+        });
+    }
+
+    ui.add_space(8.0);
+    ui.label(
+        egui::RichText::new("Attack")
+            .strong()
+            .color(BrandTheme::TEXT_PRIMARY),
+    );
+    ui.horizontal(|ui| {
+        ui.label("Attacker:");
+        ui.add(
+            egui::DragValue::new(&mut editor_state.analysis_attacker_strength)
+                .speed(0.5)
+                .range(0.0..=999.0),
+        );
+        ui.label("Defender:");
+        ui.add(
+            egui::DragValue::new(&mut editor_state.analysis_defender_strength)
+                .speed(0.5)
+                .range(0.0..=999.0),
+        );
+    });
+
+    let mut applied_modifiers = Vec::new();
+    for (definition, modifier) in modifiers.modifiers.iter().zip(modifiers.column_modifiers()) {
+        let mut applied = editor_state.analysis_modifiers.contains(&definition.id);
+        let label = format!("{}: {:+}", definition.name, definition.column_shift);
+        if ui.checkbox(&mut applied, label).changed() {
+            if applied {
+                editor_state.analysis_modifiers.push(definition.id);
+            } else {
+                editor_state
+                    .analysis_modifiers
+                    .retain(|&id| id != definition.id);
+            }
+        }
+        if applied {
+            applied_modifiers.push(modifier);
+        }
+    }
+    let (modifier_shift, _) =
+        evaluate_column_modifiers(&applied_modifiers, crt.table.columns.len());
+
+    let mut area_shift = 0;
+    for marker in &area_markers.markers {
+        let shift: i32 = marker
+            .effects
+            .iter()
+            .map(|effect| match effect {
+                AreaEffect::ColumnShift { shift } => *shift,
+                AreaEffect::CostModifier { .. } | AreaEffect::ActionRestriction { .. } => 0,
+            })
+            .sum();
+        if shift == 0 {
+            continue;
+        }
+        let identity = (marker.marker_type.clone(), marker.center);
+        let mut applied = editor_state.analysis_area_markers.contains(&identity);
+        let label = format!(
+            "{} ({}, {}): {shift:+}",
+            marker.marker_type, marker.center.q, marker.center.r
+        );
+        if ui.checkbox(&mut applied, label).changed() {
+            if applied {
+                editor_state.analysis_area_markers.push(identity);
+            } else {
+                editor_state
+                    .analysis_area_markers
+                    .retain(|selected| *selected != identity);
+            }
+        }
+        if applied {
+            area_shift += shift;
+        }
+    }
+
+    let shift = modifier_shift + area_shift;
+    match crt_attack_odds(
+        crt,
+        editor_state.analysis_attacker_strength,
+        editor_state.analysis_defender_strength,
+        shift,
+    ) {
+        Some(attack) => {
+            ui.label(
+                egui::RichText::new(format!("Column {} after {shift:+} shift", attack.label))
+                    .small()
+                    .color(BrandTheme::TEXT_SECONDARY),
+            );
+            render_odds_bars(ui, "attack_odds", std::slice::from_ref(&attack));
+        }
+        None => {
+            ui.label(
+                egui::RichText::new("Below minimum column threshold")
+                    .small()
+                    .color(BrandTheme::DANGER),
+            );
+        }
+    }
+}
+
+/// Renders the Monte Carlo odds of every movement interrupt and overrun
+/// resolution, with the context values they read.
+fn render_resolution_odds(
+    ui: &mut egui::Ui,
+    crt: &CombatResultsTable,
+    interrupts: &MovementInterruptRegistry,
+    overruns: &OverrunRegistry,
+    editor_state: &mut EditorState,
+    actions: &mut Vec<EditorAction>,
+) {
+    ui.label(
+        egui::RichText::new("Resolution Simulation")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    let resolutions: Vec<(&str, &InterruptResolution)> = interrupts
+        .rules
+        .iter()
+        .map(|rule| (rule.name.as_str(), &rule.resolution))
+        .chain(
+            overruns
+                .rules
+                .iter()
+                .map(|rule| (rule.name.as_str(), &rule.resolution)),
+        )
+        .collect();
+    if resolutions.is_empty() {
+        ui.label(
+            egui::RichText::new("No movement interrupt or overrun rules to simulate.")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+        return;
+    }
+
+    let mut keys: Vec<String> = Vec::new();
+    for (_, resolution) in &resolutions {
+        for key in resolution_input_keys(resolution) {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    for key in keys {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(&key).small());
+            let value = editor_state.analysis_inputs.entry(key).or_insert(0.0);
+            ui.add(egui::DragValue::new(value).speed(0.5));
+        });
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Runs:");
+        ui.add(egui::DragValue::new(&mut editor_state.analysis_runs).range(1..=100_000));
+        ui.label("Seed:");
+        ui.add(egui::DragValue::new(&mut editor_state.analysis_seed));
+        if ui.button("Simulate").clicked() {
+            editor_state.analysis_results = simulate_resolutions(&resolutions, crt, editor_state);
+        }
+    });
+
+    if editor_state.analysis_results.is_empty() {
+        return;
+    }
+    ui.label(
+        egui::RichText::new(format!("{} runs each", editor_state.analysis_runs))
+            .small()
+            .color(BrandTheme::TEXT_SECONDARY),
+    );
+    render_odds_bars(ui, "resolution_odds", &editor_state.analysis_results);
+    if ui.button("Export CSV").clicked() {
+        actions.push(EditorAction::ExportCsv {
+            file_name: "resolution-odds".to_string(),
+            contents: odds_csv("rule", &editor_state.analysis_results),
+        });
+    }
+}
+
+/// Simulates each named resolution with the analysis inputs, runs and
+/// seed. Step losses read the CRT outcome with the same label.
+fn simulate_resolutions(
+    resolutions: &[(&str, &InterruptResolution)],
+    crt: &CombatResultsTable,
+    editor_state: &EditorState,
+) -> Vec<OddsRow> {
+    let values: HashMap<String, f64> = editor_state.analysis_inputs.clone();
+    let mut rng = SimulationRng::new(editor_state.analysis_seed);
+    resolutions
+        .iter()
+        .map(|(name, resolution)| {
+            let outcomes = simulate_resolution(
+                resolution,
+                &values,
+                &mut rng,
+                editor_state.analysis_runs,
+                name,
+            );
+            OddsRow {
+                label: (*name).to_string(),
+                losses: expected_losses(&outcomes, crt),
+                outcomes,
+            }
+        })
+        .collect()
+}
+
+/// Renders odds rows as stacked bars, one per row, with the expected step
+/// losses beside each and a legend of the results below.
+fn render_odds_bars(ui: &mut egui::Ui, id_salt: &str, rows: &[OddsRow]) {
+    let mut labels: Vec<&str> = Vec::new();
+    for odds in rows.iter().flat_map(|row| &row.outcomes) {
+        if !labels.contains(&odds.label.as_str()) {
+            labels.push(&odds.label);
+        }
+    }
+    let color_of = |label: &str| {
+        if label == NO_RESULT_LABEL {
+            return BrandTheme::WIDGET_INACTIVE;
+        }
+        let index = labels.iter().position(|l| *l == label).unwrap_or(0);
+        SEGMENT_COLORS[index % SEGMENT_COLORS.len()]
+    };
+
+    egui::Grid::new(id_salt)
+        .num_columns(3)
+        .spacing([6.0, 4.0])
+        .show(ui, |ui| {
+            for row in rows {
+                ui.label(egui::RichText::new(&row.label).small().strong());
+                let width = 160.0;
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::vec2(width, BAR_HEIGHT), egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+                let mut left = rect.left();
+                let mut hover = Vec::with_capacity(row.outcomes.len());
+                for odds in &row.outcomes {
+                    let segment = width * odds.probability as f32;
+                    painter.rect_filled(
+                        egui::Rect::from_min_size(
+                            egui::pos2(left, rect.top()),
+                            egui::vec2(segment, BAR_HEIGHT),
+                        ),
+                        0.0,
+                        color_of(&odds.label),
+                    );
+                    left += segment;
+                    hover.push(format!("{}: {:.1}%", odds.label, odds.probability * 100.0));
+                }
+                response.on_hover_text(hover.join("\n"));
+                ui.label(
+                    egui::RichText::new(format!(
+                        "steps A {:.2} / D {:.2}",
+                        row.losses.attacker, row.losses.defender
+                    ))
+                    .small()
+                    .color(BrandTheme::TEXT_SECONDARY),
+                );
+                ui.end_row();
+            }
+        });
+
+    ui.horizontal_wrapped(|ui| {
+        for label in &labels {
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+            ui.painter().rect_filled(rect, 2.0, color_of(label));
+            ui.label(egui::RichText::new(*label).small());
+        }
+    });
+}
@@ -200,6 +200,7 @@ pub(crate) fn render_entity_type_section(
                                     "Struct",
                                     "IntRange",
                                     "FloatRange",
+                                    "UnitRef",
                                 ];
                                 egui::ComboBox::from_id_salt(format!(
                                     "{id_prefix}_pt_{display_idx}"
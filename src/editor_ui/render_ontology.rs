@@ -4,7 +4,8 @@ use bevy_egui::egui;
 
 use hexorder_contracts::game_system::{EntityRole, EntityTypeRegistry, TypeId};
 use hexorder_contracts::ontology::{
-    ConceptRegistry, ConstraintRegistry, RelationEffect, RelationRegistry, RelationTrigger,
+    ConceptRegistry, ConstraintRegistry, GatedAction, RelationEffect, RelationRegistry,
+    RelationTrigger,
 };
 
 use super::actions::{
@@ -601,6 +602,7 @@ pub(crate) fn render_constraints_tab(
     ui: &mut egui::Ui,
     constraint_registry: &mut ConstraintRegistry,
     concept_registry: &ConceptRegistry,
+    entity_registry: &EntityTypeRegistry,
     editor_state: &mut EditorState,
     actions: &mut Vec<EditorAction>,
 ) {
@@ -628,6 +630,7 @@ pub(crate) fn render_constraints_tab(
                 c.description.clone(),
                 c.auto_generated,
                 c.expression.clone(),
+                c.gates.clone(),
             )
         })
         .collect();
@@ -639,7 +642,7 @@ pub(crate) fn render_constraints_tab(
                 .color(BrandTheme::TEXT_SECONDARY),
         );
     } else {
-        for (cst_id, cst_name, _cst_desc, auto_gen, expr) in &constraint_snapshots {
+        for (cst_id, cst_name, _cst_desc, auto_gen, expr, gates) in &constraint_snapshots {
             ui.horizontal(|ui| {
                 if *auto_gen {
                     ui.label(
@@ -658,6 +661,28 @@ pub(crate) fn render_constraints_tab(
                     .small()
                     .color(BrandTheme::TEXT_SECONDARY),
             );
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Gates:").small());
+                let mut new_gates = gates.clone();
+                for (action, label) in
+                    [(GatedAction::Move, "move"), (GatedAction::Attack, "attack")]
+                {
+                    let mut gated = gates.contains(&action);
+                    if ui.checkbox(&mut gated, label).changed() {
+                        if gated {
+                            new_gates.push(action);
+                        } else {
+                            new_gates.retain(|g| *g != action);
+                        }
+                    }
+                }
+                if new_gates != *gates {
+                    actions.push(EditorAction::SetConstraintGates {
+                        id: *cst_id,
+                        gates: new_gates,
+                    });
+                }
+            });
             ui.add_space(2.0);
         }
     }
@@ -695,11 +720,17 @@ pub(crate) fn render_constraints_tab(
             }
 
             // Expression type
-            let expr_types = ["PropertyCompare", "CrossCompare", "IsType", "PathBudget"];
+            let expr_types = [
+                "PropertyCompare",
+                "CrossCompare",
+                "IsType",
+                "PathBudget",
+                "Proximity",
+            ];
             ui.horizontal(|ui| {
                 ui.label("Expr:");
                 let idx = &mut editor_state.new_constraint_expr_type_index;
-                *idx = (*idx).min(3);
+                *idx = (*idx).min(expr_types.len() - 1);
                 egui::ComboBox::from_id_salt("cst_expr")
                     .selected_text(expr_types[*idx])
                     .show_ui(ui, |ui| {
@@ -785,6 +816,97 @@ pub(crate) fn render_constraints_tab(
                         });
                     }
                 }
+                4 => {
+                    // Proximity
+                    if !role_names.is_empty() {
+                        ui.horizontal(|ui| {
+                            ui.label("Role:");
+                            let idx = &mut editor_state.new_constraint_role_index;
+                            *idx = (*idx).min(role_names.len().saturating_sub(1));
+                            egui::ComboBox::from_id_salt("cst_prox_role")
+                                .selected_text(role_names.get(*idx).copied().unwrap_or("--"))
+                                .show_ui(ui, |ui| {
+                                    for (i, name) in role_names.iter().enumerate() {
+                                        ui.selectable_value(idx, i, *name);
+                                    }
+                                });
+                        });
+                    }
+                    let targets: Vec<&str> = std::iter::once("Any unit")
+                        .chain(
+                            entity_registry
+                                .types_by_role(EntityRole::Token)
+                                .into_iter()
+                                .map(|t| t.name.as_str()),
+                        )
+                        .collect();
+                    ui.horizontal(|ui| {
+                        ui.label("Nearest:");
+                        let idx = &mut editor_state.new_constraint_proximity_type_index;
+                        *idx = (*idx).min(targets.len() - 1);
+                        egui::ComboBox::from_id_salt("cst_prox_type")
+                            .selected_text(targets[*idx])
+                            .show_ui(ui, |ui| {
+                                for (i, name) in targets.iter().enumerate() {
+                                    ui.selectable_value(idx, i, *name);
+                                }
+                            });
+                    });
+                    let factions = ["Any side", "Friendly", "Enemy"];
+                    ui.horizontal(|ui| {
+                        ui.label("Side:");
+                        let idx = &mut editor_state.new_constraint_proximity_faction_index;
+                        *idx = (*idx).min(factions.len() - 1);
+                        egui::ComboBox::from_id_salt("cst_prox_faction")
+                            .selected_text(factions[*idx])
+                            .show_ui(ui, |ui| {
+                                for (i, name) in factions.iter().enumerate() {
+                                    ui.selectable_value(idx, i, *name);
+                                }
+                            });
+                    });
+                    let measures = ["Distance", "Path cost"];
+                    ui.horizontal(|ui| {
+                        ui.label("Measure:");
+                        let idx = &mut editor_state.new_constraint_proximity_measure_index;
+                        *idx = (*idx).min(measures.len() - 1);
+                        egui::ComboBox::from_id_salt("cst_prox_measure")
+                            .selected_text(measures[*idx])
+                            .show_ui(ui, |ui| {
+                                for (i, name) in measures.iter().enumerate() {
+                                    ui.selectable_value(idx, i, *name);
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Max:");
+                        ui.text_edit_singleline(&mut editor_state.new_constraint_value_str);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Same prop:");
+                        ui.text_edit_singleline(&mut editor_state.new_constraint_property);
+                    });
+                    let sight: Vec<&str> = std::iter::once("Not required")
+                        .chain(
+                            entity_registry
+                                .types_by_role(EntityRole::BoardPosition)
+                                .into_iter()
+                                .map(|t| t.name.as_str()),
+                        )
+                        .collect();
+                    ui.horizontal(|ui| {
+                        ui.label("Sight blocked by:");
+                        let idx = &mut editor_state.new_constraint_proximity_sight_index;
+                        *idx = (*idx).min(sight.len() - 1);
+                        egui::ComboBox::from_id_salt("cst_prox_sight")
+                            .selected_text(sight[*idx])
+                            .show_ui(ui, |ui| {
+                                for (i, name) in sight.iter().enumerate() {
+                                    ui.selectable_value(idx, i, *name);
+                                }
+                            });
+                    });
+                }
                 _ => {
                     // TODO(#17): CrossCompare and IsType editors
                     ui.label(
@@ -807,7 +929,8 @@ pub(crate) fn render_constraints_tab(
                 {
                     let concept_idx = editor_state.new_constraint_concept_index;
                     if let Some((concept_id, _, roles)) = concepts.get(concept_idx) {
-                        let expression = build_constraint_expression(editor_state, roles);
+                        let expression =
+                            build_constraint_expression(editor_state, roles, entity_registry);
                         actions.push(EditorAction::CreateConstraint {
                             name: editor_state.new_constraint_name.trim().to_string(),
                             description: editor_state.new_constraint_description.trim().to_string(),
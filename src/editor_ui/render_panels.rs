@@ -3,15 +3,19 @@
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, egui};
 
-use hexorder_contracts::editor_ui::{ActiveEdgeType, EditorTool, ToastKind};
+use hexorder_contracts::editor_ui::{
+    ActiveEdgeType, ActiveVertexType, EditorTool, ToastKind, ViewportRect,
+};
 use hexorder_contracts::game_system::{
-    ActiveBoardType, ActiveTokenType, EntityRole, EntityTypeRegistry, GameSystem,
+    ActiveBoardType, ActiveTokenType, EntityRole, EntityState, EntityTypeRegistry, GameSystem,
+    StateMachineRegistry, UnitInstance,
 };
 #[cfg(feature = "inspector")]
 use hexorder_contracts::hex_grid::SelectedHex;
 use hexorder_contracts::hex_grid::{HexGridConfig, HexPosition, HexTile};
 use hexorder_contracts::persistence::{LoadRequestEvent, NewProjectEvent, Workspace};
 use hexorder_contracts::settings::{SettingsRegistry, ThemeLibrary};
+use hexorder_contracts::validation::ValidMoveSet;
 
 use super::actions::bevy_color_to_egui;
 use super::components::{BrandTheme, EditorState, GridOverlayVisible, ToastState};
@@ -465,6 +469,13 @@ pub(crate) fn render_tool_mode(ui: &mut egui::Ui, editor_tool: &mut EditorTool)
         {
             *editor_tool = EditorTool::EdgePaint;
         }
+        if ui
+            .selectable_label(*editor_tool == EditorTool::VertexPaint, "Vertex")
+            .on_hover_text("Click near a hex corner to assign vertex features (6)")
+            .clicked()
+        {
+            *editor_tool = EditorTool::VertexPaint;
+        }
         if ui
             .selectable_label(*editor_tool == EditorTool::CombatSelect, "Combat")
             .on_hover_text("Click units to assign attacker/defender (5)")
@@ -579,6 +590,65 @@ pub(crate) fn render_edge_palette(
     ui.separator();
 }
 
+pub(crate) fn render_vertex_palette(
+    ui: &mut egui::Ui,
+    registry: &EntityTypeRegistry,
+    active_vertex: &mut ActiveVertexType,
+) {
+    ui.label(
+        egui::RichText::new("Vertex Palette")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    ui.add_space(4.0);
+    ui.label(
+        egui::RichText::new("Click near a hex corner")
+            .small()
+            .color(BrandTheme::TEXT_SECONDARY),
+    );
+    ui.add_space(8.0);
+
+    // "Erase" option to remove vertex features.
+    let erase_active = active_vertex.type_name.is_none();
+    if ui.selectable_label(erase_active, "🗑 Erase").clicked() {
+        active_vertex.type_name = None;
+    }
+
+    // List all entity types as potential vertex feature types.
+    for et in &registry.types {
+        let is_active = active_vertex
+            .type_name
+            .as_ref()
+            .is_some_and(|n| *n == et.name);
+        let color = bevy_color_to_egui(et.color);
+        let et_name = et.name.clone();
+
+        ui.horizontal(|ui| {
+            let (rect, response) =
+                ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::click());
+            if ui.is_rect_visible(rect) {
+                ui.painter().rect_filled(rect, 2.0, color);
+                if is_active {
+                    ui.painter().rect_stroke(
+                        rect,
+                        2.0,
+                        egui::Stroke::new(2.0, BrandTheme::ACCENT_AMBER),
+                        egui::StrokeKind::Outside,
+                    );
+                }
+            }
+            if response.clicked() {
+                active_vertex.type_name = Some(et_name.clone());
+            }
+            if ui.selectable_label(is_active, &et_name).clicked() {
+                active_vertex.type_name = Some(et_name);
+            }
+        });
+    }
+
+    ui.separator();
+}
+
 pub(crate) fn render_unit_palette(
     ui: &mut egui::Ui,
     registry: &EntityTypeRegistry,
@@ -713,6 +783,112 @@ pub fn render_toast(
         });
 }
 
+/// Shows the cost breakdown of the cheapest route next to the pointer while
+/// it hovers a reachable hex in the viewport.
+pub fn render_move_tooltip(
+    mut contexts: EguiContexts,
+    valid_moves: Res<ValidMoveSet>,
+    viewport_rect: Res<ViewportRect>,
+    config: Option<Res<HexGridConfig>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    if valid_moves.paths.is_empty() {
+        return;
+    }
+    let Some(config) = config else {
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let Some(pointer) = ctx.pointer_hover_pos() else {
+        return;
+    };
+    if viewport_rect.0.is_some_and(|rect| !rect.contains(pointer)) {
+        return;
+    }
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+
+    // Project the pointer onto the Y=0 ground plane.
+    let Ok(ray) = camera.viewport_to_world(camera_transform, Vec2::new(pointer.x, pointer.y))
+    else {
+        return;
+    };
+    if ray.direction.y.abs() < 1e-6 {
+        return;
+    }
+    let t = -ray.origin.y / ray.direction.y;
+    if t < 0.0 {
+        return;
+    }
+    let ground = ray.origin + t * *ray.direction;
+    let hex = config
+        .layout
+        .world_pos_to_hex(Vec2::new(ground.x, ground.z));
+    let Some(pos) = config.resolve(HexPosition::from_hex(hex)) else {
+        return;
+    };
+    if !valid_moves.valid_positions.contains(&pos) {
+        return;
+    }
+
+    egui::Area::new(egui::Id::new("move_cost_tooltip"))
+        .order(egui::Order::Tooltip)
+        .fixed_pos(pointer + egui::vec2(16.0, 16.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::NONE
+                .fill(BrandTheme::BG_SURFACE)
+                .stroke(egui::Stroke::new(1.0, BrandTheme::BORDER_SUBTLE))
+                .corner_radius(4.0)
+                .inner_margin(egui::Margin::symmetric(8, 6))
+                .show(ui, |ui| {
+                    render_move_breakdown(ui, &valid_moves, pos);
+                });
+        });
+}
+
+/// Lists each step of the cheapest route to `pos` with its cost components.
+pub(crate) fn render_move_breakdown(
+    ui: &mut egui::Ui,
+    valid_moves: &ValidMoveSet,
+    pos: HexPosition,
+) {
+    let Some(steps) = valid_moves.route_steps(pos) else {
+        return;
+    };
+    let total = valid_moves
+        .paths
+        .get(&pos)
+        .map_or(0, |step| step.total_cost);
+    ui.label(
+        egui::RichText::new(format!("({}, {}) \u{2014} cost {total}", pos.q, pos.r))
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    for (step_pos, step) in steps {
+        ui.label(
+            egui::RichText::new(format!(
+                "({}, {}) +{}",
+                step_pos.q,
+                step_pos.r,
+                step.step_cost()
+            ))
+            .small()
+            .color(BrandTheme::TEXT_PRIMARY),
+        );
+        for component in &step.components {
+            ui.label(
+                egui::RichText::new(format!("  {} {:+}", component.label, component.amount))
+                    .small()
+                    .color(BrandTheme::TEXT_SECONDARY),
+            );
+        }
+    }
+}
+
 /// Renders (q,r) coordinate labels on each hex tile when the grid overlay is enabled.
 pub fn render_grid_overlay(
     mut contexts: EguiContexts,
@@ -764,3 +940,60 @@ pub fn render_grid_overlay(
         );
     }
 }
+
+/// Paints the badge of each tile's and unit's current state above it, so
+/// state changes are visible on the board.
+#[allow(clippy::type_complexity)]
+pub fn render_state_badges(
+    mut contexts: EguiContexts,
+    state_machines: Option<Res<StateMachineRegistry>>,
+    instances: Query<(&GlobalTransform, &EntityState), Or<(With<HexTile>, With<UnitInstance>)>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    let Some(state_machines) = state_machines else {
+        return;
+    };
+    if instances.is_empty() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+
+    let available = ctx.available_rect();
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("state_badges"),
+    ));
+    let font = egui::FontId::new(11.0, egui::FontFamily::Proportional);
+
+    for (transform, state) in &instances {
+        let Some(definition) = state_machines.state(state.state_id) else {
+            continue;
+        };
+        if definition.badge.is_empty() {
+            continue;
+        }
+        let Ok(viewport_pos) = camera.world_to_viewport(camera_transform, transform.translation())
+        else {
+            continue;
+        };
+        let screen_pos = egui::pos2(viewport_pos.x, viewport_pos.y - 10.0);
+        if !available.contains(screen_pos) {
+            continue;
+        }
+        let color = definition
+            .color
+            .map_or(BrandTheme::ACCENT_AMBER, bevy_color_to_egui);
+        painter.text(
+            screen_pos,
+            egui::Align2::CENTER_BOTTOM,
+            &definition.badge,
+            font.clone(),
+            color,
+        );
+    }
+}
@@ -5,26 +5,31 @@ use bevy_egui::{EguiContexts, egui};
 
 use hexorder_contracts::editor_ui::ViewportMargins;
 use hexorder_contracts::game_system::{
-    EntityData, EntityTypeRegistry, GameSystem, SelectedUnit, UnitInstance,
+    EntityData, EntityTypeRegistry, GameSystem, SelectedUnit, TypeId, UnitInstance,
 };
+use hexorder_contracts::hex_grid::HexPosition;
 use hexorder_contracts::mechanics::{
-    ActiveCombat, AreaEffect, AreaMarker, AreaMarkerRegistry, CombatModifierRegistry,
-    CombatResultsTable, ConstrainedPathRequest, MarkerDuration, PathConstraint, PathfindingContext,
-    PhaseAction, PhaseType, PostResolutionAction, PostResolutionRule, TurnState, TurnStructure,
-    collect_area_column_shifts, evaluate_post_resolution, execute_phase_action,
-    find_constrained_path, is_phase_action_legal,
+    ActiveCombat, AdvanceOption, AreaEffect, AreaMarker, AreaMarkerRegistry,
+    CombatModifierRegistry, CombatMoveEvent, CombatResolvedEvent, CombatResultsTable,
+    DeployFromZoneEvent, InterruptPause, MarkerDuration, MoveToZoneEvent,
+    MovementInterruptRegistry, OffMapZoneRegistry, OverrunRegistry, OverrunRequestedEvent,
+    OverrunTargets, PhaseAction, PhaseType, PlayLog, PostResolutionAction, ResolveInterruptEvent,
+    TurnState, TurnStructure, collect_area_column_shifts, current_phase, execute_phase_action,
+    is_phase_action_legal,
 };
 use hexorder_contracts::persistence::{
     AppScreen, CloseProjectEvent, LoadRequestEvent, SaveRequestEvent, Workspace,
 };
 use hexorder_contracts::simulation::{
-    ChainRollSource, ChainStep, DicePool, ResolutionChain, SimulationRng, reset_rng, resolve_chain,
-    roll_pool,
+    ChainRollSource, ChainStep, DicePool, DieRolled, ResolutionChain, RollHistory, SessionSeed,
+    SimulationRng, TableResolution, TableResolved, TableResult, reset_rng, resolve_chain,
+    roll_pool, table_roll,
 };
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+use std::fmt::Write as _;
 
-use super::components::{BrandTheme, EditorState};
+use super::components::{BrandTheme, EditorState, PlayBoardParams, PlayDiceParams, PlayRoll};
 use super::render_panels::{render_about_panel, render_workspace_header};
 
 /// Actions that can be triggered from the play mode file menu.
@@ -40,6 +45,22 @@ pub(crate) enum PlayMenuAction {
     ShowAbout,
 }
 
+/// A zone transfer requested from the play mode zones panel.
+/// Returned by [`render_off_map_zones_panel`] so the caller can trigger
+/// the corresponding events outside the egui closure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ZoneRequest {
+    MoveToZone {
+        entity: Entity,
+        zone_id: TypeId,
+    },
+    Deploy {
+        zone_id: TypeId,
+        index: usize,
+        position: HexPosition,
+    },
+}
+
 /// Play mode panel system. Shows the turn tracker, combat panel, and mode toggle.
 /// Runs only in `AppScreen::Play`.
 #[allow(clippy::too_many_arguments)]
@@ -57,8 +78,8 @@ pub fn play_panel_system(
     selected_unit: Res<SelectedUnit>,
     entity_types: Res<EntityTypeRegistry>,
     mut editor_state: ResMut<EditorState>,
-    mut sim_rng: ResMut<SimulationRng>,
-    mut area_markers: ResMut<AreaMarkerRegistry>,
+    mut dice: PlayDiceParams,
+    mut board: PlayBoardParams,
     unit_query: Query<
         (
             &EntityData,
@@ -98,6 +119,11 @@ pub fn play_panel_system(
         }
     }
 
+    // The table the active combat is resolved on.
+    let combat_results_table = board
+        .combat_tables
+        .table_or(active_combat.table, &combat_results_table);
+
     // -- Sidebar --
     let mut switch_to_editor = false;
     egui::SidePanel::left("play_panel")
@@ -110,18 +136,161 @@ pub fn play_panel_system(
                 &mut turn_state,
                 &turn_structure,
                 &mut active_combat,
-                &combat_results_table,
+                combat_results_table,
                 &combat_modifiers,
                 &selected_unit,
                 &entity_types,
                 &mut editor_state,
-                &mut sim_rng,
-                &mut area_markers,
+                &mut dice.sim_rng,
+                &mut board.area_markers,
                 &|e| unit_query.get(e).ok().map(|(ed, _)| ed),
                 &|e| unit_query.get(e).ok().and_then(|(_, pos)| pos),
             );
         });
 
+    // Announce the panels' rolls for the roll history.
+    for PlayRoll {
+        context,
+        roll,
+        table,
+    } in std::mem::take(&mut editor_state.play_rolls)
+    {
+        commands.trigger(DieRolled {
+            context,
+            roll: roll.clone(),
+        });
+        if let Some((table_id, resolution)) = table {
+            commands.trigger(TableResolved {
+                table_id,
+                resolution,
+                roll: Some(roll),
+            });
+        }
+    }
+
+    // Announce a freshly resolved combat so outcome consumers (such as
+    // entity state machines) can react.
+    if std::mem::take(&mut editor_state.combat_resolved)
+        && let (Some(attacker), Some(defender), Some(outcome), Some(die_roll)) = (
+            active_combat.attacker,
+            active_combat.defender,
+            active_combat.outcome.clone(),
+            active_combat.die_roll,
+        )
+    {
+        let column_label = active_combat
+            .resolved_column
+            .and_then(|col| combat_results_table.table.columns.get(col))
+            .map(|col| col.label.clone())
+            .unwrap_or_default();
+        commands.trigger(CombatResolvedEvent {
+            attacker,
+            defender,
+            attackers: active_combat.attacking_units(),
+            defenders: active_combat.defending_units(),
+            step_losses: active_combat.step_losses.clone(),
+            outcome,
+            die_roll,
+            column_label,
+        });
+    }
+
+    // Announce retreats and advances chosen in the combat panel.
+    for event in std::mem::take(&mut editor_state.combat_moves) {
+        commands.trigger(event);
+    }
+
+    // -- Off-Map Zones --
+    let mut zone_requests = Vec::new();
+    if !board.off_map_zones.zones.is_empty() {
+        egui::SidePanel::right("play_zones_panel")
+            .default_width(220.0)
+            .show(ctx, |ui| {
+                let phase_id = current_phase(&turn_state, &turn_structure).map(|p| p.id);
+                zone_requests = render_off_map_zones_panel(
+                    ui,
+                    &board.off_map_zones,
+                    &entity_types,
+                    &selected_unit,
+                    board.selected_hex.position,
+                    phase_id,
+                );
+            });
+    }
+    for request in zone_requests {
+        match request {
+            ZoneRequest::MoveToZone { entity, zone_id } => {
+                commands.trigger(MoveToZoneEvent { entity, zone_id });
+            }
+            ZoneRequest::Deploy {
+                zone_id,
+                index,
+                position,
+            } => commands.trigger(DeployFromZoneEvent {
+                zone_id,
+                index,
+                position,
+            }),
+        }
+    }
+
+    // -- Movement Interrupt --
+    if let Some(pause) = &board.active_interrupt.pause {
+        let reactor_name = unit_query
+            .get(pause.reactor)
+            .ok()
+            .and_then(|(data, _)| entity_types.get(data.entity_type_id))
+            .map_or("Unit", |t| t.name.as_str());
+        if render_movement_interrupt_window(ctx, pause, &board.movement_interrupts, reactor_name) {
+            commands.trigger(ResolveInterruptEvent);
+        }
+    }
+
+    // -- Overrun, Play Log and Roll History --
+    let unit_label = |entity: Entity| {
+        unit_query.get(entity).ok().map_or_else(
+            || "Unit".to_string(),
+            |(data, pos)| {
+                let name = entity_types
+                    .get(data.entity_type_id)
+                    .map_or("Unit", |t| t.name.as_str());
+                pos.map_or_else(
+                    || name.to_string(),
+                    |p| format!("{name} at ({}, {})", p.q, p.r),
+                )
+            },
+        )
+    };
+    let mut overrun = None;
+    let seed = dice.sim_rng.seed();
+    egui::TopBottomPanel::bottom("play_log_panel")
+        .resizable(true)
+        .default_height(140.0)
+        .show(ctx, |ui| {
+            ui.columns(2, |columns| {
+                overrun = render_overrun_options(
+                    &mut columns[0],
+                    &board.overrun_targets,
+                    &board.overruns,
+                    &unit_label,
+                );
+                render_play_log(&mut columns[0], &board.play_log);
+                render_roll_history(
+                    &mut columns[1],
+                    &dice.roll_history,
+                    &mut dice.session_seed,
+                    seed,
+                );
+            });
+        });
+    if let (Some(attacker), Some((defender, rule_id))) = (board.overrun_targets.attacker, overrun) {
+        commands.trigger(OverrunRequestedEvent {
+            attacker,
+            defender,
+            rule_id,
+        });
+    }
+
     if switch_to_editor {
         turn_state.is_active = false;
         next_state.set(AppScreen::Editor);
@@ -138,6 +307,175 @@ pub fn play_panel_system(
     render_about_panel(ctx, &mut editor_state);
 }
 
+/// Renders the window for a move halted at a movement interrupt.
+/// Returns `true` when the user asks to resolve it.
+pub(crate) fn render_movement_interrupt_window(
+    ctx: &egui::Context,
+    pause: &InterruptPause,
+    interrupts: &MovementInterruptRegistry,
+    reactor_name: &str,
+) -> bool {
+    let rule_name = interrupts
+        .get(pause.rule_id)
+        .map_or("Movement Interrupt", |rule| rule.name.as_str());
+    let mut resolve = false;
+    egui::Window::new("Movement Interrupted")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 48.0])
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(rule_name)
+                    .strong()
+                    .color(BrandTheme::ACCENT_AMBER),
+            );
+            ui.label(format!(
+                "{reactor_name} reacts at ({}, {})",
+                pause.position.q, pause.position.r
+            ));
+            ui.label(
+                egui::RichText::new(format!("{} hex(es) left to move", pause.remaining.len()))
+                    .small()
+                    .color(BrandTheme::TEXT_SECONDARY),
+            );
+            ui.add_space(4.0);
+            if ui.button("Resolve").clicked() {
+                resolve = true;
+            }
+        });
+    resolve
+}
+
+/// Renders a button per overrun the selected unit may make now.
+/// Returns the defender and rule of the one the user picked.
+pub(crate) fn render_overrun_options(
+    ui: &mut egui::Ui,
+    targets: &OverrunTargets,
+    overruns: &OverrunRegistry,
+    unit_label: &dyn Fn(Entity) -> String,
+) -> Option<(Entity, TypeId)> {
+    if targets.targets.is_empty() {
+        return None;
+    }
+    ui.label(
+        egui::RichText::new("Overrun")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    let mut picked = None;
+    for &(defender, rule_id) in &targets.targets {
+        let Some(rule) = overruns.get(rule_id) else {
+            continue;
+        };
+        let label = format!(
+            "Overrun {} \u{2014} {} ({} MP)",
+            unit_label(defender),
+            rule.name,
+            rule.mp_cost
+        );
+        if ui.button(label).clicked() {
+            picked = Some((defender, rule_id));
+        }
+    }
+    ui.add_space(4.0);
+    picked
+}
+
+/// Renders the play session's log, newest first.
+pub(crate) fn render_play_log(ui: &mut egui::Ui, log: &PlayLog) {
+    ui.label(
+        egui::RichText::new("Play Log")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    if log.entries.is_empty() {
+        ui.label(
+            egui::RichText::new("Nothing has happened yet.")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+        return;
+    }
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for entry in log.entries.iter().rev() {
+            ui.label(
+                egui::RichText::new(format!("T{}  {}", entry.turn_number, entry.message))
+                    .small()
+                    .color(BrandTheme::TEXT_PRIMARY),
+            );
+        }
+    });
+}
+
+/// Renders the play session's rolls, newest first, with the lookup each
+/// decided, and a toggle fixing the current seed for later sessions.
+pub(crate) fn render_roll_history(
+    ui: &mut egui::Ui,
+    history: &RollHistory,
+    session_seed: &mut SessionSeed,
+    seed: u64,
+) {
+    ui.label(
+        egui::RichText::new("Roll History")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    let mut fixed = session_seed.0.is_some();
+    if ui
+        .checkbox(&mut fixed, format!("Fix seed {seed} for each session"))
+        .changed()
+    {
+        session_seed.0 = fixed.then_some(seed);
+    }
+    if history.entries.is_empty() {
+        ui.label(
+            egui::RichText::new("No rolls yet.")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+        return;
+    }
+    egui::ScrollArea::vertical()
+        .id_salt("roll_history")
+        .show(ui, |ui| {
+            for (i, entry) in history.entries.iter().enumerate().rev() {
+                let values: Vec<String> =
+                    entry.roll.values.iter().map(ToString::to_string).collect();
+                let mut line = format!(
+                    "#{} {}: {} [{}] = {}",
+                    i + 1,
+                    entry.context,
+                    entry.roll.pool,
+                    values.join(", "),
+                    entry.roll.total
+                );
+                if let Some(resolution) = &entry.resolution {
+                    let _ = write!(
+                        line,
+                        " \u{2192} {} / {}: {}",
+                        resolution.column_label,
+                        resolution.row_label,
+                        table_result_label(&resolution.result)
+                    );
+                }
+                ui.label(
+                    egui::RichText::new(line)
+                        .small()
+                        .color(BrandTheme::TEXT_PRIMARY),
+                );
+            }
+        });
+}
+
+/// Display text of a table result.
+fn table_result_label(result: &TableResult) -> String {
+    match result {
+        TableResult::Text(s) => s.clone(),
+        TableResult::NumericValue(v) => format!("{v:.1}"),
+        TableResult::PropertyModifier { property, delta } => format!("{property} {delta:+.1}"),
+    }
+}
+
 /// Updates viewport margins in Play mode so the camera knows where the 3D
 /// viewport is (to the right of the play sidebar). Must run after
 /// `play_panel_system` so egui has laid out the side panel.
@@ -251,6 +589,7 @@ pub(crate) fn render_play_sidebar<'a>(
             selected_unit,
             entity_types,
             editor_state,
+            sim_rng,
             area_markers,
             unit_lookup,
             position_lookup,
@@ -427,6 +766,11 @@ pub(crate) fn render_dice_panel(
     // Roll button.
     if ui.button("Roll \u{1F3B2}").clicked() {
         let result = roll_pool(sim_rng, pool, "dice panel");
+        editor_state.play_rolls.push(PlayRoll {
+            context: "Dice panel".to_string(),
+            roll: result.clone(),
+            table: None,
+        });
         editor_state.last_dice_roll = Some(result);
     }
 
@@ -569,6 +913,18 @@ pub(crate) fn render_chain_panel(
                 tables.insert(crt.table.id, crt.table.clone());
 
                 let ctx = resolve_chain(&chain, &initial, &tables, sim_rng);
+                for (step, result) in chain.steps.iter().zip(&ctx.step_log) {
+                    if let Some(roll) = &result.roll {
+                        editor_state.play_rolls.push(PlayRoll {
+                            context: format!("{} step {}", chain.name, result.step_index + 1),
+                            roll: roll.clone(),
+                            table: result
+                                .resolution
+                                .clone()
+                                .map(|resolution| (step.table_id, resolution)),
+                        });
+                    }
+                }
                 editor_state.last_chain_result = Some(ctx);
             }
 
@@ -607,16 +963,7 @@ pub(crate) fn render_chain_panel(
                                 .color(BrandTheme::TEXT_PRIMARY),
                             );
                         });
-                        let result_text = match &res.result {
-                            hexorder_contracts::simulation::TableResult::Text(s) => s.clone(),
-                            hexorder_contracts::simulation::TableResult::NumericValue(v) => {
-                                format!("{v:.1}")
-                            }
-                            hexorder_contracts::simulation::TableResult::PropertyModifier {
-                                property,
-                                delta,
-                            } => format!("{property} {delta:+.1}"),
-                        };
+                        let result_text = table_result_label(&res.result);
                         ui.label(
                             egui::RichText::new(format!("  Result: {result_text}"))
                                 .strong()
@@ -667,14 +1014,16 @@ pub(crate) fn render_combat_panel<'a>(
     selected_unit: &SelectedUnit,
     entity_types: &EntityTypeRegistry,
     editor_state: &mut EditorState,
+    sim_rng: &mut SimulationRng,
     area_markers: &AreaMarkerRegistry,
     unit_lookup: &dyn Fn(Entity) -> Option<&'a EntityData>,
     position_lookup: &dyn Fn(Entity) -> Option<&'a hexorder_contracts::hex_grid::HexPosition>,
     in_combat_phase: bool,
 ) {
-    use hexorder_contracts::mechanics::resolve_crt;
+    use hexorder_contracts::mechanics::{allocate_step_losses, odds_column};
     use hexorder_contracts::simulation::{
-        ColumnModifier, apply_column_shift, evaluate_column_modifiers, find_table_column,
+        ColumnType, apply_column_shift, evaluate_column_modifiers, find_table_column,
+        find_table_row,
     };
 
     ui.label(
@@ -682,6 +1031,11 @@ pub(crate) fn render_combat_panel<'a>(
             .strong()
             .color(BrandTheme::ACCENT_AMBER),
     );
+    ui.label(
+        egui::RichText::new(format!("Table: {}", crt.name))
+            .small()
+            .color(BrandTheme::TEXT_SECONDARY),
+    );
     ui.add_space(4.0);
 
     if crt.table.columns.is_empty() || crt.table.rows.is_empty() {
@@ -693,81 +1047,140 @@ pub(crate) fn render_combat_panel<'a>(
         return;
     }
 
-    // -- Attacker assignment --
-    ui.horizontal(|ui| {
-        ui.label("Attacker:");
-        if let Some(atk) = active_combat.attacker {
-            let name = unit_lookup(atk)
-                .and_then(|ed| entity_types.get(ed.entity_type_id))
-                .map_or("(unknown)".to_string(), |et| et.name.clone());
-            ui.label(
-                egui::RichText::new(&name)
-                    .strong()
-                    .color(BrandTheme::TEXT_PRIMARY),
-            );
-        } else {
+    // -- Participants --
+    let mut removed = None;
+    let mut moved_earlier = None;
+    for (heading, units) in [
+        ("Attackers", active_combat.attacking_units()),
+        ("Defenders", active_combat.defending_units()),
+    ] {
+        ui.label(
+            egui::RichText::new(heading)
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+        if units.is_empty() {
             ui.label(egui::RichText::new("None").color(BrandTheme::TEXT_SECONDARY));
         }
-    });
-    if selected_unit.entity.is_some() && ui.button("Set Attacker from Selection").clicked() {
-        active_combat.attacker = selected_unit.entity;
-        // Reset resolution state when combatants change.
-        active_combat.die_roll = None;
-        active_combat.outcome = None;
+        for (i, &entity) in units.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let name = unit_lookup(entity)
+                    .and_then(|ed| entity_types.get(ed.entity_type_id))
+                    .map_or("(unknown)".to_string(), |et| et.name.clone());
+                ui.label(
+                    egui::RichText::new(&name)
+                        .strong()
+                        .color(BrandTheme::TEXT_PRIMARY),
+                );
+                if let Some((_, reason)) = active_combat
+                    .ineligible
+                    .iter()
+                    .find(|(ineligible, _)| *ineligible == entity)
+                {
+                    ui.label(
+                        egui::RichText::new(reason.label())
+                            .small()
+                            .color(BrandTheme::DANGER),
+                    );
+                }
+                if ui
+                    .small_button(egui::RichText::new("x").color(BrandTheme::DANGER))
+                    .clicked()
+                {
+                    removed = Some(entity);
+                }
+                if i > 0
+                    && ui
+                        .small_button(
+                            egui::RichText::new("\u{2191}").color(BrandTheme::TEXT_PRIMARY),
+                        )
+                        .on_hover_text("Take losses earlier")
+                        .clicked()
+                {
+                    moved_earlier = Some(entity);
+                }
+            });
+        }
+        ui.add_space(4.0);
+    }
+    if let Some(entity) = removed {
+        active_combat.remove_participant(entity);
+    }
+    if let Some(entity) = moved_earlier {
+        active_combat.move_earlier(entity);
+    }
+    if let Some(selected) = selected_unit.entity
+        && !active_combat.is_participant(selected)
+    {
+        ui.horizontal(|ui| {
+            if ui.button("Add Attacker from Selection").clicked() {
+                active_combat.add_attacker(selected);
+            }
+            if ui.button("Add Defender from Selection").clicked() {
+                active_combat.add_defender(selected);
+            }
+        });
     }
 
-    ui.add_space(4.0);
+    ui.add_space(8.0);
 
-    // -- Defender assignment --
-    ui.horizontal(|ui| {
-        ui.label("Defender:");
-        if let Some(def) = active_combat.defender {
-            let name = unit_lookup(def)
-                .and_then(|ed| entity_types.get(ed.entity_type_id))
-                .map_or("(unknown)".to_string(), |et| et.name.clone());
+    // -- Strength inputs --
+    if let Some(odds) = &active_combat.odds {
+        // Computed by the CRT's strength model; each step is shown before the roll.
+        ui.label(
+            egui::RichText::new("Computed Strengths")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+        for (step, value) in &odds.steps {
             ui.label(
-                egui::RichText::new(&name)
-                    .strong()
+                egui::RichText::new(format!("  {step}: {}", format_strength(*value)))
+                    .small()
                     .color(BrandTheme::TEXT_PRIMARY),
             );
-        } else {
-            ui.label(egui::RichText::new("None").color(BrandTheme::TEXT_SECONDARY));
         }
-    });
-    if selected_unit.entity.is_some() && ui.button("Set Defender from Selection").clicked() {
-        active_combat.defender = selected_unit.entity;
-        active_combat.die_roll = None;
-        active_combat.outcome = None;
+        editor_state.combat_attacker_strength = odds.attacker_strength;
+        editor_state.combat_defender_strength = odds.defender_strength;
+    } else {
+        ui.label(
+            egui::RichText::new("Strengths")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+        ui.horizontal(|ui| {
+            ui.label("ATK:");
+            ui.add(egui::DragValue::new(&mut editor_state.combat_attacker_strength).speed(0.5));
+            ui.label("DEF:");
+            ui.add(egui::DragValue::new(&mut editor_state.combat_defender_strength).speed(0.5));
+        });
     }
 
-    ui.add_space(8.0);
-
-    // -- Strength inputs --
-    ui.label(
-        egui::RichText::new("Strengths")
-            .small()
-            .color(BrandTheme::TEXT_SECONDARY),
-    );
-    ui.horizontal(|ui| {
-        ui.label("ATK:");
-        ui.add(egui::DragValue::new(&mut editor_state.combat_attacker_strength).speed(0.5));
-        ui.label("DEF:");
-        ui.add(egui::DragValue::new(&mut editor_state.combat_defender_strength).speed(0.5));
-    });
-
     let atk_str = editor_state.combat_attacker_strength;
     let def_str = editor_state.combat_defender_strength;
+    active_combat.raw_value = match &active_combat.odds {
+        Some(odds) => Some(odds.raw_value),
+        None => match crt.table.columns[0].column_type {
+            ColumnType::Ratio => (def_str > 0.0).then(|| atk_str / def_str),
+            ColumnType::Differential => Some(atk_str - def_str),
+            ColumnType::Direct => Some(atk_str),
+        },
+    };
 
     // -- Odds display --
-    if def_str > 0.0 {
-        let ratio = atk_str / def_str;
-        ui.label(
-            egui::RichText::new(format!("Odds: {ratio:.2}:1")).color(BrandTheme::TEXT_PRIMARY),
-        );
+    if let Some(value) = active_combat.raw_value {
+        let odds = match crt.table.columns[0].column_type {
+            ColumnType::Ratio => format!("{value:.2}:1"),
+            ColumnType::Differential => format!("{value:+}"),
+            ColumnType::Direct => format_strength(value),
+        };
+        ui.label(egui::RichText::new(format!("Odds: {odds}")).color(BrandTheme::TEXT_PRIMARY));
     }
 
     // -- Column lookup --
-    let base_column = find_table_column(atk_str, def_str, &crt.table.columns);
+    let base_column = match &active_combat.odds {
+        Some(odds) => odds_column(&crt.table.columns, odds),
+        None => find_table_column(atk_str, def_str, &crt.table.columns),
+    };
     if let Some(col_idx) = base_column {
         ui.label(
             egui::RichText::new(format!(
@@ -793,18 +1206,33 @@ pub(crate) fn render_combat_panel<'a>(
                 .small()
                 .color(BrandTheme::TEXT_SECONDARY),
         );
-        let column_modifiers: Vec<ColumnModifier> = modifiers
+        // Modifiers whose condition or terrain filter failed don't shift.
+        let applicable: Vec<_> = modifiers
             .modifiers
             .iter()
-            .map(|m| ColumnModifier {
-                name: m.name.clone(),
-                column_shift: m.column_shift,
-                cap: m.cap,
-                priority: m.priority.max(0) as u32,
-            })
+            .zip(modifiers.column_modifiers())
+            .filter(|(m, _)| active_combat.modifier_applies(m.id))
+            .map(|(_, column_modifier)| column_modifier)
             .collect();
         let (total_shift, modifier_display) =
-            evaluate_column_modifiers(&column_modifiers, crt.table.columns.len());
+            evaluate_column_modifiers(&applicable, crt.table.columns.len());
+        for result in &active_combat.modifier_results {
+            let (text, color) = if result.satisfied {
+                (
+                    format!("  {}: {}", result.constraint_name, result.explanation),
+                    BrandTheme::TEXT_PRIMARY,
+                )
+            } else {
+                (
+                    format!(
+                        "  {}: not applied \u{2014} {}",
+                        result.constraint_name, result.explanation
+                    ),
+                    BrandTheme::TEXT_SECONDARY,
+                )
+            };
+            ui.label(egui::RichText::new(text).small().color(color));
+        }
         for (name, shift) in &modifier_display {
             let sign = if *shift >= 0 { "+" } else { "" };
             ui.label(
@@ -881,33 +1309,68 @@ pub(crate) fn render_combat_panel<'a>(
                 .color(BrandTheme::TEXT_SECONDARY),
         );
     }
-    let can_resolve = base_column.is_some() && in_combat_phase;
+    if !active_combat.ineligible.is_empty() {
+        ui.label(
+            egui::RichText::new("Remove ineligible participants to resolve the attack.")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+    }
+    let can_resolve =
+        base_column.is_some() && in_combat_phase && active_combat.ineligible.is_empty();
     ui.add_enabled_ui(can_resolve, |ui| {
-        if ui.button("Roll Die \u{1F3B2}").clicked() {
-            let roll = rand_die_roll();
+        if ui.button(format!("Roll {} \u{1F3B2}", crt.dice)).clicked() {
+            let context = active_combat
+                .resolved_column
+                .and_then(|col| crt.table.columns.get(col))
+                .map_or_else(
+                    || crt.name.clone(),
+                    |col| format!("{} {}", crt.name, col.label),
+                );
+            let dice = roll_pool(sim_rng, crt.dice, &context);
+            let roll = table_roll(dice.total);
             active_combat.die_roll = Some(roll);
+            active_combat.outcome = None;
+            active_combat.step_losses.clear();
 
-            // Full resolution.
-            let final_col = active_combat.resolved_column.unwrap_or(0);
+            // Look up the row of the base column, then apply the column shift.
             let shift = active_combat.total_shift;
-
-            // Resolve using the shifted column: build a temporary single-column CRT
-            // or use resolve_crt with original strengths and apply shift after.
-            if let Some(resolution) = resolve_crt(crt, atk_str, def_str, roll) {
-                // Apply column shift to get the actual outcome.
-                let shifted_col =
-                    apply_column_shift(resolution.column_index, shift, crt.table.columns.len());
-                if let Some(row_outcomes) = crt.outcomes.get(resolution.row_index)
+            let mut table = None;
+            if let (Some(base_col), Some(row_index)) =
+                (base_column, find_table_row(roll, &crt.table.rows))
+            {
+                let shifted_col = apply_column_shift(base_col, shift, crt.table.columns.len());
+                if let Some(row_outcomes) = crt.outcomes.get(row_index)
                     && let Some(outcome) = row_outcomes.get(shifted_col)
                 {
-                    active_combat.resolved_row = Some(resolution.row_index);
+                    active_combat.resolved_row = Some(row_index);
                     active_combat.outcome = Some(outcome.clone());
+                    active_combat.step_losses =
+                        outcome.effect.as_ref().map_or_else(Vec::new, |effect| {
+                            allocate_step_losses(
+                                effect,
+                                &active_combat.loss_candidates,
+                                &crt.losses.allocation,
+                            )
+                        });
+                    editor_state.combat_resolved = true;
+                    table = Some((
+                        crt.table.id,
+                        TableResolution {
+                            column_index: shifted_col,
+                            row_index,
+                            column_label: crt.table.columns[shifted_col].label.clone(),
+                            row_label: crt.table.rows[row_index].label.clone(),
+                            result: TableResult::Text(outcome.label.clone()),
+                        },
+                    ));
                 }
-            } else {
-                // Column matched but row might not — try with just the die roll.
-                let _ = final_col; // already stored in resolved_column
-                active_combat.outcome = None;
             }
+            editor_state.play_rolls.push(PlayRoll {
+                context,
+                roll: dice,
+                table,
+            });
         }
     });
 
@@ -962,79 +1425,99 @@ pub(crate) fn render_combat_panel<'a>(
                     .color(BrandTheme::TEXT_PRIMARY),
             );
         }
-    }
 
-    // -- Post-resolution movement preview --
-    if let Some(outcome) = &active_combat.outcome
-        && let (Some(atk), Some(def)) = (active_combat.attacker, active_combat.defender)
-    {
-        let default_rules = build_default_post_resolution_rules(outcome);
-        if !default_rules.is_empty() {
-            let pending = evaluate_post_resolution(&default_rules, outcome, atk, def);
-            if !pending.is_empty() {
-                ui.add_space(4.0);
+        if !active_combat.step_losses.is_empty() {
+            ui.add_space(4.0);
+            ui.label(
+                egui::RichText::new("Step Losses")
+                    .small()
+                    .strong()
+                    .color(BrandTheme::ACCENT_TEAL),
+            );
+            for (entity, steps) in &active_combat.step_losses {
+                let name = unit_lookup(*entity)
+                    .and_then(|ed| entity_types.get(ed.entity_type_id))
+                    .map_or("(unknown)".to_string(), |et| et.name.clone());
                 ui.label(
-                    egui::RichText::new("Post-Resolution Movement")
+                    egui::RichText::new(format!("  {name}: -{steps}"))
                         .small()
-                        .strong()
-                        .color(BrandTheme::ACCENT_TEAL),
+                        .color(BrandTheme::TEXT_PRIMARY),
                 );
-                for pm in &pending {
-                    let action_text = match pm.action {
-                        PostResolutionAction::Advance => "Attacker advances (1 hex)".to_string(),
-                        PostResolutionAction::Retreat => {
-                            format!("Defender retreats ({} hex)", pm.movement_range)
-                        }
-                        PostResolutionAction::Hold => "No movement".to_string(),
-                    };
-                    ui.label(
-                        egui::RichText::new(format!("  \u{2192} {action_text}"))
-                            .small()
-                            .color(BrandTheme::TEXT_PRIMARY),
-                    );
+            }
+        }
+    }
 
-                    // Compute retreat path preview using constrained pathfinding.
-                    if pm.action == PostResolutionAction::Retreat
-                        && let (Some(atk_pos), Some(def_pos)) =
-                            (position_lookup(atk), position_lookup(def))
-                    {
-                        let path_result =
-                            compute_retreat_path(*def_pos, *atk_pos, pm.movement_range);
-                        match &path_result.path {
-                            Some(path) if path.len() > 1 => {
-                                let steps: Vec<_> = path
-                                    .iter()
-                                    .skip(1)
-                                    .map(|p| format!("({},{})", p.q, p.r))
-                                    .collect();
-                                ui.label(
-                                    egui::RichText::new(format!(
-                                        "    Path: {} ({} steps)",
-                                        steps.join(" \u{2192} "),
-                                        steps.len(),
-                                    ))
-                                    .small()
-                                    .color(BrandTheme::TEXT_SECONDARY),
-                                );
-                            }
-                            _ => {
-                                let reason = path_result
-                                    .failure_reason
-                                    .as_deref()
-                                    .unwrap_or("No valid retreat path");
-                                ui.label(
-                                    egui::RichText::new(format!("    {reason}"))
-                                        .small()
-                                        .color(BrandTheme::TEXT_SECONDARY),
-                                );
-                            }
-                        }
-                    }
+    // -- Retreats awaiting the owning player's choice --
+    if !active_combat.retreats.is_empty() {
+        ui.add_space(4.0);
+        ui.label(
+            egui::RichText::new("Choose Retreat")
+                .small()
+                .strong()
+                .color(BrandTheme::ACCENT_TEAL),
+        );
+        for choice in &active_combat.retreats {
+            let names: Vec<String> = choice
+                .units
+                .iter()
+                .map(|entity| {
+                    unit_lookup(*entity)
+                        .and_then(|ed| entity_types.get(ed.entity_type_id))
+                        .map_or("(unknown)".to_string(), |et| et.name.clone())
+                })
+                .collect();
+            ui.label(
+                egui::RichText::new(format!("  {}", names.join(", ")))
+                    .small()
+                    .color(BrandTheme::TEXT_PRIMARY),
+            );
+            for path in &choice.paths {
+                let steps: Vec<String> = path
+                    .iter()
+                    .skip(1)
+                    .map(|p| format!("({},{})", p.q, p.r))
+                    .collect();
+                if ui
+                    .small_button(format!("Retreat {}", steps.join(" \u{2192} ")))
+                    .clicked()
+                {
+                    editor_state.combat_moves.push(CombatMoveEvent {
+                        units: choice.units.clone(),
+                        path: path.clone(),
+                        action: PostResolutionAction::Retreat,
+                    });
                 }
             }
         }
     }
 
+    // -- Advance after combat into vacated defender hexes --
+    let advances = &active_combat.advances;
+    if !advances.is_empty() {
+        ui.add_space(4.0);
+        ui.label(
+            egui::RichText::new("Advance After Combat")
+                .small()
+                .strong()
+                .color(BrandTheme::ACCENT_TEAL),
+        );
+        for &AdvanceOption { unit, from, to } in advances {
+            let name = unit_lookup(unit)
+                .and_then(|ed| entity_types.get(ed.entity_type_id))
+                .map_or("(unknown)".to_string(), |et| et.name.clone());
+            if ui
+                .small_button(format!("{name} advances to ({},{})", to.q, to.r))
+                .clicked()
+            {
+                editor_state.combat_moves.push(CombatMoveEvent {
+                    units: vec![unit],
+                    path: vec![from, to],
+                    action: PostResolutionAction::Advance,
+                });
+            }
+        }
+    }
+
     ui.add_space(8.0);
 
     // -- Clear button --
@@ -1045,6 +1528,85 @@ pub(crate) fn render_combat_panel<'a>(
     }
 }
 
+/// Formats a strength or odds value: whole numbers without decimals.
+fn format_strength(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+/// Renders the off-map zones panel: zone contents and deploy buttons.
+///
+/// Returns the transfers the user requested.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_off_map_zones_panel(
+    ui: &mut egui::Ui,
+    zones: &OffMapZoneRegistry,
+    entity_types: &EntityTypeRegistry,
+    selected_unit: &SelectedUnit,
+    selected_hex: Option<HexPosition>,
+    current_phase_id: Option<TypeId>,
+) -> Vec<ZoneRequest> {
+    let mut requests = Vec::new();
+
+    ui.label(
+        egui::RichText::new("Off-Map Zones")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    ui.add_space(4.0);
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for zone in &zones.zones {
+            egui::CollapsingHeader::new(format!("{} ({})", zone.name, zone.units.len()))
+                .id_salt(("play_zone", zone.id))
+                .default_open(true)
+                .show(ui, |ui| {
+                    let can_deploy =
+                        selected_hex.is_some() && zones.can_deploy(zone.id, current_phase_id);
+                    for (index, unit) in zone.units.iter().enumerate() {
+                        let type_name = entity_types
+                            .get(unit.entity_type_id)
+                            .map_or("(unknown)", |et| et.name.as_str());
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(type_name)
+                                    .small()
+                                    .color(BrandTheme::TEXT_PRIMARY),
+                            );
+                            let deploy = ui
+                                .add_enabled(can_deploy, egui::Button::new("Deploy").small())
+                                .on_disabled_hover_text(
+                                    "Select a hex during this zone's deploy phase",
+                                );
+                            if deploy.clicked()
+                                && let Some(position) = selected_hex
+                            {
+                                requests.push(ZoneRequest::Deploy {
+                                    zone_id: zone.id,
+                                    index,
+                                    position,
+                                });
+                            }
+                        });
+                    }
+                    if let Some(entity) = selected_unit.entity
+                        && ui.small_button("Move Selected Unit Here").clicked()
+                    {
+                        requests.push(ZoneRequest::MoveToZone {
+                            entity,
+                            zone_id: zone.id,
+                        });
+                    }
+                });
+        }
+    });
+
+    requests
+}
+
 /// Renders the area markers panel: list active markers, add/remove.
 pub(crate) fn render_area_markers_panel(ui: &mut egui::Ui, area_markers: &mut AreaMarkerRegistry) {
     ui.label(
@@ -1132,98 +1694,3 @@ pub(crate) fn render_area_markers_panel(ui: &mut egui::Ui, area_markers: &mut Ar
         });
     }
 }
-
-/// Computes a retreat path for the defender moving away from the attacker.
-///
-/// Builds a `PathfindingContext` using all hex neighbors within retreat range
-/// as valid positions, then uses `find_constrained_path` with `AwayFrom`.
-fn compute_retreat_path(
-    defender_pos: hexorder_contracts::hex_grid::HexPosition,
-    attacker_pos: hexorder_contracts::hex_grid::HexPosition,
-    movement_range: u32,
-) -> hexorder_contracts::mechanics::ConstrainedPathResult {
-    use hexorder_contracts::hex_grid::HexPosition;
-
-    // Build valid positions: all hex neighbors within retreat range.
-    let def_hex = defender_pos.to_hex();
-    let mut valid_positions = HashSet::new();
-    for ring_radius in 0..=movement_range {
-        for hex in hexx::Hex::ZERO.ring(ring_radius) {
-            let candidate = hexx::Hex::new(def_hex.x + hex.x, def_hex.y + hex.y);
-            valid_positions.insert(HexPosition::new(candidate.x, candidate.y));
-        }
-    }
-
-    let ctx = PathfindingContext {
-        valid_positions,
-        influence_zones: HashMap::new(),
-        terrain_types: HashMap::new(),
-    };
-
-    let request = ConstrainedPathRequest {
-        start: defender_pos,
-        constraints: vec![
-            PathConstraint::AwayFrom {
-                source: attacker_pos,
-            },
-            PathConstraint::MaxCost {
-                budget: movement_range,
-            },
-        ],
-        max_distance: movement_range,
-    };
-
-    find_constrained_path(&request, &ctx)
-}
-
-/// Builds default post-resolution rules from the combat outcome's effect.
-///
-/// This derives movement rules from the structured effect rather than requiring
-/// designers to define post-resolution rules separately (which is a future scope).
-fn build_default_post_resolution_rules(
-    outcome: &hexorder_contracts::mechanics::CombatOutcome,
-) -> Vec<PostResolutionRule> {
-    let Some(effect) = &outcome.effect else {
-        return Vec::new();
-    };
-    match effect {
-        hexorder_contracts::mechanics::OutcomeEffect::Retreat { hexes } => {
-            vec![PostResolutionRule {
-                action: PostResolutionAction::Retreat,
-                trigger_effects: vec![],
-                movement_range: *hexes,
-            }]
-        }
-        hexorder_contracts::mechanics::OutcomeEffect::DefenderEliminated => {
-            vec![PostResolutionRule {
-                action: PostResolutionAction::Advance,
-                trigger_effects: vec![],
-                movement_range: 1,
-            }]
-        }
-        _ => Vec::new(),
-    }
-}
-
-/// Generates a random die roll in range [1, 6].
-fn rand_die_roll() -> u32 {
-    use std::time::SystemTime;
-    let seed = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .map_or(0u64, |d| d.as_nanos() as u64);
-    // Simple LCG for a quick 1-6 value; no external crate needed.
-    ((seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1) >> 33) % 6 + 1) as u32
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn rand_die_roll_returns_value_in_range() {
-        for _ in 0..100 {
-            let roll = rand_die_roll();
-            assert!((1..=6).contains(&roll), "roll {roll} out of range [1, 6]");
-        }
-    }
-}
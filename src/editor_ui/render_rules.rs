@@ -1,24 +1,38 @@
 //! Rules tab rendering — validation, mechanics, and inspector.
 
+use std::collections::HashMap;
+
+use bevy::prelude::Entity;
 use bevy_egui::egui;
 
 use hexorder_contracts::game_system::TypeId;
 use hexorder_contracts::game_system::{
-    EntityData, EntityTypeRegistry, EnumRegistry, PropertyType, PropertyValue, StructRegistry,
+    EntityData, EntityRole, EntityType, EntityTypeRegistry, EnumRegistry, Faction, FactionRegistry,
+    PropertyType, PropertyValue, StateDefinition, StateMachine, StateMachineRegistry,
+    StateTransition, StateTrigger, StructRegistry, UnitId, UnitOwner,
 };
 use hexorder_contracts::hex_grid::{
-    HexPosition, InfluenceRule, InfluenceRuleRegistry, MovementCostMatrix, StackingRule,
+    GridShape, HexPosition, InfluenceRule, InfluenceRuleRegistry, MovementCostMatrix,
+    ReachabilityBlockers, ReachabilityOverlay, ReachabilityRule, ReachabilityRuleRegistry,
+    ReachabilitySource, StackingRule, ZoneOfControl, ZoneTransition,
 };
 use hexorder_contracts::mechanics::{
     AccumulationTrigger, AccumulatorRegistry, CombatModifierRegistry, CombatResultsTable,
-    ComparisonOp, ModifierSource, PhaseType, PlayerOrder, SpawnSchedule, TurnStructure,
-    VictoryConditionRegistry,
+    CombatTableRegistry, ComparisonOp, CrtSelection, InterruptResolution, LossAllocation,
+    ModifierSource, OffMapZone, OffMapZoneRegistry, OverrunOutcome, OverrunRegistry, OverrunRule,
+    PhaseType, PlayerOrder, SpawnSchedule, StrengthRounding, TerrainMultiplier, TurnStructure,
+    VictoryConditionRegistry, ZoneUnit,
+};
+use hexorder_contracts::ontology::{ConceptRegistry, ConstraintExpr, ConstraintRegistry};
+use hexorder_contracts::simulation::{
+    ColumnType, DicePool, ResolutionTable, TableResult, find_table_column, find_table_row,
+};
+use hexorder_contracts::validation::{
+    AnalysisCategory, AnalysisSubject, RuleAnalysis, SchemaValidation,
 };
-use hexorder_contracts::simulation::{ColumnType, find_table_column, find_table_row};
-use hexorder_contracts::validation::SchemaValidation;
 
-use super::actions::{bevy_color_to_egui, egui_color_to_bevy};
-use super::components::{BrandTheme, EditorAction, EditorState};
+use super::actions::{bevy_color_to_egui, egui_color_to_bevy, format_constraint_expr};
+use super::components::{BrandTheme, DockTab, EditorAction, EditorState, OntologyTab};
 
 pub(crate) fn render_validation_tab(ui: &mut egui::Ui, validation: &SchemaValidation) {
     ui.label(
@@ -69,6 +83,67 @@ pub(crate) fn render_validation_tab(ui: &mut egui::Ui, validation: &SchemaValida
     }
 }
 
+/// Renders static rule analysis findings, each with a link to the tab that
+/// edits its subject.
+pub(crate) fn render_rule_analysis(
+    ui: &mut egui::Ui,
+    analysis: &RuleAnalysis,
+    editor_state: &mut EditorState,
+) {
+    ui.add_space(8.0);
+    ui.label(
+        egui::RichText::new("Rule Analysis")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    if analysis.findings.is_empty() {
+        ui.label(egui::RichText::new("No rule issues found").color(BrandTheme::SUCCESS));
+        return;
+    }
+    ui.label(
+        egui::RichText::new(format!("{} Warning(s)", analysis.findings.len()))
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+
+    ui.add_space(4.0);
+    for finding in &analysis.findings {
+        ui.group(|ui| {
+            let category_str = match finding.category {
+                AnalysisCategory::Conflict => "Conflict",
+                AnalysisCategory::Unsatisfiable => "Unsatisfiable",
+                AnalysisCategory::NoEffect => "No Effect",
+                AnalysisCategory::UnreachableColumn => "Unreachable Column",
+                AnalysisCategory::EmptyPhase => "Empty Phase",
+                AnalysisCategory::Unreferenced => "Unreferenced",
+            };
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(category_str)
+                        .small()
+                        .color(BrandTheme::ACCENT_AMBER),
+                );
+                let (dock_tab, ontology_tab) = match finding.subject {
+                    AnalysisSubject::EntityType(_) => (DockTab::Design, OntologyTab::Types),
+                    AnalysisSubject::Relation(_) => (DockTab::Design, OntologyTab::Relations),
+                    AnalysisSubject::Constraint(_) => (DockTab::Rules, OntologyTab::Constraints),
+                    AnalysisSubject::CrtColumn(..) | AnalysisSubject::Phase(_) => {
+                        (DockTab::Rules, OntologyTab::Mechanics)
+                    }
+                };
+                if ui
+                    .small_button(format!("Go to {ontology_tab:?}"))
+                    .on_hover_text("Show the definition this finding is about")
+                    .clicked()
+                {
+                    editor_state.active_tab = ontology_tab;
+                    editor_state.focus_dock_tab = Some(dock_tab);
+                }
+            });
+            ui.label(egui::RichText::new(&finding.message).small());
+        });
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn render_mechanics_tab(
     ui: &mut egui::Ui,
@@ -205,6 +280,26 @@ pub(crate) fn render_mechanics_tab(
             .color(BrandTheme::TEXT_SECONDARY),
     );
 
+    // Dice pool the rows are rolled with.
+    ui.horizontal(|ui| {
+        ui.label("Dice:");
+        let mut dice = crt.dice;
+        let mut changed = ui
+            .add(egui::DragValue::new(&mut dice.count).range(1..=10))
+            .changed();
+        ui.label("d");
+        changed |= ui
+            .add(egui::DragValue::new(&mut dice.sides).range(2..=20))
+            .changed();
+        ui.label("+");
+        changed |= ui
+            .add(egui::DragValue::new(&mut dice.modifier).range(-10..=10))
+            .changed();
+        if changed {
+            actions.push(EditorAction::SetCrtDice { dice });
+        }
+    });
+
     // Column headers.
     ui.label(
         egui::RichText::new(format!("Columns ({})", crt.table.columns.len()))
@@ -702,154 +797,1021 @@ pub(crate) fn render_spawn_schedule(
     });
 }
 
-/// Renders the spatial influence rules editor.
-pub(crate) fn render_influence_rules(
+/// Renders the board shape editor: hexagon or rectangle, and whether a
+/// rectangular board wraps east-west. Changing the shape rebuilds the grid.
+pub(crate) fn render_board_shape(ui: &mut egui::Ui, shape: &mut GridShape) {
+    ui.label(
+        egui::RichText::new("Board Shape")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    ui.add_space(4.0);
+
+    ui.horizontal(|ui| {
+        let is_rect = matches!(shape, GridShape::Rectangle { .. });
+        if ui.selectable_label(!is_rect, "Hexagon").clicked() && is_rect {
+            *shape = GridShape::Hexagon;
+        }
+        if ui.selectable_label(is_rect, "Rectangle").clicked() && !is_rect {
+            *shape = GridShape::Rectangle {
+                width: 20,
+                height: 14,
+                wrap_horizontal: false,
+            };
+        }
+    });
+
+    if let GridShape::Rectangle {
+        width,
+        height,
+        wrap_horizontal,
+    } = *shape
+    {
+        let (mut width, mut height, mut wrap) = (width, height, wrap_horizontal);
+        ui.horizontal(|ui| {
+            ui.label("Columns:");
+            ui.add(egui::DragValue::new(&mut width).range(2..=80).speed(0.1));
+            ui.label("Rows:");
+            ui.add(egui::DragValue::new(&mut height).range(2..=80).speed(0.1));
+        });
+        ui.checkbox(&mut wrap, "Wrap east-west (cylinder)");
+        *shape = GridShape::Rectangle {
+            width,
+            height,
+            wrap_horizontal: wrap,
+        };
+    }
+}
+
+/// Renders the off-map zone editor: holding boxes, their contents, the
+/// elimination target, and the phase in which each zone may deploy units.
+pub(crate) fn render_off_map_zones(
     ui: &mut egui::Ui,
-    influence_rules: &mut InfluenceRuleRegistry,
+    zones: &mut OffMapZoneRegistry,
+    turn_structure: &TurnStructure,
     entity_types: &EntityTypeRegistry,
     editor_state: &mut EditorState,
 ) {
     ui.label(
-        egui::RichText::new("Spatial Influence Rules")
+        egui::RichText::new("Off-Map Zones")
             .strong()
             .color(BrandTheme::ACCENT_AMBER),
     );
     ui.add_space(4.0);
 
-    let mut remove_idx = None;
-    for (i, rule) in influence_rules.rules.iter().enumerate() {
-        let type_name = entity_types
-            .get(rule.entity_type_id)
-            .map_or("Unknown", |et| et.name.as_str());
-        ui.horizontal(|ui| {
-            ui.label(
-                egui::RichText::new(type_name)
-                    .small()
-                    .color(BrandTheme::TEXT_PRIMARY),
-            );
-            ui.label(
-                egui::RichText::new(format!("range {} +{} MP", rule.range, rule.cost_modifier))
-                    .small()
-                    .color(BrandTheme::TEXT_SECONDARY),
-            );
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if ui
-                    .small_button(egui::RichText::new("x").color(BrandTheme::DANGER))
-                    .clicked()
-                {
-                    remove_idx = Some(i);
-                }
-            });
-        });
-    }
-    if let Some(idx) = remove_idx {
-        influence_rules.rules.remove(idx);
+    if zones.zones.is_empty() {
+        ui.label(
+            egui::RichText::new("No zones. Add a holding box below.")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
     }
 
-    // Add rule form.
-    ui.add_space(4.0);
     let token_types: Vec<_> = entity_types
         .types
         .iter()
-        .filter(|et| et.role == hexorder_contracts::game_system::EntityRole::Token)
+        .filter(|et| et.role == EntityRole::Token)
         .collect();
 
-    if token_types.is_empty() {
-        ui.label(
-            egui::RichText::new("No token entity types defined")
-                .small()
-                .color(BrandTheme::TEXT_SECONDARY),
-        );
-    } else {
-        ui.horizontal(|ui| {
-            ui.label("Type:");
-            let selected_name = editor_state
-                .new_influence_type_idx
-                .and_then(|idx| token_types.get(idx))
-                .map_or("Select...", |et| et.name.as_str());
-            egui::ComboBox::from_id_salt("influence_type_picker")
-                .selected_text(selected_name)
-                .show_ui(ui, |ui| {
-                    for (idx, et) in token_types.iter().enumerate() {
+    let mut remove_zone = None;
+    for zone in &mut zones.zones {
+        let zone_id = zone.id;
+        egui::CollapsingHeader::new(format!("{} ({})", zone.name, zone.units.len()))
+            .id_salt(("off_map_zone", zone_id))
+            .show(ui, |ui| {
+                let mut is_eliminated = zones.eliminated_zone_id == Some(zone_id);
+                if ui
+                    .checkbox(&mut is_eliminated, "Receives eliminated units")
+                    .changed()
+                {
+                    zones.eliminated_zone_id = is_eliminated.then_some(zone_id);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Deploy phase:");
+                    let selected = zone
+                        .deploy_phase_id
+                        .and_then(|id| turn_structure.phases.iter().find(|p| p.id == id))
+                        .map_or("Any", |p| p.name.as_str());
+                    egui::ComboBox::from_id_salt(("zone_deploy_phase", zone_id))
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            if ui
+                                .selectable_label(zone.deploy_phase_id.is_none(), "Any")
+                                .clicked()
+                            {
+                                zone.deploy_phase_id = None;
+                            }
+                            for phase in &turn_structure.phases {
+                                if ui
+                                    .selectable_label(
+                                        zone.deploy_phase_id == Some(phase.id),
+                                        &phase.name,
+                                    )
+                                    .clicked()
+                                {
+                                    zone.deploy_phase_id = Some(phase.id);
+                                }
+                            }
+                        });
+                });
+
+                let mut remove_unit = None;
+                for (i, unit) in zone.units.iter().enumerate() {
+                    let type_name = entity_types
+                        .get(unit.entity_type_id)
+                        .map_or("(unknown)", |et| et.name.as_str());
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(type_name)
+                                .small()
+                                .color(BrandTheme::TEXT_PRIMARY),
+                        );
                         if ui
-                            .selectable_label(
-                                editor_state.new_influence_type_idx == Some(idx),
-                                &et.name,
-                            )
+                            .small_button(egui::RichText::new("x").color(BrandTheme::DANGER))
                             .clicked()
                         {
-                            editor_state.new_influence_type_idx = Some(idx);
+                            remove_unit = Some(i);
                         }
-                    }
-                });
-        });
-        ui.horizontal(|ui| {
-            ui.label("Range:");
-            ui.add(
-                egui::DragValue::new(&mut editor_state.new_influence_range)
-                    .range(1..=5)
-                    .speed(0.1),
-            );
-            ui.label("Cost:");
-            ui.add(
-                egui::DragValue::new(&mut editor_state.new_influence_cost)
-                    .range(1..=20)
-                    .speed(0.1),
-            );
-        });
-        if ui.button("Add Rule").clicked()
-            && let Some(idx) = editor_state.new_influence_type_idx
-            && let Some(et) = token_types.get(idx)
-        {
-            influence_rules.rules.push(InfluenceRule {
-                id: TypeId::new(),
-                entity_type_id: et.id,
-                range: editor_state.new_influence_range,
-                cost_modifier: i64::from(editor_state.new_influence_cost),
+                    });
+                }
+                if let Some(idx) = remove_unit {
+                    zone.units.remove(idx);
+                }
+
+                if !token_types.is_empty() {
+                    ui.horizontal(|ui| {
+                        let selected_name = editor_state
+                            .new_zone_unit_type_idx
+                            .and_then(|idx| token_types.get(idx))
+                            .map_or("Select...", |et| et.name.as_str());
+                        egui::ComboBox::from_id_salt(("zone_unit_type", zone_id))
+                            .selected_text(selected_name)
+                            .show_ui(ui, |ui| {
+                                for (idx, et) in token_types.iter().enumerate() {
+                                    if ui
+                                        .selectable_label(
+                                            editor_state.new_zone_unit_type_idx == Some(idx),
+                                            &et.name,
+                                        )
+                                        .clicked()
+                                    {
+                                        editor_state.new_zone_unit_type_idx = Some(idx);
+                                    }
+                                }
+                            });
+                        if ui.button("Stage Unit").clicked()
+                            && let Some(et) = editor_state
+                                .new_zone_unit_type_idx
+                                .and_then(|idx| token_types.get(idx))
+                        {
+                            zone.units.push(ZoneUnit {
+                                entity_type_id: et.id,
+                                properties: et
+                                    .properties
+                                    .iter()
+                                    .map(|pd| {
+                                        (pd.id, PropertyValue::default_for(&pd.property_type))
+                                    })
+                                    .collect(),
+                                owner: None,
+                                id: None,
+                                state: None,
+                            });
+                        }
+                    });
+                }
+
+                if ui
+                    .small_button(egui::RichText::new("Delete Zone").color(BrandTheme::DANGER))
+                    .clicked()
+                {
+                    remove_zone = Some(zone_id);
+                }
             });
-            editor_state.new_influence_type_idx = None;
+    }
+    if let Some(id) = remove_zone {
+        zones.zones.retain(|z| z.id != id);
+        if zones.eliminated_zone_id == Some(id) {
+            zones.eliminated_zone_id = None;
         }
     }
+
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.label("Name:");
+        ui.text_edit_singleline(&mut editor_state.new_zone_name);
+    });
+    let can_add = !editor_state.new_zone_name.trim().is_empty();
+    ui.add_enabled_ui(can_add, |ui| {
+        if ui.button("Add Zone").clicked() {
+            zones
+                .zones
+                .push(OffMapZone::new(editor_state.new_zone_name.trim()));
+            editor_state.new_zone_name.clear();
+        }
+    });
 }
 
-/// Renders the stacking constraint editor.
-pub(crate) fn render_stacking_rule(
+/// Renders the spatial influence rules editor.
+/// Zone-of-control controls for a single influence rule.
+fn render_zone_of_control(ui: &mut egui::Ui, idx: usize, zone: &mut ZoneOfControl) {
+    ui.horizontal(|ui| {
+        ui.add_space(12.0);
+        ui.checkbox(&mut zone.stop_on_enter, "Stop on enter");
+        ui.checkbox(&mut zone.negated_by_friendly, "Friendly units cancel");
+        ui.label("Exit:");
+        ui.add(
+            egui::DragValue::new(&mut zone.exit_cost)
+                .range(0..=20)
+                .speed(0.1),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.add_space(12.0);
+        ui.label("Zone to zone:");
+        let selected = match zone.transition {
+            ZoneTransition::Allowed => "Allowed",
+            ZoneTransition::Forbidden => "Forbidden",
+            ZoneTransition::ExtraCost(_) => "Extra cost",
+        };
+        egui::ComboBox::from_id_salt(("zoc_transition", idx))
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                let is_extra = matches!(zone.transition, ZoneTransition::ExtraCost(_));
+                if ui
+                    .selectable_label(zone.transition == ZoneTransition::Allowed, "Allowed")
+                    .clicked()
+                {
+                    zone.transition = ZoneTransition::Allowed;
+                }
+                if ui
+                    .selectable_label(zone.transition == ZoneTransition::Forbidden, "Forbidden")
+                    .clicked()
+                {
+                    zone.transition = ZoneTransition::Forbidden;
+                }
+                if ui.selectable_label(is_extra, "Extra cost").clicked() && !is_extra {
+                    zone.transition = ZoneTransition::ExtraCost(1);
+                }
+            });
+        if let ZoneTransition::ExtraCost(cost) = &mut zone.transition {
+            ui.add(egui::DragValue::new(cost).range(1..=20).speed(0.1));
+        }
+    });
+}
+
+/// Renders the conditional combat results tables: which table the Mechanics
+/// tab edits and, for a conditional table, the phases, factions and
+/// condition that select it and the combat roles its condition reads.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_combat_tables(
     ui: &mut egui::Ui,
-    stacking_rule: &mut StackingRule,
-    entity_types: &EntityTypeRegistry,
+    default: &CombatResultsTable,
+    tables: &mut CombatTableRegistry,
+    turn_structure: &TurnStructure,
+    factions: &FactionRegistry,
+    constraints: &ConstraintRegistry,
     editor_state: &mut EditorState,
 ) {
     ui.label(
-        egui::RichText::new("Stacking Constraint")
+        egui::RichText::new("Combat Tables")
             .strong()
             .color(BrandTheme::ACCENT_AMBER),
     );
+    ui.label(
+        egui::RichText::new(
+            "Combat uses the first conditional table that applies, otherwise the default.",
+        )
+        .small()
+        .color(BrandTheme::TEXT_SECONDARY),
+    );
     ui.add_space(4.0);
 
+    ui.selectable_value(
+        &mut editor_state.edited_combat_table,
+        None,
+        format!("{} (default)", default.name),
+    );
+    let mut remove = None;
+    for table in &tables.tables {
+        ui.horizontal(|ui| {
+            ui.selectable_value(
+                &mut editor_state.edited_combat_table,
+                Some(table.id),
+                &table.name,
+            );
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .small_button(egui::RichText::new("x").color(BrandTheme::DANGER))
+                    .clicked()
+                {
+                    remove = Some(table.id);
+                }
+            });
+        });
+    }
+    if let Some(id) = remove {
+        tables.tables.retain(|t| t.id != id);
+        if editor_state.edited_combat_table == Some(id) {
+            editor_state.edited_combat_table = None;
+        }
+    }
+
+    // New tables start as a copy of the default table's layout.
     ui.horizontal(|ui| {
-        ui.label("Max units per hex:");
-        let mut max = stacking_rule.max_units as i32;
-        if ui
-            .add(egui::DragValue::new(&mut max).range(0..=20).speed(0.1))
-            .changed()
+        ui.label("Name:");
+        ui.add(
+            egui::TextEdit::singleline(&mut editor_state.new_combat_table_name)
+                .desired_width(120.0),
+        );
+        if ui.button("Add Table").clicked() && !editor_state.new_combat_table_name.trim().is_empty()
         {
-            stacking_rule.max_units = max.max(0) as u32;
-        }
-        if stacking_rule.max_units == 0 {
-            ui.label(
-                egui::RichText::new("(unlimited)")
-                    .small()
-                    .color(BrandTheme::TEXT_SECONDARY),
-            );
+            let table = CombatResultsTable {
+                id: TypeId::new(),
+                name: editor_state.new_combat_table_name.trim().to_string(),
+                selection: CrtSelection::default(),
+                ..default.clone()
+            };
+            editor_state.edited_combat_table = Some(table.id);
+            tables.tables.push(table);
+            editor_state.new_combat_table_name.clear();
         }
     });
 
-    if stacking_rule.is_active() {
-        // Show exempt types.
-        if !stacking_rule.exempt_type_ids.is_empty() {
-            ui.add_space(4.0);
-            ui.label(
-                egui::RichText::new("Exempt types:")
+    let Some(table) = editor_state
+        .edited_combat_table
+        .and_then(|id| tables.get_mut(id))
+    else {
+        return;
+    };
+
+    ui.add_space(4.0);
+    ui.label(
+        egui::RichText::new("Phases (none = every phase)")
+            .small()
+            .color(BrandTheme::TEXT_SECONDARY),
+    );
+    ui.horizontal_wrapped(|ui| {
+        for phase in &turn_structure.phases {
+            render_id_checkbox(ui, &mut table.selection.phases, phase.id, &phase.name);
+        }
+    });
+    ui.label(
+        egui::RichText::new("Attacking factions (none = every faction)")
+            .small()
+            .color(BrandTheme::TEXT_SECONDARY),
+    );
+    ui.horizontal_wrapped(|ui| {
+        for faction in &factions.factions {
+            render_id_checkbox(ui, &mut table.selection.factions, faction.id, &faction.name);
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Condition:");
+        render_condition_combo(
+            ui,
+            "combat_table_condition",
+            &mut table.selection.condition,
+            constraints,
+        );
+    });
+}
+
+/// Renders a picker choosing `condition` among the constraints'
+/// expressions, or "Always". Returns whether the choice changed.
+fn render_condition_combo(
+    ui: &mut egui::Ui,
+    salt: impl std::hash::Hash,
+    condition: &mut Option<ConstraintExpr>,
+    constraints: &ConstraintRegistry,
+) -> bool {
+    let current = condition.as_ref().map_or_else(
+        || "Always".to_string(),
+        |expr| {
+            constraints
+                .constraints
+                .iter()
+                .find(|c| c.expression == *expr)
+                .map_or_else(|| format_constraint_expr(expr), |c| c.name.clone())
+        },
+    );
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(salt)
+        .selected_text(current)
+        .show_ui(ui, |ui| {
+            changed |= ui.selectable_value(condition, None, "Always").changed();
+            for constraint in &constraints.constraints {
+                changed |= ui
+                    .selectable_value(
+                        condition,
+                        Some(constraint.expression.clone()),
+                        &constraint.name,
+                    )
+                    .changed();
+            }
+        });
+    changed
+}
+
+/// Renders the conditions of the combat modifiers: each applies only when
+/// its condition holds over the roles of the combat's CRT.
+pub(crate) fn render_combat_modifier_conditions(
+    ui: &mut egui::Ui,
+    modifiers: &CombatModifierRegistry,
+    constraints: &ConstraintRegistry,
+    actions: &mut Vec<EditorAction>,
+) {
+    if modifiers.modifiers.is_empty() {
+        return;
+    }
+    ui.label(
+        egui::RichText::new("Modifier Conditions")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    ui.label(
+        egui::RichText::new("Conditions read the combat roles set under Combat Strength.")
+            .small()
+            .color(BrandTheme::TEXT_SECONDARY),
+    );
+    ui.add_space(4.0);
+    for modifier in &modifiers.modifiers {
+        ui.horizontal(|ui| {
+            ui.label(&modifier.name);
+            let mut condition = modifier.condition.clone();
+            if render_condition_combo(
+                ui,
+                ("combat_modifier_condition", modifier.id),
+                &mut condition,
+                constraints,
+            ) {
+                actions.push(EditorAction::SetCombatModifierCondition {
+                    id: modifier.id,
+                    condition,
+                });
+            }
+        });
+    }
+}
+
+/// Renders a checkbox that toggles `id`'s membership in `ids`.
+fn render_id_checkbox(ui: &mut egui::Ui, ids: &mut Vec<TypeId>, id: TypeId, label: &str) {
+    let mut checked = ids.contains(&id);
+    if ui.checkbox(&mut checked, label).changed() {
+        if checked {
+            ids.push(id);
+        } else {
+            ids.retain(|i| *i != id);
+        }
+    }
+}
+
+/// Renders the CRT's combat strength model: the combat concept, the attack
+/// and defense properties bound in it, the rounding rule, the terrain
+/// multipliers of defense, and how step losses are allocated.
+pub(crate) fn render_combat_strength(
+    ui: &mut egui::Ui,
+    crt: &mut CombatResultsTable,
+    concepts: &ConceptRegistry,
+    entity_types: &EntityTypeRegistry,
+) {
+    ui.label(
+        egui::RichText::new("Combat Strength")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    ui.add_space(4.0);
+
+    ui.horizontal(|ui| {
+        ui.label("Concept:");
+        let current = crt
+            .combat_concept_id
+            .and_then(|id| concepts.concepts.iter().find(|c| c.id == id))
+            .map_or("None", |c| c.name.as_str());
+        egui::ComboBox::from_id_salt("combat_strength_concept")
+            .selected_text(current)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut crt.combat_concept_id, None, "None");
+                for concept in &concepts.concepts {
+                    ui.selectable_value(
+                        &mut crt.combat_concept_id,
+                        Some(concept.id),
+                        &concept.name,
+                    );
+                }
+            });
+    });
+
+    let Some(concept_id) = crt.combat_concept_id else {
+        ui.label(
+            egui::RichText::new("Strengths are entered by hand until a concept is chosen.")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+        return;
+    };
+
+    // Combat roles bind the combatants for table and modifier conditions.
+    if let Some(concept) = concepts.concepts.iter().find(|c| c.id == concept_id) {
+        let roles = &mut crt.roles;
+        for (label, slot) in [
+            ("Attacker", &mut roles.attacker),
+            ("Defender", &mut roles.defender),
+            ("Terrain", &mut roles.terrain),
+            ("Edge", &mut roles.edge),
+        ] {
+            ui.horizontal(|ui| {
+                ui.label(format!("{label} role:"));
+                let current = slot
+                    .and_then(|id| concept.role_labels.iter().find(|r| r.id == id))
+                    .map_or("None", |r| r.name.as_str());
+                egui::ComboBox::from_id_salt(("combat_strength_role", label))
+                    .selected_text(current)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(slot, None, "None");
+                        for role in &concept.role_labels {
+                            ui.selectable_value(slot, Some(role.id), &role.name);
+                        }
+                    });
+            });
+        }
+    }
+
+    let mut names: Vec<&str> = Vec::new();
+    for binding in concepts
+        .bindings
+        .iter()
+        .filter(|b| b.concept_id == concept_id)
+    {
+        for property in &binding.property_bindings {
+            if !names.contains(&property.concept_local_name.as_str()) {
+                names.push(&property.concept_local_name);
+            }
+        }
+    }
+    let model = &mut crt.strength;
+    ui.horizontal(|ui| {
+        ui.label("Attack:");
+        render_concept_name_combo(
+            ui,
+            "combat_strength_attack",
+            &names,
+            &mut model.attack_property,
+        );
+        ui.label("Defense:");
+        render_concept_name_combo(
+            ui,
+            "combat_strength_defense",
+            &names,
+            &mut model.defense_property,
+        );
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Rounding:");
+        for (rounding, label) in [
+            (StrengthRounding::DefenderFavor, "Defender"),
+            (StrengthRounding::AttackerFavor, "Attacker"),
+            (StrengthRounding::Nearest, "Nearest"),
+            (StrengthRounding::Exact, "Exact"),
+        ] {
+            ui.selectable_value(&mut model.rounding, rounding, label);
+        }
+    });
+
+    let terrains = entity_types.types_by_role(EntityRole::BoardPosition);
+    let mut remove = None;
+    for (i, multiplier) in model.terrain_multipliers.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let current = terrains
+                .iter()
+                .find(|t| t.id == multiplier.terrain_type_id)
+                .map_or("(unknown)", |t| t.name.as_str());
+            egui::ComboBox::from_id_salt(("combat_strength_terrain", i))
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for terrain in &terrains {
+                        ui.selectable_value(
+                            &mut multiplier.terrain_type_id,
+                            terrain.id,
+                            &terrain.name,
+                        );
+                    }
+                });
+            ui.label("\u{00d7}");
+            ui.add(
+                egui::DragValue::new(&mut multiplier.multiplier)
+                    .range(0.0..=10.0)
+                    .speed(0.1),
+            );
+            if ui
+                .small_button(egui::RichText::new("x").color(BrandTheme::DANGER))
+                .clicked()
+            {
+                remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove {
+        model.terrain_multipliers.remove(i);
+    }
+    if let Some(terrain) = terrains.first()
+        && ui.button("Add Terrain Multiplier").clicked()
+    {
+        model.terrain_multipliers.push(TerrainMultiplier {
+            terrain_type_id: terrain.id,
+            multiplier: 2.0,
+        });
+    }
+
+    ui.add_space(4.0);
+    ui.label(
+        egui::RichText::new("Losses")
+            .small()
+            .color(BrandTheme::TEXT_SECONDARY),
+    );
+    let losses = &mut crt.losses;
+    ui.horizontal(|ui| {
+        ui.label("Allocation:");
+        ui.selectable_value(
+            &mut losses.allocation,
+            LossAllocation::AttackerChoice,
+            "Attacker Choice",
+        );
+        ui.selectable_value(
+            &mut losses.allocation,
+            LossAllocation::LargestFirst,
+            "Largest First",
+        );
+        let by_property = matches!(losses.allocation, LossAllocation::ByProperty(_));
+        if ui.selectable_label(by_property, "By Property").clicked() && !by_property {
+            let name = names.first().copied().unwrap_or_default();
+            losses.allocation = LossAllocation::ByProperty(name.to_string());
+        }
+    });
+    if let LossAllocation::ByProperty(selected) = &mut losses.allocation {
+        ui.horizontal(|ui| {
+            ui.label("Highest first:");
+            egui::ComboBox::from_id_salt("combat_loss_property")
+                .selected_text(selected.as_str())
+                .show_ui(ui, |ui| {
+                    for name in &names {
+                        ui.selectable_value(selected, (*name).to_string(), *name);
+                    }
+                });
+        });
+    }
+    ui.horizontal(|ui| {
+        ui.label("Steps:");
+        render_concept_name_combo(ui, "combat_loss_steps", &names, &mut losses.steps_property);
+    });
+}
+
+/// Renders a picker of concept-local property names with a "None" entry.
+fn render_concept_name_combo(
+    ui: &mut egui::Ui,
+    salt: &str,
+    names: &[&str],
+    selected: &mut Option<String>,
+) {
+    egui::ComboBox::from_id_salt(salt)
+        .selected_text(selected.as_deref().unwrap_or("None"))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, "None");
+            for name in names {
+                ui.selectable_value(selected, Some((*name).to_string()), *name);
+            }
+        });
+}
+
+pub(crate) fn render_influence_rules(
+    ui: &mut egui::Ui,
+    influence_rules: &mut InfluenceRuleRegistry,
+    entity_types: &EntityTypeRegistry,
+    editor_state: &mut EditorState,
+) {
+    ui.label(
+        egui::RichText::new("Spatial Influence Rules")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    ui.add_space(4.0);
+
+    let mut remove_idx = None;
+    for (i, rule) in influence_rules.rules.iter_mut().enumerate() {
+        let type_name = entity_types
+            .get(rule.entity_type_id)
+            .map_or("Unknown", |et| et.name.as_str());
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(type_name)
+                    .small()
+                    .color(BrandTheme::TEXT_PRIMARY),
+            );
+            ui.label(
+                egui::RichText::new(format!("range {} +{} MP", rule.range, rule.cost_modifier))
+                    .small()
+                    .color(BrandTheme::TEXT_SECONDARY),
+            );
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .small_button(egui::RichText::new("x").color(BrandTheme::DANGER))
+                    .clicked()
+                {
+                    remove_idx = Some(i);
+                }
+            });
+        });
+        render_zone_of_control(ui, i, &mut rule.zone);
+        ui.horizontal(|ui| {
+            ui.add_space(12.0);
+            ui.checkbox(&mut rule.enemy_only, "Enemy units only");
+        });
+    }
+    if let Some(idx) = remove_idx {
+        influence_rules.rules.remove(idx);
+    }
+
+    // Add rule form.
+    ui.add_space(4.0);
+    let token_types: Vec<_> = entity_types
+        .types
+        .iter()
+        .filter(|et| et.role == EntityRole::Token)
+        .collect();
+
+    if token_types.is_empty() {
+        ui.label(
+            egui::RichText::new("No token entity types defined")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+    } else {
+        ui.horizontal(|ui| {
+            ui.label("Type:");
+            let selected_name = editor_state
+                .new_influence_type_idx
+                .and_then(|idx| token_types.get(idx))
+                .map_or("Select...", |et| et.name.as_str());
+            egui::ComboBox::from_id_salt("influence_type_picker")
+                .selected_text(selected_name)
+                .show_ui(ui, |ui| {
+                    for (idx, et) in token_types.iter().enumerate() {
+                        if ui
+                            .selectable_label(
+                                editor_state.new_influence_type_idx == Some(idx),
+                                &et.name,
+                            )
+                            .clicked()
+                        {
+                            editor_state.new_influence_type_idx = Some(idx);
+                        }
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Range:");
+            ui.add(
+                egui::DragValue::new(&mut editor_state.new_influence_range)
+                    .range(1..=5)
+                    .speed(0.1),
+            );
+            ui.label("Cost:");
+            ui.add(
+                egui::DragValue::new(&mut editor_state.new_influence_cost)
+                    .range(1..=20)
+                    .speed(0.1),
+            );
+        });
+        if ui.button("Add Rule").clicked()
+            && let Some(idx) = editor_state.new_influence_type_idx
+            && let Some(et) = token_types.get(idx)
+        {
+            influence_rules.rules.push(InfluenceRule {
+                id: TypeId::new(),
+                entity_type_id: et.id,
+                range: editor_state.new_influence_range,
+                cost_modifier: i64::from(editor_state.new_influence_cost),
+                zone: ZoneOfControl::default(),
+                enemy_only: false,
+            });
+            editor_state.new_influence_type_idx = None;
+        }
+    }
+}
+
+/// Renders the stacking constraint editor.
+pub(crate) fn render_stacking_rule(
+    ui: &mut egui::Ui,
+    stacking_rule: &mut StackingRule,
+    entity_types: &EntityTypeRegistry,
+    factions: &FactionRegistry,
+    editor_state: &mut EditorState,
+) {
+    ui.label(
+        egui::RichText::new("Stacking Constraint")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    ui.add_space(4.0);
+
+    ui.horizontal(|ui| {
+        ui.label("Max stacking points per hex:");
+        let mut max = stacking_rule.max_units as i32;
+        if ui
+            .add(egui::DragValue::new(&mut max).range(0..=20).speed(0.1))
+            .changed()
+        {
+            stacking_rule.max_units = max.max(0) as u32;
+        }
+        if stacking_rule.max_units == 0 {
+            ui.label(
+                egui::RichText::new("(unlimited)")
+                    .small()
+                    .color(BrandTheme::TEXT_SECONDARY),
+            );
+        }
+    });
+    ui.checkbox(
+        &mut stacking_rule.no_mixed_factions,
+        "No mixed-faction stacks",
+    );
+
+    // Stacking points property: Int properties on Token types.
+    let points_properties: Vec<(TypeId, String)> = entity_types
+        .types
+        .iter()
+        .filter(|et| et.role == EntityRole::Token)
+        .flat_map(|et| {
+            et.properties
+                .iter()
+                .filter(|p| matches!(p.property_type, PropertyType::Int))
+                .map(move |p| (p.id, format!("{}.{}", et.name, p.name)))
+        })
+        .collect();
+    ui.horizontal(|ui| {
+        ui.label("Points from:");
+        let selected = stacking_rule
+            .points_property_id
+            .and_then(|id| points_properties.iter().find(|(pid, _)| *pid == id))
+            .map_or("1 per unit", |(_, name)| name.as_str());
+        egui::ComboBox::from_id_salt("stacking_points_picker")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut stacking_rule.points_property_id, None, "1 per unit");
+                for (id, name) in &points_properties {
+                    ui.selectable_value(&mut stacking_rule.points_property_id, Some(*id), name);
+                }
+            });
+    });
+
+    // Per-terrain limits.
+    ui.add_space(4.0);
+    ui.label(
+        egui::RichText::new("Terrain limits:")
+            .small()
+            .color(BrandTheme::TEXT_SECONDARY),
+    );
+    let mut terrain_rows: Vec<(TypeId, String)> = stacking_rule
+        .terrain_limits
+        .keys()
+        .map(|id| {
+            let name = entity_types
+                .get(*id)
+                .map_or("Unknown", |et| et.name.as_str());
+            (*id, name.to_string())
+        })
+        .collect();
+    terrain_rows.sort_by(|a, b| a.1.cmp(&b.1));
+    let mut remove_terrain = None;
+    for (id, name) in &terrain_rows {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(name).small());
+            if let Some(limit) = stacking_rule.terrain_limits.get_mut(id) {
+                ui.add(egui::DragValue::new(limit).range(0..=20).speed(0.1));
+            }
+            if ui
+                .small_button(egui::RichText::new("x").color(BrandTheme::DANGER))
+                .clicked()
+            {
+                remove_terrain = Some(*id);
+            }
+        });
+    }
+    if let Some(id) = remove_terrain {
+        stacking_rule.terrain_limits.remove(&id);
+    }
+    let terrain_types: Vec<_> = entity_types
+        .types
+        .iter()
+        .filter(|et| {
+            et.role == EntityRole::BoardPosition
+                && !stacking_rule.terrain_limits.contains_key(&et.id)
+        })
+        .collect();
+    if !terrain_types.is_empty() {
+        ui.horizontal(|ui| {
+            let selected_name = editor_state
+                .new_stacking_terrain_idx
+                .and_then(|idx| terrain_types.get(idx))
+                .map_or("Select...", |et| et.name.as_str());
+            egui::ComboBox::from_id_salt("stacking_terrain_picker")
+                .selected_text(selected_name)
+                .show_ui(ui, |ui| {
+                    for (idx, et) in terrain_types.iter().enumerate() {
+                        if ui
+                            .selectable_label(
+                                editor_state.new_stacking_terrain_idx == Some(idx),
+                                &et.name,
+                            )
+                            .clicked()
+                        {
+                            editor_state.new_stacking_terrain_idx = Some(idx);
+                        }
+                    }
+                });
+            if ui.button("Add Terrain Limit").clicked()
+                && let Some(idx) = editor_state.new_stacking_terrain_idx
+                && let Some(et) = terrain_types.get(idx)
+            {
+                stacking_rule.terrain_limits.insert(et.id, 1);
+                editor_state.new_stacking_terrain_idx = None;
+            }
+        });
+    }
+
+    // Per-faction limits.
+    if !factions.factions.is_empty() {
+        ui.add_space(4.0);
+        ui.label(
+            egui::RichText::new("Faction limits:")
+                .small()
+                .color(BrandTheme::TEXT_SECONDARY),
+        );
+        let mut remove_faction = None;
+        for faction in factions.in_player_order() {
+            let Some(limit) = stacking_rule.faction_limits.get_mut(&faction.id) else {
+                continue;
+            };
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(&faction.name).small());
+                ui.add(egui::DragValue::new(limit).range(0..=20).speed(0.1));
+                if ui
+                    .small_button(egui::RichText::new("x").color(BrandTheme::DANGER))
+                    .clicked()
+                {
+                    remove_faction = Some(faction.id);
+                }
+            });
+        }
+        if let Some(id) = remove_faction {
+            stacking_rule.faction_limits.remove(&id);
+        }
+        let unlimited: Vec<_> = factions
+            .in_player_order()
+            .into_iter()
+            .filter(|f| !stacking_rule.faction_limits.contains_key(&f.id))
+            .collect();
+        if !unlimited.is_empty() {
+            ui.horizontal(|ui| {
+                let selected_name = editor_state
+                    .new_stacking_faction_idx
+                    .and_then(|idx| unlimited.get(idx))
+                    .map_or("Select...", |f| f.name.as_str());
+                egui::ComboBox::from_id_salt("stacking_faction_picker")
+                    .selected_text(selected_name)
+                    .show_ui(ui, |ui| {
+                        for (idx, faction) in unlimited.iter().enumerate() {
+                            if ui
+                                .selectable_label(
+                                    editor_state.new_stacking_faction_idx == Some(idx),
+                                    &faction.name,
+                                )
+                                .clicked()
+                            {
+                                editor_state.new_stacking_faction_idx = Some(idx);
+                            }
+                        }
+                    });
+                if ui.button("Add Faction Limit").clicked()
+                    && let Some(idx) = editor_state.new_stacking_faction_idx
+                    && let Some(faction) = unlimited.get(idx)
+                {
+                    stacking_rule.faction_limits.insert(faction.id, 1);
+                    editor_state.new_stacking_faction_idx = None;
+                }
+            });
+        }
+    }
+
+    if stacking_rule.is_active() {
+        // Show exempt types.
+        if !stacking_rule.exempt_type_ids.is_empty() {
+            ui.add_space(4.0);
+            ui.label(
+                egui::RichText::new("Exempt types:")
                     .small()
                     .color(BrandTheme::TEXT_SECONDARY),
             );
@@ -1055,6 +2017,7 @@ pub(crate) fn render_inspector(
     registry: &EntityTypeRegistry,
     enum_registry: &EnumRegistry,
     struct_registry: &StructRegistry,
+    units: &[(UnitId, String)],
 ) {
     egui::CollapsingHeader::new(
         egui::RichText::new("Tile Inspector")
@@ -1115,6 +2078,7 @@ pub(crate) fn render_inspector(
                     enum_registry,
                     struct_registry,
                     registry,
+                    units,
                     0,
                 );
             });
@@ -1130,6 +2094,7 @@ pub(crate) fn render_unit_inspector(
     registry: &EntityTypeRegistry,
     enum_registry: &EnumRegistry,
     struct_registry: &StructRegistry,
+    units: &[(UnitId, String)],
     actions: &mut Vec<EditorAction>,
 ) {
     egui::CollapsingHeader::new(
@@ -1176,6 +2141,7 @@ pub(crate) fn render_unit_inspector(
                         enum_registry,
                         struct_registry,
                         registry,
+                        units,
                         0,
                     );
                 });
@@ -1203,6 +2169,7 @@ pub(crate) fn render_property_value_editor(
     enum_registry: &EnumRegistry,
     struct_registry: &StructRegistry,
     entity_registry: &EntityTypeRegistry,
+    units: &[(UnitId, String)],
     depth: usize,
 ) {
     match value {
@@ -1277,6 +2244,30 @@ pub(crate) fn render_property_value_editor(
                     }
                 });
         }
+        PropertyValue::UnitRef(selected) => {
+            let selected_name = match *selected {
+                None => "(none)".to_string(),
+                Some(id) => units
+                    .iter()
+                    .find(|(uid, _)| *uid == id)
+                    .map_or_else(|| format!("Missing #{}", id.short()), |(_, n)| n.clone()),
+            };
+            egui::ComboBox::from_id_salt(format!("uref_{depth}"))
+                .selected_text(&selected_name)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(selected.is_none(), "(none)").clicked() {
+                        *selected = None;
+                    }
+                    for (uid, label) in units {
+                        if ui
+                            .selectable_label(*selected == Some(*uid), label)
+                            .clicked()
+                        {
+                            *selected = Some(*uid);
+                        }
+                    }
+                });
+        }
         PropertyValue::List(items) => {
             if depth >= 3 {
                 ui.label(
@@ -1305,6 +2296,7 @@ pub(crate) fn render_property_value_editor(
                                 enum_registry,
                                 struct_registry,
                                 entity_registry,
+                                units,
                                 depth + 1,
                             );
                             if ui.small_button("x").clicked() {
@@ -1356,6 +2348,7 @@ pub(crate) fn render_property_value_editor(
                                     enum_registry,
                                     struct_registry,
                                     entity_registry,
+                                    units,
                                     depth + 1,
                                 );
                             });
@@ -1414,33 +2407,545 @@ pub(crate) fn render_property_value_editor(
                                 enum_registry,
                                 struct_registry,
                                 entity_registry,
+                                units,
                                 depth + 1,
                             );
                         });
                     }
-                } else {
-                    ui.label(
-                        egui::RichText::new("(unknown struct)")
-                            .small()
-                            .color(BrandTheme::TEXT_SECONDARY),
-                    );
+                } else {
+                    ui.label(
+                        egui::RichText::new("(unknown struct)")
+                            .small()
+                            .color(BrandTheme::TEXT_SECONDARY),
+                    );
+                }
+            });
+        }
+        PropertyValue::IntRange(v) => {
+            if let PropertyType::IntRange { min, max } = prop_type {
+                ui.add(egui::DragValue::new(v).range(*min..=*max));
+            } else {
+                ui.add(egui::DragValue::new(v));
+            }
+        }
+        PropertyValue::FloatRange(v) => {
+            if let PropertyType::FloatRange { min, max } = prop_type {
+                ui.add(egui::DragValue::new(v).range(*min..=*max).speed(0.1));
+            } else {
+                ui.add(egui::DragValue::new(v).speed(0.1));
+            }
+        }
+    }
+}
+
+/// Renders the faction editor: name, colour and player order of each
+/// faction, with an add form.
+pub(crate) fn render_factions(
+    ui: &mut egui::Ui,
+    factions: &mut FactionRegistry,
+    editor_state: &mut EditorState,
+) {
+    ui.heading("Factions");
+    ui.separator();
+
+    let mut remove_idx = None;
+    for (i, faction) in factions.factions.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let mut color = bevy_color_to_egui(faction.color);
+            if egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed()
+            {
+                faction.color = egui_color_to_bevy(color);
+            }
+            ui.add(egui::TextEdit::singleline(&mut faction.name).desired_width(100.0));
+            ui.label("Order:");
+            ui.add(egui::DragValue::new(&mut faction.player_order).speed(0.1));
+            if ui.small_button("✕").clicked() {
+                remove_idx = Some(i);
+            }
+        });
+    }
+    if let Some(idx) = remove_idx {
+        factions.factions.remove(idx);
+    }
+
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(&mut editor_state.new_faction_name);
+        let can_add = !editor_state.new_faction_name.trim().is_empty();
+        if ui
+            .add_enabled(can_add, egui::Button::new("Add Faction"))
+            .clicked()
+        {
+            let player_order = factions
+                .factions
+                .iter()
+                .map(|f| f.player_order + 1)
+                .max()
+                .unwrap_or(0);
+            factions.factions.push(Faction {
+                id: TypeId::new(),
+                name: editor_state.new_faction_name.trim().to_string(),
+                color: bevy::color::Color::WHITE,
+                player_order,
+            });
+            editor_state.new_faction_name.clear();
+        }
+    });
+}
+
+/// Renders a faction picker in player order, with an "Unowned" entry.
+pub(crate) fn render_faction_combo(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    selected: &mut Option<TypeId>,
+    factions: &FactionRegistry,
+) {
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(factions.name_of(*selected))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, "Unowned");
+            for faction in factions.in_player_order() {
+                ui.selectable_value(selected, Some(faction.id), &faction.name);
+            }
+        });
+}
+
+/// Renders the owner picker for the selected unit.
+pub(crate) fn render_unit_owner(
+    ui: &mut egui::Ui,
+    owner: &mut UnitOwner,
+    factions: &FactionRegistry,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Owner:");
+        render_faction_combo(ui, "unit_owner_picker", &mut owner.faction_id, factions);
+    });
+}
+
+/// Display label of a state trigger kind.
+fn state_trigger_label(trigger: &StateTrigger) -> &'static str {
+    match trigger {
+        StateTrigger::StepLoss => "Step loss",
+        StateTrigger::Retreat => "Retreat",
+        StateTrigger::Eliminated => "Eliminated",
+        StateTrigger::PhaseStart(_) => "Phase start",
+        StateTrigger::Constraint { .. } => "Constraint",
+        StateTrigger::Reachability { .. } => "Reachability",
+        StateTrigger::Manual => "Manual",
+    }
+}
+
+/// Renders the state machine editor: per entity type, its states with
+/// property overrides, colour and badge, and the transitions between them.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_state_machines(
+    ui: &mut egui::Ui,
+    state_machines: &mut StateMachineRegistry,
+    entity_types: &EntityTypeRegistry,
+    enum_registry: &EnumRegistry,
+    struct_registry: &StructRegistry,
+    turn_structure: &TurnStructure,
+    constraints: &ConstraintRegistry,
+    reachability: &ReachabilityRuleRegistry,
+    editor_state: &mut EditorState,
+) {
+    ui.heading("State Machines");
+    ui.separator();
+
+    let mut remove_machine = None;
+    for machine in &mut state_machines.machines {
+        let machine_id = machine.id;
+        let Some(entity_type) = entity_types.get(machine.entity_type_id) else {
+            continue;
+        };
+        egui::CollapsingHeader::new(format!("{} ({})", machine.name, entity_type.name))
+            .id_salt(("state_machine", machine_id))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut machine.name);
+                });
+
+                // -- States --
+                ui.label(egui::RichText::new("States").small());
+                let initial = machine.initial().map(|s| s.id);
+                let mut remove_state = None;
+                for state in &mut machine.states {
+                    let state_id = state.id;
+                    ui.horizontal(|ui| {
+                        if ui
+                            .radio(initial == Some(state_id), "")
+                            .on_hover_text("Initial state")
+                            .clicked()
+                        {
+                            machine.initial_state = Some(state_id);
+                        }
+                        ui.add(egui::TextEdit::singleline(&mut state.name).desired_width(90.0));
+                        ui.label("Badge:");
+                        ui.add(egui::TextEdit::singleline(&mut state.badge).desired_width(30.0));
+                        let mut tinted = state.color.is_some();
+                        if ui.checkbox(&mut tinted, "Color").changed() {
+                            state.color = tinted.then_some(entity_type.color);
+                        }
+                        if let Some(color) = state.color.as_mut() {
+                            let mut c32 = bevy_color_to_egui(*color);
+                            if egui::color_picker::color_edit_button_srgba(
+                                ui,
+                                &mut c32,
+                                egui::color_picker::Alpha::Opaque,
+                            )
+                            .changed()
+                            {
+                                *color = egui_color_to_bevy(c32);
+                            }
+                        }
+                        if ui.small_button("✕").clicked() {
+                            remove_state = Some(state_id);
+                        }
+                    });
+                    for prop_def in &entity_type.properties {
+                        ui.horizontal(|ui| {
+                            ui.add_space(20.0);
+                            let mut overridden =
+                                state.property_overrides.contains_key(&prop_def.id);
+                            if ui.checkbox(&mut overridden, &prop_def.name).changed() {
+                                if overridden {
+                                    state
+                                        .property_overrides
+                                        .insert(prop_def.id, prop_def.default_value.clone());
+                                } else {
+                                    state.property_overrides.remove(&prop_def.id);
+                                }
+                            }
+                            if let Some(value) = state.property_overrides.get_mut(&prop_def.id) {
+                                ui.push_id(("state_override", state_id, prop_def.id), |ui| {
+                                    render_property_value_editor(
+                                        ui,
+                                        value,
+                                        &prop_def.property_type,
+                                        enum_registry,
+                                        struct_registry,
+                                        entity_types,
+                                        &[],
+                                        0,
+                                    );
+                                });
+                            }
+                        });
+                    }
+                }
+                if let Some(id) = remove_state {
+                    machine.states.retain(|s| s.id != id);
+                    machine
+                        .transitions
+                        .retain(|t| t.to != id && t.from != Some(id));
+                    if machine.initial_state == Some(id) {
+                        machine.initial_state = None;
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut editor_state.new_state_name);
+                    let can_add = !editor_state.new_state_name.trim().is_empty();
+                    if ui
+                        .add_enabled(can_add, egui::Button::new("Add State"))
+                        .clicked()
+                    {
+                        machine.states.push(StateDefinition {
+                            id: TypeId::new(),
+                            name: editor_state.new_state_name.trim().to_string(),
+                            property_overrides: HashMap::new(),
+                            color: None,
+                            badge: String::new(),
+                        });
+                        editor_state.new_state_name.clear();
+                    }
+                });
+
+                // -- Transitions --
+                ui.add_space(4.0);
+                ui.label(egui::RichText::new("Transitions").small());
+                let states: Vec<(TypeId, String)> = machine
+                    .states
+                    .iter()
+                    .map(|s| (s.id, s.name.clone()))
+                    .collect();
+                let state_name = |id: Option<TypeId>| {
+                    states
+                        .iter()
+                        .find(|(sid, _)| Some(*sid) == id)
+                        .map_or("Any", |(_, name)| name.as_str())
+                        .to_string()
+                };
+                let mut remove_transition = None;
+                for transition in &mut machine.transitions {
+                    let transition_id = transition.id;
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut transition.name).desired_width(80.0),
+                        );
+                        egui::ComboBox::from_id_salt(("transition_from", transition_id))
+                            .selected_text(state_name(transition.from))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut transition.from, None, "Any");
+                                for (id, name) in &states {
+                                    ui.selectable_value(&mut transition.from, Some(*id), name);
+                                }
+                            });
+                        ui.label("→");
+                        egui::ComboBox::from_id_salt(("transition_to", transition_id))
+                            .selected_text(state_name(Some(transition.to)))
+                            .show_ui(ui, |ui| {
+                                for (id, name) in &states {
+                                    ui.selectable_value(&mut transition.to, *id, name);
+                                }
+                            });
+                        ui.label("on");
+                        render_state_trigger(
+                            ui,
+                            transition_id,
+                            &mut transition.trigger,
+                            turn_structure,
+                            constraints,
+                            reachability,
+                        );
+                        if ui.small_button("✕").clicked() {
+                            remove_transition = Some(transition_id);
+                        }
+                    });
+                }
+                if let Some(id) = remove_transition {
+                    machine.transitions.retain(|t| t.id != id);
+                }
+                if let Some((first, _)) = states.first()
+                    && ui.small_button("+ Transition").clicked()
+                {
+                    machine.transitions.push(StateTransition {
+                        id: TypeId::new(),
+                        name: "Transition".to_string(),
+                        from: None,
+                        to: *first,
+                        trigger: StateTrigger::Manual,
+                    });
+                }
+
+                ui.add_space(4.0);
+                if ui
+                    .small_button(egui::RichText::new("Delete Machine").color(BrandTheme::DANGER))
+                    .clicked()
+                {
+                    remove_machine = Some(machine_id);
+                }
+            });
+    }
+    if let Some(id) = remove_machine {
+        state_machines.machines.retain(|m| m.id != id);
+    }
+
+    // -- Add machine form: one machine per entity type --
+    let free_types: Vec<_> = entity_types
+        .types
+        .iter()
+        .filter(|et| state_machines.for_type(et.id).is_none())
+        .collect();
+    if free_types.is_empty() {
+        return;
+    }
+    ui.horizontal(|ui| {
+        let selected_name = editor_state
+            .new_state_machine_type_idx
+            .and_then(|idx| free_types.get(idx))
+            .map_or("Select...", |et| et.name.as_str());
+        egui::ComboBox::from_id_salt("state_machine_type")
+            .selected_text(selected_name)
+            .show_ui(ui, |ui| {
+                for (idx, et) in free_types.iter().enumerate() {
+                    ui.selectable_value(
+                        &mut editor_state.new_state_machine_type_idx,
+                        Some(idx),
+                        &et.name,
+                    );
+                }
+            });
+        if ui.button("Add State Machine").clicked()
+            && let Some(et) = editor_state
+                .new_state_machine_type_idx
+                .take()
+                .and_then(|idx| free_types.get(idx))
+        {
+            state_machines.machines.push(StateMachine {
+                id: TypeId::new(),
+                name: format!("{} states", et.name),
+                entity_type_id: et.id,
+                states: Vec::new(),
+                initial_state: None,
+                transitions: Vec::new(),
+            });
+        }
+    });
+}
+
+/// Renders the trigger picker of one state transition.
+fn render_state_trigger(
+    ui: &mut egui::Ui,
+    transition_id: TypeId,
+    trigger: &mut StateTrigger,
+    turn_structure: &TurnStructure,
+    constraints: &ConstraintRegistry,
+    reachability: &ReachabilityRuleRegistry,
+) {
+    let first_phase = turn_structure.phases.first().map(|p| p.id);
+    let first_constraint = constraints.constraints.first().map(|c| c.id);
+    let first_rule = reachability.rules.first().map(|r| r.id);
+    egui::ComboBox::from_id_salt(("transition_trigger", transition_id))
+        .selected_text(state_trigger_label(trigger))
+        .show_ui(ui, |ui| {
+            for option in [
+                StateTrigger::StepLoss,
+                StateTrigger::Retreat,
+                StateTrigger::Eliminated,
+                StateTrigger::Manual,
+            ] {
+                ui.selectable_value(trigger, option, state_trigger_label(&option));
+            }
+            if let Some(phase_id) = first_phase
+                && ui
+                    .selectable_label(
+                        matches!(trigger, StateTrigger::PhaseStart(_)),
+                        "Phase start",
+                    )
+                    .clicked()
+                && !matches!(trigger, StateTrigger::PhaseStart(_))
+            {
+                *trigger = StateTrigger::PhaseStart(phase_id);
+            }
+            if let Some(constraint_id) = first_constraint
+                && ui
+                    .selectable_label(
+                        matches!(trigger, StateTrigger::Constraint { .. }),
+                        "Constraint",
+                    )
+                    .clicked()
+                && !matches!(trigger, StateTrigger::Constraint { .. })
+            {
+                *trigger = StateTrigger::Constraint {
+                    constraint_id,
+                    satisfied: true,
+                };
+            }
+            if let Some(rule_id) = first_rule
+                && ui
+                    .selectable_label(
+                        matches!(trigger, StateTrigger::Reachability { .. }),
+                        "Reachability",
+                    )
+                    .clicked()
+                && !matches!(trigger, StateTrigger::Reachability { .. })
+            {
+                *trigger = StateTrigger::Reachability {
+                    rule_id,
+                    in_reach: false,
+                };
+            }
+        });
+    match trigger {
+        StateTrigger::PhaseStart(phase_id) => {
+            let selected = turn_structure
+                .phases
+                .iter()
+                .find(|p| p.id == *phase_id)
+                .map_or("(missing)", |p| p.name.as_str());
+            egui::ComboBox::from_id_salt(("transition_phase", transition_id))
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for phase in &turn_structure.phases {
+                        ui.selectable_value(phase_id, phase.id, &phase.name);
+                    }
+                });
+        }
+        StateTrigger::Constraint {
+            constraint_id,
+            satisfied,
+        } => {
+            let selected = constraints
+                .constraints
+                .iter()
+                .find(|c| c.id == *constraint_id)
+                .map_or("(missing)", |c| c.name.as_str());
+            egui::ComboBox::from_id_salt(("transition_constraint", transition_id))
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for constraint in &constraints.constraints {
+                        ui.selectable_value(constraint_id, constraint.id, &constraint.name);
+                    }
+                });
+            ui.checkbox(satisfied, "holds");
+        }
+        StateTrigger::Reachability { rule_id, in_reach } => {
+            egui::ComboBox::from_id_salt(("transition_reachability", transition_id))
+                .selected_text(reachability.rule_name(Some(*rule_id)))
+                .show_ui(ui, |ui| {
+                    for rule in &reachability.rules {
+                        ui.selectable_value(rule_id, rule.id, &rule.name);
+                    }
+                });
+            ui.checkbox(in_reach, "in reach");
+        }
+        _ => {}
+    }
+}
+
+/// Renders the state of the inspected tile or unit: a picker over its
+/// machine's states and a button per manual transition out of the current
+/// one. Changes are pushed as `SetEntityState` actions.
+pub(crate) fn render_entity_state(
+    ui: &mut egui::Ui,
+    entity: Entity,
+    state_id: TypeId,
+    machine: &StateMachine,
+    actions: &mut Vec<EditorAction>,
+) {
+    ui.horizontal(|ui| {
+        ui.label("State:");
+        let current = machine
+            .state(state_id)
+            .map_or("(none)", |s| s.name.as_str());
+        egui::ComboBox::from_id_salt(("entity_state", entity))
+            .selected_text(current)
+            .show_ui(ui, |ui| {
+                for state in &machine.states {
+                    if ui
+                        .selectable_label(state.id == state_id, &state.name)
+                        .clicked()
+                        && state.id != state_id
+                    {
+                        actions.push(EditorAction::SetEntityState {
+                            entity,
+                            state_id: state.id,
+                        });
+                    }
                 }
             });
-        }
-        PropertyValue::IntRange(v) => {
-            if let PropertyType::IntRange { min, max } = prop_type {
-                ui.add(egui::DragValue::new(v).range(*min..=*max));
-            } else {
-                ui.add(egui::DragValue::new(v));
-            }
-        }
-        PropertyValue::FloatRange(v) => {
-            if let PropertyType::FloatRange { min, max } = prop_type {
-                ui.add(egui::DragValue::new(v).range(*min..=*max).speed(0.1));
-            } else {
-                ui.add(egui::DragValue::new(v).speed(0.1));
+    });
+    let manual: Vec<_> = machine
+        .transitions_from(state_id)
+        .filter(|t| t.trigger == StateTrigger::Manual)
+        .collect();
+    if !manual.is_empty() {
+        ui.horizontal_wrapped(|ui| {
+            for transition in manual {
+                if ui.small_button(&transition.name).clicked() {
+                    actions.push(EditorAction::SetEntityState {
+                        entity,
+                        state_id: transition.to,
+                    });
+                }
             }
-        }
+        });
     }
 }
 
@@ -1449,6 +2954,7 @@ pub(crate) fn render_accumulators(
     ui: &mut egui::Ui,
     accumulator_registry: &mut AccumulatorRegistry,
     victory_conditions: &mut VictoryConditionRegistry,
+    factions: &FactionRegistry,
     editor_state: &mut EditorState,
     actions: &mut Vec<EditorAction>,
 ) {
@@ -1460,7 +2966,9 @@ pub(crate) fn render_accumulators(
     for (i, acc) in accumulator_registry.accumulators.iter().enumerate() {
         ui.horizontal(|ui| {
             ui.label(&acc.id);
-            if let Some(ref faction) = acc.faction {
+            if let Some(owner) = acc.owner(factions) {
+                ui.label(format!("({})", factions.name_of(Some(owner))));
+            } else if let Some(ref faction) = acc.faction {
                 ui.label(format!("({faction})"));
             }
             ui.label(format!("Value: {}", acc.value));
@@ -1530,7 +3038,16 @@ pub(crate) fn render_accumulators(
             ui.end_row();
 
             ui.label("Faction:");
-            ui.text_edit_singleline(&mut editor_state.new_accumulator_faction);
+            if factions.factions.is_empty() {
+                ui.text_edit_singleline(&mut editor_state.new_accumulator_faction);
+            } else {
+                render_faction_combo(
+                    ui,
+                    "accumulator_faction_picker",
+                    &mut editor_state.new_accumulator_faction_id,
+                    factions,
+                );
+            }
             ui.end_row();
         });
 
@@ -1545,6 +3062,7 @@ pub(crate) fn render_accumulators(
             actions.push(EditorAction::AddAccumulator {
                 id: editor_state.new_accumulator_id.clone(),
                 faction,
+                faction_id: editor_state.new_accumulator_faction_id.take(),
             });
             editor_state.new_accumulator_id.clear();
             editor_state.new_accumulator_faction.clear();
@@ -1646,3 +3164,519 @@ pub(crate) fn render_accumulators(
         }
     });
 }
+
+// ---------------------------------------------------------------------------
+// Reachability
+// ---------------------------------------------------------------------------
+
+/// Renders the reachability rule editor: per rule, the traced unit types,
+/// sources, cost limit, blockers and the phases it runs at, with buttons to
+/// trace it now and to show it as the board overlay.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_reachability_rules(
+    ui: &mut egui::Ui,
+    reachability: &mut ReachabilityRuleRegistry,
+    overlay: &mut ReachabilityOverlay,
+    entity_types: &EntityTypeRegistry,
+    turn_structure: &TurnStructure,
+    selected_hex: Option<HexPosition>,
+    editor_state: &mut EditorState,
+    actions: &mut Vec<EditorAction>,
+) {
+    ui.label(
+        egui::RichText::new("Reachability")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    ui.add_space(4.0);
+
+    let tokens = entity_types.types_by_role(EntityRole::Token);
+    let mut remove_rule = None;
+    for rule in &mut reachability.rules {
+        let rule_id = rule.id;
+        egui::CollapsingHeader::new(&rule.name)
+            .id_salt(("reachability_rule", rule_id))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut rule.name);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Max cost:");
+                    ui.add(
+                        egui::DragValue::new(&mut rule.max_cost)
+                            .range(0..=99)
+                            .speed(0.1),
+                    );
+                });
+
+                ui.label(egui::RichText::new("Traced units").small());
+                render_type_toggles(ui, ("traced", rule_id), &tokens, &mut rule.traced_types);
+
+                ui.label(egui::RichText::new("Sources").small());
+                render_reachability_sources(
+                    ui,
+                    rule_id,
+                    &mut rule.sources,
+                    entity_types,
+                    selected_hex,
+                );
+
+                ui.label(egui::RichText::new("Blocked by").small());
+                render_reachability_blockers(ui, rule_id, &mut rule.blockers, entity_types);
+
+                ui.label(egui::RichText::new("Traced at the start of").small());
+                ui.horizontal_wrapped(|ui| {
+                    for phase in &turn_structure.phases {
+                        let mut on = rule.phases.contains(&phase.id);
+                        if ui.checkbox(&mut on, &phase.name).changed() {
+                            toggle_id(&mut rule.phases, phase.id, on);
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("Trace Now").clicked() {
+                        actions.push(EditorAction::TraceReachability {
+                            rule_id: Some(rule_id),
+                        });
+                    }
+                    let shown = overlay.rule_id == Some(rule_id);
+                    if ui.selectable_label(shown, "Overlay").clicked() {
+                        overlay.rule_id = (!shown).then_some(rule_id);
+                    }
+                    if ui
+                        .button(egui::RichText::new("Delete Rule").color(BrandTheme::DANGER))
+                        .clicked()
+                    {
+                        remove_rule = Some(rule_id);
+                    }
+                });
+            });
+    }
+    if let Some(id) = remove_rule {
+        reachability.rules.retain(|r| r.id != id);
+        if overlay.rule_id == Some(id) {
+            overlay.rule_id = None;
+        }
+    }
+
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(&mut editor_state.new_reachability_name);
+        let can_add = !editor_state.new_reachability_name.trim().is_empty();
+        if ui
+            .add_enabled(can_add, egui::Button::new("Add Reachability Rule"))
+            .clicked()
+        {
+            reachability.rules.push(ReachabilityRule {
+                id: TypeId::new(),
+                name: editor_state.new_reachability_name.trim().to_string(),
+                traced_types: Vec::new(),
+                sources: Vec::new(),
+                max_cost: 5,
+                blockers: ReachabilityBlockers::default(),
+                phases: Vec::new(),
+            });
+            editor_state.new_reachability_name.clear();
+        }
+    });
+}
+
+/// Renders a rule's sources with a remove button each, and pickers adding
+/// a terrain type, a unit type or the selected hex.
+fn render_reachability_sources(
+    ui: &mut egui::Ui,
+    rule_id: TypeId,
+    sources: &mut Vec<ReachabilitySource>,
+    entity_types: &EntityTypeRegistry,
+    selected_hex: Option<HexPosition>,
+) {
+    let type_name = |id: TypeId| {
+        entity_types
+            .get(id)
+            .map_or("(missing)", |et| et.name.as_str())
+    };
+    let mut remove_source = None;
+    for (i, source) in sources.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add_space(12.0);
+            let label = match source {
+                ReachabilitySource::Hexes(hexes) => {
+                    let coords: Vec<String> = hexes
+                        .iter()
+                        .map(|h| format!("({}, {})", h.q, h.r))
+                        .collect();
+                    format!("Hexes {}", coords.join(" "))
+                }
+                ReachabilitySource::Terrain(id) => format!("Terrain: {}", type_name(*id)),
+                ReachabilitySource::UnitType(id) => format!("Units: {}", type_name(*id)),
+            };
+            ui.label(egui::RichText::new(label).small());
+            if ui.small_button("✕").clicked() {
+                remove_source = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove_source {
+        sources.remove(i);
+    }
+
+    ui.horizontal(|ui| {
+        ui.add_space(12.0);
+        egui::ComboBox::from_id_salt(("reachability_add_source", rule_id))
+            .selected_text("Add source...")
+            .show_ui(ui, |ui| {
+                for et in entity_types.types_by_role(EntityRole::BoardPosition) {
+                    if ui
+                        .selectable_label(false, format!("Terrain: {}", et.name))
+                        .clicked()
+                    {
+                        sources.push(ReachabilitySource::Terrain(et.id));
+                    }
+                }
+                for et in entity_types.types_by_role(EntityRole::Token) {
+                    if ui
+                        .selectable_label(false, format!("Units: {}", et.name))
+                        .clicked()
+                    {
+                        sources.push(ReachabilitySource::UnitType(et.id));
+                    }
+                }
+            });
+        if let Some(hex) = selected_hex
+            && ui.button("+ Selected Hex").clicked()
+        {
+            match sources.iter_mut().find_map(|s| match s {
+                ReachabilitySource::Hexes(hexes) => Some(hexes),
+                _ => None,
+            }) {
+                Some(hexes) if !hexes.contains(&hex) => hexes.push(hex),
+                Some(_) => {}
+                None => sources.push(ReachabilitySource::Hexes(vec![hex])),
+            }
+        }
+    });
+}
+
+/// Renders a rule's blockers: enemy influence, terrain and edge features.
+fn render_reachability_blockers(
+    ui: &mut egui::Ui,
+    rule_id: TypeId,
+    blockers: &mut ReachabilityBlockers,
+    entity_types: &EntityTypeRegistry,
+) {
+    ui.horizontal(|ui| {
+        ui.add_space(12.0);
+        ui.checkbox(&mut blockers.enemy_influence, "Enemy influence");
+    });
+    let terrain = entity_types.types_by_role(EntityRole::BoardPosition);
+    ui.label(egui::RichText::new("Terrain").small());
+    render_type_toggles(
+        ui,
+        ("blocking_terrain", rule_id),
+        &terrain,
+        &mut blockers.terrain,
+    );
+    let all_types: Vec<&EntityType> = entity_types.types.iter().collect();
+    ui.label(egui::RichText::new("Edge features").small());
+    render_type_toggles(
+        ui,
+        ("blocking_edges", rule_id),
+        &all_types,
+        &mut blockers.edge_types,
+    );
+}
+
+/// Renders one checkbox per entity type, keeping `selected` in sync.
+fn render_type_toggles(
+    ui: &mut egui::Ui,
+    salt: (&str, TypeId),
+    types: &[&EntityType],
+    selected: &mut Vec<TypeId>,
+) {
+    ui.push_id(salt, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            ui.add_space(12.0);
+            for et in types {
+                let mut on = selected.contains(&et.id);
+                if ui.checkbox(&mut on, &et.name).changed() {
+                    toggle_id(selected, et.id, on);
+                }
+            }
+        });
+    });
+}
+
+/// Adds `id` to `ids` when `on`, removes it otherwise.
+fn toggle_id(ids: &mut Vec<TypeId>, id: TypeId, on: bool) {
+    ids.retain(|existing| *existing != id);
+    if on {
+        ids.push(id);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Overrun
+// ---------------------------------------------------------------------------
+
+/// Renders the overrun rule editor: per rule, its movement point cost, the
+/// unit types it applies to, its table lookup and the outcome of each
+/// result. Overruns are offered in Play during Movement phases.
+pub(crate) fn render_overrun_rules(
+    ui: &mut egui::Ui,
+    overruns: &mut OverrunRegistry,
+    crt: &CombatResultsTable,
+    entity_types: &EntityTypeRegistry,
+    state_machines: &StateMachineRegistry,
+    editor_state: &mut EditorState,
+) {
+    ui.label(
+        egui::RichText::new("Overrun")
+            .strong()
+            .color(BrandTheme::ACCENT_AMBER),
+    );
+    ui.add_space(4.0);
+
+    let tokens = entity_types.types_by_role(EntityRole::Token);
+    let mut remove_rule = None;
+    for rule in &mut overruns.rules {
+        let rule_id = rule.id;
+        egui::CollapsingHeader::new(&rule.name)
+            .id_salt(("overrun_rule", rule_id))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut rule.name);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("MP cost:");
+                    ui.add(
+                        egui::DragValue::new(&mut rule.mp_cost)
+                            .range(0..=99)
+                            .speed(0.1),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Attacker:");
+                    render_optional_type_combo(
+                        ui,
+                        ("overrun_attacker", rule_id),
+                        &tokens,
+                        &mut rule.attacker_type,
+                    );
+                    ui.label("Defender:");
+                    render_optional_type_combo(
+                        ui,
+                        ("overrun_defender", rule_id),
+                        &tokens,
+                        &mut rule.defender_type,
+                    );
+                });
+
+                render_overrun_resolution(ui, &mut rule.resolution, crt);
+
+                ui.label(egui::RichText::new("Outcomes").small());
+                render_overrun_outcomes(ui, rule_id, &mut rule.outcomes, state_machines);
+
+                if ui
+                    .button(egui::RichText::new("Delete Rule").color(BrandTheme::DANGER))
+                    .clicked()
+                {
+                    remove_rule = Some(rule_id);
+                }
+            });
+    }
+    if let Some(id) = remove_rule {
+        overruns.rules.retain(|r| r.id != id);
+    }
+
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(&mut editor_state.new_overrun_name);
+        let can_add = !editor_state.new_overrun_name.trim().is_empty();
+        if ui
+            .add_enabled(can_add, egui::Button::new("Add Overrun Rule"))
+            .clicked()
+        {
+            overruns.rules.push(OverrunRule {
+                id: TypeId::new(),
+                name: editor_state.new_overrun_name.trim().to_string(),
+                mp_cost: 1,
+                attacker_type: None,
+                defender_type: None,
+                resolution: InterruptResolution::Table {
+                    table: crt_lookup_table(crt),
+                    input_a_key: "attacker.strength".to_string(),
+                    input_b_key: "defender.strength".to_string(),
+                    dice: DicePool::single(6),
+                },
+                outcomes: Vec::new(),
+            });
+            editor_state.new_overrun_name.clear();
+        }
+    });
+}
+
+/// Renders an entity type picker with an "Any" entry.
+fn render_optional_type_combo(
+    ui: &mut egui::Ui,
+    salt: (&str, TypeId),
+    types: &[&EntityType],
+    selected: &mut Option<TypeId>,
+) {
+    let current = selected
+        .and_then(|id| types.iter().find(|t| t.id == id))
+        .map_or("Any", |t| t.name.as_str());
+    egui::ComboBox::from_id_salt(salt)
+        .selected_text(current)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, "Any");
+            for et in types {
+                ui.selectable_value(selected, Some(et.id), &et.name);
+            }
+        });
+}
+
+/// Renders an overrun's table lookup: its input keys, dice and size, with a
+/// button replacing the table by a copy of the CRT. Chains are shown only.
+fn render_overrun_resolution(
+    ui: &mut egui::Ui,
+    resolution: &mut InterruptResolution,
+    crt: &CombatResultsTable,
+) {
+    match resolution {
+        InterruptResolution::Table {
+            table,
+            input_a_key,
+            input_b_key,
+            dice,
+        } => {
+            ui.horizontal(|ui| {
+                ui.label("Inputs:");
+                ui.add(egui::TextEdit::singleline(input_a_key).desired_width(120.0));
+                ui.label("vs");
+                ui.add(egui::TextEdit::singleline(input_b_key).desired_width(120.0));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Dice:");
+                ui.add(egui::DragValue::new(&mut dice.count).range(1..=10));
+                ui.label("d");
+                ui.add(egui::DragValue::new(&mut dice.sides).range(2..=20));
+                ui.label("+");
+                ui.add(egui::DragValue::new(&mut dice.modifier).range(-10..=10));
+            });
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Table: {} columns \u{00d7} {} rows",
+                        table.columns.len(),
+                        table.rows.len()
+                    ))
+                    .small()
+                    .color(BrandTheme::TEXT_SECONDARY),
+                );
+                if ui.small_button("Copy CRT").clicked() {
+                    *table = crt_lookup_table(crt);
+                }
+            });
+        }
+        InterruptResolution::Chain { tables, .. } => {
+            ui.label(
+                egui::RichText::new(format!("Resolution chain of {} tables", tables.len()))
+                    .small()
+                    .color(BrandTheme::TEXT_SECONDARY),
+            );
+        }
+    }
+}
+
+/// A lookup table with the CRT's columns and rows whose cells hold the
+/// CRT's outcome labels.
+fn crt_lookup_table(crt: &CombatResultsTable) -> ResolutionTable {
+    ResolutionTable {
+        id: TypeId::new(),
+        name: crt.name.clone(),
+        columns: crt.table.columns.clone(),
+        rows: crt.table.rows.clone(),
+        outcomes: crt
+            .outcomes
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|outcome| TableResult::Text(outcome.label.clone()))
+                    .collect()
+            })
+            .collect(),
+    }
+}
+
+/// Renders an overrun's outcomes, one row per result, with a remove button
+/// each and a button adding one.
+fn render_overrun_outcomes(
+    ui: &mut egui::Ui,
+    rule_id: TypeId,
+    outcomes: &mut Vec<OverrunOutcome>,
+    state_machines: &StateMachineRegistry,
+) {
+    let mut remove = None;
+    for (i, outcome) in outcomes.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add_space(12.0);
+            ui.add(egui::TextEdit::singleline(&mut outcome.result).desired_width(48.0));
+            ui.checkbox(&mut outcome.clears_defender, "Clears");
+            ui.label("Attacker:");
+            render_state_combo(
+                ui,
+                ("overrun_attacker_state", rule_id, i),
+                &mut outcome.attacker_state,
+                state_machines,
+            );
+            ui.label("Defender:");
+            render_state_combo(
+                ui,
+                ("overrun_defender_state", rule_id, i),
+                &mut outcome.defender_state,
+                state_machines,
+            );
+            if ui
+                .small_button(egui::RichText::new("x").color(BrandTheme::DANGER))
+                .clicked()
+            {
+                remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove {
+        outcomes.remove(i);
+    }
+    if ui.small_button("+ Outcome").clicked() {
+        outcomes.push(OverrunOutcome {
+            result: String::new(),
+            clears_defender: false,
+            attacker_state: None,
+            defender_state: None,
+        });
+    }
+}
+
+/// Renders a picker over the states of every machine, with a "(none)" entry.
+fn render_state_combo(
+    ui: &mut egui::Ui,
+    salt: (&str, TypeId, usize),
+    selected: &mut Option<TypeId>,
+    state_machines: &StateMachineRegistry,
+) {
+    egui::ComboBox::from_id_salt(salt)
+        .selected_text(state_machines.state_name(*selected).to_string())
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, "(none)");
+            for machine in &state_machines.machines {
+                for state in &machine.states {
+                    ui.selectable_value(
+                        selected,
+                        Some(state.id),
+                        format!("{}: {}", machine.name, state.name),
+                    );
+                }
+            }
+        });
+}
@@ -3,7 +3,8 @@
 //! This module contains the dock orchestrator, tab bar renderers, settings
 //! sync/restore systems, and dock layout persistence. Rendering logic for
 //! individual tabs and panels lives in sibling modules (`render_panels`,
-//! `render_play`, `render_design`, `render_ontology`, `render_rules`).
+//! `render_play`, `render_design`, `render_ontology`, `render_rules`,
+//! `render_analysis`).
 //! Deferred action application and helper functions live in `actions`.
 
 use bevy::prelude::*;
@@ -11,8 +12,8 @@ use bevy_egui::{EguiContexts, egui};
 
 use hexorder_contracts::editor_ui::{EditorTool, ViewportMargins, ViewportRect};
 use hexorder_contracts::game_system::{
-    ActiveBoardType, ActiveTokenType, EntityData, EntityTypeRegistry, EnumRegistry, GameSystem,
-    StructRegistry, UnitInstance,
+    ActiveBoardType, ActiveTokenType, EntityData, EntityState, EntityTypeRegistry, EnumRegistry,
+    GameSystem, StructRegistry, TypeId, UnitId, UnitInstance, UnitOwner,
 };
 use hexorder_contracts::hex_grid::{HexPosition, HexTile};
 use hexorder_contracts::map_gen::MapGenParams;
@@ -27,19 +28,23 @@ use hexorder_contracts::settings::{SettingsRegistry, ThemeLibrary};
 use hexorder_contracts::shortcuts::{
     CommandCategory, CommandExecutedEvent, CommandId, KeyBinding, ShortcutRegistry,
 };
-use hexorder_contracts::validation::SchemaValidation;
+use hexorder_contracts::validation::{RuleAnalysis, SchemaValidation};
 
 use egui_dock::DockArea;
 
 use super::components::{
     BrandTheme, DockLayoutState, DockTab, EditorAction, EditorState, MechanicsParams,
     OntologyParams, OntologyTab, ProjectParams, SelectionParams, ShortcutDisplayEntry,
-    TypeRegistryParams, WorkspacePreset,
+    TypeRegistryParams, ValidationParams, WorkspacePreset,
+};
+use super::render_rules::{
+    render_entity_state, render_faction_combo, render_factions, render_inspector,
+    render_unit_inspector, render_unit_owner,
 };
-use super::render_rules::{render_inspector, render_unit_inspector};
 
 // Sibling-module functions used locally and re-exported for tests via pub(super).
 pub(super) use super::actions::apply_actions;
+pub(super) use super::render_analysis::render_odds_analysis;
 pub(super) use super::render_design::{
     render_entity_type_editor, render_enums_tab, render_structs_tab,
 };
@@ -48,18 +53,22 @@ pub(super) use super::render_ontology::{
 };
 pub(super) use super::render_panels::{
     render_about_panel, render_cell_palette, render_edge_palette, render_tool_mode,
-    render_unit_palette, render_workspace_header,
+    render_unit_palette, render_vertex_palette, render_workspace_header,
 };
 pub(super) use super::render_rules::{
-    render_accumulators, render_influence_rules, render_mechanics_tab, render_movement_cost_matrix,
-    render_spawn_schedule, render_stacking_rule, render_validation_tab,
+    render_accumulators, render_board_shape, render_combat_modifier_conditions,
+    render_combat_strength, render_combat_tables, render_influence_rules, render_mechanics_tab,
+    render_movement_cost_matrix, render_off_map_zones, render_overrun_rules,
+    render_reachability_rules, render_rule_analysis, render_spawn_schedule, render_stacking_rule,
+    render_state_machines, render_validation_tab,
 };
 
 // Public systems re-exported for plugin registration in mod.rs.
 #[cfg(feature = "inspector")]
 pub use super::render_panels::debug_inspector_panel;
 pub use super::render_panels::{
-    configure_theme, launcher_system, render_grid_overlay, render_toast,
+    configure_theme, launcher_system, render_grid_overlay, render_move_tooltip,
+    render_state_badges, render_toast,
 };
 pub use super::render_play::play_panel_system;
 
@@ -134,6 +143,8 @@ pub(crate) struct PaletteData<'a> {
     pub(crate) active_board: &'a mut ActiveBoardType,
     pub(crate) active_token: &'a mut ActiveTokenType,
     pub(crate) active_edge: &'a mut hexorder_contracts::editor_ui::ActiveEdgeType,
+    pub(crate) active_vertex: &'a mut hexorder_contracts::editor_ui::ActiveVertexType,
+    pub(crate) active_faction: &'a mut hexorder_contracts::game_system::ActiveFaction,
     pub(crate) project_workspace: &'a Workspace,
     pub(crate) project_game_system: &'a GameSystem,
 }
@@ -151,14 +162,25 @@ pub(crate) struct DesignData<'a> {
 pub(crate) struct RulesData<'a> {
     pub(crate) constraint_registry: &'a mut hexorder_contracts::ontology::ConstraintRegistry,
     pub(crate) turn_structure: &'a mut hexorder_contracts::mechanics::TurnStructure,
+    /// The default table; conditional tables live in `combat_tables`.
     pub(crate) combat_results_table: &'a mut CombatResultsTable,
+    pub(crate) combat_tables: &'a mut hexorder_contracts::mechanics::CombatTableRegistry,
     pub(crate) combat_modifiers: &'a mut hexorder_contracts::mechanics::CombatModifierRegistry,
     pub(crate) influence_rules: &'a mut hexorder_contracts::hex_grid::InfluenceRuleRegistry,
     pub(crate) stacking_rule: &'a mut hexorder_contracts::hex_grid::StackingRule,
     pub(crate) movement_cost_matrix: &'a mut hexorder_contracts::hex_grid::MovementCostMatrix,
     pub(crate) spawn_schedule: &'a mut hexorder_contracts::mechanics::SpawnSchedule,
+    pub(crate) off_map_zones: &'a mut hexorder_contracts::mechanics::OffMapZoneRegistry,
+    pub(crate) grid_shape: &'a mut hexorder_contracts::hex_grid::GridShape,
     pub(crate) accumulator_registry: &'a mut hexorder_contracts::mechanics::AccumulatorRegistry,
     pub(crate) victory_conditions: &'a mut hexorder_contracts::mechanics::VictoryConditionRegistry,
+    pub(crate) factions: &'a mut hexorder_contracts::game_system::FactionRegistry,
+    pub(crate) state_machines: &'a mut hexorder_contracts::game_system::StateMachineRegistry,
+    pub(crate) reachability_rules: &'a mut hexorder_contracts::hex_grid::ReachabilityRuleRegistry,
+    pub(crate) reachability_overlay: &'a mut hexorder_contracts::hex_grid::ReachabilityOverlay,
+    pub(crate) overruns: &'a mut hexorder_contracts::mechanics::OverrunRegistry,
+    pub(crate) area_markers: &'a hexorder_contracts::mechanics::AreaMarkerRegistry,
+    pub(crate) movement_interrupts: &'a hexorder_contracts::mechanics::MovementInterruptRegistry,
 }
 
 /// Actions returned by `render_editor_menu_bar` for deferred dispatch.
@@ -181,6 +203,12 @@ pub(crate) struct InspectorData<'a> {
     pub(crate) tile_position: Option<HexPosition>,
     pub(crate) tile_entity_data: Option<&'a mut EntityData>,
     pub(crate) unit_entity_data: Option<&'a mut EntityData>,
+    pub(crate) unit_owner: Option<&'a mut hexorder_contracts::game_system::UnitOwner>,
+    /// The inspected tile and unit with their current state, if they have one.
+    pub(crate) tile_state: Option<(Entity, TypeId)>,
+    pub(crate) unit_state: Option<(Entity, TypeId)>,
+    /// Placed units offered by unit-reference pickers, as (id, label).
+    pub(crate) unit_choices: &'a [(UnitId, String)],
 }
 
 /// Viewer context that borrows system resources for the duration of `DockArea::show()`.
@@ -196,6 +224,7 @@ pub(crate) struct EditorDockViewer<'a> {
     pub(crate) actions: &'a mut Vec<EditorAction>,
     pub(crate) next_screen: Option<AppScreen>,
     pub(crate) schema_validation: &'a SchemaValidation,
+    pub(crate) rule_analysis: &'a RuleAnalysis,
     // Single-tab fields
     pub(crate) viewport_rect: &'a mut ViewportRect,
     pub(crate) multi: &'a hexorder_contracts::editor_ui::Selection,
@@ -248,10 +277,24 @@ pub(crate) fn render_dock_tab(ui: &mut egui::Ui, tab: DockTab, viewer: &mut Edit
             }
             if *viewer.palette.editor_tool == EditorTool::Place {
                 render_unit_palette(ui, viewer.design.registry, viewer.palette.active_token);
+                if !viewer.rules.factions.factions.is_empty() {
+                    ui.horizontal(|ui| {
+                        ui.label("Faction:");
+                        render_faction_combo(
+                            ui,
+                            "active_faction_picker",
+                            &mut viewer.palette.active_faction.faction_id,
+                            viewer.rules.factions,
+                        );
+                    });
+                }
             }
             if *viewer.palette.editor_tool == EditorTool::EdgePaint {
                 render_edge_palette(ui, viewer.design.registry, viewer.palette.active_edge);
             }
+            if *viewer.palette.editor_tool == EditorTool::VertexPaint {
+                render_vertex_palette(ui, viewer.design.registry, viewer.palette.active_vertex);
+            }
         }
         DockTab::Design => {
             render_design_tab_bar(ui, viewer.editor_state);
@@ -326,23 +369,55 @@ pub(crate) fn render_dock_tab(ui: &mut egui::Ui, tab: DockTab, viewer: &mut Edit
                             ui,
                             viewer.rules.constraint_registry,
                             viewer.design.concept_registry,
+                            viewer.design.registry,
                             viewer.editor_state,
                             viewer.actions,
                         );
                     }
                     OntologyTab::Validation => {
                         render_validation_tab(ui, viewer.schema_validation);
+                        render_rule_analysis(ui, viewer.rule_analysis, viewer.editor_state);
                     }
                     OntologyTab::Mechanics => {
                         render_mechanics_tab(
                             ui,
                             viewer.rules.turn_structure,
-                            viewer.rules.combat_results_table,
+                            viewer.rules.combat_tables.table_or(
+                                viewer.editor_state.edited_combat_table,
+                                viewer.rules.combat_results_table,
+                            ),
                             viewer.rules.combat_modifiers,
                             viewer.editor_state,
                             viewer.actions,
                         );
                         ui.add_space(12.0);
+                        render_combat_tables(
+                            ui,
+                            viewer.rules.combat_results_table,
+                            viewer.rules.combat_tables,
+                            viewer.rules.turn_structure,
+                            viewer.rules.factions,
+                            viewer.rules.constraint_registry,
+                            viewer.editor_state,
+                        );
+                        ui.add_space(12.0);
+                        render_combat_modifier_conditions(
+                            ui,
+                            viewer.rules.combat_modifiers,
+                            viewer.rules.constraint_registry,
+                            viewer.actions,
+                        );
+                        ui.add_space(12.0);
+                        render_combat_strength(
+                            ui,
+                            viewer.rules.combat_tables.table_or_mut(
+                                viewer.editor_state.edited_combat_table,
+                                viewer.rules.combat_results_table,
+                            ),
+                            viewer.design.concept_registry,
+                            viewer.design.registry,
+                        );
+                        ui.add_space(12.0);
                         render_influence_rules(
                             ui,
                             viewer.rules.influence_rules,
@@ -354,6 +429,7 @@ pub(crate) fn render_dock_tab(ui: &mut egui::Ui, tab: DockTab, viewer: &mut Edit
                             ui,
                             viewer.rules.stacking_rule,
                             viewer.design.registry,
+                            viewer.rules.factions,
                             viewer.editor_state,
                         );
                         ui.add_space(12.0);
@@ -372,10 +448,70 @@ pub(crate) fn render_dock_tab(ui: &mut egui::Ui, tab: DockTab, viewer: &mut Edit
                             viewer.actions,
                         );
                         ui.add_space(12.0);
+                        render_off_map_zones(
+                            ui,
+                            viewer.rules.off_map_zones,
+                            viewer.rules.turn_structure,
+                            viewer.design.registry,
+                            viewer.editor_state,
+                        );
+                        ui.add_space(12.0);
+                        render_board_shape(ui, viewer.rules.grid_shape);
+                        ui.add_space(12.0);
+                        render_factions(ui, viewer.rules.factions, viewer.editor_state);
+                        ui.add_space(12.0);
+                        render_state_machines(
+                            ui,
+                            viewer.rules.state_machines,
+                            viewer.design.registry,
+                            viewer.design.enum_registry,
+                            viewer.design.struct_registry,
+                            viewer.rules.turn_structure,
+                            viewer.rules.constraint_registry,
+                            viewer.rules.reachability_rules,
+                            viewer.editor_state,
+                        );
+                        ui.add_space(12.0);
+                        render_reachability_rules(
+                            ui,
+                            viewer.rules.reachability_rules,
+                            viewer.rules.reachability_overlay,
+                            viewer.design.registry,
+                            viewer.rules.turn_structure,
+                            viewer.inspector.tile_position,
+                            viewer.editor_state,
+                            viewer.actions,
+                        );
+                        ui.add_space(12.0);
+                        render_overrun_rules(
+                            ui,
+                            viewer.rules.overruns,
+                            viewer.rules.combat_results_table,
+                            viewer.design.registry,
+                            viewer.rules.state_machines,
+                            viewer.editor_state,
+                        );
+                        ui.add_space(12.0);
                         render_accumulators(
                             ui,
                             viewer.rules.accumulator_registry,
                             viewer.rules.victory_conditions,
+                            viewer.rules.factions,
+                            viewer.editor_state,
+                            viewer.actions,
+                        );
+                    }
+                    OntologyTab::Analysis => {
+                        render_odds_analysis(
+                            ui,
+                            viewer.rules.combat_tables.table_or(
+                                viewer.editor_state.edited_combat_table,
+                                viewer.rules.combat_results_table,
+                            ),
+                            viewer.rules.combat_modifiers,
+                            viewer.rules.area_markers,
+                            viewer.rules.movement_interrupts,
+                            viewer.rules.overruns,
                             viewer.editor_state,
                             viewer.actions,
                         );
@@ -387,6 +523,7 @@ pub(crate) fn render_dock_tab(ui: &mut egui::Ui, tab: DockTab, viewer: &mut Edit
                             ui,
                             viewer.rules.constraint_registry,
                             viewer.design.concept_registry,
+                            viewer.design.registry,
                             viewer.editor_state,
                             viewer.actions,
                         );
@@ -402,15 +539,38 @@ pub(crate) fn render_dock_tab(ui: &mut egui::Ui, tab: DockTab, viewer: &mut Edit
                 viewer.design.registry,
                 viewer.design.enum_registry,
                 viewer.design.struct_registry,
+                viewer.inspector.unit_choices,
             );
+            if let Some((entity, state_id)) = viewer.inspector.tile_state
+                && let Some(machine) = viewer
+                    .inspector
+                    .tile_entity_data
+                    .as_deref()
+                    .and_then(|d| viewer.rules.state_machines.for_type(d.entity_type_id))
+            {
+                render_entity_state(ui, entity, state_id, machine, viewer.actions);
+            }
             render_unit_inspector(
                 ui,
                 viewer.inspector.unit_entity_data.as_deref_mut(),
                 viewer.design.registry,
                 viewer.design.enum_registry,
                 viewer.design.struct_registry,
+                viewer.inspector.unit_choices,
                 viewer.actions,
             );
+            if let Some(owner) = viewer.inspector.unit_owner.as_deref_mut() {
+                render_unit_owner(ui, owner, viewer.rules.factions);
+            }
+            if let Some((entity, state_id)) = viewer.inspector.unit_state
+                && let Some(machine) = viewer
+                    .inspector
+                    .unit_entity_data
+                    .as_deref()
+                    .and_then(|d| viewer.rules.state_machines.for_type(d.entity_type_id))
+            {
+                render_entity_state(ui, entity, state_id, machine, viewer.actions);
+            }
         }
         DockTab::Settings => {
             render_settings_tab(ui, viewer.editor_state);
@@ -498,11 +658,13 @@ pub(crate) fn render_rules_tab_bar(ui: &mut egui::Ui, editor_state: &mut EditorS
             OntologyTab::Constraints,
             OntologyTab::Validation,
             OntologyTab::Mechanics,
+            OntologyTab::Analysis,
         ] {
             let label = match tab {
                 OntologyTab::Constraints => "Constraints",
                 OntologyTab::Validation => "Validation",
                 OntologyTab::Mechanics => "Mechanics",
+                OntologyTab::Analysis => "Analysis",
                 _ => continue,
             };
             if ui
@@ -624,6 +786,7 @@ pub(crate) fn render_status_bar_content(
             EditorTool::Paint => "Paint",
             EditorTool::Place => "Place",
             EditorTool::EdgePaint => "Edge Paint",
+            EditorTool::VertexPaint => "Vertex Paint",
             EditorTool::CombatSelect => "Combat Select",
         };
         ui.label(
@@ -987,7 +1150,7 @@ pub(crate) fn render_map_generator(
 
 /// Unified dock system. Renders the menu bar as a native `TopBottomPanel`, then
 /// delegates all tabbed content to `DockArea`. Replaces the four separate zone systems.
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn editor_dock_system(
     mut contexts: EguiContexts,
     mut selection: SelectionParams,
@@ -995,15 +1158,24 @@ pub fn editor_dock_system(
     project: ProjectParams,
     mut type_regs: TypeRegistryParams,
     mut tile_data_query: Query<&mut EntityData, Without<UnitInstance>>,
-    tile_query: Query<(&HexPosition, Entity), With<HexTile>>,
-    mut unit_data_query: Query<&mut EntityData, With<UnitInstance>>,
+    tile_query: Query<(&HexPosition, Entity, Option<&EntityState>), With<HexTile>>,
+    mut unit_data_query: Query<
+        (
+            &mut EntityData,
+            &mut UnitOwner,
+            &UnitId,
+            &HexPosition,
+            Option<&EntityState>,
+        ),
+        With<UnitInstance>,
+    >,
     mut commands: Commands,
     mut ontology: OntologyParams,
     mut mechanics: MechanicsParams,
     mut next_state: ResMut<NextState<AppScreen>>,
     mut dock_layout: ResMut<DockLayoutState>,
     mut viewport_rect: ResMut<ViewportRect>,
-    validation: Res<SchemaValidation>,
+    validation: ValidationParams,
     mut map_gen: super::components::MapGenDockedParams,
 ) {
     let Ok(ctx) = contexts.ctx_mut() else {
@@ -1086,21 +1258,55 @@ pub fn editor_dock_system(
     let tile_entity = tile_position.and_then(|pos| {
         tile_query
             .iter()
-            .find(|(tp, _)| **tp == pos)
-            .map(|(_, e)| e)
+            .find(|(tp, _, _)| **tp == pos)
+            .map(|(_, e, _)| e)
     });
+    let tile_state = tile_entity
+        .and_then(|e| tile_query.get(e).ok())
+        .and_then(|(_, e, state)| state.map(|s| (e, s.state_id)));
     let mut tile_entity_data = tile_entity.and_then(|e| tile_data_query.get_mut(e).ok());
-    let mut unit_entity_data = selection
-        .selected_unit
-        .entity
-        .and_then(|e| unit_data_query.get_mut(e).ok());
+    let selected_unit = selection.selected_unit.entity;
+    // Edit a copy of the owner so tints only refresh on a real change.
+    let mut unit_owner = selected_unit
+        .and_then(|e| unit_data_query.get(e).ok())
+        .map(|(_, owner, _, _, _)| *owner);
+    let unit_state = selected_unit.and_then(|e| {
+        unit_data_query
+            .get(e)
+            .ok()
+            .and_then(|(_, _, _, _, state)| state.map(|s| (e, s.state_id)))
+    });
+    let mut unit_choices: Vec<(UnitId, String)> = unit_data_query
+        .iter()
+        .map(|(data, _, id, pos, _)| {
+            let type_name = type_regs
+                .registry
+                .get(data.entity_type_id)
+                .map_or("Unit", |et| et.name.as_str());
+            (
+                *id,
+                format!("{type_name} #{} ({}, {})", id.short(), pos.q, pos.r),
+            )
+        })
+        .collect();
+    unit_choices.sort_by(|a, b| a.1.cmp(&b.1));
+    let mut unit_entity_data = selected_unit
+        .and_then(|e| unit_data_query.get_mut(e).ok())
+        .map(|(data, _, _, _, _)| data);
+
+    // Edit a copy of the board shape so the grid only rebuilds on a real change.
+    let mut grid_shape = mechanics
+        .grid_config
+        .as_ref()
+        .map_or_else(Default::default, |config| config.shape);
 
     // DockArea for all tabbed content.
     let mut viewer = EditorDockViewer {
         editor_state: &mut editor_state,
         actions: &mut actions,
         next_screen: None,
-        schema_validation: &validation,
+        schema_validation: &validation.schema,
+        rule_analysis: &validation.analysis,
         viewport_rect: &mut viewport_rect,
         multi: &selection.multi,
         mechanic_catalog: &mechanics.mechanic_catalog,
@@ -1109,6 +1315,8 @@ pub fn editor_dock_system(
             active_board: &mut selection.active_board,
             active_token: &mut selection.active_token,
             active_edge: &mut selection.active_edge,
+            active_vertex: &mut selection.active_vertex,
+            active_faction: &mut selection.active_faction,
             project_workspace: &project.workspace,
             project_game_system: &project.game_system,
         },
@@ -1122,19 +1330,33 @@ pub fn editor_dock_system(
         rules: RulesData {
             constraint_registry: &mut ontology.constraint_registry,
             turn_structure: &mut mechanics.turn_structure,
-            combat_results_table: &mut mechanics.combat_results_table,
-            combat_modifiers: &mut mechanics.combat_modifiers,
+            combat_results_table: &mut mechanics.combat.combat_results_table,
+            combat_tables: &mut mechanics.combat.combat_tables,
+            combat_modifiers: &mut mechanics.combat.combat_modifiers,
             influence_rules: &mut mechanics.influence_rules,
             stacking_rule: &mut mechanics.stacking_rule,
             movement_cost_matrix: &mut mechanics.movement_cost_matrix,
             spawn_schedule: &mut mechanics.spawn_schedule,
+            off_map_zones: &mut mechanics.off_map_zones,
+            grid_shape: &mut grid_shape,
             accumulator_registry: &mut mechanics.accumulator_registry,
             victory_conditions: &mut mechanics.victory_conditions,
+            factions: &mut mechanics.factions,
+            state_machines: &mut mechanics.state_machines,
+            reachability_rules: &mut mechanics.movement_rules.reachability_rules,
+            reachability_overlay: &mut mechanics.movement_rules.reachability_overlay,
+            overruns: &mut mechanics.movement_rules.overruns,
+            area_markers: &mechanics.analysis.area_markers,
+            movement_interrupts: &mechanics.analysis.movement_interrupts,
         },
         inspector: InspectorData {
             tile_position,
             tile_entity_data: tile_entity_data.as_deref_mut(),
             unit_entity_data: unit_entity_data.as_deref_mut(),
+            unit_owner: unit_owner.as_mut(),
+            tile_state,
+            unit_state,
+            unit_choices: &unit_choices,
         },
         map_gen_params: &mut map_gen.params,
         is_generating,
@@ -1156,6 +1378,29 @@ pub fn editor_dock_system(
         next_state.set(screen);
     }
 
+    // Bring forward the dock tab a rule analysis link pointed at, reopening
+    // it if it was closed.
+    if let Some(tab) = editor_state.focus_dock_tab.take() {
+        match dock_layout.dock_state.find_tab(&tab) {
+            Some(location) => dock_layout.dock_state.set_active_tab(location),
+            None => dock_layout.dock_state.push_to_focused_leaf(tab),
+        }
+    }
+
+    if let Some(config) = mechanics.grid_config.as_mut()
+        && config.shape != grid_shape
+    {
+        config.shape = grid_shape;
+    }
+
+    if let Some(owner) = unit_owner
+        && let Some(entity) = selected_unit
+        && let Ok((_, mut current, _, _, _)) = unit_data_query.get_mut(entity)
+        && *current != owner
+    {
+        *current = owner;
+    }
+
     // Apply deferred actions.
     apply_actions(
         actions,
@@ -1172,8 +1417,9 @@ pub fn editor_dock_system(
         &mut ontology.relation_registry,
         &mut ontology.constraint_registry,
         &mut mechanics.turn_structure,
-        &mut mechanics.combat_results_table,
-        &mut mechanics.combat_modifiers,
+        &mut mechanics.combat.combat_results_table,
+        &mut mechanics.combat.combat_tables,
+        &mut mechanics.combat.combat_modifiers,
         &mechanics.mechanic_catalog,
         &mut mechanics.spawn_schedule,
         &mut mechanics.accumulator_registry,
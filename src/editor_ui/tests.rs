@@ -1666,6 +1666,7 @@ fn format_property_type_all_variants() {
             PropertyType::FloatRange { min: 0.0, max: 1.0 },
             "FloatRange",
         ),
+        (PropertyType::UnitRef, "UnitRef"),
     ];
 
     for (pt, expected) in &cases {
@@ -1734,6 +1735,10 @@ fn index_to_property_type_all_indices() {
         super::systems::index_to_property_type(11),
         PropertyType::FloatRange { .. }
     ));
+    assert!(matches!(
+        super::systems::index_to_property_type(12),
+        PropertyType::UnitRef
+    ));
     // Out of range → Bool.
     assert!(matches!(
         super::systems::index_to_property_type(99),
@@ -2019,7 +2024,8 @@ fn build_constraint_expression_property_compare() {
         ..EditorState::default()
     };
 
-    let expr = super::systems::build_constraint_expression(&state, &[]);
+    let expr =
+        super::systems::build_constraint_expression(&state, &[], &EntityTypeRegistry::default());
     match expr {
         ConstraintExpr::PropertyCompare {
             property_name,
@@ -2044,7 +2050,8 @@ fn build_constraint_expression_path_budget() {
         ..EditorState::default()
     };
 
-    let expr = super::systems::build_constraint_expression(&state, &[]);
+    let expr =
+        super::systems::build_constraint_expression(&state, &[], &EntityTypeRegistry::default());
     match expr {
         ConstraintExpr::PathBudget {
             cost_property,
@@ -2067,7 +2074,8 @@ fn build_constraint_expression_unknown_falls_back_to_all() {
         ..EditorState::default()
     };
 
-    let expr = super::systems::build_constraint_expression(&state, &[]);
+    let expr =
+        super::systems::build_constraint_expression(&state, &[], &EntityTypeRegistry::default());
     assert!(
         matches!(expr, ConstraintExpr::All(v) if v.is_empty()),
         "Unknown type should fall back to All([])"
@@ -2310,9 +2318,16 @@ fn run_apply_actions(
     mut concept_registry: ResMut<hexorder_contracts::ontology::ConceptRegistry>,
     mut relation_registry: ResMut<hexorder_contracts::ontology::RelationRegistry>,
     mut constraint_registry: ResMut<hexorder_contracts::ontology::ConstraintRegistry>,
-    (mut turn_structure, mut combat_results_table, mut combat_modifiers, mechanic_catalog): (
+    (
+        mut turn_structure,
+        mut combat_results_table,
+        mut combat_tables,
+        mut combat_modifiers,
+        mechanic_catalog,
+    ): (
         ResMut<hexorder_contracts::mechanics::TurnStructure>,
         ResMut<hexorder_contracts::mechanics::CombatResultsTable>,
+        ResMut<hexorder_contracts::mechanics::CombatTableRegistry>,
         ResMut<hexorder_contracts::mechanics::CombatModifierRegistry>,
         Res<hexorder_contracts::mechanic_reference::MechanicCatalog>,
     ),
@@ -2337,6 +2352,7 @@ fn run_apply_actions(
         &mut constraint_registry,
         &mut turn_structure,
         &mut combat_results_table,
+        &mut combat_tables,
         &mut combat_modifiers,
         &mechanic_catalog,
         &mut spawn_schedule,
@@ -2354,8 +2370,8 @@ fn actions_app(actions: Vec<super::components::EditorAction>) -> App {
     };
     use hexorder_contracts::mechanic_reference::MechanicCatalog;
     use hexorder_contracts::mechanics::{
-        AccumulatorRegistry, CombatModifierRegistry, CombatResultsTable, SpawnSchedule,
-        TurnStructure, VictoryConditionRegistry,
+        AccumulatorRegistry, CombatModifierRegistry, CombatResultsTable, CombatTableRegistry,
+        SpawnSchedule, TurnStructure, VictoryConditionRegistry,
     };
     use hexorder_contracts::ontology::{ConceptRegistry, ConstraintRegistry, RelationRegistry};
 
@@ -2373,6 +2389,7 @@ fn actions_app(actions: Vec<super::components::EditorAction>) -> App {
     app.init_resource::<ConstraintRegistry>();
     app.init_resource::<TurnStructure>();
     app.init_resource::<CombatResultsTable>();
+    app.init_resource::<CombatTableRegistry>();
     app.init_resource::<CombatModifierRegistry>();
     app.init_resource::<MechanicCatalog>();
     app.init_resource::<SpawnSchedule>();
@@ -2963,6 +2980,7 @@ fn apply_actions_create_and_delete_concept_cascades() {
             relation_id: None,
             expression: ConstraintExpr::All(Vec::new()),
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
     app.world_mut()
@@ -3096,6 +3114,7 @@ fn apply_actions_create_and_delete_relation_cascades() {
             relation_id: Some(relation_id),
             expression: ConstraintExpr::All(Vec::new()),
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
     app.world_mut()
@@ -3375,6 +3394,40 @@ fn apply_actions_set_crt_outcome() {
     assert_eq!(crt.outcomes[0][0].label, "AE");
 }
 
+#[test]
+fn apply_actions_crt_edits_go_to_the_edited_combat_table() {
+    use hexorder_contracts::mechanics::{CombatResultsTable, CombatTableRegistry};
+    use hexorder_contracts::simulation::DicePool;
+
+    let mut app = actions_app(Vec::new());
+    let assault = CombatResultsTable {
+        name: "Assault".to_string(),
+        ..CombatResultsTable::default()
+    };
+    app.world_mut()
+        .resource_mut::<super::components::EditorState>()
+        .edited_combat_table = Some(assault.id);
+    app.insert_resource(CombatTableRegistry {
+        tables: vec![assault],
+    });
+    let dice = DicePool {
+        count: 2,
+        sides: 6,
+        modifier: 0,
+    };
+    app.world_mut()
+        .resource_mut::<TestActions>()
+        .0
+        .push(super::components::EditorAction::SetCrtDice { dice });
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<CombatTableRegistry>().tables[0].dice,
+        dice
+    );
+    assert_ne!(app.world().resource::<CombatResultsTable>().dice, dice);
+}
+
 #[test]
 fn apply_actions_combat_modifiers() {
     use hexorder_contracts::mechanics::{CombatModifierRegistry, ModifierSource};
@@ -3392,7 +3445,19 @@ fn apply_actions_combat_modifiers() {
     assert_eq!(cm.modifiers[0].column_shift, 2);
     let mod_id = cm.modifiers[0].id;
 
+    let condition = hexorder_contracts::ontology::ConstraintExpr::All(Vec::new());
     let mut app = app;
+    app.world_mut().resource_mut::<TestActions>().0.push(
+        super::components::EditorAction::SetCombatModifierCondition {
+            id: mod_id,
+            condition: Some(condition.clone()),
+        },
+    );
+    app.update();
+
+    let cm = app.world().resource::<CombatModifierRegistry>();
+    assert_eq!(cm.modifiers[0].condition, Some(condition));
+
     app.world_mut()
         .resource_mut::<TestActions>()
         .0
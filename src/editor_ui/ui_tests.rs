@@ -26,7 +26,9 @@ use hexorder_contracts::mechanic_reference::{
 };
 use hexorder_contracts::mechanics::{
     ActiveCombat, AreaMarkerRegistry, CombatModifierDefinition, CombatModifierRegistry,
-    CombatOutcome, CombatResultsTable, ModifierSource, Phase, PhaseType, PlayerOrder, TurnState,
+    CombatOutcome, CombatResultsTable, CombatRoles, CombatStrengthModel, CombatTableRegistry,
+    CrtSelection, InterruptPause, InterruptResolution, InterruptTrigger, LossRules, ModifierSource,
+    MovementInterruptRegistry, MovementInterruptRule, Phase, PhaseType, PlayerOrder, TurnState,
     TurnStructure,
 };
 use hexorder_contracts::ontology::{
@@ -36,9 +38,13 @@ use hexorder_contracts::ontology::{
 };
 use hexorder_contracts::persistence::{AppScreen, Workspace};
 use hexorder_contracts::simulation::{
-    ColumnType, ResolutionTable, SimulationRng, TableColumn, TableRow,
+    ColumnType, DicePool, ResolutionTable, SimulationRng, TableColumn, TableResult, TableRow,
+};
+use hexorder_contracts::validation::{
+    AnalysisCategory, AnalysisFinding, AnalysisSubject, CostComponent, CostSource, PathStep,
+    RuleAnalysis, SchemaError, SchemaErrorCategory, SchemaValidation, ValidMoveSet,
+    ValidationResult,
 };
-use hexorder_contracts::validation::{SchemaError, SchemaErrorCategory, SchemaValidation};
 
 use super::actions;
 use super::components::{
@@ -147,6 +153,7 @@ fn test_constraint_registry() -> ConstraintRegistry {
                     value: PropertyValue::Int(0),
                 },
                 auto_generated: false,
+                gates: Vec::new(),
             },
             Constraint {
                 id: TypeId::new(),
@@ -156,6 +163,7 @@ fn test_constraint_registry() -> ConstraintRegistry {
                 relation_id: None,
                 expression: ConstraintExpr::All(Vec::new()),
                 auto_generated: true,
+                gates: Vec::new(),
             },
         ],
     }
@@ -284,6 +292,11 @@ fn test_crt() -> CombatResultsTable {
             ],
         ],
         combat_concept_id: None,
+        dice: DicePool::single(6),
+        strength: CombatStrengthModel::default(),
+        losses: LossRules::default(),
+        roles: CombatRoles::default(),
+        selection: CrtSelection::default(),
     }
 }
 
@@ -298,6 +311,7 @@ fn test_modifiers() -> CombatModifierRegistry {
                 priority: 10,
                 cap: None,
                 terrain_type_filter: None,
+                condition: None,
             },
             CombatModifierDefinition {
                 id: TypeId::new(),
@@ -307,6 +321,7 @@ fn test_modifiers() -> CombatModifierRegistry {
                 priority: 5,
                 cap: None,
                 terrain_type_filter: None,
+                condition: None,
             },
         ],
     }
@@ -420,6 +435,39 @@ fn tool_mode_click_place_changes_tool() {
     assert_eq!(*harness.state(), EditorTool::Place);
 }
 
+#[test]
+fn tool_mode_click_vertex_changes_tool() {
+    let tool = EditorTool::Select;
+    let mut harness = Harness::new_ui_state(
+        |ui, tool| {
+            systems::render_tool_mode(ui, tool);
+        },
+        tool,
+    );
+
+    harness.get_by_label("Vertex").click();
+    harness.run();
+
+    assert_eq!(*harness.state(), EditorTool::VertexPaint);
+}
+
+#[test]
+fn vertex_palette_click_sets_active_type() {
+    let registry = test_registry();
+    let mut harness = Harness::new_ui_state(
+        move |ui, active: &mut hexorder_contracts::editor_ui::ActiveVertexType| {
+            systems::render_vertex_palette(ui, &registry, active);
+        },
+        hexorder_contracts::editor_ui::ActiveVertexType::default(),
+    );
+    harness.get_by_label("Vertex Palette");
+
+    harness.get_by_label("Plains").click();
+    harness.run();
+
+    assert_eq!(harness.state().type_name.as_deref(), Some("Plains"));
+}
+
 // ---------------------------------------------------------------------------
 // Types Tab (Entity Type Editor)
 // ---------------------------------------------------------------------------
@@ -643,6 +691,7 @@ fn constraints_tab_shows_heading() {
             ui,
             &mut state.constraint_registry,
             &state.concept_registry,
+            &EntityTypeRegistry::default(),
             &mut state.editor_state,
             &mut state.actions,
         );
@@ -663,6 +712,7 @@ fn constraints_tab_empty_shows_no_constraints_message() {
             ui,
             &mut state.constraint_registry,
             &state.concept_registry,
+            &EntityTypeRegistry::default(),
             &mut state.editor_state,
             &mut state.actions,
         );
@@ -683,6 +733,7 @@ fn constraints_tab_shows_constraint_names() {
             ui,
             &mut state.constraint_registry,
             &state.concept_registry,
+            &EntityTypeRegistry::default(),
             &mut state.editor_state,
             &mut state.actions,
         );
@@ -704,6 +755,7 @@ fn constraints_tab_auto_generated_shows_badge() {
             ui,
             &mut state.constraint_registry,
             &state.concept_registry,
+            &EntityTypeRegistry::default(),
             &mut state.editor_state,
             &mut state.actions,
         );
@@ -728,6 +780,7 @@ fn constraints_tab_delete_constraint_produces_action() {
                 value: PropertyValue::Int(0),
             },
             auto_generated: false,
+            gates: Vec::new(),
         }],
     };
     let constraint_id = single_constraint.constraints[0].id;
@@ -745,6 +798,7 @@ fn constraints_tab_delete_constraint_produces_action() {
                 ui,
                 &mut s.constraint_registry,
                 &s.concept_registry,
+                &EntityTypeRegistry::default(),
                 &mut s.editor_state,
                 &mut s.actions,
             );
@@ -893,6 +947,45 @@ fn validation_tab_shows_error_category_badge() {
     harness.get_by_label_contains("Dangling Ref");
 }
 
+#[test]
+fn rule_analysis_shows_no_issues_when_empty() {
+    let analysis = RuleAnalysis::default();
+    let harness = Harness::new_ui_state(
+        |ui, state| {
+            render_rules::render_rule_analysis(ui, &analysis, state);
+        },
+        EditorState::default(),
+    );
+    harness.get_by_label("Rule Analysis");
+    harness.get_by_label_contains("No rule issues found");
+}
+
+#[test]
+fn rule_analysis_link_jumps_to_subject_tab() {
+    let analysis = RuleAnalysis {
+        findings: vec![AnalysisFinding {
+            category: AnalysisCategory::Unsatisfiable,
+            message: "\"Strong enough\" can never be satisfied".to_string(),
+            subject: AnalysisSubject::Constraint(TypeId::new()),
+        }],
+    };
+    let mut harness = Harness::new_ui_state(
+        |ui, state| {
+            render_rules::render_rule_analysis(ui, &analysis, state);
+        },
+        EditorState::default(),
+    );
+    harness.get_by_label_contains("1 Warning(s)");
+    harness.get_by_label("Unsatisfiable");
+    harness.get_by_label_contains("can never be satisfied");
+
+    harness.get_by_label("Go to Constraints").click();
+    harness.run();
+
+    assert_eq!(harness.state().active_tab, OntologyTab::Constraints);
+    assert_eq!(harness.state().focus_dock_tab, Some(DockTab::Rules));
+}
+
 // ---------------------------------------------------------------------------
 // Cell Palette
 // ---------------------------------------------------------------------------
@@ -1570,6 +1663,7 @@ fn constraints_tab_shows_cross_compare_constraint() {
                 right_property: "defense".to_string(),
             },
             auto_generated: false,
+            gates: Vec::new(),
         }],
     };
     let mut state = ConstraintsState {
@@ -1583,6 +1677,7 @@ fn constraints_tab_shows_cross_compare_constraint() {
             ui,
             &mut state.constraint_registry,
             &state.concept_registry,
+            &EntityTypeRegistry::default(),
             &mut state.editor_state,
             &mut state.actions,
         );
@@ -1607,6 +1702,7 @@ fn constraints_tab_shows_path_budget_constraint() {
                 cost_property: "cost".to_string(),
             },
             auto_generated: false,
+            gates: Vec::new(),
         }],
     };
     let mut state = ConstraintsState {
@@ -1620,6 +1716,7 @@ fn constraints_tab_shows_path_budget_constraint() {
             ui,
             &mut state.constraint_registry,
             &state.concept_registry,
+            &EntityTypeRegistry::default(),
             &mut state.editor_state,
             &mut state.actions,
         );
@@ -1658,7 +1755,7 @@ fn inspector_no_tile_selected() {
     let enum_reg = EnumRegistry::default();
     let struct_reg = StructRegistry::default();
     let harness = Harness::new_ui(|ui| {
-        render_rules::render_inspector(ui, None, None, &registry, &enum_reg, &struct_reg);
+        render_rules::render_inspector(ui, None, None, &registry, &enum_reg, &struct_reg, &[]);
     });
     harness.get_by_label_contains("No tile selected");
 }
@@ -1670,7 +1767,7 @@ fn inspector_shows_position() {
     let struct_reg = StructRegistry::default();
     let pos = HexPosition { q: 3, r: 5 };
     let harness = Harness::new_ui(|ui| {
-        render_rules::render_inspector(ui, Some(pos), None, &registry, &enum_reg, &struct_reg);
+        render_rules::render_inspector(ui, Some(pos), None, &registry, &enum_reg, &struct_reg, &[]);
     });
     harness.get_by_label_contains("Position: (3, 5)");
 }
@@ -1682,7 +1779,7 @@ fn inspector_shows_no_cell_data() {
     let struct_reg = StructRegistry::default();
     let pos = HexPosition { q: 0, r: 0 };
     let harness = Harness::new_ui(|ui| {
-        render_rules::render_inspector(ui, Some(pos), None, &registry, &enum_reg, &struct_reg);
+        render_rules::render_inspector(ui, Some(pos), None, &registry, &enum_reg, &struct_reg, &[]);
     });
     harness.get_by_label_contains("No cell data");
 }
@@ -1705,6 +1802,7 @@ fn inspector_shows_type_name() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
         );
     });
     harness.get_by_label_contains("Type: Plains");
@@ -1728,6 +1826,7 @@ fn inspector_shows_property_label() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
         );
     });
     harness.get_by_label_contains("movement_cost:");
@@ -1760,6 +1859,7 @@ fn inspector_no_properties_label() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
         );
     });
     harness.get_by_label_contains("No properties");
@@ -1771,7 +1871,7 @@ fn inspector_heading_present() {
     let enum_reg = EnumRegistry::default();
     let struct_reg = StructRegistry::default();
     let harness = Harness::new_ui(|ui| {
-        render_rules::render_inspector(ui, None, None, &registry, &enum_reg, &struct_reg);
+        render_rules::render_inspector(ui, None, None, &registry, &enum_reg, &struct_reg, &[]);
     });
     harness.get_by_label_contains("Tile Inspector");
 }
@@ -1814,6 +1914,7 @@ fn unit_inspector_no_unit_selected() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
             &mut actions,
         );
     });
@@ -1833,6 +1934,7 @@ fn unit_inspector_heading_present() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
             &mut actions,
         );
     });
@@ -1856,6 +1958,7 @@ fn unit_inspector_shows_type_name() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
             &mut actions,
         );
     });
@@ -1879,6 +1982,7 @@ fn unit_inspector_shows_property_label() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
             &mut actions,
         );
     });
@@ -1902,6 +2006,7 @@ fn unit_inspector_delete_button() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
             &mut actions,
         );
     });
@@ -1934,6 +2039,7 @@ fn unit_inspector_no_properties_shows_delete_only() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
             &mut actions,
         );
     });
@@ -1943,193 +2049,1037 @@ fn unit_inspector_no_properties_shows_delete_only() {
 }
 
 // ---------------------------------------------------------------------------
-// Turn Tracker (render_play::render_turn_tracker)
+// Movement Interrupt (render_play::render_movement_interrupt_window)
 // ---------------------------------------------------------------------------
 
-#[test]
-fn turn_tracker_no_phases() {
-    let empty_structure = TurnStructure {
-        player_order: PlayerOrder::Alternating,
-        phases: vec![],
+fn interrupt_window_fixture() -> (InterruptPause, MovementInterruptRegistry) {
+    let rule = MovementInterruptRule {
+        id: TypeId::new(),
+        name: "Opportunity Fire".to_string(),
+        trigger: InterruptTrigger::Proximity { max: 1 },
+        reactor_type: None,
+        mover_type: None,
+        resolution: InterruptResolution::Table {
+            table: ResolutionTable::default(),
+            input_a_key: "reactor.strength".to_string(),
+            input_b_key: "mover.strength".to_string(),
+            dice: DicePool::single(6),
+        },
+        outcomes: vec![],
     };
-    let mut turn_state = TurnState::default();
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_turn_tracker(ui, &mut turn_state, &empty_structure);
-    });
-    harness.get_by_label_contains("No phases defined");
+    let pause = InterruptPause {
+        mover: Entity::PLACEHOLDER,
+        reactor: Entity::PLACEHOLDER,
+        rule_id: rule.id,
+        position: HexPosition::new(2, -1),
+        remaining: vec![HexPosition::new(3, -1)],
+        costs: vec![2],
+        resolved: vec![],
+    };
+    (pause, MovementInterruptRegistry { rules: vec![rule] })
 }
 
 #[test]
-fn turn_tracker_heading_present() {
-    let structure = test_turn_structure();
-    let mut turn_state = TurnState::default();
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
+fn movement_interrupt_window_shows_rule_and_reactor() {
+    let (pause, interrupts) = interrupt_window_fixture();
+    let harness = Harness::new(|ctx| {
+        render_play::render_movement_interrupt_window(ctx, &pause, &interrupts, "Artillery");
     });
-    harness.get_by_label_contains("Turn Tracker");
+    harness.get_by_label_contains("Opportunity Fire");
+    harness.get_by_label_contains("Artillery reacts at (2, -1)");
 }
 
 #[test]
-fn turn_tracker_shows_turn_number() {
-    let structure = test_turn_structure();
-    let mut turn_state = TurnState::default();
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
-    });
-    harness.get_by_label_contains("Turn 1");
+fn movement_interrupt_window_resolve_button_requests_resolution() {
+    let (pause, interrupts) = interrupt_window_fixture();
+    let mut harness = Harness::new_state(
+        |ctx, resolved: &mut bool| {
+            *resolved |=
+                render_play::render_movement_interrupt_window(ctx, &pause, &interrupts, "Unit");
+        },
+        false,
+    );
+    harness.get_by_label("Resolve").click();
+    harness.run();
+    assert!(*harness.state());
 }
 
-#[test]
-fn turn_tracker_shows_current_phase_marker() {
-    let structure = test_turn_structure();
-    let mut turn_state = TurnState::default();
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
-    });
-    // Current phase (index 0) is prefixed with ▶.
-    harness.get_by_label_contains("\u{25B6} Movement");
-}
+// ---------------------------------------------------------------------------
+// Overrun options and play log (render_play)
+// ---------------------------------------------------------------------------
 
 #[test]
-fn turn_tracker_shows_non_current_phases() {
-    let structure = test_turn_structure();
-    let mut turn_state = TurnState::default();
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
-    });
-    // Non-current phases are space-prefixed.
-    harness.get_by_label_contains("  Combat");
-    harness.get_by_label_contains("  Admin");
-}
+fn overrun_option_button_picks_defender_and_rule() {
+    use hexorder_contracts::mechanics::{OverrunRegistry, OverrunRule, OverrunTargets};
 
-#[test]
-fn turn_tracker_shows_phase_type_badge() {
-    let structure = test_turn_structure();
-    let mut turn_state = TurnState::default();
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
-    });
-    harness.get_by_label_contains("[Movement]");
+    let rule = OverrunRule {
+        id: TypeId::new(),
+        name: "Armor Overrun".to_string(),
+        mp_cost: 2,
+        attacker_type: None,
+        defender_type: None,
+        resolution: interrupt_window_fixture().1.rules[0].resolution.clone(),
+        outcomes: vec![],
+    };
+    let targets = OverrunTargets {
+        attacker: Some(Entity::PLACEHOLDER),
+        targets: vec![(Entity::PLACEHOLDER, rule.id)],
+    };
+    let rule_id = rule.id;
+    let overruns = OverrunRegistry { rules: vec![rule] };
+    let mut harness = Harness::new_ui_state(
+        |ui, picked: &mut Option<(Entity, TypeId)>| {
+            let label = |_| "Infantry at (1, 0)".to_string();
+            *picked = picked.or(render_play::render_overrun_options(
+                ui, &targets, &overruns, &label,
+            ));
+        },
+        None,
+    );
+    harness
+        .get_by_label_contains("Overrun Infantry at (1, 0)")
+        .click();
+    harness.run();
+    assert_eq!(*harness.state(), Some((Entity::PLACEHOLDER, rule_id)));
+    harness.get_by_label_contains("Armor Overrun (2 MP)");
 }
 
 #[test]
-fn turn_tracker_shows_phase_count() {
-    let structure = test_turn_structure();
-    let mut turn_state = TurnState::default();
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
-    });
-    harness.get_by_label_contains("Phase 1 of 3");
-}
+fn play_log_lists_entries_with_turn() {
+    use hexorder_contracts::mechanics::{PlayLog, PlayLogEntry};
 
-#[test]
-fn turn_tracker_advance_button_present() {
-    let structure = test_turn_structure();
-    let mut turn_state = TurnState::default();
+    let log = PlayLog {
+        entries: vec![PlayLogEntry {
+            turn_number: 3,
+            message: "Overrun: defender cleared".to_string(),
+        }],
+    };
     let harness = Harness::new_ui(|ui| {
-        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
+        render_play::render_play_log(ui, &log);
     });
-    harness.get_by_label_contains("Next");
+    harness.get_by_label_contains("T3  Overrun: defender cleared");
 }
 
 // ---------------------------------------------------------------------------
-// Property Value Editor coverage (via render_inspector with typed properties)
+// Combat rolls and roll history (render_play)
 // ---------------------------------------------------------------------------
 
+/// The combat roll uses the CRT's dice through `SimulationRng` and queues
+/// the roll and its lookup for the roll history.
 #[test]
-fn inspector_bool_property_renders() {
-    let type_id = TypeId::new();
-    let registry = EntityTypeRegistry {
-        types: vec![EntityType {
-            id: type_id,
-            name: "Terrain".to_string(),
-            role: EntityRole::BoardPosition,
-            color: Color::srgb(0.5, 0.5, 0.5),
-            properties: vec![PropertyDefinition {
-                id: TypeId::new(),
-                name: "passable".to_string(),
-                property_type: PropertyType::Bool,
-                default_value: PropertyValue::Bool(true),
-            }],
-        }],
-    };
-    let enum_reg = EnumRegistry::default();
-    let struct_reg = StructRegistry::default();
-    let pos = HexPosition { q: 0, r: 0 };
-    let mut entity_data = EntityData {
-        entity_type_id: type_id,
-        properties: std::collections::HashMap::new(),
+fn combat_roll_uses_crt_dice_and_queues_roll() {
+    struct S {
+        combat: ActiveCombat,
+        state: EditorState,
+        rng: SimulationRng,
+    }
+    let mut crt = test_crt();
+    crt.dice = DicePool::new(1, 1, 2);
+    let s = S {
+        combat: ActiveCombat::default(),
+        state: EditorState {
+            combat_attacker_strength: 2.0,
+            combat_defender_strength: 2.0,
+            ..EditorState::default()
+        },
+        rng: SimulationRng::new(42),
     };
-    let harness = Harness::new_ui(|ui| {
-        render_rules::render_inspector(
-            ui,
-            Some(pos),
-            Some(&mut entity_data),
-            &registry,
-            &enum_reg,
-            &struct_reg,
-        );
-    });
-    harness.get_by_label_contains("passable:");
+    let mut harness = Harness::new_ui_state(
+        |ui, s: &mut S| {
+            render_play::render_combat_panel(
+                ui,
+                &mut s.combat,
+                &crt,
+                &CombatModifierRegistry::default(),
+                &SelectedUnit::default(),
+                &EntityTypeRegistry::default(),
+                &mut s.state,
+                &mut s.rng,
+                &AreaMarkerRegistry::default(),
+                &|_| None,
+                &|_| None,
+                true,
+            );
+        },
+        s,
+    );
+    harness.get_by_label_contains("Roll 1d1+2").click();
+    harness.run();
+
+    let s = harness.state();
+    assert_eq!(s.combat.die_roll, Some(3));
+    assert_eq!(
+        s.combat.outcome.as_ref().map(|o| o.label.as_str()),
+        Some("DE")
+    );
+    assert_eq!(s.rng.roll_count(), 1);
+    assert_eq!(s.state.play_rolls.len(), 1);
+    let roll = &s.state.play_rolls[0];
+    assert_eq!(roll.context, "Standard CRT 1:1");
+    assert_eq!(roll.roll.total, 3);
+    let (table_id, resolution) = roll.table.as_ref().expect("the roll resolved the CRT");
+    assert_eq!(*table_id, crt.table.id);
+    assert_eq!(resolution.row_label, "2");
 }
 
 #[test]
-fn inspector_float_property_renders() {
-    let type_id = TypeId::new();
-    let registry = EntityTypeRegistry {
-        types: vec![EntityType {
-            id: type_id,
-            name: "River".to_string(),
-            role: EntityRole::BoardPosition,
-            color: Color::srgb(0.2, 0.4, 0.8),
-            properties: vec![PropertyDefinition {
-                id: TypeId::new(),
-                name: "depth".to_string(),
-                property_type: PropertyType::Float,
-                default_value: PropertyValue::Float(1.5),
-            }],
-        }],
+fn roll_history_lists_rolls_and_fixes_seed() {
+    use hexorder_contracts::simulation::{
+        RollHistory, RollHistoryEntry, SessionSeed, TableResolution, TableResult,
     };
-    let enum_reg = EnumRegistry::default();
-    let struct_reg = StructRegistry::default();
-    let pos = HexPosition { q: 0, r: 0 };
-    let mut entity_data = EntityData {
-        entity_type_id: type_id,
-        properties: std::collections::HashMap::new(),
+
+    let history = RollHistory {
+        entries: vec![RollHistoryEntry {
+            context: "Standard CRT 1:1".to_string(),
+            roll: hexorder_contracts::simulation::DiceRoll {
+                pool: DicePool::new(2, 6, 0),
+                values: vec![3, 5],
+                total: 8,
+            },
+            resolution: Some(TableResolution {
+                column_index: 1,
+                row_index: 1,
+                column_label: "1:1".to_string(),
+                row_label: "2".to_string(),
+                result: TableResult::Text("DE".to_string()),
+            }),
+        }],
     };
-    let harness = Harness::new_ui(|ui| {
-        render_rules::render_inspector(
-            ui,
-            Some(pos),
-            Some(&mut entity_data),
-            &registry,
-            &enum_reg,
-            &struct_reg,
-        );
-    });
-    harness.get_by_label_contains("depth:");
+    let mut harness = Harness::new_ui_state(
+        |ui, seed: &mut SessionSeed| {
+            render_play::render_roll_history(ui, &history, seed, 42);
+        },
+        SessionSeed::default(),
+    );
+    harness.get_by_label_contains("#1 Standard CRT 1:1: 2d6 [3, 5] = 8 \u{2192} 1:1 / 2: DE");
+    harness.get_by_label("Fix seed 42 for each session").click();
+    harness.run();
+    assert_eq!(*harness.state(), SessionSeed(Some(42)));
 }
 
+/// Odds computed by the strength model replace the strength inputs: each
+/// step is listed and the model's rounded odds pick the column.
 #[test]
-fn inspector_string_property_renders() {
-    let type_id = TypeId::new();
-    let registry = EntityTypeRegistry {
-        types: vec![EntityType {
-            id: type_id,
-            name: "City".to_string(),
-            role: EntityRole::BoardPosition,
-            color: Color::srgb(0.8, 0.8, 0.2),
-            properties: vec![PropertyDefinition {
-                id: TypeId::new(),
-                name: "label".to_string(),
-                property_type: PropertyType::String,
-                default_value: PropertyValue::String("unnamed".to_string()),
-            }],
-        }],
-    };
-    let enum_reg = EnumRegistry::default();
-    let struct_reg = StructRegistry::default();
-    let pos = HexPosition { q: 0, r: 0 };
+fn combat_panel_lists_computed_strength_steps() {
+    use hexorder_contracts::mechanics::CombatOdds;
+
+    struct S {
+        combat: ActiveCombat,
+        state: EditorState,
+        rng: SimulationRng,
+    }
+    let mut crt = test_crt();
+    crt.dice = DicePool::new(1, 1, 2);
+    let s = S {
+        combat: ActiveCombat {
+            odds: Some(CombatOdds {
+                attacker_strength: 3.0,
+                defender_strength: 5.0,
+                raw_value: 0.5,
+                steps: vec![
+                    ("Attack (Infantry 3)".to_string(), 3.0),
+                    ("Defense (Infantry 2)".to_string(), 2.0),
+                    ("Terrain \u{00d7}2.5".to_string(), 5.0),
+                    ("Ratio 3:5".to_string(), 0.5),
+                ],
+            }),
+            ..ActiveCombat::default()
+        },
+        state: EditorState::default(),
+        rng: SimulationRng::new(42),
+    };
+    let mut harness = Harness::new_ui_state(
+        |ui, s: &mut S| {
+            render_play::render_combat_panel(
+                ui,
+                &mut s.combat,
+                &crt,
+                &CombatModifierRegistry::default(),
+                &SelectedUnit::default(),
+                &EntityTypeRegistry::default(),
+                &mut s.state,
+                &mut s.rng,
+                &AreaMarkerRegistry::default(),
+                &|_| None,
+                &|_| None,
+                true,
+            );
+        },
+        s,
+    );
+    harness.get_by_label_contains("Computed Strengths");
+    harness.get_by_label_contains("Terrain \u{00d7}2.5: 5");
+    harness.get_by_label_contains("Ratio 3:5: 0.50");
+    // The model's rounded odds, not the raw 3:5.
+    harness.get_by_label_contains("Odds: 0.50:1");
+    harness.get_by_label_contains("Base column: 1:2");
+    harness.get_by_label_contains("Roll 1d1+2").click();
+    harness.run();
+
+    let s = harness.state();
+    assert_eq!(s.combat.raw_value, Some(0.5));
+    assert!((s.state.combat_defender_strength - 5.0).abs() < f64::EPSILON);
+    assert_eq!(
+        s.combat.outcome.as_ref().map(|o| o.label.as_str()),
+        Some("AR")
+    );
+}
+
+/// The combat panel names the table the combat is resolved on.
+#[test]
+fn combat_panel_names_the_combat_table() {
+    let crt = CombatResultsTable {
+        name: "River Assault".to_string(),
+        ..test_crt()
+    };
+    let harness = Harness::new_ui_state(
+        |ui, state: &mut (ActiveCombat, EditorState, SimulationRng)| {
+            render_play::render_combat_panel(
+                ui,
+                &mut state.0,
+                &crt,
+                &CombatModifierRegistry::default(),
+                &SelectedUnit::default(),
+                &EntityTypeRegistry::default(),
+                &mut state.1,
+                &mut state.2,
+                &AreaMarkerRegistry::default(),
+                &|_| None,
+                &|_| None,
+                true,
+            );
+        },
+        (
+            ActiveCombat::default(),
+            EditorState::default(),
+            SimulationRng::new(1),
+        ),
+    );
+    harness.get_by_label("Table: River Assault");
+}
+
+/// Adding a conditional table copies the default's layout and selects it
+/// for editing; its phases, factions and condition are then chosen.
+#[test]
+fn combat_tables_add_and_select_a_conditional_table() {
+    use hexorder_contracts::game_system::{Faction, FactionRegistry};
+
+    let default = test_crt();
+    let phase_id = TypeId::new();
+    let turn_structure = TurnStructure {
+        phases: vec![Phase {
+            id: phase_id,
+            name: "Assault".to_string(),
+            phase_type: PhaseType::Combat,
+            description: String::new(),
+        }],
+        ..TurnStructure::default()
+    };
+    let faction_id = TypeId::new();
+    let factions = FactionRegistry {
+        factions: vec![Faction {
+            id: faction_id,
+            name: "Blue".to_string(),
+            color: Color::WHITE,
+            player_order: 0,
+        }],
+    };
+    let constraints = ConstraintRegistry::default();
+    let mut harness = Harness::new_ui_state(
+        |ui, state: &mut (CombatTableRegistry, EditorState)| {
+            render_rules::render_combat_tables(
+                ui,
+                &default,
+                &mut state.0,
+                &turn_structure,
+                &factions,
+                &constraints,
+                &mut state.1,
+            );
+        },
+        (
+            CombatTableRegistry::default(),
+            EditorState {
+                new_combat_table_name: "River".to_string(),
+                ..EditorState::default()
+            },
+        ),
+    );
+    harness.get_by_label("Add Table").click();
+    harness.run();
+    harness.get_by_label("Assault").click();
+    harness.run();
+    harness.get_by_label("Blue").click();
+    harness.run();
+
+    let (tables, state) = harness.state();
+    assert_eq!(tables.tables.len(), 1);
+    let table = &tables.tables[0];
+    assert_eq!(table.name, "River");
+    assert_ne!(table.id, default.id);
+    assert_eq!(table.table.columns.len(), default.table.columns.len());
+    assert_eq!(state.edited_combat_table, Some(table.id));
+    assert_eq!(table.selection.phases, vec![phase_id]);
+    assert_eq!(table.selection.factions, vec![faction_id]);
+    assert!(table.selection.condition.is_none());
+}
+
+/// The strength model is configured from the combat concept's bindings.
+#[test]
+fn combat_strength_model_picks_properties_and_terrain() {
+    use hexorder_contracts::mechanics::StrengthRounding;
+    use hexorder_contracts::ontology::{ConceptBinding, PropertyBinding};
+
+    let entity_types = test_registry();
+    let (concept_id, role_id) = (TypeId::new(), TypeId::new());
+    let concepts = ConceptRegistry {
+        concepts: vec![Concept {
+            id: concept_id,
+            name: "Combat".to_string(),
+            description: String::new(),
+            role_labels: vec![ConceptRole {
+                id: role_id,
+                name: "Striker".to_string(),
+                allowed_entity_roles: Vec::new(),
+            }],
+        }],
+        bindings: vec![ConceptBinding {
+            id: TypeId::new(),
+            entity_type_id: entity_types.types[1].id,
+            concept_id,
+            concept_role_id: TypeId::new(),
+            property_bindings: vec![PropertyBinding {
+                property_id: TypeId::new(),
+                concept_local_name: "attack".to_string(),
+            }],
+        }],
+    };
+    let mut harness = Harness::new_ui_state(
+        |ui, crt: &mut CombatResultsTable| {
+            render_rules::render_combat_strength(ui, crt, &concepts, &entity_types);
+        },
+        test_crt(),
+    );
+    harness.get_by_label_contains("entered by hand");
+
+    harness.state_mut().combat_concept_id = Some(concept_id);
+    harness.run();
+    harness.get_by_label("Attacker").click();
+    harness.run();
+    harness.get_by_label("Add Terrain Multiplier").click();
+    harness.run();
+    // The first role picker binds the attacker role.
+    harness
+        .get_all_by_label("None")
+        .next()
+        .expect("attacker role picker")
+        .click();
+    harness.run();
+    harness.get_by_label("Striker").click();
+    harness.run();
+
+    let crt = harness.state();
+    assert_eq!(crt.roles.attacker, Some(role_id));
+    assert_eq!(crt.strength.rounding, StrengthRounding::AttackerFavor);
+    assert_eq!(crt.strength.terrain_multipliers.len(), 1);
+    assert_eq!(
+        crt.strength.terrain_multipliers[0].terrain_type_id,
+        entity_types.types[0].id
+    );
+}
+
+/// Losses can be allocated by a property bound in the combat concept.
+#[test]
+fn combat_strength_model_allocates_losses_by_property() {
+    use hexorder_contracts::mechanics::LossAllocation;
+    use hexorder_contracts::ontology::{ConceptBinding, PropertyBinding};
+
+    let entity_types = test_registry();
+    let concept_id = TypeId::new();
+    let concepts = ConceptRegistry {
+        concepts: Vec::new(),
+        bindings: vec![ConceptBinding {
+            id: TypeId::new(),
+            entity_type_id: entity_types.types[1].id,
+            concept_id,
+            concept_role_id: TypeId::new(),
+            property_bindings: vec![PropertyBinding {
+                property_id: TypeId::new(),
+                concept_local_name: "quality".to_string(),
+            }],
+        }],
+    };
+    let mut crt = test_crt();
+    crt.combat_concept_id = Some(concept_id);
+    let mut harness = Harness::new_ui_state(
+        |ui, crt: &mut CombatResultsTable| {
+            render_rules::render_combat_strength(ui, crt, &concepts, &entity_types);
+        },
+        crt,
+    );
+    harness.get_by_label("By Property").click();
+    harness.run();
+
+    assert_eq!(
+        harness.state().losses.allocation,
+        LossAllocation::ByProperty("quality".to_string())
+    );
+    harness.get_by_label("Highest first:");
+}
+
+/// Every participant is listed with why it may not attack, and the roll
+/// waits until the ineligible ones are removed.
+#[test]
+fn combat_panel_blocks_roll_for_ineligible_participants() {
+    use hexorder_contracts::mechanics::AttackIneligibility;
+
+    struct S {
+        combat: ActiveCombat,
+        state: EditorState,
+        rng: SimulationRng,
+    }
+    let mut crt = test_crt();
+    crt.dice = DicePool::new(1, 1, 2);
+    let (attacker, defender) = (Entity::from_bits(1), Entity::from_bits(2));
+    let mut combat = ActiveCombat::default();
+    combat.add_attacker(attacker);
+    combat.add_defender(defender);
+    combat.ineligible = vec![(attacker, AttackIneligibility::AlreadyAttacked)];
+    let s = S {
+        combat,
+        state: EditorState {
+            combat_attacker_strength: 2.0,
+            combat_defender_strength: 2.0,
+            ..EditorState::default()
+        },
+        rng: SimulationRng::new(42),
+    };
+    let mut harness = Harness::new_ui_state(
+        |ui, s: &mut S| {
+            render_play::render_combat_panel(
+                ui,
+                &mut s.combat,
+                &crt,
+                &CombatModifierRegistry::default(),
+                &SelectedUnit::default(),
+                &EntityTypeRegistry::default(),
+                &mut s.state,
+                &mut s.rng,
+                &AreaMarkerRegistry::default(),
+                &|_| None,
+                &|_| None,
+                true,
+            );
+        },
+        s,
+    );
+    harness.get_by_label("already attacked this phase");
+    harness.get_by_label_contains("Remove ineligible participants");
+    harness.get_by_label_contains("Roll 1d1+2").click();
+    harness.run();
+    assert_eq!(harness.state().combat.die_roll, None);
+
+    // Removing the attacker that already attacked leaves no attacker.
+    harness
+        .get_all_by_label("x")
+        .next()
+        .expect("remove")
+        .click();
+    harness.run();
+    assert_eq!(harness.state().combat.attacker, None);
+}
+
+/// The roll spreads the outcome's step losses over the loss candidates.
+#[test]
+fn combat_roll_allocates_step_losses() {
+    use hexorder_contracts::mechanics::{CombatSide, LossCandidate, OutcomeEffect};
+
+    struct S {
+        combat: ActiveCombat,
+        state: EditorState,
+        rng: SimulationRng,
+    }
+    let mut crt = test_crt();
+    crt.dice = DicePool::new(1, 1, 2);
+    crt.outcomes[1][1].effect = Some(OutcomeEffect::StepLoss { steps: 2 });
+    let (first, second) = (Entity::from_bits(1), Entity::from_bits(2));
+    let candidate = |entity| LossCandidate {
+        entity,
+        side: CombatSide::Defender,
+        priority: 0.0,
+        steps: 1,
+    };
+    let s = S {
+        combat: ActiveCombat {
+            loss_candidates: vec![candidate(first), candidate(second)],
+            ..ActiveCombat::default()
+        },
+        state: EditorState {
+            combat_attacker_strength: 2.0,
+            combat_defender_strength: 2.0,
+            ..EditorState::default()
+        },
+        rng: SimulationRng::new(42),
+    };
+    let mut harness = Harness::new_ui_state(
+        |ui, s: &mut S| {
+            render_play::render_combat_panel(
+                ui,
+                &mut s.combat,
+                &crt,
+                &CombatModifierRegistry::default(),
+                &SelectedUnit::default(),
+                &EntityTypeRegistry::default(),
+                &mut s.state,
+                &mut s.rng,
+                &AreaMarkerRegistry::default(),
+                &|_| None,
+                &|_| None,
+                true,
+            );
+        },
+        s,
+    );
+    harness.get_by_label_contains("Roll 1d1+2").click();
+    harness.run();
+
+    assert_eq!(
+        harness.state().combat.step_losses,
+        vec![(first, 1), (second, 1)]
+    );
+    harness.get_by_label("Step Losses");
+}
+
+/// Retreats waiting for a choice offer a button per path, and the advance
+/// options the rules engine lists offer a button each.
+#[test]
+fn combat_panel_queues_retreat_choice_and_advance() {
+    use hexorder_contracts::mechanics::{AdvanceOption, PostResolutionAction, RetreatChoice};
+
+    let (attacker, defender) = (Entity::from_bits(1), Entity::from_bits(2));
+    let mut combat = ActiveCombat::default();
+    combat.add_attacker(attacker);
+    combat.add_defender(defender);
+    combat.retreats = vec![RetreatChoice {
+        units: vec![defender],
+        paths: vec![
+            vec![HexPosition::new(1, 0), HexPosition::new(2, 0)],
+            vec![HexPosition::new(1, 0), HexPosition::new(1, 1)],
+        ],
+    }];
+    combat.vacated = vec![HexPosition::new(-1, 1)];
+    combat.advances = vec![AdvanceOption {
+        unit: attacker,
+        from: HexPosition::new(0, 0),
+        to: HexPosition::new(-1, 1),
+    }];
+    let crt = test_crt();
+    let mut harness = Harness::new_ui_state(
+        |ui, s: &mut (ActiveCombat, EditorState)| {
+            render_play::render_combat_panel(
+                ui,
+                &mut s.0,
+                &crt,
+                &CombatModifierRegistry::default(),
+                &SelectedUnit::default(),
+                &EntityTypeRegistry::default(),
+                &mut s.1,
+                &mut SimulationRng::new(1),
+                &AreaMarkerRegistry::default(),
+                &|_| None,
+                &|_| None,
+                true,
+            );
+        },
+        (combat, EditorState::default()),
+    );
+    harness.get_by_label("Choose Retreat");
+    harness.get_by_label_contains("Retreat (1,1)").click();
+    harness.run();
+    harness.get_by_label_contains("advances to (-1,1)").click();
+    harness.run();
+
+    let moves = &harness.state().1.combat_moves;
+    assert_eq!(moves.len(), 2);
+    assert_eq!(moves[0].units, [defender]);
+    assert_eq!(moves[0].path.last(), Some(&HexPosition::new(1, 1)));
+    assert_eq!(moves[0].action, PostResolutionAction::Retreat);
+    assert_eq!(moves[1].units, [attacker]);
+    assert_eq!(
+        moves[1].path,
+        [HexPosition::new(0, 0), HexPosition::new(-1, 1)]
+    );
+    assert_eq!(moves[1].action, PostResolutionAction::Advance);
+}
+
+// ---------------------------------------------------------------------------
+// Odds analysis (render_analysis::render_odds_analysis)
+// ---------------------------------------------------------------------------
+
+struct AnalysisState {
+    editor_state: EditorState,
+    actions: Vec<EditorAction>,
+}
+
+fn analysis_harness(
+    crt: CombatResultsTable,
+    modifiers: CombatModifierRegistry,
+    area_markers: AreaMarkerRegistry,
+    interrupts: MovementInterruptRegistry,
+    editor_state: EditorState,
+) -> Harness<'static, AnalysisState> {
+    let overruns = hexorder_contracts::mechanics::OverrunRegistry::default();
+    Harness::new_ui_state(
+        move |ui, s: &mut AnalysisState| {
+            systems::render_odds_analysis(
+                ui,
+                &crt,
+                &modifiers,
+                &area_markers,
+                &interrupts,
+                &overruns,
+                &mut s.editor_state,
+                &mut s.actions,
+            );
+        },
+        AnalysisState {
+            editor_state,
+            actions: Vec::new(),
+        },
+    )
+}
+
+#[test]
+fn odds_analysis_shows_crt_columns_and_exports_csv() {
+    let mut harness = analysis_harness(
+        test_crt(),
+        CombatModifierRegistry::default(),
+        AreaMarkerRegistry::default(),
+        MovementInterruptRegistry::default(),
+        EditorState::default(),
+    );
+    harness.get_by_label_contains("CRT Odds");
+    harness.get_by_label("1:2");
+    harness.get_by_label_contains("No movement interrupt or overrun rules");
+    harness.get_by_label("Export CSV").click();
+    harness.run();
+
+    let actions = &harness.state().actions;
+    assert_eq!(actions.len(), 1);
+    let EditorAction::ExportCsv {
+        file_name,
+        contents,
+    } = &actions[0]
+    else {
+        panic!("expected a CSV export, got {actions:?}");
+    };
+    assert_eq!(file_name, "crt-odds");
+    assert!(contents.starts_with("column,"));
+    assert_eq!(
+        contents.lines().count(),
+        3,
+        "header and one line per column"
+    );
+}
+
+#[test]
+fn odds_analysis_applies_area_marker_shift() {
+    use hexorder_contracts::mechanics::{AreaEffect, AreaMarker, MarkerDuration};
+
+    let markers = AreaMarkerRegistry {
+        markers: vec![AreaMarker {
+            marker_type: "Barrage".to_string(),
+            center: HexPosition::new(0, 0),
+            radius: 1,
+            effects: vec![AreaEffect::ColumnShift { shift: 1 }],
+            duration: MarkerDuration::Permanent,
+        }],
+    };
+    let state = EditorState {
+        analysis_attacker_strength: 1.0,
+        analysis_defender_strength: 2.0,
+        ..EditorState::default()
+    };
+    let mut harness = analysis_harness(
+        test_crt(),
+        CombatModifierRegistry::default(),
+        markers,
+        MovementInterruptRegistry::default(),
+        state,
+    );
+    harness.get_by_label_contains("Column 1:2 after +0 shift");
+    harness.get_by_label_contains("Barrage (0, 0): +1").click();
+    harness.run();
+
+    assert_eq!(
+        harness.state().editor_state.analysis_area_markers,
+        vec![("Barrage".to_string(), HexPosition::new(0, 0))]
+    );
+    harness.get_by_label_contains("Column 1:1 after +1 shift");
+}
+
+#[test]
+fn odds_analysis_applies_only_checked_modifiers() {
+    let modifiers = test_modifiers();
+    let forest = modifiers.modifiers[0].id;
+    let mut harness = analysis_harness(
+        test_crt(),
+        modifiers,
+        AreaMarkerRegistry::default(),
+        MovementInterruptRegistry::default(),
+        EditorState::default(),
+    );
+    harness.get_by_label_contains("Column 1:1 after +0 shift");
+    harness.get_by_label("Forest Defense: -1").click();
+    harness.run();
+
+    assert_eq!(
+        harness.state().editor_state.analysis_modifiers,
+        vec![forest]
+    );
+    harness.get_by_label_contains("Column 1:2 after -1 shift");
+}
+
+#[test]
+fn odds_analysis_simulates_rule_resolutions() {
+    let (_, mut interrupts) = interrupt_window_fixture();
+    if let InterruptResolution::Table { table, .. } = &mut interrupts.rules[0].resolution {
+        table.columns = vec![TableColumn {
+            label: "Any".to_string(),
+            column_type: ColumnType::Direct,
+            threshold: 0.0,
+        }];
+        table.rows = vec![
+            TableRow {
+                label: "1-3".to_string(),
+                value_min: 1,
+                value_max: 3,
+            },
+            TableRow {
+                label: "4-6".to_string(),
+                value_min: 4,
+                value_max: 6,
+            },
+        ];
+        table.outcomes = vec![
+            vec![TableResult::Text("Halt".to_string())],
+            vec![TableResult::Text("Pass".to_string())],
+        ];
+    }
+    let state = EditorState {
+        analysis_runs: 200,
+        ..EditorState::default()
+    };
+    let mut harness = analysis_harness(
+        test_crt(),
+        CombatModifierRegistry::default(),
+        AreaMarkerRegistry::default(),
+        interrupts,
+        state,
+    );
+    harness.get_by_label("reactor.strength");
+    harness.get_by_label("Simulate").click();
+    harness.run();
+
+    let results = &harness.state().editor_state.analysis_results;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].label, "Opportunity Fire");
+    let total: f64 = results[0].outcomes.iter().map(|o| o.probability).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    assert!(results[0].outcomes.iter().any(|o| o.label == "Halt"));
+    harness.get_by_label_contains("200 runs each");
+}
+
+// ---------------------------------------------------------------------------
+// Turn Tracker (render_play::render_turn_tracker)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn turn_tracker_no_phases() {
+    let empty_structure = TurnStructure {
+        player_order: PlayerOrder::Alternating,
+        phases: vec![],
+    };
+    let mut turn_state = TurnState::default();
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_turn_tracker(ui, &mut turn_state, &empty_structure);
+    });
+    harness.get_by_label_contains("No phases defined");
+}
+
+#[test]
+fn turn_tracker_heading_present() {
+    let structure = test_turn_structure();
+    let mut turn_state = TurnState::default();
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
+    });
+    harness.get_by_label_contains("Turn Tracker");
+}
+
+#[test]
+fn turn_tracker_shows_turn_number() {
+    let structure = test_turn_structure();
+    let mut turn_state = TurnState::default();
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
+    });
+    harness.get_by_label_contains("Turn 1");
+}
+
+#[test]
+fn turn_tracker_shows_current_phase_marker() {
+    let structure = test_turn_structure();
+    let mut turn_state = TurnState::default();
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
+    });
+    // Current phase (index 0) is prefixed with ▶.
+    harness.get_by_label_contains("\u{25B6} Movement");
+}
+
+#[test]
+fn turn_tracker_shows_non_current_phases() {
+    let structure = test_turn_structure();
+    let mut turn_state = TurnState::default();
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
+    });
+    // Non-current phases are space-prefixed.
+    harness.get_by_label_contains("  Combat");
+    harness.get_by_label_contains("  Admin");
+}
+
+#[test]
+fn turn_tracker_shows_phase_type_badge() {
+    let structure = test_turn_structure();
+    let mut turn_state = TurnState::default();
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
+    });
+    harness.get_by_label_contains("[Movement]");
+}
+
+#[test]
+fn turn_tracker_shows_phase_count() {
+    let structure = test_turn_structure();
+    let mut turn_state = TurnState::default();
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
+    });
+    harness.get_by_label_contains("Phase 1 of 3");
+}
+
+#[test]
+fn turn_tracker_advance_button_present() {
+    let structure = test_turn_structure();
+    let mut turn_state = TurnState::default();
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_turn_tracker(ui, &mut turn_state, &structure);
+    });
+    harness.get_by_label_contains("Next");
+}
+
+// ---------------------------------------------------------------------------
+// Property Value Editor coverage (via render_inspector with typed properties)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn inspector_bool_property_renders() {
+    let type_id = TypeId::new();
+    let registry = EntityTypeRegistry {
+        types: vec![EntityType {
+            id: type_id,
+            name: "Terrain".to_string(),
+            role: EntityRole::BoardPosition,
+            color: Color::srgb(0.5, 0.5, 0.5),
+            properties: vec![PropertyDefinition {
+                id: TypeId::new(),
+                name: "passable".to_string(),
+                property_type: PropertyType::Bool,
+                default_value: PropertyValue::Bool(true),
+            }],
+        }],
+    };
+    let enum_reg = EnumRegistry::default();
+    let struct_reg = StructRegistry::default();
+    let pos = HexPosition { q: 0, r: 0 };
+    let mut entity_data = EntityData {
+        entity_type_id: type_id,
+        properties: std::collections::HashMap::new(),
+    };
+    let harness = Harness::new_ui(|ui| {
+        render_rules::render_inspector(
+            ui,
+            Some(pos),
+            Some(&mut entity_data),
+            &registry,
+            &enum_reg,
+            &struct_reg,
+            &[],
+        );
+    });
+    harness.get_by_label_contains("passable:");
+}
+
+#[test]
+fn inspector_float_property_renders() {
+    let type_id = TypeId::new();
+    let registry = EntityTypeRegistry {
+        types: vec![EntityType {
+            id: type_id,
+            name: "River".to_string(),
+            role: EntityRole::BoardPosition,
+            color: Color::srgb(0.2, 0.4, 0.8),
+            properties: vec![PropertyDefinition {
+                id: TypeId::new(),
+                name: "depth".to_string(),
+                property_type: PropertyType::Float,
+                default_value: PropertyValue::Float(1.5),
+            }],
+        }],
+    };
+    let enum_reg = EnumRegistry::default();
+    let struct_reg = StructRegistry::default();
+    let pos = HexPosition { q: 0, r: 0 };
+    let mut entity_data = EntityData {
+        entity_type_id: type_id,
+        properties: std::collections::HashMap::new(),
+    };
+    let harness = Harness::new_ui(|ui| {
+        render_rules::render_inspector(
+            ui,
+            Some(pos),
+            Some(&mut entity_data),
+            &registry,
+            &enum_reg,
+            &struct_reg,
+            &[],
+        );
+    });
+    harness.get_by_label_contains("depth:");
+}
+
+#[test]
+fn inspector_string_property_renders() {
+    let type_id = TypeId::new();
+    let registry = EntityTypeRegistry {
+        types: vec![EntityType {
+            id: type_id,
+            name: "City".to_string(),
+            role: EntityRole::BoardPosition,
+            color: Color::srgb(0.8, 0.8, 0.2),
+            properties: vec![PropertyDefinition {
+                id: TypeId::new(),
+                name: "label".to_string(),
+                property_type: PropertyType::String,
+                default_value: PropertyValue::String("unnamed".to_string()),
+            }],
+        }],
+    };
+    let enum_reg = EnumRegistry::default();
+    let struct_reg = StructRegistry::default();
+    let pos = HexPosition { q: 0, r: 0 };
     let mut entity_data = EntityData {
         entity_type_id: type_id,
         properties: std::collections::HashMap::new(),
@@ -2142,6 +3092,7 @@ fn inspector_string_property_renders() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
         );
     });
     harness.get_by_label_contains("label:");
@@ -2188,6 +3139,7 @@ fn inspector_enum_property_renders() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
         );
     });
     harness.get_by_label_contains("terrain:");
@@ -2225,6 +3177,7 @@ fn inspector_entity_ref_property_renders() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
         );
     });
     harness.get_by_label_contains("leader:");
@@ -2270,6 +3223,7 @@ fn inspector_multiple_properties_renders() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
         );
     });
     harness.get_by_label_contains("defense_bonus:");
@@ -2832,6 +3786,7 @@ fn constraints_tab_shows_constraint_details_when_opened() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -2853,6 +3808,7 @@ fn constraints_tab_auto_generated_badge() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -2890,6 +3846,11 @@ fn mechanics_tab_shows_differential_column() {
             effect: None,
         }]],
         combat_concept_id: None,
+        dice: DicePool::single(6),
+        strength: CombatStrengthModel::default(),
+        losses: LossRules::default(),
+        roles: CombatRoles::default(),
+        selection: CrtSelection::default(),
     };
     let structure = test_turn_structure();
     let modifiers = CombatModifierRegistry::default();
@@ -2953,6 +3914,7 @@ fn combat_panel_no_crt_defined() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -2985,6 +3947,7 @@ fn combat_panel_with_crt_shows_strengths() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -2992,8 +3955,8 @@ fn combat_panel_with_crt_shows_strengths() {
         );
     });
     harness.get_by_label_contains("Combat Resolution");
-    harness.get_by_label_contains("Attacker:");
-    harness.get_by_label_contains("Defender:");
+    harness.get_by_label("Attackers");
+    harness.get_by_label("Defenders");
     harness.get_by_label_contains("Strengths");
 }
 
@@ -3020,14 +3983,90 @@ fn combat_panel_with_modifiers_shows_breakdown() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
+            &AreaMarkerRegistry::default(),
+            &|_| None,
+            &|_| None,
+            true,
+        );
+    });
+    harness.get_by_label_contains("Modifiers");
+    harness.get_by_label_contains("Total shift:");
+}
+
+/// A conditional modifier that failed shows why and doesn't shift the column.
+#[test]
+fn combat_panel_skips_modifiers_whose_condition_failed() {
+    let crt = test_crt();
+    let modifiers = test_modifiers();
+    let flanking = &modifiers.modifiers[1];
+    let mut active_combat = ActiveCombat {
+        modifier_results: vec![ValidationResult {
+            constraint_id: flanking.id,
+            constraint_name: flanking.name.clone(),
+            satisfied: false,
+            explanation: "defender terrain is Plains, not Forest".to_string(),
+        }],
+        ..ActiveCombat::default()
+    };
+    let selected_unit = SelectedUnit::default();
+    let entity_types = test_registry();
+    let mut editor_state = EditorState {
+        combat_attacker_strength: 3.0,
+        combat_defender_strength: 3.0,
+        ..EditorState::default()
+    };
+
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_combat_panel(
+            ui,
+            &mut active_combat,
+            &crt,
+            &modifiers,
+            &selected_unit,
+            &entity_types,
+            &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
             true,
         );
     });
-    harness.get_by_label_contains("Modifiers");
-    harness.get_by_label_contains("Total shift:");
+    harness.get_by_label_contains("Flanking: not applied");
+    harness.get_by_label_contains("Forest Defense: -1");
+    harness.get_by_label_contains("Total shift: -1");
+    assert!(harness.query_by_label_contains("Flanking: +2").is_none());
+}
+
+/// Picking a constraint as a modifier's condition emits an action.
+#[test]
+fn combat_modifier_conditions_pick_a_constraint() {
+    let modifiers = test_modifiers();
+    let constraints = test_constraint_registry();
+    let forest_id = modifiers.modifiers[0].id;
+    let mut harness = Harness::new_ui_state(
+        |ui, actions: &mut Vec<EditorAction>| {
+            render_rules::render_combat_modifier_conditions(ui, &modifiers, &constraints, actions);
+        },
+        Vec::new(),
+    );
+    harness.get_by_label("Modifier Conditions");
+    harness
+        .get_all_by_label("Always")
+        .next()
+        .expect("a condition picker per modifier")
+        .click();
+    harness.run();
+    harness.get_by_label("Budget >= 0").click();
+    harness.run();
+
+    let expected = constraints.constraints[0].expression.clone();
+    assert!(harness.state().iter().any(|a| matches!(
+        a,
+        EditorAction::SetCombatModifierCondition { id, condition: Some(expr) }
+            if *id == forest_id && *expr == expected
+    )));
 }
 
 /// Tests combat panel with pre-existing outcome.
@@ -3060,6 +4099,7 @@ fn combat_panel_shows_outcome_result() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -3100,6 +4140,7 @@ fn combat_panel_shows_outcome_effects() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -3160,6 +4201,7 @@ fn combat_panel_shows_no_effect() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -3184,6 +4226,7 @@ fn combat_panel_shows_retreat_effect() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -3208,6 +4251,7 @@ fn combat_panel_shows_step_loss_effect() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -3232,6 +4276,7 @@ fn combat_panel_shows_attacker_step_loss_effect() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -3259,6 +4304,7 @@ fn combat_panel_shows_exchange_effect() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -3283,6 +4329,7 @@ fn combat_panel_shows_attacker_eliminated_effect() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -3319,6 +4366,7 @@ fn combat_panel_shows_below_minimum_threshold() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
@@ -3402,6 +4450,7 @@ fn property_value_editor_color() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3425,6 +4474,7 @@ fn property_value_editor_list_with_items() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3449,6 +4499,7 @@ fn property_value_editor_list_nested_limit() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             3, // depth >= 3
         );
     });
@@ -3481,6 +4532,7 @@ fn property_value_editor_map_with_entries() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3505,6 +4557,7 @@ fn property_value_editor_map_nested_limit() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             3,
         );
     });
@@ -3543,6 +4596,7 @@ fn property_value_editor_struct_known() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3567,6 +4621,7 @@ fn property_value_editor_struct_unknown() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3594,6 +4649,7 @@ fn property_value_editor_struct_nested_limit() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             3,
         );
     });
@@ -3618,6 +4674,7 @@ fn property_value_editor_int_range_bounded() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3641,6 +4698,7 @@ fn property_value_editor_int_range_unbounded() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3664,6 +4722,7 @@ fn property_value_editor_float_range_bounded() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3687,6 +4746,7 @@ fn property_value_editor_float_range_unbounded() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3712,6 +4772,7 @@ fn property_value_editor_entity_ref_with_role_filter() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -3736,6 +4797,7 @@ fn inspector_unknown_entity_type() {
             &registry,
             &enum_registry,
             &struct_registry,
+            &[],
         );
     });
     harness.get_by_label_contains("Unknown");
@@ -3759,6 +4821,7 @@ fn unit_inspector_unknown_entity_type() {
             &registry,
             &enum_registry,
             &struct_registry,
+            &[],
             &mut actions,
         );
     });
@@ -4040,6 +5103,7 @@ fn constraints_tab_shows_new_constraint_form() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -4064,6 +5128,7 @@ fn constraints_tab_property_compare_shows_fields() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -4089,6 +5154,7 @@ fn constraints_tab_path_budget_shows_fields() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -4114,6 +5180,7 @@ fn constraints_tab_cross_compare_shows_placeholder() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -4565,6 +5632,7 @@ fn property_value_editor_bool() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -4590,6 +5658,7 @@ fn property_value_editor_int() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -4614,6 +5683,7 @@ fn property_value_editor_float() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -4638,6 +5708,7 @@ fn property_value_editor_string() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -4663,6 +5734,7 @@ fn property_value_editor_enum() {
             &enum_reg,
             &struct_reg,
             &entity_reg,
+            &[],
             0,
         );
     });
@@ -4693,6 +5765,11 @@ fn mechanics_tab_differential_crt_column_label() {
         },
         outcomes: vec![],
         combat_concept_id: None,
+        dice: DicePool::single(6),
+        strength: CombatStrengthModel::default(),
+        losses: LossRules::default(),
+        roles: CombatRoles::default(),
+        selection: CrtSelection::default(),
     };
     let modifiers = CombatModifierRegistry::default();
     let mut state = EditorState::default();
@@ -4724,6 +5801,7 @@ fn mechanics_tab_attacker_property_modifier_source() {
             priority: 10,
             cap: None,
             terrain_type_filter: None,
+            condition: None,
         }],
     };
     let mut state = EditorState::default();
@@ -4755,6 +5833,7 @@ fn mechanics_tab_defender_property_modifier_source() {
             priority: 10,
             cap: None,
             terrain_type_filter: None,
+            condition: None,
         }],
     };
     let mut state = EditorState::default();
@@ -4813,6 +5892,11 @@ fn mechanics_tab_empty_crt_hides_outcome_grid() {
         },
         outcomes: vec![],
         combat_concept_id: None,
+        dice: DicePool::single(6),
+        strength: CombatStrengthModel::default(),
+        losses: LossRules::default(),
+        roles: CombatRoles::default(),
+        selection: CrtSelection::default(),
     };
     let modifiers = CombatModifierRegistry::default();
     let mut state = EditorState::default();
@@ -5142,6 +6226,7 @@ fn inspector_empty_properties_shows_label() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
         );
     });
     harness.get_by_label_contains("EmptyType");
@@ -5177,6 +6262,7 @@ fn unit_inspector_delete_button_renders() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
             &mut actions,
         );
     });
@@ -5247,6 +6333,7 @@ fn constraints_tab_shows_both_constraint_names() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -5300,6 +6387,7 @@ fn mechanics_tab_custom_modifier_renders_label() {
             priority: 5,
             cap: None,
             terrain_type_filter: None,
+            condition: None,
         }],
     };
     let mut state = EditorState::default();
@@ -5485,6 +6573,7 @@ fn constraints_tab_is_type_shows_placeholder() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -5542,6 +6631,11 @@ fn mechanics_tab_crt_only_rows_no_outcome_grid() {
         },
         outcomes: vec![],
         combat_concept_id: None,
+        dice: DicePool::single(6),
+        strength: CombatStrengthModel::default(),
+        losses: LossRules::default(),
+        roles: CombatRoles::default(),
+        selection: CrtSelection::default(),
     };
     let modifiers = CombatModifierRegistry::default();
     let mut state = EditorState::default();
@@ -5579,6 +6673,11 @@ fn mechanics_tab_crt_only_cols_no_outcome_grid() {
         },
         outcomes: vec![],
         combat_concept_id: None,
+        dice: DicePool::single(6),
+        strength: CombatStrengthModel::default(),
+        losses: LossRules::default(),
+        roles: CombatRoles::default(),
+        selection: CrtSelection::default(),
     };
     let modifiers = CombatModifierRegistry::default();
     let mut state = EditorState::default();
@@ -6296,6 +7395,7 @@ fn constraints_tab_property_compare_with_role() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -6322,6 +7422,7 @@ fn constraints_tab_path_budget_with_role() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -6714,6 +7815,11 @@ fn mechanics_tab_crt_differential_column() {
         },
         outcomes: vec![],
         combat_concept_id: None,
+        dice: DicePool::single(6),
+        strength: CombatStrengthModel::default(),
+        losses: LossRules::default(),
+        roles: CombatRoles::default(),
+        selection: CrtSelection::default(),
     };
     let ts = test_turn_structure();
     let mods = CombatModifierRegistry::default();
@@ -6738,6 +7844,7 @@ fn mechanics_tab_all_modifier_sources() {
                 priority: 10,
                 cap: None,
                 terrain_type_filter: None,
+                condition: None,
             },
             CombatModifierDefinition {
                 id: TypeId::new(),
@@ -6747,6 +7854,7 @@ fn mechanics_tab_all_modifier_sources() {
                 priority: 5,
                 cap: None,
                 terrain_type_filter: None,
+                condition: None,
             },
             CombatModifierDefinition {
                 id: TypeId::new(),
@@ -6756,6 +7864,7 @@ fn mechanics_tab_all_modifier_sources() {
                 priority: 3,
                 cap: None,
                 terrain_type_filter: None,
+                condition: None,
             },
             CombatModifierDefinition {
                 id: TypeId::new(),
@@ -6765,6 +7874,7 @@ fn mechanics_tab_all_modifier_sources() {
                 priority: 2,
                 cap: None,
                 terrain_type_filter: None,
+                condition: None,
             },
             CombatModifierDefinition {
                 id: TypeId::new(),
@@ -6774,6 +7884,7 @@ fn mechanics_tab_all_modifier_sources() {
                 priority: 1,
                 cap: None,
                 terrain_type_filter: None,
+                condition: None,
             },
         ],
     };
@@ -6940,6 +8051,7 @@ fn inspector_renders_property_editors() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
         );
     });
     harness.get_by_label_contains("Position: (2, 3)");
@@ -6956,7 +8068,7 @@ fn inspector_no_entity_data() {
     let struct_reg = test_struct_registry();
     let pos = Some(HexPosition { q: 0, r: 0 });
     let harness = Harness::new_ui(|ui| {
-        render_rules::render_inspector(ui, pos, None, &registry, &enum_reg, &struct_reg);
+        render_rules::render_inspector(ui, pos, None, &registry, &enum_reg, &struct_reg, &[]);
     });
     harness.get_by_label_contains("No cell data");
 }
@@ -6980,6 +8092,7 @@ fn unit_inspector_renders_properties_and_delete() {
             &registry,
             &enum_reg,
             &struct_reg,
+            &[],
             &mut actions,
         );
     });
@@ -7019,6 +8132,7 @@ fn unit_inspector_delete_produces_action() {
                 &s.registry,
                 &s.enum_reg,
                 &s.struct_reg,
+                &[],
                 &mut s.actions,
             );
         },
@@ -7049,6 +8163,7 @@ fn constraints_tab_auto_generated_label() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -7083,6 +8198,7 @@ fn constraints_tab_create_constraint_action() {
                 ui,
                 &mut s.constraint_reg,
                 &s.concept_reg,
+                &EntityTypeRegistry::default(),
                 &mut s.state,
                 &mut s.actions,
             );
@@ -7119,6 +8235,7 @@ fn constraints_tab_cross_compare_expr_placeholder() {
             ui,
             &mut constraint_reg,
             &concept_reg,
+            &EntityTypeRegistry::default(),
             &mut state,
             &mut actions,
         );
@@ -7567,6 +8684,7 @@ fn property_value_editor_int_range() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             0,
         );
     });
@@ -7589,6 +8707,7 @@ fn property_value_editor_float_range() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             0,
         );
     });
@@ -7611,6 +8730,7 @@ fn property_value_editor_list_renders() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             0,
         );
     });
@@ -7633,6 +8753,7 @@ fn property_value_editor_list_nesting_limit() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             3,
         );
     });
@@ -7656,6 +8777,7 @@ fn property_value_editor_map_renders() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             0,
         );
     });
@@ -7684,6 +8806,7 @@ fn property_value_editor_struct_renders() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             0,
         );
     });
@@ -7706,6 +8829,7 @@ fn property_value_editor_entity_ref_renders() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             0,
         );
     });
@@ -7729,6 +8853,7 @@ fn property_value_editor_enum_combobox() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             0,
         );
     });
@@ -7752,6 +8877,7 @@ fn property_value_editor_map_nesting_limit() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             3,
         );
     });
@@ -7775,6 +8901,7 @@ fn property_value_editor_struct_nesting_limit() {
             &enum_reg,
             &struct_reg,
             &registry,
+            &[],
             3,
         );
     });
@@ -8234,7 +9361,14 @@ fn constraints_tab_path_budget_form() {
     };
     let mut actions = Vec::new();
     let mut harness = Harness::new_ui(|ui| {
-        systems::render_constraints_tab(ui, &mut conreg, &creg, &mut state, &mut actions);
+        systems::render_constraints_tab(
+            ui,
+            &mut conreg,
+            &creg,
+            &EntityTypeRegistry::default(),
+            &mut state,
+            &mut actions,
+        );
     });
     harness.get_by_label("New Constraint").click();
     harness.run();
@@ -8578,7 +9712,7 @@ fn prop_editor_list_body_items() {
     let sreg = StructRegistry::default();
     let reg = test_registry();
     let mut harness = Harness::new_ui(|ui| {
-        render_rules::render_property_value_editor(ui, &mut val, &pt, &ereg, &sreg, &reg, 0);
+        render_rules::render_property_value_editor(ui, &mut val, &pt, &ereg, &sreg, &reg, &[], 0);
     });
     harness.get_by_label_contains("List (2)").click();
     harness.run();
@@ -8598,7 +9732,7 @@ fn prop_editor_map_body_entries() {
     let sreg = StructRegistry::default();
     let reg = test_registry();
     let mut harness = Harness::new_ui(|ui| {
-        render_rules::render_property_value_editor(ui, &mut val, &pt, &ereg, &sreg, &reg, 0);
+        render_rules::render_property_value_editor(ui, &mut val, &pt, &ereg, &sreg, &reg, &[], 0);
     });
     // Enum has 3 options (Open, Rough, Dense). Map shows based on those.
     harness.get_by_label_contains("Map (").click();
@@ -8622,7 +9756,7 @@ fn prop_editor_struct_body_fields() {
     let ereg = EnumRegistry::default();
     let reg = test_registry();
     let mut harness = Harness::new_ui(|ui| {
-        render_rules::render_property_value_editor(ui, &mut val, &pt, &ereg, &sreg, &reg, 0);
+        render_rules::render_property_value_editor(ui, &mut val, &pt, &ereg, &sreg, &reg, &[], 0);
     });
     harness.get_by_label_contains("Position").click();
     harness.run();
@@ -8928,7 +10062,14 @@ fn constraints_tab_shows_constraint_list() {
     let mut state = EditorState::default();
     let mut actions = Vec::new();
     let harness = Harness::new_ui(|ui| {
-        systems::render_constraints_tab(ui, &mut conreg, &creg, &mut state, &mut actions);
+        systems::render_constraints_tab(
+            ui,
+            &mut conreg,
+            &creg,
+            &EntityTypeRegistry::default(),
+            &mut state,
+            &mut actions,
+        );
     });
     harness.get_by_label_contains("Budget >= 0");
 }
@@ -8946,12 +10087,20 @@ fn constraints_tab_delete_constraint() {
             relation_id: None,
             expression: ConstraintExpr::All(Vec::new()),
             auto_generated: false,
+            gates: Vec::new(),
         }],
     };
     let mut state = EditorState::default();
     let mut actions = Vec::new();
     let mut harness = Harness::new_ui(|ui| {
-        systems::render_constraints_tab(ui, &mut conreg, &creg, &mut state, &mut actions);
+        systems::render_constraints_tab(
+            ui,
+            &mut conreg,
+            &creg,
+            &EntityTypeRegistry::default(),
+            &mut state,
+            &mut actions,
+        );
     });
     harness.get_by_label("x").click();
     harness.run();
@@ -8974,7 +10123,14 @@ fn constraints_tab_property_compare_form() {
     };
     let mut actions = Vec::new();
     let mut harness = Harness::new_ui(|ui| {
-        systems::render_constraints_tab(ui, &mut conreg, &creg, &mut state, &mut actions);
+        systems::render_constraints_tab(
+            ui,
+            &mut conreg,
+            &creg,
+            &EntityTypeRegistry::default(),
+            &mut state,
+            &mut actions,
+        );
     });
     harness.get_by_label("New Constraint").click();
     harness.run();
@@ -8990,7 +10146,14 @@ fn constraints_tab_empty_placeholder() {
     let mut state = EditorState::default();
     let mut actions = Vec::new();
     let harness = Harness::new_ui(|ui| {
-        systems::render_constraints_tab(ui, &mut conreg, &creg, &mut state, &mut actions);
+        systems::render_constraints_tab(
+            ui,
+            &mut conreg,
+            &creg,
+            &EntityTypeRegistry::default(),
+            &mut state,
+            &mut actions,
+        );
     });
     harness.get_by_label_contains("No constraints defined");
 }
@@ -9006,7 +10169,14 @@ fn constraints_tab_cross_compare_placeholder() {
     };
     let mut actions = Vec::new();
     let mut harness = Harness::new_ui(|ui| {
-        systems::render_constraints_tab(ui, &mut conreg, &creg, &mut state, &mut actions);
+        systems::render_constraints_tab(
+            ui,
+            &mut conreg,
+            &creg,
+            &EntityTypeRegistry::default(),
+            &mut state,
+            &mut actions,
+        );
     });
     harness.get_by_label("New Constraint").click();
     harness.run();
@@ -9356,7 +10526,12 @@ fn constraint_form_harness(
                 Vec<EditorAction>,
             )| {
                 super::render_ontology::render_constraints_tab(
-                    ui, &mut s.1, &s.2, &mut s.0, &mut s.3,
+                    ui,
+                    &mut s.1,
+                    &s.2,
+                    &EntityTypeRegistry::default(),
+                    &mut s.0,
+                    &mut s.3,
                 );
             },
             (
@@ -9423,7 +10598,12 @@ fn constraint_form_path_budget_combobox_dropdown() {
                 Vec<EditorAction>,
             )| {
                 super::render_ontology::render_constraints_tab(
-                    ui, &mut s.1, &s.2, &mut s.0, &mut s.3,
+                    ui,
+                    &mut s.1,
+                    &s.2,
+                    &EntityTypeRegistry::default(),
+                    &mut s.0,
+                    &mut s.3,
                 );
             },
             (
@@ -10085,6 +11265,11 @@ fn mechanics_remove_crt_row_click() {
             },
             outcomes: vec![],
             combat_concept_id: None,
+            dice: DicePool::single(6),
+            strength: CombatStrengthModel::default(),
+            losses: LossRules::default(),
+            roles: CombatRoles::default(),
+            selection: CrtSelection::default(),
         },
         CombatModifierRegistry::default(),
         EditorState::default(),
@@ -10150,6 +11335,11 @@ fn mechanics_remove_modifier_click() {
             },
             outcomes: vec![],
             combat_concept_id: None,
+            dice: DicePool::single(6),
+            strength: CombatStrengthModel::default(),
+            losses: LossRules::default(),
+            roles: CombatRoles::default(),
+            selection: CrtSelection::default(),
         },
         mods,
         EditorState::default(),
@@ -10380,7 +11570,7 @@ fn inspector_harness(
                 StructRegistry,
                 Option<HexPosition>,
             )| {
-                render_rules::render_inspector(ui, s.4, s.0.as_mut(), &s.1, &s.2, &s.3);
+                render_rules::render_inspector(ui, s.4, s.0.as_mut(), &s.1, &s.2, &s.3, &[]);
             },
             (
                 entity_data,
@@ -10655,7 +11845,15 @@ fn unit_inspector_renders_properties() {
                 StructRegistry,
                 Vec<EditorAction>,
             )| {
-                render_rules::render_unit_inspector(ui, s.0.as_mut(), &s.1, &s.2, &s.3, &mut s.4);
+                render_rules::render_unit_inspector(
+                    ui,
+                    s.0.as_mut(),
+                    &s.1,
+                    &s.2,
+                    &s.3,
+                    &[],
+                    &mut s.4,
+                );
             },
             (Some(entity_data), reg, enums, structs, vec![]),
         );
@@ -10682,7 +11880,15 @@ fn unit_inspector_diverse_no_unit_selected() {
                 StructRegistry,
                 Vec<EditorAction>,
             )| {
-                render_rules::render_unit_inspector(ui, s.0.as_mut(), &s.1, &s.2, &s.3, &mut s.4);
+                render_rules::render_unit_inspector(
+                    ui,
+                    s.0.as_mut(),
+                    &s.1,
+                    &s.2,
+                    &s.3,
+                    &[],
+                    &mut s.4,
+                );
             },
             (None, reg, enums, structs, vec![]),
         );
@@ -10706,7 +11912,15 @@ fn unit_inspector_delete_unit_click() {
                 StructRegistry,
                 Vec<EditorAction>,
             )| {
-                render_rules::render_unit_inspector(ui, s.0.as_mut(), &s.1, &s.2, &s.3, &mut s.4);
+                render_rules::render_unit_inspector(
+                    ui,
+                    s.0.as_mut(),
+                    &s.1,
+                    &s.2,
+                    &s.3,
+                    &[],
+                    &mut s.4,
+                );
             },
             (Some(entity_data), reg, enums, structs, vec![]),
         );
@@ -11552,6 +12766,7 @@ fn with_test_viewer(f: impl FnOnce(&mut EditorDockViewer<'_>)) {
     let mut editor_state = EditorState::default();
     let mut actions: Vec<EditorAction> = Vec::new();
     let validation = SchemaValidation::default();
+    let rule_analysis = RuleAnalysis::default();
     let mut viewport_rect = ViewportRect::default();
     let multi = Selection::default();
     let mechanic_catalog = MechanicCatalog::default();
@@ -11571,13 +12786,21 @@ fn with_test_viewer(f: impl FnOnce(&mut EditorDockViewer<'_>)) {
     let mut constraint_registry = ConstraintRegistry::default();
     let mut turn_structure = TurnStructure::default();
     let mut combat_results_table = CombatResultsTable::default();
+    let mut combat_tables = CombatTableRegistry::default();
     let mut combat_modifiers = CombatModifierRegistry::default();
     let mut influence_rules = hexorder_contracts::hex_grid::InfluenceRuleRegistry::default();
     let mut stacking_rule = hexorder_contracts::hex_grid::StackingRule::default();
     let mut movement_cost_matrix = hexorder_contracts::hex_grid::MovementCostMatrix::default();
     let mut spawn_schedule = hexorder_contracts::mechanics::SpawnSchedule::default();
+    let mut off_map_zones = hexorder_contracts::mechanics::OffMapZoneRegistry::default();
+    let mut grid_shape = hexorder_contracts::hex_grid::GridShape::default();
     let mut accumulator_registry = hexorder_contracts::mechanics::AccumulatorRegistry::default();
     let mut victory_conditions = hexorder_contracts::mechanics::VictoryConditionRegistry::default();
+    let mut factions = hexorder_contracts::game_system::FactionRegistry::default();
+    let mut state_machines = hexorder_contracts::game_system::StateMachineRegistry::default();
+    let mut reachability_rules = hexorder_contracts::hex_grid::ReachabilityRuleRegistry::default();
+    let mut reachability_overlay = hexorder_contracts::hex_grid::ReachabilityOverlay::default();
+    let mut overruns = hexorder_contracts::mechanics::OverrunRegistry::default();
     let mut map_gen_params = MapGenParams::default();
 
     let mut viewer = EditorDockViewer {
@@ -11585,6 +12808,7 @@ fn with_test_viewer(f: impl FnOnce(&mut EditorDockViewer<'_>)) {
         actions: &mut actions,
         next_screen: None,
         schema_validation: &validation,
+        rule_analysis: &rule_analysis,
         viewport_rect: &mut viewport_rect,
         multi: &multi,
         mechanic_catalog: &mechanic_catalog,
@@ -11593,6 +12817,8 @@ fn with_test_viewer(f: impl FnOnce(&mut EditorDockViewer<'_>)) {
             active_board: &mut active_board,
             active_token: &mut active_token,
             active_edge: &mut hexorder_contracts::editor_ui::ActiveEdgeType::default(),
+            active_vertex: &mut hexorder_contracts::editor_ui::ActiveVertexType::default(),
+            active_faction: &mut hexorder_contracts::game_system::ActiveFaction::default(),
             project_workspace: &workspace,
             project_game_system: &game_system,
         },
@@ -11607,18 +12833,33 @@ fn with_test_viewer(f: impl FnOnce(&mut EditorDockViewer<'_>)) {
             constraint_registry: &mut constraint_registry,
             turn_structure: &mut turn_structure,
             combat_results_table: &mut combat_results_table,
+            combat_tables: &mut combat_tables,
             combat_modifiers: &mut combat_modifiers,
             influence_rules: &mut influence_rules,
             stacking_rule: &mut stacking_rule,
             movement_cost_matrix: &mut movement_cost_matrix,
             spawn_schedule: &mut spawn_schedule,
+            off_map_zones: &mut off_map_zones,
+            grid_shape: &mut grid_shape,
             accumulator_registry: &mut accumulator_registry,
             victory_conditions: &mut victory_conditions,
+            factions: &mut factions,
+            state_machines: &mut state_machines,
+            reachability_rules: &mut reachability_rules,
+            reachability_overlay: &mut reachability_overlay,
+            overruns: &mut overruns,
+            area_markers: &hexorder_contracts::mechanics::AreaMarkerRegistry::default(),
+            movement_interrupts: &hexorder_contracts::mechanics::MovementInterruptRegistry::default(
+            ),
         },
         inspector: InspectorData {
             tile_position: None,
             tile_entity_data: None,
             unit_entity_data: None,
+            unit_owner: None,
+            tile_state: None,
+            unit_state: None,
+            unit_choices: &[],
         },
         map_gen_params: &mut map_gen_params,
         is_generating: false,
@@ -12688,420 +13929,897 @@ fn build_theme_visuals_sets_remaining_fields() {
 }
 
 // ---------------------------------------------------------------------------
-// build_theme_text_styles — pure function coverage
+// build_theme_text_styles — pure function coverage
+// ---------------------------------------------------------------------------
+
+/// Default base size (15.0) produces scale=1.0: Body=15.0, Heading=20.0.
+#[test]
+fn build_theme_text_styles_default_scale() {
+    let styles = render_panels::build_theme_text_styles(15.0);
+    let body = styles
+        .get(&bevy_egui::egui::TextStyle::Body)
+        .expect("Body style should exist");
+    let heading = styles
+        .get(&bevy_egui::egui::TextStyle::Heading)
+        .expect("Heading style should exist");
+    assert!(
+        (body.size - 15.0).abs() < f32::EPSILON,
+        "Body at scale 1.0 should be 15.0, got {}",
+        body.size
+    );
+    assert!(
+        (heading.size - 20.0).abs() < f32::EPSILON,
+        "Heading at scale 1.0 should be 20.0, got {}",
+        heading.size
+    );
+}
+
+/// Double base size (30.0) produces scale=2.0: Body=30.0, Heading=40.0.
+#[test]
+fn build_theme_text_styles_double_scale() {
+    let styles = render_panels::build_theme_text_styles(30.0);
+    let body = styles
+        .get(&bevy_egui::egui::TextStyle::Body)
+        .expect("Body style should exist");
+    let heading = styles
+        .get(&bevy_egui::egui::TextStyle::Heading)
+        .expect("Heading style should exist");
+    assert!(
+        (body.size - 30.0).abs() < f32::EPSILON,
+        "Body at scale 2.0 should be 30.0, got {}",
+        body.size
+    );
+    assert!(
+        (heading.size - 40.0).abs() < f32::EPSILON,
+        "Heading at scale 2.0 should be 40.0, got {}",
+        heading.size
+    );
+}
+
+/// All five standard egui text styles are present in the generated map.
+#[test]
+fn build_theme_text_styles_has_five_styles() {
+    let styles = render_panels::build_theme_text_styles(15.0);
+    assert_eq!(styles.len(), 5, "should have exactly 5 text styles");
+    assert!(
+        styles.contains_key(&bevy_egui::egui::TextStyle::Heading),
+        "missing Heading"
+    );
+    assert!(
+        styles.contains_key(&bevy_egui::egui::TextStyle::Body),
+        "missing Body"
+    );
+    assert!(
+        styles.contains_key(&bevy_egui::egui::TextStyle::Small),
+        "missing Small"
+    );
+    assert!(
+        styles.contains_key(&bevy_egui::egui::TextStyle::Button),
+        "missing Button"
+    );
+    assert!(
+        styles.contains_key(&bevy_egui::egui::TextStyle::Monospace),
+        "missing Monospace"
+    );
+}
+
+/// Monospace style uses the `Monospace` font family, all others use `Proportional`.
+#[test]
+fn build_theme_text_styles_font_families() {
+    let styles = render_panels::build_theme_text_styles(15.0);
+    let mono = styles
+        .get(&bevy_egui::egui::TextStyle::Monospace)
+        .expect("Monospace style should exist");
+    assert_eq!(
+        mono.family,
+        bevy_egui::egui::FontFamily::Monospace,
+        "Monospace style should use Monospace family"
+    );
+    for (style, font_id) in &styles {
+        if *style != bevy_egui::egui::TextStyle::Monospace {
+            assert_eq!(
+                font_id.family,
+                bevy_egui::egui::FontFamily::Proportional,
+                "{style:?} should use Proportional family"
+            );
+        }
+    }
+}
+
+/// Small text style scales correctly (13.0 at scale 1.0, 26.0 at scale 2.0).
+#[test]
+fn build_theme_text_styles_small_scales() {
+    let styles_1x = render_panels::build_theme_text_styles(15.0);
+    let small_1x = styles_1x
+        .get(&bevy_egui::egui::TextStyle::Small)
+        .expect("Small style should exist");
+    assert!(
+        (small_1x.size - 13.0).abs() < f32::EPSILON,
+        "Small at scale 1.0 should be 13.0, got {}",
+        small_1x.size
+    );
+
+    let styles_2x = render_panels::build_theme_text_styles(30.0);
+    let small_2x = styles_2x
+        .get(&bevy_egui::egui::TextStyle::Small)
+        .expect("Small style should exist");
+    assert!(
+        (small_2x.size - 26.0).abs() < f32::EPSILON,
+        "Small at scale 2.0 should be 26.0, got {}",
+        small_2x.size
+    );
+}
+
+// ---------------------------------------------------------------------------
+// render_launcher_content — additional coverage
+// ---------------------------------------------------------------------------
+
+/// Default state shows both "New Game System" and "Open..." labels.
+#[test]
+fn launcher_content_shows_new_and_open_buttons() {
+    let harness = Harness::builder()
+        .with_size(bevy_egui::egui::vec2(400.0, 600.0))
+        .build_ui_state(
+            |ui, state: &mut EditorState| {
+                render_panels::render_launcher_content(ui, state);
+            },
+            EditorState::default(),
+        );
+    harness.get_by_label("New Game System");
+    harness.get_by_label("Open...");
+}
+
+/// When `launcher_name_input_visible` is true and a project name is set,
+/// "Create" and "Cancel" are both visible.
+#[test]
+fn launcher_content_name_input_shows_create_cancel() {
+    let harness = Harness::builder()
+        .with_size(bevy_egui::egui::vec2(400.0, 600.0))
+        .build_ui_state(
+            |ui, state: &mut EditorState| {
+                render_panels::render_launcher_content(ui, state);
+            },
+            EditorState {
+                launcher_name_input_visible: true,
+                launcher_project_name: "My Project".to_string(),
+                ..EditorState::default()
+            },
+        );
+    harness.get_by_label("Create");
+    harness.get_by_label("Cancel");
+}
+
+/// Launcher renders the title "HEXORDER" and subtitle.
+#[test]
+fn launcher_content_shows_title_and_subtitle() {
+    let harness = Harness::builder()
+        .with_size(bevy_egui::egui::vec2(400.0, 600.0))
+        .build_ui_state(
+            |ui, state: &mut EditorState| {
+                render_panels::render_launcher_content(ui, state);
+            },
+            EditorState::default(),
+        );
+    harness.get_by_label("HEXORDER");
+    harness.get_by_label("Game System Design Tool");
+}
+
+/// Launcher renders the version string.
+#[test]
+fn launcher_content_shows_version() {
+    let harness = Harness::builder()
+        .with_size(bevy_egui::egui::vec2(400.0, 600.0))
+        .build_ui_state(
+            |ui, state: &mut EditorState| {
+                render_panels::render_launcher_content(ui, state);
+            },
+            EditorState::default(),
+        );
+    harness.get_by_label_contains(&format!("v{}", env!("CARGO_PKG_VERSION")));
+}
+
+// ---------------------------------------------------------------------------
+// render_play_file_menu -- return value and label tests
 // ---------------------------------------------------------------------------
 
-/// Default base size (15.0) produces scale=1.0: Body=15.0, Heading=20.0.
+/// Play file menu returns empty actions on initial render (no click).
 #[test]
-fn build_theme_text_styles_default_scale() {
-    let styles = render_panels::build_theme_text_styles(15.0);
-    let body = styles
-        .get(&bevy_egui::egui::TextStyle::Body)
-        .expect("Body style should exist");
-    let heading = styles
-        .get(&bevy_egui::egui::TextStyle::Heading)
-        .expect("Heading style should exist");
-    assert!(
-        (body.size - 15.0).abs() < f32::EPSILON,
-        "Body at scale 1.0 should be 15.0, got {}",
-        body.size
+fn play_file_menu_no_action_without_click() {
+    let harness = Harness::new_ui_state(
+        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
+            *actions = render_play::render_play_file_menu(ui);
+        },
+        Vec::new(),
     );
     assert!(
-        (heading.size - 20.0).abs() < f32::EPSILON,
-        "Heading at scale 1.0 should be 20.0, got {}",
-        heading.size
+        harness.state().is_empty(),
+        "Expected no actions when no button is clicked"
     );
 }
 
-/// Double base size (30.0) produces scale=2.0: Body=30.0, Heading=40.0.
+/// Play file menu renders all four buttons with keyboard shortcuts.
 #[test]
-fn build_theme_text_styles_double_scale() {
-    let styles = render_panels::build_theme_text_styles(30.0);
-    let body = styles
-        .get(&bevy_egui::egui::TextStyle::Body)
-        .expect("Body style should exist");
-    let heading = styles
-        .get(&bevy_egui::egui::TextStyle::Heading)
-        .expect("Heading style should exist");
-    assert!(
-        (body.size - 30.0).abs() < f32::EPSILON,
-        "Body at scale 2.0 should be 30.0, got {}",
-        body.size
-    );
-    assert!(
-        (heading.size - 40.0).abs() < f32::EPSILON,
-        "Heading at scale 2.0 should be 40.0, got {}",
-        heading.size
-    );
+fn play_file_menu_all_buttons_with_shortcuts() {
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_play_file_menu(ui);
+    });
+    harness.get_by_label_contains("Cmd+N");
+    harness.get_by_label_contains("Cmd+O");
+    harness.get_by_label_contains("Save As...");
+    harness.get_by_label_contains("Cmd+Shift+S");
 }
 
-/// All five standard egui text styles are present in the generated map.
+/// `PlayMenuAction` enum derives Debug and Clone correctly.
 #[test]
-fn build_theme_text_styles_has_five_styles() {
-    let styles = render_panels::build_theme_text_styles(15.0);
-    assert_eq!(styles.len(), 5, "should have exactly 5 text styles");
-    assert!(
-        styles.contains_key(&bevy_egui::egui::TextStyle::Heading),
-        "missing Heading"
+fn play_menu_action_debug_and_clone() {
+    let action = render_play::PlayMenuAction::NewProject;
+    assert_eq!(format!("{action:?}"), "NewProject");
+    let cloned = action;
+    assert_eq!(action, cloned);
+
+    assert_eq!(
+        format!("{:?}", render_play::PlayMenuAction::OpenProject),
+        "OpenProject"
     );
-    assert!(
-        styles.contains_key(&bevy_egui::egui::TextStyle::Body),
-        "missing Body"
+    assert_eq!(format!("{:?}", render_play::PlayMenuAction::Save), "Save");
+    assert_eq!(
+        format!("{:?}", render_play::PlayMenuAction::SaveAs),
+        "SaveAs"
     );
-    assert!(
-        styles.contains_key(&bevy_egui::egui::TextStyle::Small),
-        "missing Small"
+    assert_eq!(
+        format!("{:?}", render_play::PlayMenuAction::ShowAbout),
+        "ShowAbout"
     );
-    assert!(
-        styles.contains_key(&bevy_egui::egui::TextStyle::Button),
-        "missing Button"
+}
+
+// ---------------------------------------------------------------------------
+// render_play_sidebar -- editor button and sidebar content
+// ---------------------------------------------------------------------------
+
+/// Play sidebar returns false for `switch_to_editor` on initial render (no click).
+#[test]
+fn play_sidebar_no_switch_without_click() {
+    struct SidebarState {
+        workspace: Workspace,
+        game_system: GameSystem,
+        turn_state: TurnState,
+        turn_structure: TurnStructure,
+        active_combat: ActiveCombat,
+        crt: CombatResultsTable,
+        modifiers: CombatModifierRegistry,
+        selected_unit: SelectedUnit,
+        entity_types: EntityTypeRegistry,
+        editor_state: EditorState,
+        sim_rng: SimulationRng,
+        switch_result: bool,
+    }
+
+    let state = SidebarState {
+        workspace: Workspace {
+            name: "No Click".to_string(),
+            ..Workspace::default()
+        },
+        game_system: GameSystem {
+            id: "no-click-id".to_string(),
+            version: "1.0".to_string(),
+        },
+        turn_state: TurnState::default(),
+        turn_structure: TurnStructure::default(),
+        active_combat: ActiveCombat::default(),
+        crt: CombatResultsTable::default(),
+        modifiers: CombatModifierRegistry::default(),
+        selected_unit: SelectedUnit::default(),
+        entity_types: EntityTypeRegistry::default(),
+        editor_state: EditorState::default(),
+        sim_rng: SimulationRng::new(42),
+        switch_result: false,
+    };
+
+    let harness = Harness::new_ui_state(
+        |ui, s: &mut SidebarState| {
+            s.switch_result = render_play::render_play_sidebar(
+                ui,
+                &s.workspace,
+                &s.game_system,
+                &mut s.turn_state,
+                &s.turn_structure,
+                &mut s.active_combat,
+                &s.crt,
+                &s.modifiers,
+                &s.selected_unit,
+                &s.entity_types,
+                &mut s.editor_state,
+                &mut s.sim_rng,
+                &mut AreaMarkerRegistry::default(),
+                &|_| None,
+                &|_| None,
+            );
+        },
+        state,
     );
+
     assert!(
-        styles.contains_key(&bevy_egui::egui::TextStyle::Monospace),
-        "missing Monospace"
+        !harness.state().switch_result,
+        "Expected switch_result to be false without clicking Editor"
     );
 }
 
-/// Monospace style uses the `Monospace` font family, all others use `Proportional`.
+/// Play sidebar shows the Editor button with the stop-square icon.
 #[test]
-fn build_theme_text_styles_font_families() {
-    let styles = render_panels::build_theme_text_styles(15.0);
-    let mono = styles
-        .get(&bevy_egui::egui::TextStyle::Monospace)
-        .expect("Monospace style should exist");
-    assert_eq!(
-        mono.family,
-        bevy_egui::egui::FontFamily::Monospace,
-        "Monospace style should use Monospace family"
-    );
-    for (style, font_id) in &styles {
-        if *style != bevy_egui::egui::TextStyle::Monospace {
-            assert_eq!(
-                font_id.family,
-                bevy_egui::egui::FontFamily::Proportional,
-                "{style:?} should use Proportional family"
-            );
-        }
-    }
+fn play_sidebar_shows_editor_button_icon() {
+    let workspace = Workspace::default();
+    let game_system = GameSystem {
+        id: "icon-test".to_string(),
+        version: "1.0".to_string(),
+    };
+    let mut turn_state = TurnState::default();
+    let turn_structure = TurnStructure::default();
+    let mut active_combat = ActiveCombat::default();
+    let crt = CombatResultsTable::default();
+    let modifiers = CombatModifierRegistry::default();
+    let selected_unit = SelectedUnit::default();
+    let entity_types = EntityTypeRegistry::default();
+    let mut editor_state = EditorState::default();
+    let mut sim_rng = SimulationRng::new(42);
+
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_play_sidebar(
+            ui,
+            &workspace,
+            &game_system,
+            &mut turn_state,
+            &turn_structure,
+            &mut active_combat,
+            &crt,
+            &modifiers,
+            &selected_unit,
+            &entity_types,
+            &mut editor_state,
+            &mut sim_rng,
+            &mut AreaMarkerRegistry::default(),
+            &|_| None,
+            &|_| None,
+        );
+    });
+    harness.get_by_label_contains("\u{25A0} Editor");
 }
 
-/// Small text style scales correctly (13.0 at scale 1.0, 26.0 at scale 2.0).
+/// Play sidebar with phases renders turn tracker content within the sidebar.
 #[test]
-fn build_theme_text_styles_small_scales() {
-    let styles_1x = render_panels::build_theme_text_styles(15.0);
-    let small_1x = styles_1x
-        .get(&bevy_egui::egui::TextStyle::Small)
-        .expect("Small style should exist");
-    assert!(
-        (small_1x.size - 13.0).abs() < f32::EPSILON,
-        "Small at scale 1.0 should be 13.0, got {}",
-        small_1x.size
-    );
+fn play_sidebar_with_phases_shows_turn_tracker() {
+    let workspace = Workspace {
+        name: "Phase Test".to_string(),
+        ..Workspace::default()
+    };
+    let game_system = GameSystem {
+        id: "phase-test".to_string(),
+        version: "2.0".to_string(),
+    };
+    let mut turn_state = TurnState::default();
+    let turn_structure = test_turn_structure();
+    let mut active_combat = ActiveCombat::default();
+    let crt = CombatResultsTable::default();
+    let modifiers = CombatModifierRegistry::default();
+    let selected_unit = SelectedUnit::default();
+    let entity_types = EntityTypeRegistry::default();
+    let mut editor_state = EditorState::default();
+    let mut sim_rng = SimulationRng::new(42);
 
-    let styles_2x = render_panels::build_theme_text_styles(30.0);
-    let small_2x = styles_2x
-        .get(&bevy_egui::egui::TextStyle::Small)
-        .expect("Small style should exist");
-    assert!(
-        (small_2x.size - 26.0).abs() < f32::EPSILON,
-        "Small at scale 2.0 should be 26.0, got {}",
-        small_2x.size
-    );
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_play_sidebar(
+            ui,
+            &workspace,
+            &game_system,
+            &mut turn_state,
+            &turn_structure,
+            &mut active_combat,
+            &crt,
+            &modifiers,
+            &selected_unit,
+            &entity_types,
+            &mut editor_state,
+            &mut sim_rng,
+            &mut AreaMarkerRegistry::default(),
+            &|_| None,
+            &|_| None,
+        );
+    });
+    harness.get_by_label_contains("Turn Tracker");
+    harness.get_by_label_contains("Turn 1");
 }
 
 // ---------------------------------------------------------------------------
-// render_launcher_content — additional coverage
+// render_combat_panel -- odds display and combat resolution
 // ---------------------------------------------------------------------------
 
-/// Default state shows both "New Game System" and "Open..." labels.
+/// Combat panel shows odds ratio when defender strength is positive.
 #[test]
-fn launcher_content_shows_new_and_open_buttons() {
-    let harness = Harness::builder()
-        .with_size(bevy_egui::egui::vec2(400.0, 600.0))
-        .build_ui_state(
-            |ui, state: &mut EditorState| {
-                render_panels::render_launcher_content(ui, state);
-            },
-            EditorState::default(),
-        );
-    harness.get_by_label("New Game System");
-    harness.get_by_label("Open...");
-}
+fn combat_panel_odds_display_with_positive_defender() {
+    let mut active_combat = ActiveCombat::default();
+    let crt = test_crt();
+    let modifiers = CombatModifierRegistry::default();
+    let selected_unit = SelectedUnit::default();
+    let entity_types = EntityTypeRegistry::default();
+    let mut editor_state = EditorState {
+        combat_attacker_strength: 6.0,
+        combat_defender_strength: 2.0,
+        ..EditorState::default()
+    };
 
-/// When `launcher_name_input_visible` is true and a project name is set,
-/// "Create" and "Cancel" are both visible.
-#[test]
-fn launcher_content_name_input_shows_create_cancel() {
-    let harness = Harness::builder()
-        .with_size(bevy_egui::egui::vec2(400.0, 600.0))
-        .build_ui_state(
-            |ui, state: &mut EditorState| {
-                render_panels::render_launcher_content(ui, state);
-            },
-            EditorState {
-                launcher_name_input_visible: true,
-                launcher_project_name: "My Project".to_string(),
-                ..EditorState::default()
-            },
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_combat_panel(
+            ui,
+            &mut active_combat,
+            &crt,
+            &modifiers,
+            &selected_unit,
+            &entity_types,
+            &mut editor_state,
+            &mut SimulationRng::new(42),
+            &AreaMarkerRegistry::default(),
+            &|_| None,
+            &|_| None,
+            true,
         );
-    harness.get_by_label("Create");
-    harness.get_by_label("Cancel");
+    });
+    harness.get_by_label_contains("Odds: 3.00:1");
 }
 
-/// Launcher renders the title "HEXORDER" and subtitle.
+/// A differential CRT shows the odds as a signed difference.
 #[test]
-fn launcher_content_shows_title_and_subtitle() {
-    let harness = Harness::builder()
-        .with_size(bevy_egui::egui::vec2(400.0, 600.0))
-        .build_ui_state(
-            |ui, state: &mut EditorState| {
-                render_panels::render_launcher_content(ui, state);
-            },
-            EditorState::default(),
-        );
-    harness.get_by_label("HEXORDER");
-    harness.get_by_label("Game System Design Tool");
-}
+fn combat_panel_odds_display_follows_the_column_type() {
+    let mut active_combat = ActiveCombat::default();
+    let mut crt = test_crt();
+    for column in &mut crt.table.columns {
+        column.column_type = ColumnType::Differential;
+    }
+    let mut editor_state = EditorState {
+        combat_attacker_strength: 6.0,
+        combat_defender_strength: 2.0,
+        ..EditorState::default()
+    };
 
-/// Launcher renders the version string.
-#[test]
-fn launcher_content_shows_version() {
-    let harness = Harness::builder()
-        .with_size(bevy_egui::egui::vec2(400.0, 600.0))
-        .build_ui_state(
-            |ui, state: &mut EditorState| {
-                render_panels::render_launcher_content(ui, state);
-            },
-            EditorState::default(),
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_combat_panel(
+            ui,
+            &mut active_combat,
+            &crt,
+            &CombatModifierRegistry::default(),
+            &SelectedUnit::default(),
+            &EntityTypeRegistry::default(),
+            &mut editor_state,
+            &mut SimulationRng::new(42),
+            &AreaMarkerRegistry::default(),
+            &|_| None,
+            &|_| None,
+            true,
         );
-    harness.get_by_label_contains(&format!("v{}", env!("CARGO_PKG_VERSION")));
+    });
+    harness.get_by_label_contains("Odds: +4");
 }
 
-// ---------------------------------------------------------------------------
-// render_play_file_menu -- return value and label tests
-// ---------------------------------------------------------------------------
-
-/// Play file menu returns empty actions on initial render (no click).
+/// Combat panel shows attacker and defender labels when not set.
 #[test]
-fn play_file_menu_no_action_without_click() {
-    let harness = Harness::new_ui_state(
-        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
-            *actions = render_play::render_play_file_menu(ui);
-        },
-        Vec::new(),
-    );
-    assert!(
-        harness.state().is_empty(),
-        "Expected no actions when no button is clicked"
-    );
-}
+fn combat_panel_shows_none_when_no_combatants() {
+    let mut active_combat = ActiveCombat::default();
+    let crt = test_crt();
+    let modifiers = CombatModifierRegistry::default();
+    let selected_unit = SelectedUnit::default();
+    let entity_types = EntityTypeRegistry::default();
+    let mut editor_state = EditorState::default();
 
-/// Play file menu renders all four buttons with keyboard shortcuts.
-#[test]
-fn play_file_menu_all_buttons_with_shortcuts() {
     let harness = Harness::new_ui(|ui| {
-        render_play::render_play_file_menu(ui);
+        render_play::render_combat_panel(
+            ui,
+            &mut active_combat,
+            &crt,
+            &modifiers,
+            &selected_unit,
+            &entity_types,
+            &mut editor_state,
+            &mut SimulationRng::new(42),
+            &AreaMarkerRegistry::default(),
+            &|_| None,
+            &|_| None,
+            true,
+        );
     });
-    harness.get_by_label_contains("Cmd+N");
-    harness.get_by_label_contains("Cmd+O");
-    harness.get_by_label_contains("Save As...");
-    harness.get_by_label_contains("Cmd+Shift+S");
+    harness.get_by_label("Attackers");
+    harness.get_by_label("Defenders");
+    // Both empty combatant slots render — verify at least one "None" label via query_all.
+    assert!(harness.get_all_by_label_contains("None").count() >= 2,);
 }
 
-/// `PlayMenuAction` enum derives Debug and Clone correctly.
+/// Combat panel with modifiers shows final column after column shift.
 #[test]
-fn play_menu_action_debug_and_clone() {
-    let action = render_play::PlayMenuAction::NewProject;
-    assert_eq!(format!("{action:?}"), "NewProject");
-    let cloned = action;
-    assert_eq!(action, cloned);
+fn combat_panel_modifiers_show_final_column() {
+    let mut active_combat = ActiveCombat::default();
+    let crt = test_crt();
+    let modifiers = test_modifiers();
+    let selected_unit = SelectedUnit::default();
+    let entity_types = EntityTypeRegistry::default();
+    let mut editor_state = EditorState {
+        combat_attacker_strength: 4.0,
+        combat_defender_strength: 2.0,
+        ..EditorState::default()
+    };
 
-    assert_eq!(
-        format!("{:?}", render_play::PlayMenuAction::OpenProject),
-        "OpenProject"
-    );
-    assert_eq!(format!("{:?}", render_play::PlayMenuAction::Save), "Save");
-    assert_eq!(
-        format!("{:?}", render_play::PlayMenuAction::SaveAs),
-        "SaveAs"
-    );
-    assert_eq!(
-        format!("{:?}", render_play::PlayMenuAction::ShowAbout),
-        "ShowAbout"
-    );
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_combat_panel(
+            ui,
+            &mut active_combat,
+            &crt,
+            &modifiers,
+            &selected_unit,
+            &entity_types,
+            &mut editor_state,
+            &mut SimulationRng::new(42),
+            &AreaMarkerRegistry::default(),
+            &|_| None,
+            &|_| None,
+            true,
+        );
+    });
+    harness.get_by_label_contains("Final column:");
 }
 
-// ---------------------------------------------------------------------------
-// render_play_sidebar -- editor button and sidebar content
-// ---------------------------------------------------------------------------
-
-/// Play sidebar returns false for `switch_to_editor` on initial render (no click).
+/// Combat panel clear button resets state via actual render function.
 #[test]
-fn play_sidebar_no_switch_without_click() {
-    struct SidebarState {
-        workspace: Workspace,
-        game_system: GameSystem,
-        turn_state: TurnState,
-        turn_structure: TurnStructure,
+fn combat_panel_clear_via_render_function() {
+    struct CombatState {
         active_combat: ActiveCombat,
         crt: CombatResultsTable,
         modifiers: CombatModifierRegistry,
         selected_unit: SelectedUnit,
         entity_types: EntityTypeRegistry,
         editor_state: EditorState,
-        sim_rng: SimulationRng,
-        switch_result: bool,
     }
 
-    let state = SidebarState {
-        workspace: Workspace {
-            name: "No Click".to_string(),
-            ..Workspace::default()
-        },
-        game_system: GameSystem {
-            id: "no-click-id".to_string(),
-            version: "1.0".to_string(),
+    let state = CombatState {
+        active_combat: ActiveCombat {
+            die_roll: Some(4),
+            ..ActiveCombat::default()
         },
-        turn_state: TurnState::default(),
-        turn_structure: TurnStructure::default(),
-        active_combat: ActiveCombat::default(),
-        crt: CombatResultsTable::default(),
+        crt: test_crt(),
         modifiers: CombatModifierRegistry::default(),
         selected_unit: SelectedUnit::default(),
         entity_types: EntityTypeRegistry::default(),
-        editor_state: EditorState::default(),
-        sim_rng: SimulationRng::new(42),
-        switch_result: false,
+        editor_state: EditorState {
+            combat_attacker_strength: 3.0,
+            combat_defender_strength: 2.0,
+            ..EditorState::default()
+        },
     };
 
-    let harness = Harness::new_ui_state(
-        |ui, s: &mut SidebarState| {
-            s.switch_result = render_play::render_play_sidebar(
+    let mut harness = Harness::new_ui_state(
+        |ui, s: &mut CombatState| {
+            render_play::render_combat_panel(
                 ui,
-                &s.workspace,
-                &s.game_system,
-                &mut s.turn_state,
-                &s.turn_structure,
                 &mut s.active_combat,
                 &s.crt,
                 &s.modifiers,
                 &s.selected_unit,
                 &s.entity_types,
                 &mut s.editor_state,
-                &mut s.sim_rng,
-                &mut AreaMarkerRegistry::default(),
+                &mut SimulationRng::new(42),
+                &AreaMarkerRegistry::default(),
                 &|_| None,
                 &|_| None,
+                true,
             );
         },
         state,
     );
 
+    harness.get_by_label("Clear Combat").click();
+    harness.run();
     assert!(
-        !harness.state().switch_result,
-        "Expected switch_result to be false without clicking Editor"
+        harness.state().active_combat.die_roll.is_none(),
+        "Expected die_roll to be cleared after clicking Clear Combat"
+    );
+    assert_eq!(
+        harness.state().editor_state.combat_attacker_strength,
+        0.0,
+        "Expected attacker strength to be reset"
+    );
+    assert_eq!(
+        harness.state().editor_state.combat_defender_strength,
+        0.0,
+        "Expected defender strength to be reset"
     );
 }
 
-/// Play sidebar shows the Editor button with the stop-square icon.
+/// Combat panel shows base column label when odds match a CRT column.
 #[test]
-fn play_sidebar_shows_editor_button_icon() {
-    let workspace = Workspace::default();
-    let game_system = GameSystem {
-        id: "icon-test".to_string(),
-        version: "1.0".to_string(),
-    };
-    let mut turn_state = TurnState::default();
-    let turn_structure = TurnStructure::default();
+fn combat_panel_shows_base_column_label() {
     let mut active_combat = ActiveCombat::default();
-    let crt = CombatResultsTable::default();
+    let crt = test_crt();
     let modifiers = CombatModifierRegistry::default();
     let selected_unit = SelectedUnit::default();
     let entity_types = EntityTypeRegistry::default();
-    let mut editor_state = EditorState::default();
-    let mut sim_rng = SimulationRng::new(42);
+    let mut editor_state = EditorState {
+        combat_attacker_strength: 2.0,
+        combat_defender_strength: 2.0,
+        ..EditorState::default()
+    };
 
     let harness = Harness::new_ui(|ui| {
-        render_play::render_play_sidebar(
+        render_play::render_combat_panel(
             ui,
-            &workspace,
-            &game_system,
-            &mut turn_state,
-            &turn_structure,
             &mut active_combat,
             &crt,
             &modifiers,
             &selected_unit,
             &entity_types,
             &mut editor_state,
-            &mut sim_rng,
-            &mut AreaMarkerRegistry::default(),
+            &mut SimulationRng::new(42),
+            &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
+            true,
         );
     });
-    harness.get_by_label_contains("\u{25A0} Editor");
+    harness.get_by_label_contains("Base column:");
+}
+
+// ---------------------------------------------------------------------------
+// Group 1: Editor Menu Bar Submenu Content (systems.rs:648-720)
+// ---------------------------------------------------------------------------
+
+/// Helper: builds a harness that tracks `EditorMenuAction`s from the editor
+/// menu bar. Actions are accumulated rather than overwritten so that the
+/// `CloseOnClick` popup behavior (which re-renders after closing the menu
+/// with an empty vector) does not erase captured actions.
+fn editor_menu_harness(
+    can_undo: bool,
+    undo_desc: Option<&'static str>,
+    can_redo: bool,
+    redo_desc: Option<&'static str>,
+    active_preset: WorkspacePreset,
+) -> Harness<'static, Vec<systems::EditorMenuAction>> {
+    Harness::new_ui_state(
+        move |ui, actions: &mut Vec<systems::EditorMenuAction>| {
+            let result = systems::render_editor_menu_bar(
+                ui,
+                can_undo,
+                undo_desc,
+                can_redo,
+                redo_desc,
+                active_preset,
+            );
+            if !result.is_empty() {
+                *actions = result;
+            }
+        },
+        Vec::new(),
+    )
+}
+
+/// File > New returns `EditorMenuAction::NewProject`.
+#[test]
+fn editor_menu_file_new_returns_action() {
+    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
+    harness.get_by_label("File").click();
+    harness.run();
+    harness.get_by_label_contains("Cmd+N").click();
+    harness.run();
+    assert!(
+        harness
+            .state()
+            .contains(&systems::EditorMenuAction::NewProject),
+        "Expected NewProject action after clicking New"
+    );
+}
+
+/// File > Open returns `EditorMenuAction::OpenFile`.
+#[test]
+fn editor_menu_file_open_returns_action() {
+    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
+    harness.get_by_label("File").click();
+    harness.run();
+    harness.get_by_label_contains("Cmd+O").click();
+    harness.run();
+    assert!(
+        harness
+            .state()
+            .contains(&systems::EditorMenuAction::OpenFile),
+        "Expected OpenFile action after clicking Open"
+    );
+}
+
+/// File > Save returns `EditorMenuAction::Save`.
+#[test]
+fn editor_menu_file_save_returns_action() {
+    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
+    harness.get_by_label("File").click();
+    harness.run();
+    harness.get_by_label_contains("Save         Cmd+S").click();
+    harness.run();
+    assert!(
+        harness.state().contains(&systems::EditorMenuAction::Save),
+        "Expected Save action after clicking Save"
+    );
+}
+
+/// File > Save As returns `EditorMenuAction::SaveAs`.
+#[test]
+fn editor_menu_file_save_as_returns_action() {
+    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
+    harness.get_by_label("File").click();
+    harness.run();
+    harness.get_by_label_contains("Cmd+Shift+S").click();
+    harness.run();
+    assert!(
+        harness.state().contains(&systems::EditorMenuAction::SaveAs),
+        "Expected SaveAs action after clicking Save As"
+    );
+}
+
+/// File > Export PDF returns `EditorMenuAction::ExportPdf`.
+#[test]
+fn editor_menu_file_export_returns_action() {
+    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
+    harness.get_by_label("File").click();
+    harness.run();
+    harness.get_by_label_contains("Export PDF").click();
+    harness.run();
+    assert!(
+        harness
+            .state()
+            .contains(&systems::EditorMenuAction::ExportPdf),
+        "Expected ExportPdf action after clicking Export PDF"
+    );
+}
+
+/// File > Close returns `EditorMenuAction::CloseProject`.
+#[test]
+fn editor_menu_file_close_returns_action() {
+    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
+    harness.get_by_label("File").click();
+    harness.run();
+    harness.get_by_label_contains("Close").click();
+    harness.run();
+    assert!(
+        harness
+            .state()
+            .contains(&systems::EditorMenuAction::CloseProject),
+        "Expected CloseProject action after clicking Close"
+    );
+}
+
+/// Edit > Undo (enabled) returns `EditorMenuAction::Undo`.
+#[test]
+fn editor_menu_edit_undo_enabled() {
+    let mut harness = editor_menu_harness(
+        true,
+        Some("paint"),
+        false,
+        None,
+        WorkspacePreset::MapEditing,
+    );
+    harness.get_by_label("Edit").click();
+    harness.run();
+    harness.get_by_label_contains("Undo").click();
+    harness.run();
+    assert!(
+        harness.state().contains(&systems::EditorMenuAction::Undo),
+        "Expected Undo action after clicking Undo"
+    );
+}
+
+/// Edit > Redo (enabled) returns `EditorMenuAction::Redo`.
+#[test]
+fn editor_menu_edit_redo_enabled() {
+    let mut harness = editor_menu_harness(
+        false,
+        None,
+        true,
+        Some("place"),
+        WorkspacePreset::MapEditing,
+    );
+    harness.get_by_label("Edit").click();
+    harness.run();
+    harness.get_by_label_contains("Redo").click();
+    harness.run();
+    assert!(
+        harness.state().contains(&systems::EditorMenuAction::Redo),
+        "Expected Redo action after clicking Redo"
+    );
+}
+
+/// View > Unit Design returns `EditorMenuAction::SwitchPreset(UnitDesign)`.
+#[test]
+fn editor_menu_view_switch_preset() {
+    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
+    harness.get_by_label("View").click();
+    harness.run();
+    harness.get_by_label_contains("Unit Design").click();
+    harness.run();
+    assert!(
+        harness
+            .state()
+            .contains(&systems::EditorMenuAction::SwitchPreset(
+                WorkspacePreset::UnitDesign
+            )),
+        "Expected SwitchPreset(UnitDesign) action after clicking Unit Design"
+    );
 }
 
-/// Play sidebar with phases renders turn tracker content within the sidebar.
+/// Help > About Hexorder returns `EditorMenuAction::ShowAbout`.
 #[test]
-fn play_sidebar_with_phases_shows_turn_tracker() {
-    let workspace = Workspace {
-        name: "Phase Test".to_string(),
-        ..Workspace::default()
-    };
-    let game_system = GameSystem {
-        id: "phase-test".to_string(),
-        version: "2.0".to_string(),
+fn editor_menu_help_about() {
+    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
+    harness.get_by_label("Help").click();
+    harness.run();
+    harness.get_by_label("About Hexorder").click();
+    harness.run();
+    assert!(
+        harness
+            .state()
+            .contains(&systems::EditorMenuAction::ShowAbout),
+        "Expected ShowAbout action after clicking About Hexorder"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Group 2: Combat Panel Result Display (render_play.rs:497-546)
+// ---------------------------------------------------------------------------
+
+/// Combat panel displays die roll value when `die_roll` is `Some`.
+#[test]
+fn combat_panel_shows_die_roll_when_set() {
+    let mut active_combat = ActiveCombat {
+        die_roll: Some(4),
+        ..ActiveCombat::default()
     };
-    let mut turn_state = TurnState::default();
-    let turn_structure = test_turn_structure();
-    let mut active_combat = ActiveCombat::default();
-    let crt = CombatResultsTable::default();
+    let crt = test_crt();
     let modifiers = CombatModifierRegistry::default();
     let selected_unit = SelectedUnit::default();
-    let entity_types = EntityTypeRegistry::default();
-    let mut editor_state = EditorState::default();
-    let mut sim_rng = SimulationRng::new(42);
+    let entity_types = test_registry();
+    let mut editor_state = EditorState {
+        combat_attacker_strength: 2.0,
+        combat_defender_strength: 1.0,
+        ..EditorState::default()
+    };
 
     let harness = Harness::new_ui(|ui| {
-        render_play::render_play_sidebar(
+        render_play::render_combat_panel(
             ui,
-            &workspace,
-            &game_system,
-            &mut turn_state,
-            &turn_structure,
             &mut active_combat,
             &crt,
             &modifiers,
             &selected_unit,
             &entity_types,
             &mut editor_state,
-            &mut sim_rng,
-            &mut AreaMarkerRegistry::default(),
+            &mut SimulationRng::new(42),
+            &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
+            true,
         );
     });
-    harness.get_by_label_contains("Turn Tracker");
-    harness.get_by_label_contains("Turn 1");
+    harness.get_by_label_contains("Die roll:");
+    harness.get_by_label("4");
 }
 
-// ---------------------------------------------------------------------------
-// render_combat_panel -- odds display and combat resolution
-// ---------------------------------------------------------------------------
-
-/// Combat panel shows odds ratio when defender strength is positive.
+/// Combat panel displays the outcome label when an outcome is set.
 #[test]
-fn combat_panel_odds_display_with_positive_defender() {
-    let mut active_combat = ActiveCombat::default();
+fn combat_panel_shows_outcome_label() {
+    let mut active_combat = ActiveCombat {
+        die_roll: Some(3),
+        outcome: Some(CombatOutcome {
+            label: "NE".to_string(),
+            effect: None,
+        }),
+        ..ActiveCombat::default()
+    };
     let crt = test_crt();
     let modifiers = CombatModifierRegistry::default();
     let selected_unit = SelectedUnit::default();
-    let entity_types = EntityTypeRegistry::default();
+    let entity_types = test_registry();
     let mut editor_state = EditorState {
-        combat_attacker_strength: 6.0,
-        combat_defender_strength: 2.0,
+        combat_attacker_strength: 2.0,
+        combat_defender_strength: 1.0,
         ..EditorState::default()
     };
 
@@ -13114,24 +14832,22 @@ fn combat_panel_odds_display_with_positive_defender() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
             true,
         );
     });
-    harness.get_by_label_contains("Odds: 3.00:1");
+    harness.get_by_label_contains("Result: NE");
 }
 
-/// Combat panel shows attacker and defender labels when not set.
+/// Combat panel displays "No effect" for `OutcomeEffect::NoEffect`.
 #[test]
-fn combat_panel_shows_none_when_no_combatants() {
-    let mut active_combat = ActiveCombat::default();
-    let crt = test_crt();
-    let modifiers = CombatModifierRegistry::default();
-    let selected_unit = SelectedUnit::default();
-    let entity_types = EntityTypeRegistry::default();
-    let mut editor_state = EditorState::default();
+fn combat_panel_shows_no_effect_text() {
+    use hexorder_contracts::mechanics::OutcomeEffect;
+    let (mut active_combat, crt, modifiers, selected_unit, entity_types, mut editor_state) =
+        combat_panel_state_with_effect(OutcomeEffect::NoEffect);
 
     let harness = Harness::new_ui(|ui| {
         render_play::render_combat_panel(
@@ -13142,31 +14858,23 @@ fn combat_panel_shows_none_when_no_combatants() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
             true,
         );
     });
-    harness.get_by_label_contains("Attacker:");
-    harness.get_by_label_contains("Defender:");
-    // Both empty combatant slots render — verify at least one "None" label via query_all.
-    assert!(harness.get_all_by_label_contains("None").count() >= 2,);
+    harness.get_by_label_contains("No effect");
+    harness.get_by_label_contains("Result: EF");
 }
 
-/// Combat panel with modifiers shows final column after column shift.
+/// Combat panel displays "Attacker eliminated" for `OutcomeEffect::AttackerEliminated`.
 #[test]
-fn combat_panel_modifiers_show_final_column() {
-    let mut active_combat = ActiveCombat::default();
-    let crt = test_crt();
-    let modifiers = test_modifiers();
-    let selected_unit = SelectedUnit::default();
-    let entity_types = EntityTypeRegistry::default();
-    let mut editor_state = EditorState {
-        combat_attacker_strength: 4.0,
-        combat_defender_strength: 2.0,
-        ..EditorState::default()
-    };
+fn combat_panel_shows_attacker_eliminated() {
+    use hexorder_contracts::mechanics::OutcomeEffect;
+    let (mut active_combat, crt, modifiers, selected_unit, entity_types, mut editor_state) =
+        combat_panel_state_with_effect(OutcomeEffect::AttackerEliminated);
 
     let harness = Harness::new_ui(|ui| {
         render_play::render_combat_panel(
@@ -13177,93 +14885,22 @@ fn combat_panel_modifiers_show_final_column() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
             true,
         );
     });
-    harness.get_by_label_contains("Final column:");
-}
-
-/// Combat panel clear button resets state via actual render function.
-#[test]
-fn combat_panel_clear_via_render_function() {
-    struct CombatState {
-        active_combat: ActiveCombat,
-        crt: CombatResultsTable,
-        modifiers: CombatModifierRegistry,
-        selected_unit: SelectedUnit,
-        entity_types: EntityTypeRegistry,
-        editor_state: EditorState,
-    }
-
-    let state = CombatState {
-        active_combat: ActiveCombat {
-            die_roll: Some(4),
-            ..ActiveCombat::default()
-        },
-        crt: test_crt(),
-        modifiers: CombatModifierRegistry::default(),
-        selected_unit: SelectedUnit::default(),
-        entity_types: EntityTypeRegistry::default(),
-        editor_state: EditorState {
-            combat_attacker_strength: 3.0,
-            combat_defender_strength: 2.0,
-            ..EditorState::default()
-        },
-    };
-
-    let mut harness = Harness::new_ui_state(
-        |ui, s: &mut CombatState| {
-            render_play::render_combat_panel(
-                ui,
-                &mut s.active_combat,
-                &s.crt,
-                &s.modifiers,
-                &s.selected_unit,
-                &s.entity_types,
-                &mut s.editor_state,
-                &AreaMarkerRegistry::default(),
-                &|_| None,
-                &|_| None,
-                true,
-            );
-        },
-        state,
-    );
-
-    harness.get_by_label("Clear Combat").click();
-    harness.run();
-    assert!(
-        harness.state().active_combat.die_roll.is_none(),
-        "Expected die_roll to be cleared after clicking Clear Combat"
-    );
-    assert_eq!(
-        harness.state().editor_state.combat_attacker_strength,
-        0.0,
-        "Expected attacker strength to be reset"
-    );
-    assert_eq!(
-        harness.state().editor_state.combat_defender_strength,
-        0.0,
-        "Expected defender strength to be reset"
-    );
+    harness.get_by_label_contains("Attacker eliminated");
 }
 
-/// Combat panel shows base column label when odds match a CRT column.
+/// Combat panel displays "Defender eliminated" for `OutcomeEffect::DefenderEliminated`.
 #[test]
-fn combat_panel_shows_base_column_label() {
-    let mut active_combat = ActiveCombat::default();
-    let crt = test_crt();
-    let modifiers = CombatModifierRegistry::default();
-    let selected_unit = SelectedUnit::default();
-    let entity_types = EntityTypeRegistry::default();
-    let mut editor_state = EditorState {
-        combat_attacker_strength: 2.0,
-        combat_defender_strength: 2.0,
-        ..EditorState::default()
-    };
+fn combat_panel_shows_defender_eliminated() {
+    use hexorder_contracts::mechanics::OutcomeEffect;
+    let (mut active_combat, crt, modifiers, selected_unit, entity_types, mut editor_state) =
+        combat_panel_state_with_effect(OutcomeEffect::DefenderEliminated);
 
     let harness = Harness::new_ui(|ui| {
         render_play::render_combat_panel(
@@ -13274,443 +14911,678 @@ fn combat_panel_shows_base_column_label() {
             &selected_unit,
             &entity_types,
             &mut editor_state,
+            &mut SimulationRng::new(42),
             &AreaMarkerRegistry::default(),
             &|_| None,
             &|_| None,
             true,
         );
     });
-    harness.get_by_label_contains("Base column:");
+    harness.get_by_label_contains("Defender eliminated");
 }
 
 // ---------------------------------------------------------------------------
-// Group 1: Editor Menu Bar Submenu Content (systems.rs:648-720)
+// Group 3: Play File Menu Button Clicks (render_play.rs:118-132)
 // ---------------------------------------------------------------------------
 
-/// Helper: builds a harness that tracks `EditorMenuAction`s from the editor
-/// menu bar. Actions are accumulated rather than overwritten so that the
-/// `CloseOnClick` popup behavior (which re-renders after closing the menu
-/// with an empty vector) does not erase captured actions.
-fn editor_menu_harness(
-    can_undo: bool,
-    undo_desc: Option<&'static str>,
-    can_redo: bool,
-    redo_desc: Option<&'static str>,
-    active_preset: WorkspacePreset,
-) -> Harness<'static, Vec<systems::EditorMenuAction>> {
-    Harness::new_ui_state(
-        move |ui, actions: &mut Vec<systems::EditorMenuAction>| {
-            let result = systems::render_editor_menu_bar(
-                ui,
-                can_undo,
-                undo_desc,
-                can_redo,
-                redo_desc,
-                active_preset,
-            );
+/// Clicking New in the play file menu returns `PlayMenuAction::NewProject`.
+#[test]
+fn play_file_menu_click_new() {
+    let mut harness = Harness::new_ui_state(
+        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
+            let result = render_play::render_play_file_menu(ui);
             if !result.is_empty() {
                 *actions = result;
             }
         },
         Vec::new(),
-    )
-}
-
-/// File > New returns `EditorMenuAction::NewProject`.
-#[test]
-fn editor_menu_file_new_returns_action() {
-    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
-    harness.get_by_label("File").click();
-    harness.run();
+    );
     harness.get_by_label_contains("Cmd+N").click();
     harness.run();
-    assert!(
-        harness
-            .state()
-            .contains(&systems::EditorMenuAction::NewProject),
-        "Expected NewProject action after clicking New"
+    assert_eq!(
+        *harness.state(),
+        vec![render_play::PlayMenuAction::NewProject]
     );
 }
 
-/// File > Open returns `EditorMenuAction::OpenFile`.
+/// Clicking Open in the play file menu returns `PlayMenuAction::OpenProject`.
 #[test]
-fn editor_menu_file_open_returns_action() {
-    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
-    harness.get_by_label("File").click();
-    harness.run();
+fn play_file_menu_click_open() {
+    let mut harness = Harness::new_ui_state(
+        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
+            let result = render_play::render_play_file_menu(ui);
+            if !result.is_empty() {
+                *actions = result;
+            }
+        },
+        Vec::new(),
+    );
     harness.get_by_label_contains("Cmd+O").click();
     harness.run();
-    assert!(
-        harness
-            .state()
-            .contains(&systems::EditorMenuAction::OpenFile),
-        "Expected OpenFile action after clicking Open"
+    assert_eq!(
+        *harness.state(),
+        vec![render_play::PlayMenuAction::OpenProject]
     );
 }
 
-/// File > Save returns `EditorMenuAction::Save`.
+/// Clicking Save in the play file menu returns `PlayMenuAction::Save`.
 #[test]
-fn editor_menu_file_save_returns_action() {
-    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
-    harness.get_by_label("File").click();
-    harness.run();
+fn play_file_menu_click_save() {
+    let mut harness = Harness::new_ui_state(
+        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
+            let result = render_play::render_play_file_menu(ui);
+            if !result.is_empty() {
+                *actions = result;
+            }
+        },
+        Vec::new(),
+    );
     harness.get_by_label_contains("Save         Cmd+S").click();
     harness.run();
-    assert!(
-        harness.state().contains(&systems::EditorMenuAction::Save),
-        "Expected Save action after clicking Save"
-    );
+    assert_eq!(*harness.state(), vec![render_play::PlayMenuAction::Save]);
 }
 
-/// File > Save As returns `EditorMenuAction::SaveAs`.
+/// Clicking Save As in the play file menu returns `PlayMenuAction::SaveAs`.
 #[test]
-fn editor_menu_file_save_as_returns_action() {
-    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
-    harness.get_by_label("File").click();
-    harness.run();
+fn play_file_menu_click_save_as() {
+    let mut harness = Harness::new_ui_state(
+        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
+            let result = render_play::render_play_file_menu(ui);
+            if !result.is_empty() {
+                *actions = result;
+            }
+        },
+        Vec::new(),
+    );
     harness.get_by_label_contains("Cmd+Shift+S").click();
     harness.run();
-    assert!(
-        harness.state().contains(&systems::EditorMenuAction::SaveAs),
-        "Expected SaveAs action after clicking Save As"
-    );
+    assert_eq!(*harness.state(), vec![render_play::PlayMenuAction::SaveAs]);
 }
 
-/// File > Export PDF returns `EditorMenuAction::ExportPdf`.
+// ---------------------------------------------------------------------------
+// Off-Map Zones panel (render_play::render_off_map_zones_panel)
+// ---------------------------------------------------------------------------
+
+/// Helper: a registry with one "Reserve" zone holding a single token unit.
+fn test_zone_registry() -> hexorder_contracts::mechanics::OffMapZoneRegistry {
+    use hexorder_contracts::mechanics::{OffMapZone, OffMapZoneRegistry, ZoneUnit};
+
+    let token_id = test_registry()
+        .first_by_role(EntityRole::Token)
+        .map(|et| et.id)
+        .expect("token type");
+    let mut zone = OffMapZone::new("Reserve");
+    zone.units.push(ZoneUnit {
+        entity_type_id: token_id,
+        properties: HashMap::new(),
+        owner: None,
+        id: None,
+        state: None,
+    });
+    OffMapZoneRegistry {
+        zones: vec![zone],
+        eliminated_zone_id: None,
+    }
+}
+
+/// Zone panel lists zones with their unit counts.
 #[test]
-fn editor_menu_file_export_returns_action() {
-    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
-    harness.get_by_label("File").click();
-    harness.run();
-    harness.get_by_label_contains("Export PDF").click();
-    harness.run();
-    assert!(
-        harness
-            .state()
-            .contains(&systems::EditorMenuAction::ExportPdf),
-        "Expected ExportPdf action after clicking Export PDF"
-    );
+fn zones_panel_lists_zone_contents() {
+    let zones = test_zone_registry();
+    let entity_types = test_registry();
+    let harness = Harness::new_ui(|ui| {
+        render_play::render_off_map_zones_panel(
+            ui,
+            &zones,
+            &entity_types,
+            &SelectedUnit::default(),
+            None,
+            None,
+        );
+    });
+    harness.get_by_label("Off-Map Zones");
+    harness.get_by_label_contains("Reserve (1)");
 }
 
-/// File > Close returns `EditorMenuAction::CloseProject`.
+/// Clicking Deploy with a selected hex requests a deploy to that hex.
 #[test]
-fn editor_menu_file_close_returns_action() {
-    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
-    harness.get_by_label("File").click();
-    harness.run();
-    harness.get_by_label_contains("Close").click();
+fn zones_panel_deploy_requests_selected_hex() {
+    let zones = test_zone_registry();
+    let zone_id = zones.zones[0].id;
+    let entity_types = test_registry();
+    let mut harness = Harness::new_ui_state(
+        |ui, requests: &mut Vec<render_play::ZoneRequest>| {
+            let result = render_play::render_off_map_zones_panel(
+                ui,
+                &zones,
+                &entity_types,
+                &SelectedUnit::default(),
+                Some(HexPosition::new(2, 1)),
+                None,
+            );
+            if !result.is_empty() {
+                *requests = result;
+            }
+        },
+        Vec::new(),
+    );
+    harness.get_by_label("Deploy").click();
     harness.run();
-    assert!(
-        harness
-            .state()
-            .contains(&systems::EditorMenuAction::CloseProject),
-        "Expected CloseProject action after clicking Close"
+    assert_eq!(
+        *harness.state(),
+        vec![render_play::ZoneRequest::Deploy {
+            zone_id,
+            index: 0,
+            position: HexPosition::new(2, 1),
+        }]
     );
 }
 
-/// Edit > Undo (enabled) returns `EditorMenuAction::Undo`.
+// ---------------------------------------------------------------------------
+// Board shape editor (render_rules::render_board_shape)
+// ---------------------------------------------------------------------------
+
+/// Choosing Rectangle switches the shape and exposes the wrap toggle.
 #[test]
-fn editor_menu_edit_undo_enabled() {
-    let mut harness = editor_menu_harness(
-        true,
-        Some("paint"),
-        false,
-        None,
-        WorkspacePreset::MapEditing,
+fn board_shape_rectangle_enables_wrap_toggle() {
+    use hexorder_contracts::hex_grid::GridShape;
+
+    let mut harness = Harness::new_ui_state(
+        |ui, shape: &mut GridShape| {
+            render_rules::render_board_shape(ui, shape);
+        },
+        GridShape::Hexagon,
     );
-    harness.get_by_label("Edit").click();
+    harness.get_by_label("Rectangle").click();
     harness.run();
-    harness.get_by_label_contains("Undo").click();
+    harness.get_by_label("Wrap east-west (cylinder)").click();
     harness.run();
-    assert!(
-        harness.state().contains(&systems::EditorMenuAction::Undo),
-        "Expected Undo action after clicking Undo"
+    assert_eq!(
+        *harness.state(),
+        GridShape::Rectangle {
+            width: 20,
+            height: 14,
+            wrap_horizontal: true,
+        }
     );
 }
 
-/// Edit > Redo (enabled) returns `EditorMenuAction::Redo`.
-#[test]
-fn editor_menu_edit_redo_enabled() {
-    let mut harness = editor_menu_harness(
-        false,
-        None,
-        true,
-        Some("place"),
-        WorkspacePreset::MapEditing,
+// ---------------------------------------------------------------------------
+// Move cost breakdown
+// ---------------------------------------------------------------------------
+
+fn two_step_moves() -> ValidMoveSet {
+    let mut moves = ValidMoveSet::default();
+    let start = HexPosition::new(0, 0);
+    let a = HexPosition::new(1, 0);
+    let b = HexPosition::new(2, 0);
+    let terrain = |amount| CostComponent {
+        source: CostSource::Terrain,
+        label: "Terrain Movement Cost".to_string(),
+        amount,
+    };
+    moves.paths.insert(
+        a,
+        PathStep {
+            from: start,
+            components: vec![terrain(1)],
+            total_cost: 1,
+        },
     );
-    harness.get_by_label("Edit").click();
-    harness.run();
-    harness.get_by_label_contains("Redo").click();
-    harness.run();
-    assert!(
-        harness.state().contains(&systems::EditorMenuAction::Redo),
-        "Expected Redo action after clicking Redo"
+    moves.paths.insert(
+        b,
+        PathStep {
+            from: a,
+            components: vec![
+                CostComponent {
+                    source: CostSource::Edge,
+                    label: "River".to_string(),
+                    amount: 2,
+                },
+                terrain(1),
+            ],
+            total_cost: 4,
+        },
     );
+    moves
 }
 
-/// View > Unit Design returns `EditorMenuAction::SwitchPreset(UnitDesign)`.
 #[test]
-fn editor_menu_view_switch_preset() {
-    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
-    harness.get_by_label("View").click();
-    harness.run();
-    harness.get_by_label_contains("Unit Design").click();
-    harness.run();
-    assert!(
-        harness
-            .state()
-            .contains(&systems::EditorMenuAction::SwitchPreset(
-                WorkspacePreset::UnitDesign
-            )),
-        "Expected SwitchPreset(UnitDesign) action after clicking Unit Design"
-    );
+fn move_breakdown_shows_total_and_components() {
+    let moves = two_step_moves();
+    let harness = Harness::new_ui(|ui| {
+        render_panels::render_move_breakdown(ui, &moves, HexPosition::new(2, 0));
+    });
+    harness.get_by_label_contains("cost 4");
+    harness.get_by_label("(2, 0) +3");
+    harness.get_by_label_contains("River +2");
 }
 
-/// Help > About Hexorder returns `EditorMenuAction::ShowAbout`.
 #[test]
-fn editor_menu_help_about() {
-    let mut harness = editor_menu_harness(false, None, false, None, WorkspacePreset::MapEditing);
-    harness.get_by_label("Help").click();
-    harness.run();
-    harness.get_by_label("About Hexorder").click();
-    harness.run();
-    assert!(
-        harness
-            .state()
-            .contains(&systems::EditorMenuAction::ShowAbout),
-        "Expected ShowAbout action after clicking About Hexorder"
-    );
+fn move_breakdown_empty_for_unreachable_hex() {
+    let moves = two_step_moves();
+    let harness = Harness::new_ui(|ui| {
+        render_panels::render_move_breakdown(ui, &moves, HexPosition::new(5, 5));
+    });
+    assert!(harness.query_by_label_contains("cost").is_none());
 }
 
 // ---------------------------------------------------------------------------
-// Group 2: Combat Panel Result Display (render_play.rs:497-546)
+// Factions
 // ---------------------------------------------------------------------------
 
-/// Combat panel displays die roll value when `die_roll` is `Some`.
+/// Adding a faction appends it after the last player in turn order.
 #[test]
-fn combat_panel_shows_die_roll_when_set() {
-    let mut active_combat = ActiveCombat {
-        die_roll: Some(4),
-        ..ActiveCombat::default()
+fn factions_add_appends_in_player_order() {
+    use hexorder_contracts::game_system::{Faction, FactionRegistry};
+
+    let editor_state = EditorState {
+        new_faction_name: "Allies".to_string(),
+        ..Default::default()
     };
-    let crt = test_crt();
-    let modifiers = CombatModifierRegistry::default();
-    let selected_unit = SelectedUnit::default();
-    let entity_types = test_registry();
-    let mut editor_state = EditorState {
-        combat_attacker_strength: 2.0,
-        combat_defender_strength: 1.0,
-        ..EditorState::default()
+    let factions = FactionRegistry {
+        factions: vec![Faction {
+            id: TypeId::new(),
+            name: "Axis".to_string(),
+            color: Color::WHITE,
+            player_order: 3,
+        }],
     };
+    let mut harness = Harness::new_ui_state(
+        |ui, (factions, editor_state): &mut (FactionRegistry, EditorState)| {
+            render_rules::render_factions(ui, factions, editor_state);
+        },
+        (factions, editor_state),
+    );
+    harness.get_by_label("Add Faction").click();
+    harness.run();
 
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_combat_panel(
-            ui,
-            &mut active_combat,
-            &crt,
-            &modifiers,
-            &selected_unit,
-            &entity_types,
-            &mut editor_state,
-            &AreaMarkerRegistry::default(),
-            &|_| None,
-            &|_| None,
-            true,
-        );
-    });
-    harness.get_by_label_contains("Die roll:");
-    harness.get_by_label("4");
+    let (factions, editor_state) = harness.state();
+    assert_eq!(factions.factions.len(), 2);
+    assert_eq!(factions.factions[1].name, "Allies");
+    assert_eq!(factions.factions[1].player_order, 4);
+    assert!(editor_state.new_faction_name.is_empty());
 }
 
-/// Combat panel displays the outcome label when an outcome is set.
-#[test]
-fn combat_panel_shows_outcome_label() {
-    let mut active_combat = ActiveCombat {
-        die_roll: Some(3),
-        outcome: Some(CombatOutcome {
-            label: "NE".to_string(),
-            effect: None,
-        }),
-        ..ActiveCombat::default()
-    };
-    let crt = test_crt();
-    let modifiers = CombatModifierRegistry::default();
-    let selected_unit = SelectedUnit::default();
-    let entity_types = test_registry();
-    let mut editor_state = EditorState {
-        combat_attacker_strength: 2.0,
-        combat_defender_strength: 1.0,
-        ..EditorState::default()
-    };
+/// The owner picker assigns a faction to the selected unit.
+#[test]
+fn unit_owner_picker_assigns_faction() {
+    use hexorder_contracts::game_system::{Faction, FactionRegistry, UnitOwner};
 
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_combat_panel(
-            ui,
-            &mut active_combat,
-            &crt,
-            &modifiers,
-            &selected_unit,
-            &entity_types,
-            &mut editor_state,
-            &AreaMarkerRegistry::default(),
-            &|_| None,
-            &|_| None,
-            true,
-        );
-    });
-    harness.get_by_label_contains("Result: NE");
+    let axis = Faction {
+        id: TypeId::new(),
+        name: "Axis".to_string(),
+        color: Color::WHITE,
+        player_order: 0,
+    };
+    let axis_id = axis.id;
+    let factions = FactionRegistry {
+        factions: vec![axis],
+    };
+    let mut harness = Harness::new_ui_state(
+        |ui, owner: &mut UnitOwner| {
+            render_rules::render_unit_owner(ui, owner, &factions);
+        },
+        UnitOwner::default(),
+    );
+    harness.get_by_label("Unowned").click();
+    harness.run();
+    harness.get_by_label("Axis").click();
+    harness.run();
+    assert_eq!(harness.state().faction_id, Some(axis_id));
 }
 
-/// Combat panel displays "No effect" for `OutcomeEffect::NoEffect`.
+/// The stacking editor toggles the mixed-faction restriction.
 #[test]
-fn combat_panel_shows_no_effect_text() {
-    use hexorder_contracts::mechanics::OutcomeEffect;
-    let (mut active_combat, crt, modifiers, selected_unit, entity_types, mut editor_state) =
-        combat_panel_state_with_effect(OutcomeEffect::NoEffect);
+fn stacking_rule_toggles_mixed_factions() {
+    use hexorder_contracts::hex_grid::StackingRule;
 
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_combat_panel(
-            ui,
-            &mut active_combat,
-            &crt,
-            &modifiers,
-            &selected_unit,
-            &entity_types,
-            &mut editor_state,
-            &AreaMarkerRegistry::default(),
-            &|_| None,
-            &|_| None,
-            true,
-        );
-    });
-    harness.get_by_label_contains("No effect");
-    harness.get_by_label_contains("Result: EF");
+    let registry = EntityTypeRegistry::default();
+    let mut harness = Harness::new_ui_state(
+        |ui, (rule, editor_state): &mut (StackingRule, EditorState)| {
+            render_rules::render_stacking_rule(
+                ui,
+                rule,
+                &registry,
+                &hexorder_contracts::game_system::FactionRegistry::default(),
+                editor_state,
+            );
+        },
+        (StackingRule::default(), EditorState::default()),
+    );
+    harness.get_by_label("No mixed-faction stacks").click();
+    harness.run();
+    assert!(harness.state().0.no_mixed_factions);
 }
 
-/// Combat panel displays "Attacker eliminated" for `OutcomeEffect::AttackerEliminated`.
+/// The stacking editor adds a per-terrain limit.
 #[test]
-fn combat_panel_shows_attacker_eliminated() {
-    use hexorder_contracts::mechanics::OutcomeEffect;
-    let (mut active_combat, crt, modifiers, selected_unit, entity_types, mut editor_state) =
-        combat_panel_state_with_effect(OutcomeEffect::AttackerEliminated);
+fn stacking_rule_adds_terrain_limit() {
+    use hexorder_contracts::hex_grid::StackingRule;
 
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_combat_panel(
-            ui,
-            &mut active_combat,
-            &crt,
-            &modifiers,
-            &selected_unit,
-            &entity_types,
-            &mut editor_state,
-            &AreaMarkerRegistry::default(),
-            &|_| None,
-            &|_| None,
-            true,
-        );
-    });
-    harness.get_by_label_contains("Attacker eliminated");
+    let swamp_id = TypeId::new();
+    let registry = EntityTypeRegistry {
+        types: vec![EntityType {
+            id: swamp_id,
+            name: "Swamp".to_string(),
+            role: EntityRole::BoardPosition,
+            color: Color::WHITE,
+            properties: Vec::new(),
+        }],
+    };
+    let mut harness = Harness::new_ui_state(
+        |ui, (rule, editor_state): &mut (StackingRule, EditorState)| {
+            render_rules::render_stacking_rule(
+                ui,
+                rule,
+                &registry,
+                &hexorder_contracts::game_system::FactionRegistry::default(),
+                editor_state,
+            );
+        },
+        (StackingRule::default(), EditorState::default()),
+    );
+    click_combobox_by_value(&mut harness, "Select...");
+    harness.get_by_label("Swamp").click();
+    harness.run();
+    harness.get_by_label("Add Terrain Limit").click();
+    harness.run();
+    assert_eq!(harness.state().0.terrain_limits.get(&swamp_id), Some(&1));
 }
 
-/// Combat panel displays "Defender eliminated" for `OutcomeEffect::DefenderEliminated`.
+/// Unit reference picker lists placed units and stores the chosen id.
 #[test]
-fn combat_panel_shows_defender_eliminated() {
-    use hexorder_contracts::mechanics::OutcomeEffect;
-    let (mut active_combat, crt, modifiers, selected_unit, entity_types, mut editor_state) =
-        combat_panel_state_with_effect(OutcomeEffect::DefenderEliminated);
+fn property_value_editor_unit_ref_picks_unit() {
+    use hexorder_contracts::game_system::UnitId;
 
-    let harness = Harness::new_ui(|ui| {
-        render_play::render_combat_panel(
-            ui,
-            &mut active_combat,
-            &crt,
-            &modifiers,
-            &selected_unit,
-            &entity_types,
-            &mut editor_state,
-            &AreaMarkerRegistry::default(),
-            &|_| None,
-            &|_| None,
-            true,
-        );
-    });
-    harness.get_by_label_contains("Defender eliminated");
+    let leader = UnitId::new();
+    let units = vec![(leader, "Infantry #leader (0, 0)".to_string())];
+    let mut harness = Harness::new_ui_state(
+        move |ui, value: &mut PropertyValue| {
+            render_rules::render_property_value_editor(
+                ui,
+                value,
+                &PropertyType::UnitRef,
+                &EnumRegistry::default(),
+                &StructRegistry::default(),
+                &EntityTypeRegistry::default(),
+                &units,
+                0,
+            );
+        },
+        PropertyValue::UnitRef(None),
+    );
+    harness.run();
+    click_combobox_by_value(&mut harness, "(none)");
+    harness.get_by_label("Infantry #leader (0, 0)").click();
+    harness.run();
+    assert_eq!(*harness.state(), PropertyValue::UnitRef(Some(leader)));
 }
 
 // ---------------------------------------------------------------------------
-// Group 3: Play File Menu Button Clicks (render_play.rs:118-132)
+// State machines
 // ---------------------------------------------------------------------------
 
-/// Clicking New in the play file menu returns `PlayMenuAction::NewProject`.
+fn step_state(name: &str) -> hexorder_contracts::game_system::StateDefinition {
+    hexorder_contracts::game_system::StateDefinition {
+        id: TypeId::new(),
+        name: name.to_string(),
+        property_overrides: HashMap::new(),
+        color: None,
+        badge: String::new(),
+    }
+}
+
+/// Adding a state machine attaches it to the chosen entity type.
 #[test]
-fn play_file_menu_click_new() {
+fn state_machines_add_machine_for_type() {
+    use hexorder_contracts::game_system::StateMachineRegistry;
+
+    let registry = EntityTypeRegistry {
+        types: vec![EntityType {
+            id: TypeId::new(),
+            name: "Infantry".to_string(),
+            role: EntityRole::Token,
+            color: Color::WHITE,
+            properties: Vec::new(),
+        }],
+    };
+    let infantry_id = registry.types[0].id;
+    let editor_state = EditorState {
+        new_state_machine_type_idx: Some(0),
+        ..Default::default()
+    };
     let mut harness = Harness::new_ui_state(
-        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
-            let result = render_play::render_play_file_menu(ui);
-            if !result.is_empty() {
-                *actions = result;
-            }
+        |ui, (machines, editor_state): &mut (StateMachineRegistry, EditorState)| {
+            render_rules::render_state_machines(
+                ui,
+                machines,
+                &registry,
+                &EnumRegistry::default(),
+                &StructRegistry::default(),
+                &TurnStructure::default(),
+                &ConstraintRegistry::default(),
+                &hexorder_contracts::hex_grid::ReachabilityRuleRegistry::default(),
+                editor_state,
+            );
         },
-        Vec::new(),
+        (StateMachineRegistry::default(), editor_state),
     );
-    harness.get_by_label_contains("Cmd+N").click();
+    harness.get_by_label("Add State Machine").click();
     harness.run();
-    assert_eq!(
-        *harness.state(),
-        vec![render_play::PlayMenuAction::NewProject]
-    );
+
+    let (machines, _) = harness.state();
+    assert_eq!(machines.machines.len(), 1);
+    assert_eq!(machines.machines[0].entity_type_id, infantry_id);
+    assert!(machines.for_type(infantry_id).is_some());
 }
 
-/// Clicking Open in the play file menu returns `PlayMenuAction::OpenProject`.
+/// Manual transitions out of the current state are offered as buttons that
+/// push a `SetEntityState` action.
 #[test]
-fn play_file_menu_click_open() {
+fn entity_state_manual_transition_pushes_action() {
+    use hexorder_contracts::game_system::{StateMachine, StateTransition, StateTrigger};
+
+    let (full, reduced) = (step_state("Full"), step_state("Reduced"));
+    let reduced_id = reduced.id;
+    let machine = StateMachine {
+        id: TypeId::new(),
+        name: "Steps".to_string(),
+        entity_type_id: TypeId::new(),
+        states: vec![full.clone(), reduced],
+        initial_state: None,
+        transitions: vec![
+            StateTransition {
+                id: TypeId::new(),
+                name: "Take hit".to_string(),
+                from: Some(full.id),
+                to: reduced_id,
+                trigger: StateTrigger::Manual,
+            },
+            StateTransition {
+                id: TypeId::new(),
+                name: "Step loss".to_string(),
+                from: Some(full.id),
+                to: reduced_id,
+                trigger: StateTrigger::StepLoss,
+            },
+        ],
+    };
+    let entity = Entity::PLACEHOLDER;
     let mut harness = Harness::new_ui_state(
-        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
-            let result = render_play::render_play_file_menu(ui);
-            if !result.is_empty() {
-                *actions = result;
-            }
+        |ui, actions: &mut Vec<EditorAction>| {
+            render_rules::render_entity_state(ui, entity, full.id, &machine, actions);
         },
         Vec::new(),
     );
-    harness.get_by_label_contains("Cmd+O").click();
+    harness.get_by_label("Full");
+    assert!(harness.query_by_label("Step loss").is_none());
+    harness.get_by_label("Take hit").click();
+    harness.run();
+
+    let actions = harness.state();
+    assert_eq!(actions.len(), 1);
+    assert!(matches!(
+        actions[0],
+        EditorAction::SetEntityState { entity: e, state_id } if e == entity && state_id == reduced_id
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// Reachability
+// ---------------------------------------------------------------------------
+
+/// A new rule can take the selected hex as a source and be traced on demand.
+#[test]
+fn reachability_rule_adds_selected_hex_source_and_traces() {
+    use hexorder_contracts::hex_grid::{
+        ReachabilityOverlay, ReachabilityRuleRegistry, ReachabilitySource,
+    };
+
+    let editor_state = EditorState {
+        new_reachability_name: "Supply".to_string(),
+        ..Default::default()
+    };
+    let mut harness = Harness::new_ui_state(
+        |ui,
+         (rules, overlay, editor_state, actions): &mut (
+            ReachabilityRuleRegistry,
+            ReachabilityOverlay,
+            EditorState,
+            Vec<EditorAction>,
+        )| {
+            render_rules::render_reachability_rules(
+                ui,
+                rules,
+                overlay,
+                &EntityTypeRegistry::default(),
+                &TurnStructure::default(),
+                Some(HexPosition::new(2, -1)),
+                editor_state,
+                actions,
+            );
+        },
+        (
+            ReachabilityRuleRegistry::default(),
+            ReachabilityOverlay::default(),
+            editor_state,
+            Vec::new(),
+        ),
+    );
+    harness.get_by_label("Add Reachability Rule").click();
+    harness.run();
+    harness.get_by_label("Supply").click();
+    harness.run();
+    harness.get_by_label("+ Selected Hex").click();
+    harness.run();
+    harness.get_by_label("Trace Now").click();
     harness.run();
+
+    let (rules, _, _, actions) = harness.state();
+    assert_eq!(rules.rules.len(), 1);
+    let rule_id = rules.rules[0].id;
     assert_eq!(
-        *harness.state(),
-        vec![render_play::PlayMenuAction::OpenProject]
+        rules.rules[0].sources,
+        vec![ReachabilitySource::Hexes(vec![HexPosition::new(2, -1)])]
     );
+    assert!(matches!(
+        actions.as_slice(),
+        [EditorAction::TraceReachability { rule_id: Some(id) }] if *id == rule_id
+    ));
 }
 
-/// Clicking Save in the play file menu returns `PlayMenuAction::Save`.
+// ---------------------------------------------------------------------------
+// Overrun (render_rules::render_overrun_rules)
+// ---------------------------------------------------------------------------
+
+/// A new overrun rule looks up the CRT's outcome labels and gains outcomes.
 #[test]
-fn play_file_menu_click_save() {
+fn overrun_rule_copies_crt_and_adds_outcome() {
+    use hexorder_contracts::mechanics::OverrunRegistry;
+
+    let crt = test_crt();
+    let editor_state = EditorState {
+        new_overrun_name: "Tank Rush".to_string(),
+        ..Default::default()
+    };
     let mut harness = Harness::new_ui_state(
-        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
-            let result = render_play::render_play_file_menu(ui);
-            if !result.is_empty() {
-                *actions = result;
-            }
+        |ui, (overruns, editor_state): &mut (OverrunRegistry, EditorState)| {
+            render_rules::render_overrun_rules(
+                ui,
+                overruns,
+                &crt,
+                &EntityTypeRegistry::default(),
+                &hexorder_contracts::game_system::StateMachineRegistry::default(),
+                editor_state,
+            );
         },
-        Vec::new(),
+        (OverrunRegistry::default(), editor_state),
     );
-    harness.get_by_label_contains("Save         Cmd+S").click();
+    harness.get_by_label("Add Overrun Rule").click();
     harness.run();
-    assert_eq!(*harness.state(), vec![render_play::PlayMenuAction::Save]);
+    harness.get_by_label("Tank Rush").click();
+    harness.run();
+    harness.get_by_label("+ Outcome").click();
+    harness.run();
+
+    let (overruns, _) = harness.state();
+    assert_eq!(overruns.rules.len(), 1);
+    let rule = &overruns.rules[0];
+    assert_eq!(rule.outcomes.len(), 1);
+    let InterruptResolution::Table { table, .. } = &rule.resolution else {
+        panic!("new overrun rules look up a table");
+    };
+    assert_eq!(table.columns.len(), crt.table.columns.len());
+    assert!(matches!(&table.outcomes[0][0], TableResult::Text(label) if label == "NE"));
 }
 
-/// Clicking Save As in the play file menu returns `PlayMenuAction::SaveAs`.
 #[test]
-fn play_file_menu_click_save_as() {
+fn constraint_gate_checkbox_sets_gates() {
+    use hexorder_contracts::ontology::GatedAction;
+
+    let constraint_id = TypeId::new();
+    let state = ConstraintsState {
+        constraint_registry: ConstraintRegistry {
+            constraints: vec![Constraint {
+                id: constraint_id,
+                name: "Command range".to_string(),
+                description: String::new(),
+                concept_id: TypeId::new(),
+                relation_id: None,
+                expression: ConstraintExpr::All(Vec::new()),
+                auto_generated: false,
+                gates: vec![GatedAction::Move],
+            }],
+        },
+        concept_registry: test_concept_registry(),
+        editor_state: EditorState::default(),
+        actions: Vec::new(),
+    };
+
     let mut harness = Harness::new_ui_state(
-        |ui, actions: &mut Vec<render_play::PlayMenuAction>| {
-            let result = render_play::render_play_file_menu(ui);
-            if !result.is_empty() {
-                *actions = result;
-            }
+        |ui, s: &mut ConstraintsState| {
+            systems::render_constraints_tab(
+                ui,
+                &mut s.constraint_registry,
+                &s.concept_registry,
+                &EntityTypeRegistry::default(),
+                &mut s.editor_state,
+                &mut s.actions,
+            );
         },
-        Vec::new(),
+        state,
     );
-    harness.get_by_label_contains("Cmd+Shift+S").click();
+
+    harness.get_by_label("attack").click();
     harness.run();
-    assert_eq!(*harness.state(), vec![render_play::PlayMenuAction::SaveAs]);
+
+    let actions = &harness.state().actions;
+    assert!(
+        actions.iter().any(|a| matches!(
+            a,
+            EditorAction::SetConstraintGates { id, gates }
+                if *id == constraint_id && *gates == vec![GatedAction::Move, GatedAction::Attack]
+        )),
+        "expected SetConstraintGates, got: {actions:?}"
+    );
 }
@@ -7,11 +7,12 @@
 use bevy::prelude::*;
 
 use hexorder_contracts::game_system::{
-    ActiveBoardType, ActiveTokenType, EntityRole, SelectedUnit, StructRegistry,
+    ActiveBoardType, ActiveFaction, ActiveTokenType, EntityRole, FactionRegistry, SelectedUnit,
+    StateMachineRegistry, StructRegistry,
 };
 use hexorder_contracts::mechanics::{
-    AccumulatorRegistry, ActiveCombat, AreaMarkerRegistry, CombatModifierRegistry, SpawnSchedule,
-    TurnState, VictoryConditionRegistry,
+    AccumulatorRegistry, ActiveCombat, AreaMarkerRegistry, CombatModifierRegistry,
+    OffMapZoneRegistry, SpawnSchedule, TurnState, VictoryConditionRegistry,
 };
 
 mod systems;
@@ -44,6 +45,9 @@ impl Plugin for GameSystemPlugin {
             entity_type_id: first_token_id,
         });
         app.insert_resource(SelectedUnit::default());
+        app.insert_resource(FactionRegistry::default());
+        app.insert_resource(ActiveFaction::default());
+        app.insert_resource(StateMachineRegistry::default());
 
         // Mechanics resources (0.9.0).
         app.insert_resource(systems::create_default_turn_structure());
@@ -55,5 +59,6 @@ impl Plugin for GameSystemPlugin {
         app.insert_resource(SpawnSchedule::default());
         app.insert_resource(AccumulatorRegistry::default());
         app.insert_resource(VictoryConditionRegistry::default());
+        app.insert_resource(OffMapZoneRegistry::default());
     }
 }
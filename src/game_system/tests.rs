@@ -5,8 +5,9 @@ use std::collections::HashSet;
 use bevy::prelude::*;
 
 use hexorder_contracts::game_system::{
-    ActiveBoardType, ActiveTokenType, EntityRole, EntityTypeRegistry, EnumDefinition, EnumRegistry,
-    GameSystem, PropertyType, PropertyValue, SelectedUnit, StructRegistry, TypeId,
+    ActiveBoardType, ActiveFaction, ActiveTokenType, EntityRole, EntityTypeRegistry,
+    EnumDefinition, EnumRegistry, FactionRegistry, GameSystem, PropertyType, PropertyValue,
+    SelectedUnit, StructRegistry, TypeId,
 };
 use hexorder_contracts::mechanics::{
     ActiveCombat, AreaMarkerRegistry, CombatModifierRegistry, CombatResultsTable, PhaseType,
@@ -299,6 +300,24 @@ fn selected_unit_defaults_to_none() {
     );
 }
 
+#[test]
+fn faction_registry_defaults_empty_with_no_active_faction() {
+    let mut app = test_app();
+    app.update();
+
+    let factions = app
+        .world()
+        .get_resource::<FactionRegistry>()
+        .expect("FactionRegistry should exist");
+    assert!(factions.factions.is_empty());
+
+    let active = app
+        .world()
+        .get_resource::<ActiveFaction>()
+        .expect("ActiveFaction should exist");
+    assert!(active.faction_id.is_none());
+}
+
 #[test]
 fn infantry_has_movement_points_property() {
     let mut app = test_app();
@@ -596,3 +615,19 @@ fn plugin_inserts_victory_condition_registry_resource() {
         "Default victory condition registry should be empty"
     );
 }
+
+#[test]
+fn plugin_inserts_off_map_zone_registry_resource() {
+    let mut app = test_app();
+    app.update();
+
+    let registry = app
+        .world()
+        .get_resource::<hexorder_contracts::mechanics::OffMapZoneRegistry>()
+        .expect("OffMapZoneRegistry should exist");
+
+    assert!(
+        registry.zones.is_empty(),
+        "Default off-map zone registry should be empty"
+    );
+}
@@ -4,9 +4,10 @@
 //! with `hexx::Hex` directly. No ECS dependencies — these are testable
 //! without a Bevy app.
 
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
-use hexorder_contracts::hex_grid::{HexPosition, LineOfSightResult};
+use hexorder_contracts::hex_grid::{HexGridConfig, HexPosition, LineOfSightResult};
 
 /// Returns the 6 adjacent hex positions around `pos`.
 pub fn neighbors(pos: HexPosition) -> [HexPosition; 6] {
@@ -113,3 +114,81 @@ pub fn find_path(
     })
     .map(|path| path.into_iter().map(HexPosition::from_hex).collect())
 }
+
+/// Computes line of sight on a configured board.
+///
+/// On wrapping boards the line runs towards the nearest copy of `to`, so it
+/// takes the short way around the seam. Positions passed to `is_blocking`
+/// and returned in the result are normalized to on-board coordinates.
+pub fn line_of_sight_on(
+    config: &HexGridConfig,
+    from: HexPosition,
+    to: HexPosition,
+    is_blocking: impl Fn(HexPosition) -> bool,
+) -> LineOfSightResult {
+    let image = config.nearest_image(from, to);
+    let mut result = line_of_sight(from, image, |pos| is_blocking(config.normalize(pos)));
+    result.target = config.normalize(to);
+    for pos in &mut result.path {
+        *pos = config.normalize(*pos);
+    }
+    result.blocked_by = result.blocked_by.map(|pos| config.normalize(pos));
+    result
+}
+
+/// Finds the shortest on-board path between two positions using A*.
+///
+/// Unlike [`find_path`], neighbors come from `HexGridConfig::neighbors`, so
+/// the search stays within the board and crosses the seam of wrapping
+/// boards. The heuristic is the seam-aware `HexGridConfig::distance`.
+/// Returns `None` if either end is off the board or no path exists.
+pub fn find_path_on(
+    config: &HexGridConfig,
+    from: HexPosition,
+    to: HexPosition,
+    cost: impl Fn(HexPosition, HexPosition) -> Option<u32>,
+) -> Option<Vec<HexPosition>> {
+    let start = config.resolve(from)?;
+    let goal = config.resolve(to)?;
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<HexPosition, HexPosition> = HashMap::new();
+    let mut best: HashMap<HexPosition, u32> = HashMap::new();
+    best.insert(start, 0);
+    open.push(Reverse((config.distance(start, goal), 0, start.q, start.r)));
+
+    while let Some(Reverse((_, g, q, r))) = open.pop() {
+        let current = HexPosition::new(q, r);
+        if current == goal {
+            let mut path = vec![current];
+            let mut node = current;
+            while let Some(&prev) = came_from.get(&node) {
+                path.push(prev);
+                node = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if best.get(&current).is_some_and(|&known| known < g) {
+            continue;
+        }
+        for next in config.neighbors(current) {
+            let Some(step) = cost(current, next) else {
+                continue;
+            };
+            let next_g = g + step;
+            if best.get(&next).is_some_and(|&known| known <= next_g) {
+                continue;
+            }
+            best.insert(next, next_g);
+            came_from.insert(next, current);
+            open.push(Reverse((
+                next_g + config.distance(next, goal),
+                next_g,
+                next.q,
+                next.r,
+            )));
+        }
+    }
+    None
+}
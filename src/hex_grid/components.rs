@@ -4,9 +4,18 @@
 //! live in `hexorder_contracts::hex_grid`.
 //! This module holds types that are internal to the `hex_grid` plugin.
 
+use std::collections::HashMap;
+
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-use hexorder_contracts::hex_grid::HexPosition;
+use hexorder_contracts::editor_ui::{
+    ActiveEdgeType, ActiveVertexType, SelectedEdge, SelectedVertex,
+};
+use hexorder_contracts::game_system::EntityData;
+use hexorder_contracts::hex_grid::{
+    GridShape, HexEdgeRegistry, HexGridConfig, HexPosition, HexVertexRegistry,
+};
 
 /// Tracks the hex tile currently under the mouse cursor, if any.
 #[derive(Resource, Debug, Default)]
@@ -14,6 +23,37 @@ pub struct HoveredHex {
     pub position: Option<HexPosition>,
 }
 
+/// World-space XZ point under the mouse cursor on the ground plane, if any.
+/// Used to pick the nearest hex corner in Vertex Paint mode.
+#[derive(Resource, Debug, Default)]
+pub struct HoveredPoint {
+    pub world_pos: Option<Vec2>,
+}
+
+/// Edge and vertex paint state read by `handle_click`. Bundled to keep the
+/// system under Bevy's parameter limit.
+#[derive(SystemParam)]
+pub struct FeaturePaintParams<'w> {
+    pub selected_edge: ResMut<'w, SelectedEdge>,
+    pub active_edge: Res<'w, ActiveEdgeType>,
+    pub edge_registry: ResMut<'w, HexEdgeRegistry>,
+    pub selected_vertex: ResMut<'w, SelectedVertex>,
+    pub active_vertex: Res<'w, ActiveVertexType>,
+    pub vertex_registry: ResMut<'w, HexVertexRegistry>,
+    pub hovered_point: Option<Res<'w, HoveredPoint>>,
+    pub config: Option<Res<'w, HexGridConfig>>,
+}
+
+/// The board shape the current tile entities were spawned for. Compared
+/// against `HexGridConfig::shape` to detect when the grid must be rebuilt.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct SpawnedGridShape(pub GridShape);
+
+/// Tile data carried across a board rebuild, applied once the new tiles
+/// have received their default cell data.
+#[derive(Resource, Debug)]
+pub struct PendingTileData(pub HashMap<HexPosition, EntityData>);
+
 /// Stores the handle to the shared default material used for hex tile rendering.
 #[derive(Resource, Debug)]
 pub struct HexMaterials {
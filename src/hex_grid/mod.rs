@@ -1,7 +1,8 @@
 //! Hex grid plugin.
 //!
-//! Spawns a hexagonal grid on the XZ ground plane, handles tile selection
-//! via mouse click, and provides hover feedback.
+//! Spawns the board (hexagonal, or rectangular with optional east-west
+//! wrap) on the XZ ground plane, handles tile selection via mouse click,
+//! and provides hover feedback.
 
 use bevy::prelude::*;
 use hexorder_contracts::editor_ui::pointer_over_ui_panel;
@@ -27,6 +28,7 @@ impl Plugin for HexGridPlugin {
         register_shortcuts(&mut app.world_mut().resource_mut::<ShortcutRegistry>());
 
         app.init_resource::<hexorder_contracts::hex_grid::HexEdgeRegistry>()
+            .init_resource::<hexorder_contracts::hex_grid::HexVertexRegistry>()
             .add_systems(
                 OnEnter(AppScreen::Editor),
                 (
@@ -40,13 +42,20 @@ impl Plugin for HexGridPlugin {
             .add_systems(
                 Update,
                 (
+                    systems::rebuild_grid_on_shape_change,
+                    systems::apply_pending_tile_data,
+                    systems::sync_ghost_materials,
                     systems::update_hover.run_if(not(pointer_over_ui_panel)),
                     systems::handle_click.run_if(not(pointer_over_ui_panel)),
                     systems::update_indicators,
                     systems::sync_multi_select_indicators,
                     systems::sync_move_overlays,
                     systems::draw_edge_features,
+                    systems::draw_vertex_features,
                     systems::draw_los_ray,
+                    systems::draw_move_route,
+                    systems::draw_reachability_overlay,
+                    systems::draw_command_radius,
                 )
                     .chain()
                     .run_if(in_state(AppScreen::Editor).or(in_state(AppScreen::Play))),
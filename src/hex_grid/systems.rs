@@ -1,28 +1,34 @@
 //! Systems for the `hex_grid` plugin.
 
+use std::collections::HashMap;
+
 use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
-use hexx::shapes;
 
 use hexorder_contracts::editor_ui::{
-    ActiveEdgeType, EditorTool, PaintPreview, SelectedEdge, Selection,
+    ActiveEdgeType, ActiveVertexType, EditorTool, PaintPreview, SelectedEdge, SelectedVertex,
+    Selection,
 };
-use hexorder_contracts::game_system::{SelectedUnit, UnitInstance};
+use hexorder_contracts::game_system::{EntityData, SelectedUnit, UnitInstance};
 use hexorder_contracts::hex_grid::{
-    HexEdgeRegistry, HexGridConfig, HexPosition, HexSelectedEvent, HexTile, MoveOverlay,
-    MoveOverlayState, SelectedHex, TileBaseMaterial,
+    CommandRadius, GhostTile, GridShape, HexEdgeRegistry, HexGridConfig, HexPosition,
+    HexSelectedEvent, HexTile, HexVertex, HexVertexRegistry, MoveOverlay, MoveOverlayState,
+    ReachabilityMap, ReachabilityOverlay, SelectedHex, TileBaseMaterial, VertexFeature,
+    hex_distance,
 };
 use hexorder_contracts::validation::ValidMoveSet;
 
 use super::algorithms;
 use super::components::{
-    HexMaterials, HoverIndicator, HoveredHex, IndicatorMaterials, MultiSelectIndicator,
-    OverlayMaterials, SelectIndicator,
+    FeaturePaintParams, HexMaterials, HoverIndicator, HoveredHex, HoveredPoint, IndicatorMaterials,
+    MultiSelectIndicator, OverlayMaterials, PendingTileData, SelectIndicator, SpawnedGridShape,
 };
 
 /// Creates the hex grid configuration resource with default settings.
-pub fn setup_grid_config(mut commands: Commands) {
+///
+/// A board shape already present (e.g. restored by a project load) is kept.
+pub fn setup_grid_config(mut commands: Commands, existing: Option<Res<HexGridConfig>>) {
     let layout = hexx::HexLayout {
         orientation: hexx::HexOrientation::Pointy,
         ..hexx::HexLayout::default()
@@ -32,10 +38,12 @@ pub fn setup_grid_config(mut commands: Commands) {
     commands.insert_resource(HexGridConfig {
         layout,
         map_radius: 10,
+        shape: existing.map_or(GridShape::Hexagon, |config| config.shape),
     });
 
     commands.insert_resource(SelectedHex::default());
     commands.insert_resource(HoveredHex::default());
+    commands.insert_resource(HoveredPoint::default());
 }
 
 /// Creates the shared default material handle for hex tile rendering.
@@ -50,12 +58,23 @@ pub fn setup_materials(mut commands: Commands, mut materials: ResMut<Assets<Stan
     commands.insert_resource(hex_materials);
 }
 
-/// Spawns all hex tile entities for the configured grid radius.
+/// Spawns all hex tile entities for the configured board shape.
 pub fn spawn_grid(
     mut commands: Commands,
     config: Res<HexGridConfig>,
     hex_materials: Res<HexMaterials>,
     mut meshes: ResMut<Assets<Mesh>>,
+) {
+    spawn_tiles(&mut commands, &config, &hex_materials, &mut meshes);
+}
+
+/// Spawns tile entities plus, on wrapping boards, ghost copies of the
+/// seam columns so the east-west join reads visually.
+fn spawn_tiles(
+    commands: &mut Commands,
+    config: &HexGridConfig,
+    hex_materials: &HexMaterials,
+    meshes: &mut Assets<Mesh>,
 ) {
     // Use Bevy's built-in RegularPolygon (6 sides = hexagon) which generates
     // all required mesh attributes. The mesh is created in the XY plane, so we
@@ -67,18 +86,110 @@ pub fn spawn_grid(
     // Rotation to lay the XY-plane polygon flat on the XZ ground plane.
     let flat_rotation = Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2);
 
-    for hex in shapes::hexagon(hexx::Hex::ZERO, config.map_radius) {
-        let world_pos = config.layout.hex_to_world_pos(hex);
+    for pos in config.positions() {
+        let world_pos = config.layout.hex_to_world_pos(pos.to_hex());
 
         commands.spawn((
             HexTile,
-            HexPosition::from_hex(hex),
+            pos,
             Mesh3d(mesh_handle.clone()),
             MeshMaterial3d(hex_materials.default.clone()),
             TileBaseMaterial(hex_materials.default.clone()),
             Transform::from_xyz(world_pos.x, 0.0, world_pos.y).with_rotation(flat_rotation),
         ));
     }
+
+    commands.insert_resource(SpawnedGridShape(config.shape));
+
+    for (ghost, source) in config.ghost_positions() {
+        let world_pos = config.layout.hex_to_world_pos(ghost.to_hex());
+
+        commands.spawn((
+            GhostTile { source },
+            Mesh3d(mesh_handle.clone()),
+            MeshMaterial3d(hex_materials.default.clone()),
+            Transform::from_xyz(world_pos.x, -0.01, world_pos.y).with_rotation(flat_rotation),
+        ));
+    }
+}
+
+/// Rebuilds the tile entities when the board shape changes, carrying
+/// tile data over for positions that remain on the board.
+#[allow(clippy::too_many_arguments)]
+pub fn rebuild_grid_on_shape_change(
+    mut commands: Commands,
+    config: Res<HexGridConfig>,
+    hex_materials: Option<Res<HexMaterials>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    spawned: Option<Res<SpawnedGridShape>>,
+    tiles: Query<(Entity, &HexPosition, Option<&EntityData>), With<HexTile>>,
+    ghosts: Query<Entity, With<GhostTile>>,
+) {
+    let (Some(spawned), Some(hex_materials)) = (spawned, hex_materials) else {
+        return;
+    };
+    if spawned.0 == config.shape {
+        return;
+    }
+
+    let mut kept: HashMap<HexPosition, EntityData> = HashMap::new();
+    for (entity, pos, data) in &tiles {
+        if let Some(data) = data
+            && config.contains(*pos)
+        {
+            kept.insert(*pos, data.clone());
+        }
+        commands.entity(entity).despawn();
+    }
+    for entity in &ghosts {
+        commands.entity(entity).despawn();
+    }
+
+    spawn_tiles(&mut commands, &config, &hex_materials, &mut meshes);
+    if !kept.is_empty() {
+        commands.insert_resource(PendingTileData(kept));
+    }
+}
+
+/// Restores tile data saved by `rebuild_grid_on_shape_change`.
+pub fn apply_pending_tile_data(
+    mut commands: Commands,
+    pending: Option<Res<PendingTileData>>,
+    mut tiles: Query<(&HexPosition, &mut EntityData), With<HexTile>>,
+    tiles_pending_data: Query<(), (With<HexTile>, Without<EntityData>)>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+    // Wait for the cell plugin to attach default data to the new tiles.
+    if !tiles_pending_data.is_empty() {
+        return;
+    }
+    for (pos, mut data) in &mut tiles {
+        if let Some(saved) = pending.0.get(pos) {
+            *data = saved.clone();
+        }
+    }
+    commands.remove_resource::<PendingTileData>();
+}
+
+/// Keeps ghost tiles showing the same material as their source tile.
+pub fn sync_ghost_materials(
+    tiles: Query<(&HexPosition, &MeshMaterial3d<StandardMaterial>), With<HexTile>>,
+    mut ghosts: Query<(&GhostTile, &mut MeshMaterial3d<StandardMaterial>), Without<HexTile>>,
+) {
+    if ghosts.is_empty() {
+        return;
+    }
+    let materials: HashMap<HexPosition, &Handle<StandardMaterial>> =
+        tiles.iter().map(|(pos, mat)| (*pos, &mat.0)).collect();
+    for (ghost, mut material) in &mut ghosts {
+        if let Some(handle) = materials.get(&ghost.source)
+            && material.0 != **handle
+        {
+            material.0 = (*handle).clone();
+        }
+    }
 }
 
 /// Converts screen-space mouse position to world-space XZ coordinates
@@ -120,6 +231,7 @@ pub fn update_hover(
     cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
     config: Res<HexGridConfig>,
     mut hovered: ResMut<HoveredHex>,
+    mut hovered_point: Option<ResMut<HoveredPoint>>,
 ) {
     let Ok(window) = windows.single() else {
         return;
@@ -128,13 +240,15 @@ pub fn update_hover(
         return;
     };
 
-    let Some(cursor_pos) = window.cursor_position() else {
-        // Mouse is outside the window.
-        hovered.position = None;
-        return;
-    };
+    let world_pos = window
+        .cursor_position()
+        .and_then(|cursor_pos| screen_to_ground(camera, camera_transform, cursor_pos));
+    if let Some(point) = hovered_point.as_mut() {
+        point.world_pos = world_pos;
+    }
 
-    let Some(world_pos) = screen_to_ground(camera, camera_transform, cursor_pos) else {
+    let Some(world_pos) = world_pos else {
+        // Mouse is outside the window or not over the ground plane.
         hovered.position = None;
         return;
     };
@@ -142,14 +256,9 @@ pub fn update_hover(
     // Convert world XZ position to hex coordinates.
     let hex = config.layout.world_pos_to_hex(world_pos);
 
-    // Only consider hexes within the grid radius.
-    let distance = hex.unsigned_distance_to(hexx::Hex::ZERO);
-    if distance <= config.map_radius {
-        let pos = HexPosition::from_hex(hex);
-        hovered.position = Some(pos);
-    } else {
-        hovered.position = None;
-    }
+    // Only consider hexes on the board. Ghost columns resolve to the tile
+    // they mirror across the seam.
+    hovered.position = config.resolve(HexPosition::from_hex(hex));
 }
 
 /// Pixel distance threshold to distinguish a click from a drag.
@@ -172,9 +281,7 @@ pub fn handle_click(
     tool: Res<EditorTool>,
     mut selected: ResMut<SelectedHex>,
     mut selection: ResMut<Selection>,
-    mut selected_edge: ResMut<SelectedEdge>,
-    active_edge: Res<ActiveEdgeType>,
-    mut edge_registry: ResMut<HexEdgeRegistry>,
+    mut paint: FeaturePaintParams,
     tile_query: Query<(Entity, &HexPosition), With<HexTile>>,
     mut commands: Commands,
     mut drag_acc: Local<f32>,
@@ -202,7 +309,32 @@ pub fn handle_click(
 
     // Edge paint mode: two-click flow.
     if *tool == EditorTool::EdgePaint {
-        handle_edge_paint_click(pos, &mut selected_edge, &active_edge, &mut edge_registry);
+        if let Some(config) = paint.config.as_deref() {
+            handle_edge_paint_click(
+                pos,
+                config,
+                &mut paint.selected_edge,
+                &paint.active_edge,
+                &mut paint.edge_registry,
+            );
+        }
+        return;
+    }
+
+    // Vertex paint mode: paint the corner nearest the cursor.
+    if *tool == EditorTool::VertexPaint {
+        if let (Some(config), Some(point)) = (
+            paint.config.as_deref(),
+            paint.hovered_point.as_ref().and_then(|p| p.world_pos),
+        ) {
+            let vertex = nearest_vertex(config, point);
+            handle_vertex_paint_click(
+                vertex,
+                &mut paint.selected_vertex,
+                &paint.active_vertex,
+                &mut paint.vertex_registry,
+            );
+        }
         return;
     }
 
@@ -229,18 +361,20 @@ pub fn handle_click(
 
 /// Handles a click in Edge Paint mode using the two-click flow:
 /// 1. First click: store the hex as `first_hex`.
-/// 2. Second click on an adjacent hex: assign/remove the edge feature.
+/// 2. Second click on an adjacent hex, across the seam of a wrapping board
+///    too: assign/remove the edge feature.
 fn handle_edge_paint_click(
     pos: HexPosition,
+    config: &HexGridConfig,
     selected_edge: &mut SelectedEdge,
     active_edge: &ActiveEdgeType,
     edge_registry: &mut HexEdgeRegistry,
 ) {
-    use hexorder_contracts::hex_grid::{EdgeFeature, HexEdge};
+    use hexorder_contracts::hex_grid::EdgeFeature;
 
     if let Some(first) = selected_edge.first_hex {
         // Second click — try to form an edge.
-        if let Some(edge) = HexEdge::between(first, pos) {
+        if let Some(edge) = config.normalize_edge(first, pos) {
             if let Some(type_name) = &active_edge.type_name {
                 // Paint: assign the active edge type.
                 edge_registry.insert(
@@ -264,6 +398,56 @@ fn handle_edge_paint_click(
     }
 }
 
+/// Handles a click in Vertex Paint mode: assigns the active vertex feature
+/// type to `vertex`, or removes the feature in erase mode.
+fn handle_vertex_paint_click(
+    vertex: HexVertex,
+    selected_vertex: &mut SelectedVertex,
+    active_vertex: &ActiveVertexType,
+    vertex_registry: &mut HexVertexRegistry,
+) {
+    if let Some(type_name) = &active_vertex.type_name {
+        vertex_registry.insert(
+            vertex,
+            VertexFeature {
+                type_name: type_name.clone(),
+            },
+        );
+    } else {
+        vertex_registry.remove(&vertex);
+    }
+    selected_vertex.vertex = Some(vertex);
+}
+
+/// World-space XZ position of a hex vertex: the centroid of the three hex
+/// centers that meet there.
+pub(crate) fn vertex_world_pos(vertex: &HexVertex, config: &HexGridConfig) -> Vec2 {
+    let sum: Vec2 = vertex
+        .hexes()
+        .iter()
+        .map(|pos| config.layout.hex_to_world_pos(pos.to_hex()))
+        .sum();
+    sum / 3.0
+}
+
+/// Returns the hex corner closest to a world-space XZ point, in canonical
+/// seam-normalized form. Uses the raw hex under the point (not the resolved
+/// hover position) so ghost columns pick the corner that is drawn there.
+pub(crate) fn nearest_vertex(config: &HexGridConfig, point: Vec2) -> HexVertex {
+    let hex = HexPosition::from_hex(config.layout.world_pos_to_hex(point));
+    let corners = HexVertex::of_hex(hex);
+    let nearest = corners
+        .iter()
+        .copied()
+        .min_by(|a, b| {
+            let da = vertex_world_pos(a, config).distance_squared(point);
+            let db = vertex_world_pos(b, config).distance_squared(point);
+            da.total_cmp(&db)
+        })
+        .unwrap_or(corners[0]);
+    config.normalize_vertex(nearest)
+}
+
 /// Observer: handles commands dispatched via the shortcut registry.
 pub fn handle_hex_grid_command(
     trigger: On<hexorder_contracts::shortcuts::CommandExecutedEvent>,
@@ -592,7 +776,7 @@ pub fn draw_los_ray(
     }
 
     // TODO(#95): nothing blocks LOS until hex visibility system ships.
-    let result = algorithms::line_of_sight(unit_pos, hover_pos, |_| false);
+    let result = algorithms::line_of_sight_on(&config, unit_pos, hover_pos, |_| false);
 
     let color = if result.clear {
         Color::srgb(0.2, 0.9, 0.2)
@@ -601,12 +785,123 @@ pub fn draw_los_ray(
     };
 
     for window in result.path.windows(2) {
+        // Skip the jump where the ray crosses the seam of a wrapping board.
+        if hex_distance(window[0], window[1]) > 1 {
+            continue;
+        }
         let a = config.layout.hex_to_world_pos(window[0].to_hex());
         let b = config.layout.hex_to_world_pos(window[1].to_hex());
         gizmos.line(Vec3::new(a.x, 0.03, a.y), Vec3::new(b.x, 0.03, b.y), color);
     }
 }
 
+/// Draws the cheapest route from the selected unit to the hovered hex when
+/// the hovered hex is a valid move.
+pub fn draw_move_route(
+    valid_moves: Res<ValidMoveSet>,
+    hovered: Res<HoveredHex>,
+    config: Res<HexGridConfig>,
+    mut gizmos: Gizmos,
+) {
+    let Some(hover_pos) = hovered.position else {
+        return;
+    };
+    if !valid_moves.valid_positions.contains(&hover_pos) {
+        return;
+    }
+    let Some(route) = valid_moves.route(hover_pos) else {
+        return;
+    };
+
+    let color = Color::srgb(1.0, 0.75, 0.2);
+    for window in route.windows(2) {
+        // Skip the jump where the route crosses the seam of a wrapping board.
+        if hex_distance(window[0], window[1]) > 1 {
+            continue;
+        }
+        let a = config.layout.hex_to_world_pos(window[0].to_hex());
+        let b = config.layout.hex_to_world_pos(window[1].to_hex());
+        gizmos.line(Vec3::new(a.x, 0.04, a.y), Vec3::new(b.x, 0.04, b.y), color);
+    }
+}
+
+/// Outlines every hex of the board for the reachability rule chosen in
+/// `ReachabilityOverlay`: green where a unit could trace to a source, red
+/// where it could not. Shows the selected unit's trace when the rule traces
+/// it, otherwise the rule's first trace.
+pub fn draw_reachability_overlay(
+    overlay: Option<Res<ReachabilityOverlay>>,
+    reachability_map: Option<Res<ReachabilityMap>>,
+    selected_unit: Res<SelectedUnit>,
+    config: Res<HexGridConfig>,
+    mut gizmos: Gizmos,
+) {
+    let (Some(overlay), Some(reachability_map)) = (overlay, reachability_map) else {
+        return;
+    };
+    let Some(trace) = overlay
+        .rule_id
+        .and_then(|rule_id| reachability_map.trace_for(rule_id, selected_unit.entity))
+    else {
+        return;
+    };
+
+    let radius = config.layout.scale.x.max(config.layout.scale.y) * 0.8;
+    let y = 0.035; // Between overlays and edge features
+    let in_reach = Color::srgb(0.2, 0.8, 0.3);
+    let out_of_reach = Color::srgb(0.85, 0.25, 0.2);
+    for pos in config.positions() {
+        let center = config.layout.hex_to_world_pos(pos.to_hex());
+        let color = if trace.reached.contains_key(&pos) {
+            in_reach
+        } else {
+            out_of_reach
+        };
+        let corners: Vec<Vec3> = (0..6)
+            .map(|i| {
+                let angle = std::f32::consts::FRAC_PI_2 + std::f32::consts::TAU * i as f32 / 6.0;
+                let (sin, cos) = angle.sin_cos();
+                Vec3::new(center.x + radius * cos, y, center.y + radius * sin)
+            })
+            .collect();
+        for i in 0..corners.len() {
+            gizmos.line(corners[i], corners[(i + 1) % corners.len()], color);
+        }
+    }
+}
+
+/// Outlines the `CommandRadius` around the selected unit in amber, inside
+/// the reachability overlay's outlines.
+pub fn draw_command_radius(
+    command_radius: Option<Res<CommandRadius>>,
+    config: Res<HexGridConfig>,
+    mut gizmos: Gizmos,
+) {
+    let Some(command_radius) = command_radius else {
+        return;
+    };
+    if command_radius.source.is_none() {
+        return;
+    }
+
+    let radius = config.layout.scale.x.max(config.layout.scale.y) * 0.7;
+    let y = 0.036;
+    let color = Color::srgb(0.95, 0.7, 0.15);
+    for pos in &command_radius.hexes {
+        let center = config.layout.hex_to_world_pos(pos.to_hex());
+        let corners: Vec<Vec3> = (0..6)
+            .map(|i| {
+                let angle = std::f32::consts::FRAC_PI_2 + std::f32::consts::TAU * i as f32 / 6.0;
+                let (sin, cos) = angle.sin_cos();
+                Vec3::new(center.x + radius * cos, y, center.y + radius * sin)
+            })
+            .collect();
+        for i in 0..corners.len() {
+            gizmos.line(corners[i], corners[(i + 1) % corners.len()], color);
+        }
+    }
+}
+
 /// Draws colored line segments on hex boundaries where edge features exist.
 ///
 /// For each edge in the `HexEdgeRegistry`, computes the two world-space
@@ -637,6 +932,45 @@ pub fn draw_edge_features(
     }
 }
 
+/// Draws a small diamond marker at every hex vertex that has a feature.
+///
+/// The marker sits at the centroid of the three meeting hex centers and
+/// takes the color of the feature's entity type (falling back to amber if
+/// the type is not found).
+pub fn draw_vertex_features(
+    vertex_registry: Res<HexVertexRegistry>,
+    config: Res<HexGridConfig>,
+    entity_types: Res<hexorder_contracts::game_system::EntityTypeRegistry>,
+    mut gizmos: Gizmos,
+) {
+    if vertex_registry.is_empty() {
+        return;
+    }
+
+    let hex_size = config.layout.scale.x.max(config.layout.scale.y);
+    let half = hex_size * 0.2;
+    let y = 0.05; // Above edge features
+
+    for (vertex, feature) in vertex_registry.iter() {
+        let color = entity_types
+            .types
+            .iter()
+            .find(|t| t.name == feature.type_name)
+            .map_or(Color::srgb(1.0, 0.75, 0.2), |et| et.color);
+
+        let center = vertex_world_pos(vertex, &config);
+        let corners = [
+            Vec3::new(center.x + half, y, center.y),
+            Vec3::new(center.x, y, center.y + half),
+            Vec3::new(center.x - half, y, center.y),
+            Vec3::new(center.x, y, center.y - half),
+        ];
+        for i in 0..corners.len() {
+            gizmos.line(corners[i], corners[(i + 1) % corners.len()], color);
+        }
+    }
+}
+
 /// Computes the two world-space 3D endpoints of a hex edge boundary.
 ///
 /// Each hex has 6 vertices. The shared edge between two adjacent hexes
@@ -4,15 +4,17 @@ use std::sync::{Arc, Mutex};
 
 use bevy::prelude::*;
 
-use hexorder_contracts::editor_ui::{ActiveEdgeType, SelectedEdge, Selection};
+use hexorder_contracts::editor_ui::{
+    ActiveEdgeType, ActiveVertexType, SelectedEdge, SelectedVertex, Selection,
+};
 use hexorder_contracts::hex_grid::{
-    HexGridConfig, HexPosition, HexSelectedEvent, HexTile, MoveOverlay, MoveOverlayState,
-    SelectedHex,
+    GhostTile, GridShape, HexGridConfig, HexPosition, HexSelectedEvent, HexTile, HexVertex,
+    HexVertexRegistry, MoveOverlay, MoveOverlayState, SelectedHex,
 };
 use hexorder_contracts::persistence::AppScreen;
 use hexorder_contracts::validation::{ValidMoveSet, ValidationResult};
 
-use super::components::{HexMaterials, HoveredHex};
+use super::components::{HexMaterials, HoveredHex, HoveredPoint};
 use super::systems;
 
 /// Helper: create a minimal App with resources needed for `hex_grid` testing.
@@ -31,6 +33,9 @@ fn test_app() -> App {
     app.init_resource::<ActiveEdgeType>();
     app.init_resource::<hexorder_contracts::editor_ui::EditorTool>();
     app.init_resource::<hexorder_contracts::hex_grid::HexEdgeRegistry>();
+    app.init_resource::<SelectedVertex>();
+    app.init_resource::<ActiveVertexType>();
+    app.init_resource::<hexorder_contracts::hex_grid::HexVertexRegistry>();
     app
 }
 
@@ -337,6 +342,7 @@ fn move_overlays_spawned_on_unit_select() {
         valid_positions,
         blocked_explanations: std::collections::HashMap::new(),
         for_entity: Some(unit_entity),
+        ..Default::default()
     });
     app.update();
 
@@ -364,6 +370,7 @@ fn move_overlays_despawned_on_deselect() {
         valid_positions,
         blocked_explanations: std::collections::HashMap::new(),
         for_entity: Some(unit_entity),
+        ..Default::default()
     });
     app.update();
 
@@ -405,6 +412,7 @@ fn blocked_positions_get_red_overlay() {
         valid_positions: std::collections::HashSet::new(),
         blocked_explanations: blocked,
         for_entity: Some(unit_entity),
+        ..Default::default()
     });
     app.update();
 
@@ -631,6 +639,179 @@ fn find_path_no_route() {
     assert!(path.is_none(), "Walled-off path should return None");
 }
 
+// ---------------------------------------------------------------------------
+// Wrap-around boards
+// ---------------------------------------------------------------------------
+
+fn wrapping_config() -> HexGridConfig {
+    HexGridConfig {
+        shape: GridShape::Rectangle {
+            width: 10,
+            height: 6,
+            wrap_horizontal: true,
+        },
+        ..HexGridConfig::default()
+    }
+}
+
+#[test]
+fn find_path_on_crosses_seam() {
+    let config = wrapping_config();
+    let from = HexPosition::new(3, 0);
+    let to = HexPosition::new(-4, 0);
+    let path = algorithms::find_path_on(&config, from, to, |_, _| Some(1))
+        .expect("path across seam should exist");
+    // 3 -> 4 -> -5 -> -4: three steps the short way round.
+    assert_eq!(path.len(), 4);
+    assert!(path.contains(&HexPosition::new(-5, 0)));
+    assert!(path.iter().all(|p| config.contains(*p)));
+}
+
+#[test]
+fn find_path_on_stays_on_board() {
+    let config = HexGridConfig {
+        map_radius: 2,
+        ..HexGridConfig::default()
+    };
+    assert!(
+        algorithms::find_path_on(
+            &config,
+            HexPosition::new(0, 0),
+            HexPosition::new(5, 0),
+            |_, _| { Some(1) }
+        )
+        .is_none()
+    );
+}
+
+#[test]
+fn line_of_sight_on_wraps_and_normalizes() {
+    let config = wrapping_config();
+    let from = HexPosition::new(3, 0);
+    let to = HexPosition::new(-5, 0);
+    let blocker = HexPosition::new(4, 0);
+    let result = algorithms::line_of_sight_on(&config, from, to, |pos| pos == blocker);
+    assert_eq!(result.path.len(), 3);
+    assert_eq!(result.target, to);
+    assert_eq!(*result.path.last().expect("non-empty"), to);
+    assert_eq!(result.blocked_by, Some(blocker));
+}
+
+#[test]
+fn spawn_grid_adds_ghost_columns_on_wrapping_board() {
+    let mut app = test_app_with_grid();
+    app.insert_resource(wrapping_config());
+    app.update();
+
+    let tiles = app
+        .world_mut()
+        .query_filtered::<(), With<HexTile>>()
+        .iter(app.world())
+        .count();
+    let ghosts = app
+        .world_mut()
+        .query::<&GhostTile>()
+        .iter(app.world())
+        .count();
+    assert_eq!(tiles, 60);
+    assert_eq!(ghosts, 12);
+}
+
+#[test]
+fn changing_shape_rebuilds_grid() {
+    let mut app = test_app_with_grid();
+    app.add_systems(Update, systems::rebuild_grid_on_shape_change);
+    app.update();
+
+    app.world_mut().resource_mut::<HexGridConfig>().shape = GridShape::Rectangle {
+        width: 4,
+        height: 3,
+        wrap_horizontal: false,
+    };
+    app.update();
+    app.update();
+
+    let tiles = app
+        .world_mut()
+        .query_filtered::<(), With<HexTile>>()
+        .iter(app.world())
+        .count();
+    assert_eq!(tiles, 12);
+}
+
+// ---------------------------------------------------------------------------
+// Vertex features
+// ---------------------------------------------------------------------------
+
+#[test]
+fn nearest_vertex_picks_corner_under_point() {
+    let config = HexGridConfig::default();
+    let vertex = HexVertex::new(HexPosition::new(0, 0), 3);
+    // Nudge slightly towards the origin hex so the point is unambiguous.
+    let corner = systems::vertex_world_pos(&vertex, &config);
+    let point = corner * 0.95;
+    assert_eq!(systems::nearest_vertex(&config, point), vertex);
+}
+
+#[test]
+fn vertex_paint_click_assigns_and_erases_feature() {
+    let mut app = test_app();
+    app.init_resource::<ButtonInput<MouseButton>>();
+    app.insert_resource(SelectedHex::default());
+    app.insert_resource(HexGridConfig::default());
+    app.insert_resource(hexorder_contracts::editor_ui::EditorTool::VertexPaint);
+    app.insert_resource(ActiveVertexType {
+        type_name: Some("Town".to_string()),
+    });
+
+    let config = HexGridConfig::default();
+    let vertex = HexVertex::new(HexPosition::new(0, 0), 1);
+    let point = systems::vertex_world_pos(&vertex, &config) * 0.95;
+    app.insert_resource(HoveredHex {
+        position: Some(HexPosition::new(0, 0)),
+    });
+    app.insert_resource(HoveredPoint {
+        world_pos: Some(point),
+    });
+    app.add_systems(Update, systems::handle_click);
+
+    let click = |app: &mut App| {
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .clear();
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        app.update();
+    };
+
+    click(&mut app);
+    assert_eq!(
+        app.world()
+            .resource::<HexVertexRegistry>()
+            .get(&vertex)
+            .map(|f| f.type_name.clone()),
+        Some("Town".to_string())
+    );
+    assert_eq!(
+        app.world().resource::<SelectedVertex>().vertex,
+        Some(vertex)
+    );
+    assert!(
+        app.world().resource::<SelectedHex>().position.is_none(),
+        "vertex painting should not change the hex selection"
+    );
+
+    // Erase mode removes the feature.
+    app.world_mut().resource_mut::<ActiveVertexType>().type_name = None;
+    click(&mut app);
+    assert!(app.world().resource::<HexVertexRegistry>().is_empty());
+}
+
 // ---------------------------------------------------------------------------
 // LOS system tests (0.7.0)
 // ---------------------------------------------------------------------------
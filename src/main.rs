@@ -175,7 +175,7 @@ mod integration_tests {
         SelectedUnit, UnitInstance,
     };
     use hexorder_contracts::hex_grid::{
-        HexGridConfig, HexPosition, HexSelectedEvent, HexTile, TileBaseMaterial,
+        GridShape, HexGridConfig, HexPosition, HexSelectedEvent, HexTile, TileBaseMaterial,
     };
 
     fn headless_app() -> App {
@@ -195,6 +195,7 @@ mod integration_tests {
                 origin: bevy::math::Vec2::ZERO,
             },
             map_radius: 5,
+            shape: GridShape::Hexagon,
         });
         app.add_plugins(crate::game_system::GameSystemPlugin);
         app.init_resource::<hexorder_contracts::undo_redo::UndoStack>();
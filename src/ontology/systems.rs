@@ -109,6 +109,7 @@ pub fn auto_generate_constraints(
                     relation_id: Some(relation.id),
                     expression: expected_expr,
                     auto_generated: true,
+                    gates: Vec::new(),
                 });
             }
             None => {
@@ -124,6 +125,7 @@ pub fn auto_generate_constraints(
                     relation_id: Some(relation.id),
                     expression: expected_expr,
                     auto_generated: true,
+                    gates: Vec::new(),
                 });
             }
         }
@@ -399,7 +401,11 @@ fn validate_constraint_expr(
                 errors,
             );
         }
-        ConstraintExpr::IsType { role_id, .. } | ConstraintExpr::IsNotType { role_id, .. } => {
+        ConstraintExpr::IsType { role_id, .. }
+        | ConstraintExpr::IsNotType { role_id, .. }
+        | ConstraintExpr::InState { role_id, .. }
+        | ConstraintExpr::InReach { role_id, .. }
+        | ConstraintExpr::Proximity { role_id, .. } => {
             if !concept.role_labels.iter().any(|r| r.id == *role_id) {
                 errors.push(SchemaError {
                     category: SchemaErrorCategory::InvalidExpression,
@@ -559,6 +559,7 @@ fn manual_constraint_retained_when_relation_removed() {
                 value: PropertyValue::Int(0),
             },
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
 
@@ -621,6 +622,7 @@ fn auto_generated_constraint_without_relation_id_retained() {
                 value: PropertyValue::Int(0),
             },
             auto_generated: true,
+            gates: Vec::new(),
         });
     }
 
@@ -888,6 +890,7 @@ fn schema_validation_catches_constraint_dangling_concept() {
                 value: PropertyValue::Int(0),
             },
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
 
@@ -1044,6 +1047,7 @@ fn schema_validation_constraint_cross_compare_invalid_roles() {
                 right_property: "cost".to_string(),
             },
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
 
@@ -1114,6 +1118,7 @@ fn schema_validation_constraint_cross_compare_invalid_properties() {
                 right_property: "nonexistent_right".to_string(),
             },
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
 
@@ -1166,6 +1171,7 @@ fn schema_validation_constraint_is_type_invalid_role() {
                 entity_type_id: token_type_id(),
             },
             auto_generated: false,
+            gates: Vec::new(),
         });
 
         // IsNotType with bad role
@@ -1180,6 +1186,7 @@ fn schema_validation_constraint_is_type_invalid_role() {
                 entity_type_id: token_type_id(),
             },
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
 
@@ -1253,6 +1260,7 @@ fn schema_validation_constraint_path_budget_invalid() {
                 budget_role_id: traveler_role_id,
             },
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
 
@@ -1307,6 +1315,7 @@ fn schema_validation_constraint_all_any_not_recursion() {
                 entity_type_id: token_type_id(),
             }]),
             auto_generated: false,
+            gates: Vec::new(),
         });
 
         // Any with nested bad IsNotType
@@ -1321,6 +1330,7 @@ fn schema_validation_constraint_all_any_not_recursion() {
                 entity_type_id: token_type_id(),
             }]),
             auto_generated: false,
+            gates: Vec::new(),
         });
 
         // Not with nested bad PropertyCompare
@@ -1337,6 +1347,7 @@ fn schema_validation_constraint_all_any_not_recursion() {
                 value: PropertyValue::Int(0),
             })),
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
 
@@ -1406,6 +1417,7 @@ fn schema_validation_constraint_property_compare_bad_property() {
                 value: PropertyValue::Int(0),
             },
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
 
@@ -1461,6 +1473,7 @@ fn schema_validation_skips_property_check_when_no_bindings() {
                 value: PropertyValue::Int(0),
             },
             auto_generated: false,
+            gates: Vec::new(),
         });
     }
 